@@ -190,7 +190,7 @@ async fn process_task(
             tracing::info!(
                 "A2A: Task {} completed ({} tokens used)",
                 task_id,
-                response.usage.input_tokens + response.usage.output_tokens
+                response.usage.total()
             );
         }
         Err(e) => {
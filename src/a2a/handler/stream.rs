@@ -226,7 +226,7 @@ async fn process_task_streaming(
             tracing::info!(
                 "A2A stream: Task {} completed ({} tokens used)",
                 task_id,
-                response.usage.input_tokens + response.usage.output_tokens
+                response.usage.total()
             );
         }
         Err(e) => {
@@ -214,6 +214,38 @@ impl AgentContext {
         self.drop_leading_orphan_tool_results();
     }
 
+    /// Drop the oldest turns until the context fits in `budget` tokens.
+    ///
+    /// A leading compaction summary is kept, as is the latest message, so the
+    /// model never loses the summary or the request it's answering. Returns
+    /// the number of messages dropped.
+    pub fn trim_to_budget(&mut self, budget: usize) -> usize {
+        let before = self.messages.len();
+        let keep_summary = self.messages.first().is_some_and(|m| {
+            m.content.iter().any(|b| {
+                matches!(b, ContentBlock::Text { text } if text.starts_with("[CONTEXT COMPACTION"))
+            })
+        });
+        let start = usize::from(keep_summary);
+
+        while self.token_count > budget && self.messages.len() > start + 1 {
+            let tokens = self.estimate_message_tokens(&self.messages[start]);
+            self.token_count = self.token_count.saturating_sub(tokens);
+            self.messages.remove(start);
+
+            // Don't leave the history opening on tool results whose call is gone
+            while self.messages.len() > start + 1
+                && Self::is_orphaned_tool_result_msg(&self.messages[start])
+            {
+                let tokens = self.estimate_message_tokens(&self.messages[start]);
+                self.token_count = self.token_count.saturating_sub(tokens);
+                self.messages.remove(start);
+            }
+        }
+
+        before - self.messages.len()
+    }
+
     /// Hard-truncate old messages until token count is at or below `target_tokens`.
     /// Keeps at least 2 messages (the most recent pair) to maintain conversation validity.
     /// Compact the context by replacing old messages with a summary.
@@ -356,6 +388,50 @@ mod tests {
         assert!(context.messages.len() < original_count);
     }
 
+    #[test]
+    fn test_trim_to_budget_drops_oldest_turns() {
+        let session_id = Uuid::new_v4();
+        let mut context = AgentContext::new(session_id, 200_000);
+        context.compact_with_summary("earlier work".to_string(), 0);
+        for i in 0..40 {
+            context.add_message(Message::user(format!(
+                "question {} {}",
+                i,
+                "word ".repeat(200)
+            )));
+            context.add_message(Message::assistant(format!(
+                "answer {} {}",
+                i,
+                "word ".repeat(200)
+            )));
+        }
+
+        let dropped = context.trim_to_budget(2_000);
+
+        assert!(dropped > 0);
+        assert!(context.token_count <= 2_000);
+        // Summary survives, and the newest turn is still there
+        assert!(matches!(
+            &context.messages[0].content[0],
+            ContentBlock::Text { text } if text.starts_with("[CONTEXT COMPACTION")
+        ));
+        assert!(matches!(
+            &context.messages.last().unwrap().content[0],
+            ContentBlock::Text { text } if text.starts_with("answer 39")
+        ));
+    }
+
+    #[test]
+    fn test_trim_to_budget_noop_when_within_budget() {
+        let session_id = Uuid::new_v4();
+        let mut context = AgentContext::new(session_id, 200_000);
+        context.add_message(Message::user("hi".to_string()));
+        context.add_message(Message::assistant("hello".to_string()));
+
+        assert_eq!(context.trim_to_budget(10_000), 0);
+        assert_eq!(context.messages.len(), 2);
+    }
+
     #[test]
     fn test_compact_with_summary_keeps_recent() {
         let session_id = Uuid::new_v4();
@@ -1,6 +1,6 @@
 //! Agent error types
 
-use crate::brain::provider::ProviderError;
+use crate::brain::provider::{ProviderError, ProviderErrorKind};
 use thiserror::Error;
 
 /// Agent error types
@@ -38,10 +38,50 @@ pub enum AgentError {
     #[error("Maximum tool iterations exceeded: {0}")]
     MaxIterationsExceeded(usize),
 
+    /// Another turn is already running in the session
+    #[error("Session is busy: {0}")]
+    SessionBusy(uuid::Uuid),
+
+    /// The session used up its `[agent] max_session_tokens` / `max_session_cost`
+    #[error("Session limit reached: {reason}")]
+    SessionLimitReached {
+        session_id: uuid::Uuid,
+        reason: String,
+    },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+impl AgentError {
+    /// Classification of the underlying provider error, if this is one
+    pub fn provider_kind(&self) -> Option<ProviderErrorKind> {
+        match self {
+            AgentError::Provider(e) => Some(e.kind()),
+            _ => None,
+        }
+    }
+
+    /// The error followed by what the user can do about it, for display in
+    /// the TUI and channels.
+    pub fn user_message(&self) -> String {
+        let guidance = match self {
+            AgentError::Provider(e) => e.guidance(),
+            AgentError::ContextTooLarge { .. } => {
+                Some("Run /compact or start a /new session.".to_string())
+            }
+            AgentError::SessionLimitReached { session_id, .. } => Some(format!(
+                "Run /unlock {session_id} in the TUI to allow this session to continue."
+            )),
+            _ => None,
+        };
+        match guidance {
+            Some(hint) => format!("{self}\n{hint}"),
+            None => self.to_string(),
+        }
+    }
+}
+
 /// Result type for agent operations
 pub type Result<T> = std::result::Result<T, AgentError>;
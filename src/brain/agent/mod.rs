@@ -11,6 +11,9 @@ pub mod service;
 pub use context::AgentContext;
 pub use error::{AgentError, Result};
 pub use service::{
-    AgentResponse, AgentService, AgentStreamResponse, ApprovalCallback, MessageQueueCallback,
-    ProgressCallback, ProgressEvent, SudoCallback, ToolApprovalInfo,
+    AgentResponse, AgentService, AgentStreamResponse, ApprovalCallback, ApprovalDecision,
+    AutoApprovePolicy, MAX_VARIANTS, MessageQueueCallback, ProgressCallback, ProgressEvent,
+    SessionLimits, SudoCallback, SystemAppendCallback, ToolApprovalInfo, ToolMetrics,
+    VARIANT_TEMPERATURE, reset_tool_metrics, session_reasoning_effort, session_temperature,
+    set_session_reasoning_effort, set_session_temperature, tool_metrics,
 };
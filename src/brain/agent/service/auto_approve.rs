@@ -0,0 +1,76 @@
+//! Capability-based auto-approval.
+//!
+//! Finer-grained than `with_auto_approve_tools(true)`: a tool that needs
+//! approval skips the `ApprovalCallback` only when every capability it
+//! declares is in the allowed set. Tools that declare no capabilities, and
+//! tools listed in `[tools] require_approval`, always prompt.
+
+use crate::brain::tools::ToolCapability;
+use std::collections::HashSet;
+
+/// Which tool capabilities run without asking.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoApprovePolicy {
+    allowed: HashSet<ToolCapability>,
+}
+
+impl AutoApprovePolicy {
+    /// Auto-approve tools whose capabilities are all in `allowed`.
+    pub fn new(allowed: impl IntoIterator<Item = ToolCapability>) -> Self {
+        Self {
+            allowed: allowed.into_iter().collect(),
+        }
+    }
+
+    /// Build from `[tools] auto_approve_capabilities` names (`"read_files"`,
+    /// `"network"`, …). Unknown names are logged and ignored.
+    pub fn from_names(names: &[String]) -> Self {
+        Self::new(names.iter().filter_map(|name| {
+            let cap = ToolCapability::from_name(name);
+            if cap.is_none() {
+                tracing::warn!("[tools] auto_approve_capabilities: unknown capability '{name}'");
+            }
+            cap
+        }))
+    }
+
+    /// True when nothing is auto-approved.
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty()
+    }
+
+    /// Whether a tool with these capabilities may run without approval.
+    pub fn allows(&self, capabilities: &[ToolCapability]) -> bool {
+        !capabilities.is_empty() && capabilities.iter().all(|c| self.allowed.contains(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subset_of_allowed_capabilities() {
+        let policy = AutoApprovePolicy::new([ToolCapability::ReadFiles, ToolCapability::Network]);
+        assert!(policy.allows(&[ToolCapability::Network]));
+        assert!(policy.allows(&[ToolCapability::ReadFiles, ToolCapability::Network]));
+        assert!(!policy.allows(&[ToolCapability::Network, ToolCapability::WriteFiles]));
+        assert!(!policy.allows(&[ToolCapability::ExecuteShell]));
+        // Undeclared capabilities are never assumed safe
+        assert!(!policy.allows(&[]));
+        assert!(!AutoApprovePolicy::default().allows(&[ToolCapability::ReadFiles]));
+    }
+
+    #[test]
+    fn test_from_names_skips_unknown() {
+        let policy = AutoApprovePolicy::from_names(&[
+            "read_files".to_string(),
+            "Network".to_string(),
+            "teleport".to_string(),
+        ]);
+        assert_eq!(
+            policy,
+            AutoApprovePolicy::new([ToolCapability::ReadFiles, ToolCapability::Network])
+        );
+    }
+}
@@ -1,7 +1,11 @@
+use super::auto_approve::AutoApprovePolicy;
+use super::limits::SessionLimits;
 use super::types::*;
 use crate::brain::provider::Provider;
 use crate::brain::tools::ToolRegistry;
+use crate::config::SamplingConfig;
 use crate::services::ServiceContext;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 /// Agent Service for managing AI conversations
@@ -24,12 +28,53 @@ pub struct AgentService {
     /// Whether to auto-approve tool execution
     pub(super) auto_approve_tools: bool,
 
+    /// Capabilities that run without approval (`[tools] auto_approve_capabilities`)
+    pub(super) auto_approve_policy: AutoApprovePolicy,
+
     /// Context window limit in tokens from config
     pub(super) context_limit: u32,
 
     /// Max output tokens for API calls from config
     pub(super) max_tokens: u32,
 
+    /// `[sampling]` defaults applied to every request
+    pub(super) sampling: SamplingConfig,
+
+    /// `[models."<name>"]` sampling overrides
+    pub(super) model_sampling: BTreeMap<String, SamplingConfig>,
+
+    /// Continuations allowed per turn after a `max_tokens` cutoff (0 = off)
+    pub(super) max_auto_continues: u32,
+
+    /// Append tool calls and responses to the `[audit]` JSONL trail
+    pub(super) audit_enabled: bool,
+
+    /// Seconds to wait for an approval answer before denying (0 = forever)
+    pub(super) approval_timeout_secs: u64,
+
+    /// Tool results longer than this are truncated for `read_more` (0 = off)
+    pub(super) max_result_chars: usize,
+
+    /// Append `<brain_path>/TOOLS.md` to the system brain each turn
+    pub(super) inject_tools_md: bool,
+
+    /// Running config; when set, the live `[tools]` settings are read from it
+    /// on each use instead of the values above, so reloads apply
+    pub(super) live_config: Option<tokio::sync::watch::Receiver<crate::config::Config>>,
+
+    /// `[chat] prompt_prefix` / `prompt_suffix` wrapped around user messages
+    pub(super) prompt_prefix: Option<String>,
+    pub(super) prompt_suffix: Option<String>,
+
+    /// `[channels.<name>] system_append` for the channel this service serves
+    pub(super) system_append: Option<SystemAppendCallback>,
+
+    /// `[agent] max_session_tokens` / `max_session_cost`
+    pub(super) session_limits: SessionLimits,
+
+    /// Compact before a turn when the session has been idle this long
+    pub(super) idle_compact_after: Option<std::time::Duration>,
+
     /// Callback for requesting tool approval from user
     pub(super) approval_callback: Option<ApprovalCallback>,
 
@@ -66,8 +111,28 @@ impl AgentService {
             max_tool_iterations: 0, // 0 = unlimited (loop detection is the safety net)
             default_system_brain: None,
             auto_approve_tools: false,
+            auto_approve_policy: AutoApprovePolicy::from_names(
+                &config.tools.auto_approve_capabilities,
+            ),
             context_limit: config.agent.context_limit,
             max_tokens: config.agent.max_tokens,
+            sampling: config.sampling,
+            model_sampling: config.models,
+            max_auto_continues: if config.context.auto_continue {
+                config.context.max_auto_continues
+            } else {
+                0
+            },
+            audit_enabled: config.audit.enabled,
+            approval_timeout_secs: config.tools.approval_timeout_secs,
+            max_result_chars: config.tools.max_result_chars,
+            inject_tools_md: config.tools.inject_tools_md,
+            live_config: None,
+            prompt_prefix: config.chat.prompt_prefix,
+            prompt_suffix: config.chat.prompt_suffix,
+            system_append: None,
+            session_limits: SessionLimits::from_config(&config.agent),
+            idle_compact_after: None,
             approval_callback: None,
             progress_callback: None,
             message_queue_callback: None,
@@ -149,6 +214,185 @@ impl AgentService {
         self
     }
 
+    /// Set the capability-based auto-approve policy, consulted before the
+    /// approval callback. Overrides `[tools] auto_approve_capabilities`.
+    pub fn with_auto_approve_policy(mut self, policy: AutoApprovePolicy) -> Self {
+        self.auto_approve_policy = policy;
+        self
+    }
+
+    /// Continue replies cut off by the output token limit, at most `max`
+    /// times per turn (0 disables). Overrides `[context] auto_continue`.
+    pub fn with_auto_continue(mut self, max: u32) -> Self {
+        self.max_auto_continues = max;
+        self
+    }
+
+    /// Record tool calls and responses in the audit trail.
+    pub fn with_audit(mut self, enabled: bool) -> Self {
+        self.audit_enabled = enabled;
+        self
+    }
+
+    /// Set sampling defaults and per-model overrides. Overrides `[sampling]`
+    /// and `[models]`.
+    pub fn with_sampling(
+        mut self,
+        defaults: SamplingConfig,
+        models: BTreeMap<String, SamplingConfig>,
+    ) -> Self {
+        self.sampling = defaults;
+        self.model_sampling = models;
+        self
+    }
+
+    /// Deny approvals left unanswered for `secs` seconds (0 waits forever).
+    /// Overrides `[tools] approval_timeout_secs`.
+    pub fn with_approval_timeout(mut self, secs: u64) -> Self {
+        self.approval_timeout_secs = secs;
+        self
+    }
+
+    /// Truncate tool results over `chars` characters (0 disables).
+    /// Overrides `[tools] max_result_chars`.
+    pub fn with_max_result_chars(mut self, chars: usize) -> Self {
+        self.max_result_chars = chars;
+        self
+    }
+
+    /// Cap each session's token use and cost.
+    /// Overrides `[agent] max_session_tokens` / `max_session_cost`.
+    pub fn with_session_limits(mut self, limits: SessionLimits) -> Self {
+        self.session_limits = limits;
+        self
+    }
+
+    /// Compact the history before a turn that follows `idle` of inactivity.
+    /// Channel services take this from `[sessions] compact_after_idle_minutes`.
+    pub fn with_idle_compact_after(mut self, idle: Option<std::time::Duration>) -> Self {
+        self.idle_compact_after = idle;
+        self
+    }
+
+    /// Append the brain path's TOOLS.md to the system brain every turn.
+    /// Overrides `[tools] inject_tools_md`.
+    pub fn with_tools_md(mut self, enabled: bool) -> Self {
+        self.inject_tools_md = enabled;
+        self
+    }
+
+    /// Follow `[tools] approval_timeout_secs`, `max_result_chars` and
+    /// `inject_tools_md` from the running config, so a reload changes them
+    /// without rebuilding the service. Takes precedence over the overrides above.
+    pub fn with_live_config(
+        mut self,
+        config_rx: tokio::sync::watch::Receiver<crate::config::Config>,
+    ) -> Self {
+        self.live_config = Some(config_rx);
+        self
+    }
+
+    /// The running config this service follows, if any.
+    pub fn live_config(&self) -> Option<tokio::sync::watch::Receiver<crate::config::Config>> {
+        self.live_config.clone()
+    }
+
+    /// Current `[tools] approval_timeout_secs`.
+    pub(super) fn approval_timeout_secs(&self) -> u64 {
+        self.live_config
+            .as_ref()
+            .map_or(self.approval_timeout_secs, |rx| {
+                rx.borrow().tools.approval_timeout_secs
+            })
+    }
+
+    /// Current `[tools] max_result_chars`.
+    pub(super) fn max_result_chars(&self) -> usize {
+        self.live_config
+            .as_ref()
+            .map_or(self.max_result_chars, |rx| {
+                rx.borrow().tools.max_result_chars
+            })
+    }
+
+    /// Current `[tools] inject_tools_md`.
+    pub(super) fn inject_tools_md(&self) -> bool {
+        self.live_config
+            .as_ref()
+            .map_or(self.inject_tools_md, |rx| rx.borrow().tools.inject_tools_md)
+    }
+
+    /// Wrap every user message in `prefix` / `suffix` before it reaches the
+    /// provider. Overrides `[chat] prompt_prefix` and `prompt_suffix`.
+    pub fn with_prompt_wrap(mut self, prefix: Option<String>, suffix: Option<String>) -> Self {
+        self.prompt_prefix = prefix;
+        self.prompt_suffix = suffix;
+        self
+    }
+
+    /// `text` with the configured prefix and suffix, each on its own
+    /// paragraph. `[SYSTEM: …]` messages are left alone.
+    pub(super) fn wrap_user_message(&self, text: &str) -> String {
+        if text.starts_with("[SYSTEM:") {
+            return text.to_string();
+        }
+        let set = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let mut wrapped = String::new();
+        if let Some(prefix) = set(&self.prompt_prefix) {
+            wrapped.push_str(&prefix);
+            wrapped.push_str("\n\n");
+        }
+        wrapped.push_str(text);
+        if let Some(suffix) = set(&self.prompt_suffix) {
+            wrapped.push_str("\n\n");
+            wrapped.push_str(&suffix);
+        }
+        wrapped
+    }
+
+    /// System brain for one turn: the default brain followed by TOOLS.md when
+    /// injection is on, then any channel instructions. TOOLS.md is read fresh
+    /// so edits apply on the next turn.
+    pub(super) fn turn_system_brain(&self) -> Option<String> {
+        let tool_notes = self
+            .inject_tools_md()
+            .then(|| self.brain_path.as_ref())
+            .flatten()
+            .and_then(|path| std::fs::read_to_string(path.join("TOOLS.md")).ok())
+            .filter(|notes| !notes.trim().is_empty());
+        let brain = match (&self.default_system_brain, tool_notes) {
+            (brain, None) => brain.clone(),
+            (None, Some(notes)) => Some(format!("## Tool usage notes (TOOLS.md)\n\n{notes}")),
+            (Some(brain), Some(notes)) => Some(format!(
+                "{brain}\n\n## Tool usage notes (TOOLS.md)\n\n{notes}"
+            )),
+        };
+        let channel_notes = self.system_append.as_ref().and_then(|append| append());
+        match (brain, channel_notes) {
+            (brain, None) => brain,
+            (None, Some(notes)) => Some(format!("## Channel instructions\n\n{notes}")),
+            (Some(brain), Some(notes)) => {
+                Some(format!("{brain}\n\n## Channel instructions\n\n{notes}"))
+            }
+        }
+    }
+
+    /// Whether `[tools] auto_approve_capabilities` lets this tool skip approval.
+    /// Tools forced via `[tools] require_approval` always ask.
+    pub(super) fn policy_auto_approves(&self, tool_name: &str) -> bool {
+        if self.auto_approve_policy.is_empty() || self.tool_registry.is_approval_forced(tool_name) {
+            return false;
+        }
+        self.tool_registry
+            .get(tool_name)
+            .is_some_and(|tool| self.auto_approve_policy.allows(&tool.capabilities()))
+    }
+
     /// Set the approval callback for interactive tool approval
     pub fn with_approval_callback(mut self, callback: Option<ApprovalCallback>) -> Self {
         self.approval_callback = callback;
@@ -209,11 +453,19 @@ impl AgentService {
         self
     }
 
+    /// Append per-channel instructions to the system brain. `append` is
+    /// called every turn, so it can read the latest config.
+    pub fn with_system_append(mut self, append: SystemAppendCallback) -> Self {
+        self.system_append = Some(append);
+        self
+    }
+
     /// Set the session-updated notification sender.
     ///
-    /// When set, `run_tool_loop` fires this after every completed agent response
-    /// so the TUI can reload the session in real-time when a remote channel
-    /// (Telegram, WhatsApp, Discord, Slack) processes a message.
+    /// When set, the service fires this when the user's message is saved, after
+    /// each tool iteration and after every completed response, so the TUI can
+    /// reload the session in real-time when a remote channel (Telegram,
+    /// WhatsApp, Discord, Slack) writes to it.
     pub fn with_session_updated_tx(
         mut self,
         tx: tokio::sync::mpsc::UnboundedSender<uuid::Uuid>,
@@ -227,6 +479,13 @@ impl AgentService {
         self.session_updated_tx.clone()
     }
 
+    /// Tell the TUI that `session_id` has new messages in the database.
+    pub(super) fn notify_session_updated(&self, session_id: uuid::Uuid) {
+        if let Some(ref tx) = self.session_updated_tx {
+            let _ = tx.send(session_id);
+        }
+    }
+
     /// Get the provider name
     pub fn provider_name(&self) -> String {
         self.provider
@@ -19,11 +19,12 @@ impl AgentService {
     ) -> Result<(String, LLMRequest, MessageService, SessionService)> {
         // Get or create session
         let session_service = SessionService::new(self.context.clone());
-        let _session = session_service
+        let session = session_service
             .get_session(session_id)
             .await
             .map_err(|e| AgentError::Database(e.to_string()))?
             .ok_or(AgentError::SessionNotFound(session_id))?;
+        self.check_session_limits(&session).await?;
 
         // Load conversation context with budget-aware message trimming
         let message_service = MessageService::new(self.context.clone());
@@ -48,13 +49,13 @@ impl AgentService {
             AgentContext::from_db_messages(session_id, db_messages, context_window as usize);
 
         // Add system brain if available (count its tokens for accurate tracking)
-        if let Some(brain) = &self.default_system_brain {
-            context.token_count += AgentContext::estimate_tokens(brain);
-            context.system_brain = Some(brain.clone());
+        if let Some(brain) = self.turn_system_brain() {
+            context.token_count += AgentContext::estimate_tokens(&brain);
+            context.system_brain = Some(brain);
         }
 
-        // Add user message
-        let user_msg = Message::user(user_message.clone());
+        // Add user message, wrapped in the [chat] prefix/suffix (DB keeps the original)
+        let user_msg = Message::user(self.wrap_user_message(&user_message));
         context.add_message(user_msg);
 
         // Save user message to database
@@ -62,6 +63,7 @@ impl AgentService {
             .create_message(session_id, "user".to_string(), user_message)
             .await
             .map_err(|e| AgentError::Database(e.to_string()))?;
+        self.notify_session_updated(session_id);
 
         // Build base LLM request
         let request = LLMRequest::new(model_name.clone(), context.messages.clone())
@@ -72,6 +74,7 @@ impl AgentService {
         } else {
             request
         };
+        let request = self.apply_sampling(session_id, request);
 
         Ok((model_name, request, message_service, session_service))
     }
@@ -106,6 +109,68 @@ impl AgentService {
         }
     }
 
+    /// Whether a session whose last message was at `last_activity` has been
+    /// idle longer than `threshold` at `now`. Messages kept verbatim by
+    /// compaction (8) are not worth summarizing, so short histories never are.
+    pub fn is_idle_for_compaction(
+        last_activity: Option<chrono::DateTime<chrono::Utc>>,
+        now: chrono::DateTime<chrono::Utc>,
+        threshold: Option<std::time::Duration>,
+        history_len: usize,
+    ) -> bool {
+        let (Some(last), Some(threshold)) = (last_activity, threshold) else {
+            return false;
+        };
+        history_len > 8
+            && now
+                .signed_duration_since(last)
+                .to_std()
+                .is_ok_and(|idle| idle >= threshold)
+    }
+
+    /// Compact a stale history before the turn that wakes an idle session, so
+    /// the new message isn't sent along with everything before the pause.
+    /// Failure is logged and the turn goes ahead with the full history.
+    pub(super) async fn compact_if_idle(
+        &self,
+        session_id: Uuid,
+        last_activity: Option<chrono::DateTime<chrono::Utc>>,
+        context: &mut AgentContext,
+        model_name: &str,
+        message_service: &MessageService,
+    ) {
+        if !Self::is_idle_for_compaction(
+            last_activity,
+            chrono::Utc::now(),
+            self.idle_compact_after,
+            context.messages.len(),
+        ) {
+            return;
+        }
+        tracing::info!(
+            "Session {} idle since {:?} — compacting {} messages before this turn",
+            session_id,
+            last_activity,
+            context.messages.len()
+        );
+        match self.compact_context(session_id, context, model_name).await {
+            Ok(summary) => {
+                let compaction_marker = format!(
+                    "[CONTEXT COMPACTION — The conversation was automatically compacted. \
+                     Below is a structured summary of everything before this point.]\n\n{}",
+                    summary
+                );
+                if let Err(e) = message_service
+                    .create_message(session_id, "user".to_string(), compaction_marker)
+                    .await
+                {
+                    tracing::error!("Failed to persist idle compaction marker to DB: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Idle compaction failed, continuing uncompacted: {}", e),
+        }
+    }
+
     /// Auto-compact the context when usage is too high.
     ///
     /// Before compaction, calculates the remaining context budget and sends
@@ -128,68 +193,18 @@ impl AgentService {
         // Build a summarization request with the full conversation
         let mut summary_messages = Vec::new();
 
-        // Include all conversation messages so the LLM sees the full context.
-        // Skip any leading user messages that consist only of ToolResult blocks —
-        // they are orphaned (their tool_use was removed by a prior trim) and would
-        // cause the API to reject the request with a 400.
-        let start = context
-            .messages
-            .iter()
-            .position(|m| {
-                !(m.role == crate::brain::provider::Role::User
-                    && !m.content.is_empty()
-                    && m.content.iter().all(|b| {
-                        matches!(b, crate::brain::provider::ContentBlock::ToolResult { .. })
-                    }))
-            })
-            .unwrap_or(context.messages.len());
-
-        // Cap the messages sent to the summarizer so the compaction request itself
-        // never exceeds the provider's context window. Reserve enough tokens for:
-        // - compaction prompt (~1k tokens)
-        // - system prompt (~1k tokens)
-        // - output budget (8k tokens)
-        // - safety margin (6k tokens)
-        // Total overhead: 16k tokens. Take the LAST N messages (most recent = most useful).
-        let compaction_overhead = 16_000usize;
-        // Also cap at 75% of context window to leave headroom — compaction request
-        // must itself fit within the provider limit.
-        let max_budget = (context.max_tokens as f64 * 0.75) as usize;
-        let summary_budget = context
-            .max_tokens
-            .saturating_sub(compaction_overhead)
-            .min(max_budget);
-        let mut running_tokens = 0usize;
-        let all_msgs = &context.messages[start..];
-        // Walk backwards from most-recent until we hit the budget
-        let msgs_to_include: Vec<&Message> = all_msgs
-            .iter()
-            .rev()
-            .take_while(|m| {
-                let t = AgentContext::estimate_tokens_static(m);
-                if running_tokens + t <= summary_budget {
-                    running_tokens += t;
-                    true
-                } else {
-                    false
-                }
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect();
+        let (msgs_to_include, running_tokens) =
+            Self::recent_messages_within_budget(&context.messages, context.max_tokens);
 
         tracing::info!(
             "Compaction: sending {} / {} messages to summarizer ({} / {} tokens)",
             msgs_to_include.len(),
-            all_msgs.len(),
+            context.messages.len(),
             running_tokens,
             context.max_tokens,
         );
 
-        for msg in msgs_to_include {
-            summary_messages.push(msg.clone());
-        }
+        summary_messages.extend(msgs_to_include);
 
         // Add the compaction instruction as a user message
         let compaction_prompt = format!(
@@ -305,16 +320,7 @@ impl AgentService {
         if let Err(e) = self.save_to_memory(&summary).await {
             tracing::warn!("Failed to save compaction summary to daily log: {}", e);
         }
-
-        // Index the updated memory file in the background so memory_search picks it up
-        let memory_path = crate::config::opencrabs_home()
-            .join("memory")
-            .join(format!("{}.md", chrono::Local::now().format("%Y-%m-%d")));
-        tokio::spawn(async move {
-            if let Ok(store) = crate::memory::get_store() {
-                let _ = crate::memory::index_file(store, &memory_path).await;
-            }
-        });
+        Self::reindex_daily_memory();
 
         // Snapshot the last 8 messages as formatted text before compaction.
         // This gives the agent immediate access to recent context without needing
@@ -353,6 +359,122 @@ impl AgentService {
         Ok(summary)
     }
 
+    /// Summarize a session for the human: a read-only digest of the
+    /// conversation since the last compaction, sent to the model non-streaming.
+    /// Nothing is added to the session. With `remember`, the digest is also
+    /// appended to today's memory log.
+    pub async fn summarize_session(&self, session_id: Uuid, remember: bool) -> Result<String> {
+        let message_service = MessageService::new(self.context.clone());
+        let db_messages = message_service
+            .list_messages_for_session(session_id)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?;
+        let db_messages = Self::messages_from_last_compaction(db_messages);
+        let context =
+            AgentContext::from_db_messages(session_id, db_messages, self.context_limit as usize);
+
+        let (mut messages, tokens) =
+            Self::recent_messages_within_budget(&context.messages, context.max_tokens);
+        if messages.is_empty() {
+            return Err(AgentError::InvalidRequest(
+                "Nothing to summarize yet".to_string(),
+            ));
+        }
+        tracing::info!(
+            "Session summary: sending {} / {} messages ({} tokens)",
+            messages.len(),
+            context.messages.len(),
+            tokens,
+        );
+
+        messages.push(Message::user(
+            "Summarize this conversation so far for the user, who is coming back to it \
+             after a break. In a few short sections cover: what we set out to do, what \
+             was decided or done (name files, commands and results), and what is still \
+             open or next. Be concise — bullet points, no preamble, no tool calls."
+                .to_string(),
+        ));
+        let request = LLMRequest::new(self.provider_model(), messages)
+            .with_max_tokens(self.max_tokens)
+            .with_system(
+                "You write brief, accurate recaps of conversations between a user and \
+                 their AI agent. Only report what actually happened in the conversation."
+                    .to_string(),
+            );
+
+        let provider = self
+            .provider
+            .read()
+            .expect("provider lock poisoned")
+            .clone();
+        let response = provider
+            .complete(request)
+            .await
+            .map_err(AgentError::Provider)?;
+        let summary = Self::extract_text_from_response(&response);
+
+        if remember {
+            Self::append_to_daily_memory("Session Summary", &summary)
+                .map_err(AgentError::Internal)?;
+            Self::reindex_daily_memory();
+        }
+
+        Ok(summary)
+    }
+
+    /// The most recent messages that fit a summarizer request, oldest first,
+    /// plus their estimated token count.
+    ///
+    /// Reserves room for the summary prompt, system prompt and output
+    /// (~16k tokens) and caps at 75% of the context window so the request
+    /// itself always fits the provider limit. Leading user messages that are
+    /// only ToolResult blocks are dropped — they are orphaned (their tool_use
+    /// was trimmed) and would make the API reject the request with a 400.
+    fn recent_messages_within_budget(
+        messages: &[Message],
+        max_tokens: usize,
+    ) -> (Vec<Message>, usize) {
+        use crate::brain::provider::{ContentBlock, Role};
+
+        const SUMMARY_OVERHEAD: usize = 16_000;
+        let max_budget = (max_tokens as f64 * 0.75) as usize;
+        let budget = max_tokens.saturating_sub(SUMMARY_OVERHEAD).min(max_budget);
+
+        // Walk backwards from most-recent until we hit the budget
+        let mut running_tokens = 0usize;
+        let mut included: Vec<Message> = messages
+            .iter()
+            .rev()
+            .take_while(|m| {
+                let t = AgentContext::estimate_tokens_static(m);
+                if running_tokens + t <= budget {
+                    running_tokens += t;
+                    true
+                } else {
+                    false
+                }
+            })
+            .cloned()
+            .collect();
+        included.reverse();
+
+        let is_orphan = |m: &Message| {
+            m.role == Role::User
+                && !m.content.is_empty()
+                && m.content
+                    .iter()
+                    .all(|b| matches!(b, ContentBlock::ToolResult { .. }))
+        };
+        let start = included
+            .iter()
+            .position(|m| !is_orphan(m))
+            .unwrap_or(included.len());
+        for m in &included[..start] {
+            running_tokens -= AgentContext::estimate_tokens_static(m);
+        }
+        (included.split_off(start), running_tokens)
+    }
+
     /// Format the last N messages into a human-readable snapshot for post-compaction context.
     /// Truncates long tool results to keep the snapshot concise.
     pub(crate) fn format_recent_messages(messages: &[Message], n: usize) -> String {
@@ -416,29 +538,47 @@ impl AgentService {
     /// Multiple compactions per day append to the same file. The brain workspace's
     /// `MEMORY.md` is left untouched — it stays as user-curated durable memory.
     pub(super) async fn save_to_memory(&self, summary: &str) -> std::result::Result<(), String> {
+        Self::append_to_daily_memory("Auto-Compaction Summary", summary)
+    }
+
+    /// Append a `## {heading} (timestamp)` section to today's memory log.
+    fn append_to_daily_memory(heading: &str, body: &str) -> std::result::Result<(), String> {
         let memory_dir = crate::config::opencrabs_home().join("memory");
 
         std::fs::create_dir_all(&memory_dir)
             .map_err(|e| format!("Failed to create memory directory: {}", e))?;
 
-        let date = chrono::Local::now().format("%Y-%m-%d");
+        let date = crate::config::local_today();
         let memory_path = memory_dir.join(format!("{}.md", date));
 
-        // Read existing content (if any — multiple compactions per day stack)
+        // Read existing content (if any — multiple entries per day stack)
         let existing = std::fs::read_to_string(&memory_path).unwrap_or_default();
 
-        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+        let timestamp = crate::config::local_now().format("%Y-%m-%d %H:%M:%S");
         let new_content = format!(
-            "{}\n\n---\n\n## Auto-Compaction Summary ({})\n\n{}\n",
+            "{}\n\n---\n\n## {} ({})\n\n{}\n",
             existing.trim(),
+            heading,
             timestamp,
-            summary
+            body
         );
 
         std::fs::write(&memory_path, new_content.trim_start())
             .map_err(|e| format!("Failed to write daily memory log: {}", e))?;
 
-        tracing::info!("Saved compaction summary to {}", memory_path.display());
+        tracing::info!("Saved {} to {}", heading, memory_path.display());
         Ok(())
     }
+
+    /// Index today's memory log in the background so memory_search picks it up.
+    fn reindex_daily_memory() {
+        let memory_path = crate::config::opencrabs_home()
+            .join("memory")
+            .join(format!("{}.md", crate::config::local_today()));
+        tokio::spawn(async move {
+            if let Ok(store) = crate::memory::get_store() {
+                let _ = crate::memory::index_file(store, &memory_path).await;
+            }
+        });
+    }
 }
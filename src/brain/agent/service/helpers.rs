@@ -1,13 +1,124 @@
 use super::builder::AgentService;
-use super::types::{ProgressCallback, ProgressEvent};
+use super::metrics;
+use super::types::{AgentResponse, ProgressCallback, ProgressEvent};
 use crate::brain::provider::{
-    ContentBlock, ImageSource, LLMRequest, LLMResponse, Message, Role, StopReason,
+    ContentBlock, ImageSource, LLMRequest, LLMResponse, Message, ProviderCapabilities,
+    ProviderErrorKind, Role, StopReason,
 };
+use crate::brain::tools::read_more;
+use crate::logging::audit::{self, AuditEvent};
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Live tool output lines buffered before further lines are dropped
+const TOOL_OUTPUT_BUFFER: usize = 256;
+
 impl AgentService {
+    /// Fold a finished tool invocation into the tool metrics and append it
+    /// to the audit trail (`[audit] enabled`).
+    pub(super) fn record_tool_call(
+        &self,
+        session_id: Uuid,
+        tool_name: &str,
+        tool_input: &Value,
+        success: bool,
+        output: &str,
+        started: std::time::Instant,
+    ) {
+        let duration = started.elapsed();
+        metrics::record_tool_metrics(tool_name, success, duration);
+        if !self.audit_enabled {
+            return;
+        }
+        audit::record(AuditEvent::ToolCall {
+            session_id,
+            tool: tool_name.to_string(),
+            input: crate::utils::redact_tool_input(tool_input),
+            success,
+            summary: audit::summarize(&crate::utils::sanitize::redact_secrets(output)),
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
+
+    /// Cap tool results at `[tools] max_result_chars`. The full text of a
+    /// truncated result stays retrievable through the `read_more` tool, by
+    /// this session only. Results of `read_more` itself (among `calls`) are
+    /// already page-sized and pass through untouched.
+    pub(super) fn cap_tool_results(
+        &self,
+        session_id: Uuid,
+        calls: &[ContentBlock],
+        results: Vec<ContentBlock>,
+    ) -> Vec<ContentBlock> {
+        let max_chars = self.max_result_chars();
+        if max_chars == 0 {
+            return results;
+        }
+        let pages: std::collections::HashSet<&str> = calls
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, name, .. } if name == "read_more" => Some(id.as_str()),
+                _ => None,
+            })
+            .collect();
+        results
+            .into_iter()
+            .map(|block| match block {
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error,
+                } if !pages.contains(tool_use_id.as_str()) => ContentBlock::ToolResult {
+                    content: read_more::truncate_result(session_id, content, max_chars),
+                    tool_use_id,
+                    is_error,
+                },
+                other => other,
+            })
+            .collect()
+    }
+
+    /// Live output sink for one tool run: lines sent to it are forwarded to
+    /// `progress_callback` as `ProgressEvent::ToolOutput`. The forwarding task
+    /// ends when the tool drops its context. `None` without a callback.
+    pub(super) fn tool_output_sink(
+        session_id: Uuid,
+        tool_name: &str,
+        progress_callback: Option<&ProgressCallback>,
+    ) -> Option<tokio::sync::mpsc::Sender<String>> {
+        let cb = progress_callback?.clone();
+        let tool_name = tool_name.to_string();
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(TOOL_OUTPUT_BUFFER);
+        tokio::spawn(async move {
+            while let Some(line) = rx.recv().await {
+                cb(
+                    session_id,
+                    ProgressEvent::ToolOutput {
+                        tool_name: tool_name.clone(),
+                        line,
+                    },
+                );
+            }
+        });
+        Some(tx)
+    }
+
+    /// Append an agent response to the audit trail (`[audit] enabled`).
+    pub(super) fn audit_response(&self, session_id: Uuid, response: &AgentResponse) {
+        if !self.audit_enabled {
+            return;
+        }
+        audit::record(AuditEvent::Response {
+            session_id,
+            provider: response.provider.clone(),
+            model: response.model.clone(),
+            input_tokens: response.usage.prompt_tokens(),
+            output_tokens: response.usage.output_tokens,
+            cost: response.cost,
+        });
+    }
+
     /// Actual token count for the serialized tool schemas (cached per call).
     pub(super) fn actual_tool_schema_tokens(&self) -> usize {
         crate::brain::tokenizer::count_tokens(
@@ -15,6 +126,21 @@ impl AgentService {
         )
     }
 
+    /// Tokens of history that fit in a request to `model`: the smaller of the
+    /// configured limit and the model's window, minus the reply and tool schemas.
+    pub(super) fn history_budget(&self, model: &str) -> usize {
+        let window =
+            self.provider
+                .read()
+                .expect("provider lock poisoned")
+                .context_window(model)
+                .map_or(self.context_limit, |w| w.min(self.context_limit)) as usize;
+        let reply_reserve = (self.max_tokens as usize).min(window / 4);
+        window
+            .saturating_sub(reply_reserve)
+            .saturating_sub(self.actual_tool_schema_tokens())
+    }
+
     /// Stream a request and accumulate into an LLMResponse.
     ///
     /// Sends text deltas to the progress callback as `StreamingChunk` events
@@ -44,6 +170,42 @@ impl AgentService {
             .read()
             .expect("provider lock poisoned")
             .clone();
+
+        // Providers without streaming get one `complete` call; its text is
+        // forwarded as a single chunk so callers see the same events
+        if !provider.supports_streaming() {
+            let response = tokio::select! {
+                biased;
+                _ = async {
+                    match cancel_token {
+                        Some(token) => token.cancelled().await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    tracing::info!("Completion cancelled by user");
+                    LLMResponse {
+                        id: String::new(),
+                        model: request_model,
+                        content: Vec::new(),
+                        stop_reason: None,
+                        usage: TokenUsage {
+                            input_tokens: 0,
+                            output_tokens: 0,
+                            ..Default::default()
+                        },
+                    }
+                }
+                response = provider.complete(request) => response?,
+            };
+            let text = Self::extract_text_from_response(&response);
+            if let Some(cb) = effective_cb
+                && !text.is_empty()
+            {
+                cb(session_id, ProgressEvent::StreamingChunk { text });
+            }
+            return Ok((response, None));
+        }
+
         let mut stream = provider.stream(request).await?;
 
         // Accumulate state from stream events
@@ -52,13 +214,41 @@ impl AgentService {
         let mut stop_reason: Option<StopReason> = None;
         let mut input_tokens = 0u32;
         let mut output_tokens = 0u32;
+        let mut cache_creation_input_tokens = 0u32;
+        let mut cache_read_input_tokens = 0u32;
 
         // Track partial content blocks by index
         // Text blocks: accumulate text deltas
-        // ToolUse blocks: accumulate JSON deltas
+        // ToolUse blocks: accumulate JSON deltas, parsed only once the block ends
+        #[derive(Default)]
         struct BlockState {
-            block: ContentBlock,
+            block: Option<ContentBlock>,
             json_buf: String, // for tool use JSON accumulation
+            fragments: usize,
+        }
+        impl BlockState {
+            /// Parse the buffered JSON fragments into the tool input. Malformed
+            /// JSON is marked so the tool loop can ask for a resend.
+            fn finish_tool_input(&mut self) {
+                if let Some(ContentBlock::ToolUse {
+                    ref name,
+                    ref mut input,
+                    ..
+                }) = self.block
+                    && !self.json_buf.is_empty()
+                {
+                    let raw = std::mem::take(&mut self.json_buf);
+                    *input = crate::brain::provider::parse_tool_input(name, &raw);
+                    if crate::brain::provider::malformed_tool_input_error(input).is_some() {
+                        tracing::warn!(
+                            "Tool input for '{}' is invalid JSON after {} fragment(s), {} bytes",
+                            name,
+                            self.fragments,
+                            raw.len()
+                        );
+                    }
+                }
+            }
         }
         let mut block_states: Vec<BlockState> = Vec::new();
         let mut reasoning_buf = String::new();
@@ -103,6 +293,19 @@ impl AgentService {
 
             let event = match next {
                 Ok(e) => e,
+                // A connection lost mid-reply keeps the text received so far:
+                // with stop_reason None the tool loop resumes from it
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        ProviderErrorKind::Network | ProviderErrorKind::ServerError
+                    ) && block_states.iter().any(
+                        |s| matches!(&s.block, Some(ContentBlock::Text { text }) if !text.is_empty()),
+                    ) =>
+                {
+                    tracing::warn!("Stream error after partial reply, keeping it: {}", e);
+                    break;
+                }
                 Err(e) => {
                     tracing::warn!("Stream error: {}", e);
                     return Err(e);
@@ -114,70 +317,73 @@ impl AgentService {
                     id = message.id;
                     model = message.model;
                     input_tokens = message.usage.input_tokens;
+                    cache_creation_input_tokens = message.usage.cache_creation_input_tokens;
+                    cache_read_input_tokens = message.usage.cache_read_input_tokens;
                 }
                 StreamEvent::ContentBlockStart {
                     index,
                     content_block,
                 } => {
                     // Ensure block_states has enough capacity
-                    while block_states.len() <= index {
-                        block_states.push(BlockState {
-                            block: ContentBlock::Text {
-                                text: String::new(),
-                            },
-                            json_buf: String::new(),
-                        });
+                    if block_states.len() <= index {
+                        block_states.resize_with(index + 1, BlockState::default);
+                    }
+                    // Keep any deltas that arrived ahead of their start event
+                    let state = &mut block_states[index];
+                    let mut content_block = content_block;
+                    if let (Some(ContentBlock::Text { text: early }), ContentBlock::Text { text }) =
+                        (state.block.take(), &mut content_block)
+                    {
+                        text.insert_str(0, &early);
                     }
-                    block_states[index] = BlockState {
-                        block: content_block,
-                        json_buf: String::new(),
-                    };
+                    state.block = Some(content_block);
                 }
                 StreamEvent::ContentBlockDelta { index, delta } => {
-                    if index < block_states.len() {
-                        match delta {
-                            ContentDelta::TextDelta { text } => {
-                                // Forward to TUI / per-call callback for real-time display
-                                if let Some(cb) = effective_cb {
-                                    cb(
-                                        session_id,
-                                        ProgressEvent::StreamingChunk { text: text.clone() },
-                                    );
-                                }
-                                // Accumulate into block
-                                if let ContentBlock::Text { text: ref mut t } =
-                                    block_states[index].block
-                                {
-                                    t.push_str(&text);
-                                }
-                            }
-                            ContentDelta::InputJsonDelta { partial_json } => {
-                                block_states[index].json_buf.push_str(&partial_json);
+                    if block_states.len() <= index {
+                        tracing::debug!("Delta for content block {} before its start", index);
+                        block_states.resize_with(index + 1, BlockState::default);
+                    }
+                    match delta {
+                        ContentDelta::TextDelta { text } => {
+                            // Forward to TUI / per-call callback for real-time display
+                            if let Some(cb) = effective_cb {
+                                cb(
+                                    session_id,
+                                    ProgressEvent::StreamingChunk { text: text.clone() },
+                                );
                             }
-                            ContentDelta::ReasoningDelta { text } => {
-                                // Forward reasoning content to TUI / per-call callback
-                                if let Some(cb) = effective_cb {
-                                    cb(
-                                        session_id,
-                                        ProgressEvent::ReasoningChunk { text: text.clone() },
-                                    );
+                            // Accumulate into block
+                            let block = block_states[index].block.get_or_insert_with(|| {
+                                ContentBlock::Text {
+                                    text: String::new(),
                                 }
-                                // Accumulate for persistence
-                                reasoning_buf.push_str(&text);
+                            });
+                            if let ContentBlock::Text { text: t } = block {
+                                t.push_str(&text);
                             }
                         }
+                        ContentDelta::InputJsonDelta { partial_json } => {
+                            let state = &mut block_states[index];
+                            state.json_buf.push_str(&partial_json);
+                            state.fragments += 1;
+                        }
+                        ContentDelta::ReasoningDelta { text } => {
+                            // Forward reasoning content to TUI / per-call callback
+                            if let Some(cb) = effective_cb {
+                                cb(
+                                    session_id,
+                                    ProgressEvent::ReasoningChunk { text: text.clone() },
+                                );
+                            }
+                            // Accumulate for persistence
+                            reasoning_buf.push_str(&text);
+                        }
                     }
                 }
                 StreamEvent::ContentBlockStop { index } => {
-                    if index < block_states.len() {
-                        let state = &mut block_states[index];
-                        // Finalize tool use blocks: parse accumulated JSON
-                        if let ContentBlock::ToolUse { ref mut input, .. } = state.block
-                            && !state.json_buf.is_empty()
-                            && let Ok(parsed) = serde_json::from_str(&state.json_buf)
-                        {
-                            *input = parsed;
-                        }
+                    // Finalize tool use blocks: parse the accumulated JSON
+                    if let Some(state) = block_states.get_mut(index) {
+                        state.finish_tool_input();
                     }
                 }
                 StreamEvent::MessageDelta { delta, usage } => {
@@ -196,6 +402,10 @@ impl AgentService {
                     if usage.output_tokens > output_tokens {
                         output_tokens = usage.output_tokens;
                     }
+                    cache_creation_input_tokens =
+                        cache_creation_input_tokens.max(usage.cache_creation_input_tokens);
+                    cache_read_input_tokens =
+                        cache_read_input_tokens.max(usage.cache_read_input_tokens);
                 }
                 StreamEvent::MessageStop => break,
                 StreamEvent::Ping => {}
@@ -216,11 +426,15 @@ impl AgentService {
             );
         }
 
-        // Build final content blocks from accumulated state
+        // Build final content blocks from accumulated state. Tool blocks whose
+        // stop event never came are parsed now rather than left with empty input.
         // Filter out empty text blocks — Anthropic rejects "text content blocks must be non-empty"
         let content_blocks: Vec<ContentBlock> = block_states
             .into_iter()
-            .map(|s| s.block)
+            .filter_map(|mut s| {
+                s.finish_tool_input();
+                s.block
+            })
             .filter(|b| !matches!(b, ContentBlock::Text { text } if text.is_empty()))
             .collect();
 
@@ -244,15 +458,56 @@ impl AgentService {
                 usage: TokenUsage {
                     input_tokens,
                     output_tokens,
+                    cache_creation_input_tokens,
+                    cache_read_input_tokens,
                 },
             },
             reasoning,
         ))
     }
 
+    /// What the current provider can do, for gating images, tools and streaming.
+    pub(super) fn provider_capabilities(&self) -> ProviderCapabilities {
+        let provider = self.provider.read().expect("provider lock poisoned");
+        ProviderCapabilities::for_provider(provider.as_ref())
+    }
+
+    /// Tell the user the provider can't do something this turn asked for.
+    /// Also logged, since channel callbacks don't display the event.
+    pub(super) fn warn_capability(
+        &self,
+        session_id: Uuid,
+        callback: Option<&ProgressCallback>,
+        message: &str,
+    ) {
+        tracing::warn!("{}", message);
+        if let Some(cb) = callback {
+            cb(
+                session_id,
+                ProgressEvent::CapabilityWarning {
+                    message: message.to_string(),
+                },
+            );
+        }
+    }
+
     /// Build a user Message, auto-attaching images from `<<IMG:path>>` markers.
     /// The TUI inserts these markers for detected image paths/URLs (handles spaces).
-    pub(super) async fn build_user_message(text: &str) -> Message {
+    /// Without `vision` the markers are replaced by a note so a text-only model
+    /// knows an image was sent but never receives the raw marker.
+    pub(super) async fn build_user_message(text: &str, vision: bool) -> Message {
+        if !vision {
+            let (clean_text, images) = crate::utils::extract_img_markers(text);
+            if images.is_empty() {
+                return Message::user(clean_text);
+            }
+            return Message::user(format!(
+                "{}\n\n[{} image(s) omitted — the current model can't view images]",
+                clean_text,
+                images.len()
+            ));
+        }
+
         let mut image_blocks: Vec<ContentBlock> = Vec::new();
 
         // Extract <<IMG:path>> markers
@@ -362,6 +617,13 @@ impl AgentService {
                     .unwrap_or("?");
                 format!("Edit {}", path)
             }
+            "apply_patch" => {
+                let path = tool_input
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?");
+                format!("Patch {}", path)
+            }
             "ls" => {
                 let path = tool_input
                     .get("path")
@@ -369,6 +631,13 @@ impl AgentService {
                     .unwrap_or(".");
                 format!("ls {}", path)
             }
+            "list_files" => {
+                let path = tool_input
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(".");
+                format!("Tree {}", path)
+            }
             "glob" => {
                 let p = tool_input
                     .get("pattern")
@@ -0,0 +1,114 @@
+//! Per-session token and cost limits.
+//!
+//! `[agent] max_session_tokens` / `max_session_cost` cap what one session may
+//! spend. Once a session's persisted usage reaches either cap, new turns fail
+//! with `AgentError::SessionLimitReached` until `/unlock`, which grants a
+//! fresh allowance counted from the usage at that moment.
+
+use super::builder::AgentService;
+use crate::brain::agent::error::{AgentError, Result};
+use crate::db::models::Session;
+use crate::services::{SessionService, SettingsService};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Token and cost caps for a single session. `None` means no cap.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SessionLimits {
+    pub max_tokens: Option<u64>,
+    pub max_cost: Option<f64>,
+}
+
+impl SessionLimits {
+    /// Read `[agent] max_session_tokens` / `max_session_cost`.
+    pub fn from_config(agent: &crate::config::AgentConfig) -> Self {
+        Self {
+            max_tokens: agent.max_session_tokens,
+            max_cost: agent.max_session_cost,
+        }
+    }
+
+    /// Whether no cap is set
+    pub fn is_unlimited(&self) -> bool {
+        self.max_tokens.is_none() && self.max_cost.is_none()
+    }
+
+    /// Describe the cap that `tokens` / `cost` reach, or `None` while under both.
+    pub fn exceeded(&self, tokens: i64, cost: f64) -> Option<String> {
+        if let Some(max) = self.max_tokens
+            && tokens >= max as i64
+        {
+            return Some(format!("{} of {} tokens used", tokens, max));
+        }
+        if let Some(max) = self.max_cost
+            && cost >= max
+        {
+            return Some(format!("${:.2} of ${:.2} spent", cost, max));
+        }
+        None
+    }
+}
+
+/// Session usage at the last `/unlock`; limits count from here.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct UnlockBaseline {
+    tokens: i64,
+    cost: f64,
+}
+
+fn unlock_key(session_id: Uuid) -> String {
+    format!("session_unlock:{}", session_id)
+}
+
+impl AgentService {
+    /// Refuse the turn when `session` has used up its allowance.
+    pub(super) async fn check_session_limits(&self, session: &Session) -> Result<()> {
+        if self.session_limits.is_unlimited() {
+            return Ok(());
+        }
+        let baseline: UnlockBaseline = SettingsService::new(self.context.clone())
+            .get_or_default(&unlock_key(session.id))
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unlock state for session {}: {}", session.id, e);
+                UnlockBaseline::default()
+            });
+        let tokens = i64::from(session.token_count) - baseline.tokens;
+        let cost = session.total_cost - baseline.cost;
+        match self.session_limits.exceeded(tokens, cost) {
+            Some(reason) => Err(AgentError::SessionLimitReached {
+                session_id: session.id,
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Give `session_id` a fresh allowance starting from its current usage.
+    pub async fn unlock_session(&self, session_id: Uuid) -> Result<()> {
+        let session = SessionService::new(self.context.clone())
+            .get_session(session_id)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?
+            .ok_or(AgentError::SessionNotFound(session_id))?;
+        let baseline = UnlockBaseline {
+            tokens: i64::from(session.token_count),
+            cost: session.total_cost,
+        };
+        SettingsService::new(self.context.clone())
+            .set(&unlock_key(session_id), &baseline)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?;
+        tracing::info!(
+            "Unlocked session {} at {} tokens",
+            session_id,
+            baseline.tokens
+        );
+        Ok(())
+    }
+
+    /// The per-session caps this service enforces
+    pub fn session_limits(&self) -> SessionLimits {
+        self.session_limits
+    }
+}
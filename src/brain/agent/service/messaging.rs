@@ -1,6 +1,8 @@
 use super::builder::AgentService;
+use super::turns;
 use super::types::*;
 use crate::brain::agent::error::{AgentError, Result};
+use crate::brain::provider::track_served_by;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -30,10 +32,8 @@ impl AgentService {
             .read()
             .expect("provider lock poisoned")
             .clone();
-        let response = provider
-            .complete(request)
-            .await
-            .map_err(AgentError::Provider)?;
+        let (response, served_by) = track_served_by(provider.complete(request)).await;
+        let response = response.map_err(AgentError::Provider)?;
 
         // Extract text from response
         let assistant_text = Self::extract_text_from_response(&response);
@@ -45,14 +45,14 @@ impl AgentService {
             .map_err(|e| AgentError::Database(e.to_string()))?;
 
         // Calculate total tokens and cost for this message
-        let total_tokens = response.usage.input_tokens + response.usage.output_tokens;
+        let total_tokens = response.usage.total();
         let cost = self
             .provider
             .read()
             .expect("provider lock poisoned")
             .calculate_cost(
                 &response.model,
-                response.usage.input_tokens,
+                response.usage.billable_input_tokens(),
                 response.usage.output_tokens,
             );
 
@@ -68,15 +68,19 @@ impl AgentService {
             .await
             .map_err(|e| AgentError::Database(e.to_string()))?;
 
-        Ok(AgentResponse {
+        let response = AgentResponse {
             message_id: assistant_db_msg.id,
             content: assistant_text,
             stop_reason: response.stop_reason,
-            context_tokens: response.usage.input_tokens,
+            context_tokens: response.usage.prompt_tokens(),
             usage: response.usage,
             cost,
             model: response.model,
-        })
+            provider: served_by.unwrap_or_else(|| provider.name().to_string()),
+        };
+        self.audit_response(session_id, &response);
+        self.notify_session_updated(session_id);
+        Ok(response)
     }
 
     /// Send a message and get a streaming response
@@ -141,6 +145,7 @@ impl AgentService {
         model: Option<String>,
         cancel_token: Option<CancellationToken>,
     ) -> Result<AgentResponse> {
+        let _turn = turns::begin_foreground(session_id);
         self.run_tool_loop(session_id, user_message, model, cancel_token, None, None)
             .await
     }
@@ -159,6 +164,7 @@ impl AgentService {
         override_approval_callback: Option<ApprovalCallback>,
         override_progress_callback: Option<ProgressCallback>,
     ) -> Result<AgentResponse> {
+        let _turn = turns::begin_foreground(session_id);
         self.run_tool_loop(
             session_id,
             user_message,
@@ -169,4 +175,47 @@ impl AgentService {
         )
         .await
     }
+
+    /// Run an autonomous turn (heartbeat, boot routine) with no interactive user.
+    ///
+    /// Tools that need no approval, or that `[tools] auto_approve_capabilities`
+    /// allows, run as usual. Anything else runs only under
+    /// `approval_policy = "auto-always"` and is denied otherwise, since nobody
+    /// is there to answer. Fails with `AgentError::SessionBusy` if a turn
+    /// is already running in the session, and `cancel_token` is cancelled as
+    /// soon as a user turn starts there.
+    pub async fn send_background_message(
+        &self,
+        session_id: Uuid,
+        message: String,
+        cancel_token: CancellationToken,
+    ) -> Result<AgentResponse> {
+        let Some(_turn) = turns::begin_background(session_id, cancel_token.clone()) else {
+            return Err(AgentError::SessionBusy(session_id));
+        };
+        self.run_tool_loop(
+            session_id,
+            message,
+            None,
+            Some(cancel_token),
+            Some(std::sync::Arc::new(|info: ToolApprovalInfo| {
+                Box::pin(async move {
+                    let approved = !info.forced && crate::utils::unattended_approval_allowed();
+                    if !approved {
+                        tracing::warn!(
+                            "Background turn: denied '{}' — it needs approval and nobody is there to give it",
+                            info.tool_name
+                        );
+                    }
+                    Ok(if approved {
+                        ApprovalDecision::Approve
+                    } else {
+                        ApprovalDecision::Deny
+                    })
+                })
+            })),
+            None,
+        )
+        .await
+    }
 }
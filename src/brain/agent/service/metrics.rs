@@ -0,0 +1,98 @@
+//! Per-tool call metrics.
+//!
+//! Every tool the loop executes is timed and folded in here by name: call
+//! count, failures and durations. Kept process-wide like the `/temp`
+//! overrides so the numbers survive provider swaps; `/metrics` shows them.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+static TOOL_METRICS: Lazy<Mutex<HashMap<String, ToolMetrics>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Aggregated calls of one tool since startup (or the last reset)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolMetrics {
+    pub calls: u64,
+    pub failures: u64,
+    pub total_duration: Duration,
+    pub max_duration: Duration,
+}
+
+impl ToolMetrics {
+    /// Fold one call in
+    pub fn record(&mut self, success: bool, duration: Duration) {
+        self.calls += 1;
+        if !success {
+            self.failures += 1;
+        }
+        self.total_duration += duration;
+        self.max_duration = self.max_duration.max(duration);
+    }
+
+    pub fn avg_duration(&self) -> Duration {
+        match self.calls {
+            0 => Duration::ZERO,
+            n => self.total_duration / n as u32,
+        }
+    }
+
+    /// Share of calls that succeeded, 0.0–1.0
+    pub fn success_rate(&self) -> f64 {
+        match self.calls {
+            0 => 1.0,
+            n => (n - self.failures) as f64 / n as f64,
+        }
+    }
+}
+
+/// Record one execution of `tool`.
+pub fn record_tool_metrics(tool: &str, success: bool, duration: Duration) {
+    TOOL_METRICS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(tool.to_string())
+        .or_default()
+        .record(success, duration);
+}
+
+/// Metrics for every tool called so far, most-called first.
+pub fn tool_metrics() -> Vec<(String, ToolMetrics)> {
+    let mut all: Vec<_> = TOOL_METRICS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(name, m)| (name.clone(), *m))
+        .collect();
+    all.sort_by(|(a_name, a), (b_name, b)| b.calls.cmp(&a.calls).then(a_name.cmp(b_name)));
+    all
+}
+
+/// Forget everything recorded so far.
+pub fn reset_tool_metrics() {
+    TOOL_METRICS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_aggregates_counts_and_durations() {
+        let mut m = ToolMetrics::default();
+        m.record(true, Duration::from_millis(100));
+        m.record(false, Duration::from_millis(300));
+        m.record(true, Duration::from_millis(200));
+
+        assert_eq!(m.calls, 3);
+        assert_eq!(m.failures, 1);
+        assert_eq!(m.avg_duration(), Duration::from_millis(200));
+        assert_eq!(m.max_duration, Duration::from_millis(300));
+        assert!((m.success_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+}
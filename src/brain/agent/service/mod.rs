@@ -3,18 +3,33 @@
 //! Core service for managing AI agent conversations, coordinating between
 //! LLM providers, context management, and data persistence.
 
+mod auto_approve;
 mod builder;
 mod context;
 mod helpers;
+mod limits;
 mod messaging;
+mod metrics;
+mod sampling;
 mod tool_loop;
+mod turns;
 mod types;
+mod variants;
 
 #[cfg(test)]
 mod tests;
 
+pub use auto_approve::AutoApprovePolicy;
 pub use builder::AgentService;
+pub use limits::SessionLimits;
+pub use metrics::{ToolMetrics, reset_tool_metrics, tool_metrics};
+pub use sampling::{
+    session_reasoning_effort, session_temperature, set_session_reasoning_effort,
+    set_session_temperature,
+};
+pub use turns::is_session_busy;
 pub use types::{
-    AgentResponse, AgentStreamResponse, ApprovalCallback, MessageQueueCallback, ProgressCallback,
-    ProgressEvent, SudoCallback, ToolApprovalInfo,
+    AgentResponse, AgentStreamResponse, ApprovalCallback, ApprovalDecision, MessageQueueCallback,
+    ProgressCallback, ProgressEvent, SudoCallback, SystemAppendCallback, ToolApprovalInfo,
 };
+pub use variants::{MAX_VARIANTS, VARIANT_TEMPERATURE};
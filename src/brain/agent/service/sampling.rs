@@ -0,0 +1,96 @@
+//! Sampling parameters for outgoing requests.
+//!
+//! `[sampling]` supplies defaults and `[models."<name>"]` overrides them per
+//! model. `/temp` and `/think` set per-session temperature and reasoning
+//! effort on top of both; they are kept here rather than on the service so
+//! they survive provider swaps, which rebuild the `AgentService`.
+
+use super::builder::AgentService;
+use crate::brain::provider::{LLMRequest, ReasoningEffort};
+use crate::config::SamplingConfig;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+static SESSION_TEMPERATURE: Lazy<Mutex<HashMap<Uuid, f32>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static SESSION_REASONING: Lazy<Mutex<HashMap<Uuid, ReasoningEffort>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Override the temperature for every request in `session_id`; `None`
+/// returns to the configured value.
+pub fn set_session_temperature(session_id: Uuid, temperature: Option<f32>) {
+    let mut overrides = SESSION_TEMPERATURE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match temperature {
+        Some(t) => {
+            overrides.insert(session_id, t);
+        }
+        None => {
+            overrides.remove(&session_id);
+        }
+    }
+}
+
+/// The `/temp` override for `session_id`, if any.
+pub fn session_temperature(session_id: Uuid) -> Option<f32> {
+    SESSION_TEMPERATURE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&session_id)
+        .copied()
+}
+
+/// Override the reasoning effort for every request in `session_id`; `None`
+/// returns to the configured value.
+pub fn set_session_reasoning_effort(session_id: Uuid, effort: Option<ReasoningEffort>) {
+    let mut overrides = SESSION_REASONING.lock().unwrap_or_else(|e| e.into_inner());
+    match effort {
+        Some(e) => {
+            overrides.insert(session_id, e);
+        }
+        None => {
+            overrides.remove(&session_id);
+        }
+    }
+}
+
+/// The `/think` override for `session_id`, if any.
+pub fn session_reasoning_effort(session_id: Uuid) -> Option<ReasoningEffort> {
+    SESSION_REASONING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&session_id)
+        .copied()
+}
+
+impl AgentService {
+    /// Configured sampling for `model`: its `[models]` block over `[sampling]`.
+    pub fn sampling_for(&self, model: &str) -> SamplingConfig {
+        SamplingConfig::for_model(self.sampling, &self.model_sampling, model)
+    }
+
+    /// Fill in the configured sampling parameters. A temperature or reasoning
+    /// effort already on the request wins, then the session override, then
+    /// config; anything left unset falls through to the provider's default.
+    pub(super) fn apply_sampling(&self, session_id: Uuid, mut request: LLMRequest) -> LLMRequest {
+        let sampling = self.sampling_for(&request.model);
+        if request.temperature.is_none() {
+            request.temperature = session_temperature(session_id).or(sampling.temperature);
+        }
+        if let Some(top_p) = sampling.top_p {
+            request.top_p = Some(top_p);
+        }
+        if let Some(max_tokens) = sampling.max_tokens {
+            request.max_tokens = Some(max_tokens);
+        }
+        if request.reasoning_effort.is_none() {
+            request.reasoning_effort =
+                session_reasoning_effort(session_id).or(sampling.reasoning_effort);
+        }
+        request
+    }
+}
@@ -18,7 +18,7 @@ async fn test_auto_approve_skips_callback() {
 
     let approval_cb: ApprovalCallback = Arc::new(move |_info| {
         callback_called_clone.store(true, Ordering::SeqCst);
-        Box::pin(async move { Ok((true, false)) })
+        Box::pin(async move { Ok(ApprovalDecision::Approve) })
     });
 
     let agent_service = AgentService::new(provider, context.clone())
@@ -64,7 +64,7 @@ async fn test_approval_required_calls_callback() {
 
     let approval_cb: ApprovalCallback = Arc::new(move |_info| {
         callback_called_clone.store(true, Ordering::SeqCst);
-        Box::pin(async move { Ok((true, false)) }) // approve
+        Box::pin(async move { Ok(ApprovalDecision::Approve) }) // approve
     });
 
     let agent_service = AgentService::new(provider, context.clone())
@@ -104,7 +104,7 @@ async fn test_approval_denied_sends_error_result() {
 
     // Always deny
     let approval_cb: ApprovalCallback =
-        Arc::new(move |_info| Box::pin(async move { Ok((false, false)) }));
+        Arc::new(move |_info| Box::pin(async move { Ok(ApprovalDecision::Deny) }));
 
     let agent_service = AgentService::new(provider, context.clone())
         .with_tool_registry(Arc::new(registry))
@@ -129,6 +129,44 @@ async fn test_approval_denied_sends_error_result() {
     );
 }
 
+#[tokio::test]
+async fn test_approval_deny_and_stop_ends_turn() {
+    // Callback answers DenyAndStop → tool denied and no follow-up LLM call
+    let db = Database::connect_in_memory().await.unwrap();
+    db.run_migrations().await.unwrap();
+    let pool = db.pool().clone();
+    let context = ServiceContext::new(pool);
+
+    let provider = Arc::new(MockProviderWithNamedTool::new("approval_tool"));
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(MockToolRequiresApproval));
+
+    let approval_cb: ApprovalCallback =
+        Arc::new(move |_info| Box::pin(async move { Ok(ApprovalDecision::DenyAndStop) }));
+
+    let agent_service = AgentService::new(provider.clone(), context.clone())
+        .with_tool_registry(Arc::new(registry))
+        .with_auto_approve_tools(false)
+        .with_approval_callback(Some(approval_cb));
+
+    let session_service = SessionService::new(context);
+    let session = session_service
+        .create_session(Some("Deny And Stop Test".to_string()))
+        .await
+        .unwrap();
+
+    let result = agent_service
+        .send_message_with_tools(session.id, "Use the approval tool".to_string(), None)
+        .await;
+
+    assert!(result.is_ok(), "a stopped turn is not an error");
+    assert_eq!(
+        *provider.call_count.lock().unwrap(),
+        1,
+        "the model should not be called again after deny-and-stop"
+    );
+}
+
 #[tokio::test]
 async fn test_approval_callback_receives_session_id() {
     // ToolApprovalInfo.session_id matches the session being processed
@@ -156,7 +194,7 @@ async fn test_approval_callback_receives_session_id() {
         let captured = Arc::clone(&captured_clone);
         Box::pin(async move {
             *captured.lock().await = Some(info.session_id);
-            Ok((true, false))
+            Ok(ApprovalDecision::Approve)
         })
     });
 
@@ -229,7 +267,7 @@ async fn test_non_approval_tool_executes_directly() {
 
     let approval_cb: ApprovalCallback = Arc::new(move |_info| {
         callback_called_clone.store(true, Ordering::SeqCst);
-        Box::pin(async move { Ok((true, false)) })
+        Box::pin(async move { Ok(ApprovalDecision::Approve) })
     });
 
     let agent_service = AgentService::new(provider, context.clone())
@@ -281,7 +319,7 @@ async fn test_mixed_tools_approval_and_auto() {
 
     let approval_cb: ApprovalCallback = Arc::new(move |_info| {
         approval_count_clone.fetch_add(1, Ordering::SeqCst);
-        Box::pin(async move { Ok((true, false)) }) // approve
+        Box::pin(async move { Ok(ApprovalDecision::Approve) }) // approve
     });
 
     let agent_service = AgentService::new(provider, context.clone())
@@ -312,3 +350,142 @@ async fn test_mixed_tools_approval_and_auto() {
         "exactly one approval request should be made (for approval_tool only)"
     );
 }
+
+/// Tool that needs approval by default but only reads (files + network)
+struct MockReadOnlyTool;
+
+#[async_trait]
+impl crate::brain::tools::Tool for MockReadOnlyTool {
+    fn name(&self) -> &str {
+        "read_only_tool"
+    }
+
+    fn description(&self) -> &str {
+        "A read-only tool that requires approval"
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        serde_json::json!({"type": "object", "properties": {}})
+    }
+
+    fn capabilities(&self) -> Vec<crate::brain::tools::ToolCapability> {
+        vec![
+            crate::brain::tools::ToolCapability::ReadFiles,
+            crate::brain::tools::ToolCapability::Network,
+        ]
+    }
+
+    fn requires_approval(&self) -> bool {
+        true
+    }
+
+    async fn execute(
+        &self,
+        _input: serde_json::Value,
+        _context: &crate::brain::tools::ToolExecutionContext,
+    ) -> crate::brain::tools::Result<crate::brain::tools::ToolResult> {
+        Ok(crate::brain::tools::ToolResult::success(
+            "Read-only tool executed".to_string(),
+        ))
+    }
+}
+
+/// Run both tools in one turn and return the names the callback was asked about
+async fn approvals_requested(registry: ToolRegistry, policy: AutoApprovePolicy) -> Vec<String> {
+    approvals_requested_with(registry, policy, false).await
+}
+
+/// Like [`approvals_requested`], with `auto_approve_tools` set as given
+async fn approvals_requested_with(
+    registry: ToolRegistry,
+    policy: AutoApprovePolicy,
+    auto_approve_tools: bool,
+) -> Vec<String> {
+    let db = Database::connect_in_memory().await.unwrap();
+    db.run_migrations().await.unwrap();
+    let context = ServiceContext::new(db.pool().clone());
+
+    let requested = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let requested_clone = Arc::clone(&requested);
+    let approval_cb: ApprovalCallback = Arc::new(move |info| {
+        requested_clone.lock().unwrap().push(info.tool_name.clone());
+        Box::pin(async move { Ok(ApprovalDecision::Approve) })
+    });
+
+    let provider = Arc::new(MockProviderWithTwoToolCalls::new(
+        "read_only_tool",
+        "approval_tool",
+    ));
+    let agent_service = AgentService::new(provider, context.clone())
+        .with_tool_registry(Arc::new(registry))
+        .with_auto_approve_tools(auto_approve_tools)
+        .with_auto_approve_policy(policy)
+        .with_approval_callback(Some(approval_cb));
+
+    let session = SessionService::new(context)
+        .create_session(Some("Capability Policy Test".to_string()))
+        .await
+        .unwrap();
+    agent_service
+        .send_message_with_tools(session.id, "Use both tools".to_string(), None)
+        .await
+        .unwrap();
+
+    requested.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_capability_policy_auto_approves_read_only_tool() {
+    // ReadFiles + Network allowed: the read-only tool runs without asking,
+    // the shell tool (ExecuteShell) still prompts
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(MockReadOnlyTool));
+    registry.register(Arc::new(MockToolRequiresApproval));
+
+    let policy = AutoApprovePolicy::new([
+        crate::brain::tools::ToolCapability::ReadFiles,
+        crate::brain::tools::ToolCapability::Network,
+    ]);
+    assert_eq!(
+        approvals_requested(registry, policy).await,
+        vec!["approval_tool".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_capability_policy_respects_forced_approval() {
+    // `[tools] require_approval` wins over the capability policy
+    let tools_config = crate::config::ToolsConfig {
+        require_approval: vec!["read_only_tool".to_string()],
+        ..Default::default()
+    };
+    let mut registry = ToolRegistry::with_policy(&tools_config);
+    registry.register(Arc::new(MockReadOnlyTool));
+    registry.register(Arc::new(MockToolRequiresApproval));
+
+    let policy = AutoApprovePolicy::new([
+        crate::brain::tools::ToolCapability::ReadFiles,
+        crate::brain::tools::ToolCapability::Network,
+    ]);
+    assert_eq!(
+        approvals_requested(registry, policy).await,
+        vec!["read_only_tool".to_string(), "approval_tool".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_forced_approval_wins_over_auto_approve_tools() {
+    // `[tools] require_approval` still asks when every tool is auto-approved
+    let tools_config = crate::config::ToolsConfig {
+        require_approval: vec!["approval_tool".to_string()],
+        ..Default::default()
+    };
+    let mut registry = ToolRegistry::with_policy(&tools_config);
+    registry.register(Arc::new(MockReadOnlyTool));
+    registry.register(Arc::new(MockToolRequiresApproval));
+
+    assert_eq!(
+        approvals_requested_with(registry, AutoApprovePolicy::default(), true).await,
+        vec!["approval_tool".to_string()]
+    );
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+async fn run_turn(provider: Arc<MockProvider>, max_continues: u32) -> AgentResponse {
+    let (agent_service, session_id) = create_test_service_with_provider(provider).await;
+    agent_service
+        .with_tool_registry(Arc::new(ToolRegistry::new()))
+        .with_auto_continue(max_continues)
+        .send_message_with_tools(session_id, "Say hello world".to_string(), None)
+        .await
+        .unwrap()
+}
+
+fn truncated(text: &str) -> Reply {
+    Reply::Response(text_response(text, StopReason::MaxTokens))
+}
+
+fn finished(text: &str) -> Reply {
+    Reply::Response(text_response(text, StopReason::EndTurn))
+}
+
+#[tokio::test]
+async fn test_truncated_reply_is_continued() {
+    let provider = Arc::new(MockProvider::scripted(vec![
+        truncated("Hello wor"),
+        finished("ld"),
+    ]));
+
+    let response = run_turn(provider.clone(), 3).await;
+
+    assert_eq!(response.content, "Hello world");
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+
+    // The continuation request carries the partial reply and a continue prompt
+    let history = &requests[1].messages;
+    let partial = &history[history.len() - 2];
+    assert_eq!(partial.role, Role::Assistant);
+    assert!(matches!(&partial.content[0], ContentBlock::Text { text } if text == "Hello wor"));
+    let prompt = history.last().unwrap();
+    assert_eq!(prompt.role, Role::User);
+    assert!(matches!(&prompt.content[0], ContentBlock::Text { text } if text.contains("cut off")));
+}
+
+#[tokio::test]
+async fn test_auto_continue_is_capped() {
+    // Always truncated: stop after the configured number of continuations
+    let provider = Arc::new(MockProvider::scripted(vec![truncated("more ")]));
+
+    let response = run_turn(provider.clone(), 2).await;
+
+    assert_eq!(provider.requests().len(), 3);
+    assert_eq!(response.content, "more more more ");
+}
+
+#[tokio::test]
+async fn test_auto_continue_disabled() {
+    let provider = Arc::new(MockProvider::scripted(vec![
+        truncated("Hello wor"),
+        finished("ld"),
+    ]));
+
+    let response = run_turn(provider.clone(), 0).await;
+
+    assert_eq!(response.content, "Hello wor");
+    assert_eq!(provider.requests().len(), 1);
+}
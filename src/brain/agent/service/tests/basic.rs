@@ -255,7 +255,7 @@ async fn test_stream_complete_with_tool_use() {
 async fn test_streaming_chunks_emitted() {
     use std::sync::Mutex;
 
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let db = Database::connect_in_memory().await.unwrap();
     db.run_migrations().await.unwrap();
     let context = ServiceContext::new(db.pool().clone());
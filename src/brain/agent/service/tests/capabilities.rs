@@ -0,0 +1,62 @@
+use super::*;
+
+#[tokio::test]
+async fn test_text_only_provider_gets_gated_request() {
+    // No streaming, no tools, no vision: `stream` must never be called
+    let provider = Arc::new(MockProvider {
+        replies: vec![Reply::Response(text_response(
+            "I can only read text.",
+            StopReason::EndTurn,
+        ))],
+        text_only: true,
+        ..Default::default()
+    });
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(MockTool));
+
+    let events: Arc<std::sync::Mutex<Vec<ProgressEvent>>> = Arc::default();
+    let sink = events.clone();
+    let callback: ProgressCallback = Arc::new(move |_, event| sink.lock().unwrap().push(event));
+
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+    let service = agent_service
+        .with_tool_registry(Arc::new(registry))
+        .with_progress_callback(Some(callback));
+
+    let response = service
+        .send_message_with_tools(
+            session_id,
+            "What is in this picture? <<IMG:/tmp/cat.png>>".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.content, "I can only read text.");
+
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].tools.is_none());
+    let user = requests[0]
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == Role::User)
+        .unwrap();
+    assert_eq!(user.content.len(), 1);
+    let ContentBlock::Text { text } = &user.content[0] else {
+        panic!("expected a text block");
+    };
+    assert!(!text.contains("<<IMG:"));
+    assert!(text.contains("1 image(s) omitted"));
+
+    let events = events.lock().unwrap();
+    let warnings = events
+        .iter()
+        .filter(|e| matches!(e, ProgressEvent::CapabilityWarning { .. }))
+        .count();
+    assert_eq!(warnings, 2);
+    // The complete() reply is still forwarded as a streaming chunk
+    assert!(events.iter().any(
+        |e| matches!(e, ProgressEvent::StreamingChunk { text } if text == "I can only read text.")
+    ));
+}
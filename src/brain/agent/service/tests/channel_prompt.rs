@@ -0,0 +1,37 @@
+use super::*;
+use crate::channels::MessageSource;
+use crate::config::ChannelsConfig;
+
+#[tokio::test]
+async fn test_channel_system_append_only_for_that_channel() {
+    let provider = Arc::new(MockProvider::default());
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+    let context = agent_service.context().clone();
+
+    let mut channels = ChannelsConfig::default();
+    channels.discord.system_append = Some("Keep replies under 2000 characters.".to_string());
+    let channels = Arc::new(channels);
+
+    let build = |source: MessageSource| {
+        let channels = channels.clone();
+        AgentService::new(provider.clone(), context.clone())
+            .with_system_brain("You are a test agent.".to_string())
+            .with_tools_md(false)
+            .with_system_append(Arc::new(move || source.system_append(&channels)))
+    };
+
+    build(MessageSource::Discord)
+        .send_message(session_id, "Hello".to_string(), None)
+        .await
+        .unwrap();
+    build(MessageSource::Tui)
+        .send_message(session_id, "Again".to_string(), None)
+        .await
+        .unwrap();
+
+    let seen: Vec<_> = provider.requests().into_iter().map(|r| r.system).collect();
+    let discord = seen[0].as_deref().unwrap();
+    assert!(discord.starts_with("You are a test agent."));
+    assert!(discord.ends_with("Keep replies under 2000 characters."));
+    assert_eq!(seen[1].as_deref(), Some("You are a test agent."));
+}
@@ -81,7 +81,7 @@ fn test_large_system_brain_counted_accurately() {
 
 #[tokio::test]
 async fn test_context_includes_brain_after_db_rebuild() {
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let db = Database::connect_in_memory().await.unwrap();
     db.run_migrations().await.unwrap();
     let context = ServiceContext::new(db.pool().clone());
@@ -115,7 +115,7 @@ async fn test_context_includes_brain_after_db_rebuild() {
 
 #[tokio::test]
 async fn test_context_does_not_drop_between_requests() {
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let db = Database::connect_in_memory().await.unwrap();
     db.run_migrations().await.unwrap();
     let context = ServiceContext::new(db.pool().clone());
@@ -194,7 +194,7 @@ fn test_base_context_tokens_uses_real_tool_schemas() {
     // The one with tools should have higher base_context_tokens
     let rt = tokio::runtime::Runtime::new().unwrap();
     rt.block_on(async {
-        let provider = Arc::new(MockProvider);
+        let provider = Arc::new(MockProvider::default());
         let db = Database::connect_in_memory().await.unwrap();
         db.run_migrations().await.unwrap();
         let context = ServiceContext::new(db.pool().clone());
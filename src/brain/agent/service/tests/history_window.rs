@@ -0,0 +1,67 @@
+use super::*;
+use crate::brain::agent::context::AgentContext;
+
+#[tokio::test]
+async fn test_oversized_history_is_trimmed_to_model_window() {
+    let provider = Arc::new(MockProvider::default());
+    let (service, session_id) = create_test_service_with_provider(provider.clone()).await;
+
+    // ~20k tokens of history against a 4096-token model
+    let message_service = MessageService::new(service.context().clone());
+    for i in 0..50 {
+        let (role, text) = if i % 2 == 0 {
+            ("user", format!("question {} {}", i, "word ".repeat(400)))
+        } else {
+            ("assistant", format!("answer {} {}", i, "word ".repeat(400)))
+        };
+        message_service
+            .create_message(session_id, role.to_string(), text)
+            .await
+            .unwrap();
+    }
+
+    let events: Arc<std::sync::Mutex<Vec<ProgressEvent>>> = Arc::default();
+    let sink = events.clone();
+    let callback: ProgressCallback = Arc::new(move |_, event| sink.lock().unwrap().push(event));
+
+    service
+        .send_message_with_tools_and_callback(
+            session_id,
+            "latest question".to_string(),
+            None,
+            None,
+            None,
+            Some(callback),
+        )
+        .await
+        .unwrap();
+
+    let requests = provider.requests();
+    let sent = &requests[0].messages;
+    let sent_tokens: usize = sent.iter().map(AgentContext::estimate_tokens_static).sum();
+    assert!(
+        sent_tokens <= 4096,
+        "request should fit the window (got {} tokens)",
+        sent_tokens
+    );
+    assert!(sent.len() < 51, "oldest turns should be dropped");
+    assert!(matches!(
+        &sent.last().unwrap().content[0],
+        ContentBlock::Text { text } if text.contains("latest question")
+    ));
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        events
+            .iter()
+            .filter(|e| matches!(e, ProgressEvent::HistoryTrimmed { dropped } if *dropped > 0))
+            .count(),
+        1
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::IntermediateText { .. })),
+        "the trim note must not reach the chat as reply text"
+    );
+}
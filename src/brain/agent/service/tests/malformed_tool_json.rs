@@ -0,0 +1,167 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// One reply per `raw_inputs` entry: a `test_tool` call whose input streams
+/// as that raw JSON, or for `None` a plain text reply that ends the turn
+fn script(raw_inputs: &[Option<&str>]) -> MockProvider {
+    let replies = raw_inputs
+        .iter()
+        .enumerate()
+        .map(|(call, raw)| match raw {
+            Some(raw) => {
+                let mut events = response_events(&LLMResponse {
+                    content: vec![ContentBlock::ToolUse {
+                        id: format!("tool-{}", call),
+                        name: "test_tool".to_string(),
+                        input: serde_json::json!({}),
+                    }],
+                    ..text_response("", StopReason::ToolUse)
+                });
+                for event in &mut events {
+                    if let StreamEvent::ContentBlockDelta {
+                        delta: ContentDelta::InputJsonDelta { partial_json },
+                        ..
+                    } = event
+                    {
+                        *partial_json = raw.to_string();
+                    }
+                }
+                Reply::Events(events)
+            }
+            None => Reply::Response(text_response("Done.", StopReason::EndTurn)),
+        })
+        .collect();
+    MockProvider::scripted(replies)
+}
+
+/// Tool result contents in the history of the latest request
+fn tool_results(provider: &MockProvider) -> Vec<String> {
+    let Some(last) = provider.requests().pop() else {
+        return vec![];
+    };
+    last.messages
+        .iter()
+        .flat_map(|m| m.content.iter())
+        .filter_map(|b| match b {
+            ContentBlock::ToolResult { content, .. } => Some(content.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Mock tool that counts how many times it actually ran
+struct CountingTool {
+    runs: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl crate::brain::tools::Tool for CountingTool {
+    fn name(&self) -> &str {
+        "test_tool"
+    }
+
+    fn description(&self) -> &str {
+        "A test tool"
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {"type": "string"}
+            }
+        })
+    }
+
+    fn capabilities(&self) -> Vec<crate::brain::tools::ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(
+        &self,
+        _input: serde_json::Value,
+        _context: &crate::brain::tools::ToolExecutionContext,
+    ) -> crate::brain::tools::Result<crate::brain::tools::ToolResult> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(crate::brain::tools::ToolResult::success(
+            "Tool executed successfully".to_string(),
+        ))
+    }
+}
+
+async fn run_turn(provider: Arc<MockProvider>, runs: Arc<AtomicUsize>) -> AgentResponse {
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(CountingTool { runs }));
+
+    let (agent_service, session_id) = create_test_service_with_provider(provider).await;
+    agent_service
+        .with_tool_registry(Arc::new(registry))
+        .with_auto_approve_tools(true)
+        .send_message_with_tools(session_id, "Use the test tool".to_string(), None)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_malformed_tool_json_is_corrected_then_executed() {
+    let provider = Arc::new(script(&[
+        Some(r#"{"message": "test","#),
+        Some(r#"{"message": "test"}"#),
+        None,
+    ]));
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let response = run_turn(provider.clone(), runs.clone()).await;
+
+    assert!(!response.content.is_empty());
+    assert_eq!(
+        runs.load(Ordering::SeqCst),
+        1,
+        "only the valid tool call should execute"
+    );
+    let results = tool_results(&provider);
+    assert_eq!(results.len(), 2);
+    assert!(results[0].starts_with("your tool input was not valid JSON:"));
+    assert!(results[0].ends_with("please resend"));
+    assert_eq!(results[1], "Tool executed successfully");
+
+    // The malformed-input marker is never replayed as the model's own input
+    let requests = provider.requests();
+    let replayed_marker = requests
+        .iter()
+        .flat_map(|r| r.messages.iter())
+        .flat_map(|m| m.content.iter())
+        .any(|b| {
+            matches!(b, ContentBlock::ToolUse { input, .. }
+                if crate::brain::provider::malformed_tool_input_error(input).is_some())
+        });
+    assert!(!replayed_marker);
+}
+
+#[tokio::test]
+async fn test_malformed_tool_json_retries_are_capped() {
+    // Model never fixes its JSON — the loop must stop after 2 corrective retries
+    let provider = Arc::new(script(&[
+        Some(r#"{"message": "#),
+        Some(r#"{"message": "#),
+        Some(r#"{"message": "#),
+        Some(r#"{"message": "#),
+        None,
+    ]));
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let response = run_turn(provider.clone(), runs.clone()).await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+    // The unanswered tool calls are dropped from the final reply
+    assert_eq!(response.stop_reason, Some(StopReason::EndTurn));
+    assert_eq!(
+        provider.requests().len(),
+        3,
+        "initial call + 2 corrective retries, then stop"
+    );
+}
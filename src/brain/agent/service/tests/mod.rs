@@ -1,14 +1,29 @@
 mod approval_policies;
+mod auto_continue;
 mod basic;
+mod capabilities;
+mod channel_prompt;
 mod context_tracking;
+mod history_window;
+mod malformed_tool_json;
 mod model_selection;
 mod parallel_sessions;
+mod prompt_wrap;
+mod sampling;
+mod session_limits;
+mod session_summary;
+mod session_updated;
+mod split_tool_json;
+mod stream_resume;
 mod streaming_usage;
+mod tool_metrics;
+mod tools_md;
+mod variants;
 
 use super::*;
 use crate::brain::provider::{
-    ContentBlock, LLMRequest, LLMResponse, Message, Provider, ProviderStream, Role, StopReason,
-    TokenUsage,
+    ContentBlock, ContentDelta, LLMRequest, LLMResponse, Message, MessageDelta, Provider,
+    ProviderError, ProviderStream, Role, StopReason, StreamEvent, StreamMessage, TokenUsage,
 };
 use crate::brain::tools::ToolRegistry;
 use crate::db::Database;
@@ -17,64 +32,91 @@ use async_trait::async_trait;
 use std::sync::Arc;
 use uuid::Uuid;
 
-/// Mock provider for testing — returns simple text-only responses
-struct MockProvider;
+/// One scripted [`MockProvider`] reply
+#[derive(Clone)]
+enum Reply {
+    /// A full response, streamed block by block
+    Response(LLMResponse),
+    /// Raw stream events replayed as-is — for split, malformed or cut-off streams
+    Events(Vec<StreamEvent>),
+    /// Raw stream events, then a transport error
+    EventsThenError(Vec<StreamEvent>),
+}
+
+/// Mock provider for testing — answers with its scripted replies in order
+/// (repeating the last), or a simple text-only response when unscripted, and
+/// records every request it receives
+#[derive(Default)]
+struct MockProvider {
+    replies: Vec<Reply>,
+    /// No streaming and no tools, like a bare completion endpoint
+    text_only: bool,
+    requests: std::sync::Mutex<Vec<LLMRequest>>,
+}
+
+impl MockProvider {
+    fn scripted(replies: Vec<Reply>) -> Self {
+        Self {
+            replies,
+            ..Default::default()
+        }
+    }
+
+    /// Requests received so far, oldest first
+    fn requests(&self) -> Vec<LLMRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Record `request` and pick the reply for this call
+    fn next_reply(&self, request: LLMRequest) -> Reply {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push(request);
+        match self.replies.len() {
+            0 => Reply::Response(text_response(
+                "This is a test response",
+                StopReason::EndTurn,
+            )),
+            n => self.replies[(requests.len() - 1).min(n - 1)].clone(),
+        }
+    }
+}
 
 #[async_trait]
 impl Provider for MockProvider {
-    async fn complete(&self, _request: LLMRequest) -> crate::brain::provider::Result<LLMResponse> {
-        Ok(LLMResponse {
-            id: "test-response-1".to_string(),
-            model: "mock-model".to_string(),
-            content: vec![ContentBlock::Text {
-                text: "This is a test response".to_string(),
-            }],
-            stop_reason: Some(StopReason::EndTurn),
-            usage: TokenUsage {
-                input_tokens: 10,
-                output_tokens: 20,
-            },
-        })
+    async fn complete(&self, request: LLMRequest) -> crate::brain::provider::Result<LLMResponse> {
+        match self.next_reply(request) {
+            Reply::Response(response) => Ok(response),
+            Reply::Events(_) | Reply::EventsThenError(_) => Err(ProviderError::StreamError(
+                "reply is scripted as a raw stream".to_string(),
+            )),
+        }
     }
 
     async fn stream(&self, request: LLMRequest) -> crate::brain::provider::Result<ProviderStream> {
-        use crate::brain::provider::{ContentDelta, MessageDelta, StreamEvent, StreamMessage};
-
-        let response = self.complete(request).await?;
-        let mut events = vec![Ok(StreamEvent::MessageStart {
-            message: StreamMessage {
-                id: response.id.clone(),
-                model: response.model.clone(),
-                role: Role::Assistant,
-                usage: response.usage,
-            },
-        })];
-        for (i, block) in response.content.iter().enumerate() {
-            if let ContentBlock::Text { text } = block {
-                events.push(Ok(StreamEvent::ContentBlockStart {
-                    index: i,
-                    content_block: ContentBlock::Text {
-                        text: String::new(),
-                    },
-                }));
-                events.push(Ok(StreamEvent::ContentBlockDelta {
-                    index: i,
-                    delta: ContentDelta::TextDelta { text: text.clone() },
-                }));
-                events.push(Ok(StreamEvent::ContentBlockStop { index: i }));
-            }
-        }
-        events.push(Ok(StreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: response.stop_reason,
-                stop_sequence: None,
-            },
-            usage: response.usage,
-        }));
-        events.push(Ok(StreamEvent::MessageStop));
+        assert!(
+            !self.text_only,
+            "stream called on a provider without streaming support"
+        );
+        let events: Vec<_> = match self.next_reply(request) {
+            Reply::Response(response) => response_events(&response).into_iter().map(Ok).collect(),
+            Reply::Events(events) => events.into_iter().map(Ok).collect(),
+            Reply::EventsThenError(events) => events
+                .into_iter()
+                .map(Ok)
+                .chain([Err(ProviderError::Timeout(30))])
+                .collect(),
+        };
         Ok(Box::pin(futures::stream::iter(events)))
     }
 
+    fn supports_streaming(&self) -> bool {
+        !self.text_only
+    }
+
+    fn supports_tools(&self) -> bool {
+        !self.text_only
+    }
+
     fn name(&self) -> &str {
         "mock"
     }
@@ -134,6 +176,7 @@ impl Provider for MockProviderWithTools {
                 usage: TokenUsage {
                     input_tokens: 10,
                     output_tokens: 20,
+                    ..Default::default()
                 },
             })
         } else {
@@ -147,74 +190,14 @@ impl Provider for MockProviderWithTools {
                 usage: TokenUsage {
                     input_tokens: 15,
                     output_tokens: 25,
+                    ..Default::default()
                 },
             })
         }
     }
 
     async fn stream(&self, request: LLMRequest) -> crate::brain::provider::Result<ProviderStream> {
-        use crate::brain::provider::{ContentDelta, MessageDelta, StreamEvent, StreamMessage};
-
-        let response = self.complete(request).await?;
-        let mut events = vec![Ok(StreamEvent::MessageStart {
-            message: StreamMessage {
-                id: response.id.clone(),
-                model: response.model.clone(),
-                role: Role::Assistant,
-                usage: response.usage,
-            },
-        })];
-
-        for (i, block) in response.content.iter().enumerate() {
-            match block {
-                ContentBlock::Text { text } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::Text {
-                            text: String::new(),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::TextDelta { text: text.clone() },
-                    }));
-                }
-                ContentBlock::ToolUse { id, name, input } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::ToolUse {
-                            id: id.clone(),
-                            name: name.clone(),
-                            input: serde_json::Value::Object(Default::default()),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::InputJsonDelta {
-                            partial_json: serde_json::to_string(input).unwrap_or_default(),
-                        },
-                    }));
-                }
-                _ => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: block.clone(),
-                    }));
-                }
-            }
-            events.push(Ok(StreamEvent::ContentBlockStop { index: i }));
-        }
-
-        events.push(Ok(StreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: response.stop_reason,
-                stop_sequence: None,
-            },
-            usage: response.usage,
-        }));
-        events.push(Ok(StreamEvent::MessageStop));
-
-        Ok(Box::pin(futures::stream::iter(events)))
+        self.complete(request).await.map(stream_response)
     }
 
     fn name(&self) -> &str {
@@ -355,46 +338,13 @@ impl Provider for MockProviderWithModel {
             usage: TokenUsage {
                 input_tokens: 10,
                 output_tokens: 20,
+                ..Default::default()
             },
         })
     }
 
     async fn stream(&self, request: LLMRequest) -> crate::brain::provider::Result<ProviderStream> {
-        use crate::brain::provider::{ContentDelta, MessageDelta, StreamEvent, StreamMessage};
-
-        let response = self.complete(request).await?;
-        let mut events = vec![Ok(StreamEvent::MessageStart {
-            message: StreamMessage {
-                id: response.id.clone(),
-                model: response.model.clone(),
-                role: Role::Assistant,
-                usage: response.usage,
-            },
-        })];
-        for (i, block) in response.content.iter().enumerate() {
-            if let ContentBlock::Text { text } = block {
-                events.push(Ok(StreamEvent::ContentBlockStart {
-                    index: i,
-                    content_block: ContentBlock::Text {
-                        text: String::new(),
-                    },
-                }));
-                events.push(Ok(StreamEvent::ContentBlockDelta {
-                    index: i,
-                    delta: ContentDelta::TextDelta { text: text.clone() },
-                }));
-                events.push(Ok(StreamEvent::ContentBlockStop { index: i }));
-            }
-        }
-        events.push(Ok(StreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: response.stop_reason,
-                stop_sequence: None,
-            },
-            usage: response.usage,
-        }));
-        events.push(Ok(StreamEvent::MessageStop));
-        Ok(Box::pin(futures::stream::iter(events)))
+        self.complete(request).await.map(stream_response)
     }
 
     fn name(&self) -> &str {
@@ -458,6 +408,7 @@ impl Provider for MockProviderWithNamedTool {
                 usage: TokenUsage {
                     input_tokens: 10,
                     output_tokens: 20,
+                    ..Default::default()
                 },
             })
         } else {
@@ -471,71 +422,14 @@ impl Provider for MockProviderWithNamedTool {
                 usage: TokenUsage {
                     input_tokens: 15,
                     output_tokens: 25,
+                    ..Default::default()
                 },
             })
         }
     }
 
     async fn stream(&self, request: LLMRequest) -> crate::brain::provider::Result<ProviderStream> {
-        use crate::brain::provider::{ContentDelta, MessageDelta, StreamEvent, StreamMessage};
-
-        let response = self.complete(request).await?;
-        let mut events = vec![Ok(StreamEvent::MessageStart {
-            message: StreamMessage {
-                id: response.id.clone(),
-                model: response.model.clone(),
-                role: Role::Assistant,
-                usage: response.usage,
-            },
-        })];
-        for (i, block) in response.content.iter().enumerate() {
-            match block {
-                ContentBlock::Text { text } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::Text {
-                            text: String::new(),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::TextDelta { text: text.clone() },
-                    }));
-                }
-                ContentBlock::ToolUse { id, name, input } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::ToolUse {
-                            id: id.clone(),
-                            name: name.clone(),
-                            input: serde_json::Value::Object(Default::default()),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::InputJsonDelta {
-                            partial_json: serde_json::to_string(input).unwrap_or_default(),
-                        },
-                    }));
-                }
-                _ => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: block.clone(),
-                    }));
-                }
-            }
-            events.push(Ok(StreamEvent::ContentBlockStop { index: i }));
-        }
-        events.push(Ok(StreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: response.stop_reason,
-                stop_sequence: None,
-            },
-            usage: response.usage,
-        }));
-        events.push(Ok(StreamEvent::MessageStop));
-        Ok(Box::pin(futures::stream::iter(events)))
+        self.complete(request).await.map(stream_response)
     }
 
     fn name(&self) -> &str {
@@ -606,6 +500,7 @@ impl Provider for MockProviderWithTwoToolCalls {
                 usage: TokenUsage {
                     input_tokens: 10,
                     output_tokens: 20,
+                    ..Default::default()
                 },
             })
         } else {
@@ -619,71 +514,14 @@ impl Provider for MockProviderWithTwoToolCalls {
                 usage: TokenUsage {
                     input_tokens: 15,
                     output_tokens: 25,
+                    ..Default::default()
                 },
             })
         }
     }
 
     async fn stream(&self, request: LLMRequest) -> crate::brain::provider::Result<ProviderStream> {
-        use crate::brain::provider::{ContentDelta, MessageDelta, StreamEvent, StreamMessage};
-
-        let response = self.complete(request).await?;
-        let mut events = vec![Ok(StreamEvent::MessageStart {
-            message: StreamMessage {
-                id: response.id.clone(),
-                model: response.model.clone(),
-                role: Role::Assistant,
-                usage: response.usage,
-            },
-        })];
-        for (i, block) in response.content.iter().enumerate() {
-            match block {
-                ContentBlock::Text { text } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::Text {
-                            text: String::new(),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::TextDelta { text: text.clone() },
-                    }));
-                }
-                ContentBlock::ToolUse { id, name, input } => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: ContentBlock::ToolUse {
-                            id: id.clone(),
-                            name: name.clone(),
-                            input: serde_json::Value::Object(Default::default()),
-                        },
-                    }));
-                    events.push(Ok(StreamEvent::ContentBlockDelta {
-                        index: i,
-                        delta: ContentDelta::InputJsonDelta {
-                            partial_json: serde_json::to_string(input).unwrap_or_default(),
-                        },
-                    }));
-                }
-                _ => {
-                    events.push(Ok(StreamEvent::ContentBlockStart {
-                        index: i,
-                        content_block: block.clone(),
-                    }));
-                }
-            }
-            events.push(Ok(StreamEvent::ContentBlockStop { index: i }));
-        }
-        events.push(Ok(StreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: response.stop_reason,
-                stop_sequence: None,
-            },
-            usage: response.usage,
-        }));
-        events.push(Ok(StreamEvent::MessageStop));
-        Ok(Box::pin(futures::stream::iter(events)))
+        self.complete(request).await.map(stream_response)
     }
 
     fn name(&self) -> &str {
@@ -709,8 +547,91 @@ impl Provider for MockProviderWithTwoToolCalls {
 
 // === Shared helpers ===
 
+/// Text-only response ending with `stop_reason`
+fn text_response(text: &str, stop_reason: StopReason) -> LLMResponse {
+    LLMResponse {
+        id: "test-response-1".to_string(),
+        model: "mock-model".to_string(),
+        content: vec![ContentBlock::Text {
+            text: text.to_string(),
+        }],
+        stop_reason: Some(stop_reason),
+        usage: TokenUsage {
+            input_tokens: 10,
+            output_tokens: 20,
+            ..Default::default()
+        },
+    }
+}
+
+/// The stream events a provider sends for `response`, block by block
+fn response_events(response: &LLMResponse) -> Vec<StreamEvent> {
+    let mut events = vec![StreamEvent::MessageStart {
+        message: StreamMessage {
+            id: response.id.clone(),
+            model: response.model.clone(),
+            role: Role::Assistant,
+            usage: response.usage,
+        },
+    }];
+    for (i, block) in response.content.iter().enumerate() {
+        match block {
+            ContentBlock::Text { text } => {
+                events.push(StreamEvent::ContentBlockStart {
+                    index: i,
+                    content_block: ContentBlock::Text {
+                        text: String::new(),
+                    },
+                });
+                events.push(StreamEvent::ContentBlockDelta {
+                    index: i,
+                    delta: ContentDelta::TextDelta { text: text.clone() },
+                });
+            }
+            ContentBlock::ToolUse { id, name, input } => {
+                events.push(StreamEvent::ContentBlockStart {
+                    index: i,
+                    content_block: ContentBlock::ToolUse {
+                        id: id.clone(),
+                        name: name.clone(),
+                        input: serde_json::Value::Object(Default::default()),
+                    },
+                });
+                events.push(StreamEvent::ContentBlockDelta {
+                    index: i,
+                    delta: ContentDelta::InputJsonDelta {
+                        partial_json: serde_json::to_string(input).unwrap_or_default(),
+                    },
+                });
+            }
+            _ => {
+                events.push(StreamEvent::ContentBlockStart {
+                    index: i,
+                    content_block: block.clone(),
+                });
+            }
+        }
+        events.push(StreamEvent::ContentBlockStop { index: i });
+    }
+    events.push(StreamEvent::MessageDelta {
+        delta: MessageDelta {
+            stop_reason: response.stop_reason,
+            stop_sequence: None,
+        },
+        usage: response.usage,
+    });
+    events.push(StreamEvent::MessageStop);
+    events
+}
+
+/// Stream `response` the way a provider would
+fn stream_response(response: LLMResponse) -> ProviderStream {
+    let events: Vec<_> = response_events(&response).into_iter().map(Ok).collect();
+    Box::pin(futures::stream::iter(events))
+}
+
 async fn create_test_service() -> (AgentService, Uuid) {
-    create_test_service_with_provider(Arc::new(MockProvider)).await
+    create_test_service_with_provider(Arc::new(MockProvider::default())).await
 }
 
 async fn create_test_service_with_provider(provider: Arc<dyn Provider>) -> (AgentService, Uuid) {
@@ -8,7 +8,7 @@ async fn test_concurrent_sessions_independent() {
     let pool = db.pool().clone();
     let context = ServiceContext::new(pool);
 
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let agent_service = Arc::new(AgentService::new(provider, context.clone()));
 
     let session_service = SessionService::new(context);
@@ -172,7 +172,7 @@ async fn test_message_isolation_between_sessions() {
     let pool = db.pool().clone();
     let context = ServiceContext::new(pool);
 
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let agent_service = Arc::new(AgentService::new(provider, context.clone()));
 
     let session_service = SessionService::new(context.clone());
@@ -235,7 +235,7 @@ async fn test_session_usage_tracked_independently() {
     let pool = db.pool().clone();
     let context = ServiceContext::new(pool);
 
-    let provider = Arc::new(MockProvider);
+    let provider = Arc::new(MockProvider::default());
     let agent_service = Arc::new(AgentService::new(provider, context.clone()));
 
     let session_service = SessionService::new(context.clone());
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Text of the last user message in `request`
+fn last_user_text(request: &LLMRequest) -> String {
+    request
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == Role::User)
+        .and_then(|m| {
+            m.content.iter().find_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.clone()),
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_prompt_wrap_reaches_provider_not_history() {
+    let provider = Arc::new(MockProvider::default());
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+    let context = agent_service.context().clone();
+    let service = agent_service.with_prompt_wrap(
+        Some("Respond concisely.".to_string()),
+        Some("Use British English.".to_string()),
+    );
+
+    service
+        .send_message(session_id, "What colour is the sky?".to_string(), None)
+        .await
+        .unwrap();
+    service
+        .send_message_with_tools(session_id, "And at night?".to_string(), None)
+        .await
+        .unwrap();
+
+    let seen: Vec<_> = provider.requests().iter().map(last_user_text).collect();
+    assert_eq!(
+        seen[0],
+        "Respond concisely.\n\nWhat colour is the sky?\n\nUse British English."
+    );
+    assert_eq!(
+        seen[1],
+        "Respond concisely.\n\nAnd at night?\n\nUse British English."
+    );
+
+    let stored: Vec<String> = MessageService::new(context)
+        .list_messages_for_session(session_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|m| m.role == "user")
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(stored, vec!["What colour is the sky?", "And at night?"]);
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::brain::provider::ReasoningEffort;
+use crate::config::SamplingConfig;
+use std::collections::BTreeMap;
+
+/// temperature, top_p, max_tokens, reasoning_effort
+type SeenSampling = (
+    Option<f32>,
+    Option<f32>,
+    Option<u32>,
+    Option<ReasoningEffort>,
+);
+
+#[tokio::test]
+async fn test_configured_sampling_reaches_request() {
+    let provider = Arc::new(MockProvider::default());
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+    let mut models = BTreeMap::new();
+    models.insert(
+        "mock-model".to_string(),
+        SamplingConfig {
+            top_p: Some(0.8),
+            max_tokens: Some(1024),
+            reasoning_effort: Some(ReasoningEffort::Tokens(8000)),
+            ..Default::default()
+        },
+    );
+    let defaults = SamplingConfig {
+        temperature: Some(0.3),
+        top_p: Some(0.95),
+        max_tokens: None,
+        reasoning_effort: Some(ReasoningEffort::Low),
+    };
+    let agent_service = agent_service.with_sampling(defaults, models);
+
+    agent_service
+        .send_message(session_id, "Hello".to_string(), None)
+        .await
+        .unwrap();
+
+    // /temp and /think overrides beat config; top_p and max_tokens stay per-model
+    set_session_temperature(session_id, Some(1.2));
+    set_session_reasoning_effort(session_id, Some(ReasoningEffort::High));
+    agent_service
+        .send_message(session_id, "Again".to_string(), None)
+        .await
+        .unwrap();
+    set_session_temperature(session_id, None);
+    set_session_reasoning_effort(session_id, None);
+
+    let seen: Vec<SeenSampling> = provider
+        .requests()
+        .into_iter()
+        .map(|r| (r.temperature, r.top_p, r.max_tokens, r.reasoning_effort))
+        .collect();
+    assert_eq!(
+        seen,
+        vec![
+            (
+                Some(0.3),
+                Some(0.8),
+                Some(1024),
+                Some(ReasoningEffort::Tokens(8000))
+            ),
+            (
+                Some(1.2),
+                Some(0.8),
+                Some(1024),
+                Some(ReasoningEffort::High)
+            ),
+        ]
+    );
+}
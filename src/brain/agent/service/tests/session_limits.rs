@@ -0,0 +1,55 @@
+use super::*;
+use crate::brain::agent::error::AgentError;
+
+#[tokio::test]
+async fn test_session_over_token_limit_stops_until_unlock() {
+    let (agent_service, session_id) = create_test_service().await;
+    // MockProvider reports 30 tokens per turn
+    let agent_service = agent_service.with_session_limits(SessionLimits {
+        max_tokens: Some(30),
+        max_cost: None,
+    });
+
+    agent_service
+        .send_message(session_id, "first".to_string(), None)
+        .await
+        .unwrap();
+
+    let err = agent_service
+        .send_message_with_tools(session_id, "second".to_string(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AgentError::SessionLimitReached { .. }));
+    // Channel users are told exactly what to run in the TUI
+    assert!(
+        err.user_message()
+            .contains(&format!("/unlock {session_id}"))
+    );
+
+    agent_service.unlock_session(session_id).await.unwrap();
+    agent_service
+        .send_message(session_id, "after unlock".to_string(), None)
+        .await
+        .unwrap();
+
+    // The fresh allowance is used up again
+    assert!(matches!(
+        agent_service
+            .send_message(session_id, "again".to_string(), None)
+            .await,
+        Err(AgentError::SessionLimitReached { .. })
+    ));
+}
+
+#[test]
+fn test_session_limits_exceeded() {
+    let limits = SessionLimits {
+        max_tokens: Some(1000),
+        max_cost: Some(0.50),
+    };
+    assert_eq!(limits.exceeded(999, 0.49), None);
+    assert!(limits.exceeded(1000, 0.0).unwrap().contains("tokens"));
+    assert!(limits.exceeded(0, 0.50).unwrap().contains("$0.50"));
+    assert!(SessionLimits::default().is_unlimited());
+    assert_eq!(SessionLimits::default().exceeded(i64::MAX, f64::MAX), None);
+}
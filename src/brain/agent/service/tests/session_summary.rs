@@ -0,0 +1,44 @@
+use super::*;
+
+#[tokio::test]
+async fn test_summary_does_not_add_a_turn() {
+    let (agent_service, session_id) = create_test_service().await;
+    agent_service
+        .send_message(session_id, "Hello, world!".to_string(), None)
+        .await
+        .unwrap();
+
+    let message_service = MessageService::new(agent_service.context.clone());
+    let before = message_service
+        .list_messages_for_session(session_id)
+        .await
+        .unwrap()
+        .len();
+
+    let summary = agent_service
+        .summarize_session(session_id, false)
+        .await
+        .unwrap();
+    assert_eq!(summary, "This is a test response");
+
+    let after = message_service
+        .list_messages_for_session(session_id)
+        .await
+        .unwrap()
+        .len();
+    assert_eq!(before, after);
+}
+
+#[tokio::test]
+async fn test_summary_of_empty_session_fails() {
+    let (agent_service, session_id) = create_test_service().await;
+
+    let err = agent_service
+        .summarize_session(session_id, false)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::brain::agent::error::AgentError::InvalidRequest(_)
+    ));
+}
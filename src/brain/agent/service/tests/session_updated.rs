@@ -0,0 +1,20 @@
+use super::*;
+
+#[tokio::test]
+async fn test_session_updated_fires_for_user_message_and_reply() {
+    let (service, session_id) = create_test_service().await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let service = service.with_session_updated_tx(tx);
+
+    service
+        .send_message_with_tools(session_id, "Hello from my phone".to_string(), None)
+        .await
+        .unwrap();
+
+    let mut notified = Vec::new();
+    while let Ok(id) = rx.try_recv() {
+        notified.push(id);
+    }
+    // Once when the user's message is saved, once when the reply is done
+    assert_eq!(notified, vec![session_id, session_id]);
+}
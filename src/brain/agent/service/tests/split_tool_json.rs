@@ -0,0 +1,123 @@
+use super::*;
+
+fn usage() -> TokenUsage {
+    TokenUsage {
+        input_tokens: 10,
+        output_tokens: 5,
+        ..Default::default()
+    }
+}
+
+fn message_start() -> StreamEvent {
+    StreamEvent::MessageStart {
+        message: StreamMessage {
+            id: "split-json-resp".to_string(),
+            model: "mock-model".to_string(),
+            role: Role::Assistant,
+            usage: usage(),
+        },
+    }
+}
+
+fn tool_start(index: usize) -> StreamEvent {
+    StreamEvent::ContentBlockStart {
+        index,
+        content_block: ContentBlock::ToolUse {
+            id: format!("tool-{}", index),
+            name: "test_tool".to_string(),
+            input: serde_json::Value::Object(Default::default()),
+        },
+    }
+}
+
+fn json_delta(index: usize, partial_json: &str) -> StreamEvent {
+    StreamEvent::ContentBlockDelta {
+        index,
+        delta: ContentDelta::InputJsonDelta {
+            partial_json: partial_json.to_string(),
+        },
+    }
+}
+
+fn message_end() -> Vec<StreamEvent> {
+    vec![
+        StreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: Some(StopReason::ToolUse),
+                stop_sequence: None,
+            },
+            usage: usage(),
+        },
+        StreamEvent::MessageStop,
+    ]
+}
+
+/// Stream `events` through `stream_complete` and return the tool inputs
+async fn tool_inputs(events: Vec<StreamEvent>) -> Vec<serde_json::Value> {
+    let provider = MockProvider::scripted(vec![Reply::Events(events)]);
+    let (agent_service, _) = create_test_service_with_provider(Arc::new(provider)).await;
+    let (response, _) = agent_service
+        .stream_complete(
+            Uuid::nil(),
+            LLMRequest::new("mock-model".to_string(), vec![Message::user("x")]),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    response
+        .content
+        .into_iter()
+        .filter_map(|b| match b {
+            ContentBlock::ToolUse { input, .. } => Some(input),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The tool input split into five tiny fragments, none valid on its own
+const FRAGMENTS: [&str; 5] = ["{\"mes", "sage\": \"te", "st\", \"co", "unt\": ", "3}"];
+
+#[tokio::test]
+async fn test_tool_input_split_across_five_deltas() {
+    let mut events = vec![message_start(), tool_start(0)];
+    events.extend(FRAGMENTS.iter().map(|f| json_delta(0, f)));
+    events.push(StreamEvent::ContentBlockStop { index: 0 });
+    events.extend(message_end());
+
+    let inputs = tool_inputs(events).await;
+    assert_eq!(inputs, [serde_json::json!({"message": "test", "count": 3})]);
+}
+
+#[tokio::test]
+async fn test_split_tool_input_without_block_stop_is_still_parsed() {
+    let mut events = vec![message_start(), tool_start(0)];
+    events.extend(FRAGMENTS.iter().map(|f| json_delta(0, f)));
+    events.extend(message_end());
+
+    let inputs = tool_inputs(events).await;
+    assert_eq!(inputs, [serde_json::json!({"message": "test", "count": 3})]);
+}
+
+#[tokio::test]
+async fn test_fragment_before_block_start_is_kept() {
+    let mut events = vec![message_start(), json_delta(0, FRAGMENTS[0]), tool_start(0)];
+    events.extend(FRAGMENTS[1..].iter().map(|f| json_delta(0, f)));
+    events.push(StreamEvent::ContentBlockStop { index: 0 });
+    events.extend(message_end());
+
+    let inputs = tool_inputs(events).await;
+    assert_eq!(inputs, [serde_json::json!({"message": "test", "count": 3})]);
+}
+
+#[tokio::test]
+async fn test_incomplete_accumulated_json_is_marked_malformed() {
+    let mut events = vec![message_start(), tool_start(0)];
+    events.extend(FRAGMENTS[..4].iter().map(|f| json_delta(0, f)));
+    events.push(StreamEvent::ContentBlockStop { index: 0 });
+    events.extend(message_end());
+
+    let inputs = tool_inputs(events).await;
+    assert_eq!(inputs.len(), 1);
+    assert!(crate::brain::provider::malformed_tool_input_error(&inputs[0]).is_some());
+}
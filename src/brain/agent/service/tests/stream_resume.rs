@@ -0,0 +1,74 @@
+use super::*;
+
+async fn run_turn(provider: Arc<MockProvider>) -> AgentResponse {
+    let (agent_service, session_id) = create_test_service_with_provider(provider).await;
+    agent_service
+        .with_tool_registry(Arc::new(ToolRegistry::new()))
+        .send_message_with_tools(session_id, "Say hello world".to_string(), None)
+        .await
+        .unwrap()
+}
+
+/// The stream of a `text` reply, up to its last delta — no block stop, no
+/// stop reason, no MessageStop
+fn cut_off(text: &str) -> Vec<StreamEvent> {
+    response_events(&text_response(text, StopReason::EndTurn))
+        .into_iter()
+        .take_while(|event| !matches!(event, StreamEvent::ContentBlockStop { .. }))
+        .collect()
+}
+
+fn finished(text: &str) -> Reply {
+    Reply::Response(text_response(text, StopReason::EndTurn))
+}
+
+#[tokio::test]
+async fn test_dropped_stream_is_resumed_from_partial_text() {
+    let provider = Arc::new(MockProvider::scripted(vec![
+        Reply::Events(cut_off("Hello wor")),
+        finished("ld"),
+    ]));
+
+    let response = run_turn(provider.clone()).await;
+
+    assert_eq!(response.content, "Hello world");
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+
+    // The resume request carries the partial reply and a continue prompt
+    let history = &requests[1].messages;
+    let partial = &history[history.len() - 2];
+    assert_eq!(partial.role, Role::Assistant);
+    assert!(matches!(&partial.content[0], ContentBlock::Text { text } if text == "Hello wor"));
+    let prompt = history.last().unwrap();
+    assert!(
+        matches!(&prompt.content[0], ContentBlock::Text { text } if text.contains("dropped connection"))
+    );
+}
+
+#[tokio::test]
+async fn test_network_error_mid_stream_keeps_partial_text() {
+    let provider = Arc::new(MockProvider::scripted(vec![
+        Reply::EventsThenError(cut_off("Hello wor")),
+        finished("ld"),
+    ]));
+
+    let response = run_turn(provider.clone()).await;
+
+    assert_eq!(response.content, "Hello world");
+    assert_eq!(provider.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_unrecoverable_drop_keeps_partial_with_marker() {
+    // Every attempt drops: two resumes, then the partial is kept and marked
+    let provider = Arc::new(MockProvider::scripted(vec![Reply::Events(cut_off(
+        "more ",
+    ))]));
+
+    let response = run_turn(provider.clone()).await;
+
+    assert_eq!(provider.requests().len(), 3);
+    assert!(response.content.starts_with("more more more "));
+    assert!(response.content.ends_with("_(response interrupted)_"));
+}
@@ -0,0 +1,71 @@
+use super::*;
+use crate::brain::agent::service::metrics::tool_metrics;
+
+/// Tool that always reports failure, under a name no other test uses
+struct FailingProbeTool;
+
+#[async_trait]
+impl crate::brain::tools::Tool for FailingProbeTool {
+    fn name(&self) -> &str {
+        "metrics_probe"
+    }
+
+    fn description(&self) -> &str {
+        "Always fails"
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    fn capabilities(&self) -> Vec<crate::brain::tools::ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(
+        &self,
+        _input: serde_json::Value,
+        _context: &crate::brain::tools::ToolExecutionContext,
+    ) -> crate::brain::tools::Result<crate::brain::tools::ToolResult> {
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        Ok(crate::brain::tools::ToolResult::error(
+            "probe failed".to_string(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_tool_loop_records_metrics_per_tool() {
+    let provider = Arc::new(MockProviderWithNamedTool::new("metrics_probe"));
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(FailingProbeTool));
+
+    let db = Database::connect_in_memory().await.unwrap();
+    db.run_migrations().await.unwrap();
+    let context = ServiceContext::new(db.pool().clone());
+    let agent_service = AgentService::new(provider, context.clone())
+        .with_tool_registry(Arc::new(registry))
+        .with_auto_approve_tools(true);
+    let session = SessionService::new(context)
+        .create_session(Some("Tool Metrics Test".to_string()))
+        .await
+        .unwrap();
+
+    agent_service
+        .send_message_with_tools(session.id, "Run the probe".to_string(), None)
+        .await
+        .unwrap();
+
+    let (_, probe) = tool_metrics()
+        .into_iter()
+        .find(|(name, _)| name == "metrics_probe")
+        .expect("probe call recorded");
+    assert_eq!(probe.calls, 1);
+    assert_eq!(probe.failures, 1);
+    assert_eq!(probe.success_rate(), 0.0);
+    assert!(probe.avg_duration() >= std::time::Duration::from_millis(5));
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[tokio::test]
+async fn test_tools_md_reaches_request() {
+    let provider = Arc::new(MockProvider::default());
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+    let context = agent_service.context().clone();
+
+    let brain_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        brain_dir.path().join("TOOLS.md"),
+        "Always prefer discord_send over http_request.",
+    )
+    .unwrap();
+
+    let build = |inject: bool| {
+        AgentService::new(provider.clone(), context.clone())
+            .with_system_brain("You are a test agent.".to_string())
+            .with_brain_path(brain_dir.path().to_path_buf())
+            .with_tools_md(inject)
+    };
+
+    build(true)
+        .send_message(session_id, "Hello".to_string(), None)
+        .await
+        .unwrap();
+    build(false)
+        .send_message(session_id, "Again".to_string(), None)
+        .await
+        .unwrap();
+
+    let seen: Vec<_> = provider.requests().into_iter().map(|r| r.system).collect();
+    let injected = seen[0].as_deref().unwrap();
+    assert!(injected.starts_with("You are a test agent."));
+    assert!(injected.contains("Always prefer discord_send over http_request."));
+    assert_eq!(seen[1].as_deref(), Some("You are a test agent."));
+}
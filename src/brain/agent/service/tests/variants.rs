@@ -0,0 +1,63 @@
+use super::*;
+
+#[tokio::test]
+async fn test_generate_and_keep_variant() {
+    let provider = Arc::new(MockProvider::default());
+    let (agent_service, session_id) = create_test_service_with_provider(provider.clone()).await;
+
+    let message_service = MessageService::new(agent_service.context().clone());
+    let question = message_service
+        .create_message(session_id, "user".to_string(), "Name a crab".to_string())
+        .await
+        .unwrap();
+    message_service
+        .create_message(session_id, "assistant".to_string(), "Ferris".to_string())
+        .await
+        .unwrap();
+
+    let (anchor_id, variants) = agent_service
+        .generate_variants(session_id, 3, 0.9, None)
+        .await
+        .unwrap();
+    assert_eq!(anchor_id, question.id);
+    assert_eq!(variants.len(), 3);
+    let requests = provider.requests();
+    assert_eq!(
+        requests.iter().map(|r| r.temperature).collect::<Vec<_>>(),
+        vec![Some(0.9); 3],
+        "the temperature override should reach every request"
+    );
+    // Variants must answer the last user message, never include the old reply
+    assert!(
+        requests
+            .iter()
+            .all(|r| matches!(r.messages.last().map(|m| &m.role), Some(Role::User)))
+    );
+    // Nothing saved yet
+    assert_eq!(
+        message_service
+            .list_messages_for_session(session_id)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // A message sent after generating doesn't move what the variant replaces
+    message_service
+        .create_message(session_id, "user".to_string(), "Another?".to_string())
+        .await
+        .unwrap();
+    agent_service
+        .keep_variant(session_id, anchor_id, variants[1].clone())
+        .await
+        .unwrap();
+    let messages = message_service
+        .list_messages_for_session(session_id)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].content, "Name a crab");
+    assert_eq!(messages[1].role, "assistant");
+    assert_eq!(messages[1].content, variants[1]);
+}
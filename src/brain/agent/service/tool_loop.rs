@@ -2,7 +2,10 @@ use super::builder::AgentService;
 use super::types::*;
 use crate::brain::agent::context::AgentContext;
 use crate::brain::agent::error::{AgentError, Result};
-use crate::brain::provider::{ContentBlock, LLMRequest, LLMResponse, Message};
+use crate::brain::provider::{
+    ContentBlock, LLMRequest, LLMResponse, Message, ProviderErrorKind, malformed_tool_input_error,
+    strip_malformed_tool_input, track_served_by,
+};
 use crate::brain::tools::ToolExecutionContext;
 use crate::services::{MessageService, SessionService};
 use serde_json::Value;
@@ -10,6 +13,13 @@ use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Extra wait on top of `approval_timeout_secs` so channel prompts time out
+/// (and mark themselves expired) before the tool loop gives up on them.
+const APPROVAL_TIMEOUT_GRACE_SECS: u64 = 10;
+
+/// Appended to a reply whose stream dropped and couldn't be resumed
+const INTERRUPTED_MARKER: &str = "\n\n_(response interrupted)_";
+
 impl AgentService {
     /// Enforce the 80 % context budget rule.
     ///
@@ -103,6 +113,34 @@ impl AgentService {
         cancel_token: Option<CancellationToken>,
         override_approval_callback: Option<ApprovalCallback>,
         override_progress_callback: Option<ProgressCallback>,
+    ) -> Result<AgentResponse> {
+        // Report the provider that actually answered when a fallback chain is set
+        let (result, served_by) = track_served_by(self.run_tool_loop_inner(
+            session_id,
+            user_message,
+            model,
+            cancel_token,
+            override_approval_callback,
+            override_progress_callback,
+        ))
+        .await;
+        result.map(|mut response| {
+            if let Some(provider) = served_by {
+                response.provider = provider;
+            }
+            self.audit_response(session_id, &response);
+            response
+        })
+    }
+
+    async fn run_tool_loop_inner(
+        &self,
+        session_id: Uuid,
+        user_message: String,
+        model: Option<String>,
+        cancel_token: Option<CancellationToken>,
+        override_approval_callback: Option<ApprovalCallback>,
+        override_progress_callback: Option<ProgressCallback>,
     ) -> Result<AgentResponse> {
         // Per-call effective callbacks (override wins over service-level).
         // Track whether an explicit per-call override was provided so we can honour
@@ -116,11 +154,12 @@ impl AgentService {
 
         // Get or create session
         let session_service = SessionService::new(self.context.clone());
-        let _session = session_service
+        let session = session_service
             .get_session(session_id)
             .await
             .map_err(|e| AgentError::Database(e.to_string()))?
             .ok_or(AgentError::SessionNotFound(session_id))?;
+        self.check_session_limits(&session).await?;
 
         // Load conversation context with budget-aware message trimming
         let message_service = MessageService::new(self.context.clone());
@@ -138,6 +177,8 @@ impl AgentService {
         });
         let context_window = self.context_limit;
 
+        let last_activity = all_db_messages.last().map(|m| m.created_at);
+
         // Load from last compaction point — find the last CONTEXT COMPACTION marker
         // and only load messages from there forward. No arbitrary trimming.
         let db_messages = Self::messages_from_last_compaction(all_db_messages);
@@ -148,16 +189,51 @@ impl AgentService {
         // Add system brain if available (count its tokens so context.token_count
         // reflects the full API input from the start — prevents gross undercount
         // that causes the TUI context counter to jump wildly on first calibration)
-        if let Some(brain) = &self.default_system_brain {
-            context.token_count += AgentContext::estimate_tokens(brain);
-            context.system_brain = Some(brain.clone());
+        if let Some(brain) = self.turn_system_brain() {
+            context.token_count += AgentContext::estimate_tokens(&brain);
+            context.system_brain = Some(brain);
         }
 
         // Check for manual /compact before user_message is consumed
         let is_manual_compact = user_message.contains("[SYSTEM: Compact context now.");
 
-        // Build user message — detect and attach images from paths/URLs
-        let user_msg = Self::build_user_message(&user_message).await;
+        // A channel session waking from a long pause starts from a summary
+        if !is_manual_compact {
+            self.compact_if_idle(
+                session_id,
+                last_activity,
+                &mut context,
+                &model_name,
+                &message_service,
+            )
+            .await;
+        }
+
+        // Gate images and tools on what the current provider can actually do
+        let capabilities = self.provider_capabilities();
+        if !capabilities.vision && user_message.contains("<<IMG:") {
+            self.warn_capability(
+                session_id,
+                progress_callback.as_ref(),
+                "The current model can't view images — they were left out of this message. \
+                 Set `vision_model` for this provider or switch to one with vision.",
+            );
+        }
+        let use_tools = capabilities.tools && self.tool_registry.count() > 0;
+        if !capabilities.tools && self.tool_registry.count() > 0 {
+            self.warn_capability(
+                session_id,
+                progress_callback.as_ref(),
+                "The current model doesn't support tool calling — replying without tools.",
+            );
+        }
+
+        // Build user message — detect and attach images from paths/URLs. The
+        // [chat] prefix/suffix is applied here only, once per turn; the DB
+        // keeps what the user typed.
+        let user_msg =
+            Self::build_user_message(&self.wrap_user_message(&user_message), capabilities.vision)
+                .await;
         context.add_message(user_msg);
 
         // Save user message to database (text only — images are ephemeral)
@@ -165,6 +241,8 @@ impl AgentService {
             .create_message(session_id, "user".to_string(), user_message)
             .await
             .map_err(|e| AgentError::Database(e.to_string()))?;
+        // Show the incoming message in the TUI now rather than when the reply lands
+        self.notify_session_updated(session_id);
 
         // Create assistant message placeholder NOW for real-time persistence.
         // We'll append content as we go and update with final tokens at the end.
@@ -209,10 +287,12 @@ impl AgentService {
                         usage: crate::brain::provider::TokenUsage {
                             input_tokens: 0,
                             output_tokens: 0,
+                            ..Default::default()
                         },
                         context_tokens: context.token_count as u32,
                         cost: 0.0,
                         model: model_name,
+                        provider: self.provider_name(),
                     });
                 }
                 Err(e) => {
@@ -238,10 +318,12 @@ impl AgentService {
                         usage: crate::brain::provider::TokenUsage {
                             input_tokens: 0,
                             output_tokens: 0,
+                            ..Default::default()
                         },
                         context_tokens: context.token_count as u32,
                         cost: 0.0,
                         model: model_name,
+                        provider: self.provider_name(),
                     });
                 }
             }
@@ -300,11 +382,19 @@ impl AgentService {
         let mut iteration = 0;
         let mut total_input_tokens = 0u32;
         let mut total_output_tokens = 0u32;
+        let mut total_cache_creation = 0u32;
+        let mut total_cache_read = 0u32;
         let mut final_response: Option<LLMResponse> = None;
         let mut accumulated_text = String::new(); // Collect text from all iterations (not just final)
         let mut recent_tool_calls: Vec<String> = Vec::new(); // Track tool calls to detect loops
         let mut stream_retry_count = 0u32; // Track consecutive stream drop retries
         const MAX_STREAM_RETRIES: u32 = 2; // Retry up to 2 times on dropped streams
+        let mut malformed_retry_count = 0u32; // Track corrective resends for invalid tool JSON
+        const MAX_MALFORMED_RETRIES: u32 = 2; // Ask the model to resend at most 2 times per turn
+        let mut auto_continue_count = 0u32; // Continuations after hitting the output token limit
+        let mut continued_text = String::new(); // Text of the reply being continued, if any
+        let mut stop_turn = false; // User picked "deny and stop" at an approval prompt
+        let mut trim_reported = false; // HistoryTrimmed is sent once per turn
 
         loop {
             // Safety: warn every 50 iterations but never hard-stop
@@ -329,6 +419,10 @@ impl AgentService {
                 );
                 break;
             }
+            if stop_turn {
+                tracing::info!("🛑 Turn stopped by the user at an approval prompt");
+                break;
+            }
 
             iteration += 1;
 
@@ -366,6 +460,22 @@ impl AgentService {
                 context.add_message(Message::user(cont_text));
             }
 
+            // Compaction can't always get the history under the model's own
+            // window (small local models, failed compaction) — drop the oldest
+            // turns rather than send a request the provider will reject
+            let dropped = context.trim_to_budget(self.history_budget(&model_name));
+            if dropped > 0 {
+                tracing::info!(
+                    "Trimmed {} old message(s) to fit {}'s context window",
+                    dropped,
+                    model_name
+                );
+                if !trim_reported && let Some(ref cb) = progress_callback {
+                    cb(session_id, ProgressEvent::HistoryTrimmed { dropped });
+                }
+                trim_reported = true;
+            }
+
             // Build LLM request with tools if available
             let mut request = LLMRequest::new(model_name.clone(), context.messages.clone())
                 .with_max_tokens(self.max_tokens);
@@ -374,19 +484,20 @@ impl AgentService {
                 request = request.with_system(system.clone());
             }
 
-            // Add tools if registry has any
+            // Add tools if registry has any and the provider can call them
             let tool_count = self.tool_registry.count();
             tracing::debug!("Tool registry contains {} tools", tool_count);
-            if tool_count > 0 {
+            if use_tools {
                 let tool_defs = self.tool_registry.get_tool_definitions();
                 tracing::debug!("Adding {} tool definitions to request", tool_defs.len());
                 request = request.with_tools(tool_defs);
-            } else {
+            } else if tool_count == 0 {
                 tracing::warn!("No tools registered in tool registry!");
             }
+            let request = self.apply_sampling(session_id, request);
 
             // Send to provider via streaming — retry once after emergency compaction if prompt is too long
-            let (response, reasoning_text) = match self
+            let (mut response, reasoning_text) = match self
                 .stream_complete(
                     session_id,
                     request,
@@ -396,10 +507,7 @@ impl AgentService {
                 .await
             {
                 Ok(resp) => resp,
-                Err(ref e)
-                    if e.to_string().contains("prompt is too long")
-                        || e.to_string().contains("too many tokens") =>
-                {
+                Err(ref e) if e.kind() == ProviderErrorKind::ContextLength => {
                     tracing::warn!("Prompt too long for provider — emergency compaction");
                     let err_msg = e.to_string();
                     match self
@@ -450,9 +558,10 @@ impl AgentService {
                     if let Some(system) = &context.system_brain {
                         retry_req = retry_req.with_system(system.clone());
                     }
-                    if self.tool_registry.count() > 0 {
+                    if use_tools {
                         retry_req = retry_req.with_tools(self.tool_registry.get_tool_definitions());
                     }
+                    let retry_req = self.apply_sampling(session_id, retry_req);
                     self.stream_complete(
                         session_id,
                         retry_req,
@@ -462,13 +571,36 @@ impl AgentService {
                     .await
                     .map_err(AgentError::Provider)?
                 }
+                // Resuming a dropped reply failed outright: keep what we have
+                Err(e) if stream_retry_count > 0 && !continued_text.is_empty() => {
+                    tracing::warn!(
+                        "Resuming the interrupted reply failed: {} — keeping the partial text",
+                        e
+                    );
+                    (
+                        LLMResponse {
+                            id: String::new(),
+                            model: model_name.clone(),
+                            content: vec![ContentBlock::Text {
+                                text: INTERRUPTED_MARKER.to_string(),
+                            }],
+                            stop_reason: Some(crate::brain::provider::StopReason::EndTurn),
+                            usage: crate::brain::provider::TokenUsage {
+                                input_tokens: 0,
+                                output_tokens: 0,
+                                ..Default::default()
+                            },
+                        },
+                        None,
+                    )
+                }
                 Err(e) => return Err(AgentError::Provider(e)),
             };
 
             // Track token usage — fall back to tiktoken estimate when provider
             // doesn't report usage (e.g. MiniMax streaming ignores include_usage)
-            let call_input_tokens = if response.usage.input_tokens > 0 {
-                response.usage.input_tokens
+            let call_input_tokens = if response.usage.prompt_tokens() > 0 {
+                response.usage.prompt_tokens()
             } else {
                 // Serialize actual tool definitions to count their real token cost,
                 // matching how the provider computes it before each request.
@@ -487,11 +619,13 @@ impl AgentService {
             };
             total_input_tokens += call_input_tokens;
             total_output_tokens += response.usage.output_tokens;
+            total_cache_creation += response.usage.cache_creation_input_tokens;
+            total_cache_read += response.usage.cache_read_input_tokens;
 
             // Calibrate context token count with the API's real input_tokens.
             // Even with tiktoken, there's some drift since Anthropic's tokenizer differs slightly.
             // The API knows the exact count — use it to keep our tracking honest.
-            let api_input = response.usage.input_tokens as usize;
+            let api_input = response.usage.prompt_tokens() as usize;
             let tool_overhead = self.actual_tool_schema_tokens();
             let real_message_tokens = api_input.saturating_sub(tool_overhead);
             if real_message_tokens > 0 {
@@ -547,10 +681,28 @@ impl AgentService {
             // --- STREAM DROP DETECTION ---
             // If stop_reason is None, the stream ended without [DONE]/MessageStop.
             // This means a network interruption, provider timeout, or dropped connection.
-            // The response may contain partial/corrupt data. Retry instead of proceeding
-            // with garbage that silently drops the task.
+            // Partial text-only replies are resumed (see AUTO-CONTINUE below); anything
+            // with a tool call may hold corrupt JSON, so it's retried from scratch.
+            let mut resume_stream = false;
             if response.stop_reason.is_none() {
-                if stream_retry_count < MAX_STREAM_RETRIES {
+                let resumable = !response
+                    .content
+                    .iter()
+                    .any(|b| matches!(b, ContentBlock::ToolUse { .. }))
+                    && response.content.iter().any(
+                        |b| matches!(b, ContentBlock::Text { text } if !text.trim().is_empty()),
+                    );
+                if stream_retry_count < MAX_STREAM_RETRIES && resumable {
+                    stream_retry_count += 1;
+                    resume_stream = true;
+                    tracing::warn!(
+                        "🔄 Stream dropped mid-reply at iteration {}. \
+                         Resuming from the partial text ({}/{}).",
+                        iteration,
+                        stream_retry_count,
+                        MAX_STREAM_RETRIES,
+                    );
+                } else if stream_retry_count < MAX_STREAM_RETRIES {
                     stream_retry_count += 1;
                     tracing::warn!(
                         "🔄 Stream dropped without completion (no stop_reason) at iteration {}. \
@@ -560,8 +712,10 @@ impl AgentService {
                         MAX_STREAM_RETRIES,
                     );
                     // Subtract the tokens we just counted — they'll be re-counted on retry
-                    total_input_tokens -= response.usage.input_tokens;
+                    total_input_tokens -= response.usage.prompt_tokens();
                     total_output_tokens -= response.usage.output_tokens;
+                    total_cache_creation -= response.usage.cache_creation_input_tokens;
+                    total_cache_read -= response.usage.cache_read_input_tokens;
                     // Don't increment iteration — this is a retry, not a new turn
                     iteration -= 1;
                     continue;
@@ -576,6 +730,14 @@ impl AgentService {
                     );
                     // Reset retry counter — we're accepting the partial response
                     stream_retry_count = 0;
+                    if resumable
+                        && let Some(ContentBlock::Text { text }) = response
+                            .content
+                            .iter_mut()
+                            .rfind(|b| matches!(b, ContentBlock::Text { .. }))
+                    {
+                        text.push_str(INTERRUPTED_MARKER);
+                    }
                 }
             } else {
                 // Successful stream completion — reset retry counter
@@ -643,10 +805,67 @@ impl AgentService {
                     .await;
             }
 
+            // A continuation picks up mid-reply: glue it on without a separator
+            let continued = std::mem::take(&mut continued_text);
+            let separator = if continued.is_empty() { "\n\n" } else { "" };
+
+            // --- AUTO-CONTINUE ---
+            // Text-only reply cut off by the output token limit or a dropped stream:
+            // keep what we have and ask the model to carry on from where it stopped.
+            let hit_max_tokens = response.stop_reason
+                == Some(crate::brain::provider::StopReason::MaxTokens)
+                && auto_continue_count < self.max_auto_continues;
+            if tool_uses.is_empty()
+                && !iteration_text.is_empty()
+                && (resume_stream || hit_max_tokens)
+            {
+                let (prompt, event) = if resume_stream {
+                    (
+                        "[SYSTEM: Your previous reply was interrupted by a dropped connection. \
+                         Continue exactly where you left off. Do NOT repeat anything already written.]",
+                        ProgressEvent::StreamResumed {
+                            attempt: stream_retry_count,
+                        },
+                    )
+                } else {
+                    auto_continue_count += 1;
+                    tracing::info!(
+                        "✂️ Reply hit the output token limit — auto-continuing ({}/{})",
+                        auto_continue_count,
+                        self.max_auto_continues
+                    );
+                    (
+                        "[SYSTEM: Your previous reply was cut off by the output token limit. \
+                         Continue exactly where you left off. Do NOT repeat anything already written.]",
+                        ProgressEvent::AutoContinued {
+                            attempt: auto_continue_count,
+                        },
+                    )
+                };
+                if !accumulated_text.is_empty() {
+                    accumulated_text.push_str(separator);
+                }
+                accumulated_text.push_str(&iteration_text);
+                let _ = message_service
+                    .append_content(assistant_db_msg.id, &iteration_text)
+                    .await;
+
+                context.add_message(Message::assistant(iteration_text.clone()));
+                context.add_message(Message::user(prompt));
+                continued_text = continued + &iteration_text;
+
+                if let Some(ref cb) = progress_callback {
+                    cb(session_id, event);
+                }
+                // Not a new tool iteration — same as a stream retry
+                iteration -= 1;
+                continue;
+            }
+
             // Accumulate text from every iteration
             if !iteration_text.is_empty() {
                 if !accumulated_text.is_empty() {
-                    accumulated_text.push_str("\n\n");
+                    accumulated_text.push_str(separator);
                 }
                 accumulated_text.push_str(&iteration_text);
 
@@ -656,6 +875,11 @@ impl AgentService {
                     .await;
             }
 
+            // Surface the continued reply as one piece
+            if !continued.is_empty() {
+                iteration_text.insert_str(0, &continued);
+            }
+
             tracing::debug!("Found {} tool uses to execute", tool_uses.len());
 
             if tool_uses.is_empty() {
@@ -676,6 +900,17 @@ impl AgentService {
                 } else {
                     tracing::info!("Agent responded with text only (no tool calls)");
                 }
+                if !continued.is_empty() {
+                    match response.content.iter_mut().find_map(|block| match block {
+                        ContentBlock::Text { text } => Some(text),
+                        _ => None,
+                    }) {
+                        Some(text) => text.insert_str(0, &continued),
+                        None => response
+                            .content
+                            .insert(0, ContentBlock::Text { text: continued }),
+                    }
+                }
                 final_response = Some(response);
                 break;
             }
@@ -693,6 +928,53 @@ impl AgentService {
                 );
             }
 
+            // Malformed tool JSON: each iteration with invalid input costs one
+            // corrective retry. Past the cap, stop instead of looping forever.
+            if tool_uses
+                .iter()
+                .any(|(_, _, input)| malformed_tool_input_error(input).is_some())
+            {
+                malformed_retry_count += 1;
+                if malformed_retry_count > MAX_MALFORMED_RETRIES {
+                    tracing::warn!(
+                        "⚠️ Tool input JSON still malformed after {} corrective retries. Breaking loop.",
+                        MAX_MALFORMED_RETRIES
+                    );
+                    // Answer every call first so no tool_use is left without
+                    // its tool_result, then end the turn on the text alone
+                    let results = tool_uses
+                        .iter()
+                        .map(|(tool_id, _, _)| ContentBlock::ToolResult {
+                            tool_use_id: tool_id.clone(),
+                            content: format!(
+                                "Tool input was still not valid JSON after {} retries — the tool was not run",
+                                MAX_MALFORMED_RETRIES
+                            ),
+                            is_error: Some(true),
+                        })
+                        .collect();
+                    context.add_message(Message {
+                        role: crate::brain::provider::Role::Assistant,
+                        content: response
+                            .content
+                            .iter()
+                            .cloned()
+                            .map(strip_malformed_tool_input)
+                            .collect(),
+                    });
+                    context.add_message(Message {
+                        role: crate::brain::provider::Role::User,
+                        content: results,
+                    });
+                    response
+                        .content
+                        .retain(|block| !matches!(block, ContentBlock::ToolUse { .. }));
+                    response.stop_reason = Some(crate::brain::provider::StopReason::EndTurn);
+                    final_response = Some(response);
+                    break;
+                }
+            }
+
             // Detect tool loops: hash the full input for every tool.
             // Different arguments = different hash = no false loop detection.
             let current_call_signature = tool_uses
@@ -774,6 +1056,39 @@ impl AgentService {
                     break;
                 }
 
+                // Turn stopped at an earlier approval — answer the rest without running them
+                if stop_turn {
+                    tool_results.push(ContentBlock::ToolResult {
+                        tool_use_id: tool_id,
+                        content: "Not run: the user stopped this turn".to_string(),
+                        is_error: Some(true),
+                    });
+                    continue;
+                }
+
+                // Invalid JSON input: don't run the tool, ask the model to resend
+                if let Some(detail) = malformed_tool_input_error(&tool_input) {
+                    tracing::warn!(
+                        "Tool '{}' input was not valid JSON (retry {}/{}): {}",
+                        tool_name,
+                        malformed_retry_count,
+                        MAX_MALFORMED_RETRIES,
+                        detail
+                    );
+                    let err = format!(
+                        "your tool input was not valid JSON: {} — please resend",
+                        detail
+                    );
+                    tool_descriptions.push(format!("{} (malformed input)", tool_name));
+                    tool_outputs.push((false, err.clone()));
+                    tool_results.push(ContentBlock::ToolResult {
+                        tool_use_id: tool_id,
+                        content: err,
+                        is_error: Some(true),
+                    });
+                    continue;
+                }
+
                 tracing::info!("Executing tool '{}' (iteration {})", tool_name, iteration,);
 
                 // Save tool input for progress reporting (before it's moved to execute)
@@ -796,14 +1111,17 @@ impl AgentService {
                 // Check if approval is needed.
                 // Each channel's make_approval_callback() already checks
                 // check_approval_policy() from config — the tool loop only
-                // respects the auto_approve_tools flag and tool-level policy.
-                let needs_approval = if let Some(tool) = self.tool_registry.get(&tool_name) {
-                    tool.requires_approval_for_input(&tool_input)
-                        && (!self.auto_approve_tools || has_override_approval)
-                        && !tool_context.auto_approve
-                } else {
-                    false
-                };
+                // respects the auto_approve_tools flag, tool-level policy and
+                // the capability-based auto-approve policy. Tools forced via
+                // [tools] require_approval always ask, whatever those say.
+                let forced = self.tool_registry.is_approval_forced(&tool_name);
+                let needs_approval = self
+                    .tool_registry
+                    .requires_approval_for(&tool_name, &tool_input)
+                    && (forced
+                        || ((!self.auto_approve_tools || has_override_approval)
+                            && !tool_context.auto_approve
+                            && !self.policy_auto_approves(&tool_name)));
 
                 // Request approval if needed
                 if needs_approval {
@@ -820,6 +1138,7 @@ impl AgentService {
                                     .iter()
                                     .map(|c| format!("{:?}", c))
                                     .collect(),
+                                forced,
                             }
                         } else {
                             // Tool not found, skip approval
@@ -833,11 +1152,58 @@ impl AgentService {
                             continue;
                         };
 
-                        // Call approval callback
+                        // Call approval callback, giving up after [tools] approval_timeout_secs
                         tracing::info!("Requesting user approval for tool '{}'", tool_name);
-                        match approval_cb(tool_info).await {
-                            Ok((approved, always_approve)) => {
-                                if !approved {
+                        let timeout_secs = self.approval_timeout_secs();
+                        let decision = if timeout_secs > 0 {
+                            let limit = std::time::Duration::from_secs(
+                                timeout_secs + APPROVAL_TIMEOUT_GRACE_SECS,
+                            );
+                            match tokio::time::timeout(limit, approval_cb(tool_info)).await {
+                                Ok(decision) => decision,
+                                Err(_) => {
+                                    tracing::warn!(
+                                        "Approval for tool '{}' timed out after {}s — denying",
+                                        tool_name,
+                                        timeout_secs
+                                    );
+                                    tool_outputs.push((false, "Approval timed out".to_string()));
+                                    tool_results.push(ContentBlock::ToolResult {
+                                        tool_use_id: tool_id,
+                                        content: format!(
+                                            "No approval answer within {}s — the tool was not run. \
+                                             Tell the user what you wanted to do instead of retrying.",
+                                            timeout_secs
+                                        ),
+                                        is_error: Some(true),
+                                    });
+                                    continue;
+                                }
+                            }
+                        } else {
+                            approval_cb(tool_info).await
+                        };
+                        match decision {
+                            Ok(decision) => {
+                                if decision == ApprovalDecision::DenyAndStop {
+                                    tracing::warn!(
+                                        "User denied tool '{}' and stopped the turn",
+                                        tool_name
+                                    );
+                                    stop_turn = true;
+                                    tool_outputs.push((
+                                        false,
+                                        "User denied permission and stopped the turn".to_string(),
+                                    ));
+                                    tool_results.push(ContentBlock::ToolResult {
+                                        tool_use_id: tool_id,
+                                        content: "User denied permission and stopped the turn"
+                                            .to_string(),
+                                        is_error: Some(true),
+                                    });
+                                    continue;
+                                }
+                                if decision == ApprovalDecision::Deny {
                                     tracing::warn!("User denied approval for tool '{}'", tool_name);
                                     tool_outputs
                                         .push((false, "User denied permission".to_string()));
@@ -850,7 +1216,7 @@ impl AgentService {
                                     continue;
                                 }
                                 // Propagate "always approve" to skip callbacks for remaining tools
-                                if always_approve {
+                                if decision == ApprovalDecision::ApproveAll {
                                     tool_context.auto_approve = true;
                                     tracing::info!(
                                         "User selected 'Always' — auto-approving remaining tools in this loop"
@@ -869,9 +1235,15 @@ impl AgentService {
                                         .shared_working_directory
                                         .clone(),
                                     service_context: tool_context.service_context.clone(),
+                                    progress_sink: Self::tool_output_sink(
+                                        session_id,
+                                        &tool_name,
+                                        progress_callback.as_ref(),
+                                    ),
                                 };
 
                                 // Execute the tool with approved context
+                                let started = std::time::Instant::now();
                                 match self
                                     .tool_registry
                                     .execute(&tool_name, tool_input, &approved_tool_context)
@@ -905,6 +1277,14 @@ impl AgentService {
                                         let output_summary: String =
                                             content.chars().take(2000).collect();
                                         tool_outputs.push((success, output_summary.clone()));
+                                        self.record_tool_call(
+                                            session_id,
+                                            &tool_name,
+                                            &tool_input_for_progress,
+                                            success,
+                                            &output_summary,
+                                            started,
+                                        );
                                         if let Some(ref cb) = progress_callback {
                                             cb(
                                                 session_id,
@@ -933,6 +1313,14 @@ impl AgentService {
                                         let output_summary: String =
                                             err_msg.chars().take(2000).collect();
                                         tool_outputs.push((false, output_summary.clone()));
+                                        self.record_tool_call(
+                                            session_id,
+                                            &tool_name,
+                                            &tool_input_for_progress,
+                                            false,
+                                            &output_summary,
+                                            started,
+                                        );
                                         if let Some(ref cb) = progress_callback {
                                             cb(
                                                 session_id,
@@ -985,6 +1373,9 @@ impl AgentService {
                 // so the registry's own approval check doesn't block it)
                 let mut approved_context = tool_context.clone();
                 approved_context.auto_approve = true;
+                approved_context.progress_sink =
+                    Self::tool_output_sink(session_id, &tool_name, progress_callback.as_ref());
+                let started = std::time::Instant::now();
                 match self
                     .tool_registry
                     .execute(&tool_name, tool_input, &approved_context)
@@ -1017,6 +1408,14 @@ impl AgentService {
 
                         let output_summary: String = content.chars().take(2000).collect();
                         tool_outputs.push((success, output_summary.clone()));
+                        self.record_tool_call(
+                            session_id,
+                            &tool_name,
+                            &tool_input_for_progress,
+                            success,
+                            &output_summary,
+                            started,
+                        );
                         if let Some(ref cb) = progress_callback {
                             cb(
                                 session_id,
@@ -1040,6 +1439,14 @@ impl AgentService {
                         tracing::error!("[TOOL_EXEC] 💥 Tool '{}' error: {}", tool_name, err_msg);
                         let output_summary: String = err_msg.chars().take(2000).collect();
                         tool_outputs.push((false, output_summary.clone()));
+                        self.record_tool_call(
+                            session_id,
+                            &tool_name,
+                            &tool_input_for_progress,
+                            false,
+                            &output_summary,
+                            started,
+                        );
                         if let Some(ref cb) = progress_callback {
                             cb(
                                 session_id,
@@ -1089,20 +1496,20 @@ impl AgentService {
 
                 // Notify TUI after each tool iteration so it refreshes in real-time,
                 // even during long-running channel sessions (Telegram, WhatsApp, etc.)
-                if let Some(ref tx) = self.session_updated_tx {
-                    let _ = tx.send(session_id);
-                }
+                self.notify_session_updated(session_id);
 
                 tool_descriptions.clear();
                 tool_outputs.clear();
             }
 
-            // Add assistant message with tool use to context (filter empty text blocks)
+            // Add assistant message with tool use to context (filter empty text
+            // blocks and drop malformed-input markers)
             let clean_content: Vec<ContentBlock> = response
                 .content
                 .iter()
                 .filter(|b| !matches!(b, ContentBlock::Text { text } if text.is_empty()))
                 .cloned()
+                .map(strip_malformed_tool_input)
                 .collect();
             let assistant_msg = Message {
                 role: crate::brain::provider::Role::Assistant,
@@ -1113,7 +1520,7 @@ impl AgentService {
             // Add user message with tool results to context
             let tool_result_msg = Message {
                 role: crate::brain::provider::Role::User,
-                content: tool_results,
+                content: self.cap_tool_results(session_id, &response.content, tool_results),
             };
             context.add_message(tool_result_msg);
 
@@ -1172,6 +1579,15 @@ impl AgentService {
             }
         }
 
+        // Usage over every call of this turn; cost weighs cached prompt tokens
+        let total_usage = crate::brain::provider::TokenUsage {
+            input_tokens: total_input_tokens
+                .saturating_sub(total_cache_creation + total_cache_read),
+            output_tokens: total_output_tokens,
+            cache_creation_input_tokens: total_cache_creation,
+            cache_read_input_tokens: total_cache_read,
+        };
+
         // === GRACEFUL SAVE ON CANCEL/LOOP-BREAK ===
         // If we broke out of the loop without a final_response (cancellation, error, etc.)
         // but we have accumulated text/tool results, they're already in the DB from real-time persistence.
@@ -1182,13 +1598,17 @@ impl AgentService {
                 accumulated_text.len()
             );
             // Also save token usage for what we consumed
-            let partial_tokens = total_input_tokens + total_output_tokens;
+            let partial_tokens = total_usage.total();
             if partial_tokens > 0 {
                 let partial_cost = self
                     .provider
                     .read()
                     .expect("provider lock poisoned")
-                    .calculate_cost(&model_name, total_input_tokens, total_output_tokens);
+                    .calculate_cost(
+                        &model_name,
+                        total_usage.billable_input_tokens(),
+                        total_output_tokens,
+                    );
                 let _ = session_service
                     .update_session_usage(session_id, partial_tokens as i32, partial_cost)
                     .await;
@@ -1212,10 +1632,7 @@ impl AgentService {
                         text: accumulated_text.clone(),
                     }],
                     model: model_name.clone(),
-                    usage: crate::brain::provider::TokenUsage {
-                        input_tokens: total_input_tokens,
-                        output_tokens: total_output_tokens,
-                    },
+                    usage: total_usage,
                     stop_reason: Some(crate::brain::provider::StopReason::EndTurn),
                 }
             }
@@ -1234,12 +1651,16 @@ impl AgentService {
         // Now update with final token usage.
 
         // Calculate total cost
-        let total_tokens = total_input_tokens + total_output_tokens;
+        let total_tokens = total_usage.total();
         let cost = self
             .provider
             .read()
             .expect("provider lock poisoned")
-            .calculate_cost(&response.model, total_input_tokens, total_output_tokens);
+            .calculate_cost(
+                &response.model,
+                total_usage.billable_input_tokens(),
+                total_output_tokens,
+            );
 
         // Update message with usage info
         message_service
@@ -1255,21 +1676,17 @@ impl AgentService {
 
         // Notify the TUI that this session was updated (enables live refresh when
         // a remote channel — Telegram, WhatsApp, Discord, Slack — processes a message).
-        if let Some(ref tx) = self.session_updated_tx {
-            let _ = tx.send(session_id);
-        }
+        self.notify_session_updated(session_id);
 
         Ok(AgentResponse {
             message_id: assistant_db_msg.id,
             content: final_text,
             stop_reason: response.stop_reason,
-            usage: crate::brain::provider::TokenUsage {
-                input_tokens: total_input_tokens,
-                output_tokens: total_output_tokens,
-            },
+            usage: total_usage,
             context_tokens: context.token_count as u32,
             cost,
             model: response.model,
+            provider: self.provider_name(),
         })
     }
 }
@@ -0,0 +1,116 @@
+//! Per-session turn tracking shared by every `AgentService` instance.
+//!
+//! TUI, channel and CLI turns are foreground turns. Autonomous turns
+//! (heartbeat, boot routine) are background turns: they only start when the
+//! session is idle, and starting a foreground turn cancels any background turn
+//! running in the same session, so the two never run concurrently.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct SessionTurns {
+    foreground: usize,
+    background: Option<CancellationToken>,
+}
+
+static TURNS: Lazy<Mutex<HashMap<Uuid, SessionTurns>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Marks a turn as in flight until dropped.
+pub(crate) struct TurnGuard {
+    session_id: Uuid,
+    background: bool,
+}
+
+impl Drop for TurnGuard {
+    fn drop(&mut self) {
+        let mut turns = TURNS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = turns.get_mut(&self.session_id) {
+            if self.background {
+                state.background = None;
+            } else {
+                state.foreground = state.foreground.saturating_sub(1);
+            }
+            if state.foreground == 0 && state.background.is_none() {
+                turns.remove(&self.session_id);
+            }
+        }
+    }
+}
+
+/// Start a foreground turn, cancelling any background turn in the session.
+pub(crate) fn begin_foreground(session_id: Uuid) -> TurnGuard {
+    let mut turns = TURNS.lock().unwrap_or_else(|e| e.into_inner());
+    let state = turns.entry(session_id).or_default();
+    state.foreground += 1;
+    if let Some(token) = state.background.take() {
+        tracing::info!("Foreground turn started in {session_id} — cancelling background turn");
+        token.cancel();
+    }
+    TurnGuard {
+        session_id,
+        background: false,
+    }
+}
+
+/// Start a background turn if the session is idle. `token` is cancelled
+/// when a foreground turn starts in the same session.
+pub(crate) fn begin_background(session_id: Uuid, token: CancellationToken) -> Option<TurnGuard> {
+    let mut turns = TURNS.lock().unwrap_or_else(|e| e.into_inner());
+    let state = turns.entry(session_id).or_default();
+    if state.foreground > 0 || state.background.is_some() {
+        if state.foreground == 0 && state.background.is_none() {
+            turns.remove(&session_id);
+        }
+        return None;
+    }
+    state.background = Some(token);
+    Some(TurnGuard {
+        session_id,
+        background: true,
+    })
+}
+
+/// Whether any turn (foreground or background) is running in the session.
+pub fn is_session_busy(session_id: Uuid) -> bool {
+    TURNS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&session_id)
+        .is_some_and(|s| s.foreground > 0 || s.background.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_foreground_cancels_background() {
+        let session = Uuid::new_v4();
+        let token = CancellationToken::new();
+        let bg = begin_background(session, token.clone()).expect("idle session");
+        assert!(is_session_busy(session));
+
+        let fg = begin_foreground(session);
+        assert!(token.is_cancelled());
+        drop(bg);
+        assert!(is_session_busy(session));
+        drop(fg);
+        assert!(!is_session_busy(session));
+    }
+
+    #[test]
+    fn test_background_refused_while_busy() {
+        let session = Uuid::new_v4();
+        let fg = begin_foreground(session);
+        assert!(begin_background(session, CancellationToken::new()).is_none());
+        drop(fg);
+
+        let bg = begin_background(session, CancellationToken::new());
+        assert!(bg.is_some());
+        assert!(begin_background(session, CancellationToken::new()).is_none());
+    }
+}
@@ -23,14 +23,35 @@ pub struct ToolApprovalInfo {
     pub tool_input: Value,
     /// Tool capabilities
     pub capabilities: Vec<String>,
+    /// Listed in `[tools] require_approval`: the user must answer, whatever
+    /// the approval policy says
+    pub forced: bool,
 }
 
-/// Type alias for approval callback function.
-/// Returns `(approved, always_approve)`:
-/// - `approved`: whether this tool call is allowed
-/// - `always_approve`: if true, skip approval for all subsequent tools in this loop
+/// The user's answer to a tool approval request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalDecision {
+    /// Run this tool call
+    Approve,
+    /// Run this call and every remaining one in the turn without asking
+    ApproveAll,
+    /// Refuse this call; the model is told and carries on
+    Deny,
+    /// Refuse this call and end the turn without another model call
+    DenyAndStop,
+}
+
+impl ApprovalDecision {
+    /// Whether the tool call may run
+    pub fn is_approved(self) -> bool {
+        matches!(self, Self::Approve | Self::ApproveAll)
+    }
+}
+
+/// Type alias for approval callback function. Callbacks left unanswered past
+/// `[tools] approval_timeout_secs` are dropped and the tool is denied.
 pub type ApprovalCallback = Arc<
-    dyn Fn(ToolApprovalInfo) -> Pin<Box<dyn Future<Output = Result<(bool, bool)>> + Send>>
+    dyn Fn(ToolApprovalInfo) -> Pin<Box<dyn Future<Output = Result<ApprovalDecision>> + Send>>
         + Send
         + Sync,
 >;
@@ -49,6 +70,12 @@ pub enum ProgressEvent {
         success: bool,
         summary: String,
     },
+    /// A line of live output from a running tool (see
+    /// `ToolExecutionContext::progress_sink`)
+    ToolOutput {
+        tool_name: String,
+        line: String,
+    },
     /// Intermediate text the agent sends between tool call batches
     IntermediateText {
         text: String,
@@ -63,9 +90,11 @@ pub enum ProgressEvent {
     CompactionSummary {
         summary: String,
     },
-    /// Build completed — TUI should offer restart
+    /// Build completed — TUI should offer restart. `diff` is what changed in
+    /// the source tree; when non-empty the user reviews it before restarting.
     RestartReady {
         status: String,
+        diff: Option<crate::brain::SourceDiff>,
     },
     /// Real-time token count update — fire after every API response and tool execution
     TokenCount(usize),
@@ -73,6 +102,25 @@ pub enum ProgressEvent {
     ReasoningChunk {
         text: String,
     },
+    /// Reply hit the output token limit and is being continued automatically
+    AutoContinued {
+        attempt: u32,
+    },
+    /// The stream dropped mid-reply; the partial text is kept and the model
+    /// asked to continue it
+    StreamResumed {
+        attempt: u32,
+    },
+    /// Older messages were left out of the request to fit the model's
+    /// context window (sent at most once per turn)
+    HistoryTrimmed {
+        dropped: usize,
+    },
+    /// The current provider lacks a capability this turn asked for (vision,
+    /// tools); the turn went ahead without it
+    CapabilityWarning {
+        message: String,
+    },
 }
 
 /// Callback for reporting progress during agent execution.
@@ -90,6 +138,10 @@ pub type SudoCallback = Arc<
 pub type MessageQueueCallback =
     Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Option<String>> + Send>> + Send + Sync>;
 
+/// Returns the per-channel text appended to the system brain each turn.
+/// Called once per turn so config reloads take effect without a rebuild.
+pub type SystemAppendCallback = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
 /// Response from the agent
 #[derive(Debug, Clone)]
 pub struct AgentResponse {
@@ -113,6 +165,10 @@ pub struct AgentResponse {
 
     /// Model used
     pub model: String,
+
+    /// Provider that served the final response — differs from the configured
+    /// one when `[providers.fallback]` took over after a retryable failure
+    pub provider: String,
 }
 
 /// Streaming response from the agent
@@ -0,0 +1,147 @@
+//! Response variants
+//!
+//! `/variants N` re-runs the session's last user message several times at a
+//! looser temperature so the user can keep the reply they like best. Variants
+//! are plain completions (no tools) and nothing is saved until one is kept.
+
+use super::builder::AgentService;
+use crate::brain::agent::context::AgentContext;
+use crate::brain::agent::error::{AgentError, Result};
+use crate::brain::provider::LLMRequest;
+use crate::services::{MessageService, SessionService};
+use futures::stream::{self, StreamExt};
+use uuid::Uuid;
+
+/// Most variants a single `/variants` call may request
+pub const MAX_VARIANTS: usize = 5;
+
+/// Sampling temperature for variants — a little looser than typical defaults
+pub const VARIANT_TEMPERATURE: f32 = 0.9;
+
+/// Variant requests in flight at once
+const MAX_CONCURRENT_VARIANTS: usize = 3;
+
+impl AgentService {
+    /// Generate up to `n` alternative replies to the session's last user
+    /// message at `temperature`. Failed requests are dropped; this only errors
+    /// when none succeed. Token usage is added to the session.
+    ///
+    /// Returns the id of the user message the variants answer alongside them,
+    /// for `keep_variant`.
+    pub async fn generate_variants(
+        &self,
+        session_id: Uuid,
+        n: usize,
+        temperature: f32,
+        model: Option<String>,
+    ) -> Result<(Uuid, Vec<String>)> {
+        let message_service = MessageService::new(self.context.clone());
+        let all_messages = message_service
+            .list_messages_for_session(session_id)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?;
+        let mut db_messages = Self::messages_from_last_compaction(all_messages);
+        let last_user = db_messages
+            .iter()
+            .rposition(|m| m.role == "user")
+            .ok_or_else(|| AgentError::Internal("No user message to regenerate".to_string()))?;
+        let anchor_id = db_messages[last_user].id;
+        db_messages.truncate(last_user + 1);
+
+        let context =
+            AgentContext::from_db_messages(session_id, db_messages, self.context_limit as usize);
+        let provider = self
+            .provider
+            .read()
+            .expect("provider lock poisoned")
+            .clone();
+        let model = model.unwrap_or_else(|| provider.default_model().to_string());
+        let mut request = LLMRequest::new(model, context.messages)
+            .with_max_tokens(self.max_tokens)
+            .with_temperature(temperature);
+        if let Some(brain) = &self.default_system_brain {
+            request = request.with_system(brain.clone());
+        }
+        let request = self.apply_sampling(session_id, request);
+
+        let results: Vec<_> = stream::iter(0..n.clamp(1, MAX_VARIANTS))
+            .map(|_| {
+                let provider = provider.clone();
+                let request = request.clone();
+                async move { provider.complete(request).await }
+            })
+            .buffer_unordered(MAX_CONCURRENT_VARIANTS)
+            .collect()
+            .await;
+
+        let session_service = SessionService::new(self.context.clone());
+        let mut variants = Vec::new();
+        let mut last_error = None;
+        for result in results {
+            match result {
+                Ok(response) => {
+                    let tokens = response.usage.total();
+                    let cost = provider.calculate_cost(
+                        &response.model,
+                        response.usage.billable_input_tokens(),
+                        response.usage.output_tokens,
+                    );
+                    session_service
+                        .update_session_usage(session_id, tokens as i32, cost)
+                        .await
+                        .map_err(|e| AgentError::Database(e.to_string()))?;
+                    let text = Self::extract_text_from_response(&response);
+                    if !text.trim().is_empty() {
+                        variants.push(text);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Variant request failed: {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if variants.is_empty() {
+            return Err(match last_error {
+                Some(e) => AgentError::Provider(e),
+                None => AgentError::Internal("Every variant came back empty".to_string()),
+            });
+        }
+        Ok((anchor_id, variants))
+    }
+
+    /// Make `content` the reply to `anchor_id`, the user message returned by
+    /// `generate_variants`: whatever follows it is deleted and `content` is
+    /// saved as the assistant turn. Returns the new message id.
+    pub async fn keep_variant(
+        &self,
+        session_id: Uuid,
+        anchor_id: Uuid,
+        content: String,
+    ) -> Result<Uuid> {
+        let message_service = MessageService::new(self.context.clone());
+        let messages = message_service
+            .list_messages_for_session(session_id)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?;
+        let anchor = messages
+            .iter()
+            .position(|m| m.id == anchor_id)
+            .ok_or_else(|| {
+                AgentError::Internal("The message these variants answer is gone".to_string())
+            })?;
+
+        for message in &messages[anchor + 1..] {
+            message_service
+                .delete_message(message.id)
+                .await
+                .map_err(|e| AgentError::Database(e.to_string()))?;
+        }
+        let kept = message_service
+            .create_message(session_id, "assistant".to_string(), content)
+            .await
+            .map_err(|e| AgentError::Database(e.to_string()))?;
+        Ok(kept.id)
+    }
+}
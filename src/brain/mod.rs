@@ -14,7 +14,7 @@ pub mod tools;
 // Brain re-exports
 pub use commands::{CommandLoader, UserCommand};
 pub use prompt_builder::BrainLoader;
-pub use self_update::SelfUpdater;
+pub use self_update::{SelfUpdater, SourceDiff};
 
 // LLM re-exports
 pub use agent::{AgentContext, AgentError, AgentService};
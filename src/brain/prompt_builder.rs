@@ -3,6 +3,7 @@
 //! Reads workspace markdown files and assembles the system brain dynamically
 //! each turn, so edits to brain files take effect immediately.
 
+use crate::brain::tokenizer::count_tokens;
 use std::path::PathBuf;
 
 /// Core brain files — always injected (personality + identity only).
@@ -22,7 +23,7 @@ pub(crate) const CONTEXTUAL_BRAIN_FILES: &[(&str, &str)] = &[
 ];
 
 /// All brain files in assembly order — kept for `build_system_brain` (full mode).
-const BRAIN_FILES: &[(&str, &str)] = &[
+pub(crate) const BRAIN_FILES: &[(&str, &str)] = &[
     ("SOUL.md", "personality"),
     ("IDENTITY.md", "identity"),
     ("USER.md", "user"),
@@ -94,12 +95,43 @@ ALWAYS explore first before answering questions about a codebase. Don't guess -
 /// Loads brain workspace files and assembles the system brain.
 pub struct BrainLoader {
     workspace_path: PathBuf,
+    /// `[brain] include` — files to inject; `None` keeps each mode's defaults
+    include: Option<Vec<String>>,
+    /// `[brain] max_tokens` — cap for the injected files combined
+    max_tokens: Option<usize>,
+    /// `[agent] name` — who the preamble says the agent is
+    agent_name: String,
 }
 
 impl BrainLoader {
     /// Create a new BrainLoader with the given workspace path.
     pub fn new(workspace_path: PathBuf) -> Self {
-        Self { workspace_path }
+        Self {
+            workspace_path,
+            include: None,
+            max_tokens: None,
+            agent_name: "OpenCrabs".to_string(),
+        }
+    }
+
+    /// Apply the `[brain]` inclusion list and token budget.
+    pub fn with_policy(mut self, policy: &crate::config::BrainConfig) -> Self {
+        self.include = policy.include.clone();
+        self.max_tokens = policy.max_tokens;
+        self
+    }
+
+    /// Introduce the agent by `name` in the preamble instead of "OpenCrabs".
+    pub fn with_agent_name(mut self, name: &str) -> Self {
+        if !name.trim().is_empty() {
+            self.agent_name = name.trim().to_string();
+        }
+        self
+    }
+
+    /// The brain preamble naming the configured agent
+    fn preamble(&self) -> String {
+        BRAIN_PREAMBLE.replacen("OpenCrabs", &self.agent_name, 1)
     }
 
     /// Resolve the brain path: `~/.opencrabs/`
@@ -116,6 +148,50 @@ impl BrainLoader {
         std::fs::read_to_string(&path).ok()
     }
 
+    /// Load the brain files to inject, as `(filename, label, trimmed content)`.
+    ///
+    /// Uses `defaults` unless `[brain] include` names the files instead, skips
+    /// missing and empty ones, then drops files from the end of the list until
+    /// the rest fit in `[brain] max_tokens`.
+    fn injected_files(
+        &self,
+        defaults: &[(&'static str, &'static str)],
+    ) -> Vec<(&'static str, &'static str, String)> {
+        let selected: Vec<(&'static str, &'static str)> = match &self.include {
+            Some(names) => BRAIN_FILES
+                .iter()
+                .filter(|(filename, _)| names.iter().any(|n| is_brain_file(n, filename)))
+                .copied()
+                .collect(),
+            None => defaults.to_vec(),
+        };
+
+        let mut files: Vec<(&'static str, &'static str, String)> = selected
+            .into_iter()
+            .filter_map(|(filename, label)| {
+                let content = self.load_file(filename)?;
+                let trimmed = content.trim();
+                (!trimmed.is_empty()).then(|| (filename, label, trimmed.to_string()))
+            })
+            .collect();
+
+        if let Some(cap) = self.max_tokens {
+            let mut total: usize = files.iter().map(|(_, _, c)| count_tokens(c)).sum();
+            while total > cap
+                && let Some((filename, _, content)) = files.pop()
+            {
+                total -= count_tokens(&content);
+                tracing::debug!(
+                    "Brain: dropped {} to fit [brain] max_tokens = {}",
+                    filename,
+                    cap
+                );
+            }
+        }
+
+        files
+    }
+
     /// Build the full system brain from workspace files + brain preamble.
     ///
     /// Assembly order:
@@ -136,20 +212,15 @@ impl BrainLoader {
         let mut prompt = String::with_capacity(8192);
 
         // 1. Brain preamble — always present
-        prompt.push_str(BRAIN_PREAMBLE);
+        prompt.push_str(&self.preamble());
         prompt.push_str("\n\n");
 
         // 2-7. Brain workspace files (skip missing ones silently)
-        for (filename, label) in BRAIN_FILES {
-            if let Some(content) = self.load_file(filename) {
-                let trimmed = content.trim();
-                if !trimmed.is_empty() {
-                    prompt.push_str(&format!(
-                        "--- {} ({}) ---\n{}\n\n",
-                        filename, label, trimmed
-                    ));
-                }
-            }
+        for (filename, label, content) in self.injected_files(BRAIN_FILES) {
+            prompt.push_str(&format!(
+                "--- {} ({}) ---\n{}\n\n",
+                filename, label, content
+            ));
         }
 
         // 8. Runtime info
@@ -165,10 +236,7 @@ impl BrainLoader {
                 prompt.push_str(&format!("Working directory: {}\n", wd));
             }
             prompt.push_str(&format!("OS: {}\n", std::env::consts::OS));
-            prompt.push_str(&format!(
-                "Timestamp: {}\n",
-                chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-            ));
+            prompt.push_str(&format!("Timestamp: {}\n", prompt_timestamp()));
             prompt.push('\n');
         }
 
@@ -200,26 +268,24 @@ impl BrainLoader {
         let mut prompt = String::with_capacity(4096);
 
         // 1. Brain preamble — always present
-        prompt.push_str(BRAIN_PREAMBLE);
+        prompt.push_str(&self.preamble());
         prompt.push_str("\n\n");
 
-        // 2. Core files only (SOUL.md + IDENTITY.md)
-        for (filename, label) in CORE_BRAIN_FILES {
-            if let Some(content) = self.load_file(filename) {
-                let trimmed = content.trim();
-                if !trimmed.is_empty() {
-                    prompt.push_str(&format!(
-                        "--- {} ({}) ---\n{}\n\n",
-                        filename, label, trimmed
-                    ));
-                }
-            }
+        // 2. Core files only (SOUL.md + IDENTITY.md, or the [brain] include list)
+        let injected = self.injected_files(CORE_BRAIN_FILES);
+        for (filename, label, content) in &injected {
+            prompt.push_str(&format!(
+                "--- {} ({}) ---\n{}\n\n",
+                filename, label, content
+            ));
         }
 
-        // 3. Memory index — list contextual files that exist on disk
+        // 3. Memory index — list contextual files that exist on disk and
+        //    weren't injected above
         let available: Vec<(&str, &str)> = CONTEXTUAL_BRAIN_FILES
             .iter()
             .filter(|(name, _)| self.workspace_path.join(name).exists())
+            .filter(|(name, _)| !injected.iter().any(|(f, _, _)| f == name))
             .copied()
             .collect();
 
@@ -276,10 +342,7 @@ impl BrainLoader {
                 prompt.push_str(&format!("Working directory: {}\n", wd));
             }
             prompt.push_str(&format!("OS: {}\n", std::env::consts::OS));
-            prompt.push_str(&format!(
-                "Timestamp: {}\n",
-                chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-            ));
+            prompt.push_str(&format!("Timestamp: {}\n", prompt_timestamp()));
             prompt.push('\n');
         }
 
@@ -296,6 +359,16 @@ impl BrainLoader {
     }
 }
 
+/// Whether a `[brain] include` entry names `filename` — case-insensitive,
+/// with or without the `.md` extension.
+fn is_brain_file(name: &str, filename: &str) -> bool {
+    let name = name.trim();
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    filename
+        .strip_suffix(".md")
+        .is_some_and(|stem| stem.eq_ignore_ascii_case(name))
+}
+
 /// Runtime information injected into the system brain.
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeInfo {
@@ -304,6 +377,21 @@ pub struct RuntimeInfo {
     pub working_directory: Option<String>,
 }
 
+/// Current time for the runtime block: UTC, plus the user's local time when
+/// `[general] timezone` is set.
+fn prompt_timestamp() -> String {
+    let now = chrono::Utc::now();
+    let utc = now.format("%Y-%m-%d %H:%M:%S UTC");
+    match crate::config::timezone() {
+        Some(tz) => format!(
+            "{utc} (user's local time: {} {})",
+            crate::config::format_local(now, "%Y-%m-%d %H:%M"),
+            tz.name()
+        ),
+        None => utc.to_string(),
+    }
+}
+
 #[cfg(test)]
 #[path = "prompt_builder_tests.rs"]
 mod prompt_builder_tests;
@@ -29,6 +29,16 @@ fn test_core_brain_contains_preamble() {
     );
 }
 
+#[test]
+fn test_preamble_uses_configured_agent_name() {
+    let dir = TempDir::new().unwrap();
+    let brain = loader(&dir)
+        .with_agent_name("Ferris")
+        .build_core_brain(None, None);
+    assert!(brain.contains("You are Ferris, an AI orchestration agent"));
+    assert!(!brain.contains("You are OpenCrabs"));
+}
+
 #[test]
 fn test_soul_md_is_injected_in_core() {
     let dir = TempDir::new().unwrap();
@@ -243,3 +253,73 @@ fn test_core_brain_is_smaller_than_full_brain_when_contextual_files_exist() {
         full_len
     );
 }
+
+// ── [brain] include / max_tokens ──────────────────────────────────────────────
+
+fn policy(include: &[&str], max_tokens: Option<usize>) -> crate::config::BrainConfig {
+    crate::config::BrainConfig {
+        include: Some(include.iter().map(|s| s.to_string()).collect()),
+        max_tokens,
+    }
+}
+
+#[test]
+fn test_include_injects_only_selected_files() {
+    let dir = TempDir::new().unwrap();
+    write(&dir, "SOUL.md", "soul content");
+    write(&dir, "IDENTITY.md", "identity content");
+    write(&dir, "USER.md", "user content");
+    write(&dir, "TOOLS.md", "tools content");
+    write(&dir, "AGENTS.md", "agents content");
+
+    let brain = loader(&dir)
+        .with_policy(&policy(&["SOUL", "user.md"], None))
+        .build_system_brain(None, None);
+
+    assert!(brain.contains("soul content"));
+    assert!(brain.contains("user content"));
+    assert!(!brain.contains("identity content"));
+    assert!(!brain.contains("tools content"));
+    assert!(!brain.contains("agents content"));
+}
+
+#[test]
+fn test_include_in_core_brain_moves_files_out_of_the_index() {
+    let dir = TempDir::new().unwrap();
+    write(&dir, "SOUL.md", "soul content");
+    write(&dir, "USER.md", "user content");
+    write(&dir, "TOOLS.md", "tools content");
+
+    let brain = loader(&dir)
+        .with_policy(&policy(&["SOUL", "USER"], None))
+        .build_core_brain(None, None);
+
+    assert!(brain.contains("user content"));
+    assert!(
+        !brain.contains("**USER.md**"),
+        "injected file must not be indexed"
+    );
+    assert!(
+        brain.contains("**TOOLS.md**"),
+        "excluded file stays loadable"
+    );
+}
+
+#[test]
+fn test_max_tokens_drops_lowest_priority_files_first() {
+    let dir = TempDir::new().unwrap();
+    write(&dir, "SOUL.md", "Be kind.");
+    write(&dir, "IDENTITY.md", "Name: Crab.");
+    write(&dir, "TOOLS.md", &"verbose tool guidance ".repeat(500));
+
+    let brain = loader(&dir)
+        .with_policy(&crate::config::BrainConfig {
+            include: None,
+            max_tokens: Some(100),
+        })
+        .build_system_brain(None, None);
+
+    assert!(brain.contains("Be kind."));
+    assert!(brain.contains("Name: Crab."));
+    assert!(!brain.contains("verbose tool guidance"));
+}
@@ -2,7 +2,8 @@
 //!
 //! Implements the Provider trait for Anthropic's Claude models.
 //! Supports both standard API key auth (`x-api-key`) and OAuth Bearer tokens
-//! (detected via `sk-ant-oat` prefix).
+//! (detected via `sk-ant-oat` prefix). OAuth tokens obtained with
+//! `/login anthropic` are refreshed automatically before they expire.
 //!
 //! ## Supported Models
 //! - claude-opus-4-6
@@ -14,6 +15,7 @@
 //! - claude-3-sonnet-20240229 (legacy)
 //! - claude-3-haiku-20240307 (legacy)
 
+use super::anthropic_oauth::{self, OAuthTokens};
 use super::error::{ProviderError, Result};
 use super::r#trait::{Provider, ProviderStream};
 use super::types::*;
@@ -21,6 +23,7 @@ use async_trait::async_trait;
 use futures::stream::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
@@ -29,6 +32,7 @@ const ANTHROPIC_VERSION: &str = "2023-06-01";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300); // Total request timeout
 const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10); // Connection timeout
 const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90); // Keep connections alive
+const MAX_TEMPERATURE: f32 = 1.0; // Anthropic rejects anything hotter
 
 /// Anthropic provider for Claude models
 #[derive(Clone)]
@@ -36,6 +40,10 @@ pub struct AnthropicProvider {
     api_key: String,
     client: Client,
     custom_default_model: Option<String>,
+    /// Refreshable OAuth credentials; `None` for API keys and bare setup tokens
+    oauth: Option<Arc<tokio::sync::Mutex<OAuthTokens>>>,
+    /// Mark the system prompt and tool definitions as cacheable
+    prompt_caching: bool,
 }
 
 impl AnthropicProvider {
@@ -53,6 +61,8 @@ impl AnthropicProvider {
             api_key,
             client,
             custom_default_model: None,
+            oauth: None,
+            prompt_caching: false,
         }
     }
 
@@ -62,6 +72,8 @@ impl AnthropicProvider {
             api_key,
             client,
             custom_default_model: None,
+            oauth: None,
+            prompt_caching: false,
         }
     }
 
@@ -71,6 +83,21 @@ impl AnthropicProvider {
         self
     }
 
+    /// Add `cache_control` breakpoints after the tool definitions and the
+    /// system prompt (`[providers] prompt_caching`), so repeated turns read
+    /// that prefix from the prompt cache.
+    pub fn with_prompt_caching(mut self, enabled: bool) -> Self {
+        self.prompt_caching = enabled;
+        self
+    }
+
+    /// Refresh the access token from `tokens` whenever it is about to expire
+    pub fn with_oauth(mut self, tokens: OAuthTokens) -> Self {
+        self.api_key = tokens.access_token.clone();
+        self.oauth = Some(Arc::new(tokio::sync::Mutex::new(tokens)));
+        self
+    }
+
     /// Check if the API key is an OAuth token (starts with sk-ant-oat)
     fn is_oauth_token(&self) -> bool {
         self.api_key.starts_with("sk-ant-oat")
@@ -78,13 +105,41 @@ impl AnthropicProvider {
 
     /// Build request headers
     fn headers(&self) -> reqwest::header::HeaderMap {
+        Self::headers_for(&self.api_key)
+    }
+
+    /// Headers for the next request, refreshing an expiring OAuth token first.
+    /// A failed refresh is logged and the old token is tried anyway — the API
+    /// error then surfaces through the normal error path.
+    async fn current_headers(&self) -> reqwest::header::HeaderMap {
+        let oauth = match &self.oauth {
+            Some(oauth) if self.is_oauth_token() => oauth,
+            _ => return self.headers(),
+        };
+        let mut tokens = oauth.lock().await;
+        if tokens.needs_refresh(chrono::Utc::now().timestamp()) {
+            match anthropic_oauth::refresh(&self.client, &tokens.refresh_token).await {
+                Ok(fresh) => {
+                    tracing::info!("Refreshed Anthropic OAuth token");
+                    if let Err(e) = fresh.save() {
+                        tracing::warn!("Failed to persist refreshed Anthropic token: {}", e);
+                    }
+                    *tokens = fresh;
+                }
+                Err(e) => tracing::warn!("Anthropic OAuth token refresh failed: {}", e),
+            }
+        }
+        Self::headers_for(&tokens.access_token)
+    }
+
+    fn headers_for(api_key: &str) -> reqwest::header::HeaderMap {
         let mut headers = reqwest::header::HeaderMap::new();
 
-        if self.is_oauth_token() {
+        if api_key.starts_with("sk-ant-oat") {
             // OAuth token: use Authorization: Bearer header
             headers.insert(
                 reqwest::header::AUTHORIZATION,
-                format!("Bearer {}", self.api_key)
+                format!("Bearer {}", api_key)
                     .parse()
                     .expect("Invalid OAuth token format"),
             );
@@ -97,7 +152,7 @@ impl AnthropicProvider {
             // Standard API key: use x-api-key header
             headers.insert(
                 "x-api-key",
-                self.api_key.parse().expect("Invalid API key format"),
+                api_key.parse().expect("Invalid API key format"),
             );
         }
 
@@ -114,13 +169,43 @@ impl AnthropicProvider {
 
     /// Convert our generic request to Anthropic-specific format
     fn to_anthropic_request(&self, request: LLMRequest) -> AnthropicRequest {
+        // Extended thinking returns `thinking` blocks that must be echoed back
+        // across tool calls; until those are kept, stay on the default
+        if let Some(effort) = request.reasoning_effort {
+            tracing::debug!("Anthropic: ignoring reasoning_effort = {}", effort);
+        }
+        let cache_control = self.prompt_caching.then_some(CacheControl::EPHEMERAL);
+        let system = request.system.map(|text| match cache_control {
+            Some(cache_control) => SystemPrompt::Blocks(vec![SystemBlock {
+                kind: "text",
+                text,
+                cache_control: Some(cache_control),
+            }]),
+            None => SystemPrompt::Text(text),
+        });
+        let tools = request.tools.map(|tools| {
+            let last = tools.len().saturating_sub(1);
+            tools
+                .into_iter()
+                .enumerate()
+                .map(|(i, tool)| AnthropicTool {
+                    tool,
+                    cache_control: cache_control.filter(|_| i == last),
+                })
+                .collect()
+        });
+        // Anthropic accepts temperatures up to 1.0 and rejects top_p alongside
+        // a temperature, so the temperature wins
+        let temperature = request.temperature.map(|t| t.min(MAX_TEMPERATURE));
+        let top_p = request.top_p.filter(|_| temperature.is_none());
         AnthropicRequest {
             model: request.model,
             messages: request.messages,
-            system: request.system,
+            system,
             max_tokens: request.max_tokens.unwrap_or(16384),
-            temperature: request.temperature,
-            tools: request.tools,
+            temperature,
+            top_p,
+            tools,
             stream: Some(request.stream),
             metadata: request.metadata,
         }
@@ -223,7 +308,7 @@ impl Provider for AnthropicProvider {
                 let response = self
                     .client
                     .post(ANTHROPIC_API_URL)
-                    .headers(self.headers())
+                    .headers(self.current_headers().await)
                     .json(&anthropic_request)
                     .send()
                     .await?;
@@ -239,8 +324,9 @@ impl Provider for AnthropicProvider {
                 let llm_response = self.from_anthropic_response(anthropic_response);
 
                 tracing::info!(
-                    "Anthropic API response: input_tokens={}, output_tokens={}, stop_reason={:?}",
+                    "Anthropic API response: input_tokens={}, cache_read={}, output_tokens={}, stop_reason={:?}",
                     llm_response.usage.input_tokens,
+                    llm_response.usage.cache_read_input_tokens,
                     llm_response.usage.output_tokens,
                     llm_response.stop_reason
                 );
@@ -279,7 +365,7 @@ impl Provider for AnthropicProvider {
                 let response = self
                     .client
                     .post(ANTHROPIC_API_URL)
-                    .headers(self.headers())
+                    .headers(self.current_headers().await)
                     .json(&anthropic_request)
                     .send()
                     .await?;
@@ -397,18 +483,10 @@ impl Provider for AnthropicProvider {
             data: Vec<ModelEntry>,
         }
 
-        let mut req = self
+        let req = self
             .client
             .get(ANTHROPIC_MODELS_URL)
-            .header("anthropic-version", ANTHROPIC_VERSION);
-
-        if self.api_key.starts_with("sk-ant-oat") {
-            req = req
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .header("anthropic-beta", "oauth-2025-04-20");
-        } else {
-            req = req.header("x-api-key", &self.api_key);
-        }
+            .headers(self.current_headers().await);
 
         match req.send().await {
             Ok(resp) if resp.status().is_success() => match resp.json::<ModelsResponse>().await {
@@ -452,18 +530,57 @@ struct AnthropicRequest {
     model: String,
     messages: Vec<Message>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<SystemPrompt>,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    tools: Option<Vec<Tool>>,
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<AnthropicTool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<std::collections::HashMap<String, String>>,
 }
 
+/// `system` is a plain string, or text blocks when a cache breakpoint is set
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum SystemPrompt {
+    Text(String),
+    Blocks(Vec<SystemBlock>),
+}
+
+#[derive(Debug, Serialize)]
+struct SystemBlock {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    #[serde(flatten)]
+    tool: Tool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
+/// Prompt-cache breakpoint: everything up to and including the marked block
+/// is cached
+#[derive(Debug, Clone, Copy, Serialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+impl CacheControl {
+    const EPHEMERAL: Self = Self { kind: "ephemeral" };
+}
+
 // Anthropic-specific response format
 #[derive(Debug, Deserialize)]
 struct AnthropicResponse {
@@ -590,4 +707,57 @@ mod tests {
         assert!(provider.supports_tools());
         assert!(provider.supports_vision());
     }
+
+    fn cacheable_request() -> LLMRequest {
+        let tool = |name: &str| Tool {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({"type": "object"}),
+        };
+        LLMRequest::new("claude-sonnet-4-5", vec![Message::user("hi")])
+            .with_system("brain files")
+            .with_tools(vec![tool("read_file"), tool("bash")])
+    }
+
+    #[test]
+    fn test_prompt_caching_marks_system_and_tools() {
+        let provider = AnthropicProvider::new("test-key".to_string()).with_prompt_caching(true);
+        let body =
+            serde_json::to_value(provider.to_anthropic_request(cacheable_request())).unwrap();
+
+        assert_eq!(body["system"][0]["text"], "brain files");
+        assert_eq!(body["system"][0]["cache_control"]["type"], "ephemeral");
+        // One breakpoint after the last tool covers every definition
+        assert!(body["tools"][0].get("cache_control").is_none());
+        assert_eq!(body["tools"][1]["name"], "bash");
+        assert_eq!(body["tools"][1]["cache_control"]["type"], "ephemeral");
+    }
+
+    #[test]
+    fn test_prompt_caching_off_sends_plain_system() {
+        let provider = AnthropicProvider::new("test-key".to_string());
+        let body =
+            serde_json::to_value(provider.to_anthropic_request(cacheable_request())).unwrap();
+
+        assert_eq!(body["system"], "brain files");
+        assert!(body["tools"][1].get("cache_control").is_none());
+    }
+
+    #[test]
+    fn test_sampling_fits_anthropic_limits() {
+        let provider = AnthropicProvider::new("test-key".to_string());
+        let request = || LLMRequest::new("claude-sonnet-4-5", vec![Message::user("hi")]);
+
+        let body = serde_json::to_value(
+            provider.to_anthropic_request(request().with_temperature(1.6).with_top_p(0.9)),
+        )
+        .unwrap();
+        assert_eq!(body["temperature"], 1.0);
+        assert!(body.get("top_p").is_none());
+
+        let body =
+            serde_json::to_value(provider.to_anthropic_request(request().with_top_p(0.9))).unwrap();
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["top_p"].as_f64().map(|p| p as f32), Some(0.9));
+    }
 }
@@ -0,0 +1,258 @@
+//! Anthropic OAuth (Claude Pro / Max subscriptions)
+//!
+//! Authorization-code flow with PKCE. The user opens the authorize URL,
+//! approves access, and pastes back the `code#state` shown on the callback
+//! page. The access token (`sk-ant-oat…`) is stored as the Anthropic
+//! `api_key` in keys.toml next to its refresh token and expiry, so
+//! `AnthropicProvider` can refresh it before it runs out.
+
+use super::error::{ProviderError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::Path;
+
+const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
+const AUTHORIZE_URL: &str = "https://claude.ai/oauth/authorize";
+const TOKEN_URL: &str = "https://console.anthropic.com/v1/oauth/token";
+const REDIRECT_URI: &str = "https://console.anthropic.com/oauth/code/callback";
+const SCOPES: &str = "org:create_api_key user:profile user:inference";
+
+/// Refresh this many seconds before the reported expiry
+const EXPIRY_SKEW_SECS: i64 = 300;
+
+/// keys.toml section holding the Anthropic credentials
+const KEYS_SECTION: &str = "providers.anthropic";
+
+/// A login in progress: the URL to open, the PKCE verifier to finish it and
+/// the `state` the callback must echo back.
+#[derive(Debug, Clone)]
+pub struct PkceLogin {
+    pub url: String,
+    verifier: String,
+    state: String,
+}
+
+impl PkceLogin {
+    /// Generate a fresh verifier/challenge pair, an independent random
+    /// `state`, and the authorize URL. The verifier never leaves this process.
+    pub fn start() -> Self {
+        let verifier = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+        let state = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+        let challenge = URL_SAFE_NO_PAD.encode(openssl::sha::sha256(verifier.as_bytes()));
+        let url = format!(
+            "{AUTHORIZE_URL}?code=true&client_id={CLIENT_ID}&response_type=code\
+             &redirect_uri={}&scope={}&code_challenge={challenge}\
+             &code_challenge_method=S256&state={state}",
+            urlencoding::encode(REDIRECT_URI),
+            urlencoding::encode(SCOPES),
+        );
+        Self {
+            url,
+            verifier,
+            state,
+        }
+    }
+
+    /// Trade the pasted `code#state` for tokens.
+    pub async fn exchange(&self, client: &Client, pasted: &str) -> Result<OAuthTokens> {
+        let pasted = pasted.trim();
+        let (code, state) = pasted.split_once('#').unwrap_or((pasted, ""));
+        if code.is_empty() {
+            return Err(ProviderError::InvalidRequest(
+                "empty authorization code".to_string(),
+            ));
+        }
+        if state != self.state {
+            return Err(ProviderError::InvalidRequest(
+                "authorization code belongs to a different login attempt".to_string(),
+            ));
+        }
+        token_request(
+            client,
+            serde_json::json!({
+                "grant_type": "authorization_code",
+                "code": code,
+                "state": state,
+                "client_id": CLIENT_ID,
+                "redirect_uri": REDIRECT_URI,
+                "code_verifier": self.verifier,
+            }),
+        )
+        .await
+    }
+}
+
+/// Exchange a refresh token for a new access token.
+pub async fn refresh(client: &Client, refresh_token: &str) -> Result<OAuthTokens> {
+    token_request(
+        client,
+        serde_json::json!({
+            "grant_type": "refresh_token",
+            "refresh_token": refresh_token,
+            "client_id": CLIENT_ID,
+        }),
+    )
+    .await
+}
+
+async fn token_request(client: &Client, body: serde_json::Value) -> Result<OAuthTokens> {
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+        refresh_token: String,
+        expires_in: i64,
+    }
+
+    let response = client.post(TOKEN_URL).json(&body).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(ProviderError::ApiError {
+            status: status.as_u16(),
+            message: format!("OAuth token request failed: {}", message.trim()),
+            error_type: None,
+        });
+    }
+    let token: TokenResponse = response.json().await?;
+    Ok(OAuthTokens {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: chrono::Utc::now().timestamp() + token.expires_in,
+    })
+}
+
+/// Stored OAuth credentials. `expires_at` is a unix timestamp in seconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: i64,
+}
+
+impl OAuthTokens {
+    /// Whether the access token is expired or about to be.
+    pub fn needs_refresh(&self, now: i64) -> bool {
+        now >= self.expires_at - EXPIRY_SKEW_SECS
+    }
+
+    /// Load from `~/.opencrabs/keys.toml`, if a login has been stored.
+    pub fn load() -> Option<Self> {
+        Self::load_from(&crate::config::keys_path())
+    }
+
+    /// Load from a keys.toml at `path`. Only an `sk-ant-oat` api_key with a
+    /// refresh token counts — a plain API key has nothing to refresh.
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let doc: toml::Value = toml::from_str(&content).ok()?;
+        let section = doc.get("providers")?.get("anthropic")?;
+        let field = |key: &str| section.get(key)?.as_str().map(str::to_string);
+
+        let access_token = field("api_key").filter(|k| k.starts_with("sk-ant-oat"))?;
+        Some(Self {
+            access_token,
+            refresh_token: field("oauth_refresh_token")?,
+            // Unknown expiry: refresh on first use
+            expires_at: field("oauth_expires_at")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+        })
+    }
+
+    /// Persist to keys.toml as the Anthropic api_key plus refresh metadata.
+    pub fn save(&self) -> anyhow::Result<()> {
+        crate::config::write_secret_key(KEYS_SECTION, "api_key", &self.access_token)?;
+        crate::config::write_secret_key(KEYS_SECTION, "oauth_refresh_token", &self.refresh_token)?;
+        crate::config::write_secret_key(
+            KEYS_SECTION,
+            "oauth_expires_at",
+            &self.expires_at.to_string(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorize_url_carries_pkce_challenge() {
+        let login = PkceLogin::start();
+        let challenge = URL_SAFE_NO_PAD.encode(openssl::sha::sha256(login.verifier.as_bytes()));
+        assert!(login.url.starts_with(AUTHORIZE_URL));
+        assert!(login.url.contains(&format!("code_challenge={challenge}")));
+        assert!(login.url.contains("code_challenge_method=S256"));
+        assert!(login.url.contains(&format!("state={}", login.state)));
+        // The verifier stays secret: state is generated separately
+        assert_ne!(login.state, login.verifier);
+        assert!(!login.url.contains(&login.verifier));
+        // Each login gets its own verifier and state
+        let other = PkceLogin::start();
+        assert_ne!(login.verifier, other.verifier);
+        assert_ne!(login.state, other.state);
+    }
+
+    #[test]
+    fn test_needs_refresh_with_skew() {
+        let tokens = OAuthTokens {
+            access_token: "sk-ant-oat01-a".into(),
+            refresh_token: "r".into(),
+            expires_at: 10_000,
+        };
+        assert!(!tokens.needs_refresh(10_000 - EXPIRY_SKEW_SECS - 1));
+        assert!(tokens.needs_refresh(10_000 - EXPIRY_SKEW_SECS));
+        assert!(tokens.needs_refresh(20_000));
+    }
+
+    #[test]
+    fn test_load_from_keys_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.toml");
+
+        std::fs::write(
+            &path,
+            "[providers.anthropic]\napi_key = \"sk-ant-oat01-abc\"\n\
+             oauth_refresh_token = \"sk-ant-ort01-xyz\"\noauth_expires_at = \"1700000000\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            OAuthTokens::load_from(&path),
+            Some(OAuthTokens {
+                access_token: "sk-ant-oat01-abc".into(),
+                refresh_token: "sk-ant-ort01-xyz".into(),
+                expires_at: 1_700_000_000,
+            })
+        );
+
+        // Plain API key: nothing to refresh
+        std::fs::write(
+            &path,
+            "[providers.anthropic]\napi_key = \"sk-ant-api03-abc\"\n\
+             oauth_refresh_token = \"stale\"\n",
+        )
+        .unwrap();
+        assert_eq!(OAuthTokens::load_from(&path), None);
+    }
+
+    #[tokio::test]
+    async fn test_exchange_rejects_foreign_state() {
+        let login = PkceLogin::start();
+        let err = login
+            .exchange(&Client::new(), "somecode#not-our-state")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidRequest(_)));
+
+        // The verifier is not the state, and a missing state is refused
+        for pasted in [
+            format!("somecode#{}", login.verifier),
+            "somecode".to_string(),
+        ] {
+            let err = login.exchange(&Client::new(), &pasted).await.unwrap_err();
+            assert!(matches!(err, ProviderError::InvalidRequest(_)));
+        }
+    }
+}
@@ -83,6 +83,52 @@ fn filter_think_tags(text: &str, inside_think: &mut bool, active_close_tag: &mut
     result
 }
 
+/// Model families that take `reasoning_effort`; others reject or ignore it
+const REASONING_MODEL_PREFIXES: &[&str] = &["o1", "o3", "o4", "gpt-5", "grok-3-mini"];
+
+/// Whether `model` is a reasoning model that accepts `reasoning_effort`.
+/// `gpt-5-chat` is the non-reasoning variant.
+pub(crate) fn is_reasoning_model(model: &str) -> bool {
+    let model = model.to_ascii_lowercase();
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    !name.starts_with("gpt-5-chat") && REASONING_MODEL_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// Model families served over OpenAI-compatible APIs that take image input
+const VISION_MODEL_PREFIXES: &[&str] = &[
+    "gpt-4o",
+    "chatgpt-4o",
+    "gpt-4.1",
+    "gpt-4-turbo",
+    "gpt-4-vision",
+    "gpt-5",
+    "o1",
+    "o3",
+    "o4",
+    "claude",
+    "gemini",
+    "gemma-3",
+    "grok-4",
+    "pixtral",
+    "llava",
+    "llama-4",
+    "minicpm-v",
+];
+
+/// Whether `model` accepts images, judged by its name. Router prefixes
+/// (`openai/gpt-4o`) are ignored; `*-mini` reasoning models are text-only.
+pub(crate) fn model_accepts_images(model: &str) -> bool {
+    let model = model.to_ascii_lowercase();
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    if name.starts_with("o1-mini") || name.starts_with("o3-mini") {
+        return false;
+    }
+    VISION_MODEL_PREFIXES.iter().any(|p| name.starts_with(p))
+        || name.contains("vision")
+        || name.contains("-vl")
+        || name.contains("4v")
+}
+
 /// Strip complete reasoning/markup blocks from non-streaming content.
 fn strip_think_blocks(text: &str) -> String {
     let mut result = text.to_string();
@@ -382,11 +428,18 @@ impl OpenAIProvider {
                 .collect()
         });
 
+        let reasoning_effort = request
+            .reasoning_effort
+            .filter(|_| is_reasoning_model(&request.model))
+            .map(|e| e.level());
+
         OpenAIRequest {
             model: request.model,
             messages,
             temperature: request.temperature,
+            top_p: request.top_p,
             max_tokens: request.max_tokens,
+            reasoning_effort,
             stream: Some(request.stream),
             stream_options: None,
             tools,
@@ -451,14 +504,7 @@ impl OpenAIProvider {
             for tool_call in tool_calls {
                 // Parse arguments JSON string
                 let input =
-                    serde_json::from_str(&tool_call.function.arguments).unwrap_or_else(|e| {
-                        tracing::warn!(
-                            "Failed to parse tool arguments for {}: {}",
-                            tool_call.function.name,
-                            e
-                        );
-                        serde_json::json!({})
-                    });
+                    parse_tool_input(&tool_call.function.name, &tool_call.function.arguments);
 
                 tracing::debug!(
                     "Converted tool call: {} with id {}",
@@ -492,6 +538,7 @@ impl OpenAIProvider {
             usage: TokenUsage {
                 input_tokens: response.usage.prompt_tokens.unwrap_or(0),
                 output_tokens: response.usage.completion_tokens.unwrap_or(0),
+                ..Default::default()
             },
         }
     }
@@ -773,8 +820,7 @@ impl Provider for OpenAIProvider {
                                 if json_str == "[DONE]" {
                                     // Flush any accumulated tool calls before DONE
                                     for (_idx, accum) in st.tool_calls.drain() {
-                                        let input = serde_json::from_str(&accum.arguments)
-                                            .unwrap_or_else(|_| serde_json::json!({}));
+                                        let input = parse_tool_input(&accum.name, &accum.arguments);
                                         tracing::info!(
                                             "[TOOL_EMIT] Flushing tool on DONE: id={}, name={}, args={}",
                                             accum.id, accum.name, &accum.arguments.chars().take(200).collect::<String>()
@@ -800,6 +846,7 @@ impl Provider for OpenAIProvider {
                                             usage: crate::brain::provider::types::TokenUsage {
                                                 input_tokens: total_input_tokens as u32,
                                                 output_tokens: 0,
+                                                ..Default::default()
                                             },
                                         }));
                                     }
@@ -821,6 +868,7 @@ impl Provider for OpenAIProvider {
                                                     usage: crate::brain::provider::types::TokenUsage {
                                                         input_tokens: 0,
                                                         output_tokens: 0,
+                                                        ..Default::default()
                                                     },
                                                 },
                                             }));
@@ -895,14 +943,7 @@ impl Provider for OpenAIProvider {
                                             && (reason == "tool_calls" || reason == "function_call") {
                                                 // Emit all accumulated tool calls
                                                 for (idx, accum) in st.tool_calls.drain() {
-                                                    let input = serde_json::from_str(&accum.arguments)
-                                                        .unwrap_or_else(|e| {
-                                                            tracing::warn!(
-                                                                "[TOOL_EMIT] Failed to parse accumulated args for '{}': {} | args: {}",
-                                                                accum.name, e, &accum.arguments.chars().take(300).collect::<String>()
-                                                            );
-                                                            serde_json::json!({})
-                                                        });
+                                                    let input = parse_tool_input(&accum.name, &accum.arguments);
                                                     tracing::info!(
                                                         "[TOOL_EMIT] Emitting tool call: idx={}, id={}, name={}, args_len={}",
                                                         idx, accum.id, accum.name, accum.arguments.len()
@@ -997,6 +1038,7 @@ impl Provider for OpenAIProvider {
                                                     usage: crate::brain::provider::types::TokenUsage {
                                                         input_tokens: raw_input,
                                                         output_tokens: raw_output,
+                                                        ..Default::default()
                                                     },
                                                 }));
                                                 events.push(Ok(StreamEvent::MessageStop));
@@ -1024,6 +1066,7 @@ impl Provider for OpenAIProvider {
                                                         usage: crate::brain::provider::types::TokenUsage {
                                                             input_tokens: input,
                                                             output_tokens: output,
+                                                            ..Default::default()
                                                         },
                                                     }));
                                                     events.push(Ok(StreamEvent::MessageStop));
@@ -1062,8 +1105,17 @@ impl Provider for OpenAIProvider {
         true
     }
 
+    fn supports_reasoning_effort(&self, model: &str) -> bool {
+        is_reasoning_model(model)
+    }
+
+    /// Images go inline when the chat model can see them. `vision_model`
+    /// only decides when no default model is configured to judge by.
     fn supports_vision(&self) -> bool {
-        self.vision_model.is_some()
+        match self.custom_default_model.as_deref() {
+            Some(model) => model_accepts_images(model),
+            None => self.vision_model.is_some(),
+        }
     }
 
     fn name(&self) -> &str {
@@ -1153,7 +1205,12 @@ struct OpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    /// `low`/`medium`/`high` for reasoning models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<&'static str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1326,6 +1383,27 @@ mod tests {
         assert_eq!(provider.context_window("unknown"), None);
     }
 
+    #[test]
+    fn test_reasoning_effort_reaches_request_body() {
+        let provider = OpenAIProvider::new("test-key".to_string());
+        let request = LLMRequest::new("o4-mini", vec![Message::user("hi")])
+            .with_reasoning_effort(ReasoningEffort::Tokens(20000));
+        let body = serde_json::to_value(provider.to_openai_request(request)).unwrap();
+        assert_eq!(body["reasoning_effort"], "high");
+
+        let plain = LLMRequest::new("o4-mini", vec![Message::user("hi")]);
+        let body = serde_json::to_value(provider.to_openai_request(plain)).unwrap();
+        assert!(body.get("reasoning_effort").is_none());
+
+        // Non-reasoning models reject the field
+        let chat = LLMRequest::new("gpt-4o", vec![Message::user("hi")])
+            .with_reasoning_effort(ReasoningEffort::High);
+        let body = serde_json::to_value(provider.to_openai_request(chat)).unwrap();
+        assert!(body.get("reasoning_effort").is_none());
+        assert!(provider.supports_reasoning_effort("openai/o3"));
+        assert!(!provider.supports_reasoning_effort("gpt-5-chat-latest"));
+    }
+
     #[test]
     fn test_calculate_cost() {
         let provider = OpenAIProvider::new("test-key".to_string());
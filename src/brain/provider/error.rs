@@ -1,5 +1,6 @@
 //! Error types for LLM providers
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Provider error types
@@ -83,6 +84,114 @@ impl ProviderError {
     }
 }
 
+/// What a provider error means for the caller, independent of which provider
+/// raised it or how its API phrases the failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderErrorKind {
+    /// API key missing, invalid or not permitted — re-run onboarding
+    Auth,
+    /// Too many requests — retry, after `retry_after` if the provider said
+    RateLimit { retry_after: Option<Duration> },
+    /// Conversation doesn't fit the model's context window — compact
+    ContextLength,
+    /// Provider-side failure (5xx, overloaded) — retry or fall back
+    ServerError,
+    /// Connection failure or timeout
+    Network,
+    /// Anything else
+    Other,
+}
+
+/// Body fragments providers use to report an oversized prompt with a 400
+const CONTEXT_LENGTH_MARKERS: &[&str] = &[
+    "context length",
+    "context_length",
+    "context window",
+    "maximum context",
+    "prompt is too long",
+    "too many tokens",
+    "input token count",
+];
+
+impl ProviderError {
+    /// Classify the error from its HTTP status and error body.
+    pub fn kind(&self) -> ProviderErrorKind {
+        match self {
+            ProviderError::InvalidApiKey => ProviderErrorKind::Auth,
+            ProviderError::RateLimitExceeded(message) => ProviderErrorKind::RateLimit {
+                retry_after: retry_after(message),
+            },
+            ProviderError::ContextLengthExceeded(_) => ProviderErrorKind::ContextLength,
+            ProviderError::HttpError(_) | ProviderError::Timeout(_) => ProviderErrorKind::Network,
+            // Mid-stream errors carry only the provider's message
+            ProviderError::StreamError(message) if is_context_length(message) => {
+                ProviderErrorKind::ContextLength
+            }
+            ProviderError::ApiError {
+                status,
+                message,
+                error_type,
+            } => {
+                let body =
+                    format!("{} {}", error_type.as_deref().unwrap_or(""), message).to_lowercase();
+                match *status {
+                    401 | 403 => ProviderErrorKind::Auth,
+                    429 => ProviderErrorKind::RateLimit {
+                        retry_after: retry_after(message),
+                    },
+                    _ if is_context_length(&body) => ProviderErrorKind::ContextLength,
+                    _ if body.contains("authentication") || body.contains("api key") => {
+                        ProviderErrorKind::Auth
+                    }
+                    500.. => ProviderErrorKind::ServerError,
+                    _ => ProviderErrorKind::Other,
+                }
+            }
+            _ => ProviderErrorKind::Other,
+        }
+    }
+
+    /// What the user can do about the error, when there is something.
+    pub fn guidance(&self) -> Option<String> {
+        match self.kind() {
+            ProviderErrorKind::Auth => Some(
+                "Check the API key in keys.toml, or run `opencrabs onboard` to set it up again."
+                    .to_string(),
+            ),
+            ProviderErrorKind::RateLimit {
+                retry_after: Some(wait),
+            } => Some(format!(
+                "Rate limited — try again in {}s.",
+                wait.as_secs().max(1)
+            )),
+            ProviderErrorKind::RateLimit { retry_after: None } => {
+                Some("Rate limited — try again shortly.".to_string())
+            }
+            ProviderErrorKind::ContextLength => Some(
+                "The conversation is too long for this model — run /compact or start a /new session."
+                    .to_string(),
+            ),
+            ProviderErrorKind::ServerError => Some(
+                "The provider is having trouble — try again, or switch with /models.".to_string(),
+            ),
+            ProviderErrorKind::Network => {
+                Some("Couldn't reach the provider — check your connection.".to_string())
+            }
+            ProviderErrorKind::Other => None,
+        }
+    }
+}
+
+fn is_context_length(message: &str) -> bool {
+    let message = message.to_lowercase();
+    CONTEXT_LENGTH_MARKERS.iter().any(|m| message.contains(m))
+}
+
+/// Wait the provider asked for, when its message names one
+fn retry_after(message: &str) -> Option<Duration> {
+    super::retry::parse_retry_seconds(message).map(Duration::from_secs)
+}
+
 /// Result type for provider operations
 pub type Result<T> = std::result::Result<T, ProviderError>;
 
@@ -113,6 +222,63 @@ mod tests {
         assert!(!client_error.is_retryable());
     }
 
+    #[test]
+    fn test_error_kind() {
+        let api = |status, message: &str, error_type: Option<&str>| ProviderError::ApiError {
+            status,
+            message: message.to_string(),
+            error_type: error_type.map(str::to_string),
+        };
+        assert_eq!(ProviderError::InvalidApiKey.kind(), ProviderErrorKind::Auth);
+        assert_eq!(
+            api(401, "invalid x-api-key", Some("authentication_error")).kind(),
+            ProviderErrorKind::Auth
+        );
+        assert_eq!(
+            ProviderError::RateLimitExceeded("slow down (retry after 20 seconds)".to_string())
+                .kind(),
+            ProviderErrorKind::RateLimit {
+                retry_after: Some(Duration::from_secs(20))
+            }
+        );
+        assert_eq!(
+            api(
+                400,
+                "prompt is too long: 210000 tokens > 200000 maximum",
+                None
+            )
+            .kind(),
+            ProviderErrorKind::ContextLength
+        );
+        assert_eq!(
+            api(
+                400,
+                "This model's maximum context length is 128000 tokens",
+                Some("invalid_request_error")
+            )
+            .kind(),
+            ProviderErrorKind::ContextLength
+        );
+        assert_eq!(
+            ProviderError::StreamError("Prompt is too long".to_string()).kind(),
+            ProviderErrorKind::ContextLength
+        );
+        assert_eq!(
+            api(529, "Overloaded", Some("overloaded_error")).kind(),
+            ProviderErrorKind::ServerError
+        );
+        assert_eq!(
+            ProviderError::Timeout(30).kind(),
+            ProviderErrorKind::Network
+        );
+        assert_eq!(
+            api(400, "Bad Request", None).kind(),
+            ProviderErrorKind::Other
+        );
+        assert!(api(400, "Bad Request", None).guidance().is_none());
+        assert!(ProviderError::InvalidApiKey.guidance().is_some());
+    }
+
     #[test]
     fn test_status_code() {
         let error = ProviderError::ApiError {
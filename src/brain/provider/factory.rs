@@ -3,11 +3,12 @@
 //! Creates providers based on config.toml settings.
 
 use super::{
-    Provider, anthropic::AnthropicProvider, custom_openai_compatible::OpenAIProvider,
-    gemini::GeminiProvider,
+    Provider, anthropic::AnthropicProvider, anthropic_oauth::OAuthTokens,
+    custom_openai_compatible::OpenAIProvider, gemini::GeminiProvider,
 };
 use crate::config::{Config, ProviderConfig};
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Create a provider based on config.toml
@@ -67,6 +68,7 @@ pub fn create_provider(config: &Config) -> Result<Arc<dyn Provider>> {
         };
 
     // Build fallback chain if configured
+    let mut fallback_models = HashMap::new();
     let fallback_providers = if let Some(fallback) = &config.providers.fallback
         && fallback.enabled
     {
@@ -76,6 +78,10 @@ pub fn create_provider(config: &Config) -> Result<Arc<dyn Provider>> {
             match create_fallback(config, name) {
                 Ok(p) => {
                     tracing::info!("Fallback provider '{}' ready", name);
+                    // Mapping is keyed by chain name; the wrapper looks up by provider name
+                    if let Some(model) = fallback.models.get(name) {
+                        fallback_models.insert(p.name().to_string(), model.clone());
+                    }
                     providers.push(p);
                 }
                 Err(e) => {
@@ -97,10 +103,10 @@ pub fn create_provider(config: &Config) -> Result<Arc<dyn Provider>> {
                     "Wrapping primary provider with {} fallback(s)",
                     fallback_providers.len()
                 );
-                Ok(Arc::new(super::FallbackProvider::new(
-                    provider,
-                    fallback_providers,
-                )))
+                Ok(Arc::new(
+                    super::FallbackProvider::new(provider, fallback_providers)
+                        .with_models(fallback_models),
+                ))
             }
         }
         None => {
@@ -142,18 +148,36 @@ pub fn create_provider_by_name(config: &Config, name: &str) -> Result<Arc<dyn Pr
     }
 }
 
+/// Create the provider behind a `custom:<name>/<model>` model-selector entry
+/// (ignores enabled flag), defaulting to that model. Returns the model too.
+/// This is what lets several custom providers be used side by side.
+pub fn create_provider_for_custom_model(
+    config: &Config,
+    choice: &str,
+) -> Result<(Arc<dyn Provider>, String)> {
+    let (name, model) = crate::config::ProviderConfigs::parse_custom_model_choice(choice)
+        .ok_or_else(|| anyhow::anyhow!("Not a custom:<name>/<model> entry: {}", choice))?;
+    let mut custom_config = config
+        .providers
+        .custom_by_name(name)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Custom provider '{}' not configured", name))?;
+    custom_config.default_model = Some(model.to_string());
+    let provider = create_custom(name, &custom_config)?
+        .ok_or_else(|| anyhow::anyhow!("Custom provider '{}' has no API key", name))?;
+    Ok((provider, model.to_string()))
+}
+
 /// Try to create a specific named custom provider (ignores enabled flag).
 fn try_create_custom_by_name(config: &Config, name: &str) -> Result<Option<Arc<dyn Provider>>> {
-    let customs = match &config.providers.custom {
-        Some(map) => map,
-        None => return Ok(None),
-    };
-
-    let custom_config = match customs.get(name) {
-        Some(cfg) => cfg.clone(),
-        None => return Ok(None),
-    };
+    match config.providers.custom_by_name(name) {
+        Some(custom_config) => create_custom(name, custom_config),
+        None => Ok(None),
+    }
+}
 
+/// Build a named custom OpenAI-compatible provider from its config section.
+fn create_custom(name: &str, custom_config: &ProviderConfig) -> Result<Option<Arc<dyn Provider>>> {
     let Some(api_key) = &custom_config.api_key else {
         return Ok(None);
     };
@@ -170,7 +194,7 @@ fn try_create_custom_by_name(config: &Config, name: &str) -> Result<Option<Arc<d
     tracing::info!("Creating custom provider '{}' at: {}", name, base_url);
     let provider = configure_openai_compatible(
         OpenAIProvider::with_base_url(api_key.clone(), base_url).with_name(name),
-        &custom_config,
+        custom_config,
     );
     Ok(Some(Arc::new(provider)))
 }
@@ -422,7 +446,13 @@ fn try_create_anthropic(config: &Config) -> Result<Option<Arc<dyn Provider>>> {
         None => return Ok(None),
     };
 
-    let mut provider = AnthropicProvider::new(api_key);
+    let mut provider = AnthropicProvider::new(api_key.clone())
+        .with_prompt_caching(config.providers.prompt_caching);
+
+    // Logged in via `/login anthropic`: attach the refresh token
+    if let Some(tokens) = OAuthTokens::load().filter(|t| t.access_token == api_key) {
+        provider = provider.with_oauth(tokens);
+    }
 
     if let Some(model) = &anthropic_config.default_model {
         tracing::info!("Using custom default model: {}", model);
@@ -555,6 +585,55 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_custom_providers_with_same_model_are_disambiguated() {
+        let custom = |base_url: &str| ProviderConfig {
+            enabled: true,
+            api_key: Some("key".to_string()),
+            base_url: Some(base_url.to_string()),
+            default_model: None,
+            models: vec!["llama3".to_string(), "qwen2.5".to_string()],
+            vision_model: None,
+        };
+        let config = Config {
+            providers: ProviderConfigs {
+                custom: Some(
+                    [
+                        ("ollama".to_string(), custom("http://localhost:11434/v1")),
+                        // Selecting another provider disables this one; it must stay listed
+                        (
+                            "vllm".to_string(),
+                            ProviderConfig {
+                                enabled: false,
+                                ..custom("https://vllm.example.com/v1")
+                            },
+                        ),
+                    ]
+                    .into(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let choices = config.providers.custom_model_choices();
+        assert!(choices.contains(&"custom:ollama/llama3".to_string()));
+        assert!(choices.contains(&"custom:vllm/llama3".to_string()));
+        assert_eq!(choices.len(), 4);
+
+        let (ollama, model) =
+            create_provider_for_custom_model(&config, "custom:ollama/llama3").unwrap();
+        assert_eq!((ollama.name(), model.as_str()), ("ollama", "llama3"));
+        assert_eq!(ollama.default_model(), "llama3");
+
+        let (vllm, model) =
+            create_provider_for_custom_model(&config, "custom:vllm/llama3").unwrap();
+        assert_eq!((vllm.name(), model.as_str()), ("vllm", "llama3"));
+
+        assert!(create_provider_for_custom_model(&config, "custom:missing/llama3").is_err());
+        assert!(create_provider_for_custom_model(&config, "llama3").is_err());
+    }
+
     #[test]
     fn test_create_provider_no_credentials() {
         let config = Config {
@@ -1,23 +1,71 @@
 //! Fallback Provider
 //!
 //! Wraps a primary provider with an ordered list of fallbacks.
-//! When the primary fails, each fallback is tried in sequence.
+//! When the primary fails with a retryable error (rate limit, outage,
+//! timeout), each fallback is tried in sequence with its mapped model.
 
 use super::error::Result;
 use super::r#trait::{Provider, ProviderStream};
 use super::types::{LLMRequest, LLMResponse};
 use async_trait::async_trait;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 
+tokio::task_local! {
+    /// Provider that served the last request inside `track_served_by`
+    static SERVED_BY: RefCell<Option<String>>;
+}
+
+/// Run `fut`, also returning the name of the provider a `FallbackProvider`
+/// last served from while it ran. `None` when no fallback chain was involved.
+pub async fn track_served_by<F: Future>(fut: F) -> (F::Output, Option<String>) {
+    SERVED_BY
+        .scope(RefCell::new(None), async move {
+            let output = fut.await;
+            (output, SERVED_BY.with(|s| s.borrow_mut().take()))
+        })
+        .await
+}
+
+fn record_served_by(name: &str) {
+    let _ = SERVED_BY.try_with(|s| *s.borrow_mut() = Some(name.to_string()));
+}
+
 /// A provider that tries a chain of providers in order on failure.
 pub struct FallbackProvider {
     primary: Arc<dyn Provider>,
     fallbacks: Vec<Arc<dyn Provider>>,
+    /// Model to request from each fallback, keyed by provider name
+    models: HashMap<String, String>,
 }
 
 impl FallbackProvider {
     pub fn new(primary: Arc<dyn Provider>, fallbacks: Vec<Arc<dyn Provider>>) -> Self {
-        Self { primary, fallbacks }
+        Self {
+            primary,
+            fallbacks,
+            models: HashMap::new(),
+        }
+    }
+
+    /// Map fallback provider names to the model each should be asked for.
+    pub fn with_models(mut self, models: HashMap<String, String>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// The request as a fallback should see it: the primary's model id rarely
+    /// exists elsewhere, so swap in the mapped (or fallback default) model.
+    fn request_for(&self, fb: &Arc<dyn Provider>, request: &LLMRequest) -> LLMRequest {
+        let mut request = request.clone();
+        request.model = self
+            .models
+            .get(fb.name())
+            .cloned()
+            .unwrap_or_else(|| fb.default_model().to_string());
+        request
     }
 }
 
@@ -25,7 +73,11 @@ impl FallbackProvider {
 impl Provider for FallbackProvider {
     async fn complete(&self, request: LLMRequest) -> Result<LLMResponse> {
         match self.primary.complete(request.clone()).await {
-            Ok(resp) => return Ok(resp),
+            Ok(resp) => {
+                record_served_by(self.primary.name());
+                Ok(resp)
+            }
+            Err(e) if !e.is_retryable() => Err(e),
             Err(e) => {
                 tracing::warn!(
                     "Primary provider '{}' failed: {} — trying fallbacks",
@@ -33,9 +85,10 @@ impl Provider for FallbackProvider {
                     e
                 );
                 for fb in &self.fallbacks {
-                    match fb.complete(request.clone()).await {
+                    match fb.complete(self.request_for(fb, &request)).await {
                         Ok(resp) => {
                             tracing::info!("Fallback provider '{}' succeeded", fb.name());
+                            record_served_by(fb.name());
                             return Ok(resp);
                         }
                         Err(e) => {
@@ -50,7 +103,11 @@ impl Provider for FallbackProvider {
 
     async fn stream(&self, request: LLMRequest) -> Result<ProviderStream> {
         match self.primary.stream(request.clone()).await {
-            Ok(stream) => return Ok(stream),
+            Ok(stream) => {
+                record_served_by(self.primary.name());
+                Ok(stream)
+            }
+            Err(e) if !e.is_retryable() => Err(e),
             Err(e) => {
                 tracing::warn!(
                     "Primary provider '{}' stream failed: {} — trying fallbacks",
@@ -58,9 +115,10 @@ impl Provider for FallbackProvider {
                     e
                 );
                 for fb in &self.fallbacks {
-                    match fb.stream(request.clone()).await {
+                    match fb.stream(self.request_for(fb, &request)).await {
                         Ok(stream) => {
                             tracing::info!("Fallback provider '{}' stream succeeded", fb.name());
+                            record_served_by(fb.name());
                             return Ok(stream);
                         }
                         Err(e) => {
@@ -89,6 +147,10 @@ impl Provider for FallbackProvider {
         self.primary.supports_vision()
     }
 
+    fn supports_reasoning_effort(&self, model: &str) -> bool {
+        self.primary.supports_reasoning_effort(model)
+    }
+
     fn name(&self) -> &str {
         self.primary.name()
     }
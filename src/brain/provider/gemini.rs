@@ -33,6 +33,19 @@ const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
 const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 
+/// Whether `model` accepts a `thinkingConfig` (Gemini 2.5 and later).
+/// Older models reject the field.
+fn is_thinking_model(model: &str) -> bool {
+    let name = model.rsplit('/').next().unwrap_or(model);
+    name.contains("thinking")
+        || name
+            .strip_prefix("gemini-")
+            .and_then(|rest| rest.split(['-', '.']).next())
+            .and_then(|major| major.parse::<u32>().ok())
+            .is_some_and(|major| major >= 3)
+        || name.starts_with("gemini-2.5")
+}
+
 /// Google Gemini provider
 #[derive(Clone)]
 pub struct GeminiProvider {
@@ -187,6 +200,18 @@ impl GeminiProvider {
                 "maxOutputTokens": request.max_tokens.unwrap_or(65536)
             }
         });
+        if let Some(temperature) = request.temperature {
+            body["generationConfig"]["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = request.top_p {
+            body["generationConfig"]["topP"] = serde_json::json!(top_p);
+        }
+        if let Some(effort) = request.reasoning_effort
+            && is_thinking_model(&request.model)
+        {
+            body["generationConfig"]["thinkingConfig"] =
+                serde_json::json!({ "thinkingBudget": effort.budget_tokens() });
+        }
 
         // System instruction
         if let Some(ref system) = request.system {
@@ -275,6 +300,7 @@ impl GeminiProvider {
             usage: TokenUsage {
                 input_tokens,
                 output_tokens,
+                ..Default::default()
             },
         }
     }
@@ -288,7 +314,17 @@ impl GeminiProvider {
                 .unwrap_or("Unknown error")
                 .to_string();
             if status == 429 {
-                return ProviderError::RateLimitExceeded(message);
+                // RetryInfo detail carries the wait as e.g. "retryDelay": "20s"
+                let retry_delay = body["error"]["details"].as_array().and_then(|details| {
+                    details
+                        .iter()
+                        .find_map(|d| d["retryDelay"].as_str())
+                        .and_then(|s| s.trim_end_matches('s').parse::<f64>().ok())
+                });
+                return ProviderError::RateLimitExceeded(match retry_delay {
+                    Some(secs) => format!("{} (retry after {} seconds)", message, secs.ceil()),
+                    None => message,
+                });
             }
             return ProviderError::ApiError {
                 status,
@@ -457,6 +493,7 @@ impl Provider for GeminiProvider {
                                             usage: TokenUsage {
                                                 input_tokens: 0,
                                                 output_tokens: 0,
+                                                ..Default::default()
                                             },
                                         },
                                     }));
@@ -564,6 +601,10 @@ impl Provider for GeminiProvider {
         true
     }
 
+    fn supports_reasoning_effort(&self, model: &str) -> bool {
+        is_thinking_model(model)
+    }
+
     fn name(&self) -> &str {
         "gemini"
     }
@@ -10,13 +10,14 @@ mod r#trait;
 pub mod types;
 
 // Re-exports
-pub use error::{ProviderError, Result};
+pub use error::{ProviderError, ProviderErrorKind, Result};
 pub use placeholder::PlaceholderProvider;
 pub use r#trait::{Provider, ProviderCapabilities, ProviderStream};
 pub use types::*;
 
 // Provider implementations
 pub mod anthropic;
+pub mod anthropic_oauth;
 pub mod custom_openai_compatible;
 pub mod factory;
 pub mod fallback;
@@ -25,5 +26,5 @@ pub mod gemini;
 pub use anthropic::AnthropicProvider;
 pub use custom_openai_compatible::OpenAIProvider;
 pub use factory::create_provider;
-pub use fallback::FallbackProvider;
+pub use fallback::{FallbackProvider, track_served_by};
 pub use gemini::GeminiProvider;
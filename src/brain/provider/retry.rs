@@ -216,7 +216,7 @@ pub fn extract_retry_after(error: &ProviderError) -> Option<Duration> {
 }
 
 /// Parse retry seconds from error message
-fn parse_retry_seconds(msg: &str) -> Option<u64> {
+pub(super) fn parse_retry_seconds(msg: &str) -> Option<u64> {
     // Try to extract numbers followed by "second" or "s"
     use regex::Regex;
 
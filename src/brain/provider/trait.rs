@@ -43,6 +43,12 @@ pub trait Provider: Send + Sync {
         false // Not all providers support vision
     }
 
+    /// Whether `model` honours `reasoning_effort`; other models never get
+    /// the setting sent
+    fn supports_reasoning_effort(&self, _model: &str) -> bool {
+        false
+    }
+
     /// Get the provider name
     fn name(&self) -> &str;
 
@@ -75,6 +75,44 @@ pub enum ContentBlock {
     },
 }
 
+/// Key marking a tool input whose raw JSON failed to parse.
+/// Providers substitute `{ MALFORMED_TOOL_INPUT_KEY: "<parse error>" }` so the
+/// tool loop can send a corrective error back instead of running the tool.
+pub const MALFORMED_TOOL_INPUT_KEY: &str = "__malformed_json";
+
+/// Parse raw tool-call arguments, marking the input as malformed on failure.
+pub fn parse_tool_input(tool_name: &str, raw: &str) -> serde_json::Value {
+    if raw.trim().is_empty() {
+        return serde_json::json!({});
+    }
+    serde_json::from_str(raw).unwrap_or_else(|e| {
+        tracing::warn!(
+            "Malformed tool input JSON for '{}': {} | args: {}",
+            tool_name,
+            e,
+            raw.chars().take(300).collect::<String>()
+        );
+        serde_json::json!({ MALFORMED_TOOL_INPUT_KEY: e.to_string() })
+    })
+}
+
+/// The parse error recorded by `parse_tool_input`, if the input was malformed.
+pub fn malformed_tool_input_error(input: &serde_json::Value) -> Option<&str> {
+    input.get(MALFORMED_TOOL_INPUT_KEY)?.as_str()
+}
+
+/// `block` with a malformed-input marker replaced by an empty object. The
+/// marker is for the tool loop only and must not be replayed to the model as
+/// its own input.
+pub fn strip_malformed_tool_input(mut block: ContentBlock) -> ContentBlock {
+    if let ContentBlock::ToolUse { input, .. } = &mut block
+        && malformed_tool_input_error(input).is_some()
+    {
+        *input = serde_json::json!({});
+    }
+    block
+}
+
 /// Image source for image content blocks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -98,12 +136,18 @@ pub struct LLMRequest {
     /// Available tools
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<Tool>>,
-    /// Temperature (0.0-1.0)
+    /// Temperature (0.0-2.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+    /// Nucleus sampling cutoff (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
     /// Maximum tokens to generate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    /// How hard a reasoning model should think (provider default if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
     /// Whether to stream the response
     #[serde(skip)]
     pub stream: bool,
@@ -121,7 +165,9 @@ impl LLMRequest {
             system: None,
             tools: None,
             temperature: None,
+            top_p: None,
             max_tokens: None,
+            reasoning_effort: None,
             stream: false,
             metadata: None,
         }
@@ -145,12 +191,24 @@ impl LLMRequest {
         self
     }
 
+    /// Set top_p
+    pub fn with_top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
     /// Set max tokens
     pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
         self.max_tokens = Some(max_tokens);
         self
     }
 
+    /// Set reasoning effort
+    pub fn with_reasoning_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.reasoning_effort = Some(effort);
+        self
+    }
+
     /// Enable streaming
     pub fn with_streaming(mut self) -> Self {
         self.stream = true;
@@ -158,6 +216,103 @@ impl LLMRequest {
     }
 }
 
+/// How much a reasoning model should think before answering: a named
+/// level, or an explicit token budget. Providers that take levels get the
+/// nearest one; providers that take budgets get tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+    /// Explicit thinking budget in tokens
+    Tokens(u32),
+}
+
+impl ReasoningEffort {
+    /// Thinking budget in tokens, for providers that take one
+    pub fn budget_tokens(self) -> u32 {
+        match self {
+            Self::Low => 1024,
+            Self::Medium => 4096,
+            Self::High => 16384,
+            Self::Tokens(n) => n,
+        }
+    }
+
+    /// `low`/`medium`/`high`, for providers that take a level. Token
+    /// budgets round to the nearest level.
+    pub fn level(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Tokens(n) if n <= 2048 => "low",
+            Self::Tokens(n) if n <= 8192 => "medium",
+            Self::Tokens(_) => "high",
+        }
+    }
+}
+
+impl std::str::FromStr for ReasoningEffort {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            other => match other.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(Self::Tokens(n)),
+                _ => Err(format!(
+                    "expected low, medium, high or a token budget, got \"{s}\""
+                )),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for ReasoningEffort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tokens(n) => write!(f, "{n} tokens"),
+            level => f.write_str(level.level()),
+        }
+    }
+}
+
+/// Written as a level string or a bare token count
+impl Serialize for ReasoningEffort {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Tokens(n) => serializer.serialize_u32(*n),
+            level => serializer.serialize_str(level.level()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ReasoningEffort {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Tokens(u32),
+            Level(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Tokens(0) => Err(serde::de::Error::custom(
+                "reasoning_effort token budget must be greater than 0",
+            )),
+            Raw::Tokens(n) => Ok(Self::Tokens(n)),
+            Raw::Level(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 /// Tool definition for LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tool {
@@ -199,18 +354,37 @@ pub enum StopReason {
 }
 
 /// Token usage information
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct TokenUsage {
-    /// Input tokens
+    /// Input tokens (excluding any read from or written to the prompt cache)
     pub input_tokens: u32,
     /// Output tokens
     pub output_tokens: u32,
+    /// Input tokens written to the prompt cache (billed at 1.25×)
+    #[serde(default)]
+    pub cache_creation_input_tokens: u32,
+    /// Input tokens served from the prompt cache (billed at 0.1×)
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
 }
 
 impl TokenUsage {
     /// Total tokens used
     pub fn total(&self) -> u32 {
-        self.input_tokens + self.output_tokens
+        self.prompt_tokens() + self.output_tokens
+    }
+
+    /// Everything sent as input, cached or not — the size of the prompt
+    pub fn prompt_tokens(&self) -> u32 {
+        self.input_tokens + self.cache_creation_input_tokens + self.cache_read_input_tokens
+    }
+
+    /// The number of full-price input tokens that would cost the same as
+    /// this prompt, so cache hits show up in `calculate_cost`.
+    pub fn billable_input_tokens(&self) -> u32 {
+        let cached = self.cache_creation_input_tokens as f64 * 1.25
+            + self.cache_read_input_tokens as f64 * 0.1;
+        self.input_tokens + cached.round() as u32
     }
 }
 
@@ -284,6 +458,28 @@ mod tests {
         assert_eq!(assistant_msg.role, Role::Assistant);
     }
 
+    #[test]
+    fn test_parse_tool_input_marks_malformed() {
+        let ok = parse_tool_input("bash", r#"{"command": "ls"}"#);
+        assert_eq!(ok["command"], "ls");
+        assert!(malformed_tool_input_error(&ok).is_none());
+
+        let empty = parse_tool_input("bash", "");
+        assert!(malformed_tool_input_error(&empty).is_none());
+
+        let bad = parse_tool_input("bash", r#"{"command": "ls",}"#);
+        assert!(malformed_tool_input_error(&bad).is_some());
+
+        let stripped = strip_malformed_tool_input(ContentBlock::ToolUse {
+            id: "t1".to_string(),
+            name: "bash".to_string(),
+            input: bad,
+        });
+        assert!(
+            matches!(stripped, ContentBlock::ToolUse { input, .. } if input == serde_json::json!({}))
+        );
+    }
+
     #[test]
     fn test_llm_request_builder() {
         let request = LLMRequest::new("claude-3-sonnet-20240229", vec![Message::user("Test")])
@@ -299,12 +495,37 @@ mod tests {
         assert!(request.stream);
     }
 
+    #[test]
+    fn test_reasoning_effort_parses_levels_and_budgets() {
+        assert_eq!("High".parse(), Ok(ReasoningEffort::High));
+        assert_eq!("8000".parse(), Ok(ReasoningEffort::Tokens(8000)));
+        assert!("0".parse::<ReasoningEffort>().is_err());
+        assert!("max".parse::<ReasoningEffort>().is_err());
+
+        assert_eq!(ReasoningEffort::Tokens(8000).level(), "medium");
+        assert_eq!(ReasoningEffort::Low.budget_tokens(), 1024);
+    }
+
     #[test]
     fn test_token_usage() {
         let usage = TokenUsage {
             input_tokens: 100,
             output_tokens: 200,
+            ..Default::default()
         };
         assert_eq!(usage.total(), 300);
     }
+
+    #[test]
+    fn test_token_usage_with_cache() {
+        let usage: TokenUsage = serde_json::from_str(
+            r#"{"input_tokens":100,"output_tokens":50,
+                "cache_creation_input_tokens":1000,"cache_read_input_tokens":10000}"#,
+        )
+        .unwrap();
+        assert_eq!(usage.prompt_tokens(), 11_100);
+        assert_eq!(usage.total(), 11_150);
+        // 100 + 1000 × 1.25 + 10000 × 0.1
+        assert_eq!(usage.billable_input_tokens(), 2_350);
+    }
 }
@@ -6,6 +6,9 @@
 //!
 //! If the binary was downloaded (no source tree), `auto_detect()` automatically
 //! clones the repo into `~/.opencrabs/source/` so `/rebuild` works everywhere.
+//!
+//! `diff()` summarises what changed in the source tree so the user can review
+//! self-modified code before restarting into it.
 
 use anyhow::Result;
 use std::path::PathBuf;
@@ -14,6 +17,157 @@ use uuid::Uuid;
 /// GitHub repo URL for auto-cloning when source is not available locally.
 const REPO_URL: &str = "https://github.com/adolfousier/opencrabs.git";
 
+/// Cap on the full patch kept for the restart dialog
+const MAX_PATCH_BYTES: usize = 256 * 1024;
+
+/// One changed file in a [`SourceDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// What changed in the source tree since a base commit — shown in the
+/// restart dialog so the user can review self-modified code before running it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceDiff {
+    /// Per-file stats (`git diff --numstat`), untracked files included
+    pub files: Vec<FileChange>,
+    /// Full unified diff of tracked files, capped at [`MAX_PATCH_BYTES`]
+    pub patch: String,
+}
+
+impl SourceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn insertions(&self) -> usize {
+        self.files.iter().map(|f| f.insertions).sum()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(|f| f.deletions).sum()
+    }
+
+    /// e.g. `3 files changed, +42 −7`
+    pub fn summary(&self) -> String {
+        let n = self.files.len();
+        format!(
+            "{} file{} changed, +{} −{}",
+            n,
+            if n == 1 { "" } else { "s" },
+            self.insertions(),
+            self.deletions()
+        )
+    }
+}
+
+/// Parse `git diff --numstat` output. Binary files (`-\t-`) count as 0/0.
+fn parse_numstat(output: &str) -> Vec<FileChange> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let insertions = parts.next()?.parse().unwrap_or(0);
+            let deletions = parts.next()?.parse().unwrap_or(0);
+            let path = parts.next()?.to_string();
+            Some(FileChange {
+                path,
+                insertions,
+                deletions,
+            })
+        })
+        .collect()
+}
+
+/// Running tally of a `cargo test` run, fed one output line at a time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestProgress {
+    /// Tests announced so far (`running N tests`, summed across test binaries)
+    pub total: usize,
+    /// Tests that have reported a result
+    pub done: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    /// (passed, failed, ignored) streamed for the current test binary
+    current: (usize, usize, usize),
+}
+
+impl TestProgress {
+    /// Update from one line of `cargo test` output; returns whether the tally
+    /// changed. A binary's `test result:` line is authoritative and corrects
+    /// whatever was counted from its individual `test … ok` lines.
+    pub fn observe(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        if let Some(n) = line
+            .strip_prefix("running ")
+            .and_then(|rest| rest.strip_suffix(" tests").or(rest.strip_suffix(" test")))
+            .and_then(|n| n.parse::<usize>().ok())
+        {
+            self.total += n;
+            self.current = (0, 0, 0);
+            return n > 0;
+        }
+        if let Some(summary) = line.strip_prefix("test result: ") {
+            let (passed, failed, ignored) = parse_test_summary(summary);
+            let (seen_passed, seen_failed, seen_ignored) = self.current;
+            let before = *self;
+            self.passed = self.passed - seen_passed + passed;
+            self.failed = self.failed - seen_failed + failed;
+            self.ignored = self.ignored - seen_ignored + ignored;
+            self.done = self.passed + self.failed + self.ignored;
+            self.current = (0, 0, 0);
+            return *self != before;
+        }
+        if !line.starts_with("test ") {
+            return false;
+        }
+        if line.ends_with(" ... ok") {
+            self.passed += 1;
+            self.current.0 += 1;
+        } else if line.ends_with(" ... FAILED") {
+            self.failed += 1;
+            self.current.1 += 1;
+        } else if line.contains(" ... ignored") {
+            self.ignored += 1;
+            self.current.2 += 1;
+        } else {
+            return false;
+        }
+        self.done += 1;
+        true
+    }
+
+    /// e.g. `running tests: 42/58…`, or `tests: 57 passed, 1 failed` once done
+    pub fn status(&self, finished: bool) -> String {
+        if !finished {
+            return format!("running tests: {}/{}…", self.done, self.total);
+        }
+        let mut status = format!("tests: {} passed, {} failed", self.passed, self.failed);
+        if self.ignored > 0 {
+            status.push_str(&format!(", {} ignored", self.ignored));
+        }
+        status
+    }
+}
+
+/// (passed, failed, ignored) from the part of a summary line after
+/// `test result: `, e.g. `ok. 58 passed; 0 failed; 1 ignored; 0 measured; …`
+fn parse_test_summary(summary: &str) -> (usize, usize, usize) {
+    let counts = summary.split_once(". ").map_or(summary, |(_, c)| c);
+    let count = |label: &str| {
+        counts
+            .split(';')
+            .filter_map(|part| part.trim().strip_suffix(label))
+            .find_map(|n| n.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    (count(" passed"), count(" failed"), count(" ignored"))
+}
+
 /// Handles building, testing, and restarting OpenCrabs from source.
 pub struct SelfUpdater {
     /// Root of the OpenCrabs project (where Cargo.toml lives)
@@ -152,44 +306,113 @@ impl SelfUpdater {
 
     /// Run tests with `cargo test`.
     ///
-    /// Returns `Ok(())` on success or `Err(test_output)` on failure.
-    pub async fn test(&self) -> Result<(), String> {
+    /// Returns the final tally on success or `Err(test_output)` on failure.
+    pub async fn test(&self) -> Result<TestProgress, String> {
+        self.test_streaming(|_| {}, |_| {}).await
+    }
+
+    /// Run tests with streaming progress — calls `on_line` for each output
+    /// line (compiler progress and test results) and `on_progress` whenever
+    /// the pass/fail tally changes.
+    ///
+    /// Returns the final tally on success or `Err(test_output)` on failure.
+    pub async fn test_streaming<F, P>(
+        &self,
+        on_line: F,
+        on_progress: P,
+    ) -> Result<TestProgress, String>
+    where
+        F: Fn(String) + Send + 'static,
+        P: Fn(TestProgress) + Send + 'static,
+    {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        use tokio::process::Command;
+
         tracing::info!("Running tests at {}", self.project_root.display());
 
-        let output = tokio::process::Command::new("cargo")
+        let mut child = Command::new("cargo")
             .arg("test")
             .current_dir(&self.project_root)
-            .output()
-            .await
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
             .map_err(|e| format!("Failed to spawn cargo test: {}", e))?;
 
-        if output.status.success() {
-            tracing::info!("Tests passed");
-            Ok(())
+        let stdout = child.stdout.take().ok_or("cargo test stdout unavailable")?;
+        let stderr = child.stderr.take().ok_or("cargo test stderr unavailable")?;
+        let mut stdout = BufReader::new(stdout).lines();
+        let mut stderr = BufReader::new(stderr).lines();
+        let (mut stdout_open, mut stderr_open) = (true, true);
+        let mut progress = TestProgress::default();
+        let mut output = Vec::new();
+
+        // Test results go to stdout, compiler progress to stderr
+        while stdout_open || stderr_open {
+            let line = tokio::select! {
+                line = stdout.next_line(), if stdout_open => match line {
+                    Ok(Some(line)) => {
+                        if progress.observe(&line) {
+                            on_progress(progress);
+                        }
+                        line
+                    }
+                    _ => {
+                        stdout_open = false;
+                        continue;
+                    }
+                },
+                line = stderr.next_line(), if stderr_open => match line {
+                    Ok(Some(line)) => line,
+                    _ => {
+                        stderr_open = false;
+                        continue;
+                    }
+                },
+            };
+            output.push(line.clone());
+            on_line(line);
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| format!("Test process error: {}", e))?;
+
+        if status.success() {
+            tracing::info!("Tests passed ({})", progress.status(true));
+            Ok(progress)
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            tracing::warn!("Tests failed:\n{}\n{}", stderr, stdout);
-            Err(format!("{}\n{}", stderr, stdout))
+            let output = output.join("\n");
+            tracing::warn!("Tests failed ({}):\n{}", progress.status(true), output);
+            Err(output)
         }
     }
 
     /// Replace the running process with the new binary via Unix exec().
     ///
-    /// Passes `chat --session <session_id>` to resume the same session.
+    /// Passes the arguments from [`restart_args`]: the same session, workspace
+    /// and config file as this process.
     /// This function only returns on error — on success, the process is replaced.
     #[cfg(unix)]
     pub fn restart(&self, session_id: Uuid) -> Result<()> {
         use std::os::unix::process::CommandExt;
 
+        let args = restart_args(
+            session_id,
+            crate::config::opencrabs_home_override(),
+            crate::config::config_path_override(),
+        );
         tracing::info!(
-            "Restarting OpenCrabs: {} chat --session {}",
+            "Restarting OpenCrabs: {} {}",
             self.binary_path.display(),
-            session_id
+            args.iter()
+                .map(|a| a.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ")
         );
 
         let err = std::process::Command::new(&self.binary_path)
-            .args(["chat", "--session", &session_id.to_string()])
+            .args(&args)
             .exec(); // Replaces the process — only returns on error
 
         Err(anyhow::anyhow!("exec() failed: {}", err))
@@ -203,6 +426,50 @@ impl SelfUpdater {
         ))
     }
 
+    /// Diff the working tree against `base` — the commit the source was at
+    /// before it was modified (`HEAD` when the edits are uncommitted).
+    pub fn diff(&self, base: &str) -> Result<SourceDiff> {
+        let mut files = parse_numstat(&self.git(&["diff", "--numstat", base])?);
+
+        // `git diff` ignores untracked files — count them as fully added
+        let untracked = self.git(&["ls-files", "--others", "--exclude-standard"])?;
+        for path in untracked.lines().filter(|l| !l.is_empty()) {
+            let insertions = std::fs::read(self.project_root.join(path))
+                .map(|bytes| bytes.iter().filter(|&&b| b == b'\n').count())
+                .unwrap_or(0);
+            files.push(FileChange {
+                path: path.to_string(),
+                insertions,
+                deletions: 0,
+            });
+        }
+
+        let mut patch = self.git(&["diff", base])?;
+        if patch.len() > MAX_PATCH_BYTES {
+            let mut cut = MAX_PATCH_BYTES;
+            while !patch.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            patch.truncate(cut);
+            patch.push_str("\n… (diff truncated)\n");
+        }
+
+        Ok(SourceDiff { files, patch })
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&self.project_root)
+            .output()
+            .map_err(|e| anyhow::anyhow!("Failed to run git (is git installed?): {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("git {} failed: {}", args.join(" "), stderr));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
     /// Get the project root path.
     pub fn project_root(&self) -> &std::path::Path {
         &self.project_root
@@ -214,6 +481,32 @@ impl SelfUpdater {
     }
 }
 
+/// Arguments for the restarted process: `--workspace <dir>` and
+/// `--config <file>` when overridden, then `chat --session <session_id>` to
+/// resume the same session.
+#[cfg(unix)]
+fn restart_args(
+    session_id: Uuid,
+    workspace: Option<PathBuf>,
+    config: Option<PathBuf>,
+) -> Vec<std::ffi::OsString> {
+    let mut args = Vec::new();
+    if let Some(workspace) = workspace {
+        args.push("--workspace".into());
+        args.push(workspace.into_os_string());
+    }
+    if let Some(config) = config {
+        args.push("--config".into());
+        args.push(config.into_os_string());
+    }
+    args.extend([
+        "chat".into(),
+        "--session".into(),
+        session_id.to_string().into(),
+    ]);
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +523,93 @@ mod tests {
             std::path::Path::new("/tmp/project/target/release/opencrabs")
         );
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_restart_args_keep_workspace_and_config() {
+        let session_id = Uuid::nil();
+        let args = restart_args(
+            session_id,
+            Some(PathBuf::from("/work/crab")),
+            Some(PathBuf::from("/etc/crab.toml")),
+        );
+        assert_eq!(
+            args,
+            [
+                "--workspace",
+                "/work/crab",
+                "--config",
+                "/etc/crab.toml",
+                "chat",
+                "--session",
+                &session_id.to_string(),
+            ]
+        );
+
+        let args = restart_args(session_id, None, None);
+        assert_eq!(args, ["chat", "--session", &session_id.to_string()]);
+    }
+
+    #[test]
+    fn test_parse_numstat_and_summary() {
+        let files = parse_numstat(
+            "40\t5\tsrc/brain/self_update.rs\n2\t2\tREADME.md\n-\t-\tassets/logo.png\n",
+        );
+        assert_eq!(
+            files[0],
+            FileChange {
+                path: "src/brain/self_update.rs".into(),
+                insertions: 40,
+                deletions: 5,
+            }
+        );
+        assert_eq!((files[2].insertions, files[2].deletions), (0, 0));
+
+        let diff = SourceDiff {
+            files,
+            patch: String::new(),
+        };
+        assert_eq!(diff.summary(), "3 files changed, +42 −7");
+        assert_eq!(
+            SourceDiff {
+                files: diff.files[..1].to_vec(),
+                patch: String::new(),
+            }
+            .summary(),
+            "1 file changed, +40 −5"
+        );
+        assert!(SourceDiff::default().is_empty());
+    }
+
+    #[test]
+    fn test_progress_from_cargo_output() {
+        let mut progress = TestProgress::default();
+        for line in [
+            "running 3 tests",
+            "test a ... ok",
+            "test b ... FAILED",
+            "test c ... ignored, needs network",
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s",
+            "running 2 tests",
+            "test d ... ok",
+        ] {
+            progress.observe(line);
+        }
+        assert_eq!(progress.status(false), "running tests: 4/5…");
+
+        // The summary corrects a binary whose lines were interleaved or missed
+        progress.observe(
+            "test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+        );
+        assert_eq!(
+            (progress.passed, progress.failed, progress.ignored),
+            (3, 1, 1)
+        );
+        assert_eq!(progress.done, 5);
+        assert_eq!(
+            progress.status(true),
+            "tests: 3 passed, 1 failed, 1 ignored"
+        );
+        assert!(!progress.observe("   Compiling opencrabs v0.2.0"));
+    }
 }
@@ -5,6 +5,9 @@
 //! a much closer approximation than chars/N heuristics (~5-10% variance vs ~30-50%).
 //!
 //! The tokenizer is initialized lazily via `once_cell` and reused across all calls.
+//!
+//! `count_tokens_for_model` picks the encoding by model family for pre-send
+//! estimates (e.g. the input token count in the TUI status bar).
 
 use once_cell::sync::Lazy;
 use tiktoken_rs::CoreBPE;
@@ -30,6 +33,40 @@ pub fn count_tokens(text: &str) -> usize {
     tokens.len().max(1)
 }
 
+/// o200k_base — GPT-4o, GPT-4.1, GPT-5 and the o-series reasoning models.
+static TOKENIZER_O200K: Lazy<CoreBPE> =
+    Lazy::new(|| tiktoken_rs::o200k_base().expect("Failed to initialize o200k_base tokenizer"));
+
+/// Count tokens in `text` as `model` would, before sending it.
+///
+/// - GPT-4o / GPT-4.1 / GPT-5 / o1, o3, o4: o200k_base (exact)
+/// - Other GPT models and Claude: cl100k_base (exact / close approximation)
+/// - Everything else (Gemini, MiniMax, open-weight models, …): no public
+///   tokenizer, so chars / 4 rounded up — the usual rule of thumb for English
+///
+/// Router-style ids such as `openai/gpt-4o` are matched on the part after
+/// the last `/`.
+pub fn count_tokens_for_model(model: &str, text: &str) -> usize {
+    if text.is_empty() {
+        return 0;
+    }
+    let name = model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .to_ascii_lowercase();
+    let o200k = ["gpt-4o", "gpt-4.1", "gpt-5", "o1", "o3", "o4"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix));
+    if o200k {
+        TOKENIZER_O200K.encode_ordinary(text).len().max(1)
+    } else if name.starts_with("gpt-") || name.starts_with("claude") {
+        count_tokens(text)
+    } else {
+        text.chars().count().div_ceil(4)
+    }
+}
+
 /// Count tokens for a message with structural overhead.
 ///
 /// Each message has ~4 tokens of overhead for role tags and separators.
@@ -114,6 +151,27 @@ mod tests {
         assert!(tiktoken_count > 0);
     }
 
+    #[test]
+    fn test_count_tokens_for_model() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(count_tokens_for_model("gpt-4o", ""), 0);
+        // cl100k_base families match the default counter
+        assert_eq!(
+            count_tokens_for_model("claude-sonnet-4-5", text),
+            count_tokens(text)
+        );
+        assert_eq!(
+            count_tokens_for_model("openai/gpt-4-turbo", text),
+            count_tokens(text)
+        );
+        // o200k_base is a real BPE count, not the fallback approximation
+        let o200k = count_tokens_for_model("openai/gpt-4o", text);
+        assert!((5..=15).contains(&o200k), "got {o200k}");
+        // Unknown tokenizer: chars / 4, rounded up
+        assert_eq!(count_tokens_for_model("gemini-2.5-pro", text), 11);
+        assert_eq!(count_tokens_for_model("MiniMax-M2.5", "abcde"), 2);
+    }
+
     #[test]
     fn test_message_overhead() {
         let count = count_message_tokens("Hello");
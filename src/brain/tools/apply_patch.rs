@@ -0,0 +1,573 @@
+//! Apply Patch Tool
+//!
+//! Apply a unified diff (or a list of exact find/replace edits) to one file.
+//! Every hunk is checked against the current contents before anything is
+//! written, so a patch either applies whole or leaves the file untouched and
+//! reports the hunk that didn't fit.
+
+use super::edit::build_edit_diff;
+use super::error::{Result, ToolError, validate_path_safety};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::fs;
+
+/// How far (in lines) a hunk may have drifted from its header position and
+/// still be placed by searching for its context
+const MAX_FUZZ_LINES: usize = 200;
+
+/// Patch tool
+pub struct ApplyPatchTool;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FindReplace {
+    /// Exact text to find — must occur exactly once
+    find: String,
+    /// Text to put in its place
+    replace: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ApplyPatchInput {
+    /// File to patch
+    path: String,
+
+    /// Unified diff for `path`
+    #[serde(default)]
+    patch: Option<String>,
+
+    /// Find/replace edits, applied in order
+    #[serde(default)]
+    edits: Option<Vec<FindReplace>>,
+}
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn name(&self) -> &str {
+        "apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (or exact find/replace edits) to a file. All hunks are checked against the current file first; if one doesn't match, nothing is written and the failing hunk is returned so you can regenerate it from the real file contents."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File to patch (relative to the working directory)"
+                },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff with @@ -a,b +c,d @@ hunks. ---/+++ headers are optional. Use --- /dev/null to create a new file."
+                },
+                "edits": {
+                    "type": "array",
+                    "description": "Alternative to patch: exact find/replace pairs applied in order. Each find must occur exactly once.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "find": { "type": "string" },
+                            "replace": { "type": "string" }
+                        },
+                        "required": ["find", "replace"]
+                    }
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![
+            ToolCapability::ReadFiles,
+            ToolCapability::WriteFiles,
+            ToolCapability::SystemModification,
+        ]
+    }
+
+    fn requires_approval(&self) -> bool {
+        true // Patching files requires approval
+    }
+
+    fn validate_input(&self, input: &Value) -> Result<()> {
+        let input: ApplyPatchInput = serde_json::from_value(input.clone())
+            .map_err(|e| ToolError::InvalidInput(format!("Invalid input: {}", e)))?;
+        match (&input.patch, &input.edits) {
+            (Some(_), Some(_)) => Err(ToolError::InvalidInput(
+                "Pass either patch or edits, not both".to_string(),
+            )),
+            (None, None) => Err(ToolError::InvalidInput(
+                "Pass a unified diff in patch, or find/replace pairs in edits".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        let input: ApplyPatchInput = serde_json::from_value(input)?;
+
+        let path = match validate_path_safety(&input.path, &context.working_directory) {
+            Ok(p) => p,
+            Err(ToolError::PermissionDenied(msg)) => {
+                return Ok(ToolResult::error(format!("Access denied: {}", msg)));
+            }
+            Err(e) => return Ok(ToolResult::error(format!("Invalid path: {}", e))),
+        };
+        if path.is_dir() {
+            return Ok(ToolResult::error(format!(
+                "Path is a directory: {}",
+                path.display()
+            )));
+        }
+
+        let exists = path.exists();
+        let content = if exists {
+            fs::read_to_string(&path).await.map_err(ToolError::Io)?
+        } else {
+            String::new()
+        };
+
+        let patched = match (input.patch, input.edits) {
+            (Some(patch), _) => {
+                let hunks = match parse_unified_diff(&patch) {
+                    Ok(h) => h,
+                    Err(msg) => return Ok(ToolResult::error(msg)),
+                };
+                if !exists && hunks.iter().any(|h| !h.old_lines().is_empty()) {
+                    return Ok(ToolResult::error(format!(
+                        "File not found: {} (a patch creating it must only add lines)",
+                        path.display()
+                    )));
+                }
+                apply_hunks(&content, &hunks)
+            }
+            (None, Some(edits)) if exists => apply_edits(&content, &edits),
+            (None, Some(_)) => {
+                return Ok(ToolResult::error(format!(
+                    "File not found: {}",
+                    path.display()
+                )));
+            }
+            (None, None) => {
+                return Ok(ToolResult::error(
+                    "Pass a unified diff in patch, or find/replace pairs in edits".to_string(),
+                ));
+            }
+        };
+        let new_content = match patched {
+            Ok(c) => c,
+            Err(conflict) => return Ok(ToolResult::error(conflict)),
+        };
+
+        if new_content == content {
+            return Ok(ToolResult::error(
+                "Patch applied cleanly but changes nothing".to_string(),
+            ));
+        }
+
+        fs::write(&path, &new_content)
+            .await
+            .map_err(ToolError::Io)?;
+
+        let mut output = format!(
+            "{} {}. Lines: {} → {}\n",
+            if exists { "Patched" } else { "Created" },
+            path.display(),
+            content.lines().count(),
+            new_content.lines().count()
+        );
+        output.push_str(&build_edit_diff(&content, &new_content));
+        Ok(ToolResult::success(output))
+    }
+}
+
+/// One `@@` hunk of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hunk {
+    /// The `@@ ... @@` line, for error messages
+    header: String,
+    /// 1-based line the hunk starts at in the original file
+    old_start: usize,
+    /// Lines tagged ' ', '-' or '+'
+    lines: Vec<(char, String)>,
+}
+
+impl Hunk {
+    /// Lines the file must contain for the hunk to apply
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|(tag, _)| *tag != '+')
+            .map(|(_, l)| l.as_str())
+            .collect()
+    }
+
+    /// Lines the hunk leaves in their place
+    fn new_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|(tag, _)| *tag != '-')
+            .map(|(_, l)| l.as_str())
+            .collect()
+    }
+
+    /// The hunk as the model sent it
+    fn text(&self) -> String {
+        let mut text = self.header.clone();
+        for (tag, line) in &self.lines {
+            text.push('\n');
+            text.push(*tag);
+            text.push_str(line);
+        }
+        text
+    }
+}
+
+/// Parse the hunks of a single-file unified diff. File headers and
+/// `\ No newline at end of file` markers are skipped.
+fn parse_unified_diff(patch: &str) -> std::result::Result<Vec<Hunk>, String> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            let old_start = parse_hunk_header(line)
+                .ok_or_else(|| format!("Malformed hunk header: `{}`", line))?;
+            hunks.push(Hunk {
+                header: line.to_string(),
+                old_start,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            // Preamble: diff --git, index, ---/+++ headers
+            continue;
+        };
+        if line.starts_with('\\') {
+            continue;
+        }
+        if line.starts_with("diff --git") {
+            return Err("Patch touches more than one file — send one file per call".into());
+        }
+        match line.chars().next() {
+            Some(tag @ (' ' | '-' | '+')) => hunk.lines.push((tag, line[1..].to_string())),
+            // Editors and models often drop the space on blank context lines
+            None => hunk.lines.push((' ', String::new())),
+            Some(_) => {
+                return Err(format!(
+                    "Unexpected line in hunk `{}`: `{}` (lines must start with ' ', '-' or '+')",
+                    hunk.header, line
+                ));
+            }
+        }
+    }
+    if hunks.is_empty() {
+        return Err(
+            "No hunks found — a unified diff needs at least one @@ -a,b +c,d @@ line".into(),
+        );
+    }
+    Ok(hunks)
+}
+
+/// Old-file start line from `@@ -a,b +c,d @@`
+fn parse_hunk_header(line: &str) -> Option<usize> {
+    let old = line.strip_prefix("@@ -")?.split_whitespace().next()?;
+    old.split(',').next()?.parse().ok()
+}
+
+/// Apply every hunk to `content`, or explain the first one that doesn't fit.
+fn apply_hunks(content: &str, hunks: &[Hunk]) -> std::result::Result<String, String> {
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let trailing_newline = content.is_empty() || content.ends_with('\n');
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    // Lines added minus lines removed by earlier hunks, and the first line
+    // a later hunk may touch (hunks apply top to bottom)
+    let mut offset: isize = 0;
+    let mut floor = 0usize;
+
+    for (n, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        let expected = (hunk.old_start.saturating_sub(1) as isize + offset).max(0) as usize;
+        // `-0,0` inserts at the top; `-a,0` inserts after line a
+        let expected = if old.is_empty() && hunk.old_start > 0 {
+            expected + 1
+        } else {
+            expected
+        }
+        .max(floor)
+        .min(lines.len());
+
+        let at = find_hunk(&lines, &old, expected, floor)
+            .ok_or_else(|| conflict_message(n + 1, hunks.len(), hunk, &lines, &old, expected))?;
+
+        let new: Vec<String> = hunk.new_lines().into_iter().map(str::to_string).collect();
+        let added = new.len();
+        lines.splice(at..at + old.len(), new);
+        offset += added as isize - old.len() as isize;
+        floor = at + added;
+    }
+
+    let mut out = lines.join(eol);
+    if trailing_newline && !out.is_empty() {
+        out.push_str(eol);
+    }
+    Ok(out)
+}
+
+/// Where `old` occurs in `lines`: at `expected` if it matches there, else the
+/// nearest match within [`MAX_FUZZ_LINES`], never before `floor`.
+fn find_hunk(lines: &[String], old: &[&str], expected: usize, floor: usize) -> Option<usize> {
+    let matches_at = |at: usize| {
+        at >= floor
+            && at + old.len() <= lines.len()
+            && lines[at..at + old.len()]
+                .iter()
+                .zip(old)
+                .all(|(have, want)| have.trim_end() == want.trim_end())
+    };
+    if matches_at(expected) {
+        return Some(expected);
+    }
+    (1..=MAX_FUZZ_LINES).find_map(|d| {
+        if matches_at(expected + d) {
+            Some(expected + d)
+        } else {
+            expected.checked_sub(d).filter(|&at| matches_at(at))
+        }
+    })
+}
+
+/// Explain why hunk `n` didn't apply, including the hunk itself.
+fn conflict_message(
+    n: usize,
+    total: usize,
+    hunk: &Hunk,
+    lines: &[String],
+    old: &[&str],
+    expected: usize,
+) -> String {
+    let mismatch = old
+        .iter()
+        .enumerate()
+        .find(|(i, want)| {
+            lines
+                .get(expected + i)
+                .is_none_or(|have| have.trim_end() != want.trim_end())
+        })
+        .map(|(i, want)| match lines.get(expected + i) {
+            Some(have) => format!(
+                "line {} should be `{}` but the file has `{}`",
+                expected + i + 1,
+                want,
+                have
+            ),
+            None => format!(
+                "expected `{}` at line {}, past the end of the file ({} lines)",
+                want,
+                expected + i + 1,
+                lines.len()
+            ),
+        })
+        .unwrap_or_else(|| "its context wasn't found".to_string());
+    format!(
+        "Hunk {}/{} doesn't apply: {}. Nothing was written. Re-read the file and regenerate this hunk:\n{}",
+        n,
+        total,
+        mismatch,
+        hunk.text()
+    )
+}
+
+/// Apply find/replace pairs in order; each `find` must occur exactly once.
+fn apply_edits(content: &str, edits: &[FindReplace]) -> std::result::Result<String, String> {
+    let mut out = content.to_string();
+    for (i, edit) in edits.iter().enumerate() {
+        if edit.find.is_empty() {
+            return Err(format!("Edit {} has an empty find", i + 1));
+        }
+        match out.matches(&edit.find).count() {
+            1 => out = out.replacen(&edit.find, &edit.replace, 1),
+            0 => {
+                return Err(format!(
+                    "Edit {}/{} doesn't apply: text not found. Nothing was written.\nfind:\n{}",
+                    i + 1,
+                    edits.len(),
+                    edit.find
+                ));
+            }
+            count => {
+                return Err(format!(
+                    "Edit {}/{} is ambiguous: text occurs {} times — include more surrounding lines. Nothing was written.\nfind:\n{}",
+                    i + 1,
+                    edits.len(),
+                    count,
+                    edit.find
+                ));
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    const FILE: &str =
+        "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+
+    fn setup() -> (TempDir, ToolExecutionContext) {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.rs"), FILE).unwrap();
+        let ctx = ToolExecutionContext::new(Uuid::new_v4())
+            .with_working_directory(dir.path().to_path_buf());
+        (dir, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_apply_unified_diff() {
+        let (dir, ctx) = setup();
+        let patch = "--- a/main.rs\n+++ b/main.rs\n@@ -2,3 +2,3 @@\n     let a = 1;\n-    let b = 2;\n+    let b = 40;\n     println!(\"{}\", a + b);\n";
+        let result = ApplyPatchTool
+            .execute(
+                serde_json::json!({ "path": "main.rs", "patch": patch }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+            FILE.replace("b = 2", "b = 40")
+        );
+    }
+
+    #[test]
+    fn test_hunks_apply_after_drift_and_keep_offsets() {
+        // Headers point two lines too early; the second hunk relies on the
+        // first one's added line being accounted for
+        let content = "a\nb\nc\nd\ne\nf\ng\n";
+        let patch = "@@ -1,2 +1,3 @@\n c\n+c2\n d\n@@ -5,2 +6,2 @@\n f\n-g\n+G\n";
+        let hunks = parse_unified_diff(patch).unwrap();
+        assert_eq!(
+            apply_hunks(content, &hunks).unwrap(),
+            "a\nb\nc\nc2\nd\ne\nf\nG\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_only_hunk_creates_content() {
+        let hunks =
+            parse_unified_diff("--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n")
+                .unwrap();
+        assert_eq!(apply_hunks("", &hunks).unwrap(), "one\ntwo\n");
+    }
+
+    #[tokio::test]
+    async fn test_conflict_reports_failing_hunk_and_writes_nothing() {
+        let (dir, ctx) = setup();
+        let patch = "@@ -2,2 +2,2 @@\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n@@ -4,1 +4,1 @@\n-    println!(\"{}\", a * b);\n+    dbg!(a * b);\n";
+        let result = ApplyPatchTool
+            .execute(
+                serde_json::json!({ "path": "main.rs", "patch": patch }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.starts_with("Hunk 2/2 doesn't apply: line 4 should be"));
+        assert!(error.contains("@@ -4,1 +4,1 @@\n-    println!(\"{}\", a * b);"));
+        // First hunk was fine but the patch is all-or-nothing
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+            FILE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_replace_edits_must_be_unique() {
+        let (dir, ctx) = setup();
+        let result = ApplyPatchTool
+            .execute(
+                serde_json::json!({
+                    "path": "main.rs",
+                    "edits": [{ "find": "let", "replace": "const" }]
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("occurs 2 times"));
+
+        let result = ApplyPatchTool
+            .execute(
+                serde_json::json!({
+                    "path": "main.rs",
+                    "edits": [{ "find": "let a = 1;", "replace": "let a = 10;" }]
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(
+            std::fs::read_to_string(dir.path().join("main.rs"))
+                .unwrap()
+                .contains("let a = 10;")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_patch_outside_workspace_is_refused() {
+        let outer = TempDir::new().unwrap();
+        let inner = outer.path().join("project");
+        std::fs::create_dir(&inner).unwrap();
+        std::fs::write(outer.path().join("victim.txt"), "keep\n").unwrap();
+        let ctx = ToolExecutionContext::new(Uuid::new_v4()).with_working_directory(inner);
+
+        let result = ApplyPatchTool
+            .execute(
+                serde_json::json!({
+                    "path": "../victim.txt",
+                    "patch": "@@ -1,1 +1,1 @@\n-keep\n+gone\n"
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Access denied"));
+        assert_eq!(
+            std::fs::read_to_string(outer.path().join("victim.txt")).unwrap(),
+            "keep\n"
+        );
+    }
+
+    #[test]
+    fn test_patch_or_edits_required() {
+        let tool = ApplyPatchTool;
+        assert!(tool.requires_approval());
+        assert!(
+            tool.validate_input(&serde_json::json!({ "path": "x" }))
+                .is_err()
+        );
+        assert!(
+            tool.validate_input(&serde_json::json!({ "path": "x", "patch": "", "edits": [] }))
+                .is_err()
+        );
+    }
+}
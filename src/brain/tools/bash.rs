@@ -7,7 +7,7 @@ use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::process::Command;
 use tokio::time::{Duration, timeout};
 
@@ -117,7 +117,7 @@ impl Tool for BashTool {
         let sudo_password = if is_sudo {
             if let Some(ref callback) = context.sudo_callback {
                 match callback(input.command.clone()).await {
-                    Ok(Some(password)) => Some(password),
+                    Ok(Some(password)) => Some(zeroize::Zeroizing::new(password)),
                     Ok(None) => return Ok(ToolResult::error("Sudo cancelled by user".to_string())),
                     Err(e) => return Ok(ToolResult::error(format!("Sudo prompt failed: {}", e))),
                 }
@@ -150,13 +150,49 @@ impl Tool for BashTool {
 
                 // Write password to stdin and close it
                 if let Some(mut stdin) = child.stdin.take() {
-                    let _ = stdin.write_all(format!("{}\n", password).as_bytes()).await;
+                    let _ = stdin.write_all(password.as_bytes()).await;
+                    let _ = stdin.write_all(b"\n").await;
                     drop(stdin);
                 }
 
                 child.wait_with_output().await
             };
 
+            match timeout(Duration::from_secs(effective_timeout), command_future).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => {
+                    return Ok(ToolResult::error(format!(
+                        "Command execution failed: {}",
+                        e
+                    )));
+                }
+                Err(_) => {
+                    return Err(ToolError::Timeout(effective_timeout));
+                }
+            }
+        } else if context.progress_sink.is_some() {
+            // Stream output lines to the progress sink while collecting them
+            let command_future = async {
+                let mut child = Command::new(shell)
+                    .arg(shell_arg)
+                    .arg(&input.command)
+                    .current_dir(&working_dir)
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn()?;
+                let stdout = child.stdout.take();
+                let stderr = child.stderr.take();
+                let (stdout, stderr) =
+                    tokio::join!(pump_lines(stdout, context), pump_lines(stderr, context));
+                let status = child.wait().await?;
+                Ok::<_, std::io::Error>(std::process::Output {
+                    status,
+                    stdout,
+                    stderr,
+                })
+            };
+
             match timeout(Duration::from_secs(effective_timeout), command_future).await {
                 Ok(Ok(output)) => output,
                 Ok(Err(e)) => {
@@ -235,6 +271,31 @@ impl Tool for BashTool {
     }
 }
 
+/// Read `pipe` to the end, reporting each line as progress. Returns
+/// everything read.
+async fn pump_lines<R: tokio::io::AsyncRead + Unpin>(
+    pipe: Option<R>,
+    context: &ToolExecutionContext,
+) -> Vec<u8> {
+    let mut collected = Vec::new();
+    let Some(pipe) = pipe else {
+        return collected;
+    };
+    let mut reader = tokio::io::BufReader::new(pipe);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                context.report_progress(String::from_utf8_lossy(&line).trim_end().to_string());
+                collected.extend_from_slice(&line);
+            }
+        }
+    }
+    collected
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +353,33 @@ mod tests {
         assert!(!result.success);
     }
 
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_bash_streams_lines_to_progress_sink() {
+        let tool = BashTool;
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let context = ToolExecutionContext::new(Uuid::new_v4())
+            .with_auto_approve(true)
+            .with_progress_sink(tx);
+
+        let input = serde_json::json!({
+            "command": "echo one; echo two >&2; echo three"
+        });
+
+        let result = tool.execute(input, &context).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("one\nthree"));
+        assert!(result.output.contains("STDERR:\ntwo"));
+
+        drop(context);
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        lines.sort();
+        assert_eq!(lines, vec!["one", "three", "two"]);
+    }
+
     #[tokio::test]
     #[cfg(not(target_os = "windows"))] // Skip on Windows due to cmd.exe limitations
     async fn test_bash_timeout() {
@@ -102,7 +102,7 @@ impl Tool for ChannelSearchTool {
                     .map(|c| {
                         let name = c.channel_chat_name.as_deref().unwrap_or("unnamed");
                         let ts = DateTime::from_timestamp(c.last_message_at, 0)
-                            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                            .map(|dt| crate::config::format_local(dt, "%Y-%m-%d %H:%M"))
                             .unwrap_or_default();
                         format!(
                             "- [{}] {} \"{}\" (id={}) — {} msgs, last: {}",
@@ -149,7 +149,7 @@ impl Tool for ChannelSearchTool {
                     .iter()
                     .rev() // oldest first for readability
                     .map(|m| {
-                        let ts = m.created_at.format("%m-%d %H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%m-%d %H:%M");
                         format!("[{}] {}: {}", ts, m.sender_name, m.content)
                     })
                     .collect();
@@ -189,7 +189,7 @@ impl Tool for ChannelSearchTool {
                 let lines: Vec<String> = messages
                     .iter()
                     .map(|m| {
-                        let ts = m.created_at.format("%m-%d %H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%m-%d %H:%M");
                         let chat = m.channel_chat_name.as_deref().unwrap_or(&m.channel_chat_id);
                         format!(
                             "[{}] [{}:{}] {}: {}",
@@ -6,8 +6,8 @@
 
 use super::error::Result;
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
-use crate::channels::ChannelFactory;
 use crate::channels::discord::DiscordState;
+use crate::channels::{ChannelFactory, MessageSource};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
@@ -113,7 +113,7 @@ impl Tool for DiscordConnectTool {
 
         // Create and spawn the Discord agent
         let factory = self.channel_factory.clone();
-        let agent = factory.create_agent_service();
+        let agent = factory.create_channel_agent_service(MessageSource::Discord);
         let service_context = factory.service_context();
         let shared_session = factory.shared_session_id();
         let discord_state = self.discord_state.clone();
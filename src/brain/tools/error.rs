@@ -41,6 +41,10 @@ pub enum ToolError {
     #[error("Tool execution timed out after {0}s")]
     Timeout(u64),
 
+    /// Two or more tools registered under the same name
+    #[error("Duplicate tool name(s): {0}")]
+    DuplicateName(String),
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -350,6 +350,7 @@ impl Tool for EvolveTool {
                         "Evolved: v{} -> v{}. Restarting now.",
                         current_version, latest_version
                     ),
+                    diff: None,
                 },
             );
         }
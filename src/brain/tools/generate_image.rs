@@ -1,23 +1,192 @@
 //! Generate Image Tool
 //!
-//! Generates images from text prompts using Google Gemini's image generation API.
-//! Saves the result as a PNG file in ~/.opencrabs/images/ and returns the path.
+//! Generates images from text prompts using Google Gemini or OpenAI's
+//! `images/generations` API. Saves the results as PNG files in
+//! ~/.opencrabs/generated/ and returns the paths.
 
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
 use async_trait::async_trait;
 use serde_json::Value;
+use std::path::{Path, PathBuf};
 
 const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+const OPENAI_IMAGES_URL: &str = "https://api.openai.com/v1/images/generations";
 
-/// Image generation tool using Google Gemini
+const DEFAULT_SIZE: &str = "1024x1024";
+const MAX_IMAGES: u64 = 4;
+
+/// Aspect ratios Gemini's `imageConfig` accepts
+const GEMINI_ASPECT_RATIOS: &[&str] = &[
+    "1:1", "2:3", "3:2", "3:4", "4:3", "4:5", "5:4", "9:16", "16:9", "21:9",
+];
+
+/// Which image API backs the tool (`[image.generation] provider`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageBackend {
+    Gemini,
+    OpenAi,
+}
+
+impl ImageBackend {
+    /// Parse a config name (`"gemini"`, `"openai"`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "gemini" | "google" => Some(Self::Gemini),
+            "openai" => Some(Self::OpenAi),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Gemini => "Gemini",
+            Self::OpenAi => "OpenAI",
+        }
+    }
+
+    fn default_model(self) -> &'static str {
+        match self {
+            Self::Gemini => "gemini-3.1-flash-image-preview",
+            Self::OpenAi => "gpt-image-1",
+        }
+    }
+
+    /// `configured` when it names one of this backend's models, else the
+    /// backend's default — switching `provider` without touching `model`
+    /// leaves the other API's model behind.
+    fn model_for(self, configured: &str) -> String {
+        let configured = configured.trim();
+        let foreign = match self {
+            Self::Gemini => configured.starts_with("gpt-") || configured.starts_with("dall-e"),
+            Self::OpenAi => configured.starts_with("gemini") || configured.starts_with("imagen"),
+        };
+        if configured.is_empty() || foreign {
+            self.default_model().to_string()
+        } else {
+            configured.to_string()
+        }
+    }
+}
+
+/// Image generation tool using Google Gemini or OpenAI
 pub struct GenerateImageTool {
+    backend: ImageBackend,
     api_key: String,
     model: String,
 }
 
 impl GenerateImageTool {
     pub fn new(api_key: String, model: String) -> Self {
-        Self { api_key, model }
+        Self {
+            backend: ImageBackend::Gemini,
+            api_key,
+            model,
+        }
+    }
+
+    /// Use a different image API, with its default model if the configured
+    /// one belongs to another API
+    pub fn with_backend(mut self, backend: ImageBackend) -> Self {
+        self.backend = backend;
+        self.model = backend.model_for(&self.model);
+        self
+    }
+
+    /// Gemini: one image per request, sized by aspect ratio
+    async fn generate_gemini(
+        &self,
+        client: &reqwest::Client,
+        prompt: &str,
+        size: &str,
+        n: usize,
+    ) -> std::result::Result<(Vec<Vec<u8>>, String), String> {
+        let url = format!(
+            "{}/models/{}:generateContent?key={}",
+            GEMINI_BASE_URL, self.model, self.api_key
+        );
+
+        let mut generation_config = serde_json::json!({
+            "responseModalities": ["TEXT", "IMAGE"]
+        });
+        if let Some(ratio) = aspect_ratio(size) {
+            generation_config["imageConfig"] = serde_json::json!({ "aspectRatio": ratio });
+        }
+        let body = serde_json::json!({
+            "contents": [{"parts": [{"text": prompt}]}],
+            "generationConfig": generation_config
+        });
+
+        let mut images = Vec::new();
+        let mut text_response = String::new();
+        for _ in 0..n {
+            let json = post_json(client, &url, None, &body).await?;
+
+            // Find inlineData (base64 image) in response parts
+            let parts = json["candidates"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c["content"]["parts"].as_array())
+                .flatten();
+            let mut found = None;
+            for part in parts {
+                if let Some(text) = part["text"].as_str() {
+                    text_response.push_str(text);
+                }
+                if let Some(data) = part["inlineData"]["data"].as_str() {
+                    found = Some(base64_decode(data)?);
+                    break;
+                }
+            }
+            match found {
+                Some(bytes) => images.push(bytes),
+                // Text-only answer: the model declined or can't draw this prompt
+                None => break,
+            }
+        }
+        Ok((images, text_response))
+    }
+
+    /// OpenAI: all images in one request, as base64 or hosted URLs
+    async fn generate_openai(
+        &self,
+        client: &reqwest::Client,
+        prompt: &str,
+        size: &str,
+        n: usize,
+    ) -> std::result::Result<(Vec<Vec<u8>>, String), String> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "size": size,
+            "n": n,
+        });
+        let json = post_json(client, OPENAI_IMAGES_URL, Some(&self.api_key), &body).await?;
+
+        let mut images = Vec::new();
+        let mut revised_prompt = String::new();
+        for item in json["data"].as_array().into_iter().flatten() {
+            if let Some(b64) = item["b64_json"].as_str() {
+                images.push(base64_decode(b64)?);
+            } else if let Some(url) = item["url"].as_str() {
+                let bytes = client
+                    .get(url)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| format!("Failed to download generated image: {}", e))?
+                    .bytes()
+                    .await
+                    .map_err(|e| format!("Failed to download generated image: {}", e))?;
+                images.push(bytes.to_vec());
+            }
+            if revised_prompt.is_empty()
+                && let Some(revised) = item["revised_prompt"].as_str()
+            {
+                revised_prompt = format!("Revised prompt: {}", revised);
+            }
+        }
+        Ok((images, revised_prompt))
     }
 }
 
@@ -28,7 +197,7 @@ impl Tool for GenerateImageTool {
     }
 
     fn description(&self) -> &str {
-        "Generate an image from a text prompt using Google Gemini. Returns the file path to the saved PNG. Use <<IMG:path>> syntax in your reply to send the image through a channel."
+        "Generate images from a text prompt using the configured image API (Google Gemini or OpenAI). Returns the file paths of the saved PNGs. Use <<IMG:path>> syntax in your reply to send an image through a channel."
     }
 
     fn input_schema(&self) -> Value {
@@ -39,6 +208,14 @@ impl Tool for GenerateImageTool {
                     "type": "string",
                     "description": "Text description of the image to generate"
                 },
+                "size": {
+                    "type": "string",
+                    "description": "Image size as WIDTHxHEIGHT, e.g. 1024x1024, 1536x1024, 1024x1536. Defaults to 1024x1024."
+                },
+                "n": {
+                    "type": "integer",
+                    "description": "Number of images to generate (1-4). Defaults to 1."
+                },
                 "filename": {
                     "type": "string",
                     "description": "Optional filename (without path). Defaults to a UUID-based name."
@@ -70,142 +247,202 @@ impl Tool for GenerateImageTool {
             }
         };
 
-        let filename = input["filename"]
-            .as_str()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| format!("{}.png", uuid::Uuid::new_v4().simple()));
+        let size = input["size"].as_str().unwrap_or(DEFAULT_SIZE).trim();
+        if parse_size(size).is_none() {
+            return Ok(ToolResult::error(format!(
+                "Invalid size '{}': expected WIDTHxHEIGHT, e.g. 1024x1024",
+                size
+            )));
+        }
+        let n = input["n"].as_u64().unwrap_or(1).clamp(1, MAX_IMAGES) as usize;
 
-        // Ensure images directory exists
-        let images_dir = crate::config::opencrabs_home().join("images");
-        if let Err(e) = tokio::fs::create_dir_all(&images_dir).await {
+        // Ensure output directory exists
+        let output_dir = crate::config::opencrabs_home().join("generated");
+        if let Err(e) = tokio::fs::create_dir_all(&output_dir).await {
             return Ok(ToolResult::error(format!(
-                "Failed to create images directory: {}",
+                "Failed to create generated images directory: {}",
                 e
             )));
         }
 
-        let save_path = images_dir.join(&filename);
-
-        // Build Gemini request for image generation
-        let url = format!(
-            "{}/models/{}:generateContent?key={}",
-            GEMINI_BASE_URL, self.model, self.api_key
-        );
-
-        let body = serde_json::json!({
-            "contents": [{"parts": [{"text": prompt}]}],
-            "generationConfig": {
-                "responseModalities": ["TEXT", "IMAGE"]
-            }
-        });
-
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(120))
             .build()
             .map_err(|e| super::error::ToolError::Execution(e.to_string()))?;
 
-        let response = client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| super::error::ToolError::Execution(e.to_string()))?;
+        let result = match self.backend {
+            ImageBackend::Gemini => self.generate_gemini(&client, &prompt, size, n).await,
+            ImageBackend::OpenAi => self.generate_openai(&client, &prompt, size, n).await,
+        };
+        let (images, text_response) = match result {
+            Ok(r) => r,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let err_body = response.text().await.unwrap_or_default();
-            return Ok(ToolResult::error(format!(
-                "Gemini API error {}: {}",
-                status, err_body
-            )));
+        if images.is_empty() {
+            // The model might return text only (no image for this prompt)
+            return if !text_response.is_empty() {
+                Ok(ToolResult::success(format!(
+                    "No image generated. {} response: {}",
+                    self.backend.label(),
+                    text_response
+                )))
+            } else {
+                Ok(ToolResult::error(format!(
+                    "No image data found in {} response",
+                    self.backend.label()
+                )))
+            };
         }
 
-        let json: Value = response
-            .json()
-            .await
-            .map_err(|e| super::error::ToolError::Execution(e.to_string()))?;
-
-        // Find inlineData (base64 image) in response parts
-        let empty_vec = vec![];
-        let candidates = json["candidates"].as_array().unwrap_or(&empty_vec);
-        let mut image_data: Option<String> = None;
-        let mut text_response = String::new();
-
-        'outer: for candidate in candidates {
-            let empty_parts = vec![];
-            let parts = candidate["content"]["parts"]
-                .as_array()
-                .unwrap_or(&empty_parts);
-            for part in parts {
-                if let Some(text) = part["text"].as_str() {
-                    text_response.push_str(text);
-                }
-                if let Some(data) = part["inlineData"]["data"].as_str() {
-                    image_data = Some(data.to_string());
-                    break 'outer;
-                }
-            }
+        let paths = output_paths(&output_dir, input["filename"].as_str(), images.len());
+        for (path, bytes) in paths.iter().zip(&images) {
+            tokio::fs::write(path, bytes)
+                .await
+                .map_err(|e| super::error::ToolError::Execution(e.to_string()))?;
         }
 
-        match image_data {
-            Some(b64) => {
-                // Decode base64 and save to file
-                let bytes = match base64_decode(&b64) {
-                    Ok(b) => b,
-                    Err(e) => {
-                        return Ok(ToolResult::error(format!(
-                            "Failed to decode image data: {}",
-                            e
-                        )));
-                    }
-                };
-
-                tokio::fs::write(&save_path, &bytes)
-                    .await
-                    .map_err(|e| super::error::ToolError::Execution(e.to_string()))?;
-
-                let path_str = save_path.to_string_lossy().to_string();
-                let mut output = format!(
-                    "Generated image saved to: {}\nUse <<IMG:{}>> to reference it.",
-                    path_str, path_str
-                );
-                if !text_response.is_empty() {
-                    output = format!("{}\n\n{}", text_response.trim(), output);
-                }
-                Ok(ToolResult::success(output))
-            }
-            None => {
-                // Gemini might return text only (model doesn't support image gen for this prompt)
-                if !text_response.is_empty() {
-                    Ok(ToolResult::success(format!(
-                        "No image generated. Gemini response: {}",
-                        text_response
-                    )))
-                } else {
-                    Ok(ToolResult::error(
-                        "No image data found in Gemini response".to_string(),
-                    ))
-                }
-            }
+        let mut output = String::from("Generated images saved to:");
+        for path in &paths {
+            output.push_str(&format!("\n- {}", path.display()));
         }
+        output.push_str(&format!(
+            "\nUse <<IMG:{}>> to reference an image.",
+            paths[0].display()
+        ));
+        if !text_response.is_empty() {
+            output = format!("{}\n\n{}", text_response.trim(), output);
+        }
+        Ok(ToolResult::success(output))
+    }
+}
+
+/// POST a JSON body and return the JSON response, or a readable error
+async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    bearer: Option<&str>,
+    body: &Value,
+) -> std::result::Result<Value, String> {
+    let mut request = client.post(url).json(body);
+    if let Some(key) = bearer {
+        request = request.bearer_auth(key);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let err_body = response.text().await.unwrap_or_default();
+        return Err(format!("Image API error {}: {}", status, err_body));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// `WIDTHxHEIGHT` → (width, height)
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (w, h) = size.split_once('x')?;
+    let (w, h) = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+    (w > 0 && h > 0).then_some((w, h))
+}
+
+/// Reduce a size to one of Gemini's supported aspect ratios, if it matches one
+fn aspect_ratio(size: &str) -> Option<&'static str> {
+    fn gcd(a: u32, b: u32) -> u32 {
+        if b == 0 { a } else { gcd(b, a % b) }
     }
+    let (w, h) = parse_size(size)?;
+    let d = gcd(w, h);
+    let ratio = format!("{}:{}", w / d, h / d);
+    GEMINI_ASPECT_RATIOS.iter().copied().find(|r| *r == ratio)
+}
+
+/// One `.png` path per image. A caller-supplied name gets `-2`, `-3`, …
+/// suffixes when more than one image was generated.
+fn output_paths(dir: &Path, filename: Option<&str>, count: usize) -> Vec<PathBuf> {
+    let stem = filename
+        .and_then(|f| Path::new(f).file_name())
+        .map(|f| {
+            Path::new(f)
+                .with_extension("")
+                .to_string_lossy()
+                .to_string()
+        })
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    (0..count)
+        .map(|i| match i {
+            0 => dir.join(format!("{}.png", stem)),
+            _ => dir.join(format!("{}-{}.png", stem, i + 1)),
+        })
+        .collect()
 }
 
-fn base64_decode(input: &str) -> Result<Vec<u8>, String> {
-    // Use base64 via the standard approach — decode without padding issues
+fn base64_decode(input: &str) -> std::result::Result<Vec<u8>, String> {
+    use base64::Engine;
+    // Drop whitespace/newlines some APIs wrap the payload with
     let clean: String = input
         .chars()
         .filter(|c| c.is_ascii_alphanumeric() || *c == '+' || *c == '/' || *c == '=')
         .collect();
-    base64_decode_inner(&clean)
+    base64::engine::general_purpose::STANDARD
+        .decode(clean)
+        .map_err(|e| format!("Failed to decode image data: {}", e))
 }
 
-fn base64_decode_inner(input: &str) -> Result<Vec<u8>, String> {
-    // Simple base64 decode without external crate (reqwest already depends on base64 indirectly)
-    // Use the engine from the existing base64 crate that reqwest pulls in
-    use base64::Engine;
-    base64::engine::general_purpose::STANDARD
-        .decode(input)
-        .map_err(|e| e.to_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_and_aspect_ratio() {
+        assert_eq!(parse_size("1024x1536"), Some((1024, 1536)));
+        assert_eq!(parse_size("big"), None);
+        assert_eq!(parse_size("0x512"), None);
+        assert_eq!(aspect_ratio("1024x1024"), Some("1:1"));
+        assert_eq!(aspect_ratio("1920x1080"), Some("16:9"));
+        assert_eq!(aspect_ratio("1536x1024"), Some("3:2"));
+        assert_eq!(aspect_ratio("1000x700"), None);
+    }
+
+    #[test]
+    fn test_output_paths() {
+        let dir = Path::new("/tmp/generated");
+        assert_eq!(
+            output_paths(dir, Some("crab.png"), 2),
+            vec![dir.join("crab.png"), dir.join("crab-2.png")]
+        );
+        // Path components in the name are stripped
+        assert_eq!(
+            output_paths(dir, Some("../../etc/logo"), 1),
+            vec![dir.join("logo.png")]
+        );
+        assert_eq!(output_paths(dir, None, 3).len(), 3);
+    }
+
+    #[test]
+    fn test_backend_from_name() {
+        assert_eq!(
+            ImageBackend::from_name("OpenAI"),
+            Some(ImageBackend::OpenAi)
+        );
+        assert_eq!(
+            ImageBackend::from_name("gemini"),
+            Some(ImageBackend::Gemini)
+        );
+        assert_eq!(ImageBackend::from_name("midjourney"), None);
+    }
+
+    #[test]
+    fn test_backend_keeps_only_its_own_model() {
+        let tool = GenerateImageTool::new("key".into(), "gemini-3.1-flash-image-preview".into())
+            .with_backend(ImageBackend::OpenAi);
+        assert_eq!(tool.model, "gpt-image-1");
+
+        let tool = GenerateImageTool::new("key".into(), "dall-e-3".into())
+            .with_backend(ImageBackend::OpenAi);
+        assert_eq!(tool.model, "dall-e-3");
+
+        let tool = GenerateImageTool::new("key".into(), "gpt-image-1".into())
+            .with_backend(ImageBackend::Gemini);
+        assert_eq!(tool.model, "gemini-3.1-flash-image-preview");
+    }
 }
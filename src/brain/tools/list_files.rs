@@ -0,0 +1,288 @@
+//! List Files Tool
+//!
+//! Compact tree of the workspace for orienting in a project. Honours
+//! `.gitignore`/`.ignore` and stays inside the working directory.
+
+use super::error::{Result, ToolError, validate_directory_path};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Depth used when the model doesn't ask for one
+const DEFAULT_DEPTH: usize = 3;
+
+/// Deepest walk allowed
+const MAX_DEPTH: usize = 10;
+
+/// Entries listed when the model doesn't ask for a number
+const DEFAULT_MAX_FILES: usize = 200;
+
+/// Most entries listed in one call
+const MAX_FILES: usize = 2000;
+
+/// Workspace tree tool
+pub struct ListFilesTool;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ListFilesInput {
+    /// Directory to list (defaults to the working directory)
+    #[serde(default)]
+    path: Option<String>,
+
+    /// How many levels below `path` to descend
+    #[serde(default)]
+    max_depth: Option<usize>,
+
+    /// Stop after this many entries
+    #[serde(default)]
+    max_files: Option<usize>,
+
+    /// Include dotfiles and dot-directories
+    #[serde(default)]
+    show_hidden: bool,
+}
+
+#[async_trait]
+impl Tool for ListFilesTool {
+    fn name(&self) -> &str {
+        "list_files"
+    }
+
+    fn description(&self) -> &str {
+        "Show the workspace as a compact tree, skipping anything .gitignore excludes. Use it to get oriented in a project before reading files."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to list (defaults to the working directory)"
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Levels to descend below path (default: 3, max: 10)",
+                    "minimum": 1
+                },
+                "max_files": {
+                    "type": "integer",
+                    "description": "Stop after this many entries (default: 200, max: 2000)",
+                    "minimum": 1
+                },
+                "show_hidden": {
+                    "type": "boolean",
+                    "description": "Include dotfiles and dot-directories",
+                    "default": false
+                }
+            }
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadFiles]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false // Listing files is safe
+    }
+
+    fn validate_input(&self, input: &Value) -> Result<()> {
+        let _: ListFilesInput = serde_json::from_value(input.clone())
+            .map_err(|e| ToolError::InvalidInput(format!("Invalid input: {}", e)))?;
+        Ok(())
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        let input: ListFilesInput = serde_json::from_value(input)?;
+
+        let requested = input.path.as_deref().unwrap_or(".");
+        let root = match validate_directory_path(requested, &context.working_directory) {
+            Ok(p) => p,
+            Err(msg) => return Ok(ToolResult::error(msg)),
+        };
+        let max_depth = input.max_depth.unwrap_or(DEFAULT_DEPTH).clamp(1, MAX_DEPTH);
+        let max_files = input
+            .max_files
+            .unwrap_or(DEFAULT_MAX_FILES)
+            .clamp(1, MAX_FILES);
+        let show_hidden = input.show_hidden;
+
+        let walk_root = root.clone();
+        let (tree, listed, truncated) = tokio::task::spawn_blocking(move || {
+            build_tree(&walk_root, max_depth, max_files, show_hidden)
+        })
+        .await
+        .map_err(|e| ToolError::Internal(format!("Directory walk failed: {}", e)))?;
+
+        let mut output = format!("{}/\n", requested.trim_end_matches('/'));
+        render(&tree, "", &mut output);
+        if truncated {
+            output.push_str(&format!(
+                "… stopped at {} entries — narrow path or raise max_files\n",
+                max_files
+            ));
+        }
+
+        Ok(ToolResult::success(output)
+            .with_metadata("path".to_string(), root.display().to_string())
+            .with_metadata("entries".to_string(), listed.to_string()))
+    }
+}
+
+/// A directory's children by name; files have no children
+#[derive(Debug, Default)]
+struct Node {
+    is_dir: bool,
+    children: BTreeMap<String, Node>,
+}
+
+/// Walk `root` and collect up to `max_files` entries. Returns the tree, how
+/// many entries it holds, and whether the cap cut the walk short.
+fn build_tree(
+    root: &Path,
+    max_depth: usize,
+    max_files: usize,
+    show_hidden: bool,
+) -> (Node, usize, bool) {
+    let mut tree = Node {
+        is_dir: true,
+        ..Node::default()
+    };
+    let mut listed = 0;
+    let walk = ignore::WalkBuilder::new(root)
+        .max_depth(Some(max_depth))
+        .hidden(!show_hidden)
+        // Respect .gitignore even outside a git checkout
+        .require_git(false)
+        .build()
+        .flatten();
+
+    for entry in walk {
+        let Ok(rel) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if rel.as_os_str().is_empty() {
+            continue;
+        }
+        if listed == max_files {
+            return (tree, listed, true);
+        }
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        let mut node = &mut tree;
+        for part in rel.components() {
+            node = node
+                .children
+                .entry(part.as_os_str().to_string_lossy().into_owned())
+                .or_default();
+        }
+        node.is_dir = is_dir;
+        listed += 1;
+    }
+    (tree, listed, false)
+}
+
+/// Append `node`'s children as tree lines, directories first.
+fn render(node: &Node, prefix: &str, out: &mut String) {
+    let mut children: Vec<(&String, &Node)> = node.children.iter().collect();
+    children.sort_by_key(|(_, child)| !child.is_dir);
+    let last = children.len().saturating_sub(1);
+    for (i, (name, child)) in children.into_iter().enumerate() {
+        let (branch, indent) = if i == last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        out.push_str(prefix);
+        out.push_str(branch);
+        out.push_str(name);
+        if child.is_dir {
+            out.push('/');
+        }
+        out.push('\n');
+        render(child, &format!("{prefix}{indent}"), out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn workspace() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+        std::fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join("src/bin/tool.rs"), "").unwrap();
+        std::fs::write(dir.path().join("target/debug/app"), "").unwrap();
+        dir
+    }
+
+    fn context(dir: &Path) -> ToolExecutionContext {
+        ToolExecutionContext::new(Uuid::new_v4()).with_working_directory(dir.to_path_buf())
+    }
+
+    #[tokio::test]
+    async fn test_list_files_tree_respects_gitignore() {
+        let dir = workspace();
+        let result = ListFilesTool
+            .execute(serde_json::json!({}), &context(dir.path()))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        let expected = [
+            "./",
+            "├── src/",
+            "│   ├── bin/",
+            "│   │   └── tool.rs",
+            "│   └── main.rs",
+            "└── Cargo.toml",
+        ];
+        assert_eq!(result.output, format!("{}\n", expected.join("\n")));
+        assert!(!result.output.contains("target"));
+    }
+
+    #[tokio::test]
+    async fn test_list_files_depth_and_count_caps() {
+        let dir = workspace();
+        let ctx = context(dir.path());
+
+        let shallow = ListFilesTool
+            .execute(serde_json::json!({ "max_depth": 1 }), &ctx)
+            .await
+            .unwrap();
+        assert!(shallow.output.contains("src/"));
+        assert!(!shallow.output.contains("main.rs"));
+
+        let capped = ListFilesTool
+            .execute(serde_json::json!({ "max_files": 2 }), &ctx)
+            .await
+            .unwrap();
+        assert!(capped.output.contains("stopped at 2 entries"));
+    }
+
+    #[tokio::test]
+    async fn test_list_files_rejects_traversal_outside_workspace() {
+        let outer = TempDir::new().unwrap();
+        let inner = outer.path().join("project");
+        std::fs::create_dir(&inner).unwrap();
+        let ctx = context(&inner);
+
+        for path in ["..", "../..", "/"] {
+            let result = ListFilesTool
+                .execute(serde_json::json!({ "path": path }), &ctx)
+                .await
+                .unwrap();
+            assert!(!result.success, "{path} should be refused");
+            assert!(result.error.unwrap().contains("Access denied"));
+        }
+    }
+}
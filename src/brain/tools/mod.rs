@@ -8,12 +8,15 @@ pub mod registry;
 mod r#trait;
 
 // Tool implementations - Phase 1: Essential File Operations
+pub mod apply_patch;
 pub mod bash;
 pub mod edit;
 pub mod glob;
 pub mod grep;
+pub mod list_files;
 pub mod ls;
 pub mod read;
+pub mod shell;
 pub mod write;
 
 // Tool implementations - Phase 2: Advanced Features
@@ -38,10 +41,14 @@ pub mod load_brain_file;
 pub mod memory_search;
 pub mod plan_tool;
 pub mod provider_vision;
+pub mod read_more;
 pub mod rebuild;
+pub mod reminder;
 pub mod session_search;
+pub mod sessions;
 pub mod slash_command;
 pub mod task;
+pub mod update_brain;
 pub mod write_opencrabs_file;
 
 // Tool implementations - Phase 4: Channel Integrations
@@ -68,5 +75,5 @@ pub mod whatsapp_send;
 
 // Re-exports
 pub use error::{Result, ToolError};
-pub use registry::ToolRegistry;
+pub use registry::{ToolInfo, ToolRegistry};
 pub use r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
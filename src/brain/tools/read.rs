@@ -34,6 +34,10 @@ struct ReadInput {
     /// Optional: Number of lines to read
     #[serde(skip_serializing_if = "Option::is_none")]
     line_count: Option<usize>,
+
+    /// Prefix each line with its 1-based line number
+    #[serde(default)]
+    line_numbers: bool,
 }
 
 #[async_trait]
@@ -63,6 +67,11 @@ impl Tool for ReadTool {
                     "type": "integer",
                     "description": "Optional: Number of lines to read from start_line",
                     "minimum": 1
+                },
+                "line_numbers": {
+                    "type": "boolean",
+                    "description": "Optional: Prefix each line with its 1-based line number (`  42| ...`) so later edits can reference exact lines. Line N is start_line N-1.",
+                    "default": false
                 }
             },
             "required": ["path"]
@@ -118,6 +127,12 @@ impl Tool for ReadTool {
                 (contents, line_count, None)
             };
 
+        let output = if input.line_numbers {
+            number_lines(&output, input.start_line.unwrap_or(0) + 1)
+        } else {
+            output
+        };
+
         let output_len = output.len();
         let mut result = ToolResult::success(output)
             .with_metadata("path".to_string(), path.display().to_string())
@@ -218,6 +233,17 @@ impl ReadTool {
     }
 }
 
+/// Prefix each line of `text` with its number, counting from `first`
+fn number_lines(text: &str, first: usize) -> String {
+    let last = first + text.lines().count().saturating_sub(1);
+    let width = last.to_string().len().max(4);
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| format!("{:>width$}| {}", first + i, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,6 +301,42 @@ mod tests {
         assert!(!result.output.contains("Line 4"));
     }
 
+    #[tokio::test]
+    async fn test_read_file_with_line_numbers() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_file_path = temp_dir.path().join("test.rs");
+        std::fs::write(&temp_file_path, "fn main() {\n    run();\n}\n").unwrap();
+
+        let tool = ReadTool;
+        let context = ToolExecutionContext::new(Uuid::new_v4())
+            .with_working_directory(temp_dir.path().to_path_buf());
+
+        let input = serde_json::json!({
+            "path": temp_file_path.to_str().unwrap(),
+            "line_numbers": true
+        });
+        let result = tool.execute(input, &context).await.unwrap();
+        assert_eq!(
+            result.output,
+            "   1| fn main() {\n   2|     run();\n   3| }"
+        );
+
+        let input = serde_json::json!({
+            "path": temp_file_path.to_str().unwrap(),
+            "start_line": 1,
+            "line_count": 1,
+            "line_numbers": true
+        });
+        let result = tool.execute(input, &context).await.unwrap();
+        assert_eq!(result.output, "   2|     run();");
+    }
+
+    #[test]
+    fn test_number_lines_widens_for_large_numbers() {
+        assert_eq!(number_lines("a\nb", 9999), " 9999| a\n10000| b");
+        assert_eq!(number_lines("", 1), "");
+    }
+
     #[tokio::test]
     async fn test_read_nonexistent_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -293,6 +355,29 @@ mod tests {
         assert!(result.error.unwrap().contains("not found"));
     }
 
+    #[tokio::test]
+    async fn test_read_file_rejects_traversal_outside_workspace() {
+        let outer = TempDir::new().unwrap();
+        let inner = outer.path().join("project");
+        std::fs::create_dir(&inner).unwrap();
+        std::fs::write(outer.path().join("secret.txt"), "top secret").unwrap();
+
+        let context = ToolExecutionContext::new(Uuid::new_v4()).with_working_directory(inner);
+        let escaped = outer.path().join("secret.txt");
+        for path in [
+            "../secret.txt",
+            "./../secret.txt",
+            escaped.to_str().unwrap(),
+        ] {
+            let result = ReadTool
+                .execute(serde_json::json!({ "path": path }), &context)
+                .await
+                .unwrap();
+            assert!(!result.success, "{path} should be refused");
+            assert!(result.error.unwrap().contains("Access denied"));
+        }
+    }
+
     #[test]
     fn test_read_tool_schema() {
         let tool = ReadTool;
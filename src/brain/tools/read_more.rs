@@ -0,0 +1,215 @@
+//! Read More Tool
+//!
+//! Tool results longer than `[tools] max_result_chars` reach the model
+//! truncated, with a handle to the full text. `read_more` pages through it.
+//! Handles belong to the session that produced the result; other sessions
+//! can't read them.
+
+use super::error::Result;
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Full results kept for paging; the oldest is dropped first
+const MAX_STORED_RESULTS: usize = 32;
+
+/// Page size when `max_result_chars` is 0 (truncation off)
+const DEFAULT_PAGE_CHARS: usize = 8000;
+
+/// (session, handle, full tool result), oldest first
+type StoredResults = VecDeque<(Uuid, String, String)>;
+static STORED: Lazy<Mutex<StoredResults>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Cut `content` to `max_chars` and append a note naming the handle the full
+/// text is stored under for `session_id`. Content within the limit (or
+/// `max_chars == 0`) is returned unchanged.
+pub fn truncate_result(session_id: Uuid, content: String, max_chars: usize) -> String {
+    let total = content.chars().count();
+    if max_chars == 0 || total <= max_chars {
+        return content;
+    }
+    let head: String = content.chars().take(max_chars).collect();
+    let handle = store(session_id, content);
+    format!(
+        "{head}\n\n(truncated, {} chars omitted — call read_more with handle={handle} and offset={max_chars} to continue)",
+        total - max_chars
+    )
+}
+
+fn store(session_id: Uuid, content: String) -> String {
+    let handle = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+    let mut stored = STORED.lock().unwrap_or_else(|e| e.into_inner());
+    if stored.len() >= MAX_STORED_RESULTS {
+        stored.pop_front();
+    }
+    stored.push_back((session_id, handle.clone(), content));
+    handle
+}
+
+/// Characters `offset..offset + limit` of `session_id`'s result under
+/// `handle`, with the result's total length.
+fn page(session_id: Uuid, handle: &str, offset: usize, limit: usize) -> Option<(String, usize)> {
+    let stored = STORED.lock().unwrap_or_else(|e| e.into_inner());
+    let (_, _, content) = stored
+        .iter()
+        .find(|(s, h, _)| *s == session_id && h == handle)?;
+    let text = content.chars().skip(offset).take(limit).collect();
+    Some((text, content.chars().count()))
+}
+
+/// Pages through tool results truncated by the tool loop.
+pub struct ReadMoreTool {
+    page_chars: usize,
+}
+
+impl ReadMoreTool {
+    /// `page_chars` is `[tools] max_result_chars`, so a page is never
+    /// truncated again.
+    pub fn new(page_chars: usize) -> Self {
+        Self {
+            page_chars: if page_chars == 0 {
+                DEFAULT_PAGE_CHARS
+            } else {
+                page_chars
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ReadMoreTool {
+    fn name(&self) -> &str {
+        "read_more"
+    }
+
+    fn description(&self) -> &str {
+        "Continue reading a tool result that was truncated. Pass the handle and \
+         offset from the '(truncated, ...)' note at the end of that result."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "handle": {
+                    "type": "string",
+                    "description": "Handle from the truncation note"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Character offset to read from (default: 0)",
+                    "default": 0
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": format!("Characters to read (default and max: {})", self.page_chars)
+                }
+            },
+            "required": ["handle"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadFiles]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        let Some(handle) = input.get("handle").and_then(|v| v.as_str()) else {
+            return Ok(ToolResult::error(
+                "handle parameter is required".to_string(),
+            ));
+        };
+        let offset = input.get("offset").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let limit = input
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(self.page_chars, |l| (l as usize).clamp(1, self.page_chars));
+
+        let Some((text, total)) = page(context.session_id, handle, offset, limit) else {
+            return Ok(ToolResult::error(format!(
+                "Unknown or expired handle '{handle}' — run the original tool again"
+            )));
+        };
+        let end = (offset + limit).min(total);
+        let note = if end < total {
+            format!(
+                "(chars {offset}-{end} of {total} — call read_more with handle={handle} and offset={end} to continue)"
+            )
+        } else {
+            format!("(end of result, {total} chars)")
+        };
+        Ok(ToolResult::success(format!("{text}\n\n{note}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle_of(note: &str) -> String {
+        let start = note.find("handle=").unwrap() + "handle=".len();
+        note[start..start + 8].to_string()
+    }
+
+    #[test]
+    fn test_short_result_untouched() {
+        let session = Uuid::new_v4();
+        assert_eq!(truncate_result(session, "short".to_string(), 100), "short");
+        assert_eq!(truncate_result(session, "x".repeat(50), 0), "x".repeat(50));
+    }
+
+    #[tokio::test]
+    async fn test_truncate_and_page_through() {
+        let full: String = (0..25).map(|i| char::from(b'a' + i as u8)).collect();
+        let session = Uuid::new_v4();
+        let truncated = truncate_result(session, full.clone(), 10);
+        assert!(truncated.starts_with("abcdefghij\n\n(truncated, 15 chars omitted"));
+
+        let tool = ReadMoreTool::new(10);
+        let ctx = ToolExecutionContext::new(session);
+        let handle = handle_of(&truncated);
+
+        let second = tool
+            .execute(serde_json::json!({"handle": handle, "offset": 10}), &ctx)
+            .await
+            .unwrap();
+        assert!(
+            second
+                .output
+                .starts_with("klmnopqrst\n\n(chars 10-20 of 25")
+        );
+
+        let last = tool
+            .execute(
+                serde_json::json!({"handle": handle, "offset": 20, "limit": 100}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(last.output, "uvwxy\n\n(end of result, 25 chars)");
+
+        let missing = tool
+            .execute(serde_json::json!({"handle": "nope"}), &ctx)
+            .await
+            .unwrap();
+        assert!(!missing.success);
+
+        // Another session can't read this session's results
+        let other = tool
+            .execute(
+                serde_json::json!({"handle": handle}),
+                &ToolExecutionContext::new(Uuid::new_v4()),
+            )
+            .await
+            .unwrap();
+        assert!(!other.success);
+    }
+}
@@ -3,8 +3,9 @@
 //! Lets the agent build OpenCrabs from source and exec() restart automatically.
 //! The build runs via `SelfUpdater::build_streaming` — progress lines are forwarded
 //! through the ProgressCallback so the TUI shows them live.  On success, a
-//! `ProgressEvent::RestartReady` is emitted which triggers an automatic exec() restart
-//! (no user prompt needed).
+//! `ProgressEvent::RestartReady` is emitted carrying the source diff against `HEAD`:
+//! with no local changes the TUI exec() restarts automatically, otherwise it shows
+//! the diff and waits for the user to confirm.
 
 use super::error::Result;
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
@@ -32,8 +33,9 @@ impl Tool for RebuildTool {
 
     fn description(&self) -> &str {
         "Build OpenCrabs from source (cargo build --release) and signal the TUI to hot-restart. \
-         Call this after editing source code to apply your changes. On success the binary is \
-         exec()-replaced automatically (no prompt). On failure the compiler output is returned."
+         Call this after editing source code to apply your changes. On success the user is shown \
+         a diff of the source changes and confirms the restart. On failure the compiler output \
+         is returned."
     }
 
     fn input_schema(&self) -> Value {
@@ -87,18 +89,33 @@ impl Tool for RebuildTool {
 
         match result {
             Ok(path) => {
-                // Signal auto-restart — TuiEvent::RestartReady triggers exec() with no prompt
+                let diff = updater
+                    .diff("HEAD")
+                    .inspect_err(|e| tracing::warn!("Rebuild: could not diff source: {}", e))
+                    .ok();
+                let changes = match &diff {
+                    Some(d) if !d.is_empty() => {
+                        format!(
+                            " Source changes: {}. Waiting for the user to confirm the restart.",
+                            d.summary()
+                        )
+                    }
+                    _ => " Restarting now.".to_string(),
+                };
+                // Signal restart — the TUI reviews a non-empty diff with the user first
                 if let Some(ref cb) = self.progress {
                     cb(
                         sid,
                         ProgressEvent::RestartReady {
                             status: format!("Build successful: {}", path.display()),
+                            diff,
                         },
                     );
                 }
                 Ok(ToolResult::success(format!(
-                    "Build successful: {}. Restarting now.",
-                    path.display()
+                    "Build successful: {}.{}",
+                    path.display(),
+                    changes
                 )))
             }
             Err(output) => Ok(ToolResult::error(format!("Build failed:\n{}", output))),
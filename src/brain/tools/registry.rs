@@ -3,9 +3,9 @@
 //! Manages the collection of available tools that can be invoked by agents.
 
 use super::error::{Result, ToolError};
-use super::r#trait::{Tool, ToolExecutionContext, ToolResult};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Per-tool parameter aliases that LLMs commonly confuse.
@@ -25,6 +25,9 @@ const PARAM_ALIASES: &[(&str, &str, &str)] = &[
     ("edit_file", "file", "path"),
     ("edit_file", "file_path", "path"),
     ("edit_file", "filepath", "path"),
+    ("apply_patch", "file", "path"),
+    ("apply_patch", "file_path", "path"),
+    ("apply_patch", "diff", "patch"),
     ("doc_parser", "file", "path"),
     ("doc_parser", "file_path", "path"),
     // write: "text", "body" → "content"
@@ -62,9 +65,25 @@ fn normalize_tool_input(tool_name: &str, mut input: Value) -> Value {
     input
 }
 
+/// Metadata snapshot of a registered tool (for `/tools` introspection)
+#[derive(Debug, Clone)]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: String,
+    pub capabilities: Vec<ToolCapability>,
+    pub requires_approval: bool,
+    pub input_schema: Value,
+}
+
 /// Registry of available tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
+    /// Tool names that are never registered (`[tools] disabled`)
+    disabled: HashSet<String>,
+    /// Tool names that always require approval (`[tools] require_approval`)
+    forced_approval: HashSet<String>,
+    /// Names registered more than once (reported by `validate`)
+    duplicates: Vec<String>,
 }
 
 impl ToolRegistry {
@@ -72,16 +91,71 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            disabled: HashSet::new(),
+            forced_approval: HashSet::new(),
+            duplicates: Vec::new(),
         }
     }
 
-    /// Register a tool
+    /// Create an empty registry that enforces the `[tools]` config policy:
+    /// disabled tools are skipped on register, listed tools always need approval.
+    pub fn with_policy(policy: &crate::config::ToolsConfig) -> Self {
+        Self {
+            tools: HashMap::new(),
+            disabled: policy.disabled.iter().cloned().collect(),
+            forced_approval: policy.require_approval.iter().cloned().collect(),
+            duplicates: Vec::new(),
+        }
+    }
+
+    /// Register a tool (no-op if the tool is disabled by config).
+    ///
+    /// A name collision replaces the earlier tool, logs a warning and is
+    /// recorded so `validate` fails at startup.
     pub fn register(&mut self, tool: Arc<dyn Tool>) {
         let name = tool.name().to_string();
+        if self.disabled.contains(&name) {
+            tracing::info!("Tool '{}' disabled by config — not registered", name);
+            return;
+        }
+        if self.tools.contains_key(&name) {
+            tracing::warn!(
+                "Tool name collision: '{}' registered twice — the later tool shadows the earlier one",
+                name
+            );
+            self.duplicates.push(name.clone());
+        }
         tracing::debug!("Registered tool: {}", name);
         self.tools.insert(name, tool);
     }
 
+    /// Fail if any tool name was registered more than once
+    pub fn validate(&self) -> Result<()> {
+        if self.duplicates.is_empty() {
+            return Ok(());
+        }
+        let mut names = self.duplicates.clone();
+        names.sort();
+        names.dedup();
+        Err(ToolError::DuplicateName(names.join(", ")))
+    }
+
+    /// Whether `name` is in the config's `require_approval` list
+    pub fn is_approval_forced(&self, name: &str) -> bool {
+        self.forced_approval.contains(name)
+    }
+
+    /// Whether a call to `name` with `input` needs approval, taking both the
+    /// tool's own policy and the config's `require_approval` list into account
+    pub fn requires_approval_for(&self, name: &str, input: &Value) -> bool {
+        if self.forced_approval.contains(name) {
+            return true;
+        }
+        self.tools
+            .get(name)
+            .is_some_and(|tool| tool.requires_approval_for_input(input))
+    }
+
     /// Get a tool by name
     pub fn get(&self, name: &str) -> Option<Arc<dyn Tool>> {
         self.tools.get(name).cloned()
@@ -97,6 +171,24 @@ impl ToolRegistry {
         self.tools.keys().cloned().collect()
     }
 
+    /// List every registered tool with its metadata, sorted by name
+    pub fn list(&self) -> Vec<ToolInfo> {
+        let mut tools: Vec<ToolInfo> = self
+            .tools
+            .values()
+            .map(|tool| ToolInfo {
+                name: tool.name().to_string(),
+                description: tool.description().to_string(),
+                capabilities: tool.capabilities(),
+                requires_approval: tool.requires_approval()
+                    || self.forced_approval.contains(tool.name()),
+                input_schema: tool.input_schema(),
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
     /// Get tool definitions in LLM format
     pub fn get_tool_definitions(&self) -> Vec<crate::brain::provider::Tool> {
         self.tools
@@ -127,7 +219,9 @@ impl ToolRegistry {
         tool.validate_input(&input)?;
 
         // Check if approval is required
-        if tool.requires_approval() && !context.auto_approve {
+        if (tool.requires_approval() || self.forced_approval.contains(name))
+            && !context.auto_approve
+        {
             return Err(ToolError::ApprovalRequired(format!(
                 "Tool '{}' requires approval before execution",
                 name
@@ -166,7 +260,6 @@ impl Default for ToolRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::brain::tools::r#trait::ToolCapability;
     use async_trait::async_trait;
     use uuid::Uuid;
 
@@ -236,6 +329,37 @@ mod tests {
         assert!(!registry.has_tool("nonexistent"));
     }
 
+    #[test]
+    fn test_duplicate_tool_name_is_reported() {
+        let mut registry = ToolRegistry::new();
+        for _ in 0..2 {
+            registry.register(Arc::new(MockTool {
+                name: "dup_tool".to_string(),
+                requires_approval: false,
+            }));
+        }
+        registry.register(Arc::new(MockTool {
+            name: "unique_tool".to_string(),
+            requires_approval: false,
+        }));
+
+        assert_eq!(registry.count(), 2);
+        match registry.validate() {
+            Err(ToolError::DuplicateName(names)) => assert_eq!(names, "dup_tool"),
+            other => panic!("expected DuplicateName, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_passes_without_duplicates() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool {
+            name: "tool1".to_string(),
+            requires_approval: false,
+        }));
+        assert!(registry.validate().is_ok());
+    }
+
     #[test]
     fn test_list_tools() {
         let mut registry = ToolRegistry::new();
@@ -255,6 +379,74 @@ mod tests {
         assert!(tools.contains(&"tool2".to_string()));
     }
 
+    #[test]
+    fn test_list_returns_sorted_metadata() {
+        let mut registry = ToolRegistry::new();
+
+        registry.register(Arc::new(MockTool {
+            name: "zeta".to_string(),
+            requires_approval: true,
+        }));
+        registry.register(Arc::new(MockTool {
+            name: "alpha".to_string(),
+            requires_approval: false,
+        }));
+
+        let tools = registry.list();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "alpha");
+        assert!(!tools[0].requires_approval);
+        assert_eq!(tools[1].name, "zeta");
+        assert!(tools[1].requires_approval);
+        assert_eq!(tools[1].capabilities, vec![ToolCapability::ReadFiles]);
+        assert_eq!(tools[1].description, "A mock tool for testing");
+    }
+
+    #[test]
+    fn test_policy_skips_disabled_tools() {
+        let policy = crate::config::ToolsConfig {
+            disabled: vec!["self_update".to_string()],
+            ..Default::default()
+        };
+        let mut registry = ToolRegistry::with_policy(&policy);
+        registry.register(Arc::new(MockTool {
+            name: "self_update".to_string(),
+            requires_approval: false,
+        }));
+        registry.register(Arc::new(MockTool {
+            name: "read_file".to_string(),
+            requires_approval: false,
+        }));
+
+        assert_eq!(registry.count(), 1);
+        assert!(!registry.has_tool("self_update"));
+        assert!(registry.has_tool("read_file"));
+    }
+
+    #[tokio::test]
+    async fn test_policy_forces_approval() {
+        let policy = crate::config::ToolsConfig {
+            require_approval: vec!["fetch_url".to_string()],
+            ..Default::default()
+        };
+        let mut registry = ToolRegistry::with_policy(&policy);
+        registry.register(Arc::new(MockTool {
+            name: "fetch_url".to_string(),
+            requires_approval: false,
+        }));
+
+        let input = serde_json::json!({ "message": "test" });
+        assert!(registry.requires_approval_for("fetch_url", &input));
+        assert!(registry.list()[0].requires_approval);
+
+        let context = ToolExecutionContext::new(Uuid::new_v4());
+        let result = registry.execute("fetch_url", input, &context).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            ToolError::ApprovalRequired(_)
+        ));
+    }
+
     #[tokio::test]
     async fn test_execute_tool() {
         let mut registry = ToolRegistry::new();
@@ -0,0 +1,624 @@
+//! Reminder Tools
+//!
+//! `set_reminder`, `list_reminders` and `cancel_reminder` let the agent
+//! schedule one-shot messages for the owner. The reminder scheduler
+//! (`crate::cron::ReminderScheduler`) delivers them to the owner's channel
+//! when they come due. `schedule_task` stores an instruction the scheduler
+//! runs as an agent turn instead, delivering the reply.
+//!
+//! Every delivery goes to the owner, so all four tools refuse calls from
+//! other channel users' sessions.
+
+use super::error::{Result, ToolError};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use crate::db::ReminderRepository;
+use crate::db::models::Reminder;
+use async_trait::async_trait;
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Channels a reminder can be pinned to. Omitted = first connected channel.
+const REMINDER_CHANNELS: &[&str] = &["telegram", "discord", "whatsapp"];
+
+/// Whether the call comes from the owner's current session.
+async fn is_owner(owner_session: &Mutex<Option<Uuid>>, context: &ToolExecutionContext) -> bool {
+    *owner_session.lock().await == Some(context.session_id)
+}
+
+/// Read the `when` and optional `channel` inputs shared by `set_reminder`
+/// and `schedule_task`: a known channel and a future time in the user's
+/// timezone. The error is the message to return to the agent.
+fn parse_schedule(input: &Value) -> std::result::Result<(DateTime<Utc>, Option<String>), String> {
+    let when = match input.get("when").and_then(|v| v.as_str()) {
+        Some(w) if !w.trim().is_empty() => w,
+        _ => return Err("'when' is required".to_string()),
+    };
+    let channel = input
+        .get("channel")
+        .and_then(|v| v.as_str())
+        .filter(|c| !c.is_empty())
+        .map(str::to_lowercase);
+    if let Some(ref c) = channel
+        && !REMINDER_CHANNELS.contains(&c.as_str())
+    {
+        return Err(format!(
+            "Unknown channel '{c}'. Valid: {}",
+            REMINDER_CHANNELS.join(", ")
+        ));
+    }
+
+    let now = Utc::now();
+    let at = match crate::config::timezone() {
+        Some(tz) => parse_when(when, now.with_timezone(&tz)),
+        None => parse_when(when, now.with_timezone(&Local)),
+    }?;
+    if at <= now {
+        return Err(format!(
+            "'{when}' resolves to {} which is in the past.",
+            format_local(at)
+        ));
+    }
+    Ok((at, channel))
+}
+
+/// Parse a reminder time — ISO 8601 or simple natural language — relative to `now`.
+///
+/// Accepted forms: RFC 3339 (`2026-03-10T09:00:00Z`), local `YYYY-MM-DD HH:MM`,
+/// `YYYY-MM-DD` (09:00), `in 20 minutes` / `in 2h`, `tomorrow at 9am`,
+/// `today 17:30`, `tonight`, `friday at noon`, `at 18:00` (next occurrence).
+pub fn parse_when<Tz: TimeZone>(
+    input: &str,
+    now: DateTime<Tz>,
+) -> std::result::Result<DateTime<Utc>, String> {
+    let raw = input.trim();
+    if raw.is_empty() {
+        return Err("'when' is empty".to_string());
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    let tz = now.timezone();
+    let to_utc = |ndt: NaiveDateTime| -> std::result::Result<DateTime<Utc>, String> {
+        tz.from_local_datetime(&ndt)
+            .earliest()
+            .map(|d| d.with_timezone(&Utc))
+            .ok_or_else(|| format!("'{raw}' does not exist in the local timezone"))
+    };
+
+    for fmt in [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ] {
+        if let Ok(ndt) = NaiveDateTime::parse_from_str(raw, fmt) {
+            return to_utc(ndt);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return to_utc(date.and_time(default_time()));
+    }
+
+    let lower = raw.to_lowercase();
+    let text = lower.trim_end_matches('.');
+
+    // Relative: "in 20 minutes", "in an hour", "in 2h", "90 minutes from now"
+    let relative = text
+        .strip_prefix("in ")
+        .or_else(|| text.strip_suffix(" from now"));
+    if let Some(rel) = relative {
+        let delta = parse_duration(rel.trim())
+            .ok_or_else(|| format!("Could not understand duration '{rel}'"))?;
+        return now
+            .with_timezone(&Utc)
+            .checked_add_signed(delta)
+            .ok_or_else(|| format!("'{rel}' is too far in the future"));
+    }
+
+    let today = now.date_naive();
+    let (first, rest) = match text.split_once(' ') {
+        Some((f, r)) => (f, r.trim()),
+        None => (text, ""),
+    };
+    let rest = rest.strip_prefix("at ").unwrap_or(rest).trim();
+    let time_or = |default: NaiveTime| -> std::result::Result<NaiveTime, String> {
+        if rest.is_empty() {
+            Ok(default)
+        } else {
+            parse_time_of_day(rest).ok_or_else(|| format!("Could not understand time '{rest}'"))
+        }
+    };
+
+    // Day word: "today", "tomorrow", "tonight", weekday names
+    let day = match first {
+        "today" => Some((today, time_or(default_time())?)),
+        "tonight" => Some((
+            today,
+            time_or(NaiveTime::from_hms_opt(20, 0, 0).unwrap_or_default())?,
+        )),
+        "tomorrow" => Some((today + Duration::days(1), time_or(default_time())?)),
+        "next" => {
+            // "next monday at 9"
+            let (wd, after) = rest.split_once(' ').unwrap_or((rest, ""));
+            let after = after.trim();
+            let after = after.strip_prefix("at ").unwrap_or(after).trim();
+            let weekday =
+                parse_weekday(wd).ok_or_else(|| format!("Could not understand '{raw}'"))?;
+            let time = if after.is_empty() {
+                default_time()
+            } else {
+                parse_time_of_day(after)
+                    .ok_or_else(|| format!("Could not understand time '{after}'"))?
+            };
+            Some((next_weekday(today, weekday), time))
+        }
+        other => match parse_weekday(other) {
+            Some(wd) => Some((next_weekday(today, wd), time_or(default_time())?)),
+            None => None,
+        },
+    };
+    if let Some((date, time)) = day {
+        return to_utc(date.and_time(time));
+    }
+
+    // Bare time: "at 18:00", "9am" — today, or tomorrow if already past
+    let bare = text.strip_prefix("at ").unwrap_or(text).trim();
+    if let Some(time) = parse_time_of_day(bare) {
+        let candidate = to_utc(today.and_time(time))?;
+        if candidate > now.with_timezone(&Utc) {
+            return Ok(candidate);
+        }
+        return to_utc((today + Duration::days(1)).and_time(time));
+    }
+
+    Err(format!(
+        "Could not understand '{raw}'. Use ISO 8601 (2026-03-10T09:00:00Z), \
+         'YYYY-MM-DD HH:MM', 'in 30 minutes', 'tomorrow at 9am' or 'friday at noon'."
+    ))
+}
+
+/// Default time of day when only a date is given.
+fn default_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default()
+}
+
+/// Parse "20 minutes", "an hour", "2h", "1 day", "3 weeks".
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    // Split number and unit: "2h", "2 h", "an hour"
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let (amount, unit) = if num.is_empty() {
+        let (article, unit) = s.split_once(' ')?;
+        if article != "a" && article != "an" {
+            return None;
+        }
+        (1, unit.trim())
+    } else {
+        (num.parse::<i64>().ok()?, unit.trim())
+    };
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(amount),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(amount),
+        "d" | "day" | "days" => Duration::try_days(amount),
+        "w" | "week" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Parse "9", "9am", "9:30 pm", "17:00", "noon", "midnight".
+fn parse_time_of_day(s: &str) -> Option<NaiveTime> {
+    let s = s.trim();
+    match s {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    let (body, pm) = if let Some(b) = s.strip_suffix("am") {
+        (b.trim(), Some(false))
+    } else if let Some(b) = s.strip_suffix("pm") {
+        (b.trim(), Some(true))
+    } else {
+        (s, None)
+    };
+    let (h, m) = match body.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None => (body.parse::<u32>().ok()?, 0),
+    };
+    let h = match pm {
+        Some(pm) => {
+            if h == 0 || h > 12 {
+                return None;
+            }
+            h % 12 + if pm { 12 } else { 0 }
+        }
+        None => h,
+    };
+    NaiveTime::from_hms_opt(h, m, 0)
+}
+
+fn parse_weekday(s: &str) -> Option<chrono::Weekday> {
+    match s {
+        "monday" | "mon" => Some(chrono::Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(chrono::Weekday::Tue),
+        "wednesday" | "wed" => Some(chrono::Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(chrono::Weekday::Thu),
+        "friday" | "fri" => Some(chrono::Weekday::Fri),
+        "saturday" | "sat" => Some(chrono::Weekday::Sat),
+        "sunday" | "sun" => Some(chrono::Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The next date (strictly after `from`) that falls on `weekday`.
+fn next_weekday(from: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    from + Duration::days(ahead as i64)
+}
+
+/// Tool for scheduling a reminder.
+pub struct SetReminderTool {
+    repo: ReminderRepository,
+    /// The owner's current session; reminders are delivered to the owner,
+    /// so other channel users may not set them.
+    owner_session: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl SetReminderTool {
+    pub fn new(repo: ReminderRepository, owner_session: Arc<Mutex<Option<Uuid>>>) -> Self {
+        Self {
+            repo,
+            owner_session,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SetReminderTool {
+    fn name(&self) -> &str {
+        "set_reminder"
+    }
+
+    fn description(&self) -> &str {
+        "Schedule a one-shot reminder for the user. When it comes due, the message is sent to the \
+         user's channel (Telegram/Discord/WhatsApp) and shown in the TUI. Use for 'remind me ...' requests. \
+         Only the owner can set reminders."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "when": {
+                    "type": "string",
+                    "description": "When to fire, in the user's local time unless an offset is given. ISO 8601 ('2026-03-10T09:00:00Z', '2026-03-10 09:00') or natural ('in 30 minutes', 'tomorrow at 9am', 'friday at noon', 'at 18:00')"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Reminder text delivered to the user"
+                },
+                "channel": {
+                    "type": "string",
+                    "enum": REMINDER_CHANNELS,
+                    "description": "Deliver on this channel. Omit to use the first connected channel"
+                }
+            },
+            "required": ["when", "message"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        if !is_owner(&self.owner_session, context).await {
+            return Ok(ToolResult::error(
+                "Only the owner can set reminders.".to_string(),
+            ));
+        }
+        let message = match input.get("message").and_then(|v| v.as_str()) {
+            Some(m) if !m.trim().is_empty() => m,
+            _ => return Ok(ToolResult::error("'message' is required".to_string())),
+        };
+        let (remind_at, channel) = match parse_schedule(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
+
+        let reminder = Reminder::new(
+            message.to_string(),
+            remind_at,
+            channel.clone(),
+            Some(context.session_id),
+        );
+        self.repo
+            .insert(&reminder)
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+
+        Ok(ToolResult::success(format!(
+            "Reminder set:\n  ID: {}\n  When: {}\n  Channel: {}\n  Message: {}",
+            reminder.id,
+            format_local(remind_at),
+            channel.as_deref().unwrap_or("first connected"),
+            message
+        )))
+    }
+}
+
+/// Tool for scheduling a delayed agent action.
+pub struct ScheduleTaskTool {
+    repo: ReminderRepository,
+    /// The owner's current session (TUI, or the owner on a channel). Only
+    /// calls from it may schedule tasks; other channel users may not.
+    owner_session: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl ScheduleTaskTool {
+    pub fn new(repo: ReminderRepository, owner_session: Arc<Mutex<Option<Uuid>>>) -> Self {
+        Self {
+            repo,
+            owner_session,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ScheduleTaskTool {
+    fn name(&self) -> &str {
+        "schedule_task"
+    }
+
+    fn description(&self) -> &str {
+        "Schedule an instruction for yourself to carry out later, e.g. 'in 2 hours, check if the \
+         build finished and report'. At that time it runs as an autonomous turn in this \
+         session and the reply is sent to the user's channel. Nobody is there to approve tools \
+         then, so ones that need approval are denied unless the approval policy is auto-always. \
+         Only the owner can schedule tasks. Use set_reminder when the user only needs a \
+         message. Cancel with cancel_reminder."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "when": {
+                    "type": "string",
+                    "description": "When to run, in the user's local time unless an offset is given. ISO 8601 ('2026-03-10T09:00:00Z', '2026-03-10 09:00') or natural ('in 2 hours', 'tomorrow at 9am', 'at 18:00')"
+                },
+                "instruction": {
+                    "type": "string",
+                    "description": "What to do at that time, written as a self-contained instruction"
+                },
+                "channel": {
+                    "type": "string",
+                    "enum": REMINDER_CHANNELS,
+                    "description": "Deliver the result on this channel. Omit to use the first connected channel"
+                }
+            },
+            "required": ["when", "instruction"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        true // the task later runs with nobody watching
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        if !is_owner(&self.owner_session, context).await {
+            return Ok(ToolResult::error(
+                "Only the owner can schedule tasks.".to_string(),
+            ));
+        }
+        let instruction = match input.get("instruction").and_then(|v| v.as_str()) {
+            Some(i) if !i.trim().is_empty() => i,
+            _ => return Ok(ToolResult::error("'instruction' is required".to_string())),
+        };
+        let (run_at, channel) = match parse_schedule(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
+
+        let task = Reminder::new_task(
+            instruction.to_string(),
+            run_at,
+            channel.clone(),
+            Some(context.session_id),
+        );
+        self.repo
+            .insert(&task)
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+
+        Ok(ToolResult::success(format!(
+            "Task scheduled:\n  ID: {}\n  When: {}\n  Channel: {}\n  Instruction: {}",
+            task.id,
+            format_local(run_at),
+            channel.as_deref().unwrap_or("first connected"),
+            instruction
+        )))
+    }
+}
+
+/// Tool for listing pending reminders.
+pub struct ListRemindersTool {
+    repo: ReminderRepository,
+    /// The owner's current session; only it may see the pending list.
+    owner_session: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl ListRemindersTool {
+    pub fn new(repo: ReminderRepository, owner_session: Arc<Mutex<Option<Uuid>>>) -> Self {
+        Self {
+            repo,
+            owner_session,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ListRemindersTool {
+    fn name(&self) -> &str {
+        "list_reminders"
+    }
+
+    fn description(&self) -> &str {
+        "List the user's pending reminders and scheduled tasks (soonest first) with their IDs. \
+         Only the owner can list them."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, _input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        if !is_owner(&self.owner_session, context).await {
+            return Ok(ToolResult::error(
+                "Only the owner can list reminders.".to_string(),
+            ));
+        }
+        let reminders = self
+            .repo
+            .list_pending()
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+
+        if reminders.is_empty() {
+            return Ok(ToolResult::success("No pending reminders.".to_string()));
+        }
+
+        let lines: Vec<String> = reminders
+            .iter()
+            .map(|r| {
+                format!(
+                    "- {}{} (id={})\n    Channel: {}\n    {}: {}",
+                    format_local(r.remind_at),
+                    if r.run_agent { " [task]" } else { "" },
+                    r.id,
+                    r.channel.as_deref().unwrap_or("first connected"),
+                    if r.run_agent {
+                        "Instruction"
+                    } else {
+                        "Message"
+                    },
+                    r.message
+                )
+            })
+            .collect();
+
+        Ok(ToolResult::success(format!(
+            "Pending reminders ({}):\n{}",
+            reminders.len(),
+            lines.join("\n")
+        )))
+    }
+}
+
+/// Tool for cancelling a pending reminder.
+pub struct CancelReminderTool {
+    repo: ReminderRepository,
+    /// The owner's current session; only it may cancel reminders.
+    owner_session: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl CancelReminderTool {
+    pub fn new(repo: ReminderRepository, owner_session: Arc<Mutex<Option<Uuid>>>) -> Self {
+        Self {
+            repo,
+            owner_session,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CancelReminderTool {
+    fn name(&self) -> &str {
+        "cancel_reminder"
+    }
+
+    fn description(&self) -> &str {
+        "Cancel a pending reminder or scheduled task by ID (use list_reminders to find it). \
+         Only the owner can cancel them."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "reminder_id": {
+                    "type": "string",
+                    "description": "ID of the reminder to cancel"
+                }
+            },
+            "required": ["reminder_id"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        if !is_owner(&self.owner_session, context).await {
+            return Ok(ToolResult::error(
+                "Only the owner can cancel reminders.".to_string(),
+            ));
+        }
+        let id = match input.get("reminder_id").and_then(|v| v.as_str()) {
+            Some(id) if !id.is_empty() => id,
+            _ => return Ok(ToolResult::error("'reminder_id' is required".to_string())),
+        };
+
+        let cancelled = self
+            .repo
+            .cancel(id)
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+
+        if cancelled {
+            Ok(ToolResult::success(format!("Reminder {id} cancelled.")))
+        } else {
+            Ok(ToolResult::error(format!(
+                "No pending reminder found with ID '{id}'."
+            )))
+        }
+    }
+}
+
+/// Format a UTC timestamp in the user's local time.
+fn format_local(ts: DateTime<Utc>) -> String {
+    crate::config::format_local(ts, "%Y-%m-%d %H:%M %Z")
+}
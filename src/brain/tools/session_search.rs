@@ -130,7 +130,7 @@ impl SessionSearchTool {
         for (i, session) in sessions.iter().enumerate() {
             let count = message_repo.count_by_session(session.id).await.unwrap_or(0);
             let title = session.title.as_deref().unwrap_or("Untitled");
-            let date = session.updated_at.format("%Y-%m-%d").to_string();
+            let date = crate::config::format_local(session.updated_at, "%Y-%m-%d");
             output.push_str(&format!(
                 "{}. \"{}\" — {}, {} messages\n",
                 i + 1,
@@ -246,7 +246,7 @@ impl SessionSearchTool {
             );
 
             let title_str = title.to_string();
-            let date = session.updated_at.format("%Y-%m-%d").to_string();
+            let date = crate::config::format_local(session.updated_at, "%Y-%m-%d");
             let mut body = format!(
                 "# {}\nDate: {}\nSession: {}\n\n",
                 title_str, date, session.id
@@ -372,9 +372,7 @@ fn index_session_body(
     let now = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string();
 
     tracing::debug!("[index_session] Acquiring store lock for {}", doc_path);
-    let s = store
-        .lock()
-        .map_err(|e| format!("Store lock poisoned: {e}"))?;
+    let s = crate::memory::lock_store(store);
     tracing::debug!("[index_session] Store lock acquired for {}", doc_path);
 
     if matches!(s.find_active_document(COLLECTION, doc_path), Ok(Some((_, ref h, _))) if h == &hash)
@@ -407,9 +405,14 @@ fn index_session_body(
         let doc_title = Store::extract_title(&body);
         if let Ok(emb) = engine.embed_document(&body, Some(&doc_title)) {
             drop(engine); // release engine before re-acquiring store
-            if let Ok(st) = store.lock() {
-                let _ = st.insert_embedding(&hash, 0, 0, &emb.embedding, &emb.model, &now);
-            }
+            let _ = crate::memory::lock_store(store).insert_embedding(
+                &hash,
+                0,
+                0,
+                &emb.embedding,
+                &emb.model,
+                &now,
+            );
         }
     }
 
@@ -446,9 +449,7 @@ fn search_in_sessions(
     );
 
     tracing::info!("[search_in_sessions] Acquiring store lock for search");
-    let s = store
-        .lock()
-        .map_err(|e| format!("Store lock poisoned: {e}"))?;
+    let s = crate::memory::lock_store(store);
     tracing::info!("[search_in_sessions] Store lock acquired");
 
     let fts_results = s
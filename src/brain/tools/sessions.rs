@@ -0,0 +1,375 @@
+//! Sessions Tool
+//!
+//! Lets the agent list recent sessions and read another session's recent
+//! messages, so it can pull context from a past conversation. Read-only —
+//! nothing here writes to any session. Only the owner sees other sessions;
+//! a non-owner channel user's turn is limited to its own.
+
+use super::error::{Result, ToolError};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use crate::db::repository::SessionListOptions;
+use crate::services::{MessageService, ServiceContext, SessionService};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Sessions listed when `n` is not given
+const DEFAULT_LIST: usize = 20;
+
+/// Messages returned by `read` when `n` is not given
+const DEFAULT_READ: usize = 20;
+
+/// Upper bound on `n` for either operation
+const MAX_N: usize = 100;
+
+/// Longest message body returned by `read`; longer ones are cut
+const MAX_MESSAGE_CHARS: usize = 2_000;
+
+/// Tool for listing sessions and reading their recent messages
+pub struct SessionsTool {
+    context: ServiceContext,
+    /// The owner's current session (TUI, or the owner on a channel). Calls
+    /// from any other session only see that session.
+    owner_session: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl SessionsTool {
+    pub fn new(context: ServiceContext, owner_session: Arc<Mutex<Option<Uuid>>>) -> Self {
+        Self {
+            context,
+            owner_session,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionsInput {
+    operation: String,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    n: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for SessionsTool {
+    fn name(&self) -> &str {
+        "sessions"
+    }
+
+    fn description(&self) -> &str {
+        "List recent chat sessions or read another session's recent messages (read-only). \
+         Use 'list' to get session ids and titles, then 'read' with a session_id (or its \
+         first 8 characters) to pull context from a past conversation. Only the owner can \
+         see sessions other than the current one."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["list", "read"],
+                    "description": "'list' recent sessions, or 'read' one session's messages"
+                },
+                "session_id": {
+                    "type": "string",
+                    "description": "Session id or id prefix from 'list' (required for 'read')"
+                },
+                "n": {
+                    "type": "integer",
+                    "description": "Sessions to list or most recent messages to read (default: 20, max: 100)",
+                    "minimum": 1
+                }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadFiles]
+    }
+
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    fn validate_input(&self, input: &Value) -> Result<()> {
+        let _: SessionsInput = serde_json::from_value(input.clone())
+            .map_err(|e| ToolError::InvalidInput(format!("Invalid input: {}", e)))?;
+        Ok(())
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        let input: SessionsInput = serde_json::from_value(input)?;
+        let is_owner = *self.owner_session.lock().await == Some(context.session_id);
+        match input.operation.as_str() {
+            "list" => {
+                self.list(
+                    input.n.unwrap_or(DEFAULT_LIST),
+                    context.session_id,
+                    is_owner,
+                )
+                .await
+            }
+            "read" => {
+                let Some(id) = input.session_id.filter(|s| !s.trim().is_empty()) else {
+                    return Ok(ToolResult::error(
+                        "'session_id' is required for read".to_string(),
+                    ));
+                };
+                let id = id.trim();
+                let session_id = if is_owner {
+                    match self.resolve(id).await? {
+                        Ok(session_id) => session_id,
+                        Err(message) => return Ok(ToolResult::error(message)),
+                    }
+                } else if context
+                    .session_id
+                    .to_string()
+                    .starts_with(&id.to_lowercase())
+                {
+                    context.session_id
+                } else {
+                    return Ok(ToolResult::error(
+                        "Only the owner can read other sessions.".to_string(),
+                    ));
+                };
+                self.read(session_id, input.n.unwrap_or(DEFAULT_READ)).await
+            }
+            other => Ok(ToolResult::error(format!(
+                "Unknown operation '{}'. Use 'list' or 'read'.",
+                other
+            ))),
+        }
+    }
+}
+
+impl SessionsTool {
+    /// Recent sessions, or for a non-owner just `current`
+    async fn list(&self, n: usize, current: Uuid, is_owner: bool) -> Result<ToolResult> {
+        let session_service = SessionService::new(self.context.clone());
+        let sessions = if is_owner {
+            session_service
+                .list_sessions(SessionListOptions {
+                    include_archived: false,
+                    limit: Some(n.clamp(1, MAX_N)),
+                    offset: 0,
+                })
+                .await
+        } else {
+            session_service
+                .get_session(current)
+                .await
+                .map(|s| s.into_iter().collect())
+        }
+        .map_err(|e| ToolError::Execution(e.to_string()))?;
+        if sessions.is_empty() {
+            return Ok(ToolResult::success("No sessions found.".to_string()));
+        }
+
+        let mut output = String::new();
+        for session in sessions {
+            let title = session.title.as_deref().unwrap_or("Untitled");
+            let date = crate::config::format_local(session.updated_at, "%Y-%m-%d %H:%M");
+            let marker = if session.id == current {
+                " (this session)"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "{} — \"{}\", updated {}{}\n",
+                session.id, title, date, marker
+            ));
+        }
+        Ok(ToolResult::success(output))
+    }
+
+    async fn read(&self, session_id: Uuid, n: usize) -> Result<ToolResult> {
+        let messages = MessageService::new(self.context.clone())
+            .list_messages_for_session(session_id)
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+        let messages: Vec<_> = messages
+            .into_iter()
+            .filter(|m| !m.content.trim().is_empty())
+            .collect();
+        if messages.is_empty() {
+            return Ok(ToolResult::success(
+                "That session has no messages.".to_string(),
+            ));
+        }
+
+        let skip = messages.len().saturating_sub(n.clamp(1, MAX_N));
+        let mut output = format!(
+            "Last {} of {} messages in session {}:\n",
+            messages.len() - skip,
+            messages.len(),
+            session_id
+        );
+        for message in &messages[skip..] {
+            let time = crate::config::format_local(message.created_at, "%Y-%m-%d %H:%M");
+            let body: String = message.content.chars().take(MAX_MESSAGE_CHARS).collect();
+            let cut = if message.content.chars().count() > MAX_MESSAGE_CHARS {
+                " […]"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "\n[{}] {}:\n{}{}\n",
+                time, message.role, body, cut
+            ));
+        }
+        Ok(ToolResult::success(output))
+    }
+
+    /// Full session id for `id`, which may be a unique prefix of one.
+    /// The inner error is a message for the model.
+    async fn resolve(&self, id: &str) -> Result<std::result::Result<Uuid, String>> {
+        if let Ok(uuid) = Uuid::parse_str(id) {
+            return Ok(Ok(uuid));
+        }
+        let id = id.to_lowercase();
+        let sessions = SessionService::new(self.context.clone())
+            .list_sessions(SessionListOptions {
+                include_archived: true,
+                limit: None,
+                offset: 0,
+            })
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+        let matches: Vec<Uuid> = sessions
+            .iter()
+            .map(|s| s.id)
+            .filter(|s| s.to_string().starts_with(&id))
+            .collect();
+        Ok(match matches.as_slice() {
+            [one] => Ok(*one),
+            [] => Err(format!("No session matches '{}'", id)),
+            _ => Err(format!(
+                "'{}' matches {} sessions — use more of the id",
+                id,
+                matches.len()
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    /// The tool with `owner` as the owner's session
+    async fn setup(owner: Option<Uuid>) -> (ServiceContext, SessionsTool) {
+        let db = Database::connect_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let context = ServiceContext::new(db.pool().clone());
+        let tool = SessionsTool::new(context.clone(), Arc::new(Mutex::new(owner)));
+        (context, tool)
+    }
+
+    #[tokio::test]
+    async fn test_list_and_read_by_prefix() {
+        let owner = Uuid::new_v4();
+        let (context, tool) = setup(Some(owner)).await;
+        let session = SessionService::new(context.clone())
+            .create_session(Some("Pricing decision".to_string()))
+            .await
+            .unwrap();
+        let messages = MessageService::new(context);
+        for (role, text) in [
+            ("user", "Should we charge per seat?"),
+            ("assistant", "Per seat, with a free tier."),
+            ("user", "Agreed."),
+        ] {
+            messages
+                .create_message(session.id, role.to_string(), text.to_string())
+                .await
+                .unwrap();
+        }
+
+        let ctx = ToolExecutionContext::new(owner);
+        let list = tool
+            .execute(serde_json::json!({"operation": "list"}), &ctx)
+            .await
+            .unwrap();
+        assert!(list.output.contains("Pricing decision"));
+        assert!(list.output.contains(&session.id.to_string()));
+
+        let prefix = &session.id.to_string()[..8];
+        let read = tool
+            .execute(
+                serde_json::json!({"operation": "read", "session_id": prefix, "n": 2}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(read.success);
+        assert!(read.output.starts_with("Last 2 of 3 messages"));
+        assert!(read.output.contains("Per seat, with a free tier."));
+        assert!(!read.output.contains("Should we charge per seat?"));
+    }
+
+    #[tokio::test]
+    async fn test_read_requires_known_session() {
+        let owner = Uuid::new_v4();
+        let (_context, tool) = setup(Some(owner)).await;
+        let ctx = ToolExecutionContext::new(owner);
+
+        let missing = tool
+            .execute(serde_json::json!({"operation": "read"}), &ctx)
+            .await
+            .unwrap();
+        assert!(!missing.success);
+
+        let unknown = tool
+            .execute(
+                serde_json::json!({"operation": "read", "session_id": "deadbeef"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!unknown.success);
+    }
+
+    #[tokio::test]
+    async fn test_non_owner_only_sees_own_session() {
+        let (context, tool) = setup(Some(Uuid::new_v4())).await;
+        let sessions = SessionService::new(context.clone());
+        let private = sessions
+            .create_session(Some("Owner notes".to_string()))
+            .await
+            .unwrap();
+        let own = sessions
+            .create_session(Some("Discord user".to_string()))
+            .await
+            .unwrap();
+        MessageService::new(context)
+            .create_message(private.id, "user".to_string(), "secret".to_string())
+            .await
+            .unwrap();
+
+        let ctx = ToolExecutionContext::new(own.id);
+        let list = tool
+            .execute(serde_json::json!({"operation": "list"}), &ctx)
+            .await
+            .unwrap();
+        assert!(list.output.contains("Discord user"));
+        assert!(!list.output.contains("Owner notes"));
+
+        let read = tool
+            .execute(
+                serde_json::json!({"operation": "read", "session_id": private.id.to_string()}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!read.success);
+        assert!(!read.output.contains("secret"));
+    }
+}
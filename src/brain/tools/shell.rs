@@ -0,0 +1,435 @@
+//! Allowlisted Shell Command Tool
+//!
+//! Runs a single command without a shell. Binaries listed in
+//! `[tools.shell] allowed` run without asking; anything else needs approval.
+//! `sudo` commands always need approval and ask the user for the password
+//! through the sudo callback.
+
+use super::error::{Result, ToolError};
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::{Duration, timeout};
+use zeroize::Zeroizing;
+
+/// Characters that only mean something to a shell. Commands using them
+/// unquoted are rejected — pipelines and redirects belong in `bash`.
+const SHELL_METACHARACTERS: &[char] = &['|', ';', '&', '>', '<', '`', '$'];
+
+/// Shell command tool gated by a binary allowlist
+pub struct ShellTool {
+    allowed: Vec<String>,
+}
+
+impl ShellTool {
+    /// `allowed` holds binary names (e.g. `git`, `ls`) that skip approval.
+    pub fn new(allowed: Vec<String>) -> Self {
+        Self { allowed }
+    }
+
+    /// Whether `argv[0]` is on the allowlist. Only bare names resolved
+    /// through `PATH` match; anything with a `/` (`./git`,
+    /// `node_modules/.bin/ls`) could be any binary and needs approval.
+    fn is_allowed(&self, program: &str) -> bool {
+        !program.contains('/') && self.allowed.iter().any(|a| a == program)
+    }
+}
+
+/// File name of `program`, so `/usr/bin/sudo` reads as `sudo`.
+fn program_name(program: &str) -> &str {
+    std::path::Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program)
+}
+
+/// Whether `argv` runs `sudo`, directly, by path, or through `env`
+/// (`env -i FOO=1 sudo …`).
+fn runs_sudo(argv: &[String]) -> bool {
+    let mut args = argv.iter().map(|a| program_name(a));
+    match args.next() {
+        Some("sudo") => true,
+        Some("env") => args
+            .find(|a| !a.starts_with('-') && !a.contains('='))
+            .is_some_and(|a| a == "sudo"),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ShellInput {
+    /// Command to execute
+    command: String,
+
+    /// Optional timeout in seconds (overrides context default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout_secs: Option<u64>,
+}
+
+/// Split `command` into arguments, honouring single and double quotes and
+/// backslash escapes. Fails on unbalanced quotes and unquoted shell
+/// metacharacters.
+fn split_command(command: &str) -> std::result::Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some('"') if c == '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            Some(_) => current.push(c),
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_arg = true;
+                }
+                '\\' => {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                    }
+                    in_arg = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_arg {
+                        args.push(std::mem::take(&mut current));
+                        in_arg = false;
+                    }
+                }
+                c if SHELL_METACHARACTERS.contains(&c) => {
+                    return Err(format!(
+                        "'{}' is shell syntax — the shell tool runs a single command; use bash for pipelines, redirects and variables",
+                        c
+                    ));
+                }
+                c => {
+                    current.push(c);
+                    in_arg = true;
+                }
+            },
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unbalanced quote in command".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[async_trait]
+impl Tool for ShellTool {
+    fn name(&self) -> &str {
+        "shell"
+    }
+
+    fn description(&self) -> &str {
+        "Run a single command (no pipes, redirects or variables) in the working directory. Returns stdout, stderr, and exit code. Allowlisted binaries run without approval — prefer this over bash for read-only commands like git status or ls."
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "Command and arguments, e.g. \"git log --oneline -5\""
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Optional: Timeout in seconds (default 120, max 600)"
+                }
+            },
+            "required": ["command"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ExecuteShell]
+    }
+
+    fn requires_approval(&self) -> bool {
+        true
+    }
+
+    fn requires_approval_for_input(&self, input: &Value) -> bool {
+        let Some(command) = input.get("command").and_then(|c| c.as_str()) else {
+            return true;
+        };
+        match split_command(command) {
+            Ok(argv) => {
+                runs_sudo(&argv) || argv.first().is_none_or(|program| !self.is_allowed(program))
+            }
+            Err(_) => true,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> Result<()> {
+        let input: ShellInput = serde_json::from_value(input.clone())
+            .map_err(|e| ToolError::InvalidInput(format!("Invalid input: {}", e)))?;
+
+        let argv = split_command(&input.command).map_err(ToolError::InvalidInput)?;
+        if argv.is_empty() {
+            return Err(ToolError::InvalidInput(
+                "Command cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn execute(&self, input: Value, context: &ToolExecutionContext) -> Result<ToolResult> {
+        let input: ShellInput = serde_json::from_value(input)?;
+        let argv = match split_command(&input.command) {
+            Ok(argv) if !argv.is_empty() => argv,
+            Ok(_) => return Ok(ToolResult::error("Command cannot be empty".to_string())),
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
+
+        let working_dir = &context.working_directory;
+        let effective_timeout = input.timeout_secs.unwrap_or(context.timeout_secs).min(600);
+
+        // Elevated commands get the password from the user; it lives only in
+        // this zeroizing buffer and the child's stdin
+        let sudo_password = if program_name(&argv[0]) == "sudo" {
+            match context.sudo_callback {
+                Some(ref callback) => match callback(input.command.clone()).await {
+                    Ok(Some(password)) => Some(Zeroizing::new(password)),
+                    Ok(None) => return Ok(ToolResult::error("Sudo cancelled by user".to_string())),
+                    Err(e) => return Ok(ToolResult::error(format!("Sudo prompt failed: {}", e))),
+                },
+                None => None, // No callback — run normally (fails if a password is needed)
+            }
+        } else {
+            None
+        };
+
+        let mut command = match sudo_password {
+            Some(_) => {
+                // -S reads the password from stdin; -p "" hides sudo's own prompt
+                let mut command = Command::new("sudo");
+                command
+                    .args(["-S", "-p", ""])
+                    .args(&argv[1..])
+                    .stdin(std::process::Stdio::piped());
+                command
+            }
+            None => {
+                let mut command = Command::new(&argv[0]);
+                command.args(&argv[1..]).stdin(std::process::Stdio::null());
+                command
+            }
+        };
+        command
+            .current_dir(working_dir)
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+
+        let command_future = async {
+            let mut child = command.spawn()?;
+            if let Some(password) = sudo_password
+                && let Some(mut stdin) = child.stdin.take()
+            {
+                let _ = stdin.write_all(password.as_bytes()).await;
+                let _ = stdin.write_all(b"\n").await;
+                drop(stdin);
+            }
+            child.wait_with_output().await
+        };
+
+        let output = match timeout(Duration::from_secs(effective_timeout), command_future).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                return Ok(ToolResult::error(format!(
+                    "Command execution failed: {}",
+                    e
+                )));
+            }
+            Err(_) => {
+                return Err(ToolError::Timeout(effective_timeout));
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+
+        let mut result_text = String::new();
+        if !stdout.is_empty() {
+            result_text.push_str("STDOUT:\n");
+            result_text.push_str(&stdout);
+        }
+        if !stderr.is_empty() {
+            if !result_text.is_empty() {
+                result_text.push_str("\n\n");
+            }
+            result_text.push_str("STDERR:\n");
+            result_text.push_str(&stderr);
+        }
+        if result_text.is_empty() {
+            result_text = "(no output)".to_string();
+        }
+
+        let result = if output.status.success() {
+            ToolResult::success(result_text)
+        } else {
+            ToolResult {
+                success: false,
+                output: result_text,
+                error: Some(format!("Command exited with code {}", exit_code)),
+                metadata: std::collections::HashMap::new(),
+            }
+        };
+
+        Ok(result
+            .with_metadata("exit_code".to_string(), exit_code.to_string())
+            .with_metadata("working_dir".to_string(), working_dir.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn tool() -> ShellTool {
+        ShellTool::new(vec!["git".to_string(), "echo".to_string()])
+    }
+
+    #[test]
+    fn test_split_command_honours_quotes() {
+        assert_eq!(
+            split_command(r#"git commit -m "fix: a b" 'c d' e\ f"#).unwrap(),
+            vec!["git", "commit", "-m", "fix: a b", "c d", "e f"]
+        );
+        assert_eq!(split_command("  ").unwrap(), Vec::<String>::new());
+        assert!(split_command("echo 'open").is_err());
+    }
+
+    #[test]
+    fn test_split_command_rejects_shell_syntax() {
+        assert!(split_command("ls | wc -l").is_err());
+        assert!(split_command("git status; rm -rf .").is_err());
+        assert!(split_command("echo $HOME").is_err());
+        assert!(split_command("cat x > y").is_err());
+        // Quoted metacharacters are plain arguments
+        assert!(split_command("grep -E 'a|b' file").is_ok());
+    }
+
+    #[test]
+    fn test_allowlisted_binaries_skip_approval() {
+        let tool = tool();
+        let ask =
+            |cmd: &str| tool.requires_approval_for_input(&serde_json::json!({ "command": cmd }));
+
+        assert!(!ask("git status"));
+        assert!(ask("/usr/bin/git log -1"));
+        assert!(ask("./git status"));
+        assert!(ask("node_modules/.bin/ls"));
+        assert!(ask("/tmp/x/cat secrets"));
+        assert!(ask("rm -rf target"));
+        assert!(ask("gitx status"));
+        assert!(ask("git status | cat"));
+        assert!(ask(""));
+        assert!(
+            ShellTool::new(Vec::new())
+                .requires_approval_for_input(&serde_json::json!({ "command": "git status" }))
+        );
+    }
+
+    #[test]
+    fn test_sudo_always_requires_approval() {
+        let tool = ShellTool::new(vec!["sudo".to_string(), "apt".to_string()]);
+        assert!(
+            tool.requires_approval_for_input(&serde_json::json!({ "command": "sudo apt update" }))
+        );
+        assert!(!tool.requires_approval_for_input(&serde_json::json!({ "command": "apt list" })));
+
+        let tool = ShellTool::new(vec!["env".to_string(), "apt".to_string()]);
+        for cmd in [
+            "/usr/bin/sudo apt update",
+            "env sudo apt update",
+            "env -i PATH=/usr/bin /usr/bin/sudo apt update",
+        ] {
+            assert!(
+                tool.requires_approval_for_input(&serde_json::json!({ "command": cmd })),
+                "{cmd}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_sudo_cancelled_by_user() {
+        let callback: crate::brain::agent::SudoCallback =
+            std::sync::Arc::new(|_command| Box::pin(async { Ok(None) }));
+        let mut context = ToolExecutionContext::new(Uuid::new_v4()).with_auto_approve(true);
+        context.sudo_callback = Some(callback);
+
+        let result = tool()
+            .execute(serde_json::json!({ "command": "sudo ls /root" }), &context)
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("cancelled"));
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_and_shell_syntax() {
+        let tool = tool();
+        assert!(
+            tool.validate_input(&serde_json::json!({ "command": "" }))
+                .is_err()
+        );
+        assert!(
+            tool.validate_input(&serde_json::json!({ "command": "ls && ls" }))
+                .is_err()
+        );
+        assert!(
+            tool.validate_input(&serde_json::json!({ "command": "ls -la" }))
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_shell_runs_in_working_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("marker.txt"), "").unwrap();
+        let context = ToolExecutionContext::new(Uuid::new_v4())
+            .with_auto_approve(true)
+            .with_working_directory(dir.path().to_path_buf());
+
+        let result = ShellTool::new(vec!["ls".to_string()])
+            .execute(serde_json::json!({ "command": "ls" }), &context)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("marker.txt"));
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_shell_timeout() {
+        let context = ToolExecutionContext::new(Uuid::new_v4())
+            .with_auto_approve(true)
+            .with_timeout(1);
+
+        let result = ShellTool::new(Vec::new())
+            .execute(serde_json::json!({ "command": "sleep 5" }), &context)
+            .await;
+        assert!(matches!(result, Err(ToolError::Timeout(_))));
+    }
+}
@@ -5,8 +5,8 @@
 
 use super::error::Result;
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
-use crate::channels::ChannelFactory;
 use crate::channels::telegram::TelegramState;
+use crate::channels::{ChannelFactory, MessageSource};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
@@ -104,7 +104,7 @@ impl Tool for TelegramConnectTool {
 
         // Create and spawn the Telegram agent
         let factory = self.channel_factory.clone();
-        let agent = factory.create_agent_service();
+        let agent = factory.create_channel_agent_service(MessageSource::Telegram);
         let service_context = factory.service_context();
         let shared_session = factory.shared_session_id();
         let telegram_state = self.telegram_state.clone();
@@ -34,6 +34,11 @@ pub struct ToolExecutionContext {
 
     /// Service context — tools use this to create SessionService for /usage stats.
     pub service_context: Option<crate::services::ServiceContext>,
+
+    /// Live output sink — long-running tools send output lines here as they
+    /// are produced so the UI can show them before the tool finishes. Tools
+    /// that don't stream ignore it.
+    pub progress_sink: Option<tokio::sync::mpsc::Sender<String>>,
 }
 
 impl std::fmt::Debug for ToolExecutionContext {
@@ -44,6 +49,7 @@ impl std::fmt::Debug for ToolExecutionContext {
             .field("auto_approve", &self.auto_approve)
             .field("timeout_secs", &self.timeout_secs)
             .field("sudo_callback", &self.sudo_callback.is_some())
+            .field("progress_sink", &self.progress_sink.is_some())
             .finish()
     }
 }
@@ -60,6 +66,7 @@ impl ToolExecutionContext {
             sudo_callback: None,
             shared_working_directory: None,
             service_context: None,
+            progress_sink: None,
         }
     }
 
@@ -80,6 +87,20 @@ impl ToolExecutionContext {
         self.timeout_secs = timeout_secs;
         self
     }
+
+    /// Set the live output sink
+    pub fn with_progress_sink(mut self, sink: tokio::sync::mpsc::Sender<String>) -> Self {
+        self.progress_sink = Some(sink);
+        self
+    }
+
+    /// Send one line of live output to the progress sink, if any. Never
+    /// blocks — lines are dropped when the UI falls behind.
+    pub fn report_progress(&self, line: impl Into<String>) {
+        if let Some(sink) = &self.progress_sink {
+            let _ = sink.try_send(line.into());
+        }
+    }
 }
 
 /// Tool result
@@ -127,7 +148,7 @@ impl ToolResult {
 }
 
 /// Tool capability flags
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ToolCapability {
     /// Can read files
     ReadFiles,
@@ -143,6 +164,26 @@ pub enum ToolCapability {
     PlanManagement,
 }
 
+impl ToolCapability {
+    /// Parse a config name: snake_case (`read_files`) or the variant name (`ReadFiles`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| *c != '_' && *c != '-')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match normalized.as_str() {
+            "readfiles" => Some(Self::ReadFiles),
+            "writefiles" => Some(Self::WriteFiles),
+            "executeshell" => Some(Self::ExecuteShell),
+            "network" => Some(Self::Network),
+            "systemmodification" => Some(Self::SystemModification),
+            "planmanagement" => Some(Self::PlanManagement),
+            _ => None,
+        }
+    }
+}
+
 /// Tool trait - defines an executable tool
 #[async_trait]
 pub trait Tool: Send + Sync {
@@ -5,9 +5,9 @@
 
 use super::error::Result;
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
-use crate::channels::ChannelFactory;
 use crate::channels::trello::TrelloState;
 use crate::channels::trello::client::TrelloClient;
+use crate::channels::{ChannelFactory, MessageSource};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
@@ -180,7 +180,7 @@ impl Tool for TrelloConnectTool {
 
         // Spawn the TrelloAgent
         let factory = self.channel_factory.clone();
-        let agent_svc = factory.create_agent_service();
+        let agent_svc = factory.create_channel_agent_service(MessageSource::Trello);
         let service_ctx = factory.service_context();
         let shared_session = factory.shared_session_id();
         let trello_state = self.trello_state.clone();
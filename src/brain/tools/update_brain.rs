@@ -0,0 +1,245 @@
+//! Update Brain Tool
+//!
+//! Inserts an entry under a named markdown heading in one of the workspace
+//! brain files (`USER.md`, `AGENTS.md`, `MEMORY.md`, …), creating the heading
+//! when it is missing, then reindexes the file into the `brain` memory
+//! collection so the new fact is searchable straight away.
+
+use super::error::Result;
+use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::brain::prompt_builder::BRAIN_FILES;
+
+pub struct UpdateBrainTool;
+
+#[async_trait]
+impl Tool for UpdateBrainTool {
+    fn name(&self) -> &str {
+        "update_brain"
+    }
+
+    fn description(&self) -> &str {
+        "Add a durable entry to a brain file in ~/.opencrabs/ — e.g. a fact about the user \
+         to USER.md or a rule to AGENTS.md. The content is inserted at the end of the named \
+         markdown section, which is created if it doesn't exist yet. Use this for things \
+         worth remembering across sessions; use `write_opencrabs_file` for free-form edits."
+    }
+
+    fn input_schema(&self) -> Value {
+        let files: Vec<&str> = BRAIN_FILES.iter().map(|(n, _)| *n).collect();
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "file": {
+                    "type": "string",
+                    "enum": files,
+                    "description": "Brain file to update"
+                },
+                "section": {
+                    "type": "string",
+                    "description": "Markdown heading to insert under, e.g. \"Preferences\". Created as a ## heading if absent."
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Markdown to insert, e.g. \"- Prefers metric units\""
+                }
+            },
+            "required": ["file", "section", "content"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![
+            ToolCapability::WriteFiles,
+            ToolCapability::SystemModification,
+        ]
+    }
+
+    fn requires_approval(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, _ctx: &ToolExecutionContext) -> Result<ToolResult> {
+        let field = |key: &str| {
+            input
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        };
+        let (file, section, content) = (field("file"), field("section"), field("content"));
+        let section = section.trim_start_matches('#').trim();
+        if section.is_empty() || content.is_empty() {
+            return Ok(ToolResult::error(
+                "file, section and content are required".to_string(),
+            ));
+        }
+
+        // Only the known brain files — also rules out path traversal
+        let Some(canonical) = BRAIN_FILES
+            .iter()
+            .map(|(n, _)| *n)
+            .find(|n| n.eq_ignore_ascii_case(&file))
+        else {
+            let known: Vec<&str> = BRAIN_FILES.iter().map(|(n, _)| *n).collect();
+            return Ok(ToolResult::error(format!(
+                "Unknown brain file '{}'. Valid options: {}",
+                file,
+                known.join(", ")
+            )));
+        };
+
+        let path = crate::config::opencrabs_home().join(canonical);
+        let existing = std::fs::read_to_string(&path).unwrap_or_default();
+        let (updated, created) = insert_under_heading(&existing, section, &content);
+        if let Err(e) = std::fs::write(&path, &updated) {
+            return Ok(ToolResult::error(format!(
+                "Failed to write {}: {}",
+                canonical, e
+            )));
+        }
+
+        if let Ok(store) = crate::memory::get_store()
+            && let Err(e) = crate::memory::index_brain_file(store, &path).await
+        {
+            tracing::warn!("Failed to reindex {}: {}", canonical, e);
+        }
+
+        let action = if created { "new section" } else { "section" };
+        Ok(ToolResult::success(format!(
+            "Added to {} \"{}\" in {}",
+            action, section, canonical
+        )))
+    }
+}
+
+/// Heading level and text when `line` is an ATX markdown heading.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+        Some((level, rest.trim().trim_end_matches('#').trim()))
+    } else {
+        None
+    }
+}
+
+/// Insert `content` at the end of the section headed `section` (matched
+/// case-insensitively at any level), appending a new `##` section when there
+/// is none. Headings inside code fences are ignored. Returns the new document
+/// and whether the section was created.
+fn insert_under_heading(doc: &str, section: &str, content: &str) -> (String, bool) {
+    let lines: Vec<&str> = doc.lines().collect();
+    let mut in_fence = false;
+    let mut found: Option<usize> = None;
+    let mut end = lines.len();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((level, text)) = heading(line) else {
+            continue;
+        };
+        match found {
+            None if text.eq_ignore_ascii_case(section) => found = Some(level),
+            Some(open) if level <= open => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if found.is_none() {
+        let mut out = doc.trim_end().to_string();
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(&format!("## {}\n\n{}\n", section, content));
+        return (out, true);
+    }
+
+    // Drop trailing blank lines of the section so the entry sits right after
+    // its last line, then keep one blank line before the next heading
+    let mut insert_at = end;
+    while insert_at > 0 && lines[insert_at - 1].trim().is_empty() {
+        insert_at -= 1;
+    }
+    let mut out: Vec<&str> = lines[..insert_at].to_vec();
+    if lines[insert_at - 1].trim_start().starts_with('#') {
+        out.push("");
+    }
+    out.push(content);
+    if end < lines.len() {
+        out.push("");
+        out.extend_from_slice(&lines[end..]);
+    }
+    (out.join("\n") + "\n", false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# User\n\n## Preferences\n\n- Likes tea\n\n## Work\n\n- Rust\n";
+
+    #[test]
+    fn test_inserts_at_end_of_existing_section() {
+        let (out, created) = insert_under_heading(DOC, "preferences", "- Metric units");
+        assert!(!created);
+        assert_eq!(
+            out,
+            "# User\n\n## Preferences\n\n- Likes tea\n- Metric units\n\n## Work\n\n- Rust\n"
+        );
+
+        let (out, _) = insert_under_heading(DOC, "Work", "- Go");
+        assert!(out.ends_with("## Work\n\n- Rust\n- Go\n"));
+    }
+
+    #[test]
+    fn test_creates_missing_section() {
+        let (out, created) = insert_under_heading(DOC, "Family", "- Two cats");
+        assert!(created);
+        assert!(out.ends_with("- Rust\n\n## Family\n\n- Two cats\n"));
+
+        let (out, created) = insert_under_heading("", "Rules", "- Be brief");
+        assert!(created);
+        assert_eq!(out, "## Rules\n\n- Be brief\n");
+    }
+
+    #[test]
+    fn test_subsections_stay_inside_and_fences_are_ignored() {
+        let doc = "## Notes\n\n```\n## Fake\n```\n\n### Sub\n\n- a\n\n## Next\n";
+        let (out, created) = insert_under_heading(doc, "Notes", "- b");
+        assert!(!created);
+        assert_eq!(
+            out,
+            "## Notes\n\n```\n## Fake\n```\n\n### Sub\n\n- a\n- b\n\n## Next\n"
+        );
+        assert!(insert_under_heading(doc, "Fake", "- c").1);
+
+        let (out, _) = insert_under_heading("## Empty\n", "Empty", "- first");
+        assert_eq!(out, "## Empty\n\n- first\n");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_file() {
+        let ctx = ToolExecutionContext::new(uuid::Uuid::new_v4());
+        let result = UpdateBrainTool
+            .execute(
+                serde_json::json!({"file": "../config.toml", "section": "x", "content": "y"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown brain file"));
+    }
+}
@@ -7,8 +7,8 @@
 use super::error::Result;
 use super::r#trait::{Tool, ToolCapability, ToolExecutionContext, ToolResult};
 use crate::brain::agent::{ProgressCallback, ProgressEvent};
-use crate::channels::ChannelFactory;
 use crate::channels::whatsapp::handler;
+use crate::channels::{ChannelFactory, MessageSource};
 use crate::config::opencrabs_home;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -352,7 +352,7 @@ impl Tool for WhatsAppConnectTool {
         // 3. Prepare the FULL message handler state upfront so the bot handles
         //    messages immediately after pairing — no abort/respawn needed.
         let factory = self.channel_factory.clone();
-        let agent = factory.create_agent_service();
+        let agent = factory.create_channel_agent_service(MessageSource::WhatsApp);
         let session_svc = crate::services::SessionService::new(factory.service_context());
         let shared_session = factory.shared_session_id();
         let config_rx = factory.config_rx();
@@ -72,16 +72,13 @@ impl Tool for WhatsAppSendTool {
             }
         };
 
-        let client = match self.whatsapp_state.client().await {
-            Some(c) => c,
-            None => {
-                return Ok(ToolResult::error(
-                    "WhatsApp is not connected. Ask the user to connect WhatsApp first \
-                     (use the whatsapp_connect tool)."
-                        .to_string(),
-                ));
-            }
-        };
+        if !self.whatsapp_state.is_connected().await {
+            return Ok(ToolResult::error(
+                "WhatsApp is not connected. Ask the user to connect WhatsApp first \
+                 (use the whatsapp_connect tool)."
+                    .to_string(),
+            ));
+        }
 
         // Resolve target JID: explicit phone or owner
         let jid_str = if let Some(phone) = input.get("phone").and_then(|v| v.as_str()) {
@@ -114,34 +111,8 @@ impl Tool for WhatsAppSendTool {
             }
         };
 
-        let jid: wacore_binary::jid::Jid = match jid_str.parse() {
-            Ok(j) => j,
-            Err(e) => {
-                return Ok(ToolResult::error(format!(
-                    "Invalid phone number format: {}",
-                    e
-                )));
-            }
-        };
-
-        // Prepend agent header and split long messages
-        let tagged = format!(
-            "{}\n\n{}",
-            crate::channels::whatsapp::handler::MSG_HEADER,
-            message
-        );
-        let chunks = crate::channels::whatsapp::handler::split_message(&tagged, 4000);
-        for chunk in chunks {
-            let wa_msg = waproto::whatsapp::Message {
-                conversation: Some(chunk.to_string()),
-                ..Default::default()
-            };
-            if let Err(e) = client.send_message(jid.clone(), wa_msg).await {
-                return Ok(ToolResult::error(format!(
-                    "Failed to send WhatsApp message: {}",
-                    e
-                )));
-            }
+        if let Err(e) = self.whatsapp_state.send_text(&jid_str, &message).await {
+            return Ok(ToolResult::error(e));
         }
 
         Ok(ToolResult::success(format!(
@@ -6,6 +6,7 @@
 use uuid::Uuid;
 
 use crate::brain::agent::AgentService;
+use crate::config::Config;
 use crate::db::repository::SessionListOptions;
 use crate::services::SessionService;
 
@@ -82,6 +83,170 @@ pub async fn handle_command(
     }
 }
 
+// ── /allow, /deny, /allowlist ───────────────────────────────────────────────
+
+/// A channel whose allowlist the owner can edit from chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllowlistChannel {
+    Telegram,
+    Discord,
+    Slack,
+    WhatsApp,
+    Matrix,
+}
+
+impl AllowlistChannel {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Telegram => "telegram",
+            Self::Discord => "discord",
+            Self::Slack => "slack",
+            Self::WhatsApp => "whatsapp",
+            Self::Matrix => "matrix",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::WhatsApp => "allowed_phones",
+            _ => "allowed_users",
+        }
+    }
+
+    fn entries(self, config: &Config) -> &[String] {
+        let c = &config.channels;
+        match self {
+            Self::Telegram => &c.telegram.allowed_users,
+            Self::Discord => &c.discord.allowed_users,
+            Self::Slack => &c.slack.allowed_users,
+            Self::WhatsApp => &c.whatsapp.allowed_phones,
+            Self::Matrix => &c.matrix.allowed_users,
+        }
+    }
+
+    /// Canonical form of a user-supplied ID, or `None` if it can't be one.
+    fn normalize(self, id: &str) -> Option<String> {
+        let id = id.trim();
+        let valid = match self {
+            Self::Telegram | Self::Discord => id.parse::<u64>().is_ok(),
+            Self::WhatsApp => {
+                let digits = id.trim_start_matches('+');
+                !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+            }
+            Self::Slack => !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()),
+            // Full Matrix user ID: @localpart:server
+            Self::Matrix => {
+                id.starts_with('@') && id.contains(':') && !id.contains(char::is_whitespace)
+            }
+        };
+        valid.then(|| match self {
+            Self::WhatsApp => id.trim_start_matches('+').to_string(),
+            _ => id.to_string(),
+        })
+    }
+}
+
+/// Handle `/allow <id>`, `/deny <id>` and `/allowlist` from `sender` on
+/// `channel`. Returns the reply, or `None` when `text` is none of these.
+/// Only the channel's owner may use them; changes are written to config.toml
+/// and reach every handler through the config watcher.
+pub fn handle_allowlist_command(
+    text: &str,
+    channel: AllowlistChannel,
+    config: &Config,
+    sender: &str,
+) -> Option<String> {
+    let trimmed = text.trim();
+    let (cmd, arg) = trimmed
+        .split_once(char::is_whitespace)
+        .map(|(c, a)| (c, a.trim()))
+        .unwrap_or((trimmed, ""));
+    if !matches!(cmd, "/allow" | "/deny" | "/allowlist") {
+        return None;
+    }
+
+    let owner = config.channel_owner_id(channel.name());
+    if owner.is_none() || owner != channel.normalize(sender) {
+        return Some("⛔ Only the owner can manage the allowlist.".to_string());
+    }
+    let entries = channel.entries(config);
+    if cmd == "/allowlist" {
+        return Some(format_allowlist(entries));
+    }
+
+    let Some(id) = channel.normalize(arg) else {
+        return Some(format!("Usage: `{cmd} <id>`"));
+    };
+    let updated = match edit_allowlist(entries, cmd == "/allow", &id, owner.as_deref()) {
+        Ok(updated) => updated,
+        Err(reply) => return Some(reply),
+    };
+    let section = format!("channels.{}", channel.name());
+    if let Err(e) = Config::write_array(&section, channel.key(), &updated) {
+        tracing::error!("Failed to persist {} allowlist: {}", channel.name(), e);
+        return Some(format!("❌ Failed to save the allowlist: {e}"));
+    }
+    Some(if cmd == "/allow" {
+        format!("✅ `{id}` can now message me.")
+    } else {
+        format!("🚫 `{id}` removed from the allowlist.")
+    })
+}
+
+/// `entries` with `id` added (`allow`) or removed.
+///
+/// The owner always stays on the list: adding to an empty list (which lets
+/// everyone in) adds the owner too, so the first `/allow` can't lock them out.
+/// The last entry can't be removed, since an empty list opens the bot to anyone.
+fn edit_allowlist(
+    entries: &[String],
+    allow: bool,
+    id: &str,
+    owner: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let present = entries
+        .iter()
+        .any(|e| e.trim().trim_start_matches('+') == id);
+    let mut updated = entries.to_vec();
+    if allow {
+        if present {
+            return Err(format!("`{id}` is already allowed."));
+        }
+        if let Some(owner) = owner
+            && owner != id
+            && !updated
+                .iter()
+                .any(|e| e.trim().trim_start_matches('+') == owner)
+        {
+            updated.insert(0, owner.to_string());
+        }
+        updated.push(id.to_string());
+    } else {
+        if owner == Some(id) {
+            return Err("The owner can't be removed from the allowlist.".to_string());
+        }
+        if !present {
+            return Err(format!("`{id}` is not on the allowlist."));
+        }
+        updated.retain(|e| e.trim().trim_start_matches('+') != id);
+        if updated.is_empty() {
+            return Err(
+                "That's the last entry — an empty allowlist lets anyone message me.".to_string(),
+            );
+        }
+    }
+    Ok(updated)
+}
+
+fn format_allowlist(entries: &[String]) -> String {
+    if entries.is_empty() {
+        return "📋 *Allowlist* is empty — anyone can message me.".to_string();
+    }
+    let mut lines = vec!["📋 *Allowlist*".to_string(), String::new()];
+    lines.extend(entries.iter().map(|e| format!("• `{}`", e.trim())));
+    lines.join("\n")
+}
+
 // ── User-defined commands ───────────────────────────────────────────────────
 
 fn match_user_command(text: &str) -> ChannelCommand {
@@ -124,6 +289,9 @@ fn format_help() -> String {
         String::new(),
         "`/compact`  — Compact context (summarize & trim)".to_string(),
         "`/evolve`   — Download latest release & restart".to_string(),
+        "`/allow`    — Allow a user (owner only)".to_string(),
+        "`/allowlist` — Show allowed users (owner only)".to_string(),
+        "`/deny`     — Remove an allowed user (owner only)".to_string(),
         "`/help`     — Show this message".to_string(),
         "`/models`   — Switch AI model".to_string(),
         "`/new`      — Start a new session".to_string(),
@@ -145,8 +313,19 @@ fn format_help() -> String {
         }
     }
 
+    let agent = crate::config::Config::load()
+        .map(|c| c.agent)
+        .unwrap_or_default();
     lines.push(String::new());
-    lines.push("🦀 Any other message is sent to OpenCrabs. 🦀".to_string());
+    lines.push(
+        format!(
+            "{emoji} Any other message is sent to {name}. {emoji}",
+            emoji = agent.emoji,
+            name = agent.name
+        )
+        .trim()
+        .to_string(),
+    );
     lines.join("\n")
 }
 
@@ -263,7 +442,7 @@ async fn format_sessions(
         } else {
             ""
         };
-        let date = s.updated_at.format("%b %d %H:%M");
+        let date = crate::config::format_local(s.updated_at, "%b %d %H:%M");
         let label = format!("{} ({})", title, date);
         text_lines.push(format!("• `{}`{}", label, marker));
         items.push((s.id, label));
@@ -680,6 +859,87 @@ mod tests {
         ));
     }
 
+    // ── allowlist ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn allowlist_commands_are_owner_only() {
+        let mut config = Config::default();
+        config.channels.telegram.allowed_users = vec!["111".to_string(), "222".to_string()];
+        let tg = AllowlistChannel::Telegram;
+        assert!(handle_allowlist_command("hello", tg, &config, "111").is_none());
+        assert!(
+            handle_allowlist_command("/allowlist", tg, &config, "222")
+                .unwrap()
+                .contains("Only the owner")
+        );
+        assert!(
+            handle_allowlist_command("/allowlist", tg, &config, "111")
+                .unwrap()
+                .contains("`222`")
+        );
+
+        // An empty allowlist has no owner, so nobody may edit it
+        let open = Config::default();
+        assert!(
+            handle_allowlist_command("/allow 5", tg, &open, "5")
+                .unwrap()
+                .contains("Only the owner")
+        );
+    }
+
+    #[test]
+    fn edit_allowlist_adds_removes_and_protects_owner() {
+        let entries = vec!["111".to_string(), "+222".to_string()];
+        assert_eq!(
+            edit_allowlist(&entries, true, "333", Some("111")).unwrap(),
+            vec!["111", "+222", "333"]
+        );
+        assert_eq!(
+            edit_allowlist(&entries, false, "222", Some("111")).unwrap(),
+            vec!["111"]
+        );
+        assert!(edit_allowlist(&entries, true, "111", Some("111")).is_err());
+        assert!(edit_allowlist(&entries, false, "111", Some("111")).is_err());
+        assert!(edit_allowlist(&entries, false, "999", Some("111")).is_err());
+    }
+
+    #[test]
+    fn edit_allowlist_keeps_the_owner_and_never_empties() {
+        // An empty list lets everyone in; the first /allow must keep the owner
+        assert_eq!(
+            edit_allowlist(&[], true, "333", Some("111")).unwrap(),
+            vec!["111", "333"]
+        );
+        assert_eq!(
+            edit_allowlist(&["+111".to_string()], true, "333", Some("111")).unwrap(),
+            vec!["+111", "333"]
+        );
+        assert!(edit_allowlist(&["222".to_string()], false, "222", None).is_err());
+        assert!(edit_allowlist(&["222".to_string()], false, "222", Some("111")).is_err());
+    }
+
+    #[test]
+    fn allowlist_ids_are_validated() {
+        assert_eq!(AllowlistChannel::Telegram.normalize("abc"), None);
+        assert_eq!(
+            AllowlistChannel::WhatsApp
+                .normalize("+351912345678")
+                .as_deref(),
+            Some("351912345678")
+        );
+        assert_eq!(
+            AllowlistChannel::Slack.normalize("U0123ABCD").as_deref(),
+            Some("U0123ABCD")
+        );
+        assert_eq!(
+            AllowlistChannel::Matrix
+                .normalize(" @alice:matrix.org ")
+                .as_deref(),
+            Some("@alice:matrix.org")
+        );
+        assert_eq!(AllowlistChannel::Matrix.normalize("alice"), None);
+    }
+
     /// Helper to name variants for panic messages (ChannelCommand has no Debug).
     fn variant_name(cmd: &ChannelCommand) -> &'static str {
         match cmd {
@@ -67,6 +67,13 @@ impl DiscordAgent {
 
             let extra_sessions: Arc<Mutex<HashMap<u64, (Uuid, std::time::Instant)>>> =
                 Arc::new(Mutex::new(HashMap::new()));
+            let idle_sweep = crate::channels::spawn_idle_sweep(
+                "Discord",
+                extra_sessions.clone(),
+                self.session_service.clone(),
+                self.config_rx.clone(),
+                |c| c.channels.discord.session_idle_hours,
+            );
 
             let event_handler = Handler {
                 agent: self.agent_service,
@@ -89,6 +96,7 @@ impl DiscordAgent {
                 Ok(c) => c,
                 Err(e) => {
                     tracing::error!("Discord: failed to create client: {}", e);
+                    idle_sweep.abort();
                     return;
                 }
             };
@@ -96,6 +104,7 @@ impl DiscordAgent {
             if let Err(e) = client.start().await {
                 tracing::error!("Discord: client error: {}", e);
             }
+            idle_sweep.abort();
         })
     }
 }
@@ -247,14 +256,7 @@ impl EventHandler for Handler {
                 if let Ok(new_id) = session_id_str.parse::<Uuid>() {
                     let cfg = self.config_rx.borrow().clone();
                     let caller_id = comp.user.id.get();
-                    let owner_id = cfg
-                        .channels
-                        .discord
-                        .allowed_users
-                        .first()
-                        .and_then(|s| s.parse::<u64>().ok());
-                    let is_owner = cfg.channels.discord.allowed_users.is_empty()
-                        || owner_id == Some(caller_id);
+                    let is_owner = cfg.is_discord_owner(caller_id);
 
                     if is_owner {
                         *self.shared_session.lock().await = Some(new_id);
@@ -298,25 +300,28 @@ impl EventHandler for Handler {
                 return;
             }
 
-            let (approved, always, yolo, approval_id) =
-                if let Some(id) = custom_id.strip_prefix("approve:") {
-                    (true, false, false, id.to_string())
-                } else if let Some(id) = custom_id.strip_prefix("always:") {
-                    (true, true, false, id.to_string())
-                } else if let Some(id) = custom_id.strip_prefix("yolo:") {
-                    (true, true, true, id.to_string())
-                } else if let Some(id) = custom_id.strip_prefix("deny:") {
-                    (false, false, false, id.to_string())
-                } else {
-                    tracing::warn!("Discord: unknown interaction custom_id: {}", custom_id);
-                    let _ = comp
-                        .create_response(
-                            &ctx.http,
-                            serenity::builder::CreateInteractionResponse::Acknowledge,
-                        )
-                        .await;
-                    return;
-                };
+            use crate::brain::agent::ApprovalDecision;
+            let (decision, yolo, approval_id) = if let Some(id) = custom_id.strip_prefix("approve:")
+            {
+                (ApprovalDecision::Approve, false, id.to_string())
+            } else if let Some(id) = custom_id.strip_prefix("always:") {
+                (ApprovalDecision::ApproveAll, false, id.to_string())
+            } else if let Some(id) = custom_id.strip_prefix("yolo:") {
+                (ApprovalDecision::ApproveAll, true, id.to_string())
+            } else if let Some(id) = custom_id.strip_prefix("deny:") {
+                (ApprovalDecision::Deny, false, id.to_string())
+            } else if let Some(id) = custom_id.strip_prefix("stop:") {
+                (ApprovalDecision::DenyAndStop, false, id.to_string())
+            } else {
+                tracing::warn!("Discord: unknown interaction custom_id: {}", custom_id);
+                let _ = comp
+                    .create_response(
+                        &ctx.http,
+                        serenity::builder::CreateInteractionResponse::Acknowledge,
+                    )
+                    .await;
+                return;
+            };
 
             if yolo {
                 crate::utils::persist_auto_always_policy();
@@ -324,13 +329,12 @@ impl EventHandler for Handler {
 
             let resolved = self
                 .discord_state
-                .resolve_pending_approval(&approval_id, approved, always)
+                .resolve_pending_approval(&approval_id, decision)
                 .await;
             tracing::info!(
-                "Discord approval resolved: id={}, approved={}, always={}, found_pending={}",
+                "Discord approval resolved: id={}, decision={:?}, found_pending={}",
                 approval_id,
-                approved,
-                always,
+                decision,
                 resolved
             );
             if !resolved {
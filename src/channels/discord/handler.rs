@@ -5,6 +5,9 @@
 
 use super::DiscordState;
 use crate::brain::agent::AgentService;
+use crate::channels::MessageSource;
+use crate::channels::image_limits::ImageLimits;
+use crate::channels::voice::SttOptions;
 use crate::config::{Config, RespondTo};
 use crate::db::ChannelMessageRepository;
 use crate::db::models::ChannelMessage as DbChannelMessage;
@@ -20,30 +23,153 @@ use serenity::builder::{CreateAttachment, CreateMessage};
 use serenity::model::channel::Message;
 use serenity::prelude::*;
 
+/// Code blocks at least this many characters long count as large for
+/// `[channels.discord] large_code_as_file`.
+const DEFAULT_LARGE_CODE_THRESHOLD: usize = 1500;
+
 /// Split a message into chunks that fit Discord's 2000 char limit.
-pub fn split_message(text: &str, max_len: usize) -> Vec<&str> {
+/// A code fence cut by a split is closed at the end of its chunk and
+/// reopened, language tag included, at the start of the next, so every
+/// chunk renders on its own.
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
     if text.len() <= max_len {
-        return vec![text];
+        return vec![text.to_string()];
     }
     let mut chunks = Vec::new();
+    let mut open_fence: Option<String> = None;
     let mut start = 0;
     while start < text.len() {
-        let end = (start + max_len).min(text.len());
+        let prefix = open_fence
+            .as_ref()
+            .map(|fence| format!("{fence}\n"))
+            .unwrap_or_default();
+        // Leave room for the reopened fence and a closing "\n```"
+        let budget = max_len.saturating_sub(prefix.len() + 4).max(1);
+        let mut end = (start + budget).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == start {
+            end = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        }
         let break_at = if end < text.len() {
             text[start..end]
                 .rfind('\n')
-                .filter(|&pos| pos > end - start - 200)
+                .filter(|&pos| pos > (end - start).saturating_sub(200))
                 .map(|pos| start + pos + 1)
                 .unwrap_or(end)
         } else {
             end
         };
-        chunks.push(&text[start..break_at]);
+
+        let body = &text[start..break_at];
+        let still_open = fence_open_after(open_fence.take(), body);
+        let mut chunk = prefix + body;
+        if still_open.is_some() && break_at < text.len() {
+            if !chunk.ends_with('\n') {
+                chunk.push('\n');
+            }
+            chunk.push_str("```");
+        }
+        chunks.push(chunk);
+        open_fence = still_open;
         start = break_at;
     }
     chunks
 }
 
+/// The opening line of the code fence still open after `body`, given the
+/// fence open before it.
+fn fence_open_after(mut open: Option<String>, body: &str) -> Option<String> {
+    for line in body.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            open = match open {
+                Some(_) => None,
+                None => Some(line.to_string()),
+            };
+        }
+    }
+    open
+}
+
+/// A reply's dominant code block, pulled out to send as a file.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CodeFile {
+    /// The reply with the block replaced by a pointer to the attachment
+    pub text: String,
+    pub filename: String,
+    pub code: String,
+}
+
+/// Pull out the largest fenced code block when it is at least `threshold`
+/// characters and makes up most of the reply. Returns `None` otherwise.
+pub(crate) fn extract_code_file(text: &str, threshold: usize) -> Option<CodeFile> {
+    // (fence start, body start, body end, block end, language)
+    let mut blocks: Vec<(usize, usize, usize, usize, String)> = Vec::new();
+    let mut open: Option<(usize, usize, String)> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(tag) = trimmed.strip_prefix("```") {
+            match open.take() {
+                Some((fence_start, body_start, lang)) => {
+                    blocks.push((fence_start, body_start, offset, offset + line.len(), lang));
+                }
+                None => open = Some((offset, offset + line.len(), tag.trim().to_lowercase())),
+            }
+        }
+        offset += line.len();
+    }
+
+    let (fence_start, body_start, body_end, block_end, lang) = blocks
+        .into_iter()
+        .max_by_key(|(_, body_start, body_end, _, _)| body_end - body_start)?;
+    let code = &text[body_start..body_end];
+    if code.chars().count() < threshold || code.len() * 2 < text.len() {
+        return None;
+    }
+
+    let filename = format!("code.{}", code_extension(&lang));
+    let before = text[..fence_start].trim_end();
+    let after = text[block_end..].trim_start();
+    let pointer = format!("📎 `{filename}`");
+    let text = [before, pointer.as_str(), after]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(CodeFile {
+        text,
+        filename,
+        code: code.to_string(),
+    })
+}
+
+/// File extension for a code fence language tag.
+fn code_extension(lang: &str) -> &'static str {
+    match lang {
+        "rust" | "rs" => "rs",
+        "python" | "py" => "py",
+        "javascript" | "js" | "jsx" => "js",
+        "typescript" | "ts" | "tsx" => "ts",
+        "go" | "golang" => "go",
+        "c" | "h" => "c",
+        "cpp" | "c++" | "cc" | "hpp" => "cpp",
+        "java" => "java",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "sh" | "bash" | "shell" | "zsh" => "sh",
+        "html" => "html",
+        "css" => "css",
+        "sql" => "sql",
+        "markdown" | "md" => "md",
+        "diff" | "patch" => "diff",
+        _ => "txt",
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn handle_message(
     ctx: &Context,
@@ -58,6 +184,20 @@ pub(crate) async fn handle_message(
 ) {
     // Read latest config from watch channel — single source of truth
     let cfg = config_rx.borrow().clone();
+
+    // Paused via /pause discord — tell the owner once, ignore everyone else
+    if crate::channels::is_paused(MessageSource::Discord) {
+        if cfg.is_discord_owner(msg.author.id.get())
+            && crate::channels::take_pause_notice(MessageSource::Discord)
+        {
+            let _ = msg
+                .channel_id
+                .say(&ctx.http, crate::channels::PAUSED_NOTICE)
+                .await;
+        }
+        return;
+    }
+
     let dc_cfg = &cfg.channels.discord;
     let allowed: HashSet<i64> = dc_cfg
         .allowed_users
@@ -66,7 +206,14 @@ pub(crate) async fn handle_message(
         .collect();
     let respond_to = &dc_cfg.respond_to;
     let allowed_channels: HashSet<String> = dc_cfg.allowed_channels.iter().cloned().collect();
-    let idle_timeout_hours = dc_cfg.session_idle_hours;
+    let idle_timeout = cfg.sessions.channel_idle_timeout(dc_cfg.session_idle_hours);
+    let archive_idle = cfg.sessions.archive_idle;
+    let reply_header = dc_cfg.reply_header.clone().unwrap_or_default();
+    let code_file_threshold = dc_cfg.large_code_as_file.then(|| {
+        dc_cfg
+            .large_code_threshold
+            .unwrap_or(DEFAULT_LARGE_CODE_THRESHOLD)
+    });
     let mut voice_config = cfg.voice.clone();
     voice_config.stt_provider = cfg.providers.stt.as_ref().and_then(|s| s.groq.clone());
     let tts_providers = cfg.providers.tts.as_ref();
@@ -77,12 +224,6 @@ pub(crate) async fn handle_message(
     if let Some(ref m) = tts_providers.and_then(|t| t.model.as_ref()) {
         voice_config.tts_model = m.to_string();
     }
-    let openai_tts_key = cfg
-        .providers
-        .tts
-        .as_ref()
-        .and_then(|t| t.openai.as_ref())
-        .and_then(|p| p.api_key.clone());
 
     let user_id = msg.author.id.get() as i64;
 
@@ -178,12 +319,17 @@ pub(crate) async fn handle_message(
 
     if let Some(audio) = audio_attachment
         && voice_config.stt_enabled
-        && let Some(ref stt_provider) = voice_config.stt_provider
-        && let Some(ref stt_key) = stt_provider.api_key
+        && let Some(stt) = crate::channels::voice::build_stt(&voice_config)
         && let Ok(resp) = reqwest::get(&audio.url).await
         && let Ok(bytes) = resp.bytes().await
     {
-        match crate::channels::voice::transcribe_audio(bytes.to_vec(), stt_key).await {
+        match stt
+            .transcribe(
+                bytes.to_vec(),
+                &SttOptions::for_user(&voice_config, &user_id.to_string()),
+            )
+            .await
+        {
             Ok(transcript) => {
                 tracing::info!(
                     "Discord: transcribed voice: {}",
@@ -211,11 +357,16 @@ pub(crate) async fn handle_message(
     // Handle attachments — images as <<IMG:url>>, text files extracted inline
     if !is_voice {
         use crate::utils::{FileContent, classify_file};
+        let mut images = ImageLimits::from_config(&cfg.channels).budget("Discord");
         for attachment in &msg.attachments {
             let mime = attachment.content_type.as_deref().unwrap_or("");
             let fname = &attachment.filename;
+            let size = Some(attachment.size as u64);
 
             if mime.starts_with("image/") {
+                if !images.admit(fname, size) {
+                    continue;
+                }
                 if content.is_empty() {
                     content = "Describe this image.".to_string();
                 }
@@ -231,6 +382,9 @@ pub(crate) async fn handle_message(
                         }
                         FileContent::Image => {
                             // Rare: image MIME not caught above — use URL
+                            if !images.admit(fname, size) {
+                                continue;
+                            }
                             if content.is_empty() {
                                 content = "Describe this image.".to_string();
                             }
@@ -243,6 +397,9 @@ pub(crate) async fn handle_message(
                 }
             }
         }
+        if let Some(note) = images.note() {
+            content.push_str(&format!("\n\n{note}"));
+        }
     }
 
     if content.is_empty() {
@@ -258,12 +415,7 @@ pub(crate) async fn handle_message(
     );
 
     // Track owner's channel for proactive messaging
-    let is_owner = allowed.is_empty()
-        || allowed
-            .iter()
-            .next()
-            .map(|&a| a == user_id)
-            .unwrap_or(false);
+    let is_owner = cfg.is_discord_owner(msg.author.id.get());
 
     if is_owner {
         discord_state.set_owner_channel(msg.channel_id.get()).await;
@@ -310,10 +462,10 @@ pub(crate) async fn handle_message(
         let mut map = extra_sessions.lock().await;
         let disc_user_id = msg.author.id.get();
         if let Some((old_id, last_activity)) = map.get(&disc_user_id).copied() {
-            if idle_timeout_hours
-                .is_some_and(|h| last_activity.elapsed().as_secs() > (h * 3600.0) as u64)
-            {
-                let _ = session_svc.archive_session(old_id).await;
+            if idle_timeout.is_some_and(|t| last_activity.elapsed() > t) {
+                if archive_idle {
+                    let _ = session_svc.archive_session(old_id).await;
+                }
                 map.remove(&disc_user_id);
                 let title = format!("Discord: {}", msg.author.name);
                 match session_svc.create_session(Some(title)).await {
@@ -347,7 +499,16 @@ pub(crate) async fn handle_message(
 
     // ── Channel commands (/help, /usage, /models) ──────────────────────────
     {
-        use crate::channels::commands::{self, ChannelCommand};
+        use crate::channels::commands::{self, AllowlistChannel, ChannelCommand};
+        if let Some(reply) = commands::handle_allowlist_command(
+            &content,
+            AllowlistChannel::Discord,
+            &cfg,
+            &msg.author.id.get().to_string(),
+        ) {
+            let _ = msg.channel_id.say(&ctx.http, &reply).await;
+            return;
+        }
         match commands::handle_command(&content, session_id, &agent, &session_svc).await {
             ChannelCommand::Help(body) | ChannelCommand::Usage(body) => {
                 let _ = msg.channel_id.say(&ctx.http, &body).await;
@@ -530,7 +691,7 @@ pub(crate) async fn handle_message(
                     .iter()
                     .rev()
                     .map(|m| {
-                        let ts = m.created_at.format("%H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%H:%M");
                         format!("[{}] {}: {}", ts, m.sender_name, m.content)
                     })
                     .collect();
@@ -559,7 +720,10 @@ pub(crate) async fn handle_message(
     discord_state
         .register_session_channel(session_id, msg.channel_id.get())
         .await;
-    let approval_cb = make_approval_callback(discord_state.clone());
+    // Queue behind other channel messages at [channels] max_concurrent
+    let permit =
+        crate::channels::acquire_agent_permit("Discord", cfg.channels.max_concurrent).await;
+    let approval_cb = make_approval_callback(discord_state.clone(), config_rx.clone(), permit);
 
     let cancel_token = tokio_util::sync::CancellationToken::new();
     discord_state
@@ -608,25 +772,34 @@ pub(crate) async fn handle_message(
                 }
             }
 
+            // A reply that is mostly one big code block goes out as a file
+            let code_file =
+                code_file_threshold.and_then(|threshold| extract_code_file(&text_only, threshold));
+            let text_only = code_file.as_ref().map_or(text_only, |f| f.text.clone());
+
+            let text_only = crate::channels::with_reply_header(&reply_header, &text_only);
             for chunk in split_message(&text_only, 2000) {
                 if let Err(e) = msg.channel_id.say(&ctx.http, chunk).await {
                     tracing::error!("Discord: failed to send reply: {}", e);
                 }
             }
+            if let Some(code_file) = code_file {
+                let file = CreateAttachment::bytes(code_file.code.as_bytes(), code_file.filename);
+                if let Err(e) = msg
+                    .channel_id
+                    .send_message(&ctx.http, CreateMessage::new().add_file(file))
+                    .await
+                {
+                    tracing::error!("Discord: failed to send code attachment: {}", e);
+                }
+            }
 
             // TTS: send voice reply if input was audio and TTS is enabled
             if is_voice
                 && voice_config.tts_enabled
-                && let Some(ref oai_key) = openai_tts_key
+                && let Some(tts) = crate::channels::voice::build_tts(&voice_config)
             {
-                match crate::channels::voice::synthesize_speech(
-                    &response.content,
-                    oai_key,
-                    &voice_config.tts_voice,
-                    &voice_config.tts_model,
-                )
-                .await
-                {
+                match tts.synthesize(&response.content).await {
                     Ok(audio_bytes) => {
                         let file = CreateAttachment::bytes(audio_bytes.as_slice(), "response.ogg");
                         if let Err(e) = msg
@@ -643,18 +816,21 @@ pub(crate) async fn handle_message(
         }
         Err(e) => {
             tracing::error!("Discord: agent error: {}", e);
-            let error_msg = format!("Error: {}", e);
+            let error_msg = format!("Error: {}", e.user_message());
             let _ = msg.channel_id.say(&ctx.http, error_msg).await;
         }
     }
 }
 
-/// Build an `ApprovalCallback` that sends a Discord message with 3 buttons
-/// (Yes / Always / No) and waits up to 5 min for a click.
+/// Build an `ApprovalCallback` that sends a Discord message with buttons
+/// (Yes / Always / YOLO / No / Stop) and waits up to
+/// `[tools] approval_timeout_secs` for a click, giving up `permit` meanwhile.
 pub(crate) fn make_approval_callback(
     state: Arc<super::DiscordState>,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    permit: crate::channels::AgentPermit,
 ) -> crate::brain::agent::ApprovalCallback {
-    use crate::brain::agent::ToolApprovalInfo;
+    use crate::brain::agent::{ApprovalDecision, ToolApprovalInfo};
     use crate::utils::{check_approval_policy, persist_auto_session_policy};
     use serenity::builder::{CreateActionRow, CreateButton, CreateMessage, EditMessage};
     use serenity::model::application::ButtonStyle;
@@ -663,16 +839,20 @@ pub(crate) fn make_approval_callback(
 
     Arc::new(move |info: ToolApprovalInfo| {
         let state = state.clone();
+        let config_rx = config_rx.clone();
+        let permit = permit.clone();
         Box::pin(async move {
-            if let Some(result) = check_approval_policy() {
-                return Ok(result);
+            if !info.forced
+                && let Some(decision) = check_approval_policy()
+            {
+                return Ok(decision);
             }
 
             let http = match state.http().await {
                 Some(h) => h,
                 None => {
                     tracing::warn!("Discord approval: bot not connected");
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -685,7 +865,7 @@ pub(crate) fn make_approval_callback(
                             "Discord approval: no channel_id for session {}",
                             info.session_id
                         );
-                        return Ok((false, false));
+                        return Ok(ApprovalDecision::Deny);
                     }
                 },
             };
@@ -713,6 +893,9 @@ pub(crate) fn make_approval_callback(
                 CreateButton::new(format!("deny:{}", approval_id))
                     .label("❌ No")
                     .style(ButtonStyle::Danger),
+                CreateButton::new(format!("stop:{}", approval_id))
+                    .label("🛑 No, stop")
+                    .style(ButtonStyle::Danger),
             ]);
 
             // Register BEFORE sending to prevent race condition
@@ -736,7 +919,7 @@ pub(crate) fn make_approval_callback(
                 Ok(m) => m,
                 Err(e) => {
                     tracing::error!("Discord approval: failed to send message: {}", e);
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -745,39 +928,40 @@ pub(crate) fn make_approval_callback(
                 approval_id
             );
 
-            match tokio::time::timeout(std::time::Duration::from_secs(300), rx).await {
-                Ok(Ok((approved, always))) => {
+            match permit
+                .released_while(crate::utils::await_approval(rx, &config_rx))
+                .await
+            {
+                Some(Ok(decision)) => {
                     tracing::info!(
-                        "Discord approval: user responded id={}, approved={}, always={}",
+                        "Discord approval: user responded id={}, decision={:?}",
                         approval_id,
-                        approved,
-                        always
+                        decision
                     );
-                    if always {
+                    if decision == ApprovalDecision::ApproveAll {
                         persist_auto_session_policy();
                     }
-                    let label = if always {
-                        "🔁 Always approved (session)"
-                    } else if approved {
-                        "✅ Approved"
-                    } else {
-                        "❌ Denied"
+                    let label = match decision {
+                        ApprovalDecision::DenyAndStop => "🛑 Denied — turn stopped",
+                        ApprovalDecision::ApproveAll => "🔁 Always approved (session)",
+                        ApprovalDecision::Approve => "✅ Approved",
+                        ApprovalDecision::Deny => "❌ Denied",
                     };
                     let _ = sent_msg
                         .edit(&http, EditMessage::new().content(label).components(vec![]))
                         .await;
-                    Ok((approved, always))
+                    Ok(decision)
                 }
-                Ok(Err(_)) => {
+                Some(Err(_)) => {
                     tracing::warn!(
                         "Discord approval: oneshot channel closed (id={})",
                         approval_id
                     );
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
-                Err(_) => {
+                None => {
                     tracing::warn!(
-                        "Discord approval: 5-minute timeout — auto-denying (id={})",
+                        "Discord approval: timed out — auto-denying (id={})",
                         approval_id
                     );
                     let _ = sent_msg
@@ -788,7 +972,7 @@ pub(crate) fn make_approval_callback(
                                 .components(vec![]),
                         )
                         .await;
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
             }
         })
@@ -816,4 +1000,45 @@ mod tests {
         let joined: String = chunks.into_iter().collect();
         assert_eq!(joined, text);
     }
+
+    #[test]
+    fn test_split_reopens_cut_code_fence() {
+        let code: String = (0..300).map(|i| format!("let x{i} = {i};\n")).collect();
+        let text = format!("Here you go:\n```rust\n{code}```\nDone.");
+        let chunks = split_message(&text, 2000);
+        assert!(chunks.len() >= 2);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 2000, "chunk is {} bytes", chunk.len());
+            // Every chunk has balanced fences
+            assert_eq!(chunk.matches("```").count() % 2, 0, "{chunk}");
+        }
+        assert!(chunks[1].starts_with("```rust\n"));
+        assert!(chunks.last().unwrap().ends_with("Done."));
+    }
+
+    #[test]
+    fn test_split_never_cuts_inside_a_char() {
+        let text = "é".repeat(1500);
+        let chunks = split_message(&text, 2000);
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[test]
+    fn test_extract_code_file() {
+        let code = "fn main() {}\n".repeat(200);
+        let text = format!("Here's the file:\n\n```rust\n{code}```\n\nRun it with cargo.");
+        let file = extract_code_file(&text, 1500).unwrap();
+        assert_eq!(file.filename, "code.rs");
+        assert_eq!(file.code, code);
+        assert_eq!(
+            file.text,
+            "Here's the file:\n\n📎 `code.rs`\n\nRun it with cargo."
+        );
+
+        // Below the threshold, or not the bulk of the reply: stays inline
+        assert_eq!(extract_code_file(&text, 5000), None);
+        let chatty = format!("{}\n```\n{code}```", "words ".repeat(1000));
+        assert_eq!(extract_code_file(&chatty, 1500), None);
+        assert_eq!(extract_code_file("no code here", 10), None);
+    }
 }
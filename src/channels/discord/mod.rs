@@ -8,6 +8,7 @@ pub(crate) mod handler;
 
 pub use agent::DiscordAgent;
 
+use crate::brain::agent::ApprovalDecision;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{Mutex, oneshot};
@@ -29,7 +30,7 @@ pub struct DiscordState {
     /// Maps session_id → channel_id for approval routing
     session_channels: Mutex<HashMap<Uuid, u64>>,
     /// Pending approval channels: approval_id → oneshot sender of (approved, always)
-    pending_approvals: Mutex<HashMap<String, oneshot::Sender<(bool, bool)>>>,
+    pending_approvals: Mutex<HashMap<String, oneshot::Sender<ApprovalDecision>>>,
     /// Per-session cancel tokens for aborting in-flight agent tasks via /stop
     cancel_tokens: Mutex<HashMap<Uuid, CancellationToken>>,
 }
@@ -115,14 +116,18 @@ impl DiscordState {
     }
 
     /// Register a pending approval oneshot channel.
-    pub async fn register_pending_approval(&self, id: String, tx: oneshot::Sender<(bool, bool)>) {
+    pub async fn register_pending_approval(
+        &self,
+        id: String,
+        tx: oneshot::Sender<ApprovalDecision>,
+    ) {
         self.pending_approvals.lock().await.insert(id, tx);
     }
 
     /// Resolve a pending approval. Returns true if one existed.
-    pub async fn resolve_pending_approval(&self, id: &str, approved: bool, always: bool) -> bool {
+    pub async fn resolve_pending_approval(&self, id: &str, decision: ApprovalDecision) -> bool {
         if let Some(tx) = self.pending_approvals.lock().await.remove(id) {
-            let _ = tx.send((approved, always));
+            let _ = tx.send(decision);
             true
         } else {
             false
@@ -6,6 +6,7 @@
 use crate::brain::agent::AgentService;
 use crate::brain::provider::Provider;
 use crate::brain::tools::ToolRegistry;
+use crate::channels::MessageSource;
 use crate::config::{Config, VoiceConfig};
 use crate::services::ServiceContext;
 use std::path::PathBuf;
@@ -73,10 +74,30 @@ impl ChannelFactory {
     /// set, otherwise the override is ignored. A2A and headless tools that have no interactive
     /// user can set their own auto-approval via session context.
     pub fn create_agent_service(&self) -> Arc<AgentService> {
+        Arc::new(self.build_agent_service())
+    }
+
+    /// Like [`create_agent_service`], plus the `[channels.<name>] system_append`
+    /// for `source` (read from the config watch each turn, so edits apply live)
+    /// and the `[sessions] compact_after_idle_minutes` policy.
+    pub fn create_channel_agent_service(&self, source: MessageSource) -> Arc<AgentService> {
+        let config_rx = self.config_rx.clone();
+        let idle_compact = config_rx.borrow().sessions.idle_compact_after();
+        Arc::new(
+            self.build_agent_service()
+                .with_idle_compact_after(idle_compact)
+                .with_system_append(Arc::new(move || {
+                    source.system_append(&config_rx.borrow().channels)
+                })),
+        )
+    }
+
+    fn build_agent_service(&self) -> AgentService {
         let mut builder = AgentService::new(self.provider.clone(), self.service_context.clone())
             .with_system_brain(self.shared_brain.clone())
             .with_working_directory(self.working_directory.clone())
-            .with_brain_path(self.brain_path.clone());
+            .with_brain_path(self.brain_path.clone())
+            .with_live_config(self.config_rx.clone());
 
         if let Some(registry) = self.tool_registry.get() {
             builder = builder.with_tool_registry(registry.clone());
@@ -86,7 +107,7 @@ impl ChannelFactory {
             builder = builder.with_session_updated_tx(tx.clone());
         }
 
-        Arc::new(builder)
+        builder
     }
 
     pub fn shared_session_id(&self) -> Arc<Mutex<Option<Uuid>>> {
@@ -0,0 +1,146 @@
+//! Image Limits
+//!
+//! Caps on incoming images from `[channels] max_images_per_message` and
+//! `max_image_bytes`. Checked before anything is downloaded, so a pile of
+//! screenshots or one huge photo never reaches the vision model.
+
+use crate::config::ChannelsConfig;
+
+/// Per-message image caps. Zero means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageLimits {
+    pub max_count: usize,
+    pub max_bytes: u64,
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self::from_config(&ChannelsConfig::default())
+    }
+}
+
+impl ImageLimits {
+    pub fn from_config(cfg: &ChannelsConfig) -> Self {
+        Self {
+            max_count: cfg.max_images_per_message,
+            max_bytes: cfg.max_image_bytes,
+        }
+    }
+
+    /// Start admitting images for one message.
+    pub fn budget(self, channel: &'static str) -> ImageBudget {
+        ImageBudget {
+            limits: self,
+            channel,
+            accepted: 0,
+            dropped: 0,
+            rejected: Vec::new(),
+        }
+    }
+}
+
+/// Tracks the images admitted for one message and what was turned away.
+#[derive(Debug)]
+pub struct ImageBudget {
+    limits: ImageLimits,
+    channel: &'static str,
+    accepted: usize,
+    dropped: usize,
+    rejected: Vec<String>,
+}
+
+impl ImageBudget {
+    /// Whether the image `name` of `size` bytes (if known) may be attached.
+    /// Rejections are logged and remembered for [`note`](Self::note).
+    pub fn admit(&mut self, name: &str, size: Option<u64>) -> bool {
+        if let Some(size) = size
+            && self.limits.max_bytes > 0
+            && size > self.limits.max_bytes
+        {
+            tracing::warn!(
+                "{}: rejected image {} ({} bytes > {} byte limit)",
+                self.channel,
+                name,
+                size,
+                self.limits.max_bytes
+            );
+            self.rejected
+                .push(format!("{} ({})", name, format_size(size)));
+            return false;
+        }
+        if self.limits.max_count > 0 && self.accepted >= self.limits.max_count {
+            tracing::warn!(
+                "{}: dropped image {} (limit {} per message)",
+                self.channel,
+                name,
+                self.limits.max_count
+            );
+            self.dropped += 1;
+            return false;
+        }
+        self.accepted += 1;
+        true
+    }
+
+    /// A note for the agent about skipped images, if any were.
+    pub fn note(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.dropped > 0 {
+            parts.push(format!(
+                "{} image(s) dropped — only {} are accepted per message",
+                self.dropped, self.limits.max_count
+            ));
+        }
+        if !self.rejected.is_empty() {
+            parts.push(format!(
+                "rejected as larger than {}: {}",
+                format_size(self.limits.max_bytes),
+                self.rejected.join(", ")
+            ));
+        }
+        (!parts.is_empty()).then(|| format!("[Attachments skipped: {}]", parts.join("; ")))
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_and_size_limits() {
+        let limits = ImageLimits {
+            max_count: 2,
+            max_bytes: 1000,
+        };
+        let mut budget = limits.budget("Test");
+        assert!(budget.admit("a.png", Some(10)));
+        assert!(!budget.admit("huge.png", Some(5000)));
+        assert!(budget.admit("b.png", None));
+        assert!(!budget.admit("c.png", Some(10)));
+
+        let note = budget.note().unwrap();
+        assert!(note.contains("1 image(s) dropped"));
+        assert!(note.contains("huge.png"));
+    }
+
+    #[test]
+    fn test_zero_means_unlimited() {
+        let mut budget = ImageLimits {
+            max_count: 0,
+            max_bytes: 0,
+        }
+        .budget("Test");
+        for _ in 0..10 {
+            assert!(budget.admit("a.png", Some(u64::MAX)));
+        }
+        assert_eq!(budget.note(), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Matrix Agent
+//!
+//! Agent struct and startup logic. Restores the bot account's session from
+//! its access token, then long-polls `/sync` for room events.
+
+use super::MatrixState;
+use super::handler;
+use crate::brain::agent::AgentService;
+use crate::config::Config;
+use crate::db::ChannelMessageRepository;
+use crate::services::{ServiceContext, SessionService};
+use matrix_sdk::authentication::matrix::{MatrixSession, MatrixSessionTokens};
+use matrix_sdk::config::SyncSettings;
+use matrix_sdk::ruma::{OwnedDeviceId, OwnedUserId};
+use matrix_sdk::{Client, SessionMeta};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Matrix bot that forwards room messages to the AgentService
+pub struct MatrixAgent {
+    agent_service: Arc<AgentService>,
+    session_service: SessionService,
+    shared_session_id: Arc<Mutex<Option<Uuid>>>,
+    matrix_state: Arc<MatrixState>,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    channel_msg_repo: ChannelMessageRepository,
+}
+
+impl MatrixAgent {
+    pub fn new(
+        agent_service: Arc<AgentService>,
+        service_context: ServiceContext,
+        shared_session_id: Arc<Mutex<Option<Uuid>>>,
+        matrix_state: Arc<MatrixState>,
+        config_rx: tokio::sync::watch::Receiver<Config>,
+        channel_msg_repo: ChannelMessageRepository,
+    ) -> Self {
+        Self {
+            agent_service,
+            session_service: SessionService::new(service_context),
+            shared_session_id,
+            matrix_state,
+            config_rx,
+            channel_msg_repo,
+        }
+    }
+
+    /// Start the bot as a background task. Returns a JoinHandle.
+    pub fn start(
+        self,
+        homeserver: String,
+        user_id: String,
+        access_token: String,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let user_id = match OwnedUserId::try_from(user_id.as_str()) {
+                Ok(id) => id,
+                Err(e) => {
+                    tracing::error!("Matrix: invalid user_id '{}': {}", user_id, e);
+                    return;
+                }
+            };
+
+            let cfg = self.config_rx.borrow().clone();
+            let device_id = match cfg.channels.matrix.device_id.clone() {
+                Some(id) if !id.is_empty() => id,
+                _ => match whoami_device(&homeserver, &access_token).await {
+                    Ok(id) => id,
+                    Err(e) => {
+                        tracing::error!("Matrix: couldn't look up the device ID: {}", e);
+                        return;
+                    }
+                },
+            };
+
+            // Crypto keys and sync state persist here so encrypted rooms
+            // survive restarts
+            let store_path = crate::config::opencrabs_home().join("matrix");
+            let _ = std::fs::create_dir_all(&store_path);
+
+            let client = match Client::builder()
+                .homeserver_url(&homeserver)
+                .sqlite_store(&store_path, None)
+                .build()
+                .await
+            {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("Matrix: failed to build client: {}", e);
+                    return;
+                }
+            };
+
+            let session = MatrixSession {
+                meta: SessionMeta {
+                    user_id: user_id.clone(),
+                    device_id: OwnedDeviceId::from(device_id.as_str()),
+                },
+                tokens: MatrixSessionTokens {
+                    access_token,
+                    refresh_token: None,
+                },
+            };
+            if let Err(e) = client.restore_session(session).await {
+                tracing::error!("Matrix: failed to restore session: {}", e);
+                return;
+            }
+
+            tracing::info!(
+                "Starting Matrix bot as {} with {} allowed user(s)",
+                user_id,
+                cfg.channels.matrix.allowed_users.len(),
+            );
+            if cfg.channels.matrix.allowed_users.is_empty() && cfg.owner.matrix_id.is_none() {
+                tracing::warn!(
+                    "Matrix: allowed_users is empty and [owner] matrix_id is unset — \
+                     invites and messages are ignored until one is configured"
+                );
+            }
+
+            // Initial sync without handlers so the backlog isn't answered
+            let response = match client.sync_once(SyncSettings::default()).await {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::error!("Matrix: initial sync failed: {}", e);
+                    return;
+                }
+            };
+            self.matrix_state.set_connected(client.clone()).await;
+
+            let extra_sessions: crate::channels::ChannelSessions<String> =
+                Arc::new(Mutex::new(std::collections::HashMap::new()));
+            let idle_sweep = crate::channels::spawn_idle_sweep(
+                "Matrix",
+                extra_sessions.clone(),
+                self.session_service.clone(),
+                self.config_rx.clone(),
+                |c| c.channels.matrix.session_idle_hours,
+            );
+
+            client.add_event_handler_context(Arc::new(handler::HandlerState {
+                agent: self.agent_service,
+                session_svc: self.session_service,
+                extra_sessions,
+                shared_session: self.shared_session_id,
+                matrix_state: self.matrix_state,
+                bot_user_id: user_id,
+                config_rx: self.config_rx,
+                channel_msg_repo: self.channel_msg_repo,
+            }));
+            client.add_event_handler(handler::on_room_message);
+            client.add_event_handler(handler::on_invite);
+
+            tracing::info!("Matrix: connected, listening for messages");
+            let settings = SyncSettings::default().token(response.next_batch);
+            if let Err(e) = client.sync(settings).await {
+                tracing::error!("Matrix: sync loop ended: {}", e);
+            }
+            idle_sweep.abort();
+        })
+    }
+}
+
+/// Device ID the access token belongs to, via `/account/whoami`.
+async fn whoami_device(homeserver: &str, access_token: &str) -> Result<String, String> {
+    let url = format!(
+        "{}/_matrix/client/v3/account/whoami",
+        homeserver.trim_end_matches('/')
+    );
+    let body: serde_json::Value = reqwest::Client::new()
+        .get(&url)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    body.get("device_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "whoami returned no device_id — set [channels.matrix] device_id".into())
+}
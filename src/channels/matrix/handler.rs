@@ -0,0 +1,752 @@
+//! Matrix Message Handler
+//!
+//! Processes incoming Matrix room messages: text + images, allowlist
+//! enforcement, session routing (owner shares TUI session, others get
+//! per-user sessions). Replies are sent as Matrix HTML.
+
+use super::{MatrixApproval, MatrixState};
+use crate::brain::agent::{AgentService, ApprovalCallback, ApprovalDecision};
+use crate::channels::ChannelSessions;
+use crate::config::{Config, RespondTo};
+use crate::db::ChannelMessageRepository;
+use crate::db::models::ChannelMessage as DbChannelMessage;
+use crate::services::SessionService;
+use crate::utils::sanitize::redact_secrets;
+use crate::utils::truncate_str;
+use matrix_sdk::attachment::AttachmentConfig;
+use matrix_sdk::event_handler::Ctx;
+use matrix_sdk::ruma::events::room::member::{MembershipState, StrippedRoomMemberEvent};
+use matrix_sdk::ruma::events::room::message::{
+    MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
+};
+use matrix_sdk::ruma::{OwnedUserId, UserId};
+use matrix_sdk::{Client, Room, RoomState};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Longest message body sent in one event; longer replies are split
+const MAX_MESSAGE_LEN: usize = 12_000;
+
+/// State shared by the event handlers (registered as client context).
+pub(crate) struct HandlerState {
+    pub agent: Arc<AgentService>,
+    pub session_svc: SessionService,
+    pub extra_sessions: ChannelSessions<String>,
+    pub shared_session: Arc<Mutex<Option<Uuid>>>,
+    pub matrix_state: Arc<MatrixState>,
+    pub bot_user_id: OwnedUserId,
+    pub config_rx: tokio::sync::watch::Receiver<Config>,
+    pub channel_msg_repo: ChannelMessageRepository,
+}
+
+/// Join rooms the bot is invited to by an allowlisted user.
+pub(crate) async fn on_invite(
+    ev: StrippedRoomMemberEvent,
+    room: Room,
+    state: Ctx<Arc<HandlerState>>,
+) {
+    if ev.state_key != state.bot_user_id || ev.content.membership != MembershipState::Invite {
+        return;
+    }
+    let allowed = state
+        .config_rx
+        .borrow()
+        .is_matrix_allowed(ev.sender.as_str());
+    if !allowed {
+        tracing::debug!(
+            "Matrix: ignoring invite from non-allowed user {}",
+            ev.sender
+        );
+        return;
+    }
+    tracing::info!(
+        "Matrix: joining {} (invited by {})",
+        room.room_id(),
+        ev.sender
+    );
+    if let Err(e) = room.join().await {
+        tracing::error!("Matrix: failed to join {}: {}", room.room_id(), e);
+    }
+}
+
+/// Room message callback. Each message is handled on its own task so a
+/// reply to a pending approval isn't queued behind the turn waiting on it.
+pub(crate) async fn on_room_message(
+    ev: OriginalSyncRoomMessageEvent,
+    room: Room,
+    client: Client,
+    state: Ctx<Arc<HandlerState>>,
+) {
+    let state = state.0.clone();
+    tokio::spawn(async move { handle_message(ev, room, client, state).await });
+}
+
+async fn handle_message(
+    ev: OriginalSyncRoomMessageEvent,
+    room: Room,
+    client: Client,
+    state: Arc<HandlerState>,
+) {
+    // Skip our own messages, rooms we're not in and edits of earlier messages
+    if ev.sender == state.bot_user_id || room.state() != RoomState::Joined {
+        return;
+    }
+    if matches!(ev.content.relates_to, Some(Relation::Replacement(_))) {
+        return;
+    }
+
+    let user_id = ev.sender.to_string();
+    let room_id = room.room_id().to_string();
+    let (text, image) = match &ev.content.msgtype {
+        MessageType::Text(t) => (t.body.clone(), None),
+        MessageType::Image(img) => (
+            img.caption().map(str::to_string).unwrap_or_default(),
+            Some(img.clone()),
+        ),
+        _ => return,
+    };
+    if text.is_empty() && image.is_none() {
+        return;
+    }
+
+    let sender_name = match room.get_member_no_sync(&ev.sender).await {
+        Ok(Some(member)) => member.display_name().unwrap_or_default().to_string(),
+        _ => String::new(),
+    };
+
+    // Helper: passively capture a room message for history
+    let store_channel_msg = |text: String| {
+        let repo = state.channel_msg_repo.clone();
+        let room_id = room_id.clone();
+        let room_name = room.name();
+        let uid = user_id.clone();
+        let name = sender_name.clone();
+        async move {
+            if text.is_empty() {
+                return;
+            }
+            let cm = DbChannelMessage::new(
+                "matrix".into(),
+                room_id,
+                room_name,
+                uid,
+                name,
+                text,
+                "text".into(),
+                None,
+            );
+            if let Err(e) = repo.insert(&cm).await {
+                tracing::warn!("Failed to store Matrix channel message: {e}");
+            }
+        }
+    };
+
+    // Read latest config from watch channel — single source of truth
+    let cfg = state.config_rx.borrow().clone();
+    let mx_cfg = &cfg.channels.matrix;
+    let allowed_rooms: HashSet<&str> = mx_cfg.allowed_rooms.iter().map(|r| r.trim()).collect();
+    let idle_timeout = cfg.sessions.channel_idle_timeout(mx_cfg.session_idle_hours);
+    let archive_idle = cfg.sessions.archive_idle;
+
+    // Allowlist check — an empty list (and no [owner] matrix_id) accepts nobody
+    if !cfg.is_matrix_allowed(user_id.as_str()) {
+        tracing::debug!("Matrix: ignoring message from non-allowed user {}", user_id);
+        return;
+    }
+
+    // Pending approval check: if a tool approval is waiting for this user,
+    // interpret this message as Yes / Always / No instead of routing it —
+    // before mention filtering, so a bare "yes" counts in group rooms
+    if let Some(choice) = parse_approval(&text)
+        && state
+            .matrix_state
+            .resolve_pending_approval(&user_id, choice)
+            .await
+    {
+        tracing::info!("Matrix: approval from {}: {:?}", user_id, choice);
+        return;
+    }
+
+    // respond_to / allowed_rooms filtering — direct rooms always pass
+    let is_dm = room.is_direct().await.unwrap_or(false);
+    let mut text = text;
+    if !is_dm {
+        if !allowed_rooms.is_empty() && !allowed_rooms.contains(room_id.as_str()) {
+            tracing::debug!("Matrix: ignoring message in non-allowed room {}", room_id);
+            store_channel_msg(text).await;
+            return;
+        }
+        store_channel_msg(text.clone()).await;
+
+        match mx_cfg.respond_to {
+            RespondTo::DmOnly => {
+                tracing::debug!("Matrix: respond_to=dm_only, ignoring room message");
+                return;
+            }
+            RespondTo::Mention => {
+                let intentional = ev
+                    .content
+                    .mentions
+                    .as_ref()
+                    .is_some_and(|m| m.user_ids.contains(&state.bot_user_id));
+                match strip_mention(&text, &state.bot_user_id) {
+                    Some(stripped) => text = stripped,
+                    None if intentional => {}
+                    None => {
+                        tracing::debug!("Matrix: respond_to=mention, bot not mentioned — ignoring");
+                        return;
+                    }
+                }
+            }
+            RespondTo::All => {}
+        }
+    }
+
+    tracing::info!(
+        "Matrix: message from {}: {}",
+        user_id,
+        truncate_str(&text, 50)
+    );
+
+    // Download the image (decrypted by the SDK in encrypted rooms)
+    let mut content = text;
+    if let Some(img) = image {
+        match client.media().get_file(&img, true).await {
+            Ok(Some(bytes)) => {
+                let ext = match img.info.as_ref().and_then(|i| i.mimetype.as_deref()) {
+                    Some("image/png") => "png",
+                    Some("image/gif") => "gif",
+                    Some("image/webp") => "webp",
+                    _ => "jpg",
+                };
+                let tmp = std::env::temp_dir().join(format!("mx_img_{}.{}", Uuid::new_v4(), ext));
+                if std::fs::write(&tmp, &bytes).is_ok() {
+                    if content.is_empty() {
+                        content = "Describe this image.".to_string();
+                    }
+                    content.push_str(&format!(" <<IMG:{}>>", tmp.display()));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Matrix: failed to download image: {}", e);
+                let _ = send_text(&room, "⚠️ Couldn't download that image.").await;
+            }
+        }
+    }
+    if content.is_empty() {
+        return;
+    }
+
+    // Resolve session: owner ([owner].matrix_id, else first in allowed list)
+    // shares the TUI session, others get their own
+    let is_owner = cfg.is_matrix_owner(&user_id);
+    if is_owner {
+        state.matrix_state.set_owner_room(room_id.clone()).await;
+    }
+
+    let session_id = if is_owner {
+        let shared = state.shared_session.lock().await;
+        match *shared {
+            Some(id) => id,
+            None => {
+                drop(shared);
+                // Resume most recent session from DB (survives daemon restarts)
+                let restored = match state.session_svc.get_most_recent_session().await {
+                    Ok(Some(s)) => {
+                        tracing::info!("Matrix: restored most recent session {}", s.id);
+                        Some(s.id)
+                    }
+                    _ => None,
+                };
+                let id = match restored {
+                    Some(id) => id,
+                    None => {
+                        tracing::info!("Matrix: no existing session, creating one for owner");
+                        match state
+                            .session_svc
+                            .create_session(Some("Chat".to_string()))
+                            .await
+                        {
+                            Ok(session) => session.id,
+                            Err(e) => {
+                                tracing::error!("Matrix: failed to create session: {}", e);
+                                return;
+                            }
+                        }
+                    }
+                };
+                *state.shared_session.lock().await = Some(id);
+                id
+            }
+        }
+    } else {
+        let mut map = state.extra_sessions.lock().await;
+        let existing = map.get(&user_id).copied();
+        match existing {
+            Some((old_id, last_activity))
+                if !idle_timeout.is_some_and(|t| last_activity.elapsed() > t) =>
+            {
+                map.insert(user_id.clone(), (old_id, std::time::Instant::now()));
+                old_id
+            }
+            _ => {
+                if let Some((old_id, _)) = existing
+                    && archive_idle
+                {
+                    let _ = state.session_svc.archive_session(old_id).await;
+                }
+                let title = format!("Matrix: {}", user_id);
+                match state.session_svc.create_session(Some(title)).await {
+                    Ok(session) => {
+                        map.insert(user_id.clone(), (session.id, std::time::Instant::now()));
+                        session.id
+                    }
+                    Err(e) => {
+                        tracing::error!("Matrix: failed to create session: {}", e);
+                        return;
+                    }
+                }
+            }
+        }
+    };
+
+    // ── Channel commands (/help, /usage, /models, /stop) ────────────────────
+    {
+        use crate::channels::commands::{self, AllowlistChannel, ChannelCommand};
+        if let Some(reply) =
+            commands::handle_allowlist_command(&content, AllowlistChannel::Matrix, &cfg, &user_id)
+        {
+            let _ = send_markdown(&room, &reply).await;
+            return;
+        }
+        match commands::handle_command(&content, session_id, &state.agent, &state.session_svc).await
+        {
+            ChannelCommand::Help(body) | ChannelCommand::Usage(body) => {
+                let _ = send_markdown(&room, &body).await;
+                return;
+            }
+            ChannelCommand::Models(resp) | ChannelCommand::Sessions(resp) => {
+                // No inline buttons — send the plain text list
+                let _ = send_markdown(&room, &resp.text).await;
+                return;
+            }
+            ChannelCommand::NewSession => {
+                let reply = match state
+                    .session_svc
+                    .create_session(Some("Chat".to_string()))
+                    .await
+                {
+                    Ok(new_session) => {
+                        if is_owner {
+                            *state.shared_session.lock().await = Some(new_session.id);
+                        } else {
+                            state.extra_sessions.lock().await.insert(
+                                user_id.clone(),
+                                (new_session.id, std::time::Instant::now()),
+                            );
+                        }
+                        "✅ New session started."
+                    }
+                    Err(e) => {
+                        tracing::error!("Matrix: failed to create session: {}", e);
+                        "Failed to create session."
+                    }
+                };
+                let _ = send_text(&room, reply).await;
+                return;
+            }
+            ChannelCommand::Stop => {
+                let reply = if state.matrix_state.cancel_session(session_id).await {
+                    "Operation cancelled."
+                } else {
+                    "No operation in progress."
+                };
+                let _ = send_text(&room, reply).await;
+                return;
+            }
+            ChannelCommand::Compact => {
+                let _ = send_text(&room, "⏳ Compacting context...").await;
+                content =
+                    "[SYSTEM: Compact context now. Summarize this conversation for continuity.]"
+                        .to_string();
+            }
+            ChannelCommand::UserPrompt(prompt) => {
+                content = prompt;
+                // fall through to agent with the prompt as the message
+            }
+            ChannelCommand::UserSystem(text) => {
+                let _ = send_markdown(&room, &text).await;
+                return;
+            }
+            ChannelCommand::NotACommand => {}
+        }
+    }
+
+    // For non-owner users, prepend sender identity so the agent knows who
+    // it's talking to and doesn't assume it's the owner.
+    let agent_input = if !is_owner {
+        let from = if sender_name.is_empty() {
+            user_id.clone()
+        } else {
+            format!("{} ({})", sender_name, user_id)
+        };
+        if is_dm {
+            format!("[Matrix DM from {from}]\n{content}")
+        } else {
+            format!("[Matrix message from {from} in room {room_id}]\n{content}")
+        }
+    } else {
+        content
+    };
+
+    // Inject recent room history so the agent has full conversation context.
+    let agent_input = if !is_dm {
+        match state
+            .channel_msg_repo
+            .recent(Some("matrix"), &room_id, 30)
+            .await
+        {
+            Ok(messages) if !messages.is_empty() => {
+                let history: Vec<String> = messages
+                    .iter()
+                    .rev()
+                    .map(|m| {
+                        let ts = crate::config::format_local(m.created_at, "%H:%M");
+                        format!("[{}] {}: {}", ts, m.sender_name, m.content)
+                    })
+                    .collect();
+                format!(
+                    "[Recent room history ({} messages):\n{}\n--- end history ---]\n{}",
+                    history.len(),
+                    history.join("\n"),
+                    agent_input
+                )
+            }
+            _ => agent_input,
+        }
+    } else {
+        agent_input
+    };
+
+    // Tell the LLM its text response is automatically delivered to the room.
+    let agent_input = format!(
+        "[Channel: Matrix — your text response is automatically sent to this room. \
+         Markdown is rendered. Just reply with text.]\n{agent_input}"
+    );
+
+    // Typing indicator — refreshed while the agent thinks
+    let typing_cancel = CancellationToken::new();
+    tokio::spawn({
+        let room = room.clone();
+        let cancel = typing_cancel.clone();
+        async move {
+            loop {
+                let _ = room.typing_notice(true).await;
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(20)) => {}
+                }
+            }
+            let _ = room.typing_notice(false).await;
+        }
+    });
+
+    // Queue behind other channel messages at [channels] max_concurrent
+    let permit = crate::channels::acquire_agent_permit("Matrix", cfg.channels.max_concurrent).await;
+    let approval_cb = make_approval_callback(
+        room.clone(),
+        user_id.clone(),
+        state.matrix_state.clone(),
+        state.config_rx.clone(),
+        permit,
+    );
+
+    let cancel_token = CancellationToken::new();
+    state
+        .matrix_state
+        .store_cancel_token(session_id, cancel_token.clone())
+        .await;
+
+    let result = state
+        .agent
+        .send_message_with_tools_and_callback(
+            session_id,
+            agent_input,
+            None,
+            Some(cancel_token),
+            Some(approval_cb),
+            None,
+        )
+        .await;
+
+    state.matrix_state.remove_cancel_token(session_id).await;
+    typing_cancel.cancel();
+
+    match result {
+        Ok(response) => {
+            // Extract <<IMG:path>> markers — upload each as a Matrix image.
+            let (text_only, img_paths) = crate::utils::extract_img_markers(&response.content);
+            let text_only = redact_secrets(&text_only);
+
+            for img_path in img_paths {
+                match tokio::fs::read(&img_path).await {
+                    Ok(bytes) => {
+                        let name = std::path::Path::new(&img_path)
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("image.png")
+                            .to_string();
+                        let mime = if img_path.ends_with(".png") {
+                            mime::IMAGE_PNG
+                        } else {
+                            mime::IMAGE_JPEG
+                        };
+                        if let Err(e) = room
+                            .send_attachment(&name, &mime, bytes, AttachmentConfig::new())
+                            .await
+                        {
+                            tracing::error!("Matrix: failed to send generated image: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Matrix: failed to read image {}: {}", img_path, e);
+                    }
+                }
+            }
+
+            if let Err(e) = send_markdown(&room, &text_only).await {
+                tracing::error!("Matrix: failed to send reply: {}", e);
+            }
+        }
+        Err(e) => {
+            tracing::error!("Matrix: agent error: {}", e);
+            let _ = send_text(&room, &format!("Error: {}", e.user_message())).await;
+        }
+    }
+}
+
+/// Build an `ApprovalCallback` that asks in `room` for a text reply
+/// (yes / always / yolo / no / stop) from `user_id` and waits up to
+/// `[tools] approval_timeout_secs`, giving up `permit` meanwhile.
+fn make_approval_callback(
+    room: Room,
+    user_id: String,
+    matrix_state: Arc<MatrixState>,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    permit: crate::channels::AgentPermit,
+) -> ApprovalCallback {
+    use crate::utils::{check_approval_policy, persist_auto_session_policy};
+
+    Arc::new(move |tool_info| {
+        let room = room.clone();
+        let user_id = user_id.clone();
+        let matrix_state = matrix_state.clone();
+        let config_rx = config_rx.clone();
+        let permit = permit.clone();
+        Box::pin(async move {
+            // Respect config-level approval policy (single source of truth)
+            if !tool_info.forced
+                && let Some(decision) = check_approval_policy()
+            {
+                return Ok(decision);
+            }
+
+            // Redact secrets before display
+            let safe_input = crate::utils::redact_tool_input(&tool_info.tool_input);
+            let input_preview = serde_json::to_string_pretty(&safe_input).unwrap_or_default();
+            let body = format!(
+                "🔐 **Tool Approval Required**\n\nTool: `{}`\n```\n{}\n```\n\nReply **yes**, **always** (session), **yolo** (permanent), **no**, or **stop** (deny and end the turn).",
+                tool_info.tool_name,
+                truncate_str(&input_preview, 600),
+            );
+            if let Err(e) = send_markdown(&room, &body).await {
+                tracing::error!("Matrix: failed to send approval request: {}", e);
+                return Ok(ApprovalDecision::Deny);
+            }
+
+            let (tx, rx) = tokio::sync::oneshot::channel::<MatrixApproval>();
+            matrix_state
+                .register_pending_approval(user_id.clone(), tx)
+                .await;
+
+            match permit
+                .released_while(crate::utils::await_approval(rx, &config_rx))
+                .await
+            {
+                Some(Ok(MatrixApproval::Yes)) => Ok(ApprovalDecision::Approve),
+                Some(Ok(MatrixApproval::Always)) => {
+                    persist_auto_session_policy();
+                    Ok(ApprovalDecision::ApproveAll)
+                }
+                Some(Ok(MatrixApproval::Yolo)) => {
+                    crate::utils::persist_auto_always_policy();
+                    Ok(ApprovalDecision::ApproveAll)
+                }
+                Some(Ok(MatrixApproval::No)) => Ok(ApprovalDecision::Deny),
+                Some(Ok(MatrixApproval::Stop)) => Ok(ApprovalDecision::DenyAndStop),
+                _ => {
+                    tracing::warn!(
+                        "Matrix: approval timed out or channel dropped — denying (user={})",
+                        user_id
+                    );
+                    let _ = send_markdown(
+                        &room,
+                        &format!(
+                            "⏰ No response in time — **{}** was denied.",
+                            tool_info.tool_name
+                        ),
+                    )
+                    .await;
+                    Ok(ApprovalDecision::Deny)
+                }
+            }
+        })
+    })
+}
+
+/// An approval answer typed as a reply, if `text` is one.
+fn parse_approval(text: &str) -> Option<MatrixApproval> {
+    match text.trim().to_lowercase().as_str() {
+        "yes" | "y" => Some(MatrixApproval::Yes),
+        "always" => Some(MatrixApproval::Always),
+        "yolo" => Some(MatrixApproval::Yolo),
+        "no" | "n" => Some(MatrixApproval::No),
+        "stop" => Some(MatrixApproval::Stop),
+        _ => None,
+    }
+}
+
+/// `body` without the bot's mention when it addresses the bot — by full
+/// user ID anywhere, or by a leading "name:" as clients insert for pills.
+/// `None` when the bot isn't mentioned.
+fn strip_mention(body: &str, bot: &UserId) -> Option<String> {
+    if body.contains(bot.as_str()) {
+        return Some(body.replace(bot.as_str(), "").trim().to_string());
+    }
+    let (head, rest) = body.split_once(':')?;
+    let head = head.trim().trim_start_matches('@');
+    head.eq_ignore_ascii_case(bot.localpart())
+        .then(|| rest.trim().to_string())
+}
+
+/// Convert agent markdown to the HTML subset Matrix clients render.
+/// Raw HTML in the input is escaped rather than passed through.
+pub(crate) fn markdown_to_matrix_html(text: &str) -> String {
+    use pulldown_cmark::{Event, Options, Parser, html};
+
+    let parser = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES).map(
+        |event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            other => other,
+        },
+    );
+    let mut out = String::with_capacity(text.len() + 64);
+    html::push_html(&mut out, parser);
+    out.trim_end().to_string()
+}
+
+/// Send `text` as formatted Matrix HTML, split into event-sized chunks.
+async fn send_markdown(room: &Room, text: &str) -> matrix_sdk::Result<()> {
+    for chunk in split_message(text, MAX_MESSAGE_LEN) {
+        if chunk.trim().is_empty() {
+            continue;
+        }
+        let content =
+            RoomMessageEventContent::text_html(chunk.to_string(), markdown_to_matrix_html(chunk));
+        room.send(content).await?;
+    }
+    Ok(())
+}
+
+/// Send `text` unformatted.
+async fn send_text(room: &Room, text: &str) -> matrix_sdk::Result<()> {
+    room.send(RoomMessageEventContent::text_plain(text)).await?;
+    Ok(())
+}
+
+/// Split a message into chunks, preferring newline boundaries.
+pub fn split_message(text: &str, max_len: usize) -> Vec<&str> {
+    if text.len() <= max_len {
+        return vec![text];
+    }
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + max_len).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let break_at = if end < text.len() {
+            text[start..end]
+                .rfind('\n')
+                .filter(|&pos| pos > 0)
+                .map(|pos| start + pos + 1)
+                .unwrap_or(end)
+        } else {
+            end
+        };
+        chunks.push(&text[start..break_at]);
+        start = break_at;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_matrix_html() {
+        let html = markdown_to_matrix_html("**bold** and `code` ~~gone~~\n\n- one\n- two");
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<code>code</code>"));
+        assert!(html.contains("<del>gone</del>"));
+        assert!(html.contains("<ul>\n<li>one</li>"));
+
+        let html = markdown_to_matrix_html("```rust\nfn main() {}\n```");
+        assert!(html.contains("<pre><code class=\"language-rust\">fn main() {}"));
+    }
+
+    #[test]
+    fn test_raw_html_is_escaped() {
+        let html = markdown_to_matrix_html("hi <script>alert(1)</script>");
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_strip_mention() {
+        let bot = UserId::parse("@crabs:example.org").unwrap();
+        assert_eq!(
+            strip_mention("@crabs:example.org what's up?", &bot).as_deref(),
+            Some("what's up?")
+        );
+        assert_eq!(
+            strip_mention("Crabs: summarise this", &bot).as_deref(),
+            Some("summarise this")
+        );
+        assert_eq!(strip_mention("lunch at 12:30?", &bot), None);
+    }
+
+    #[test]
+    fn test_parse_approval() {
+        assert_eq!(parse_approval(" Yes "), Some(MatrixApproval::Yes));
+        assert_eq!(parse_approval("yolo"), Some(MatrixApproval::Yolo));
+        assert_eq!(parse_approval("STOP"), Some(MatrixApproval::Stop));
+        assert_eq!(parse_approval("yes please"), None);
+    }
+
+    #[test]
+    fn test_split_long_message() {
+        let text = format!("{}\n{}", "a".repeat(10), "b".repeat(10));
+        let chunks = split_message(&text, 15);
+        assert_eq!(
+            chunks,
+            vec![format!("{}\n", "a".repeat(10)), "b".repeat(10)]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Matrix Integration
+//!
+//! Runs a Matrix client alongside the TUI, forwarding messages from
+//! allowlisted users to the AgentService and replying with responses.
+//! Encrypted rooms work out of the box: keys live in a local SQLite store.
+
+mod agent;
+pub(crate) mod handler;
+
+pub use agent::MatrixAgent;
+
+use matrix_sdk::Client;
+use std::collections::HashMap;
+use tokio::sync::{Mutex, oneshot};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Approval choices mirroring the TUI's Yes / Always (session) / YOLO (permanent) / No.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixApproval {
+    /// Approve this tool call once.
+    Yes,
+    /// Approve this and all future tool calls for the rest of the session.
+    Always,
+    /// Approve permanently (survives restarts).
+    Yolo,
+    /// Deny this tool call.
+    No,
+    /// Deny this tool call and end the agent's turn.
+    Stop,
+}
+
+/// Shared Matrix state.
+///
+/// Set when the client finishes its first sync. Holds pending text approvals
+/// and per-session cancel tokens for the message handler.
+pub struct MatrixState {
+    client: Mutex<Option<Client>>,
+    /// Room ID of the owner's last message — used as default for proactive sends
+    owner_room_id: Mutex<Option<String>>,
+    /// Pending tool approvals: user ID → oneshot sender of MatrixApproval.
+    /// While one is in flight, the user's next message is read as the answer
+    /// instead of being routed to the agent.
+    pending_approvals: Mutex<HashMap<String, oneshot::Sender<MatrixApproval>>>,
+    /// Per-session cancel tokens for aborting in-flight agent tasks via /stop
+    cancel_tokens: Mutex<HashMap<Uuid, CancellationToken>>,
+}
+
+impl Default for MatrixState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatrixState {
+    pub fn new() -> Self {
+        Self {
+            client: Mutex::new(None),
+            owner_room_id: Mutex::new(None),
+            pending_approvals: Mutex::new(HashMap::new()),
+            cancel_tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Store the connected client.
+    pub async fn set_connected(&self, client: Client) {
+        *self.client.lock().await = Some(client);
+    }
+
+    /// Get a clone of the connected client, if any.
+    pub async fn client(&self) -> Option<Client> {
+        self.client.lock().await.clone()
+    }
+
+    /// Check if Matrix is currently connected.
+    pub async fn is_connected(&self) -> bool {
+        self.client.lock().await.is_some()
+    }
+
+    /// Update the owner's room ID (called on each owner message).
+    pub async fn set_owner_room(&self, room_id: String) {
+        *self.owner_room_id.lock().await = Some(room_id);
+    }
+
+    /// Get the owner's last room ID for proactive messaging.
+    pub async fn owner_room_id(&self) -> Option<String> {
+        self.owner_room_id.lock().await.clone()
+    }
+
+    /// Register a pending approval for a user.
+    pub async fn register_pending_approval(
+        &self,
+        user_id: String,
+        tx: oneshot::Sender<MatrixApproval>,
+    ) {
+        self.pending_approvals.lock().await.insert(user_id, tx);
+    }
+
+    /// Resolve a pending approval. Returns true if one existed.
+    pub async fn resolve_pending_approval(&self, user_id: &str, choice: MatrixApproval) -> bool {
+        if let Some(tx) = self.pending_approvals.lock().await.remove(user_id) {
+            let _ = tx.send(choice);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Store a cancel token for a session (before starting agent call).
+    pub async fn store_cancel_token(&self, session_id: Uuid, token: CancellationToken) {
+        self.cancel_tokens.lock().await.insert(session_id, token);
+    }
+
+    /// Cancel and remove the token for a session. Returns true if a token existed.
+    pub async fn cancel_session(&self, session_id: Uuid) -> bool {
+        if let Some(token) = self.cancel_tokens.lock().await.remove(&session_id) {
+            token.cancel();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove the cancel token after the agent call completes (cleanup).
+    pub async fn remove_cancel_token(&self, session_id: Uuid) {
+        self.cancel_tokens.lock().await.remove(&session_id);
+    }
+}
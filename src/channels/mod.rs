@@ -1,14 +1,17 @@
 //! Channel Integrations
 //!
-//! Messaging channel integrations (Telegram, WhatsApp, Discord, Slack) and the
-//! shared factory for creating channel-specific agent services.
+//! Messaging channel integrations (Telegram, WhatsApp, Discord, Slack, Matrix)
+//! and the shared factory for creating channel-specific agent services.
 
 pub mod commands;
 mod factory;
+pub mod image_limits;
 pub mod voice;
 
 #[cfg(feature = "discord")]
 pub mod discord;
+#[cfg(feature = "matrix")]
+pub mod matrix;
 #[cfg(feature = "slack")]
 pub mod slack;
 #[cfg(feature = "telegram")]
@@ -19,3 +22,323 @@ pub mod trello;
 pub mod whatsapp;
 
 pub use factory::ChannelFactory;
+
+use crate::config::{ChannelsConfig, Config};
+use crate::services::SessionService;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Arc, LazyLock, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore, TryAcquireError};
+use uuid::Uuid;
+
+/// Where a request to the agent originated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageSource {
+    Tui,
+    Telegram,
+    Discord,
+    Slack,
+    WhatsApp,
+    Matrix,
+    Trello,
+}
+
+impl MessageSource {
+    /// Channels `/pause` can stop
+    pub const PAUSABLE: [Self; 3] = [Self::Telegram, Self::Discord, Self::WhatsApp];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Tui => "tui",
+            Self::Telegram => "telegram",
+            Self::Discord => "discord",
+            Self::Slack => "slack",
+            Self::WhatsApp => "whatsapp",
+            Self::Matrix => "matrix",
+            Self::Trello => "trello",
+        }
+    }
+
+    /// The `[channels.<name>] system_append` text for this source, if set.
+    /// The TUI has no channel section and never gets one.
+    pub fn system_append(self, channels: &ChannelsConfig) -> Option<String> {
+        let append = match self {
+            Self::Tui => return None,
+            Self::Telegram => &channels.telegram.system_append,
+            Self::Discord => &channels.discord.system_append,
+            Self::Slack => &channels.slack.system_append,
+            Self::WhatsApp => &channels.whatsapp.system_append,
+            Self::Matrix => &channels.matrix.system_append,
+            Self::Trello => &channels.trello.system_append,
+        };
+        append.clone().filter(|text| !text.trim().is_empty())
+    }
+}
+
+/// Sent once to the owner when they message a paused channel
+pub const PAUSED_NOTICE: &str =
+    "⏸ This bot is paused. Run /resume in OpenCrabs to turn it back on.";
+
+/// Paused channels, and those whose owner was already told they're paused
+#[derive(Default)]
+struct PauseState {
+    paused: HashSet<MessageSource>,
+    notified: HashSet<MessageSource>,
+}
+
+static PAUSE_STATE: LazyLock<std::sync::Mutex<PauseState>> = LazyLock::new(Default::default);
+
+fn pause_state() -> std::sync::MutexGuard<'static, PauseState> {
+    PAUSE_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Pause or resume a channel. Returns false if it was already in that state.
+pub fn set_paused(source: MessageSource, paused: bool) -> bool {
+    let mut state = pause_state();
+    if !paused {
+        return state.paused.remove(&source);
+    }
+    let changed = state.paused.insert(source);
+    if changed {
+        state.notified.remove(&source);
+    }
+    changed
+}
+
+/// Checked at the top of each channel handler; paused channels drop messages.
+pub fn is_paused(source: MessageSource) -> bool {
+    pause_state().paused.contains(&source)
+}
+
+/// Channels currently paused, for the status bar
+pub fn paused_channels() -> Vec<MessageSource> {
+    MessageSource::PAUSABLE
+        .into_iter()
+        .filter(|source| is_paused(*source))
+        .collect()
+}
+
+/// True the first time it is called after `source` was paused, so the owner
+/// gets [`PAUSED_NOTICE`] once instead of on every message.
+pub fn take_pause_notice(source: MessageSource) -> bool {
+    pause_state().notified.insert(source)
+}
+
+/// Non-owner channel users → (session id, last activity)
+pub type ChannelSessions<K> = Arc<Mutex<HashMap<K, (Uuid, Instant)>>>;
+
+/// How often the idle sweep checks `ChannelSessions`
+const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Shared cap on channel messages being handled by the agent at once.
+/// Sized from `[channels] max_concurrent` on first use; `None` = no limit.
+static AGENT_PERMITS: OnceLock<Option<Arc<Semaphore>>> = OnceLock::new();
+
+/// A channel message's slot under `[channels] max_concurrent`. Clones share
+/// the slot, so the approval callback can give it up while the user decides.
+/// Holds nothing when there is no cap.
+#[derive(Clone, Default)]
+pub struct AgentPermit {
+    permits: Option<Arc<Semaphore>>,
+    channel: &'static str,
+    held: Arc<Mutex<Option<OwnedSemaphorePermit>>>,
+}
+
+impl AgentPermit {
+    /// Give up the slot while `fut` runs, then queue for it again before
+    /// returning — an approval wait mustn't block other channel messages.
+    pub async fn released_while<F: std::future::Future>(&self, fut: F) -> F::Output {
+        let Some(permits) = &self.permits else {
+            return fut.await;
+        };
+        self.held.lock().await.take();
+        let output = fut.await;
+        *self.held.lock().await = wait_for_permit(permits, self.channel).await;
+        output
+    }
+}
+
+/// Wait for a slot to hand a `channel` message to the agent. Hold the permit
+/// until the agent call returns. Holds nothing when `limit` is 0 (no cap).
+pub async fn acquire_agent_permit(channel: &'static str, limit: usize) -> AgentPermit {
+    let Some(permits) = AGENT_PERMITS
+        .get_or_init(|| (limit > 0).then(|| Arc::new(Semaphore::new(limit))))
+        .clone()
+    else {
+        return AgentPermit::default();
+    };
+    let held = wait_for_permit(&permits, channel).await;
+    AgentPermit {
+        permits: Some(permits),
+        channel,
+        held: Arc::new(Mutex::new(held)),
+    }
+}
+
+async fn wait_for_permit(permits: &Arc<Semaphore>, channel: &str) -> Option<OwnedSemaphorePermit> {
+    match permits.clone().try_acquire_owned() {
+        Ok(permit) => Some(permit),
+        Err(TryAcquireError::NoPermits) => {
+            tracing::info!(
+                "{}: channel concurrency limit reached, queueing message",
+                channel
+            );
+            permits.clone().acquire_owned().await.ok()
+        }
+        Err(TryAcquireError::Closed) => None,
+    }
+}
+
+/// `text` below `header` (`[channels.*] reply_header`), separated by a blank
+/// line. An empty header or blank text leaves `text` unchanged.
+pub fn with_reply_header(header: &str, text: &str) -> String {
+    if header.is_empty() || text.trim().is_empty() {
+        text.to_string()
+    } else {
+        format!("{header}\n\n{text}")
+    }
+}
+
+/// Remove entries idle longer than `timeout` as of `now`, returning their
+/// session ids.
+pub fn take_idle_sessions<K: Eq + Hash>(
+    sessions: &mut HashMap<K, (Uuid, Instant)>,
+    timeout: Duration,
+    now: Instant,
+) -> Vec<Uuid> {
+    let mut expired = Vec::new();
+    sessions.retain(|_, (id, last_activity)| {
+        let idle = now.saturating_duration_since(*last_activity) > timeout;
+        if idle {
+            expired.push(*id);
+        }
+        !idle
+    });
+    expired
+}
+
+/// Periodically drop idle entries from `sessions` (`[sessions]
+/// channel_idle_minutes`, or the channel's `session_idle_hours` picked by
+/// `channel_hours`), archiving them when `archive_idle` is set. Config is
+/// re-read every tick so reloads apply.
+pub fn spawn_idle_sweep<K>(
+    channel: &'static str,
+    sessions: ChannelSessions<K>,
+    session_svc: SessionService,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    channel_hours: fn(&Config) -> Option<f64>,
+) -> tokio::task::JoinHandle<()>
+where
+    K: Eq + Hash + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let cfg = config_rx.borrow().clone();
+            let Some(timeout) = cfg.sessions.channel_idle_timeout(channel_hours(&cfg)) else {
+                continue;
+            };
+            let expired = take_idle_sessions(&mut *sessions.lock().await, timeout, Instant::now());
+            if expired.is_empty() {
+                continue;
+            }
+            tracing::info!("{}: dropped {} idle session(s)", channel, expired.len());
+            if cfg.sessions.archive_idle {
+                for id in expired {
+                    if let Err(e) = session_svc.archive_session(id).await {
+                        tracing::warn!("{}: failed to archive idle session {}: {}", channel, id, e);
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_notice_sent_once_per_pause() {
+        let source = MessageSource::WhatsApp;
+        assert!(set_paused(source, true));
+        assert!(!set_paused(source, true));
+        assert!(is_paused(source));
+        assert!(paused_channels().contains(&source));
+
+        assert!(take_pause_notice(source));
+        assert!(!take_pause_notice(source));
+
+        assert!(set_paused(source, false));
+        assert!(!is_paused(source));
+        assert!(set_paused(source, true));
+        assert!(take_pause_notice(source));
+        set_paused(source, false);
+    }
+
+    #[test]
+    fn test_system_append_per_source() {
+        let mut channels = ChannelsConfig::default();
+        channels.discord.system_append = Some("Keep it short.".to_string());
+        channels.slack.system_append = Some("  ".to_string());
+
+        assert_eq!(
+            MessageSource::Discord.system_append(&channels).as_deref(),
+            Some("Keep it short.")
+        );
+        assert_eq!(MessageSource::Slack.system_append(&channels), None);
+        assert_eq!(MessageSource::Telegram.system_append(&channels), None);
+        assert_eq!(MessageSource::Tui.system_append(&channels), None);
+    }
+
+    #[test]
+    fn test_take_idle_sessions() {
+        let now = Instant::now();
+        let (fresh, stale) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut sessions = HashMap::new();
+        sessions.insert(1, (fresh, now - Duration::from_secs(60)));
+        sessions.insert(2, (stale, now - Duration::from_secs(3600)));
+
+        let expired = take_idle_sessions(&mut sessions, Duration::from_secs(600), now);
+        assert_eq!(expired, vec![stale]);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[&1].0, fresh);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_permit_queues_at_limit() {
+        let permits = Arc::new(Semaphore::new(1));
+        let first = wait_for_permit(&permits, "Test").await.unwrap();
+
+        let queued =
+            tokio::time::timeout(Duration::from_millis(50), wait_for_permit(&permits, "Test"))
+                .await;
+        assert!(queued.is_err(), "second message should wait");
+
+        drop(first);
+        assert!(wait_for_permit(&permits, "Test").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_permit_released_while_waiting() {
+        let permits = Arc::new(Semaphore::new(1));
+        let permit = AgentPermit {
+            held: Arc::new(Mutex::new(wait_for_permit(&permits, "Test").await)),
+            permits: Some(permits.clone()),
+            channel: "Test",
+        };
+        assert_eq!(permits.available_permits(), 0);
+
+        // Another message gets the slot during the wait
+        let during = permit
+            .released_while(async { permits.available_permits() })
+            .await;
+        assert_eq!(during, 1);
+        // ...and it's held again afterwards
+        assert_eq!(permits.available_permits(), 0);
+        drop(permit);
+        assert_eq!(permits.available_permits(), 1);
+    }
+}
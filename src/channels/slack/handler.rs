@@ -7,7 +7,8 @@
 //! Socket Mode callbacks require plain function pointers (not closures).
 
 use super::SlackState;
-use crate::brain::agent::AgentService;
+use crate::brain::agent::{AgentService, ApprovalDecision};
+use crate::channels::voice::SttOptions;
 use crate::config::{Config, RespondTo};
 use crate::db::ChannelMessageRepository;
 use crate::db::models::ChannelMessage as DbChannelMessage;
@@ -125,14 +126,7 @@ pub async fn on_interaction(
                             .as_ref()
                             .map(|u| u.id.0.as_str())
                             .unwrap_or("");
-                        let is_owner = cfg.channels.slack.allowed_users.is_empty()
-                            || cfg
-                                .channels
-                                .slack
-                                .allowed_users
-                                .first()
-                                .map(|a| a == caller_id)
-                                .unwrap_or(false);
+                        let is_owner = cfg.is_slack_owner(caller_id);
 
                         if is_owner {
                             *state.shared_session.lock().await = Some(new_id);
@@ -169,31 +163,31 @@ pub async fn on_interaction(
                     continue;
                 }
 
-                let (approved, always, yolo, id) =
-                    if let Some(id) = action_id.strip_prefix("approve:") {
-                        (true, false, false, id.to_string())
-                    } else if let Some(id) = action_id.strip_prefix("always:") {
-                        (true, true, false, id.to_string())
-                    } else if let Some(id) = action_id.strip_prefix("yolo:") {
-                        (true, true, true, id.to_string())
-                    } else if let Some(id) = action_id.strip_prefix("deny:") {
-                        (false, false, false, id.to_string())
-                    } else {
-                        tracing::warn!("Slack: unknown action_id: {}", action_id);
-                        continue;
-                    };
+                let (decision, yolo, id) = if let Some(id) = action_id.strip_prefix("approve:") {
+                    (ApprovalDecision::Approve, false, id.to_string())
+                } else if let Some(id) = action_id.strip_prefix("always:") {
+                    (ApprovalDecision::ApproveAll, false, id.to_string())
+                } else if let Some(id) = action_id.strip_prefix("yolo:") {
+                    (ApprovalDecision::ApproveAll, true, id.to_string())
+                } else if let Some(id) = action_id.strip_prefix("deny:") {
+                    (ApprovalDecision::Deny, false, id.to_string())
+                } else if let Some(id) = action_id.strip_prefix("stop:") {
+                    (ApprovalDecision::DenyAndStop, false, id.to_string())
+                } else {
+                    tracing::warn!("Slack: unknown action_id: {}", action_id);
+                    continue;
+                };
                 if yolo {
                     crate::utils::persist_auto_always_policy();
                 }
                 let resolved = state
                     .slack_state
-                    .resolve_pending_approval(&id, approved, always)
+                    .resolve_pending_approval(&id, decision)
                     .await;
                 tracing::info!(
-                    "Slack approval resolved: id={}, approved={}, always={}, found_pending={}",
+                    "Slack approval resolved: id={}, decision={:?}, found_pending={}",
                     id,
-                    approved,
-                    always,
+                    decision,
                     resolved
                 );
                 if !resolved {
@@ -444,12 +438,7 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
     tracing::info!("Slack: message from {}: {}", user_id, text_preview);
 
     // Track owner's channel for proactive messaging
-    let is_owner = allowed.is_empty()
-        || allowed
-            .iter()
-            .next()
-            .map(|a| *a == user_id)
-            .unwrap_or(false);
+    let is_owner = cfg.is_slack_owner(&user_id);
 
     if is_owner {
         state
@@ -572,10 +561,12 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
             // Audio → STT
             if mime.starts_with("audio/") {
                 if voice_config.stt_enabled
-                    && let Some(ref stt_provider) = voice_config.stt_provider
-                    && let Some(ref stt_key) = stt_provider.api_key
+                    && let Some(stt) = crate::channels::voice::build_stt(&voice_config)
                 {
-                    match crate::channels::voice::transcribe_audio(dl_bytes, stt_key).await {
+                    match stt
+                        .transcribe(dl_bytes, &SttOptions::for_user(&voice_config, &user_id))
+                        .await
+                    {
                         Ok(transcript) => {
                             tracing::info!(
                                 "Slack: transcribed audio: {}",
@@ -634,7 +625,19 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
 
     // ── Channel commands (/help, /usage, /models) ──────────────────────────
     {
-        use crate::channels::commands::{self, ChannelCommand};
+        use crate::channels::commands::{self, AllowlistChannel, ChannelCommand};
+        if let Some(reply) =
+            commands::handle_allowlist_command(&content, AllowlistChannel::Slack, &cfg, &user_id)
+        {
+            let token = SlackApiToken::new(SlackApiTokenValue::from(state.bot_token.clone()));
+            let session = client.open_session(&token);
+            let request = SlackApiChatPostMessageRequest::new(
+                SlackChannelId::new(channel_id),
+                SlackMessageContent::new().with_text(reply),
+            );
+            let _ = session.chat_post_message(&request).await;
+            return;
+        }
         match commands::handle_command(&content, session_id, &state.agent, &state.session_svc).await
         {
             ChannelCommand::Help(body) | ChannelCommand::Usage(body) => {
@@ -843,7 +846,7 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
                     .iter()
                     .rev()
                     .map(|m| {
-                        let ts = m.created_at.format("%H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%H:%M");
                         format!("[{}] {}: {}", ts, m.sender_name, m.content)
                     })
                     .collect();
@@ -873,7 +876,10 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
         .slack_state
         .register_session_channel(session_id, channel_id.clone())
         .await;
-    let approval_cb = make_approval_callback(state.slack_state.clone());
+    // Queue behind other channel messages at [channels] max_concurrent
+    let permit = crate::channels::acquire_agent_permit("Slack", cfg.channels.max_concurrent).await;
+    let approval_cb =
+        make_approval_callback(state.slack_state.clone(), state.config_rx.clone(), permit);
 
     let cancel_token = tokio_util::sync::CancellationToken::new();
     state
@@ -952,7 +958,7 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
             tracing::error!("Slack: agent error: {}", e);
             let token = SlackApiToken::new(SlackApiTokenValue::from(state.bot_token.clone()));
             let session = client.open_session(&token);
-            let error_msg = format!("Error: {}", e);
+            let error_msg = format!("Error: {}", e.user_message());
             let request = SlackApiChatPostMessageRequest::new(
                 SlackChannelId::new(channel_id),
                 SlackMessageContent::new().with_text(error_msg),
@@ -962,10 +968,13 @@ async fn handle_message(msg: &SlackMessageEvent, client: Arc<SlackHyperClient>)
     }
 }
 
-/// Build an `ApprovalCallback` that sends a Slack Block Kit message with 3 buttons
-/// (Yes / Always / No) and waits up to 5 min for a click.
+/// Build an `ApprovalCallback` that sends a Slack Block Kit message with buttons
+/// (Yes / Always / YOLO / No / Stop) and waits up to
+/// `[tools] approval_timeout_secs` for a click, giving up `permit` meanwhile.
 pub(crate) fn make_approval_callback(
     state: Arc<super::SlackState>,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    permit: crate::channels::AgentPermit,
 ) -> crate::brain::agent::ApprovalCallback {
     use crate::brain::agent::ToolApprovalInfo;
     use crate::utils::{check_approval_policy, persist_auto_session_policy};
@@ -973,16 +982,20 @@ pub(crate) fn make_approval_callback(
 
     Arc::new(move |info: ToolApprovalInfo| {
         let state = state.clone();
+        let config_rx = config_rx.clone();
+        let permit = permit.clone();
         Box::pin(async move {
-            if let Some(result) = check_approval_policy() {
-                return Ok(result);
+            if !info.forced
+                && let Some(decision) = check_approval_policy()
+            {
+                return Ok(decision);
             }
 
             let client = match state.client().await {
                 Some(c) => c,
                 None => {
                     tracing::warn!("Slack approval: bot not connected");
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -990,7 +1003,7 @@ pub(crate) fn make_approval_callback(
                 Some(t) => t,
                 None => {
                     tracing::warn!("Slack approval: no bot token");
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -1003,7 +1016,7 @@ pub(crate) fn make_approval_callback(
                             "Slack approval: no channel_id for session {}",
                             info.session_id
                         );
-                        return Ok((false, false));
+                        return Ok(ApprovalDecision::Deny);
                     }
                 },
             };
@@ -1041,11 +1054,17 @@ pub(crate) fn make_approval_callback(
                 SlackBlockPlainTextOnly::from(SlackBlockPlainText::new("❌ No".to_string())),
             )
             .with_style("danger".to_string());
+            let stop_btn = SlackBlockButtonElement::new(
+                SlackActionId::new(format!("stop:{}", approval_id)),
+                SlackBlockPlainTextOnly::from(SlackBlockPlainText::new("🛑 No, stop".to_string())),
+            )
+            .with_style("danger".to_string());
             let actions = SlackBlock::Actions(SlackActionsBlock::new(vec![
                 SlackActionBlockElement::Button(approve_btn),
                 SlackActionBlockElement::Button(always_btn),
                 SlackActionBlockElement::Button(yolo_btn),
                 SlackActionBlockElement::Button(deny_btn),
+                SlackActionBlockElement::Button(stop_btn),
             ]));
 
             let content = SlackMessageContent::new()
@@ -1073,7 +1092,7 @@ pub(crate) fn make_approval_callback(
                 Ok(r) => r,
                 Err(e) => {
                     tracing::error!("Slack approval: failed to send message: {}", e);
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -1083,23 +1102,24 @@ pub(crate) fn make_approval_callback(
                 approval_id
             );
 
-            match tokio::time::timeout(std::time::Duration::from_secs(300), rx).await {
-                Ok(Ok((approved, always))) => {
+            match permit
+                .released_while(crate::utils::await_approval(rx, &config_rx))
+                .await
+            {
+                Some(Ok(decision)) => {
                     tracing::info!(
-                        "Slack approval: user responded id={}, approved={}, always={}",
+                        "Slack approval: user responded id={}, decision={:?}",
                         approval_id,
-                        approved,
-                        always
+                        decision
                     );
-                    if always {
+                    if decision == ApprovalDecision::ApproveAll {
                         persist_auto_session_policy();
                     }
-                    let label = if always {
-                        "🔁 Always approved (session)"
-                    } else if approved {
-                        "✅ Approved"
-                    } else {
-                        "❌ Denied"
+                    let label = match decision {
+                        ApprovalDecision::DenyAndStop => "🛑 Denied — turn stopped",
+                        ApprovalDecision::ApproveAll => "🔁 Always approved (session)",
+                        ApprovalDecision::Approve => "✅ Approved",
+                        ApprovalDecision::Deny => "❌ Denied",
                     };
                     let update = SlackApiChatUpdateRequest::new(
                         SlackChannelId::new(channel_id),
@@ -1107,18 +1127,18 @@ pub(crate) fn make_approval_callback(
                         msg_ts,
                     );
                     let _ = session.chat_update(&update).await;
-                    Ok((approved, always))
+                    Ok(decision)
                 }
-                Ok(Err(_)) => {
+                Some(Err(_)) => {
                     tracing::warn!(
                         "Slack approval: oneshot channel closed (id={})",
                         approval_id
                     );
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
-                Err(_) => {
+                None => {
                     tracing::warn!(
-                        "Slack approval: 5-minute timeout — auto-denying (id={})",
+                        "Slack approval: timed out — auto-denying (id={})",
                         approval_id
                     );
                     let update = SlackApiChatUpdateRequest::new(
@@ -1128,7 +1148,7 @@ pub(crate) fn make_approval_callback(
                         msg_ts,
                     );
                     let _ = session.chat_update(&update).await;
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
             }
         })
@@ -107,6 +107,13 @@ impl TelegramAgent {
             // Per-user session tracking for non-owner users (owner shares TUI session)
             let extra_sessions: Arc<Mutex<HashMap<i64, (Uuid, std::time::Instant)>>> =
                 Arc::new(Mutex::new(HashMap::new()));
+            let idle_sweep = crate::channels::spawn_idle_sweep(
+                "Telegram",
+                extra_sessions.clone(),
+                self.session_service.clone(),
+                self.config_rx.clone(),
+                |c| c.channels.telegram.session_idle_hours,
+            );
             let agent = self.agent_service.clone();
             let session_svc = self.session_service.clone();
             let bot_token = Arc::new(token);
@@ -115,8 +122,8 @@ impl TelegramAgent {
             let config_rx = self.config_rx.clone();
             let channel_msg_repo = self.channel_msg_repo.clone();
 
-            // ── Message handler ───────────────────────────────────────────────
-            let msg_handler = Update::filter_message().endpoint({
+            // ── Message handler (new and edited messages) ─────────────────────
+            let message_endpoint = |edited: bool| {
                 let agent = agent.clone();
                 let session_svc = session_svc.clone();
                 let extra_sessions = extra_sessions.clone();
@@ -142,6 +149,7 @@ impl TelegramAgent {
                             if let Err(e) = handle_message(
                                 bot,
                                 msg,
+                                edited,
                                 agent,
                                 session_svc,
                                 extra_sessions,
@@ -159,7 +167,10 @@ impl TelegramAgent {
                         ResponseResult::Ok(())
                     }
                 }
-            });
+            };
+            let msg_handler = Update::filter_message().endpoint(message_endpoint(false));
+            // Edits are answered as corrections — see `handle_message`
+            let edit_handler = Update::filter_edited_message().endpoint(message_endpoint(true));
 
             // ── Callback query handler (for Approve / Deny inline buttons) ────
             let cb_handler = Update::filter_callback_query().endpoint({
@@ -262,14 +273,7 @@ impl TelegramAgent {
                                     // Determine if caller is owner
                                     let cfg = config_rx.borrow().clone();
                                     let caller_id = query.from.id.0 as i64;
-                                    let owner_id = cfg
-                                        .channels
-                                        .telegram
-                                        .allowed_users
-                                        .first()
-                                        .and_then(|s| s.parse::<i64>().ok());
-                                    let is_owner = cfg.channels.telegram.allowed_users.is_empty()
-                                        || owner_id == Some(caller_id);
+                                    let is_owner = cfg.is_telegram_owner(caller_id);
 
                                     if is_owner {
                                         *shared_session.lock().await = Some(new_id);
@@ -316,15 +320,18 @@ impl TelegramAgent {
                                 return ResponseResult::Ok(());
                             }
 
-                            let (approved, always, yolo, id) =
+                            use crate::brain::agent::ApprovalDecision;
+                            let (decision, yolo, id) =
                                 if let Some(id) = data.strip_prefix("approve:") {
-                                    (true, false, false, id.to_string())
+                                    (ApprovalDecision::Approve, false, id.to_string())
                                 } else if let Some(id) = data.strip_prefix("always:") {
-                                    (true, true, false, id.to_string())
+                                    (ApprovalDecision::ApproveAll, false, id.to_string())
                                 } else if let Some(id) = data.strip_prefix("yolo:") {
-                                    (true, true, true, id.to_string())
+                                    (ApprovalDecision::ApproveAll, true, id.to_string())
                                 } else if let Some(id) = data.strip_prefix("deny:") {
-                                    (false, false, false, id.to_string())
+                                    (ApprovalDecision::Deny, false, id.to_string())
+                                } else if let Some(id) = data.strip_prefix("stop:") {
+                                    (ApprovalDecision::DenyAndStop, false, id.to_string())
                                 } else {
                                     tracing::warn!("Telegram: unknown callback data: {}", data);
                                     let _ = bot.answer_callback_query(&query.id).await;
@@ -336,10 +343,10 @@ impl TelegramAgent {
                                 crate::utils::persist_auto_always_policy();
                             }
 
-                            let resolved = state.resolve_pending_approval(&id, approved, always).await;
+                            let resolved = state.resolve_pending_approval(&id, decision).await;
                             tracing::info!(
-                                "Telegram approval resolved: id={}, approved={}, always={}, found_pending={}",
-                                id, approved, always, resolved
+                                "Telegram approval resolved: id={}, decision={:?}, found_pending={}",
+                                id, decision, resolved
                             );
                             if !resolved {
                                 tracing::warn!(
@@ -351,14 +358,12 @@ impl TelegramAgent {
 
                             // Edit the approval message: keep original context, append outcome, remove buttons
                             if let Some(msg) = &query.message {
-                                let label = if yolo {
-                                    "\n\n🔥 YOLO — always approved"
-                                } else if always {
-                                    "\n\n🔁 Always approved (session)"
-                                } else if approved {
-                                    "\n\n✅ Approved"
-                                } else {
-                                    "\n\n❌ Denied"
+                                let label = match decision {
+                                    _ if yolo => "\n\n🔥 YOLO — always approved",
+                                    ApprovalDecision::DenyAndStop => "\n\n🛑 Denied — turn stopped",
+                                    ApprovalDecision::ApproveAll => "\n\n🔁 Always approved (session)",
+                                    ApprovalDecision::Approve => "\n\n✅ Approved",
+                                    ApprovalDecision::Deny => "\n\n❌ Denied",
                                 };
                                 let original_text = match msg {
                                     teloxide::types::MaybeInaccessibleMessage::Regular(m) => {
@@ -388,9 +393,13 @@ impl TelegramAgent {
                 }
             });
 
-            let tree = dptree::entry().branch(msg_handler).branch(cb_handler);
+            let tree = dptree::entry()
+                .branch(msg_handler)
+                .branch(edit_handler)
+                .branch(cb_handler);
 
             Dispatcher::builder(bot, tree).build().dispatch().await;
+            idle_sweep.abort();
         })
     }
 }
@@ -5,6 +5,9 @@
 
 use super::TelegramState;
 use crate::brain::agent::{AgentService, ProgressCallback, ProgressEvent};
+use crate::channels::MessageSource;
+use crate::channels::image_limits::ImageLimits;
+use crate::channels::voice::SttOptions;
 use crate::config::{Config, RespondTo};
 use crate::db::ChannelMessageRepository;
 use crate::db::models::ChannelMessage as DbChannelMessage;
@@ -82,10 +85,20 @@ impl StreamingState {
     }
 }
 
+/// Process one incoming (or edited) Telegram message.
+///
+/// `edited` is set for `edited_message` updates. Only text edits are
+/// processed; they go to the agent as a new prompt, labelled so the agent
+/// knows it replaces earlier wording:
+/// - an edit of the chat's latest message is a correction — any turn still
+///   running for it is cancelled and the corrected text is answered instead;
+/// - an edit of an older message is answered as a follow-up that quotes
+///   the new wording.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn handle_message(
     bot: Bot,
     msg: Message,
+    edited: bool,
     agent: Arc<AgentService>,
     session_svc: SessionService,
     extra_sessions: Arc<Mutex<HashMap<i64, (Uuid, std::time::Instant)>>>,
@@ -102,12 +115,19 @@ pub(crate) async fn handle_message(
 
     let user_id = user.id.0 as i64;
 
+    // Edits: text only — re-running media or commands on a caption fix is not wanted
+    if edited && msg.text().is_none_or(|t| t.starts_with('/')) {
+        tracing::debug!("Telegram: ignoring non-text edit of message {}", msg.id.0);
+        return Ok(());
+    }
+
     // /start command -- always respond with user ID (for allowlist setup)
     if let Some(text) = msg.text()
         && text.starts_with("/start")
     {
         let reply = format!(
-            "OpenCrabs Telegram Bot\n\nYour user ID: {}\n\nAdd this ID to your config.toml under [channels.telegram] allowed_users to get started.",
+            "{} Telegram Bot\n\nYour user ID: {}\n\nAdd this ID to your config.toml under [channels.telegram] allowed_users to get started.",
+            config_rx.borrow().agent.name,
             user_id
         );
         bot.send_message(msg.chat.id, reply).await?;
@@ -121,6 +141,18 @@ pub(crate) async fn handle_message(
 
     // Read latest config from watch channel — single source of truth
     let cfg = config_rx.borrow().clone();
+
+    // Paused via /pause telegram — tell the owner once, ignore everyone else
+    if crate::channels::is_paused(MessageSource::Telegram) {
+        if cfg.is_telegram_owner(user_id)
+            && crate::channels::take_pause_notice(MessageSource::Telegram)
+        {
+            bot.send_message(msg.chat.id, crate::channels::PAUSED_NOTICE)
+                .await?;
+        }
+        return Ok(());
+    }
+
     let tg_cfg = &cfg.channels.telegram;
     let allowed: HashSet<i64> = tg_cfg
         .allowed_users
@@ -129,7 +161,9 @@ pub(crate) async fn handle_message(
         .collect();
     let respond_to = &tg_cfg.respond_to;
     let allowed_channels: HashSet<String> = tg_cfg.allowed_channels.iter().cloned().collect();
-    let idle_timeout_hours = tg_cfg.session_idle_hours;
+    let idle_timeout = cfg.sessions.channel_idle_timeout(tg_cfg.session_idle_hours);
+    let archive_idle = cfg.sessions.archive_idle;
+    let reply_header = tg_cfg.reply_header.clone().unwrap_or_default();
     let mut voice_config = cfg.voice.clone();
     voice_config.stt_provider = cfg.providers.stt.as_ref().and_then(|s| s.groq.clone());
     let tts_providers = cfg.providers.tts.as_ref();
@@ -140,12 +174,6 @@ pub(crate) async fn handle_message(
     if let Some(ref m) = tts_providers.and_then(|t| t.model.as_ref()) {
         voice_config.tts_model = m.to_string();
     }
-    let openai_tts_key = cfg
-        .providers
-        .tts
-        .as_ref()
-        .and_then(|t| t.openai.as_ref())
-        .and_then(|p| p.api_key.clone());
 
     // Allowlist check — read from config (hot-reloaded via watch channel)
     if !allowed.is_empty() && !allowed.contains(&user_id) {
@@ -313,25 +341,17 @@ pub(crate) async fn handle_message(
             return Ok(());
         }
 
-        let stt_key = match &voice_config.stt_provider {
-            Some(provider) => match &provider.api_key {
-                Some(key) => key.clone(),
-                None => {
-                    tracing::warn!("Telegram: voice note received but no STT API key configured");
-                    bot.send_message(
-                        msg.chat.id,
-                        "Voice transcription not configured (missing API key).",
-                    )
-                    .await?;
-                    return Ok(());
-                }
-            },
-            None => {
-                tracing::warn!("Telegram: voice note received but no STT provider configured");
-                bot.send_message(msg.chat.id, "Voice transcription not configured.")
-                    .await?;
-                return Ok(());
-            }
+        let Some(stt) = crate::channels::voice::build_stt(&voice_config) else {
+            tracing::warn!(
+                "Telegram: voice note received but STT provider '{}' is not configured",
+                voice_config.stt_backend
+            );
+            bot.send_message(
+                msg.chat.id,
+                "Voice transcription not configured (missing API key or model).",
+            )
+            .await?;
+            return Ok(());
         };
 
         tracing::info!(
@@ -367,19 +387,72 @@ pub(crate) async fn handle_message(
             }
         };
 
-        // Transcribe with STT provider
-        match crate::channels::voice::transcribe_audio(audio_bytes, &stt_key).await {
+        // Acknowledge right away — long notes take a while to transcribe
+        let status = bot
+            .send_message(
+                msg.chat.id,
+                format!("🎙️ Transcribing {}s of audio…", voice.duration),
+            )
+            .await
+            .ok();
+
+        // Stream partial text into the status message when the backend can
+        let (partial_tx, mut partial_rx) = tokio::sync::watch::channel(String::new());
+        let preview = match status {
+            Some(ref status) if stt.supports_streaming() => {
+                let (bot, chat, mid) = (bot.clone(), msg.chat.id, status.id);
+                Some(tokio::spawn(async move {
+                    while partial_rx.changed().await.is_ok() {
+                        let text = partial_rx.borrow_and_update().clone();
+                        let display = format!("🎙️ {}…", truncate_str(&text, 3900));
+                        let _ = bot.edit_message_text(chat, mid, display).await;
+                        // Stay under Telegram's edit rate limit
+                        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+                    }
+                }))
+            }
+            _ => None,
+        };
+
+        let options = SttOptions::for_user(&voice_config, &user_id.to_string());
+        let result = stt
+            .transcribe_streaming(audio_bytes, &options, &|text: &str| {
+                let _ = partial_tx.send(text.to_string());
+            })
+            .await;
+        drop(partial_tx);
+        if let Some(preview) = preview {
+            let _ = preview.await;
+        }
+
+        match result {
             Ok(transcript) => {
                 tracing::info!(
                     "Telegram: transcribed voice: {}",
                     truncate_str(&transcript, 80)
                 );
+                if let Some(status) = status {
+                    let _ = bot
+                        .edit_message_text(
+                            msg.chat.id,
+                            status.id,
+                            format!("🎙️ {}", truncate_str(&transcript, 4000)),
+                        )
+                        .await;
+                }
                 (transcript, true)
             }
             Err(e) => {
                 tracing::error!("Telegram: STT error: {}", e);
-                bot.send_message(msg.chat.id, format!("Transcription error: {}", e))
-                    .await?;
+                let error = format!("Transcription error: {}", e);
+                match status {
+                    Some(status) => {
+                        let _ = bot.edit_message_text(msg.chat.id, status.id, error).await;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, error).await?;
+                    }
+                }
                 return Ok(());
             }
         }
@@ -396,6 +469,14 @@ pub(crate) async fn handle_message(
             photo.height,
         );
 
+        // Albums arrive one photo per message, so only the size cap applies here
+        let mut images = ImageLimits::from_config(&cfg.channels).budget("Telegram");
+        if !images.admit("photo", Some(photo.file.size as u64)) {
+            let note = images.note().unwrap_or_default();
+            bot.send_message(msg.chat.id, note).await?;
+            return Ok(());
+        }
+
         let file = bot.get_file(&photo.file.id).await?;
         let download_url = format!(
             "https://api.telegram.org/file/bot{}/{}",
@@ -455,6 +536,14 @@ pub(crate) async fn handle_message(
             mime
         );
 
+        // Reject oversized images before downloading them
+        let mut images = ImageLimits::from_config(&cfg.channels).budget("Telegram");
+        if mime.starts_with("image/") && !images.admit(fname, Some(doc.file.size as u64)) {
+            let note = images.note().unwrap_or_default();
+            bot.send_message(msg.chat.id, note).await?;
+            return Ok(());
+        }
+
         let file = bot.get_file(&doc.file.id).await?;
         let download_url = format!(
             "https://api.telegram.org/file/bot{}/{}",
@@ -482,6 +571,13 @@ pub(crate) async fn handle_message(
 
         use crate::utils::{FileContent, classify_file};
         match classify_file(&bytes, mime, fname) {
+            FileContent::Image
+                if !mime.starts_with("image/")
+                    && !images.admit(fname, Some(bytes.len() as u64)) =>
+            {
+                // Image with a generic MIME type: only caught after download
+                (images.note().unwrap_or_default(), false)
+            }
             FileContent::Image => {
                 let tmp_path =
                     std::env::temp_dir().join(format!("tg_doc_{}.{}", Uuid::new_v4(), ext));
@@ -575,12 +671,8 @@ pub(crate) async fn handle_message(
     });
 
     // Resolve session: owner shares the TUI session, other users get their own.
-    // Owner = first user in the config's allowed_users list (Vec order, not HashSet).
-    let owner_id = tg_cfg
-        .allowed_users
-        .first()
-        .and_then(|s| s.parse::<i64>().ok());
-    let is_owner = allowed.is_empty() || owner_id == Some(user_id);
+    // Owner = [owner].telegram_id, else first user in allowed_users (Vec order, not HashSet).
+    let is_owner = cfg.is_telegram_owner(user_id);
 
     tracing::info!(
         "Telegram: session resolve — is_owner={}, is_dm={}, chat=\"{}\" ({}), user={} ({})",
@@ -638,10 +730,10 @@ pub(crate) async fn handle_message(
         // Non-owner users get their own separate sessions
         let mut map = extra_sessions.lock().await;
         if let Some((old_id, last_activity)) = map.get(&user_id).copied() {
-            if idle_timeout_hours
-                .is_some_and(|h| last_activity.elapsed().as_secs() > (h * 3600.0) as u64)
-            {
-                let _ = session_svc.archive_session(old_id).await;
+            if idle_timeout.is_some_and(|t| last_activity.elapsed() > t) {
+                if archive_idle {
+                    let _ = session_svc.archive_session(old_id).await;
+                }
                 map.remove(&user_id);
                 let title = format!("Telegram: {}", user.first_name);
                 match session_svc.create_session(Some(title)).await {
@@ -691,10 +783,38 @@ pub(crate) async fn handle_message(
         .register_session_chat(session_id, msg.chat.id.0)
         .await;
 
+    let newest_before = telegram_state
+        .note_user_message(msg.chat.id.0, msg.id.0)
+        .await;
+    let text = if !edited {
+        text
+    } else if newest_before == Some(msg.id.0) {
+        // Supersede the turn for the original wording if it's still running
+        if telegram_state.cancel_session(session_id).await {
+            tracing::info!("Telegram: message edited mid-turn — cancelled the original turn");
+        }
+        format!(
+            "[Correction — the user edited their last message. Answer this version instead:]\n{text}"
+        )
+    } else {
+        format!("[The user edited an earlier message to:]\n{text}")
+    };
+
     // ── Channel commands (/help, /usage, /models) ──────────────────────────
     let mut text = text;
     if !is_voice {
-        use crate::channels::commands::{self, ChannelCommand};
+        use crate::channels::commands::{self, AllowlistChannel, ChannelCommand};
+        if let Some(reply) = commands::handle_allowlist_command(
+            &text,
+            AllowlistChannel::Telegram,
+            &cfg,
+            &user_id.to_string(),
+        ) {
+            bot.send_message(msg.chat.id, md_to_html(&reply))
+                .parse_mode(ParseMode::Html)
+                .await?;
+            return Ok(());
+        }
         match commands::handle_command(&text, session_id, &agent, &session_svc).await {
             ChannelCommand::Help(body) | ChannelCommand::Usage(body) => {
                 bot.send_message(msg.chat.id, md_to_html(&body))
@@ -872,7 +992,7 @@ pub(crate) async fn handle_message(
                     .iter()
                     .rev() // oldest first
                     .map(|m| {
-                        let ts = m.created_at.format("%H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%H:%M");
                         format!("[{}] {}: {}", ts, m.sender_name, m.content)
                     })
                     .collect();
@@ -1059,10 +1179,13 @@ pub(crate) async fn handle_message(
         })
     };
 
-    // Build Telegram-native approval callback for this session
-    let approval_cb = make_approval_callback(telegram_state.clone());
-
     // ── Agent call ────────────────────────────────────────────────────────────
+    let permit =
+        crate::channels::acquire_agent_permit("Telegram", cfg.channels.max_concurrent).await;
+
+    // Build Telegram-native approval callback for this session
+    let approval_cb =
+        make_approval_callback(telegram_state.clone(), config_rx.clone(), permit.clone());
     let cancel_token = tokio_util::sync::CancellationToken::new();
     telegram_state
         .store_cancel_token(session_id, cancel_token.clone())
@@ -1097,7 +1220,8 @@ pub(crate) async fn handle_message(
                     telegram_state
                         .register_session_chat(new_id, msg.chat.id.0)
                         .await;
-                    let approval_cb2 = make_approval_callback(telegram_state.clone());
+                    let approval_cb2 =
+                        make_approval_callback(telegram_state.clone(), config_rx.clone(), permit);
                     let cancel_token2 = tokio_util::sync::CancellationToken::new();
                     telegram_state
                         .store_cancel_token(new_id, cancel_token2.clone())
@@ -1169,7 +1293,10 @@ pub(crate) async fn handle_message(
             }
 
             // Send final response as a clean separate message
-            let html = markdown_to_telegram_html(&text_only);
+            let html = markdown_to_telegram_html(&crate::channels::with_reply_header(
+                &reply_header,
+                &text_only,
+            ));
             if !html.is_empty() {
                 for chunk in split_message(&html, 4096) {
                     match bot
@@ -1206,16 +1333,9 @@ pub(crate) async fn handle_message(
             // If input was voice AND TTS is enabled, also send voice note after text
             if is_voice
                 && voice_config.tts_enabled
-                && let Some(ref oai_key) = openai_tts_key
+                && let Some(tts) = crate::channels::voice::build_tts(&voice_config)
             {
-                match crate::channels::voice::synthesize_speech(
-                    &response.content,
-                    oai_key,
-                    &voice_config.tts_voice,
-                    &voice_config.tts_model,
-                )
-                .await
-                {
+                match tts.synthesize(&response.content).await {
                     Ok(audio_bytes) => {
                         bot.send_voice(msg.chat.id, InputFile::memory(audio_bytes))
                             .await?;
@@ -1231,10 +1351,10 @@ pub(crate) async fn handle_message(
             // If a streaming message was started, edit it to show the error
             if let Some(mid) = streaming_msg_id {
                 let _ = bot
-                    .edit_message_text(msg.chat.id, mid, format!("Error: {}", e))
+                    .edit_message_text(msg.chat.id, mid, format!("Error: {}", e.user_message()))
                     .await;
             } else {
-                bot.send_message(msg.chat.id, format!("Error: {}", e))
+                bot.send_message(msg.chat.id, format!("Error: {}", e.user_message()))
                     .await?;
             }
         }
@@ -1276,7 +1396,9 @@ fn tool_context(name: &str, input: &serde_json::Value) -> String {
             .get("command")
             .and_then(|v| v.as_str())
             .map(String::from),
-        "read" | "write" | "edit" => safe.get("path").and_then(|v| v.as_str()).map(String::from),
+        "read" | "write" | "edit" | "apply_patch" => {
+            safe.get("path").and_then(|v| v.as_str()).map(String::from)
+        }
         "glob" => safe
             .get("pattern")
             .and_then(|v| v.as_str())
@@ -1285,7 +1407,7 @@ fn tool_context(name: &str, input: &serde_json::Value) -> String {
             .get("pattern")
             .and_then(|v| v.as_str())
             .map(String::from),
-        "ls" => safe.get("path").and_then(|v| v.as_str()).map(String::from),
+        "ls" | "list_files" => safe.get("path").and_then(|v| v.as_str()).map(String::from),
         "http_request" | "web_fetch" => safe.get("url").and_then(|v| v.as_str()).map(String::from),
         "brave_search" | "exa_search" | "web_search" | "memory_search" | "session_search" => {
             safe.get("query").and_then(|v| v.as_str()).map(String::from)
@@ -1520,11 +1642,14 @@ pub(crate) fn split_message(text: &str, max_len: usize) -> Vec<&str> {
 }
 
 /// Build an `ApprovalCallback` that sends an inline-keyboard message to Telegram
-/// and waits (up to 5 min) for the user to tap Yes, Always, or No.
+/// and waits (up to `[tools] approval_timeout_secs`) for the user to tap
+/// Yes, Always, YOLO, No, or No-and-stop. `permit` is given up while waiting.
 pub(crate) fn make_approval_callback(
     state: Arc<super::TelegramState>,
+    config_rx: tokio::sync::watch::Receiver<Config>,
+    permit: crate::channels::AgentPermit,
 ) -> crate::brain::agent::ApprovalCallback {
-    use crate::brain::agent::ToolApprovalInfo;
+    use crate::brain::agent::{ApprovalDecision, ToolApprovalInfo};
     use crate::utils::{check_approval_policy, persist_auto_session_policy};
     use teloxide::payloads::SendMessageSetters;
     use teloxide::prelude::Requester;
@@ -1533,10 +1658,14 @@ pub(crate) fn make_approval_callback(
 
     Arc::new(move |info: ToolApprovalInfo| {
         let state = state.clone();
+        let config_rx = config_rx.clone();
+        let permit = permit.clone();
         Box::pin(async move {
             // Respect config-level approval policy (single source of truth)
-            if let Some(result) = check_approval_policy() {
-                return Ok(result);
+            if !info.forced
+                && let Some(decision) = check_approval_policy()
+            {
+                return Ok(decision);
             }
 
             // Find the chat this session is active in
@@ -1549,7 +1678,7 @@ pub(crate) fn make_approval_callback(
                             "Telegram approval: no chat_id for session {}",
                             info.session_id
                         );
-                        return Ok((false, false));
+                        return Ok(ApprovalDecision::Deny);
                     }
                 },
             };
@@ -1558,7 +1687,7 @@ pub(crate) fn make_approval_callback(
                 Some(b) => b,
                 None => {
                     tracing::warn!("Telegram approval: bot not connected");
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             };
 
@@ -1581,6 +1710,10 @@ pub(crate) fn make_approval_callback(
                     ),
                     InlineKeyboardButton::callback("❌ No", format!("deny:{}", approval_id)),
                 ],
+                vec![InlineKeyboardButton::callback(
+                    "🛑 No, stop this turn",
+                    format!("stop:{}", approval_id),
+                )],
             ]);
 
             // Format message — redact secrets before display, truncate to fit Telegram limit
@@ -1623,37 +1756,38 @@ pub(crate) fn make_approval_callback(
                 }
                 Err(e) => {
                     tracing::error!("Telegram approval: failed to send message: {}", e);
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
             }
 
-            // Wait up to 5 minutes
-            match tokio::time::timeout(std::time::Duration::from_secs(300), rx).await {
-                Ok(Ok((approved, always))) => {
+            match permit
+                .released_while(crate::utils::await_approval(rx, &config_rx))
+                .await
+            {
+                Some(Ok(decision)) => {
                     tracing::info!(
-                        "Telegram approval: user responded id={}, approved={}, always={}",
+                        "Telegram approval: user responded id={}, decision={:?}",
                         approval_id,
-                        approved,
-                        always
+                        decision
                     );
-                    if always {
+                    if decision == ApprovalDecision::ApproveAll {
                         persist_auto_session_policy();
                     }
-                    Ok((approved, always))
+                    Ok(decision)
                 }
-                Ok(Err(_)) => {
+                Some(Err(_)) => {
                     tracing::warn!(
                         "Telegram approval: oneshot channel closed (id={})",
                         approval_id
                     );
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
-                Err(_) => {
+                None => {
                     tracing::warn!(
-                        "Telegram approval: 5-minute timeout — auto-denying (id={})",
+                        "Telegram approval: timed out — auto-denying (id={})",
                         approval_id
                     );
-                    Ok((false, false))
+                    Ok(ApprovalDecision::Deny)
                 }
             }
         })
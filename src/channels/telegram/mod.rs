@@ -8,6 +8,7 @@ pub(crate) mod handler;
 
 pub use agent::TelegramAgent;
 
+use crate::brain::agent::ApprovalDecision;
 use std::collections::HashMap;
 use teloxide::prelude::Bot;
 use tokio::sync::{Mutex, oneshot};
@@ -28,9 +29,12 @@ pub struct TelegramState {
     /// Maps session_id → Telegram chat_id for approval routing
     session_chats: Mutex<HashMap<Uuid, i64>>,
     /// Pending approval channels: approval_id → oneshot sender of (approved, always).
-    pending_approvals: Mutex<HashMap<String, oneshot::Sender<(bool, bool)>>>,
+    pending_approvals: Mutex<HashMap<String, oneshot::Sender<ApprovalDecision>>>,
     /// Per-session cancel tokens for aborting in-flight agent tasks via /stop
     cancel_tokens: Mutex<HashMap<Uuid, CancellationToken>>,
+    /// Newest user message id processed per chat — tells edits of the latest
+    /// message (corrections) apart from edits of older ones
+    last_user_messages: Mutex<HashMap<i64, i32>>,
 }
 
 impl Default for TelegramState {
@@ -48,6 +52,7 @@ impl TelegramState {
             session_chats: Mutex::new(HashMap::new()),
             pending_approvals: Mutex::new(HashMap::new()),
             cancel_tokens: Mutex::new(HashMap::new()),
+            last_user_messages: Mutex::new(HashMap::new()),
         }
     }
 
@@ -97,16 +102,19 @@ impl TelegramState {
     }
 
     /// Register a pending approval channel by id.
-    pub async fn register_pending_approval(&self, id: String, tx: oneshot::Sender<(bool, bool)>) {
+    pub async fn register_pending_approval(
+        &self,
+        id: String,
+        tx: oneshot::Sender<ApprovalDecision>,
+    ) {
         self.pending_approvals.lock().await.insert(id, tx);
     }
 
     /// Resolve a pending approval.
-    /// `approved` — whether tool is allowed; `always` — auto-approve all future tools.
     /// Returns true if a pending approval existed.
-    pub async fn resolve_pending_approval(&self, id: &str, approved: bool, always: bool) -> bool {
+    pub async fn resolve_pending_approval(&self, id: &str, decision: ApprovalDecision) -> bool {
         if let Some(tx) = self.pending_approvals.lock().await.remove(id) {
-            let _ = tx.send((approved, always));
+            let _ = tx.send(decision);
             true
         } else {
             false
@@ -132,4 +140,33 @@ impl TelegramState {
     pub async fn remove_cancel_token(&self, session_id: Uuid) {
         self.cancel_tokens.lock().await.remove(&session_id);
     }
+
+    /// Record that `message_id` in `chat_id` is being processed and return
+    /// the newest id seen before it. Older ids (edits) don't move it back.
+    pub async fn note_user_message(&self, chat_id: i64, message_id: i32) -> Option<i32> {
+        let mut last = self.last_user_messages.lock().await;
+        let previous = last.get(&chat_id).copied();
+        if previous.is_none_or(|prev| message_id > prev) {
+            last.insert(chat_id, message_id);
+        }
+        previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_note_user_message_tracks_newest() {
+        let state = TelegramState::new();
+        assert_eq!(state.note_user_message(1, 10).await, None);
+        assert_eq!(state.note_user_message(1, 11).await, Some(10));
+        // Edit of the latest message: previous newest is the edited one
+        assert_eq!(state.note_user_message(1, 11).await, Some(11));
+        // Edit of an older message doesn't rewind
+        assert_eq!(state.note_user_message(1, 10).await, Some(11));
+        assert_eq!(state.note_user_message(1, 12).await, Some(11));
+        assert_eq!(state.note_user_message(2, 5).await, None);
+    }
 }
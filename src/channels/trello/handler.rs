@@ -183,7 +183,7 @@ pub async fn process_comment(
 
     // Trello is poll-based with no interactive approval UI — auto-approve all tools.
     let approval_cb: crate::brain::agent::ApprovalCallback =
-        Arc::new(|_info| Box::pin(async { Ok((true, false)) }));
+        Arc::new(|_info| Box::pin(async { Ok(crate::brain::agent::ApprovalDecision::Approve) }));
 
     let response = match agent
         .send_message_with_tools_and_callback(
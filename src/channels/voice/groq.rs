@@ -0,0 +1,105 @@
+//! Groq Whisper speech-to-text (whisper-large-v3-turbo), the default STT backend.
+
+use super::provider::{SttOptions, SttProvider};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+const GROQ_TRANSCRIPTION_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
+
+pub struct GroqStt {
+    api_key: String,
+    url: String,
+}
+
+impl GroqStt {
+    pub fn new(api_key: String) -> Self {
+        Self::with_url(api_key, GROQ_TRANSCRIPTION_URL.to_string())
+    }
+
+    /// Point at a different endpoint (for testing).
+    pub(super) fn with_url(api_key: String, url: String) -> Self {
+        Self { api_key, url }
+    }
+}
+
+#[async_trait]
+impl SttProvider for GroqStt {
+    fn name(&self) -> &str {
+        "groq"
+    }
+
+    async fn transcribe(&self, audio: Vec<u8>, options: &SttOptions) -> Result<String> {
+        let client = Client::new();
+
+        let file_part = reqwest::multipart::Part::bytes(audio)
+            .file_name("voice.ogg")
+            .mime_str("audio/ogg")?;
+
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", file_part)
+            .text("model", "whisper-large-v3-turbo")
+            .text("response_format", "json");
+        if let Some(ref language) = options.language {
+            form = form.text("language", language.clone());
+        }
+        if let Some(ref prompt) = options.prompt {
+            form = form.text("prompt", prompt.clone());
+        }
+
+        let response = client
+            .post(&self.url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .multipart(form)
+            .send()
+            .await
+            .context("Failed to send audio to Groq Whisper")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Groq STT error ({}): {}", status, error_text);
+        }
+
+        let result: TranscriptionResponse = response
+            .json()
+            .await
+            .context("Failed to parse Groq transcription response")?;
+
+        tracing::info!("Groq STT: transcribed {} chars", result.text.len());
+
+        Ok(result.text)
+    }
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcription_response_parse() {
+        let json = r#"{"text": "Hello, this is a test."}"#;
+        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(result.text, "Hello, this is a test.");
+    }
+
+    #[test]
+    fn test_transcription_response_parse_unicode() {
+        let json = r#"{"text": "Olá, como você está?"}"#;
+        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(result.text, "Olá, como você está?");
+    }
+
+    #[test]
+    fn test_transcription_response_parse_empty() {
+        let json = r#"{"text": ""}"#;
+        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(result.text, "");
+    }
+}
@@ -1,7 +1,17 @@
 //! Voice Processing Module
 //!
-//! Speech-to-text (Groq Whisper) and text-to-speech (OpenAI TTS) services.
+//! Speech-to-text and text-to-speech behind pluggable [`SttProvider`] /
+//! [`TtsProvider`] backends: Groq Whisper and OpenAI TTS by default, or
+//! whisper.cpp for local transcription.
 
+mod groq;
+mod openai_tts;
+mod provider;
 mod service;
+mod whisper_cpp;
 
-pub use service::{synthesize_speech, transcribe_audio};
+pub use groq::GroqStt;
+pub use openai_tts::OpenAiTts;
+pub use provider::{SttOptions, SttProvider, TtsProvider, build_stt, build_tts};
+pub use service::{synthesize_speech, transcribe_audio, transcribe_audio_with_options};
+pub use whisper_cpp::WhisperCppStt;
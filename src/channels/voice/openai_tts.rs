@@ -0,0 +1,79 @@
+//! OpenAI text-to-speech, the default TTS backend.
+
+use super::provider::TtsProvider;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+
+const OPENAI_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+
+pub struct OpenAiTts {
+    api_key: String,
+    voice: String,
+    model: String,
+    url: String,
+}
+
+impl OpenAiTts {
+    pub fn new(api_key: String, voice: String, model: String) -> Self {
+        Self::with_url(api_key, voice, model, OPENAI_SPEECH_URL.to_string())
+    }
+
+    /// Point at a different endpoint (for testing).
+    pub(super) fn with_url(api_key: String, voice: String, model: String, url: String) -> Self {
+        Self {
+            api_key,
+            voice,
+            model,
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl TtsProvider for OpenAiTts {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<Vec<u8>> {
+        let client = Client::new();
+
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": text,
+            "voice": self.voice,
+            "response_format": "opus",
+        });
+
+        let response = client
+            .post(&self.url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to send TTS request to OpenAI")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("OpenAI TTS error ({}): {}", status, error_text);
+        }
+
+        let audio_bytes = response
+            .bytes()
+            .await
+            .context("Failed to read TTS audio bytes")?
+            .to_vec();
+
+        tracing::info!(
+            "OpenAI TTS: generated {} bytes of audio (voice={}, model={})",
+            audio_bytes.len(),
+            self.voice,
+            self.model,
+        );
+
+        Ok(audio_bytes)
+    }
+}
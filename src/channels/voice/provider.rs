@@ -0,0 +1,267 @@
+//! Voice Providers
+//!
+//! `SttProvider` / `TtsProvider` traits and the factory that picks a backend
+//! from `[voice] stt_provider` / `tts_provider`. Groq Whisper and OpenAI TTS
+//! are the defaults; `whisper_cpp` transcribes locally so audio never leaves
+//! the machine.
+
+use super::groq::GroqStt;
+use super::openai_tts::OpenAiTts;
+use super::whisper_cpp::WhisperCppStt;
+use crate::config::VoiceConfig;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Speech-to-text backend.
+#[async_trait]
+pub trait SttProvider: Send + Sync {
+    /// Backend name, as written in `[voice] stt_provider`
+    fn name(&self) -> &str;
+
+    /// Transcribe OGG/Opus audio (voice note format) to text.
+    async fn transcribe(&self, audio: Vec<u8>, options: &SttOptions) -> Result<String>;
+
+    /// Whether [`transcribe_streaming`](Self::transcribe_streaming) reports
+    /// partial text before it finishes.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Transcribe, calling `on_partial` with the text so far as it grows.
+    /// Backends that can't stream report nothing and just return the result.
+    async fn transcribe_streaming(
+        &self,
+        audio: Vec<u8>,
+        options: &SttOptions,
+        on_partial: &(dyn Fn(&str) + Send + Sync),
+    ) -> Result<String> {
+        let _ = on_partial;
+        self.transcribe(audio, options).await
+    }
+}
+
+/// Per-request transcription hints.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SttOptions {
+    /// ISO 639-1 language code; `None` lets the model auto-detect
+    pub language: Option<String>,
+    /// Text that biases vocabulary (names, jargon the user uses)
+    pub prompt: Option<String>,
+}
+
+impl SttOptions {
+    /// Hints for `user` (channel user ID or phone): their entry in
+    /// `[voice.stt_user_languages]`, else `[voice] stt_language`, plus `stt_prompt`.
+    pub fn for_user(cfg: &VoiceConfig, user: &str) -> Self {
+        let user = user.trim_start_matches('+');
+        let language = cfg
+            .stt_user_languages
+            .iter()
+            .find(|(id, _)| id.trim_start_matches('+') == user)
+            .map(|(_, lang)| lang)
+            .or(cfg.stt_language.as_ref());
+        let non_empty = |s: &String| {
+            let s = s.trim();
+            (!s.is_empty()).then(|| s.to_string())
+        };
+        Self {
+            language: language.and_then(non_empty).map(|l| l.to_lowercase()),
+            prompt: cfg.stt_prompt.as_ref().and_then(non_empty),
+        }
+    }
+}
+
+/// Text-to-speech backend.
+#[async_trait]
+pub trait TtsProvider: Send + Sync {
+    /// Backend name, as written in `[voice] tts_provider`
+    fn name(&self) -> &str;
+
+    /// Synthesize `text` to OGG/Opus audio suitable for voice notes.
+    async fn synthesize(&self, text: &str) -> Result<Vec<u8>>;
+}
+
+/// The configured STT backend, or `None` when it isn't usable
+/// (unknown name, missing API key or model).
+pub fn build_stt(cfg: &VoiceConfig) -> Option<Box<dyn SttProvider>> {
+    match cfg.stt_backend.as_str() {
+        "groq" => {
+            let key = cfg.stt_provider.as_ref()?.api_key.clone()?;
+            Some(Box::new(GroqStt::new(key)))
+        }
+        "whisper_cpp" => {
+            let Some(ref model) = cfg.whisper_cpp_model else {
+                tracing::warn!("Voice: stt_provider = \"whisper_cpp\" needs whisper_cpp_model");
+                return None;
+            };
+            Some(Box::new(WhisperCppStt::new(
+                cfg.whisper_cpp_binary.clone(),
+                crate::config::expand_tilde(std::path::Path::new(model)),
+            )))
+        }
+        other => {
+            tracing::warn!("Voice: unknown stt_provider '{}'", other);
+            None
+        }
+    }
+}
+
+/// The configured TTS backend, or `None` when it isn't usable. The voice
+/// comes from the persona (see [`persona_voice`]), else `[voice] tts_voice`.
+pub fn build_tts(cfg: &VoiceConfig) -> Option<Box<dyn TtsProvider>> {
+    match cfg.tts_backend.as_str() {
+        "openai" => {
+            let key = cfg.tts_provider.as_ref()?.api_key.clone()?;
+            let voice = persona_voice(&crate::brain::BrainLoader::resolve_path())
+                .unwrap_or_else(|| cfg.tts_voice.clone());
+            Some(Box::new(OpenAiTts::new(key, voice, cfg.tts_model.clone())))
+        }
+        other => {
+            tracing::warn!("Voice: unknown tts_provider '{}'", other);
+            None
+        }
+    }
+}
+
+/// Voice set by the agent's persona: a `tts_voice:` key in the front-matter
+/// of `SOUL.md` in the brain workspace.
+pub fn persona_voice(brain_path: &Path) -> Option<String> {
+    let soul = std::fs::read_to_string(brain_path.join("SOUL.md")).ok()?;
+    front_matter_value(&soul, "tts_voice")
+}
+
+/// `key: value` from a `---`-fenced front-matter block at the top of `content`
+fn front_matter_value(content: &str, key: &str) -> Option<String> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    lines
+        .take_while(|line| line.trim() != "---")
+        .filter_map(|line| line.split_once(':'))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+
+    fn with_key(key: &str) -> Option<ProviderConfig> {
+        Some(ProviderConfig {
+            api_key: Some(key.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_defaults_need_api_keys() {
+        let mut cfg = VoiceConfig::default();
+        assert!(build_stt(&cfg).is_none());
+        assert!(build_tts(&cfg).is_none());
+
+        cfg.stt_provider = with_key("gsk-test");
+        cfg.tts_provider = with_key("sk-test");
+        assert_eq!(build_stt(&cfg).unwrap().name(), "groq");
+        assert_eq!(build_tts(&cfg).unwrap().name(), "openai");
+    }
+
+    #[test]
+    fn test_persona_voice_from_front_matter() {
+        let soul = "---\nname: serious\ntts_voice: \"onyx\"\n---\n# SOUL\ntts_voice: nova\n";
+        assert_eq!(
+            front_matter_value(soul, "tts_voice").as_deref(),
+            Some("onyx")
+        );
+        assert_eq!(front_matter_value(soul, "missing"), None);
+        assert_eq!(
+            front_matter_value("# SOUL\ntts_voice: nova\n", "tts_voice"),
+            None
+        );
+        assert_eq!(
+            front_matter_value("---\ntts_voice:\n---\n", "tts_voice"),
+            None
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(persona_voice(dir.path()), None);
+        std::fs::write(dir.path().join("SOUL.md"), soul).unwrap();
+        assert_eq!(persona_voice(dir.path()).as_deref(), Some("onyx"));
+    }
+
+    #[test]
+    fn test_whisper_cpp_needs_model() {
+        let mut cfg = VoiceConfig {
+            stt_backend: "whisper_cpp".to_string(),
+            ..Default::default()
+        };
+        assert!(build_stt(&cfg).is_none());
+
+        cfg.whisper_cpp_model = Some("/models/ggml-base.bin".to_string());
+        assert_eq!(build_stt(&cfg).unwrap().name(), "whisper_cpp");
+    }
+
+    #[test]
+    fn test_stt_options_for_user() {
+        let mut cfg = VoiceConfig {
+            stt_language: Some("PT".to_string()),
+            stt_prompt: Some("OpenCrabs, ratatui, tokio".to_string()),
+            ..Default::default()
+        };
+        cfg.stt_user_languages
+            .insert("+15551234567".to_string(), "de".to_string());
+
+        let opts = SttOptions::for_user(&cfg, "15551234567");
+        assert_eq!(opts.language.as_deref(), Some("de"));
+        assert_eq!(opts.prompt.as_deref(), Some("OpenCrabs, ratatui, tokio"));
+        assert_eq!(
+            SttOptions::for_user(&cfg, "42").language.as_deref(),
+            Some("pt")
+        );
+        assert_eq!(
+            SttOptions::for_user(&VoiceConfig::default(), "42"),
+            SttOptions::default()
+        );
+    }
+
+    struct FixedStt;
+
+    #[async_trait]
+    impl SttProvider for FixedStt {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn transcribe(&self, _audio: Vec<u8>, _options: &SttOptions) -> Result<String> {
+            Ok("hello".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_falls_back_to_transcribe() {
+        let partials = std::sync::Mutex::new(Vec::new());
+        let stt = FixedStt;
+        assert!(!stt.supports_streaming());
+        let text = stt
+            .transcribe_streaming(Vec::new(), &SttOptions::default(), &|p: &str| {
+                partials.lock().unwrap().push(p.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(text, "hello");
+        assert!(partials.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unknown_backend() {
+        let cfg = VoiceConfig {
+            stt_backend: "nope".to_string(),
+            stt_provider: with_key("gsk-test"),
+            ..Default::default()
+        };
+        assert!(build_stt(&cfg).is_none());
+    }
+}
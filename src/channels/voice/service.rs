@@ -1,62 +1,43 @@
 //! Voice Processing Module
 //!
-//! Speech-to-text (Groq Whisper) and text-to-speech (OpenAI TTS) services
-//! used by the Telegram bot for voice note support.
+//! Speech-to-text (Groq Whisper) and text-to-speech (OpenAI TTS) entry points
+//! kept for callers that hold an API key directly. They are thin wrappers
+//! over the default [`SttProvider`] / [`TtsProvider`] backends.
 
-use anyhow::{Context, Result};
-use reqwest::Client;
-use serde::Deserialize;
-
-const GROQ_TRANSCRIPTION_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
-const OPENAI_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+use super::groq::GroqStt;
+use super::openai_tts::OpenAiTts;
+use super::provider::{SttOptions, SttProvider, TtsProvider};
+use anyhow::Result;
 
 /// Transcribe audio bytes using Groq Whisper (whisper-large-v3-turbo).
 ///
 /// Accepts OGG/Opus audio (Telegram voice note format).
 /// Returns the transcribed text.
 pub async fn transcribe_audio(audio_bytes: Vec<u8>, groq_api_key: &str) -> Result<String> {
-    transcribe_audio_with_url(audio_bytes, groq_api_key, GROQ_TRANSCRIPTION_URL).await
+    transcribe_audio_with_options(audio_bytes, groq_api_key, &SttOptions::default()).await
+}
+
+/// Like [`transcribe_audio`], with a language hint and/or vocabulary prompt.
+pub async fn transcribe_audio_with_options(
+    audio_bytes: Vec<u8>,
+    groq_api_key: &str,
+    options: &SttOptions,
+) -> Result<String> {
+    GroqStt::new(groq_api_key.to_string())
+        .transcribe(audio_bytes, options)
+        .await
 }
 
 /// Internal: transcribe with configurable URL (for testing).
+#[cfg(test)]
 async fn transcribe_audio_with_url(
     audio_bytes: Vec<u8>,
     api_key: &str,
     url: &str,
 ) -> Result<String> {
-    let client = Client::new();
-
-    let file_part = reqwest::multipart::Part::bytes(audio_bytes)
-        .file_name("voice.ogg")
-        .mime_str("audio/ogg")?;
-
-    let form = reqwest::multipart::Form::new()
-        .part("file", file_part)
-        .text("model", "whisper-large-v3-turbo")
-        .text("response_format", "json");
-
-    let response = client
-        .post(url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send()
-        .await
-        .context("Failed to send audio to Groq Whisper")?;
-
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("Groq STT error ({}): {}", status, error_text);
-    }
-
-    let result: TranscriptionResponse = response
-        .json()
+    GroqStt::with_url(api_key.to_string(), url.to_string())
+        .transcribe(audio_bytes, &SttOptions::default())
         .await
-        .context("Failed to parse Groq transcription response")?;
-
-    tracing::info!("Groq STT: transcribed {} chars", result.text.len());
-
-    Ok(result.text)
 }
 
 /// Synthesize speech from text using OpenAI TTS.
@@ -68,10 +49,17 @@ pub async fn synthesize_speech(
     voice: &str,
     model: &str,
 ) -> Result<Vec<u8>> {
-    synthesize_speech_with_url(text, openai_api_key, voice, model, OPENAI_SPEECH_URL).await
+    OpenAiTts::new(
+        openai_api_key.to_string(),
+        voice.to_string(),
+        model.to_string(),
+    )
+    .synthesize(text)
+    .await
 }
 
 /// Internal: synthesize with configurable URL (for testing).
+#[cfg(test)]
 async fn synthesize_speech_with_url(
     text: &str,
     api_key: &str,
@@ -79,76 +67,20 @@ async fn synthesize_speech_with_url(
     model: &str,
     url: &str,
 ) -> Result<Vec<u8>> {
-    let client = Client::new();
-
-    let body = serde_json::json!({
-        "model": model,
-        "input": text,
-        "voice": voice,
-        "response_format": "opus",
-    });
-
-    let response = client
-        .post(url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .context("Failed to send TTS request to OpenAI")?;
-
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("OpenAI TTS error ({}): {}", status, error_text);
-    }
-
-    let audio_bytes = response
-        .bytes()
-        .await
-        .context("Failed to read TTS audio bytes")?
-        .to_vec();
-
-    tracing::info!(
-        "OpenAI TTS: generated {} bytes of audio (voice={}, model={})",
-        audio_bytes.len(),
-        voice,
-        model,
-    );
-
-    Ok(audio_bytes)
-}
-
-#[derive(Deserialize)]
-struct TranscriptionResponse {
-    text: String,
+    OpenAiTts::with_url(
+        api_key.to_string(),
+        voice.to_string(),
+        model.to_string(),
+        url.to_string(),
+    )
+    .synthesize(text)
+    .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_transcription_response_parse() {
-        let json = r#"{"text": "Hello, this is a test."}"#;
-        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(result.text, "Hello, this is a test.");
-    }
-
-    #[test]
-    fn test_transcription_response_parse_unicode() {
-        let json = r#"{"text": "Olá, como você está?"}"#;
-        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(result.text, "Olá, como você está?");
-    }
-
-    #[test]
-    fn test_transcription_response_parse_empty() {
-        let json = r#"{"text": ""}"#;
-        let result: TranscriptionResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(result.text, "");
-    }
-
     // --- STT tests with mock HTTP server ---
 
     #[tokio::test]
@@ -231,6 +163,33 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("parse"));
     }
 
+    #[tokio::test]
+    async fn test_stt_sends_language_and_prompt() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex("name=\"language\"\r\n\r\nde\r\n".to_string()),
+                mockito::Matcher::Regex("name=\"prompt\"\r\n\r\nOpenCrabs\r\n".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"text": "Hallo"}"#)
+            .create_async()
+            .await;
+
+        let options = SttOptions {
+            language: Some("de".to_string()),
+            prompt: Some("OpenCrabs".to_string()),
+        };
+        let result = GroqStt::with_url("key".to_string(), server.url())
+            .transcribe(vec![0u8; 50], &options)
+            .await;
+
+        mock.assert_async().await;
+        assert_eq!(result.unwrap(), "Hallo");
+    }
+
     #[tokio::test]
     async fn test_stt_long_transcription() {
         let long_text = "word ".repeat(500);
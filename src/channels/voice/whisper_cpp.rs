@@ -0,0 +1,136 @@
+//! Local speech-to-text with whisper.cpp — no audio leaves the machine.
+//!
+//! Voice notes are OGG/Opus, which whisper.cpp can't read, so `ffmpeg`
+//! converts them to 16 kHz mono WAV first. Both binaries must be installed.
+//! whisper.cpp prints one line per decoded segment, so partial transcripts
+//! stream out as it goes.
+
+use super::provider::{SttOptions, SttProvider};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+pub struct WhisperCppStt {
+    binary: String,
+    model: PathBuf,
+}
+
+impl WhisperCppStt {
+    pub fn new(binary: String, model: PathBuf) -> Self {
+        Self { binary, model }
+    }
+
+    /// Convert to WAV, then transcribe with the whisper.cpp CLI, reporting
+    /// the text so far after each segment.
+    async fn run(
+        &self,
+        ogg_path: &Path,
+        wav_path: &Path,
+        options: &SttOptions,
+        on_partial: &(dyn Fn(&str) + Send + Sync),
+    ) -> Result<String> {
+        let convert = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-i"])
+            .arg(ogg_path)
+            .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(wav_path)
+            .output()
+            .await
+            .context("Failed to run ffmpeg (is it installed?)")?;
+        if !convert.status.success() {
+            anyhow::bail!(
+                "ffmpeg conversion failed: {}",
+                String::from_utf8_lossy(&convert.stderr).trim()
+            );
+        }
+
+        let mut whisper = Command::new(&self.binary);
+        whisper
+            .arg("-m")
+            .arg(&self.model)
+            .arg("-f")
+            .arg(wav_path)
+            .args(["-l", options.language.as_deref().unwrap_or("auto")])
+            .args(["-nt", "-np"]);
+        if let Some(ref prompt) = options.prompt {
+            whisper.args(["--prompt", prompt]);
+        }
+        let mut child = whisper
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!("Failed to run {} (is whisper.cpp installed?)", self.binary)
+            })?;
+
+        // Drain stderr alongside stdout so a chatty run can't block on a full pipe
+        let mut stderr = child.stderr.take().context("whisper.cpp stderr missing")?;
+        let stderr_task = tokio::spawn(async move {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf).await;
+            buf
+        });
+
+        let stdout = child.stdout.take().context("whisper.cpp stdout missing")?;
+        let mut lines = BufReader::new(stdout).lines();
+        let mut text = String::new();
+        while let Some(line) = lines.next_line().await? {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(line);
+            on_partial(&text);
+        }
+
+        let status = child.wait().await?;
+        if !status.success() {
+            let stderr = stderr_task.await.unwrap_or_default();
+            anyhow::bail!("whisper.cpp error ({}): {}", status, stderr.trim());
+        }
+        tracing::info!("whisper.cpp STT: transcribed {} chars", text.len());
+        Ok(text)
+    }
+}
+
+#[async_trait]
+impl SttProvider for WhisperCppStt {
+    fn name(&self) -> &str {
+        "whisper_cpp"
+    }
+
+    async fn transcribe(&self, audio: Vec<u8>, options: &SttOptions) -> Result<String> {
+        self.transcribe_streaming(audio, options, &|_: &str| {})
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn transcribe_streaming(
+        &self,
+        audio: Vec<u8>,
+        options: &SttOptions,
+        on_partial: &(dyn Fn(&str) + Send + Sync),
+    ) -> Result<String> {
+        let stem = std::env::temp_dir().join(format!("opencrabs_stt_{}", uuid::Uuid::new_v4()));
+        let ogg_path = stem.with_extension("ogg");
+        let wav_path = stem.with_extension("wav");
+        tokio::fs::write(&ogg_path, &audio)
+            .await
+            .context("Failed to write voice note to temp file")?;
+
+        let result = self.run(&ogg_path, &wav_path, options, on_partial).await;
+
+        let _ = tokio::fs::remove_file(&ogg_path).await;
+        let _ = tokio::fs::remove_file(&wav_path).await;
+        result
+    }
+}
@@ -5,10 +5,13 @@
 use super::WhatsAppState;
 use super::handler;
 use crate::brain::agent::AgentService;
-use crate::config::Config;
+use crate::config::{Config, WhatsAppConfig};
 use crate::db::ChannelMessageRepository;
 use crate::services::{ServiceContext, SessionService};
+use crate::utils::retry::RetryConfig;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
@@ -18,6 +21,33 @@ use whatsapp_rust::bot::Bot;
 use whatsapp_rust_tokio_transport::TokioWebSocketTransportFactory;
 use whatsapp_rust_ureq_http_client::UreqHttpClient;
 
+/// Why one run of the bot ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunEnd {
+    /// Connection dropped; the paired session is still good
+    Disconnected,
+    /// WhatsApp revoked the session; only re-pairing helps
+    LoggedOut,
+    /// The bot couldn't be built or started
+    Failed,
+}
+
+/// Wait before reconnect `attempt` (1-based): 2s doubling up to
+/// `reconnect_max_delay_secs`. `None` once `reconnect_max_attempts` is used up.
+fn reconnect_delay(cfg: &WhatsAppConfig, attempt: u32) -> Option<Duration> {
+    if cfg.reconnect_max_attempts.is_some_and(|max| attempt > max) {
+        return None;
+    }
+    let backoff = RetryConfig {
+        max_attempts: 0,
+        initial_delay: Duration::from_secs(2),
+        max_delay: Duration::from_secs(cfg.reconnect_max_delay_secs.unwrap_or(300)),
+        backoff_multiplier: 2.0,
+        jitter: 0.0,
+    };
+    Some(backoff.calculate_delay(attempt - 1))
+}
+
 /// WhatsApp agent that forwards messages to the AgentService
 pub struct WhatsAppAgent {
     agent_service: Arc<AgentService>,
@@ -50,6 +80,8 @@ impl WhatsAppAgent {
     /// Start as a background task. Returns JoinHandle.
     /// If already paired (session.db exists), reconnects silently.
     /// If not paired, QR events are logged.
+    /// A dropped connection is retried with backoff on the same session;
+    /// a logout stops the agent, since only re-pairing can fix it.
     pub fn start(self) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
             let db_path = crate::config::opencrabs_home()
@@ -92,13 +124,11 @@ impl WhatsAppAgent {
                 cfg.voice.tts_enabled,
             );
 
-            // Derive owner JID from first allowed phone (for proactive messaging)
+            // Derive owner JID from [owner].whatsapp_phone or first allowed phone
+            // (for proactive messaging)
             let owner_jid = cfg
-                .channels
-                .whatsapp
-                .allowed_phones
-                .first()
-                .map(|p| format!("{}@s.whatsapp.net", p.trim_start_matches('+')));
+                .whatsapp_owner_phone()
+                .map(|p| format!("{}@s.whatsapp.net", p));
 
             let agent = self.agent_service.clone();
             let session_svc = self.session_service.clone();
@@ -109,89 +139,180 @@ impl WhatsAppAgent {
             let extra_sessions: Arc<
                 Mutex<std::collections::HashMap<String, (Uuid, std::time::Instant)>>,
             > = Arc::new(Mutex::new(std::collections::HashMap::new()));
+            let idle_sweep = crate::channels::spawn_idle_sweep(
+                "WhatsApp",
+                extra_sessions.clone(),
+                self.session_service.clone(),
+                self.config_rx.clone(),
+                |c| c.channels.whatsapp.session_idle_hours,
+            );
 
-            let owner_jid_clone = owner_jid.clone();
-
-            let bot_result = Bot::builder()
-                .with_backend(backend)
-                .with_transport_factory(TokioWebSocketTransportFactory::new())
-                .with_http_client(UreqHttpClient::new())
-                .on_event(move |event, client| {
-                    let agent = agent.clone();
-                    let session_svc = session_svc.clone();
-                    let extra_sessions = extra_sessions.clone();
-                    let shared_session = shared_session.clone();
-                    let wa_state = wa_state.clone();
-                    let owner_jid = owner_jid_clone.clone();
-                    let config_rx = config_rx.clone();
-                    let channel_msg_repo = channel_msg_repo.clone();
-                    async move {
-                        match event {
-                            Event::PairingQrCode { ref code, .. } => {
-                                tracing::info!(
-                                    "WhatsApp: QR code available (scan with your phone)"
-                                );
-                                // In static mode, just log — QR display is handled by the connect tool
-                                tracing::debug!("WhatsApp QR: {}", code);
-                            }
-                            Event::Connected(_) => {
-                                tracing::info!("WhatsApp: connected successfully");
-                                wa_state
-                                    .set_connected(client.clone(), owner_jid.clone())
+            // Consecutive failed (re)connects; reset once a connection is up
+            let failures = Arc::new(AtomicU32::new(0));
+
+            loop {
+                let (end_tx, mut end_rx) = tokio::sync::mpsc::unbounded_channel::<RunEnd>();
+                let agent = agent.clone();
+                let session_svc = session_svc.clone();
+                let extra_sessions = extra_sessions.clone();
+                let shared_session = shared_session.clone();
+                let wa_state = wa_state.clone();
+                let owner_jid_clone = owner_jid.clone();
+                let config_rx = config_rx.clone();
+                let channel_msg_repo = channel_msg_repo.clone();
+                let failures_on_event = failures.clone();
+
+                // Same backend every time: the paired session is reused, no new QR
+                let bot_result = Bot::builder()
+                    .with_backend(backend.clone())
+                    .with_transport_factory(TokioWebSocketTransportFactory::new())
+                    .with_http_client(UreqHttpClient::new())
+                    .on_event(move |event, client| {
+                        let agent = agent.clone();
+                        let session_svc = session_svc.clone();
+                        let extra_sessions = extra_sessions.clone();
+                        let shared_session = shared_session.clone();
+                        let wa_state = wa_state.clone();
+                        let owner_jid = owner_jid_clone.clone();
+                        let config_rx = config_rx.clone();
+                        let channel_msg_repo = channel_msg_repo.clone();
+                        let end_tx = end_tx.clone();
+                        let failures = failures_on_event.clone();
+                        async move {
+                            match event {
+                                Event::PairingQrCode { ref code, .. } => {
+                                    tracing::info!(
+                                        "WhatsApp: QR code available (scan with your phone)"
+                                    );
+                                    // In static mode, just log — QR display is handled by the connect tool
+                                    tracing::debug!("WhatsApp QR: {}", code);
+                                }
+                                Event::Connected(_) => {
+                                    let previous = failures.swap(0, Ordering::SeqCst);
+                                    if previous > 0 {
+                                        tracing::info!(
+                                            "WhatsApp: reconnected after {} failed attempt(s)",
+                                            previous
+                                        );
+                                    } else {
+                                        tracing::info!("WhatsApp: connected successfully");
+                                    }
+                                    wa_state
+                                        .set_connected(client.clone(), owner_jid.clone())
+                                        .await;
+                                }
+                                Event::PairSuccess(_) => {
+                                    tracing::info!("WhatsApp: pairing successful");
+                                }
+                                Event::Message(msg, info) => {
+                                    tracing::debug!("WhatsApp: Event::Message received");
+                                    handler::handle_message(
+                                        *msg,
+                                        info,
+                                        client,
+                                        agent,
+                                        session_svc,
+                                        extra_sessions,
+                                        shared_session,
+                                        wa_state.clone(),
+                                        config_rx,
+                                        channel_msg_repo,
+                                    )
                                     .await;
+                                }
+                                Event::LoggedOut(_) => {
+                                    let _ = end_tx.send(RunEnd::LoggedOut);
+                                }
+                                Event::Disconnected(_) => {
+                                    tracing::warn!("WhatsApp: disconnected");
+                                    let _ = end_tx.send(RunEnd::Disconnected);
+                                }
+                                other => {
+                                    tracing::debug!("WhatsApp: unhandled event: {:?}", other);
+                                }
                             }
-                            Event::PairSuccess(_) => {
-                                tracing::info!("WhatsApp: pairing successful");
-                            }
-                            Event::Message(msg, info) => {
-                                tracing::debug!("WhatsApp: Event::Message received");
-                                handler::handle_message(
-                                    *msg,
-                                    info,
-                                    client,
-                                    agent,
-                                    session_svc,
-                                    extra_sessions,
-                                    shared_session,
-                                    wa_state.clone(),
-                                    config_rx,
-                                    channel_msg_repo,
-                                )
-                                .await;
-                            }
-                            Event::LoggedOut(_) => {
-                                tracing::warn!("WhatsApp: logged out");
-                            }
-                            Event::Disconnected(_) => {
-                                tracing::warn!("WhatsApp: disconnected");
-                            }
-                            other => {
-                                tracing::debug!("WhatsApp: unhandled event: {:?}", other);
+                        }
+                    })
+                    .build()
+                    .await;
+
+                let end = match bot_result {
+                    Ok(mut bot) => match bot.run().await {
+                        Ok(mut handle) => {
+                            tokio::select! {
+                                result = &mut handle => {
+                                    if let Err(e) = result {
+                                        tracing::error!("WhatsApp agent task error: {:?}", e);
+                                    }
+                                    RunEnd::Disconnected
+                                }
+                                end = end_rx.recv() => {
+                                    handle.abort();
+                                    end.unwrap_or(RunEnd::Disconnected)
+                                }
                             }
                         }
+                        Err(e) => {
+                            tracing::error!("WhatsApp agent error: {}", e);
+                            RunEnd::Failed
+                        }
+                    },
+                    Err(e) => {
+                        tracing::error!("WhatsApp: failed to build bot: {}", e);
+                        RunEnd::Failed
                     }
-                })
-                .build()
-                .await;
+                };
 
-            let mut bot = match bot_result {
-                Ok(b) => b,
-                Err(e) => {
-                    tracing::error!("WhatsApp: failed to build bot: {}", e);
-                    return;
+                if end == RunEnd::LoggedOut {
+                    tracing::error!(
+                        "WhatsApp: logged out — the paired session is no longer valid. \
+                         Re-pair with 'connect WhatsApp' in chat; not reconnecting."
+                    );
+                    break;
                 }
-            };
 
-            match bot.run().await {
-                Ok(handle) => {
-                    if let Err(e) = handle.await {
-                        tracing::error!("WhatsApp agent task error: {:?}", e);
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("WhatsApp agent error: {}", e);
-                }
+                let attempt = failures.fetch_add(1, Ordering::SeqCst) + 1;
+                let wa_cfg = self.config_rx.borrow().channels.whatsapp.clone();
+                let Some(delay) = reconnect_delay(&wa_cfg, attempt) else {
+                    tracing::error!(
+                        "WhatsApp: connection lost and {} reconnect attempt(s) used up; \
+                         the bot is stopped until OpenCrabs restarts",
+                        attempt - 1
+                    );
+                    break;
+                };
+                tracing::warn!(
+                    "WhatsApp: reconnecting in {}s (attempt {})",
+                    delay.as_secs(),
+                    attempt
+                );
+                tokio::time::sleep(delay).await;
             }
+            idle_sweep.abort();
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay_backs_off_and_caps() {
+        let mut cfg = WhatsAppConfig {
+            reconnect_max_delay_secs: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(reconnect_delay(&cfg, 1), Some(Duration::from_secs(2)));
+        assert_eq!(reconnect_delay(&cfg, 2), Some(Duration::from_secs(4)));
+        assert_eq!(reconnect_delay(&cfg, 5), Some(Duration::from_secs(10)));
+        assert!(reconnect_delay(&cfg, 1000).is_some());
+
+        cfg.reconnect_max_attempts = Some(2);
+        assert!(reconnect_delay(&cfg, 2).is_some());
+        assert_eq!(reconnect_delay(&cfg, 3), None);
+
+        cfg.reconnect_max_attempts = Some(0);
+        assert_eq!(reconnect_delay(&cfg, 1), None);
+    }
+}
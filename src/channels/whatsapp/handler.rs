@@ -5,6 +5,8 @@
 
 use crate::brain::agent::AgentService;
 use crate::brain::agent::{ApprovalCallback, ProgressCallback, ProgressEvent};
+use crate::channels::MessageSource;
+use crate::channels::voice::SttOptions;
 use crate::channels::whatsapp::WhatsAppState;
 use crate::config::Config;
 use crate::db::ChannelMessageRepository;
@@ -22,9 +24,46 @@ use wacore::types::message::MessageInfo;
 use waproto::whatsapp::Message;
 use whatsapp_rust::client::Client;
 
-/// Header prepended to all outgoing messages so the user knows it's from the agent.
+/// Header prepended to all outgoing messages so the user knows it's from the
+/// agent, as built for the default `[agent] emoji` and `name`. Replaced by
+/// `[channels.whatsapp] reply_header`.
 pub const MSG_HEADER: &str = "\u{1f980} *OpenCrabs*";
 
+/// Invisible prefix for outgoing messages when the header is disabled, so our
+/// own replies are still recognised when they echo back as `is_from_me`.
+const ECHO_MARKER: &str = "\u{2063}";
+
+/// The configured reply header (may be empty). Unset, it is the agent emoji
+/// and bold name — `MSG_HEADER` with the default `[agent]` settings.
+pub fn reply_header(cfg: &crate::config::Config) -> String {
+    cfg.channels
+        .whatsapp
+        .reply_header
+        .clone()
+        .unwrap_or_else(|| {
+            let agent = &cfg.agent;
+            if agent.emoji.is_empty() {
+                format!("*{}*", agent.name)
+            } else {
+                format!("{} *{}*", agent.emoji, agent.name)
+            }
+        })
+}
+
+/// `text` as sent: below the header, or behind the echo marker without one.
+pub fn tag_outgoing(header: &str, text: &str) -> String {
+    if header.is_empty() {
+        format!("{ECHO_MARKER}{text}")
+    } else {
+        format!("{header}\n\n{text}")
+    }
+}
+
+/// Whether an `is_from_me` text is one of our own replies echoing back.
+fn is_own_echo(text: &str, header: &str) -> bool {
+    text.starts_with(ECHO_MARKER) || (!header.is_empty() && text.starts_with(header))
+}
+
 /// Unwrap nested message wrappers (device_sent, ephemeral, view_once, etc.)
 /// Returns the innermost Message that contains actual content.
 fn unwrap_message(msg: &Message) -> &Message {
@@ -115,49 +154,125 @@ fn has_document(msg: &Message) -> bool {
     msg.document_message.is_some()
 }
 
-/// Download a document from WhatsApp. Returns (bytes, mime, filename) on success.
-async fn download_document(msg: &Message, client: &Client) -> Option<(Vec<u8>, String, String)> {
-    let msg = unwrap_message(msg);
-    let doc = msg.document_message.as_ref()?;
-    let mime = doc.mimetype.clone().unwrap_or_default();
-    let fname = doc.file_name.clone().unwrap_or_else(|| "file".to_string());
-    match client.download(doc.as_ref()).await {
-        Ok(bytes) => {
-            tracing::debug!(
-                "WhatsApp: downloaded document {} ({} bytes)",
-                fname,
-                bytes.len()
-            );
-            Some((bytes, mime, fname))
+/// Attempts per media download before giving up
+const MEDIA_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Whether a download error means the media is gone for good. WhatsApp media
+/// URLs expire after a while; retrying those only delays the error.
+fn is_expired_media_error(err: &str) -> bool {
+    let err = err.to_lowercase();
+    [
+        "expired",
+        "403",
+        "404",
+        "410",
+        "forbidden",
+        "not found",
+        "gone",
+    ]
+    .iter()
+    .any(|marker| err.contains(marker))
+}
+
+/// Download and decrypt `media`, retrying transient failures with a short
+/// backoff. The error is a message fit to show the sender.
+async fn download_media(
+    client: &Client,
+    media: &dyn wacore::download::Downloadable,
+    kind: &str,
+) -> Result<Vec<u8>, String> {
+    let mut attempt = 1;
+    loop {
+        match client.download(media).await {
+            Ok(bytes) => {
+                tracing::debug!(
+                    "WhatsApp: downloaded {kind} ({} bytes, attempt {attempt})",
+                    bytes.len()
+                );
+                return Ok(bytes);
+            }
+            Err(e) => {
+                let err = e.to_string();
+                if is_expired_media_error(&err) {
+                    tracing::warn!("WhatsApp: {kind} media expired: {err}");
+                    return Err(format!(
+                        "That {kind} has expired on WhatsApp's servers. Please send it again."
+                    ));
+                }
+                if attempt >= MEDIA_DOWNLOAD_ATTEMPTS {
+                    tracing::error!(
+                        "WhatsApp: failed to download {kind} after {attempt} attempts: {err}"
+                    );
+                    return Err(format!(
+                        "Couldn't download that {kind} after {attempt} attempts. Please try again."
+                    ));
+                }
+                tracing::warn!(
+                    "WhatsApp: {kind} download attempt {attempt}/{MEDIA_DOWNLOAD_ATTEMPTS} failed: {err}"
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(u64::from(attempt))).await;
+                attempt += 1;
+            }
         }
-        Err(e) => {
-            tracing::error!("WhatsApp: failed to download document: {e}");
+    }
+}
+
+/// Unwrap a media download, telling the sender when it failed.
+async fn notify_on_error<T>(
+    result: Result<T, String>,
+    client: &Client,
+    info: &MessageInfo,
+    header: &str,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(reason) => {
+            // Tagged like every reply, so the echo in a self-chat is ignored
+            // instead of being handled as a new message
+            let reply = waproto::whatsapp::Message {
+                conversation: Some(tag_outgoing(header, &format!("\u{26a0}\u{fe0f} {reason}"))),
+                ..Default::default()
+            };
+            let _ = client.send_message(info.source.chat.clone(), reply).await;
             None
         }
     }
 }
 
+/// Download a document from WhatsApp. Returns (bytes, mime, filename) on success.
+async fn download_document(
+    msg: &Message,
+    client: &Client,
+) -> Result<(Vec<u8>, String, String), String> {
+    let msg = unwrap_message(msg);
+    let doc = msg
+        .document_message
+        .as_ref()
+        .ok_or_else(|| "No document found in that message.".to_string())?;
+    let mime = doc.mimetype.clone().unwrap_or_default();
+    let fname = doc.file_name.clone().unwrap_or_else(|| "file".to_string());
+    let bytes = download_media(client, doc.as_ref(), "document").await?;
+    Ok((bytes, mime, fname))
+}
+
 /// Download audio from WhatsApp. Returns raw bytes on success.
-async fn download_audio(msg: &Message, client: &Client) -> Option<Vec<u8>> {
+async fn download_audio(msg: &Message, client: &Client) -> Result<Vec<u8>, String> {
     let msg = unwrap_message(msg);
-    let audio = msg.audio_message.as_ref()?;
-    match client.download(audio.as_ref()).await {
-        Ok(bytes) => {
-            tracing::debug!("WhatsApp: downloaded audio ({} bytes)", bytes.len());
-            Some(bytes)
-        }
-        Err(e) => {
-            tracing::error!("WhatsApp: failed to download audio: {e}");
-            None
-        }
-    }
+    let audio = msg
+        .audio_message
+        .as_ref()
+        .ok_or_else(|| "No voice note found in that message.".to_string())?;
+    download_media(client, audio.as_ref(), "voice note").await
 }
 
 /// Download image from WhatsApp and save to a temp file.
 /// Returns the file path on success.
-async fn download_image(msg: &Message, client: &Client) -> Option<String> {
+async fn download_image(msg: &Message, client: &Client) -> Result<String, String> {
     let msg = unwrap_message(msg);
-    let img = msg.image_message.as_ref()?;
+    let img = msg
+        .image_message
+        .as_ref()
+        .ok_or_else(|| "No image found in that message.".to_string())?;
 
     let mime = img.mimetype.as_deref().unwrap_or("image/jpeg");
     let ext = match mime {
@@ -167,28 +282,16 @@ async fn download_image(msg: &Message, client: &Client) -> Option<String> {
         _ => "jpg",
     };
 
-    match client.download(img.as_ref()).await {
-        Ok(bytes) => {
-            let path =
-                std::env::temp_dir().join(format!("wa_img_{}.{}", uuid::Uuid::new_v4(), ext));
-            match std::fs::write(&path, &bytes) {
-                Ok(()) => {
-                    tracing::debug!(
-                        "WhatsApp: downloaded image ({} bytes) to {}",
-                        bytes.len(),
-                        path.display()
-                    );
-                    Some(path.to_string_lossy().to_string())
-                }
-                Err(e) => {
-                    tracing::error!("WhatsApp: failed to save image: {}", e);
-                    None
-                }
-            }
+    let bytes = download_media(client, img.as_ref(), "image").await?;
+    let path = std::env::temp_dir().join(format!("wa_img_{}.{}", uuid::Uuid::new_v4(), ext));
+    match std::fs::write(&path, &bytes) {
+        Ok(()) => {
+            tracing::debug!("WhatsApp: saved image to {}", path.display());
+            Ok(path.to_string_lossy().to_string())
         }
         Err(e) => {
-            tracing::error!("WhatsApp: failed to download image: {}", e);
-            None
+            tracing::error!("WhatsApp: failed to save image: {}", e);
+            Err("Couldn't save that image. Please try again.".to_string())
         }
     }
 }
@@ -270,11 +373,12 @@ pub(crate) async fn handle_message(
 
     // Skip bot's own outgoing replies (they echo back as is_from_me).
     // User messages from their phone are also is_from_me (same account),
-    // so we only skip if the text starts with our agent header.
+    // so we only skip if the text starts with our agent header (or echo marker).
     // Never skip audio/image — those are real user messages even when is_from_me.
+    let header = reply_header(&config_rx.borrow());
     if info.source.is_from_me {
         if let Some(text) = extract_text(&msg) {
-            if text.starts_with(MSG_HEADER) {
+            if is_own_echo(&text, &header) {
                 return;
             }
         } else if !has_audio(&msg) && !has_image(&msg) {
@@ -283,6 +387,20 @@ pub(crate) async fn handle_message(
         }
     }
 
+    // Paused via /pause whatsapp — tell the owner once, ignore everyone else.
+    // Our own notice echoes back as is_from_me and lands here again, harmlessly.
+    if crate::channels::is_paused(MessageSource::WhatsApp) {
+        let is_owner = config_rx.borrow().is_whatsapp_owner(&phone);
+        if is_owner && crate::channels::take_pause_notice(MessageSource::WhatsApp) {
+            let reply = waproto::whatsapp::Message {
+                conversation: Some(crate::channels::PAUSED_NOTICE.to_string()),
+                ..Default::default()
+            };
+            let _ = client.send_message(info.source.chat.clone(), reply).await;
+        }
+        return;
+    }
+
     // Build message content: text, image, audio, or document
     let has_img = has_image(&msg);
     let has_aud = has_audio(&msg);
@@ -324,7 +442,8 @@ pub(crate) async fn handle_message(
     let cfg = config_rx.borrow().clone();
     let wa_cfg = &cfg.channels.whatsapp;
     let allowed: HashSet<String> = wa_cfg.allowed_phones.iter().cloned().collect();
-    let idle_timeout_hours = wa_cfg.session_idle_hours;
+    let idle_timeout = cfg.sessions.channel_idle_timeout(wa_cfg.session_idle_hours);
+    let archive_idle = cfg.sessions.archive_idle;
     let mut voice_config = cfg.voice.clone();
     voice_config.stt_provider = cfg.providers.stt.as_ref().and_then(|s| s.groq.clone());
     let tts_providers = cfg.providers.tts.as_ref();
@@ -341,8 +460,8 @@ pub(crate) async fn handle_message(
     // sender=owner but recipient=contact — must not treat that as "owner messaging bot".
     // If allowed_phones is empty (unconfigured), fall through without filtering.
     if !allowed.is_empty() {
-        let owner_phone_raw = allowed.iter().next().cloned().unwrap_or_default();
-        let owner_phone = owner_phone_raw.trim_start_matches('+');
+        let owner_phone_raw = cfg.whatsapp_owner_phone().unwrap_or_default();
+        let owner_phone = owner_phone_raw.as_str();
         let sender_normalized = phone.trim_start_matches('+');
         let recipient = recipient_phone(&info);
         let recipient_normalized = recipient.as_ref().map(|r| r.trim_start_matches('+'));
@@ -361,6 +480,18 @@ pub(crate) async fn handle_message(
         }
     }
 
+    // Blue ticks for accepted messages, only when [channels.whatsapp] send_receipts
+    // is on — by default the sender sees delivery, not read
+    if wa_cfg.send_receipts && !info.source.is_from_me {
+        let sender = info.source.is_group.then_some(&info.source.sender);
+        if let Err(e) = client
+            .mark_as_read(&info.source.chat, sender, vec![info.id.clone()])
+            .await
+        {
+            tracing::debug!("WhatsApp: failed to send read receipt: {}", e);
+        }
+    }
+
     // Pending approval check: if a tool approval is waiting for this phone,
     // interpret this message as Yes / Always / No instead of routing to the agent.
     // Handles both button taps (ButtonsResponseMessage) and plain text replies.
@@ -378,6 +509,7 @@ pub(crate) async fn handle_message(
                 "wa_approve_always" => Some(WaApproval::Always),
                 "wa_approve_yolo" => Some(WaApproval::Yolo),
                 "wa_approve_no" => Some(WaApproval::No),
+                "wa_approve_stop" => Some(WaApproval::Stop),
                 _ => None,
             }
         } else if let Some(raw_text) = extract_text(&msg) {
@@ -390,6 +522,8 @@ pub(crate) async fn handle_message(
                 Some(WaApproval::Yolo)
             } else if matches!(answer.as_str(), "no" | "n" | "nao" | "não") {
                 Some(WaApproval::No)
+            } else if matches!(answer.as_str(), "stop" | "parar") {
+                Some(WaApproval::Stop)
             } else {
                 None
             }
@@ -420,11 +554,14 @@ pub(crate) async fn handle_message(
     let mut content;
     if has_aud
         && voice_config.stt_enabled
-        && let Some(ref stt_provider) = voice_config.stt_provider
-        && let Some(ref stt_key) = stt_provider.api_key
-        && let Some(audio_bytes) = download_audio(&msg, &client).await
+        && let Some(stt) = crate::channels::voice::build_stt(&voice_config)
+        && let Some(audio_bytes) =
+            notify_on_error(download_audio(&msg, &client).await, &client, &info, &header).await
     {
-        match crate::channels::voice::transcribe_audio(audio_bytes, stt_key).await {
+        match stt
+            .transcribe(audio_bytes, &SttOptions::for_user(&voice_config, &phone))
+            .await
+        {
             Ok(transcript) => {
                 tracing::info!(
                     "WhatsApp: transcribed voice: {}",
@@ -444,7 +581,8 @@ pub(crate) async fn handle_message(
     // Download image if present, append <<IMG:path>> marker
     if has_img
         && !has_aud
-        && let Some(img_path) = download_image(&msg, &client).await
+        && let Some(img_path) =
+            notify_on_error(download_image(&msg, &client).await, &client, &info, &header).await
     {
         if content.is_empty() {
             content = "Describe this image.".to_string();
@@ -456,7 +594,13 @@ pub(crate) async fn handle_message(
     if has_doc
         && !has_aud
         && !has_img
-        && let Some((bytes, mime, fname)) = download_document(&msg, &client).await
+        && let Some((bytes, mime, fname)) = notify_on_error(
+            download_document(&msg, &client).await,
+            &client,
+            &info,
+            &header,
+        )
+        .await
     {
         use crate::utils::{FileContent, classify_file};
         match classify_file(&bytes, &mime, &fname) {
@@ -492,13 +636,22 @@ pub(crate) async fn handle_message(
         return;
     }
 
-    // Resolve session: owner (first in allowed list) shares TUI session, others get their own
-    let is_owner = allowed.is_empty()
-        || allowed
-            .iter()
-            .next()
-            .map(|a| a.trim_start_matches('+') == phone)
-            .unwrap_or(false);
+    // Resolve session: owner ([owner].whatsapp_phone, else first in allowed list) shares
+    // TUI session, others get their own
+    let is_owner = cfg.is_whatsapp_owner(&phone);
+
+    // Track owner's chat for proactive messaging — only a private chat that is
+    // verifiably the owner's: their own note-to-self, or the configured owner
+    // phone. An empty allowlist must not let the first sender claim it, and a
+    // group JID would broadcast proactive messages to everyone in it.
+    let verified_owner_chat = !info.source.is_group
+        && (info.source.is_from_me
+            || cfg
+                .whatsapp_owner_phone()
+                .is_some_and(|owner| owner == phone.trim_start_matches('+')));
+    if verified_owner_chat {
+        wa_state.set_owner_jid(info.source.chat.to_string()).await;
+    }
 
     let session_id = if is_owner {
         let shared = shared_session.lock().await;
@@ -534,10 +687,10 @@ pub(crate) async fn handle_message(
     } else {
         let mut map = extra_sessions.lock().await;
         if let Some((old_id, last_activity)) = map.get(&phone).copied() {
-            if idle_timeout_hours
-                .is_some_and(|h| last_activity.elapsed().as_secs() > (h * 3600.0) as u64)
-            {
-                let _ = session_svc.archive_session(old_id).await;
+            if idle_timeout.is_some_and(|t| last_activity.elapsed() > t) {
+                if archive_idle {
+                    let _ = session_svc.archive_session(old_id).await;
+                }
                 map.remove(&phone);
                 let title = format!("WhatsApp: {}", phone);
                 match session_svc.create_session(Some(title)).await {
@@ -571,7 +724,17 @@ pub(crate) async fn handle_message(
 
     // ── Channel commands (/help, /usage, /models, /stop) ────────────────────
     {
-        use crate::channels::commands::{self, ChannelCommand};
+        use crate::channels::commands::{self, AllowlistChannel, ChannelCommand};
+        if let Some(reply) =
+            commands::handle_allowlist_command(&content, AllowlistChannel::WhatsApp, &cfg, &phone)
+        {
+            let reply = waproto::whatsapp::Message {
+                conversation: Some(reply),
+                ..Default::default()
+            };
+            let _ = client.send_message(info.source.chat.clone(), reply).await;
+            return;
+        }
         match commands::handle_command(&content, session_id, &agent, &session_svc).await {
             ChannelCommand::Help(body) | ChannelCommand::Usage(body) => {
                 let reply = waproto::whatsapp::Message {
@@ -712,7 +875,7 @@ pub(crate) async fn handle_message(
                     .iter()
                     .rev()
                     .map(|m| {
-                        let ts = m.created_at.format("%H:%M");
+                        let ts = crate::config::format_local(m.created_at, "%H:%M");
                         format!("[{}] {}: {}", ts, m.sender_name, m.content)
                     })
                     .collect();
@@ -762,6 +925,7 @@ pub(crate) async fn handle_message(
         let client_cb = client.clone();
         let jid_cb = info.source.chat.clone();
         let was_streamed_cb = was_streamed.clone();
+        let header = header.clone();
         Arc::new(move |_session_id, event| {
             if let ProgressEvent::IntermediateText { text, .. } = event {
                 let (clean, _) = crate::utils::extract_img_markers(&text);
@@ -770,7 +934,7 @@ pub(crate) async fn handle_message(
                     was_streamed_cb.store(true, std::sync::atomic::Ordering::Relaxed);
                     let client = client_cb.clone();
                     let jid = jid_cb.clone();
-                    let tagged = format!("{}\n\n{}", MSG_HEADER, clean.trim());
+                    let tagged = tag_outgoing(&header, clean.trim());
                     tokio::spawn(async move {
                         for chunk in split_message(&tagged, 4000) {
                             let msg = waproto::whatsapp::Message {
@@ -787,26 +951,40 @@ pub(crate) async fn handle_message(
         })
     };
 
+    // Queue behind other channel messages at [channels] max_concurrent
+    let permit =
+        crate::channels::acquire_agent_permit("WhatsApp", cfg.channels.max_concurrent).await;
+
     // Build per-call approval callback.
     // If the user previously chose "Always (session)", auto-approve without asking.
-    // Otherwise send a 3-button message (Yes / Always / No) and wait up to 5 min.
+    // Otherwise send a text prompt (yes / always / yolo / no / stop) and wait up to
+    // [tools] approval_timeout_secs, giving up the permit meanwhile.
     let approval_cb: ApprovalCallback = {
+        use crate::brain::agent::ApprovalDecision;
         use crate::channels::whatsapp::WaApproval;
         use crate::utils::{check_approval_policy, persist_auto_session_policy};
 
+        let config_rx = config_rx.clone();
         let client = client.clone();
         let chat_jid = info.source.chat.clone();
         let phone_key = phone.clone();
         let wa_state = wa_state.clone();
+        let header = header.clone();
+        let permit = permit.clone();
         Arc::new(move |tool_info| {
+            let config_rx = config_rx.clone();
+            let permit = permit.clone();
             let client = client.clone();
             let chat_jid = chat_jid.clone();
             let phone_key = phone_key.clone();
             let wa_state = wa_state.clone();
+            let header = header.clone();
             Box::pin(async move {
                 // Respect config-level approval policy (single source of truth)
-                if let Some(result) = check_approval_policy() {
-                    return Ok(result);
+                if !tool_info.forced
+                    && let Some(decision) = check_approval_policy()
+                {
+                    return Ok(decision);
                 }
 
                 // Redact secrets before display
@@ -821,9 +999,11 @@ pub(crate) async fn handle_message(
                 // Send plain text approval request (ButtonsMessage is deprecated
                 // by WhatsApp and silently never renders — use text only)
                 let text_msg = waproto::whatsapp::Message {
-                    conversation: Some(format!(
-                        "{}\n\n{}\n\nReply *yes*, *always* (session), *yolo* (permanent), or *no* (5 min timeout).",
-                        MSG_HEADER, body
+                    conversation: Some(tag_outgoing(
+                        &header,
+                        &format!(
+                            "{body}\n\nReply *yes*, *always* (session), *yolo* (permanent), *no*, or *stop* (deny and end the turn)."
+                        ),
                     )),
                     ..Default::default()
                 };
@@ -834,7 +1014,7 @@ pub(crate) async fn handle_message(
                 );
                 if let Err(e) = client.send_message(chat_jid.clone(), text_msg).await {
                     tracing::error!("WhatsApp: failed to send approval request: {}", e);
-                    return Ok((false, false));
+                    return Ok(ApprovalDecision::Deny);
                 }
 
                 let (tx, rx) = tokio::sync::oneshot::channel::<WaApproval>();
@@ -846,27 +1026,37 @@ pub(crate) async fn handle_message(
                     phone_key
                 );
 
-                match tokio::time::timeout(std::time::Duration::from_secs(300), rx).await {
-                    Ok(Ok(WaApproval::Yes)) => {
+                match permit
+                    .released_while(crate::utils::await_approval(rx, &config_rx))
+                    .await
+                {
+                    Some(Ok(WaApproval::Yes)) => {
                         tracing::info!("WhatsApp approval: user approved (phone={})", phone_key);
-                        Ok((true, false))
+                        Ok(ApprovalDecision::Approve)
                     }
-                    Ok(Ok(WaApproval::Always)) => {
+                    Some(Ok(WaApproval::Always)) => {
                         tracing::info!(
                             "WhatsApp approval: user chose Always (phone={})",
                             phone_key
                         );
                         persist_auto_session_policy();
-                        Ok((true, true))
+                        Ok(ApprovalDecision::ApproveAll)
                     }
-                    Ok(Ok(WaApproval::Yolo)) => {
+                    Some(Ok(WaApproval::Yolo)) => {
                         tracing::info!("WhatsApp approval: user chose YOLO (phone={})", phone_key);
                         crate::utils::persist_auto_always_policy();
-                        Ok((true, true))
+                        Ok(ApprovalDecision::ApproveAll)
                     }
-                    Ok(Ok(WaApproval::No)) => {
+                    Some(Ok(WaApproval::No)) => {
                         tracing::info!("WhatsApp approval: user denied (phone={})", phone_key);
-                        Ok((false, false))
+                        Ok(ApprovalDecision::Deny)
+                    }
+                    Some(Ok(WaApproval::Stop)) => {
+                        tracing::info!(
+                            "WhatsApp approval: user denied and stopped the turn (phone={})",
+                            phone_key
+                        );
+                        Ok(ApprovalDecision::DenyAndStop)
                     }
                     _ => {
                         tracing::warn!(
@@ -874,14 +1064,17 @@ pub(crate) async fn handle_message(
                             phone_key
                         );
                         let timeout_msg = waproto::whatsapp::Message {
-                            conversation: Some(format!(
-                                "{}\n\n⏰ No response in 5 minutes — *{}* was denied.\n\nSend your message again and reply *yes*, *always*, or *no* when prompted.",
-                                MSG_HEADER, tool_info.tool_name,
+                            conversation: Some(tag_outgoing(
+                                &header,
+                                &format!(
+                                    "⏰ No response in time — *{}* was denied.\n\nSend your message again and reply *yes*, *always*, or *no* when prompted.",
+                                    tool_info.tool_name,
+                                ),
                             )),
                             ..Default::default()
                         };
                         let _ = client.send_message(chat_jid, timeout_msg).await;
-                        Ok((false, false))
+                        Ok(ApprovalDecision::Deny)
                     }
                 }
             })
@@ -971,7 +1164,7 @@ pub(crate) async fn handle_message(
             // (happens when the agent used tool calls — text was sent between iterations).
             if !text_content.is_empty() && !was_streamed.load(std::sync::atomic::Ordering::Relaxed)
             {
-                let tagged = format!("{}\n\n{}", MSG_HEADER, text_content);
+                let tagged = tag_outgoing(&header, &text_content);
                 for chunk in split_message(&tagged, 4000) {
                     let reply_msg = waproto::whatsapp::Message {
                         conversation: Some(chunk.to_string()),
@@ -986,17 +1179,9 @@ pub(crate) async fn handle_message(
             // If input was voice AND TTS is enabled, also send voice note after text
             if has_aud
                 && voice_config.tts_enabled
-                && let Some(ref tts_provider) = voice_config.tts_provider
-                && let Some(ref tts_key) = tts_provider.api_key
+                && let Some(tts) = crate::channels::voice::build_tts(&voice_config)
             {
-                match crate::channels::voice::synthesize_speech(
-                    &response.content,
-                    tts_key,
-                    &voice_config.tts_voice,
-                    &voice_config.tts_model,
-                )
-                .await
-                {
+                match tts.synthesize(&response.content).await {
                     Ok(audio_bytes) => {
                         // WhatsApp requires uploading media to its servers first,
                         // then sending the message with the returned URL + crypto keys.
@@ -1038,7 +1223,10 @@ pub(crate) async fn handle_message(
         Err(e) => {
             tracing::error!("WhatsApp: agent error: {}", e);
             let error_msg = waproto::whatsapp::Message {
-                conversation: Some(format!("{}\n\nError: {}", MSG_HEADER, e)),
+                conversation: Some(tag_outgoing(
+                    &header,
+                    &format!("Error: {}", e.user_message()),
+                )),
                 ..Default::default()
             };
             let _ = client
@@ -1052,6 +1240,43 @@ pub(crate) async fn handle_message(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_expired_media_errors_are_not_retried() {
+        assert!(is_expired_media_error("HTTP 410 Gone"));
+        assert!(is_expired_media_error("media URL expired"));
+        assert!(is_expired_media_error("download failed: 404 Not Found"));
+        assert!(!is_expired_media_error("connection reset by peer"));
+        assert!(!is_expired_media_error(
+            "HMAC mismatch while decrypting media"
+        ));
+    }
+
+    #[test]
+    fn test_own_echo_with_and_without_header() {
+        let tagged = tag_outgoing(MSG_HEADER, "hi");
+        assert_eq!(tagged, format!("{MSG_HEADER}\n\nhi"));
+        assert!(is_own_echo(&tagged, MSG_HEADER));
+
+        // Header disabled: invisible marker still identifies our replies
+        let bare = tag_outgoing("", "hi");
+        assert!(is_own_echo(&bare, ""));
+        assert!(!is_own_echo("hi", ""));
+        assert!(!is_own_echo("hello from my phone", MSG_HEADER));
+    }
+
+    #[test]
+    fn test_reply_header_follows_agent_name() {
+        let mut cfg = crate::config::Config::default();
+        assert_eq!(reply_header(&cfg), MSG_HEADER);
+
+        cfg.agent.name = "Ferris".to_string();
+        cfg.agent.emoji = String::new();
+        assert_eq!(reply_header(&cfg), "*Ferris*");
+
+        cfg.channels.whatsapp.reply_header = Some("Bot".to_string());
+        assert_eq!(reply_header(&cfg), "Bot");
+    }
+
     #[test]
     fn test_split_short_message() {
         let chunks = split_message("hello", 4000);
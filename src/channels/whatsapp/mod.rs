@@ -27,6 +27,8 @@ pub enum WaApproval {
     Yolo,
     /// Deny this tool call.
     No,
+    /// Deny this tool call and end the agent's turn.
+    Stop,
 }
 
 /// Shared WhatsApp client state for proactive messaging.
@@ -35,7 +37,8 @@ pub enum WaApproval {
 /// Read by the `whatsapp_send` tool to send messages on demand.
 pub struct WhatsAppState {
     client: Mutex<Option<Arc<Client>>>,
-    /// Owner's JID (phone@s.whatsapp.net) — first in allowed_phones list
+    /// Owner's JID (phone@s.whatsapp.net) — from config at connect time,
+    /// then updated to the chat the owner last wrote from
     owner_jid: Mutex<Option<String>>,
     /// Pending tool approvals: phone → oneshot sender of WaApproval.
     /// When a tool approval is in flight, the next message from that phone
@@ -104,6 +107,47 @@ impl WhatsAppState {
         self.owner_jid.lock().await.clone()
     }
 
+    /// Record the chat the owner writes from, so proactive messages
+    /// (reminders, heartbeat) reach them there.
+    pub async fn set_owner_jid(&self, jid: String) {
+        *self.owner_jid.lock().await = Some(jid);
+    }
+
+    /// Send `text` to `jid` with the agent header, split into WhatsApp-sized
+    /// chunks.
+    pub async fn send_text(&self, jid: &str, text: &str) -> Result<(), String> {
+        let client = self
+            .client()
+            .await
+            .ok_or_else(|| "WhatsApp is not connected".to_string())?;
+        let jid: wacore_binary::jid::Jid = jid
+            .parse()
+            .map_err(|e| format!("Invalid WhatsApp JID '{jid}': {e}"))?;
+
+        let cfg = crate::config::Config::load().unwrap_or_default();
+        let tagged = handler::tag_outgoing(&handler::reply_header(&cfg), text);
+        for chunk in handler::split_message(&tagged, 4000) {
+            let wa_msg = waproto::whatsapp::Message {
+                conversation: Some(chunk.to_string()),
+                ..Default::default()
+            };
+            client
+                .send_message(jid.clone(), wa_msg)
+                .await
+                .map_err(|e| format!("Failed to send WhatsApp message: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Send `text` to the owner. Fails when not connected or the owner is unknown.
+    pub async fn send_to_owner(&self, text: &str) -> Result<(), String> {
+        let jid = self
+            .owner_jid()
+            .await
+            .ok_or_else(|| "WhatsApp owner is not known yet".to_string())?;
+        self.send_text(&jid, text).await
+    }
+
     /// Check if WhatsApp is currently connected.
     pub async fn is_connected(&self) -> bool {
         self.client.lock().await.is_some()
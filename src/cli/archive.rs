@@ -0,0 +1,552 @@
+//! State Archive
+//!
+//! `opencrabs export` / `opencrabs import`: the whole workspace — config,
+//! sessions DB, memory and brain files — as one `.tar.gz`, for backups and
+//! moving an agent to another machine.
+
+use crate::brain::prompt_builder::BRAIN_FILES;
+use crate::db::Database;
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// Archive entry describing what's inside; always written first
+const MANIFEST: &str = "manifest.toml";
+
+/// Archive name of the sessions DB, wherever `[database] path` points
+const DB_ENTRY: &str = "opencrabs.db";
+
+const MEMORY_DIR: &str = "memory";
+
+const MEMORY_DB: &str = "memory.db";
+
+/// What an archive holds and which build wrote it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    /// OpenCrabs version that wrote the archive
+    pub version: String,
+    /// Newest migration applied to the archived sessions DB
+    pub schema_version: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Whether `keys.toml` and inline credentials were included
+    pub secrets: bool,
+}
+
+/// Bundle `home` and the sessions DB at `db_path` into `out`. Credentials
+/// are left out unless `include_secrets`. Returns the archived entries.
+pub(crate) async fn export_state(
+    home: &Path,
+    db_path: &Path,
+    out: &Path,
+    include_secrets: bool,
+) -> Result<Vec<String>> {
+    // Snapshot both databases first so a running agent can't tear them
+    let scratch = scratch_dir(home, "export")?;
+    let result = async {
+        let mut schema_version = None;
+        let db_snapshot = scratch.join(DB_ENTRY);
+        if db_path.is_file() {
+            let db = Database::connect(db_path).await?;
+            schema_version = db.schema_version().await;
+            db.snapshot_to(&db_snapshot).await?;
+            db.close().await?;
+        }
+        let memory_db = home.join(MEMORY_DIR).join(MEMORY_DB);
+        let memory_snapshot = scratch.join(MEMORY_DB);
+        if memory_db.is_file() {
+            let db = Database::connect(&memory_db).await?;
+            db.snapshot_to(&memory_snapshot).await?;
+            db.close().await?;
+        }
+
+        let file =
+            File::create(out).with_context(|| format!("Failed to create {}", out.display()))?;
+        let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        let mut entries = Vec::new();
+
+        let manifest = Manifest {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            created_at: chrono::Utc::now(),
+            secrets: include_secrets,
+        };
+        append_bytes(&mut tar, MANIFEST, toml::to_string(&manifest)?.as_bytes())?;
+
+        let config_path = home.join("config.toml");
+        if config_path.is_file() {
+            let raw = std::fs::read_to_string(&config_path)?;
+            let contents = if include_secrets {
+                raw
+            } else {
+                let mut value: toml::Value = toml::from_str(&raw)
+                    .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+                crate::config::secrets::strip_secrets(&mut value);
+                toml::to_string_pretty(&value)?
+            };
+            append_bytes(&mut tar, "config.toml", contents.as_bytes())?;
+            entries.push("config.toml".to_string());
+        }
+        let keys_path = home.join("keys.toml");
+        if include_secrets && keys_path.is_file() {
+            tar.append_path_with_name(&keys_path, "keys.toml")?;
+            entries.push("keys.toml".to_string());
+        }
+
+        if db_snapshot.is_file() {
+            tar.append_path_with_name(&db_snapshot, DB_ENTRY)?;
+            entries.push(DB_ENTRY.to_string());
+        }
+
+        let memory_dir = home.join(MEMORY_DIR);
+        if memory_dir.is_dir() {
+            append_dir(&mut tar, &memory_dir, Path::new(MEMORY_DIR), &mut entries)?;
+        }
+        if memory_snapshot.is_file() {
+            let name = format!("{MEMORY_DIR}/{MEMORY_DB}");
+            tar.append_path_with_name(&memory_snapshot, &name)?;
+            entries.push(name);
+        }
+
+        for (name, _) in BRAIN_FILES {
+            let path = home.join(name);
+            if path.is_file() {
+                tar.append_path_with_name(&path, name)?;
+                entries.push(name.to_string());
+            }
+        }
+
+        tar.into_inner()?.finish()?.flush()?;
+        Ok(entries)
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Append the files under `dir` as `prefix/...`, skipping the live memory
+/// DB and its WAL — those go in as a snapshot.
+fn append_dir<W: Write>(
+    tar: &mut tar::Builder<W>,
+    dir: &Path,
+    prefix: &Path,
+    entries: &mut Vec<String>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with(MEMORY_DB) {
+            continue;
+        }
+        let path = entry.path();
+        let archived = prefix.join(&name);
+        if path.is_dir() {
+            append_dir(tar, &path, &archived, entries)?;
+        } else if path.is_file() {
+            tar.append_path_with_name(&path, &archived)?;
+            entries.push(archived.to_string_lossy().into_owned());
+        }
+    }
+    Ok(())
+}
+
+fn append_bytes<W: Write>(tar: &mut tar::Builder<W>, name: &str, data: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// A fresh scratch directory inside `home`, so restores are same-filesystem
+/// renames.
+fn scratch_dir(home: &Path, purpose: &str) -> Result<PathBuf> {
+    let dir = home.join(format!(
+        ".{purpose}-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%f")
+    ));
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Whether an archive entry is something an export writes. Anything else —
+/// absolute paths, `..`, unknown files — is refused.
+fn is_allowed_entry(path: &Path) -> bool {
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return false;
+    }
+    let Some(first) = path.iter().next().and_then(|c| c.to_str()) else {
+        return false;
+    };
+    let top_level = path.components().count() == 1;
+    match first {
+        MEMORY_DIR => !top_level,
+        MANIFEST | "config.toml" | "keys.toml" | DB_ENTRY => top_level,
+        name => top_level && BRAIN_FILES.iter().any(|(f, _)| *f == name),
+    }
+}
+
+/// An unpacked archive waiting to be restored
+pub(crate) struct StagedImport {
+    pub manifest: Manifest,
+    /// Schema of the staged sessions DB, read from the DB itself
+    pub schema_version: Option<i64>,
+    /// `(staged file, destination)` pairs
+    pub files: Vec<(PathBuf, PathBuf)>,
+    dir: PathBuf,
+}
+
+impl StagedImport {
+    /// Destinations that already exist and would be replaced
+    pub fn overwrites(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .map(|(_, dest)| dest.as_path())
+            .filter(|dest| dest.exists())
+    }
+
+    /// Move the staged files into place. Databases lose any stale WAL from
+    /// the copy they replace, then get migrated to this build's schema.
+    pub async fn restore(self, db_path: &Path) -> Result<()> {
+        let result = async {
+            for (staged, dest) in &self.files {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                if is_sqlite(dest) {
+                    for suffix in ["-wal", "-shm"] {
+                        let mut sidecar = dest.clone().into_os_string();
+                        sidecar.push(suffix);
+                        let _ = std::fs::remove_file(sidecar);
+                    }
+                }
+                if std::fs::rename(staged, dest).is_err() {
+                    // Different filesystem (custom [database] path)
+                    std::fs::copy(staged, dest)
+                        .with_context(|| format!("Failed to restore {}", dest.display()))?;
+                }
+            }
+            if self.files.iter().any(|(_, dest)| dest == db_path) {
+                Database::connect(db_path).await?.run_migrations().await?;
+            }
+            Ok(())
+        }
+        .await;
+        let _ = std::fs::remove_dir_all(&self.dir);
+        result
+    }
+
+    /// Drop the staged files without restoring anything
+    pub fn discard(self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn is_sqlite(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "db")
+}
+
+/// Unpack `archive` into a scratch directory under `home` and check it can
+/// be restored by this build. Nothing in `home` is touched yet.
+pub(crate) async fn stage_import(
+    archive: &Path,
+    home: &Path,
+    db_path: &Path,
+) -> Result<StagedImport> {
+    let dir = scratch_dir(home, "import")?;
+    let staged = async {
+        let file =
+            File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+        let mut tar = tar::Archive::new(GzDecoder::new(file));
+        let mut names = Vec::new();
+        for entry in tar.entries().context("Not a .tar.gz archive")? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+            if !is_allowed_entry(&path) {
+                anyhow::bail!(
+                    "Refusing to import {}: not part of an OpenCrabs export",
+                    path.display()
+                );
+            }
+            // Links could point the restore outside the home directory
+            let kind = entry.header().entry_type();
+            if !kind.is_file() && !kind.is_dir() {
+                anyhow::bail!(
+                    "Refusing to import {}: only regular files and directories are allowed",
+                    path.display()
+                );
+            }
+            entry.unpack_in(&dir)?;
+            if path.as_os_str() != MANIFEST {
+                names.push(path);
+            }
+        }
+
+        let manifest: Manifest = std::fs::read_to_string(dir.join(MANIFEST))
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .context("Archive has no readable manifest.toml — not an OpenCrabs export")?;
+
+        let mut schema_version = None;
+        let staged_db = dir.join(DB_ENTRY);
+        if staged_db.is_file() {
+            let db = Database::connect(&staged_db).await?;
+            schema_version = db.schema_version().await;
+            db.close().await?;
+            let latest = Database::latest_schema_version();
+            if schema_version.is_some_and(|v| v > latest) {
+                anyhow::bail!(
+                    "Archive was written by OpenCrabs {} with a newer database schema \
+                     ({} > {}); upgrade this install before importing",
+                    manifest.version,
+                    schema_version.unwrap_or_default(),
+                    latest
+                );
+            }
+        }
+
+        let files = names
+            .into_iter()
+            .filter(|name| dir.join(name).is_file())
+            .map(|name| {
+                let dest = if name.as_os_str() == DB_ENTRY {
+                    db_path.to_path_buf()
+                } else {
+                    home.join(&name)
+                };
+                (dir.join(name), dest)
+            })
+            .collect();
+        Ok((manifest, schema_version, files))
+    }
+    .await;
+
+    match staged {
+        Ok((manifest, schema_version, files)) => Ok(StagedImport {
+            manifest,
+            schema_version,
+            files,
+            dir,
+        }),
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&dir);
+            Err(e)
+        }
+    }
+}
+
+/// `opencrabs export <file>`
+pub(crate) async fn cmd_export(
+    config: &crate::config::Config,
+    file: &Path,
+    include_secrets: bool,
+) -> Result<()> {
+    let home = crate::config::opencrabs_home();
+    println!("📦 Exporting {} ...", home.display());
+    let entries = export_state(&home, &config.database.path, file, include_secrets).await?;
+    println!("✅ Wrote {} entries to {}", entries.len(), file.display());
+    if !include_secrets {
+        println!("   Credentials were left out; pass --include-secrets to keep them.");
+    }
+    Ok(())
+}
+
+/// `opencrabs import <file>`
+pub(crate) async fn cmd_import(
+    config: &crate::config::Config,
+    file: &Path,
+    force: bool,
+) -> Result<()> {
+    let home = crate::config::opencrabs_home();
+    let db_path = &config.database.path;
+    let staged = stage_import(file, &home, db_path).await?;
+
+    let manifest = &staged.manifest;
+    println!(
+        "📦 Export from OpenCrabs {} ({}), {} files{}",
+        manifest.version,
+        manifest.created_at.format("%Y-%m-%d %H:%M UTC"),
+        staged.files.len(),
+        if manifest.secrets {
+            ", credentials included"
+        } else {
+            ""
+        }
+    );
+    if staged
+        .schema_version
+        .is_some_and(|v| v < Database::latest_schema_version())
+    {
+        println!("   The sessions database is older and will be migrated after restoring.");
+    }
+
+    let overwrites: Vec<&Path> = staged.overwrites().collect();
+    if !overwrites.is_empty() {
+        println!("\n⚠️  These files will be replaced:\n");
+        for path in &overwrites {
+            println!("   • {}", path.display());
+        }
+        println!("\n   Close any running OpenCrabs first.");
+        if !force {
+            use std::io::{self, Write};
+            print!("Type 'yes' to confirm import: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            if input.trim().to_lowercase() != "yes" {
+                staged.discard();
+                println!("❌ Cancelled - nothing was changed");
+                return Ok(());
+            }
+        }
+    }
+
+    let secrets = staged.manifest.secrets;
+    staged.restore(db_path).await?;
+    println!("✅ Restored into {}", home.display());
+    if !secrets {
+        println!("   The archive had no credentials; existing keys.toml was kept.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn seeded_home() -> (TempDir, PathBuf) {
+        let home = TempDir::new().unwrap();
+        let db_path = home.path().join("opencrabs.db");
+        Database::connect(&db_path)
+            .await
+            .unwrap()
+            .run_migrations()
+            .await
+            .unwrap();
+        std::fs::write(
+            home.path().join("config.toml"),
+            "[providers.anthropic]\nenabled = true\napi_key = \"sk-ant-secret\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            home.path().join("keys.toml"),
+            "[providers.openai]\napi_key = \"sk-x\"\n",
+        )
+        .unwrap();
+        std::fs::write(home.path().join("SOUL.md"), "I am a careful crab.").unwrap();
+        std::fs::create_dir_all(home.path().join("memory/2026")).unwrap();
+        std::fs::write(home.path().join("memory/2026/notes.md"), "remember this").unwrap();
+        (home, db_path)
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let (source, source_db) = seeded_home().await;
+        let archive = source.path().join("backup.tar.gz");
+        let entries = export_state(source.path(), &source_db, &archive, false)
+            .await
+            .unwrap();
+        assert!(entries.contains(&"opencrabs.db".to_string()));
+        assert!(entries.contains(&"memory/2026/notes.md".to_string()));
+        assert!(!entries.contains(&"keys.toml".to_string()));
+
+        let target = TempDir::new().unwrap();
+        let target_db = target.path().join("data/sessions.db");
+        let staged = stage_import(&archive, target.path(), &target_db)
+            .await
+            .unwrap();
+        assert!(!staged.manifest.secrets);
+        assert_eq!(
+            staged.schema_version,
+            Some(Database::latest_schema_version())
+        );
+        assert_eq!(staged.overwrites().count(), 0);
+        staged.restore(&target_db).await.unwrap();
+
+        assert!(target_db.is_file());
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("SOUL.md")).unwrap(),
+            "I am a careful crab."
+        );
+        assert!(target.path().join("memory/2026/notes.md").is_file());
+        let config = std::fs::read_to_string(target.path().join("config.toml")).unwrap();
+        assert!(config.contains("enabled = true"));
+        assert!(!config.contains("sk-ant-secret"));
+        assert!(!target.path().join("keys.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_newer_schema() {
+        let (source, source_db) = seeded_home().await;
+        let db = Database::connect(&source_db).await.unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+             VALUES (99990101000001, 'from the future', 1, x'00', 0)",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+        db.close().await.unwrap();
+
+        let archive = source.path().join("backup.tar.gz");
+        export_state(source.path(), &source_db, &archive, true)
+            .await
+            .unwrap();
+
+        let target = TempDir::new().unwrap();
+        let err = stage_import(&archive, target.path(), &target.path().join("opencrabs.db"))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("newer database schema"));
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_links() {
+        let source = TempDir::new().unwrap();
+        for kind in [tar::EntryType::Symlink, tar::EntryType::Link] {
+            let archive = source.path().join("links.tar.gz");
+            let mut tar = tar::Builder::new(GzEncoder::new(
+                File::create(&archive).unwrap(),
+                Compression::default(),
+            ));
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(kind);
+            header.set_size(0);
+            tar.append_link(&mut header, "SOUL.md", "/etc/passwd")
+                .unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+
+            let target = TempDir::new().unwrap();
+            let err = stage_import(&archive, target.path(), &target.path().join("opencrabs.db"))
+                .await
+                .err()
+                .unwrap();
+            assert!(err.to_string().contains("only regular files"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_only_export_entries_are_allowed() {
+        for ok in ["manifest.toml", "opencrabs.db", "memory/a/b.md", "SOUL.md"] {
+            assert!(is_allowed_entry(Path::new(ok)), "{ok}");
+        }
+        for bad in [
+            "../etc/passwd",
+            "/abs",
+            "memory",
+            "bin/opencrabs",
+            "x/SOUL.md",
+        ] {
+            assert!(!is_allowed_entry(Path::new(bad)), "{bad}");
+        }
+    }
+}
@@ -20,8 +20,20 @@ pub(crate) async fn load_config(config_path: Option<&str>) -> Result<crate::conf
         Config::load()?
     };
 
-    // Validate configuration
-    config.validate()?;
+    // Validate configuration — errors stop startup, warnings are shown
+    let mut report = config.check();
+    for path in Config::source_paths(config_path.map(std::path::Path::new)) {
+        report.add_unknown_keys(&path);
+    }
+    if report.has_errors() {
+        anyhow::bail!("{}", report.render());
+    }
+    for warning in report.warnings() {
+        tracing::warn!("Config: {}", warning);
+    }
+    if !report.diagnostics.is_empty() {
+        eprintln!("{}", report.render());
+    }
 
     Ok(config)
 }
@@ -59,7 +71,23 @@ pub(crate) async fn cmd_init(_config: &crate::config::Config, force: bool) -> Re
 }
 
 /// Show configuration
-pub(crate) async fn cmd_config(config: &crate::config::Config, show_secrets: bool) -> Result<()> {
+pub(crate) async fn cmd_config(
+    config: &crate::config::Config,
+    show: bool,
+    show_secrets: bool,
+) -> Result<()> {
+    if show {
+        // Plain TOML so it can be piped or diffed against config.toml
+        let toml = if show_secrets {
+            toml::to_string_pretty(config)?
+        } else {
+            config.to_redacted_toml()?
+        };
+        println!("# Effective OpenCrabs config (files, keys.toml, env and defaults)");
+        print!("{}", toml);
+        return Ok(());
+    }
+
     println!("🦀 OpenCrabs Configuration\n");
 
     if show_secrets {
@@ -105,7 +133,7 @@ pub(crate) async fn cmd_config(config: &crate::config::Config, show_secrets: boo
             );
         }
 
-        println!("\n💡 Use --show-secrets to display API keys");
+        println!("\n💡 Use --show for every setting in effect, --show-secrets to display API keys");
     }
 
     Ok(())
@@ -215,30 +243,128 @@ pub(crate) async fn cmd_db(config: &crate::config::Config, operation: DbCommands
     }
 }
 
+/// Options for a one-shot `run` invocation
+pub(crate) struct RunOptions {
+    pub auto_approve: bool,
+    pub format: OutputFormat,
+    pub session: Option<String>,
+    pub session_name: Option<String>,
+    pub no_tools: bool,
+    pub quiet: bool,
+}
+
+/// How `run` reports progress on stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProgressMode {
+    /// Emoji status lines, for a terminal
+    Pretty,
+    /// Bare `thinking…` / `tool: <name>` / `done` lines, for logs and pipes
+    Plain,
+    /// Nothing but the answer (`--quiet`)
+    Quiet,
+}
+
+impl ProgressMode {
+    fn detect(quiet: bool, stderr_is_terminal: bool) -> Self {
+        match (quiet, stderr_is_terminal) {
+            (true, _) => Self::Quiet,
+            (false, true) => Self::Pretty,
+            (false, false) => Self::Plain,
+        }
+    }
+
+    /// Status line for `event`, if this mode shows one.
+    fn line(self, event: &crate::brain::agent::ProgressEvent) -> Option<String> {
+        use crate::brain::agent::ProgressEvent;
+
+        let pretty = match self {
+            Self::Quiet => return None,
+            Self::Pretty => true,
+            Self::Plain => false,
+        };
+        let line = match event {
+            ProgressEvent::Thinking if pretty => "🤔 Thinking...".to_string(),
+            ProgressEvent::Thinking => "thinking…".to_string(),
+            ProgressEvent::ToolStarted { tool_name, .. } if pretty => format!("🔧 {tool_name}"),
+            ProgressEvent::ToolStarted { tool_name, .. } => format!("tool: {tool_name}"),
+            ProgressEvent::ToolCompleted {
+                tool_name,
+                success: false,
+                ..
+            } if pretty => format!("❌ {tool_name} failed"),
+            ProgressEvent::ToolCompleted {
+                tool_name,
+                success: false,
+                ..
+            } => format!("tool failed: {tool_name}"),
+            ProgressEvent::Compacting if pretty => "🗜️  Compacting context...".to_string(),
+            ProgressEvent::Compacting => "compacting context…".to_string(),
+            _ => return None,
+        };
+        Some(line)
+    }
+}
+
+/// Combine the prompt argument with piped stdin (if any).
+///
+/// A prompt of `-` means "stdin only". Errors when both are empty.
+fn combine_run_prompt(prompt: Option<String>, stdin_text: &str) -> Result<String> {
+    let prompt = prompt.filter(|p| p != "-" && !p.trim().is_empty());
+    let stdin_text = stdin_text.trim();
+    match (prompt, stdin_text.is_empty()) {
+        (Some(p), true) => Ok(p),
+        (Some(p), false) => Ok(format!("{p}\n\n{stdin_text}")),
+        (None, false) => Ok(stdin_text.to_string()),
+        (None, true) => {
+            anyhow::bail!("No prompt given — pass it as an argument, with --prompt, or via stdin")
+        }
+    }
+}
+
 /// Run a single command non-interactively
 pub(crate) async fn cmd_run(
     config: &crate::config::Config,
-    prompt: String,
-    auto_approve: bool,
-    format: OutputFormat,
+    prompt: Option<String>,
+    options: RunOptions,
 ) -> Result<()> {
     use crate::{
         brain::{
             agent::AgentService,
             tools::{
-                bash::BashTool, brave_search::BraveSearchTool, code_exec::CodeExecTool,
-                config_tool::ConfigTool, context::ContextTool, doc_parser::DocParserTool,
-                edit::EditTool, exa_search::ExaSearchTool, glob::GlobTool, grep::GrepTool,
-                http::HttpClientTool, ls::LsTool, memory_search::MemorySearchTool,
-                notebook::NotebookEditTool, plan_tool::PlanTool, read::ReadTool,
-                registry::ToolRegistry, session_search::SessionSearchTool,
-                slash_command::SlashCommandTool, task::TaskTool, web_search::WebSearchTool,
-                write::WriteTool,
+                apply_patch::ApplyPatchTool, bash::BashTool, brave_search::BraveSearchTool,
+                code_exec::CodeExecTool, config_tool::ConfigTool, context::ContextTool,
+                doc_parser::DocParserTool, edit::EditTool, exa_search::ExaSearchTool,
+                glob::GlobTool, grep::GrepTool, http::HttpClientTool, list_files::ListFilesTool,
+                ls::LsTool, memory_search::MemorySearchTool, notebook::NotebookEditTool,
+                plan_tool::PlanTool, read::ReadTool, read_more::ReadMoreTool,
+                registry::ToolRegistry, session_search::SessionSearchTool, sessions::SessionsTool,
+                shell::ShellTool, slash_command::SlashCommandTool, task::TaskTool,
+                web_search::WebSearchTool, write::WriteTool,
             },
         },
         db::Database,
         services::{ServiceContext, SessionService},
     };
+    use std::io::{IsTerminal, Read};
+
+    let RunOptions {
+        auto_approve,
+        format,
+        session,
+        session_name,
+        no_tools,
+        quiet,
+    } = options;
+    let progress = ProgressMode::detect(quiet, std::io::stderr().is_terminal());
+
+    // Piped input (e.g. `opencrabs run "summarize" < file.txt`) is appended to the prompt
+    let mut stdin_text = String::new();
+    if !std::io::stdin().is_terminal() {
+        std::io::stdin()
+            .read_to_string(&mut stdin_text)
+            .context("Failed to read prompt from stdin")?;
+    }
+    let prompt = combine_run_prompt(prompt, &stdin_text)?;
 
     tracing::info!("Running non-interactive command: {}", prompt);
 
@@ -249,58 +375,76 @@ pub(crate) async fn cmd_run(
     // Select provider based on configuration using factory
     let provider = crate::brain::provider::create_provider(config)?;
 
-    // Create tool registry
-    let mut tool_registry = ToolRegistry::new();
-    // Phase 1: Essential file operations
-    tool_registry.register(Arc::new(ReadTool));
-    tool_registry.register(Arc::new(WriteTool));
-    tool_registry.register(Arc::new(EditTool));
-    tool_registry.register(Arc::new(BashTool));
-    tool_registry.register(Arc::new(LsTool));
-    tool_registry.register(Arc::new(GlobTool));
-    tool_registry.register(Arc::new(GrepTool));
-    // Phase 2: Advanced features
-    tool_registry.register(Arc::new(WebSearchTool));
-    tool_registry.register(Arc::new(CodeExecTool));
-    tool_registry.register(Arc::new(NotebookEditTool));
-    tool_registry.register(Arc::new(DocParserTool));
-    // Phase 3: Workflow & integration
-    tool_registry.register(Arc::new(TaskTool));
-    tool_registry.register(Arc::new(ContextTool));
-    tool_registry.register(Arc::new(HttpClientTool));
-    tool_registry.register(Arc::new(PlanTool));
-    // Memory search (built-in FTS5, always available)
-    tool_registry.register(Arc::new(MemorySearchTool));
-    // Session search — hybrid QMD search across all session message history
-    tool_registry.register(Arc::new(SessionSearchTool::new(db.pool().clone())));
-    // Config management (read/write config.toml, commands.toml)
-    tool_registry.register(Arc::new(ConfigTool));
-    // Slash command invocation (agent can call any slash command)
-    tool_registry.register(Arc::new(SlashCommandTool));
-    // EXA search: always available (free via MCP), uses direct API if key is set
-    let exa_key = config
-        .providers
-        .web_search
-        .as_ref()
-        .and_then(|ws| ws.exa.as_ref())
-        .and_then(|p| p.api_key.clone())
-        .filter(|k| !k.is_empty());
-    tool_registry.register(Arc::new(ExaSearchTool::new(exa_key)));
-    // Brave search: requires enabled = true in config.toml AND API key in keys.toml
-    if let Some(brave_cfg) = config
-        .providers
-        .web_search
-        .as_ref()
-        .and_then(|ws| ws.brave.as_ref())
-        && brave_cfg.enabled
-        && let Some(brave_key) = brave_cfg.api_key.clone()
-    {
-        tool_registry.register(Arc::new(BraveSearchTool::new(brave_key)));
+    // Whoever runs the CLI is the owner; set once the session is known
+    let owner_session: Arc<tokio::sync::Mutex<Option<uuid::Uuid>>> = Arc::default();
+
+    // Create tool registry (left empty with --no-tools)
+    let mut tool_registry = ToolRegistry::with_policy(&config.tools);
+    if !no_tools {
+        // Phase 1: Essential file operations
+        tool_registry.register(Arc::new(ReadTool));
+        tool_registry.register(Arc::new(WriteTool));
+        tool_registry.register(Arc::new(EditTool));
+        tool_registry.register(Arc::new(ApplyPatchTool));
+        tool_registry.register(Arc::new(BashTool));
+        tool_registry.register(Arc::new(ShellTool::new(config.tools.shell.allowed.clone())));
+        tool_registry.register(Arc::new(LsTool));
+        tool_registry.register(Arc::new(ListFilesTool));
+        tool_registry.register(Arc::new(GlobTool));
+        tool_registry.register(Arc::new(GrepTool));
+        // Phase 2: Advanced features
+        tool_registry.register(Arc::new(WebSearchTool));
+        tool_registry.register(Arc::new(CodeExecTool));
+        tool_registry.register(Arc::new(NotebookEditTool));
+        tool_registry.register(Arc::new(DocParserTool));
+        // Phase 3: Workflow & integration
+        tool_registry.register(Arc::new(TaskTool));
+        tool_registry.register(Arc::new(ContextTool));
+        tool_registry.register(Arc::new(HttpClientTool));
+        tool_registry.register(Arc::new(PlanTool));
+        // Memory search (built-in FTS5, always available)
+        tool_registry.register(Arc::new(MemorySearchTool));
+        // Pages through tool results truncated at [tools] max_result_chars
+        tool_registry.register(Arc::new(ReadMoreTool::new(config.tools.max_result_chars)));
+        // Session search — hybrid QMD search across all session message history
+        tool_registry.register(Arc::new(SessionSearchTool::new(db.pool().clone())));
+        // Sessions — list sessions and read another session's recent messages (read-only)
+        tool_registry.register(Arc::new(SessionsTool::new(
+            ServiceContext::new(db.pool().clone()),
+            owner_session.clone(),
+        )));
+        // Config management (read/write config.toml, commands.toml)
+        tool_registry.register(Arc::new(ConfigTool));
+        // Slash command invocation (agent can call any slash command)
+        tool_registry.register(Arc::new(SlashCommandTool));
+        // EXA search: always available (free via MCP), uses direct API if key is set
+        let exa_key = config
+            .providers
+            .web_search
+            .as_ref()
+            .and_then(|ws| ws.exa.as_ref())
+            .and_then(|p| p.api_key.clone())
+            .filter(|k| !k.is_empty());
+        tool_registry.register(Arc::new(ExaSearchTool::new(exa_key)));
+        // Brave search: requires enabled = true in config.toml AND API key in keys.toml
+        if let Some(brave_cfg) = config
+            .providers
+            .web_search
+            .as_ref()
+            .and_then(|ws| ws.brave.as_ref())
+            && brave_cfg.enabled
+            && let Some(brave_key) = brave_cfg.api_key.clone()
+        {
+            tool_registry.register(Arc::new(BraveSearchTool::new(brave_key)));
+        }
     }
+    tool_registry.validate()?;
 
     // Build dynamic system brain from workspace files
     let brain_path = BrainLoader::resolve_path();
-    let brain_loader = BrainLoader::new(brain_path.clone());
+    let brain_loader = BrainLoader::new(brain_path.clone())
+        .with_policy(&config.brain)
+        .with_agent_name(&config.agent.name);
     let runtime_info = RuntimeInfo {
         model: Some(provider.default_model().to_string()),
         provider: Some(provider.name().to_string()),
@@ -317,36 +461,75 @@ pub(crate) async fn cmd_run(
     let service_context = ServiceContext::new(db.pool().clone());
     let agent_service = AgentService::new(provider.clone(), service_context.clone())
         .with_tool_registry(Arc::new(tool_registry))
-        .with_system_brain(system_brain);
+        .with_system_brain(system_brain)
+        .with_auto_approve_tools(auto_approve);
 
-    // Create or get session
+    // Resume the given session, the named one, or create an ephemeral one
     let session_service = SessionService::new(service_context);
 
-    let session = session_service
-        .create_session(Some("CLI Run".to_string()))
-        .await?;
+    let session_id = match (session, session_name) {
+        (_, Some(name)) => session_service.get_or_create_by_name(&name).await?.id,
+        (Some(id), None) => {
+            let id = uuid::Uuid::parse_str(&id)
+                .with_context(|| format!("Invalid session ID: {}", id))?;
+            session_service
+                .get_session(id)
+                .await?
+                .with_context(|| format!("Session not found: {}", id))?
+                .id
+        }
+        (None, None) => {
+            session_service
+                .create_session(Some("CLI Run".to_string()))
+                .await?
+                .id
+        }
+    };
+
+    *owner_session.lock().await = Some(session_id);
 
-    // Send message
-    println!("🤔 Processing...\n");
-    let response = agent_service.send_message(session.id, prompt, None).await?;
+    // Send message — progress goes to stderr so stdout stays pipeable
+    let progress_cb: crate::brain::agent::ProgressCallback = Arc::new(move |_, event| {
+        if let Some(line) = progress.line(&event) {
+            eprintln!("{line}");
+        }
+    });
+    let response = agent_service
+        .send_message_with_tools_and_callback(
+            session_id,
+            prompt,
+            None,
+            None,
+            None,
+            Some(progress_cb),
+        )
+        .await?;
 
     // Format and display output
+    let tokens = response.usage.total();
     match format {
         OutputFormat::Text => {
             println!("{}", response.content);
-            println!();
-            println!(
-                "📊 Tokens: {}",
-                response.usage.input_tokens + response.usage.output_tokens
-            );
-            println!("💰 Cost: ${:.6}", response.cost);
+            match progress {
+                ProgressMode::Pretty => {
+                    eprintln!();
+                    eprintln!("📊 Tokens: {}", tokens);
+                    eprintln!("💰 Cost: ${:.6}", response.cost);
+                }
+                ProgressMode::Plain => {
+                    eprintln!("done ({} tokens, ${:.6})", tokens, response.cost);
+                }
+                ProgressMode::Quiet => {}
+            }
         }
         OutputFormat::Json => {
             let output = serde_json::json!({
                 "content": response.content,
+                "session_id": session_id,
                 "usage": {
-                    "input_tokens": response.usage.input_tokens,
+                    "input_tokens": response.usage.prompt_tokens(),
                     "output_tokens": response.usage.output_tokens,
+                    "cache_read_input_tokens": response.usage.cache_read_input_tokens,
                 },
                 "cost": response.cost,
                 "model": response.model,
@@ -357,16 +540,13 @@ pub(crate) async fn cmd_run(
             println!("# Response\n");
             println!("{}\n", response.content);
             println!("---");
-            println!(
-                "**Tokens:** {}",
-                response.usage.input_tokens + response.usage.output_tokens
-            );
+            println!("**Tokens:** {}", tokens);
             println!("**Cost:** ${:.6}", response.cost);
         }
     }
 
-    if auto_approve {
-        println!("\n⚠️  Auto-approve mode was enabled");
+    if auto_approve && progress != ProgressMode::Quiet {
+        eprintln!("\n⚠️  Auto-approve mode was enabled");
     }
 
     Ok(())
@@ -520,3 +700,64 @@ pub(crate) async fn cmd_logs(operation: LogCommands) -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_run_prompt_appends_stdin() {
+        let prompt = combine_run_prompt(Some("summarize".to_string()), "file body\n").unwrap();
+        assert_eq!(prompt, "summarize\n\nfile body");
+    }
+
+    #[test]
+    fn test_combine_run_prompt_stdin_only() {
+        assert_eq!(combine_run_prompt(None, "hello").unwrap(), "hello");
+        assert_eq!(
+            combine_run_prompt(Some("-".to_string()), "hello").unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn test_progress_mode_plain_has_no_escapes_or_emoji() {
+        use crate::brain::agent::ProgressEvent;
+
+        assert_eq!(ProgressMode::detect(false, true), ProgressMode::Pretty);
+        assert_eq!(ProgressMode::detect(false, false), ProgressMode::Plain);
+        assert_eq!(ProgressMode::detect(true, true), ProgressMode::Quiet);
+
+        let started = ProgressEvent::ToolStarted {
+            tool_name: "http_request".to_string(),
+            tool_input: serde_json::json!({}),
+        };
+        assert_eq!(
+            ProgressMode::Plain.line(&started).as_deref(),
+            Some("tool: http_request")
+        );
+        assert_eq!(
+            ProgressMode::Plain
+                .line(&ProgressEvent::Thinking)
+                .as_deref(),
+            Some("thinking…")
+        );
+        assert!(ProgressMode::Quiet.line(&started).is_none());
+        assert!(
+            ProgressMode::Plain
+                .line(&ProgressEvent::StreamingChunk {
+                    text: "hi".to_string()
+                })
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_combine_run_prompt_requires_input() {
+        assert!(combine_run_prompt(None, "  \n").is_err());
+        assert_eq!(
+            combine_run_prompt(Some("hi".to_string()), "").unwrap(),
+            "hi"
+        );
+    }
+}
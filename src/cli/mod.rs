@@ -2,6 +2,7 @@
 //!
 //! Command-line interface for OpenCrabs using Clap v4.
 
+mod archive;
 mod commands;
 mod cron;
 mod ui;
@@ -18,10 +19,24 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub debug: bool,
 
+    /// Log verbosity: -v info, -vv debug, -vvv trace (RUST_LOG overrides)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
     /// Configuration file path
     #[arg(short, long, global = true)]
     pub config: Option<String>,
 
+    /// Workspace directory — isolated config, sessions DB and memory
+    /// (default: ~/.opencrabs, or $OPENCRABS_HOME)
+    #[arg(short, long, global = true, value_name = "DIR")]
+    pub workspace: Option<std::path::PathBuf>,
+
+    /// Recovery launch: chat with the default provider only — no brain files,
+    /// tools, channels or schedulers. Falls back to default config if it won't load
+    #[arg(long, global = true)]
+    pub safe_mode: bool,
+
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -44,9 +59,15 @@ pub enum Commands {
     Onboard,
 
     /// Run a single command non-interactively
+    ///
+    /// Piped stdin is appended to the prompt, e.g. `opencrabs run "summarize" < file.txt`.
     Run {
-        /// The prompt to execute
-        prompt: String,
+        /// The prompt to execute (omit to read it entirely from stdin)
+        prompt: Option<String>,
+
+        /// The prompt to execute (alternative to the positional argument)
+        #[arg(long = "prompt", value_name = "PROMPT", conflicts_with = "prompt")]
+        prompt_opt: Option<String>,
 
         /// Auto-approve all tool executions (dangerous!)
         #[arg(long, alias = "yolo")]
@@ -55,6 +76,27 @@ pub enum Commands {
         /// Output format
         #[arg(short, long, default_value = "text")]
         format: OutputFormat,
+
+        /// Print JSON with content, usage and cost (shorthand for --format json)
+        #[arg(long)]
+        json: bool,
+
+        /// Session ID to run in (default: a new ephemeral session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Named session to run in, created on first use — repeated runs with
+        /// the same name share history
+        #[arg(long, value_name = "NAME", conflicts_with = "session")]
+        session_name: Option<String>,
+
+        /// Disable tool use — plain completion for deterministic output
+        #[arg(long)]
+        no_tools: bool,
+
+        /// Print only the answer — no progress or usage lines on stderr
+        #[arg(short, long)]
+        quiet: bool,
     },
 
     /// Initialize configuration
@@ -66,6 +108,10 @@ pub enum Commands {
 
     /// Show configuration
     Config {
+        /// Print the full effective config as TOML (secrets redacted)
+        #[arg(long)]
+        show: bool,
+
         /// Show full configuration including secrets
         #[arg(short, long)]
         show_secrets: bool,
@@ -83,6 +129,26 @@ pub enum Commands {
         operation: LogCommands,
     },
 
+    /// Bundle config, sessions DB, memory and brain files into one .tar.gz
+    Export {
+        /// Archive to write, e.g. opencrabs-backup.tar.gz
+        file: std::path::PathBuf,
+
+        /// Include keys.toml and inline credentials (keep the archive private)
+        #[arg(long)]
+        include_secrets: bool,
+    },
+
+    /// Restore an archive written by `opencrabs export`
+    Import {
+        /// Archive to restore
+        file: std::path::PathBuf,
+
+        /// Replace existing files without asking
+        #[arg(short, long)]
+        force: bool,
+    },
+
     /// Run in headless daemon mode — no TUI, channel bots only (Telegram, Discord, Slack, WhatsApp)
     /// Used by the systemd/LaunchAgent service installed during onboarding
     Daemon,
@@ -213,8 +279,15 @@ pub async fn run() -> Result<()> {
         tracing::info!("Debug mode enabled");
     }
 
-    // Load configuration
-    let config = commands::load_config(cli.config.as_deref()).await?;
+    // Load configuration — safe mode must still start when the config is broken
+    let config = match commands::load_config(cli.config.as_deref()).await {
+        Ok(config) => config,
+        Err(e) if cli.safe_mode => {
+            eprintln!("⚠️  Config failed to load: {e:#}\n   Safe mode continues with defaults.");
+            crate::config::Config::default()
+        }
+        Err(e) => return Err(e),
+    };
 
     // Auto-generate config.toml if API keys exist in env but no config file yet.
     // This prevents the onboarding wizard from triggering when .env is already set up.
@@ -240,6 +313,9 @@ pub async fn run() -> Result<()> {
                 Some(Commands::Chat { session, onboard }) => (session.clone(), *onboard),
                 _ => (None, false),
             };
+            if cli.safe_mode {
+                return ui::cmd_chat_safe(&config, session).await;
+            }
             ui::cmd_chat(&config, session, force_onboard).await
         }
         Some(Commands::Onboard) => {
@@ -247,16 +323,38 @@ pub async fn run() -> Result<()> {
             ui::cmd_chat(&config, None, true).await
         }
         Some(Commands::Init { force }) => commands::cmd_init(&config, force).await,
-        Some(Commands::Config { show_secrets }) => {
-            commands::cmd_config(&config, show_secrets).await
+        Some(Commands::Config { show, show_secrets }) => {
+            commands::cmd_config(&config, show, show_secrets).await
         }
         Some(Commands::Db { operation }) => commands::cmd_db(&config, operation).await,
         Some(Commands::Logs { operation }) => commands::cmd_logs(operation).await,
+        Some(Commands::Export {
+            file,
+            include_secrets,
+        }) => archive::cmd_export(&config, &file, include_secrets).await,
+        Some(Commands::Import { file, force }) => archive::cmd_import(&config, &file, force).await,
         Some(Commands::Run {
             prompt,
+            prompt_opt,
             auto_approve,
             format,
-        }) => commands::cmd_run(&config, prompt, auto_approve, format).await,
+            json,
+            session,
+            session_name,
+            no_tools,
+            quiet,
+        }) => {
+            let format = if json { OutputFormat::Json } else { format };
+            let options = commands::RunOptions {
+                auto_approve,
+                format,
+                session,
+                session_name,
+                no_tools,
+                quiet,
+            };
+            commands::cmd_run(&config, prompt.or(prompt_opt), options).await
+        }
         Some(Commands::Daemon) => ui::cmd_daemon(&config).await,
         Some(Commands::Cron { operation }) => cron::cmd_cron(&config, operation).await,
     }
@@ -19,6 +19,106 @@ pub(crate) async fn cmd_chat(
     cmd_chat_inner(config, session_id, force_onboard, false).await
 }
 
+/// System prompt used instead of the brain files in safe mode. Names the
+/// files this process actually uses, so `--workspace`/`--config` are honoured.
+fn safe_mode_brain() -> String {
+    let home = crate::config::opencrabs_home();
+    let config_path =
+        crate::config::Config::system_config_path().unwrap_or_else(|| home.join("config.toml"));
+    format!(
+        "You are OpenCrabs running in SAFE MODE. The user launched with --safe-mode because a \
+         normal start failed or misbehaved. No brain files, tools, channels or schedulers are \
+         loaded, so you cannot read or edit files yourself. Help the user find and fix the \
+         problem in {} (config), {} (API keys) or the brain files such as SOUL.md in {} by \
+         telling them exactly what to change. /rebuild still works and restarts normally.",
+        config_path.display(),
+        crate::config::keys_path().display(),
+        home.display()
+    )
+}
+
+/// Recovery launch (`--safe-mode`): the TUI with the default provider only —
+/// no brain files, tools, channels, schedulers or config watcher.
+pub(crate) async fn cmd_chat_safe(
+    config: &crate::config::Config,
+    session_id: Option<String>,
+) -> Result<()> {
+    use crate::{brain::agent::AgentService, db::Database, services::ServiceContext, tui};
+
+    println!("\n\x1b[38;2;215;100;20m🦀 Safe mode — brain, tools and channels are off.\x1b[0m");
+
+    let db = Database::connect(&config.database.path)
+        .await
+        .context("Failed to connect to database")?;
+    db.run_migrations()
+        .await
+        .context("Failed to run database migrations")?;
+
+    // Default provider only — no fallback chain
+    let mut provider_config = config.clone();
+    if let Some(fallback) = provider_config.providers.fallback.as_mut() {
+        fallback.enabled = false;
+    }
+    let provider = crate::brain::provider::create_provider(&provider_config)?;
+    tracing::info!("Safe mode: using provider {}", provider.name());
+
+    let service_context = ServiceContext::new(db.pool().clone());
+    let working_directory = std::env::current_dir().unwrap_or_default();
+
+    let mut app = tui::App::new(
+        Arc::new(AgentService::new(provider.clone(), service_context.clone())),
+        service_context.clone(),
+    );
+
+    // Stream replies into the chat; everything else is tool/channel chatter we don't have
+    let progress_sender = app.event_sender();
+    let progress_callback: crate::brain::agent::ProgressCallback =
+        Arc::new(move |session_id, event| {
+            use crate::brain::agent::ProgressEvent;
+            use crate::tui::events::TuiEvent;
+
+            let result = match event {
+                ProgressEvent::StreamingChunk { text } => {
+                    progress_sender.send(TuiEvent::ResponseChunk { session_id, text })
+                }
+                ProgressEvent::TokenCount(count) => {
+                    progress_sender.send(TuiEvent::TokenCountUpdated { session_id, count })
+                }
+                _ => return,
+            };
+            if let Err(e) = result {
+                tracing::error!("Progress event channel closed: {}", e);
+            }
+        });
+
+    app.set_agent_service(Arc::new(
+        AgentService::new(provider, service_context)
+            .with_system_brain(safe_mode_brain())
+            .with_progress_callback(Some(progress_callback))
+            .with_working_directory(working_directory),
+    ));
+
+    crate::tui::events::keys::set_keymap(crate::tui::keymap::KeyMap::from_config(
+        &config.keybindings,
+    ));
+    app.mouse_enabled = config.tui.mouse;
+    app.max_content_width = config.tui.max_content_width;
+    app.save_draft = config.tui.save_draft;
+    app.greeting = config.tui.greeting.clone();
+    app.agent = config.agent.clone();
+    app.safe_mode = true;
+    app.user_commands.clear();
+
+    if let Some(ref sid) = session_id
+        && let Ok(uuid) = uuid::Uuid::parse_str(sid)
+    {
+        app.resume_session_id = Some(uuid);
+    }
+
+    tui::run(app).await.context("TUI error")?;
+    Ok(())
+}
+
 async fn cmd_chat_inner(
     config: &crate::config::Config,
     session_id: Option<String>,
@@ -29,15 +129,17 @@ async fn cmd_chat_inner(
         brain::{
             agent::AgentService,
             tools::{
-                analyze_image::AnalyzeImageTool, bash::BashTool, brave_search::BraveSearchTool,
-                code_exec::CodeExecTool, config_tool::ConfigTool, context::ContextTool,
-                doc_parser::DocParserTool, edit::EditTool, exa_search::ExaSearchTool,
-                generate_image::GenerateImageTool, glob::GlobTool, grep::GrepTool,
-                http::HttpClientTool, load_brain_file::LoadBrainFileTool, ls::LsTool,
-                memory_search::MemorySearchTool, notebook::NotebookEditTool, plan_tool::PlanTool,
-                provider_vision::ProviderVisionTool, read::ReadTool, registry::ToolRegistry,
-                session_search::SessionSearchTool, slash_command::SlashCommandTool, task::TaskTool,
-                web_search::WebSearchTool, write::WriteTool,
+                analyze_image::AnalyzeImageTool, apply_patch::ApplyPatchTool, bash::BashTool,
+                brave_search::BraveSearchTool, code_exec::CodeExecTool, config_tool::ConfigTool,
+                context::ContextTool, doc_parser::DocParserTool, edit::EditTool,
+                exa_search::ExaSearchTool, generate_image::GenerateImageTool, glob::GlobTool,
+                grep::GrepTool, http::HttpClientTool, list_files::ListFilesTool,
+                load_brain_file::LoadBrainFileTool, ls::LsTool, memory_search::MemorySearchTool,
+                notebook::NotebookEditTool, plan_tool::PlanTool,
+                provider_vision::ProviderVisionTool, read::ReadTool, read_more::ReadMoreTool,
+                registry::ToolRegistry, session_search::SessionSearchTool, sessions::SessionsTool,
+                shell::ShellTool, slash_command::SlashCommandTool, task::TaskTool,
+                update_brain::UpdateBrainTool, web_search::WebSearchTool, write::WriteTool,
                 write_opencrabs_file::WriteOpenCrabsFileTool,
             },
         },
@@ -85,15 +187,23 @@ async fn cmd_chat_inner(
     let provider = crate::brain::provider::create_provider(config)?;
     tracing::info!("Using provider: {}", provider.name());
 
+    // The owner's current session, shared by the TUI, owner channel messages
+    // and tools that are owner-only
+    let shared_session_id: Arc<tokio::sync::Mutex<Option<uuid::Uuid>>> =
+        Arc::new(tokio::sync::Mutex::new(None));
+
     // Create tool registry
     tracing::debug!("Setting up tool registry");
-    let mut tool_registry = ToolRegistry::new();
+    let mut tool_registry = ToolRegistry::with_policy(&config.tools);
     // Phase 1: Essential file operations
     tool_registry.register(Arc::new(ReadTool));
     tool_registry.register(Arc::new(WriteTool));
     tool_registry.register(Arc::new(EditTool));
+    tool_registry.register(Arc::new(ApplyPatchTool));
     tool_registry.register(Arc::new(BashTool));
+    tool_registry.register(Arc::new(ShellTool::new(config.tools.shell.allowed.clone())));
     tool_registry.register(Arc::new(LsTool));
+    tool_registry.register(Arc::new(ListFilesTool));
     tool_registry.register(Arc::new(GlobTool));
     tool_registry.register(Arc::new(GrepTool));
     // Phase 2: Advanced features
@@ -108,12 +218,21 @@ async fn cmd_chat_inner(
     tool_registry.register(Arc::new(PlanTool));
     // Memory search (built-in FTS5, always available)
     tool_registry.register(Arc::new(MemorySearchTool));
+    // Pages through tool results truncated at [tools] max_result_chars
+    tool_registry.register(Arc::new(ReadMoreTool::new(config.tools.max_result_chars)));
     // On-demand brain file loader — agent fetches USER.md, MEMORY.md etc. only when needed
     tool_registry.register(Arc::new(LoadBrainFileTool));
     // OpenCrabs file writer — agent can edit/append/overwrite any file in ~/.opencrabs/
     tool_registry.register(Arc::new(WriteOpenCrabsFileTool));
+    // Update brain — insert entries under a heading in a brain file and reindex it
+    tool_registry.register(Arc::new(UpdateBrainTool));
     // Session search — hybrid QMD search across all session message history
     tool_registry.register(Arc::new(SessionSearchTool::new(db.pool().clone())));
+    // Sessions — list sessions and read another session's recent messages (read-only)
+    tool_registry.register(Arc::new(SessionsTool::new(
+        ServiceContext::new(db.pool().clone()),
+        shared_session_id.clone(),
+    )));
     // Channel search — search passively captured channel messages (Telegram groups, etc.)
     use crate::brain::tools::channel_search::ChannelSearchTool;
     tool_registry.register(Arc::new(ChannelSearchTool::new(
@@ -124,6 +243,29 @@ async fn cmd_chat_inner(
     tool_registry.register(Arc::new(CronManageTool::new(
         crate::db::CronJobRepository::new(db.pool().clone()),
     )));
+    // Reminders — agent can schedule one-shot reminders delivered to the owner
+    {
+        use crate::brain::tools::reminder::{
+            CancelReminderTool, ListRemindersTool, ScheduleTaskTool, SetReminderTool,
+        };
+        let reminder_repo = crate::db::ReminderRepository::new(db.pool().clone());
+        tool_registry.register(Arc::new(SetReminderTool::new(
+            reminder_repo.clone(),
+            shared_session_id.clone(),
+        )));
+        tool_registry.register(Arc::new(ScheduleTaskTool::new(
+            reminder_repo.clone(),
+            shared_session_id.clone(),
+        )));
+        tool_registry.register(Arc::new(ListRemindersTool::new(
+            reminder_repo.clone(),
+            shared_session_id.clone(),
+        )));
+        tool_registry.register(Arc::new(CancelReminderTool::new(
+            reminder_repo,
+            shared_session_id.clone(),
+        )));
+    }
     // A2A send — agent can communicate with remote A2A agents
     use crate::brain::tools::a2a_send::A2aSendTool;
     tool_registry.register(Arc::new(A2aSendTool::new()));
@@ -163,11 +305,23 @@ async fn cmd_chat_inner(
     if config.image.generation.enabled
         && let Some(ref key) = config.image.generation.api_key
     {
-        tool_registry.register(Arc::new(GenerateImageTool::new(
-            key.clone(),
-            config.image.generation.model.clone(),
-        )));
-        tracing::info!("Registered generate_image tool");
+        use crate::brain::tools::generate_image::ImageBackend;
+        match ImageBackend::from_name(&config.image.generation.provider) {
+            Some(backend) => {
+                tool_registry.register(Arc::new(
+                    GenerateImageTool::new(key.clone(), config.image.generation.model.clone())
+                        .with_backend(backend),
+                ));
+                tracing::info!(
+                    "Registered generate_image tool ({})",
+                    config.image.generation.provider
+                );
+            }
+            None => tracing::warn!(
+                "[image.generation] unknown provider '{}' — generate_image disabled",
+                config.image.generation.provider
+            ),
+        }
     }
     // Image vision tool — prefer Gemini, fall back to provider's vision_model
     if config.image.vision.enabled
@@ -216,7 +370,9 @@ async fn cmd_chat_inner(
 
     // Build dynamic system brain from workspace files
     let brain_path = BrainLoader::resolve_path();
-    let brain_loader = BrainLoader::new(brain_path.clone());
+    let brain_loader = BrainLoader::new(brain_path.clone())
+        .with_policy(&config.brain)
+        .with_agent_name(&config.agent.name);
     let command_loader = CommandLoader::from_brain_path(&brain_path);
     let user_commands = command_loader.load();
 
@@ -244,6 +400,7 @@ async fn cmd_chat_inner(
     // Create TUI app first (so we can get the event sender)
     tracing::debug!("Creating TUI app");
     let mut app = tui::App::new(agent_service, service_context.clone());
+    app.shared_session_id = shared_session_id;
 
     // Get event sender from app
     let event_sender = app.event_sender();
@@ -266,6 +423,7 @@ async fn cmd_chat_inner(
                 tool_description: tool_info.tool_description,
                 tool_input: tool_info.tool_input,
                 capabilities: tool_info.capabilities,
+                forced: tool_info.forced,
                 response_tx,
                 requested_at: std::time::Instant::now(),
             };
@@ -280,24 +438,15 @@ async fn cmd_chat_inner(
                     ))
                 })?;
 
-            // Wait for response with timeout to prevent indefinite hang
-            let response =
-                tokio::time::timeout(std::time::Duration::from_secs(120), response_rx.recv())
-                    .await
-                    .map_err(|_| {
-                        tracing::warn!("Approval request timed out after 120s, auto-denying");
-                        crate::brain::agent::AgentError::Internal(
-                            "Approval request timed out (120s) — auto-denied".to_string(),
-                        )
-                    })?
-                    .ok_or_else(|| {
-                        tracing::warn!("Approval response channel closed unexpectedly");
-                        crate::brain::agent::AgentError::Internal(
-                            "Approval response channel closed".to_string(),
-                        )
-                    })?;
+            // The tool loop gives up after [tools] approval_timeout_secs
+            let response = response_rx.recv().await.ok_or_else(|| {
+                tracing::warn!("Approval response channel closed unexpectedly");
+                crate::brain::agent::AgentError::Internal(
+                    "Approval response channel closed".to_string(),
+                )
+            })?;
 
-            Ok((response.approved, false))
+            Ok(response.decision)
         })
     });
 
@@ -337,6 +486,13 @@ async fn cmd_chat_inner(
                     success,
                     summary,
                 }),
+                ProgressEvent::ToolOutput { tool_name, line } => {
+                    progress_sender.send(TuiEvent::ToolOutput {
+                        session_id,
+                        tool_name,
+                        line,
+                    })
+                }
                 ProgressEvent::IntermediateText { text, reasoning } => {
                     progress_sender.send(TuiEvent::IntermediateText {
                         session_id,
@@ -364,8 +520,8 @@ async fn cmd_chat_inner(
                         summary,
                     })
                 }
-                ProgressEvent::RestartReady { status } => {
-                    progress_sender.send(TuiEvent::RestartReady(status))
+                ProgressEvent::RestartReady { status, diff } => {
+                    progress_sender.send(TuiEvent::RestartReady { status, diff })
                 }
                 ProgressEvent::TokenCount(count) => {
                     // Real count from the API — update baseline and reset streaming accumulator.
@@ -376,6 +532,27 @@ async fn cmd_chat_inner(
                 ProgressEvent::ReasoningChunk { text } => {
                     progress_sender.send(TuiEvent::ReasoningChunk { session_id, text })
                 }
+                ProgressEvent::AutoContinued { attempt } => {
+                    progress_sender.send(TuiEvent::AutoContinued {
+                        session_id,
+                        attempt,
+                    })
+                }
+                ProgressEvent::StreamResumed { attempt } => {
+                    progress_sender.send(TuiEvent::StreamResumed {
+                        session_id,
+                        attempt,
+                    })
+                }
+                ProgressEvent::HistoryTrimmed { dropped } => {
+                    progress_sender.send(TuiEvent::HistoryTrimmed {
+                        session_id,
+                        dropped,
+                    })
+                }
+                ProgressEvent::CapabilityWarning { message } => {
+                    progress_sender.send(TuiEvent::SystemMessage(format!("⚠️ {}", message)))
+                }
             };
             if let Err(e) = result {
                 tracing::error!("Progress event channel closed: {}", e);
@@ -495,6 +672,10 @@ async fn cmd_chat_inner(
         crate::brain::tools::slack_send::SlackSendTool::new(slack_state.clone()),
     ));
 
+    // Shared Matrix state (client, pending approvals, cancel tokens)
+    #[cfg(feature = "matrix")]
+    let matrix_state = Arc::new(crate::channels::matrix::MatrixState::new());
+
     // Shared Trello state for proactive card operations
     #[cfg(feature = "trello")]
     let trello_state = Arc::new(crate::channels::trello::TrelloState::new());
@@ -573,6 +754,7 @@ async fn cmd_chat_inner(
 
     // Create agent service with approval callback, progress callback, and message queue
     tracing::debug!("Creating agent service with approval, progress, and message queue callbacks");
+    tool_registry.validate()?;
     let shared_tool_registry = Arc::new(tool_registry);
 
     // Now that the registry is Arc'd, give it to the channel factory
@@ -591,13 +773,19 @@ async fn cmd_chat_inner(
             .with_message_queue_callback(Some(message_queue_callback))
             .with_sudo_callback(Some(sudo_callback))
             .with_working_directory(working_directory.clone())
-            .with_brain_path(brain_path)
-            .with_session_updated_tx(session_updated_tx),
+            .with_brain_path(brain_path.clone())
+            .with_session_updated_tx(session_updated_tx)
+            .with_live_config(channel_factory.config_rx()),
     );
 
     // Update app with the configured agent service (preserve event channels!)
     app.set_agent_service(agent_service);
 
+    // Key remapping from [keybindings]
+    crate::tui::events::keys::set_keymap(crate::tui::keymap::KeyMap::from_config(
+        &config.keybindings,
+    ));
+
     // Spawn config hot-reload watcher — fires on any change to config.toml, keys.toml,
     // or commands.toml without requiring a restart.
     {
@@ -614,7 +802,14 @@ async fn cmd_chat_inner(
             let sender = app.event_sender();
             callbacks.push(Arc::new(move |cfg: crate::config::Config| {
                 // Broadcast full config to all channels via watch channel
-                let _ = config_tx.send(cfg.clone());
+                let old = config_tx.send_replace(cfg.clone());
+                let report = crate::config::ReloadReport::between(&old, &cfg);
+
+                if old.logging.level != cfg.logging.level
+                    && let Err(e) = crate::logging::set_log_level(&cfg.logging.level)
+                {
+                    tracing::warn!("ConfigWatcher: log level not applied: {}", e);
+                }
 
                 // Provider swap still needs explicit call
                 let agent = agent.clone();
@@ -633,14 +828,38 @@ async fn cmd_chat_inner(
                     }
                 });
 
+                crate::tui::events::keys::set_keymap(crate::tui::keymap::KeyMap::from_config(
+                    &cfg.keybindings,
+                ));
+
                 // TUI refresh — commands autocomplete + approval policy
-                let _ = sender.send(TuiEvent::ConfigReloaded);
+                let _ = sender.send(TuiEvent::ConfigReloaded(report));
             }));
         }
 
         let _config_watcher = config_watcher::spawn(callbacks);
+
+        // SIGHUP re-reads config the same way /reload does
+        #[cfg(unix)]
+        tokio::spawn(async {
+            use tokio::signal::unix::{SignalKind, signal};
+            let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+                tracing::warn!("ConfigWatcher: cannot listen for SIGHUP");
+                return;
+            };
+            while hangup.recv().await.is_some() {
+                tracing::info!("ConfigWatcher: SIGHUP received, reloading config");
+                config_watcher::trigger_reload();
+            }
+        });
     }
 
+    app.mouse_enabled = config.tui.mouse;
+    app.max_content_width = config.tui.max_content_width;
+    app.save_draft = config.tui.save_draft;
+    app.greeting = config.tui.greeting.clone();
+    app.agent = config.agent.clone();
+
     // Set force onboard flag if requested
     if force_onboard {
         app.force_onboard = true;
@@ -666,6 +885,37 @@ async fn cmd_chat_inner(
         tracing::info!("Cron scheduler spawned");
     }
 
+    // Spawn reminder scheduler — fires due reminders and scheduled tasks to the
+    // TUI and owner's channel
+    {
+        let reminder_repo = crate::db::ReminderRepository::new(db.pool().clone());
+        let reminder_scheduler = crate::cron::ReminderScheduler::new(reminder_repo)
+            .with_tui(app.event_sender())
+            .with_agent(channel_factory.clone());
+        #[cfg(feature = "telegram")]
+        let reminder_scheduler = reminder_scheduler.with_telegram(telegram_state.clone());
+        #[cfg(feature = "discord")]
+        let reminder_scheduler = reminder_scheduler.with_discord(discord_state.clone());
+        #[cfg(feature = "whatsapp")]
+        let reminder_scheduler = reminder_scheduler.with_whatsapp(whatsapp_state.clone());
+        let _reminder_handle = reminder_scheduler.spawn();
+        tracing::info!("Reminder scheduler spawned");
+    }
+
+    // Spawn heartbeat scheduler — idles until [heartbeat] enabled = true (hot-reloaded)
+    {
+        let heartbeat_scheduler =
+            crate::cron::HeartbeatScheduler::new(channel_factory.clone(), brain_path);
+        #[cfg(feature = "telegram")]
+        let heartbeat_scheduler = heartbeat_scheduler.with_telegram(telegram_state.clone());
+        #[cfg(feature = "discord")]
+        let heartbeat_scheduler = heartbeat_scheduler.with_discord(discord_state.clone());
+        #[cfg(feature = "whatsapp")]
+        let heartbeat_scheduler = heartbeat_scheduler.with_whatsapp(whatsapp_state.clone());
+        let _heartbeat_handle = heartbeat_scheduler.spawn();
+        tracing::info!("Heartbeat scheduler spawned");
+    }
+
     // Spawn A2A gateway if configured
     if config.a2a.enabled {
         let a2a_agent = channel_factory.create_agent_service();
@@ -704,7 +954,8 @@ async fn cmd_chat_inner(
 
         if tg.enabled && has_valid_token {
             if let Some(ref token) = tg_token {
-                let tg_agent = channel_factory.create_agent_service();
+                let tg_agent = channel_factory
+                    .create_channel_agent_service(crate::channels::MessageSource::Telegram);
                 let bot = crate::channels::telegram::TelegramAgent::new(
                     tg_agent,
                     service_context.clone(),
@@ -733,7 +984,8 @@ async fn cmd_chat_inner(
         let wa = &config.channels.whatsapp;
         if wa.enabled {
             let wa_agent = crate::channels::whatsapp::WhatsAppAgent::new(
-                channel_factory.create_agent_service(),
+                channel_factory
+                    .create_channel_agent_service(crate::channels::MessageSource::WhatsApp),
                 service_context.clone(),
                 app.shared_session_id(),
                 whatsapp_state.clone(),
@@ -763,7 +1015,8 @@ async fn cmd_chat_inner(
         if dc.enabled && has_valid_token {
             if let Some(ref token) = dc_token {
                 let dc_agent = crate::channels::discord::DiscordAgent::new(
-                    channel_factory.create_agent_service(),
+                    channel_factory
+                        .create_channel_agent_service(crate::channels::MessageSource::Discord),
                     service_context.clone(),
                     app.shared_session_id(),
                     discord_state.clone(),
@@ -801,7 +1054,8 @@ async fn cmd_chat_inner(
         if sl.enabled && has_valid_tokens {
             if let (Some(bot_tok), Some(app_tok)) = (sl_token, sl_app_token) {
                 let sl_agent = crate::channels::slack::SlackAgent::new(
-                    channel_factory.create_agent_service(),
+                    channel_factory
+                        .create_channel_agent_service(crate::channels::MessageSource::Slack),
                     service_context.clone(),
                     app.shared_session_id(),
                     slack_state.clone(),
@@ -822,6 +1076,45 @@ async fn cmd_chat_inner(
         }
     };
 
+    // Spawn Matrix bot if configured (needs homeserver, user ID and access token)
+    #[cfg(feature = "matrix")]
+    let _matrix_handle = {
+        let mx = &config.channels.matrix;
+        let non_empty = |v: &Option<String>| v.clone().filter(|s| !s.trim().is_empty());
+        match (
+            non_empty(&mx.homeserver),
+            non_empty(&mx.user_id),
+            non_empty(&mx.access_token),
+        ) {
+            (Some(homeserver), Some(user_id), Some(token)) if mx.enabled => {
+                let mx_agent = crate::channels::matrix::MatrixAgent::new(
+                    channel_factory
+                        .create_channel_agent_service(crate::channels::MessageSource::Matrix),
+                    service_context.clone(),
+                    app.shared_session_id(),
+                    matrix_state.clone(),
+                    channel_factory.config_rx(),
+                    crate::db::ChannelMessageRepository::new(db.pool().clone()),
+                );
+                tracing::info!(
+                    "Spawning Matrix bot ({} allowed user(s))",
+                    mx.allowed_users.len()
+                );
+                Some(mx_agent.start(homeserver, user_id, token))
+            }
+            _ => {
+                if mx.enabled {
+                    tracing::debug!("Matrix enabled but homeserver, user_id or token missing");
+                }
+                None
+            }
+        }
+    };
+    #[cfg(not(feature = "matrix"))]
+    if config.channels.matrix.enabled {
+        tracing::warn!("Matrix is enabled in config but this build lacks the `matrix` feature");
+    }
+
     // Spawn Trello agent if configured (polling-based, needs API Key + API Token + board IDs)
     #[cfg(feature = "trello")]
     let _trello_handle = {
@@ -838,7 +1131,8 @@ async fn cmd_chat_inner(
         if tr.enabled && has_valid_creds && has_boards {
             if let (Some(api_key), Some(api_token)) = (tr_api_key, tr_api_token) {
                 let tr_agent = crate::channels::trello::TrelloAgent::new(
-                    channel_factory.create_agent_service(),
+                    channel_factory
+                        .create_channel_agent_service(crate::channels::MessageSource::Trello),
                     service_context.clone(),
                     tr.allowed_users.clone(),
                     app.shared_session_id(),
@@ -0,0 +1,105 @@
+//! `[general]` settings and the user's clock.
+//!
+//! Headless deployments often run in UTC while the user lives elsewhere, so
+//! `[general] timezone` (an IANA name like "Europe/Lisbon") pins daily memory
+//! logs, reminder times and displayed timestamps to the user's zone. Unset
+//! keeps the system local time.
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+/// `[general]` section — app-wide settings.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GeneralConfig {
+    /// IANA timezone for dates and times (default: system local)
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Active timezone, set whenever the config is loaded
+static TIMEZONE: RwLock<Option<Tz>> = RwLock::new(None);
+
+/// Parse an IANA name. Empty means "system local".
+fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// Apply `[general] timezone`. Unknown names are logged and fall back to
+/// system local time.
+pub fn set_timezone(name: Option<&str>) {
+    let tz = name.filter(|n| !n.trim().is_empty()).and_then(|n| {
+        let tz = parse_timezone(n);
+        if tz.is_none() {
+            tracing::warn!("[general] timezone: unknown timezone '{n}', using system local");
+        }
+        tz
+    });
+    *TIMEZONE.write().unwrap_or_else(|e| e.into_inner()) = tz;
+}
+
+/// The configured timezone, or `None` for system local time.
+pub fn timezone() -> Option<Tz> {
+    *TIMEZONE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Current time in the user's timezone.
+pub fn local_now() -> DateTime<FixedOffset> {
+    to_local(Utc::now())
+}
+
+/// A UTC timestamp in the user's timezone.
+pub fn to_local(ts: DateTime<Utc>) -> DateTime<FixedOffset> {
+    in_zone(timezone(), ts)
+}
+
+/// Today's date (`YYYY-MM-DD`) in the user's timezone — daily log names.
+pub fn local_today() -> String {
+    local_now().format("%Y-%m-%d").to_string()
+}
+
+/// Format a UTC timestamp in the user's timezone. `%Z` prints the zone
+/// abbreviation when a timezone is configured.
+pub fn format_local(ts: DateTime<Utc>, fmt: &str) -> String {
+    match timezone() {
+        Some(tz) => ts.with_timezone(&tz).format(fmt).to_string(),
+        None => ts.with_timezone(&Local).format(fmt).to_string(),
+    }
+}
+
+fn in_zone(tz: Option<Tz>, ts: DateTime<Utc>) -> DateTime<FixedOffset> {
+    match tz {
+        Some(tz) => ts.with_timezone(&tz).fixed_offset(),
+        None => ts.with_timezone(&Local).fixed_offset(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(
+            parse_timezone("America/New_York"),
+            Some(Tz::America__New_York)
+        );
+        assert_eq!(parse_timezone(" UTC "), Some(Tz::UTC));
+        assert_eq!(parse_timezone("Mars/Olympus"), None);
+    }
+
+    #[test]
+    fn test_date_boundary_follows_timezone() {
+        // 23:30 UTC is already tomorrow in Tokyo and still today in New York
+        let ts = Utc.with_ymd_and_hms(2025, 3, 1, 23, 30, 0).unwrap();
+        let tokyo = in_zone(Some(Tz::Asia__Tokyo), ts);
+        assert_eq!(
+            tokyo.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-03-02 08:30"
+        );
+        let new_york = in_zone(Some(Tz::America__New_York), ts);
+        assert_eq!(new_york.format("%Y-%m-%d").to_string(), "2025-03-01");
+    }
+}
@@ -3,11 +3,21 @@
 //! Handles application configuration loading, validation, and management.
 
 pub mod crabrace;
+mod general;
+mod owner;
+mod reload;
 pub mod secrets;
 mod types;
 pub mod update;
+mod validate;
 
 pub use crabrace::{CrabraceConfig, CrabraceIntegration};
+pub use general::{
+    GeneralConfig, format_local, local_now, local_today, set_timezone, timezone, to_local,
+};
+pub use owner::OwnerConfig;
+pub use reload::ReloadReport;
 pub use secrets::SecretString;
 pub use types::*;
 pub use update::{ProviderUpdater, UpdateResult};
+pub use validate::{Diagnostic, Severity, ValidationReport, unknown_keys};
@@ -0,0 +1,216 @@
+//! Owner identity shared across all channels.
+//!
+//! The owner is the person whose channel messages share the TUI session.
+//! `[owner]` maps that person to their ID on each channel so every handler
+//! resolves "is owner" the same way. When a channel's ID is unset, the legacy
+//! heuristic applies: the first entry of that channel's allowlist is the owner,
+//! and an empty allowlist makes everyone the owner.
+
+use super::Config;
+use serde::{Deserialize, Serialize};
+
+/// `[owner]` section — the owner's ID on each channel.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OwnerConfig {
+    /// Telegram numeric user ID
+    #[serde(default)]
+    pub telegram_id: Option<String>,
+
+    /// Discord numeric user ID
+    #[serde(default)]
+    pub discord_id: Option<String>,
+
+    /// Slack member ID (e.g. "U0123ABCD")
+    #[serde(default)]
+    pub slack_id: Option<String>,
+
+    /// WhatsApp phone number in international format (leading '+' optional)
+    #[serde(default)]
+    pub whatsapp_phone: Option<String>,
+
+    /// Matrix user ID (e.g. "@alice:example.org")
+    #[serde(default)]
+    pub matrix_id: Option<String>,
+}
+
+/// Returns the configured owner ID if present and non-empty, else the first allowlist entry.
+fn resolve<'a>(explicit: Option<&'a String>, allowlist: &'a [String]) -> Option<&'a str> {
+    explicit
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .or_else(|| allowlist.first().map(|s| s.trim()))
+}
+
+impl Config {
+    /// Whether a Telegram user is the owner.
+    pub fn is_telegram_owner(&self, user_id: i64) -> bool {
+        match resolve(
+            self.owner.telegram_id.as_ref(),
+            &self.channels.telegram.allowed_users,
+        ) {
+            Some(owner) => owner.parse::<i64>().ok() == Some(user_id),
+            None => true,
+        }
+    }
+
+    /// Whether a Discord user is the owner.
+    pub fn is_discord_owner(&self, user_id: u64) -> bool {
+        match resolve(
+            self.owner.discord_id.as_ref(),
+            &self.channels.discord.allowed_users,
+        ) {
+            Some(owner) => owner.parse::<u64>().ok() == Some(user_id),
+            None => true,
+        }
+    }
+
+    /// Whether a Slack user is the owner.
+    pub fn is_slack_owner(&self, user_id: &str) -> bool {
+        match resolve(
+            self.owner.slack_id.as_ref(),
+            &self.channels.slack.allowed_users,
+        ) {
+            Some(owner) => owner == user_id,
+            None => true,
+        }
+    }
+
+    /// Whether a Matrix user is the owner. Unlike the other channels, an
+    /// empty allowlist makes nobody the owner: anyone can invite a Matrix bot.
+    pub fn is_matrix_owner(&self, user_id: &str) -> bool {
+        resolve(
+            self.owner.matrix_id.as_ref(),
+            &self.channels.matrix.allowed_users,
+        )
+        .is_some_and(|owner| owner == user_id)
+    }
+
+    /// Whether a Matrix user may invite and message the bot: listed in
+    /// `allowed_users`, or the explicit owner. Nobody is when both are unset.
+    pub fn is_matrix_allowed(&self, user_id: &str) -> bool {
+        self.channels
+            .matrix
+            .allowed_users
+            .iter()
+            .any(|u| u.trim() == user_id)
+            || self
+                .owner
+                .matrix_id
+                .as_deref()
+                .is_some_and(|owner| owner.trim() == user_id)
+    }
+
+    /// The owner's ID on `channel` ("telegram", "discord", "slack", "matrix"
+    /// or "whatsapp"), or `None` when no owner resolves. Unlike the
+    /// `is_*_owner` checks, an empty allowlist does not make everyone the owner.
+    pub fn channel_owner_id(&self, channel: &str) -> Option<String> {
+        let c = &self.channels;
+        match channel {
+            "telegram" => resolve(self.owner.telegram_id.as_ref(), &c.telegram.allowed_users),
+            "discord" => resolve(self.owner.discord_id.as_ref(), &c.discord.allowed_users),
+            "slack" => resolve(self.owner.slack_id.as_ref(), &c.slack.allowed_users),
+            "matrix" => resolve(self.owner.matrix_id.as_ref(), &c.matrix.allowed_users),
+            "whatsapp" => return self.whatsapp_owner_phone(),
+            _ => None,
+        }
+        .map(str::to_string)
+    }
+
+    /// The owner's WhatsApp phone, normalized without the leading '+'.
+    pub fn whatsapp_owner_phone(&self) -> Option<String> {
+        resolve(
+            self.owner.whatsapp_phone.as_ref(),
+            &self.channels.whatsapp.allowed_phones,
+        )
+        .map(|p| p.trim_start_matches('+').to_string())
+    }
+
+    /// Whether a WhatsApp phone number (with or without '+') is the owner.
+    pub fn is_whatsapp_owner(&self, phone: &str) -> bool {
+        match self.whatsapp_owner_phone() {
+            Some(owner) => owner == phone.trim_start_matches('+'),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_falls_back_to_first_allowed_user() {
+        let mut config = Config::default();
+        config.channels.telegram.allowed_users = vec!["111".to_string(), "222".to_string()];
+        assert!(config.is_telegram_owner(111));
+        assert!(!config.is_telegram_owner(222));
+    }
+
+    #[test]
+    fn test_owner_empty_allowlist_means_everyone() {
+        let config = Config::default();
+        assert!(config.is_telegram_owner(42));
+        assert!(config.is_discord_owner(42));
+        assert!(config.is_slack_owner("U1"));
+        // Matrix bots accept invites from anyone, so empty means nobody
+        assert!(!config.is_matrix_owner("@anyone:example.org"));
+        assert!(!config.is_matrix_allowed("@anyone:example.org"));
+        assert!(config.is_whatsapp_owner("+15551234567"));
+    }
+
+    #[test]
+    fn test_explicit_owner_overrides_allowlist_order() {
+        let mut config = Config::default();
+        config.channels.discord.allowed_users = vec!["1".to_string(), "2".to_string()];
+        config.owner.discord_id = Some("2".to_string());
+        assert!(config.is_discord_owner(2));
+        assert!(!config.is_discord_owner(1));
+    }
+
+    #[test]
+    fn test_explicit_owner_with_empty_allowlist_is_exclusive() {
+        let mut config = Config::default();
+        config.owner.slack_id = Some("UOWNER".to_string());
+        assert!(config.is_slack_owner("UOWNER"));
+        assert!(!config.is_slack_owner("UOTHER"));
+    }
+
+    #[test]
+    fn test_matrix_allows_listed_users_and_explicit_owner() {
+        let mut config = Config::default();
+        config.owner.matrix_id = Some("@owner:example.org".to_string());
+        assert!(config.is_matrix_allowed("@owner:example.org"));
+        assert!(config.is_matrix_owner("@owner:example.org"));
+        assert!(!config.is_matrix_allowed("@other:example.org"));
+
+        config.channels.matrix.allowed_users = vec!["@alice:example.org".to_string()];
+        assert!(config.is_matrix_allowed("@alice:example.org"));
+        assert!(!config.is_matrix_owner("@alice:example.org"));
+    }
+
+    #[test]
+    fn test_whatsapp_owner_normalizes_plus() {
+        let mut config = Config::default();
+        config.owner.whatsapp_phone = Some("+15551234567".to_string());
+        assert_eq!(
+            config.whatsapp_owner_phone().as_deref(),
+            Some("15551234567")
+        );
+        assert!(config.is_whatsapp_owner("15551234567"));
+        assert!(config.is_whatsapp_owner("+15551234567"));
+        assert!(!config.is_whatsapp_owner("15550000000"));
+    }
+
+    #[test]
+    fn test_owner_section_from_toml() {
+        let toml_content = r#"
+[owner]
+telegram_id = "123456"
+whatsapp_phone = "+351912345678"
+        "#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.owner.telegram_id.as_deref(), Some("123456"));
+        assert!(config.owner.discord_id.is_none());
+        assert!(config.is_telegram_owner(123456));
+    }
+}
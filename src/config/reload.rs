@@ -0,0 +1,199 @@
+//! What a config reload can apply live.
+//!
+//! Compares the running config with the freshly loaded one and sorts the
+//! changed settings into those picked up immediately (pushed to channels,
+//! provider swap, keymap, approval policy, log level, timezone, tool limits) and those
+//! only read at startup, which need a restart.
+
+use super::Config;
+use serde::Serialize;
+
+/// Changed settings, split by whether they took effect.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    pub applied: Vec<String>,
+    pub deferred: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Sort every setting that differs between `old` and `new`.
+    pub fn between(old: &Config, new: &Config) -> Self {
+        let mut report = Self::default();
+
+        for (name, changed) in [
+            ("providers", differs(&old.providers, &new.providers)),
+            ("voice", differs(&old.voice, &new.voice)),
+            ("owner", differs(&old.owner, &new.owner)),
+            ("keybindings", differs(&old.keybindings, &new.keybindings)),
+            (
+                "agent.approval_policy",
+                old.agent.approval_policy != new.agent.approval_policy,
+            ),
+            ("logging.level", old.logging.level != new.logging.level),
+            ("general.timezone", differs(&old.general, &new.general)),
+            ("sessions", differs(&old.sessions, &new.sessions)),
+            ("dev", differs(&old.dev, &new.dev)),
+            (
+                "tools.approval_timeout_secs",
+                old.tools.approval_timeout_secs != new.tools.approval_timeout_secs,
+            ),
+            (
+                "tools.max_result_chars",
+                old.tools.max_result_chars != new.tools.max_result_chars,
+            ),
+            (
+                "tools.inject_tools_md",
+                old.tools.inject_tools_md != new.tools.inject_tools_md,
+            ),
+        ] {
+            if changed {
+                report.applied.push(name.to_string());
+            }
+        }
+
+        // Allowlists, respond_to etc. reach the channels through the config
+        // watch channel; starting or stopping a bot does not.
+        let (old_enabled, new_enabled) = (enabled_channels(old), enabled_channels(new));
+        if differs(
+            &without_enabled_flags(old.clone()).channels,
+            &without_enabled_flags(new.clone()).channels,
+        ) {
+            report.applied.push("channels".to_string());
+        }
+        for ((name, was), (_, now)) in old_enabled.iter().zip(&new_enabled) {
+            if was != now {
+                report.deferred.push(format!("channels.{name}.enabled"));
+            }
+        }
+        // The shared agent semaphore is sized once
+        if old.channels.max_concurrent != new.channels.max_concurrent {
+            report.deferred.push("channels.max_concurrent".to_string());
+        }
+
+        // Everything else is read once at startup
+        let mut old_agent = old.agent.clone();
+        old_agent.approval_policy = new.agent.approval_policy.clone();
+        let mut old_tools = old.tools.clone();
+        old_tools.approval_timeout_secs = new.tools.approval_timeout_secs;
+        old_tools.max_result_chars = new.tools.max_result_chars;
+        old_tools.inject_tools_md = new.tools.inject_tools_md;
+        for (name, changed) in [
+            ("agent", differs(&old_agent, &new.agent)),
+            ("database", differs(&old.database, &new.database)),
+            ("logging.file", old.logging.file != new.logging.file),
+            ("a2a", differs(&old.a2a, &new.a2a)),
+            ("image", differs(&old.image, &new.image)),
+            ("tools", differs(&old_tools, &new.tools)),
+            ("heartbeat", differs(&old.heartbeat, &new.heartbeat)),
+            ("boot", differs(&old.boot, &new.boot)),
+            ("tui", differs(&old.tui, &new.tui)),
+            ("context", differs(&old.context, &new.context)),
+            ("audit", differs(&old.audit, &new.audit)),
+            ("sampling", differs(&old.sampling, &new.sampling)),
+            ("models", differs(&old.models, &new.models)),
+            ("chat", differs(&old.chat, &new.chat)),
+            ("brain", differs(&old.brain, &new.brain)),
+            ("debug", differs(&old.debug, &new.debug)),
+            ("crabrace", differs(&old.crabrace, &new.crabrace)),
+        ] {
+            if changed {
+                report.deferred.push(name.to_string());
+            }
+        }
+
+        report
+    }
+
+    /// Status message for `/reload`.
+    pub fn summary(&self) -> String {
+        if self.applied.is_empty() && self.deferred.is_empty() {
+            return "Config reloaded — no changes.".to_string();
+        }
+        let mut lines = vec!["Config reloaded.".to_string()];
+        if !self.applied.is_empty() {
+            lines.push(format!("  Applied: {}", self.applied.join(", ")));
+        }
+        if !self.deferred.is_empty() {
+            lines.push(format!("  Requires restart: {}", self.deferred.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
+fn differs<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() != serde_json::to_value(b).ok()
+}
+
+fn enabled_channels(cfg: &Config) -> [(&'static str, bool); 9] {
+    let c = &cfg.channels;
+    [
+        ("telegram", c.telegram.enabled),
+        ("discord", c.discord.enabled),
+        ("whatsapp", c.whatsapp.enabled),
+        ("slack", c.slack.enabled),
+        ("trello", c.trello.enabled),
+        ("signal", c.signal.enabled),
+        ("google_chat", c.google_chat.enabled),
+        ("imessage", c.imessage.enabled),
+        ("matrix", c.matrix.enabled),
+    ]
+}
+
+fn without_enabled_flags(mut cfg: Config) -> Config {
+    let c = &mut cfg.channels;
+    c.telegram.enabled = false;
+    c.discord.enabled = false;
+    c.whatsapp.enabled = false;
+    c.slack.enabled = false;
+    c.trello.enabled = false;
+    c.signal.enabled = false;
+    c.google_chat.enabled = false;
+    c.imessage.enabled = false;
+    c.matrix.enabled = false;
+    c.max_concurrent = 0;
+    cfg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_changes() {
+        let cfg = Config::default();
+        let report = ReloadReport::between(&cfg, &cfg.clone());
+        assert_eq!(report, ReloadReport::default());
+        assert_eq!(report.summary(), "Config reloaded — no changes.");
+    }
+
+    #[test]
+    fn test_sorts_live_and_restart_settings() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.agent.approval_policy = "auto-always".to_string();
+        new.agent.context_limit += 1;
+        new.channels.telegram.allowed_users = vec!["42".to_string()];
+        new.channels.discord.enabled = !old.channels.discord.enabled;
+        new.database.path = "/tmp/other.db".into();
+        new.tools.approval_timeout_secs += 1;
+
+        let report = ReloadReport::between(&old, &new);
+        assert_eq!(
+            report.applied,
+            vec![
+                "agent.approval_policy",
+                "tools.approval_timeout_secs",
+                "channels"
+            ]
+        );
+        assert_eq!(
+            report.deferred,
+            vec!["channels.discord.enabled", "agent", "database"]
+        );
+
+        new.tools.disabled = vec!["shell".to_string()];
+        let report = ReloadReport::between(&old, &new);
+        assert!(report.deferred.contains(&"tools".to_string()));
+        assert!(report.summary().contains("Requires restart: "));
+    }
+}
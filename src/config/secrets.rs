@@ -96,10 +96,82 @@ impl From<&str> for SecretString {
     }
 }
 
+/// Config keys whose values are credentials
+const SECRET_KEYS: &[&str] = &["api_key", "token", "app_token", "access_token"];
+
+/// Whether a config key holds a credential
+fn is_secret_key(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+        || key.ends_with("_secret")
+        || key.ends_with("_password")
+        || key == "password"
+}
+
+/// Replace every non-empty credential in a TOML tree with `[REDACTED]`.
+/// Empty values stay empty so "not set" is still visible.
+pub fn redact_toml(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, v) in table.iter_mut() {
+                match v {
+                    toml::Value::String(s) if is_secret_key(key) && !s.is_empty() => {
+                        *s = "[REDACTED]".to_string();
+                    }
+                    _ => redact_toml(v),
+                }
+            }
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(redact_toml),
+        _ => {}
+    }
+}
+
+/// Remove every credential from a TOML tree, for copies that leave the
+/// machine without them.
+pub fn strip_secrets(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            table.retain(|key, v| !(is_secret_key(key) && v.is_str()));
+            table.values_mut().for_each(strip_secrets);
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(strip_secrets),
+        _ => {}
+    }
+}
+
+impl super::Config {
+    /// The resolved config (files, `keys.toml`, env overrides and defaults)
+    /// as TOML, with credentials redacted.
+    pub fn to_redacted_toml(&self) -> anyhow::Result<String> {
+        let mut value = toml::Value::try_from(self)?;
+        redact_toml(&mut value);
+        Ok(toml::to_string_pretty(&value)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_redacted_toml_masks_credentials() {
+        let config: crate::config::Config = toml::from_str(
+            "[providers.anthropic]\napi_key = \"sk-ant-secret\"\n\
+             [channels.slack]\nenabled = true\ntoken = \"xoxb-secret\"\napp_token = \"\"\n\
+             [agent]\nmax_tokens = 1234",
+        )
+        .unwrap();
+        let shown = config.to_redacted_toml().unwrap();
+        assert!(!shown.contains("sk-ant-secret"));
+        assert!(!shown.contains("xoxb-secret"));
+        assert!(shown.contains("api_key = \"[REDACTED]\""));
+        assert!(shown.contains("app_token = \"\""));
+        // Not a credential despite the name
+        assert!(shown.contains("max_tokens = 1234"));
+        // Still valid TOML
+        assert!(toml::from_str::<toml::Value>(&shown).is_ok());
+    }
+
     #[test]
     fn test_secret_string_creation() {
         let secret = SecretString::from_str("my-secret-key");
@@ -1,11 +1,14 @@
 //! Configuration types, defaults, loading, and validation.
 
 use super::crabrace::CrabraceConfig;
+use super::general::GeneralConfig;
+use super::owner::OwnerConfig;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +52,384 @@ pub struct Config {
     /// Image generation and vision configuration
     #[serde(default)]
     pub image: ImageConfig,
+
+    /// Owner identity shared across all channels
+    #[serde(default)]
+    pub owner: OwnerConfig,
+
+    /// Tool policy (disabled tools, forced approval)
+    #[serde(default)]
+    pub tools: ToolsConfig,
+
+    /// Periodic autonomous turns driven by HEARTBEAT.md
+    #[serde(default)]
+    pub heartbeat: HeartbeatConfig,
+
+    /// Startup routine driven by BOOT.md
+    #[serde(default)]
+    pub boot: BootConfig,
+
+    /// TUI key remapping — action name → key spec(s)
+    #[serde(default)]
+    pub keybindings: BTreeMap<String, KeySpec>,
+
+    /// Terminal UI behaviour
+    #[serde(default)]
+    pub tui: TuiConfig,
+
+    /// Conversation context handling
+    #[serde(default)]
+    pub context: ContextConfig,
+
+    /// App-wide settings (timezone)
+    #[serde(default)]
+    pub general: GeneralConfig,
+
+    /// Tool-call and response audit trail
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// Default sampling parameters for every request
+    #[serde(default)]
+    pub sampling: SamplingConfig,
+
+    /// Per-model sampling overrides — model name → `[models."<name>"]`
+    #[serde(default)]
+    pub models: BTreeMap<String, SamplingConfig>,
+
+    /// Channel session lifecycle
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+
+    /// Standing instructions wrapped around every user message
+    #[serde(default)]
+    pub chat: ChatConfig,
+
+    /// Developer options
+    #[serde(default)]
+    pub dev: DevConfig,
+
+    /// Which brain files are injected into the system prompt
+    #[serde(default)]
+    pub brain: BrainConfig,
+}
+
+/// `[chat]` section — text wrapped around each user message before it is
+/// sent to the provider. The stored message keeps the original text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatConfig {
+    /// Prepended to the user's message, e.g. "Respond concisely."
+    #[serde(default)]
+    pub prompt_prefix: Option<String>,
+
+    /// Appended to the user's message, e.g. "Use British English."
+    #[serde(default)]
+    pub prompt_suffix: Option<String>,
+}
+
+/// `[sessions]` section — lifecycle of sessions created for channel users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionsConfig {
+    /// Minutes a non-owner channel user's session may sit idle before it is
+    /// dropped and their next message starts a fresh one. A channel's own
+    /// `session_idle_hours` takes precedence. Unset = never expire.
+    #[serde(default)]
+    pub channel_idle_minutes: Option<u64>,
+
+    /// Archive the database session when it expires (default: true)
+    #[serde(default = "default_enabled")]
+    pub archive_idle: bool,
+
+    /// Minutes a channel session may sit idle before its next turn first
+    /// compacts the old history into a summary. Unset = never.
+    #[serde(default)]
+    pub compact_after_idle_minutes: Option<u64>,
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            channel_idle_minutes: None,
+            archive_idle: true,
+            compact_after_idle_minutes: None,
+        }
+    }
+}
+
+impl SessionsConfig {
+    /// Idle timeout for a channel: its `session_idle_hours` if set, otherwise
+    /// `channel_idle_minutes`.
+    pub fn channel_idle_timeout(&self, channel_hours: Option<f64>) -> Option<Duration> {
+        match channel_hours {
+            Some(h) => Some(Duration::from_secs((h * 3600.0) as u64)),
+            None => self
+                .channel_idle_minutes
+                .map(|m| Duration::from_secs(m * 60)),
+        }
+    }
+
+    /// Idle time after which a channel session compacts before its next turn
+    pub fn idle_compact_after(&self) -> Option<Duration> {
+        self.compact_after_idle_minutes
+            .map(|m| Duration::from_secs(m * 60))
+    }
+}
+
+/// `[sampling]` and `[models."<name>"]` sections — sampling parameters.
+/// Unset values leave the provider's own default in place.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingConfig {
+    /// Sampling temperature, 0.0–2.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling cutoff, above 0.0 and at most 1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Output token cap; takes precedence over `[agent] max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Thinking budget for reasoning models: `"low"`, `"medium"`, `"high"`
+    /// or a token count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<crate::brain::provider::ReasoningEffort>,
+}
+
+impl SamplingConfig {
+    /// Sampling parameters for `model`: its `[models."<name>"]` block over
+    /// the `[sampling]` defaults.
+    pub fn for_model(defaults: Self, models: &BTreeMap<String, Self>, model: &str) -> Self {
+        models.get(model).copied().unwrap_or_default().or(defaults)
+    }
+
+    /// `self` with any unset value taken from `fallback`
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            temperature: self.temperature.or(fallback.temperature),
+            top_p: self.top_p.or(fallback.top_p),
+            max_tokens: self.max_tokens.or(fallback.max_tokens),
+            reasoning_effort: self.reasoning_effort.or(fallback.reasoning_effort),
+        }
+    }
+
+    /// Check value ranges; `section` names the table in the error.
+    pub fn validate(&self, section: &str) -> Result<()> {
+        if let Some(t) = self.temperature
+            && !(0.0..=2.0).contains(&t)
+        {
+            anyhow::bail!("{section} temperature must be between 0.0 and 2.0, got {t}");
+        }
+        if let Some(p) = self.top_p
+            && !(p > 0.0 && p <= 1.0)
+        {
+            anyhow::bail!("{section} top_p must be above 0.0 and at most 1.0, got {p}");
+        }
+        if self.max_tokens == Some(0) {
+            anyhow::bail!("{section} max_tokens must be greater than 0");
+        }
+        Ok(())
+    }
+}
+
+/// `[audit]` section — JSONL record of tool calls and responses.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuditConfig {
+    /// Append every tool call and agent response to
+    /// `~/.opencrabs/audit/YYYY-MM-DD.jsonl` (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// `[context]` section — conversation context handling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextConfig {
+    /// Automatically ask the model to continue when a reply is cut off by the
+    /// output token limit (default: false)
+    #[serde(default)]
+    pub auto_continue: bool,
+
+    /// Most continuations per turn when `auto_continue` is on (default: 3)
+    #[serde(default = "default_max_auto_continues")]
+    pub max_auto_continues: u32,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            auto_continue: false,
+            max_auto_continues: default_max_auto_continues(),
+        }
+    }
+}
+
+fn default_max_auto_continues() -> u32 {
+    3
+}
+
+/// `[tui]` section — terminal UI behaviour.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TuiConfig {
+    /// Capture the mouse for wheel scrolling and click-to-select (default: true).
+    /// Disable to keep the terminal's native text selection.
+    #[serde(default = "default_tui_mouse")]
+    pub mouse: bool,
+
+    /// Widest column prose wraps to in the chat view; code blocks still use
+    /// the full width. Unset = the whole terminal width.
+    #[serde(default)]
+    pub max_content_width: Option<usize>,
+
+    /// Save the half-typed input to `~/.opencrabs/drafts/` and restore it on
+    /// launch, so a crash or restart doesn't lose it (default: true)
+    #[serde(default = "default_enabled")]
+    pub save_draft: bool,
+
+    /// Greeting shown in an empty chat, above the active model and a few
+    /// commands to try. Unset = the default greeting, empty string = none.
+    #[serde(default)]
+    pub greeting: Option<String>,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            mouse: default_tui_mouse(),
+            max_content_width: None,
+            save_draft: true,
+            greeting: None,
+        }
+    }
+}
+
+fn default_tui_mouse() -> bool {
+    true
+}
+
+/// A `[keybindings]` value — one key spec or a list of them.
+///
+/// ```toml
+/// [keybindings]
+/// sessions = "ctrl+o"
+/// scroll_up = ["pageup", "ctrl+b"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    /// All key specs in this entry
+    pub fn specs(&self) -> Vec<&str> {
+        match self {
+            KeySpec::One(s) => vec![s.as_str()],
+            KeySpec::Many(v) => v.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// `[tools]` section — operator policy over which tools the agent gets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolsConfig {
+    /// Tool names that are never registered (invisible to the model)
+    #[serde(default)]
+    pub disabled: Vec<String>,
+
+    /// Tool names that always require approval, even if normally auto-approved
+    #[serde(default)]
+    pub require_approval: Vec<String>,
+
+    /// Capabilities that skip approval — a tool runs without asking when all
+    /// its capabilities are listed (e.g. `["read_files", "network"]`)
+    #[serde(default)]
+    pub auto_approve_capabilities: Vec<String>,
+
+    /// Seconds to wait for an approval answer before auto-denying the tool
+    /// (default: 300, 0 = wait forever)
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+
+    /// Longest tool result sent to the model, in characters; longer results
+    /// are cut and paged with `read_more` (default: 8000, 0 = no limit)
+    #[serde(default = "default_max_result_chars")]
+    pub max_result_chars: usize,
+
+    /// Append the user's TOOLS.md to the system prompt every turn, so its
+    /// tool-usage preferences always apply (default: false — the agent loads
+    /// it on demand with `load_brain_file`)
+    #[serde(default)]
+    pub inject_tools_md: bool,
+
+    /// `[tools.shell]` — allowlist for the `shell` tool
+    #[serde(default)]
+    pub shell: ShellToolConfig,
+}
+
+/// `[tools.shell]` section — binaries the `shell` tool may run without asking.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShellToolConfig {
+    /// Binary names (e.g. `["git", "ls", "cargo"]`) that run without
+    /// approval; any other command prompts as usual
+    #[serde(default)]
+    pub allowed: Vec<String>,
+}
+
+fn default_max_result_chars() -> usize {
+    8000
+}
+
+fn default_approval_timeout_secs() -> u64 {
+    300
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            disabled: Vec::new(),
+            require_approval: Vec::new(),
+            auto_approve_capabilities: Vec::new(),
+            approval_timeout_secs: default_approval_timeout_secs(),
+            max_result_chars: default_max_result_chars(),
+            inject_tools_md: false,
+            shell: ShellToolConfig::default(),
+        }
+    }
+}
+
+/// `[boot]` section — run BOOT.md once as the first agent turn of each launch.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BootConfig {
+    /// Whether BOOT.md runs on TUI startup (default: false)
+    #[serde(default)]
+    pub run_on_start: bool,
+}
+
+/// `[heartbeat]` section — run HEARTBEAT.md as an agent turn on an interval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    /// Whether the heartbeat scheduler runs (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Minutes between heartbeats (default: 30)
+    #[serde(default = "default_heartbeat_interval")]
+    pub interval_minutes: u64,
+}
+
+fn default_heartbeat_interval() -> u64 {
+    30
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_heartbeat_interval(),
+        }
+    }
 }
 
 /// A2A (Agent-to-Agent) protocol gateway configuration.
@@ -97,8 +478,12 @@ impl Default for A2aConfig {
     }
 }
 
-/// Messaging channel integrations configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Messaging channel integrations configuration.
+///
+/// Each chat channel's `system_append` holds extra instructions appended to
+/// the system prompt for requests from that channel only (e.g. "Keep replies
+/// under 2000 characters" on Discord). Unset = none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelsConfig {
     #[serde(default)]
     pub telegram: TelegramConfig,
@@ -116,6 +501,54 @@ pub struct ChannelsConfig {
     pub google_chat: GoogleChatConfig,
     #[serde(default)]
     pub imessage: IMessageConfig,
+    #[serde(default)]
+    pub matrix: MatrixConfig,
+
+    /// Most images passed to the model per incoming message; extras are
+    /// dropped with a note (default: 4, 0 = no limit)
+    #[serde(default = "default_max_images_per_message")]
+    pub max_images_per_message: usize,
+
+    /// Largest image accepted, in bytes; bigger ones are rejected before
+    /// download (default: 10 MiB, 0 = no limit)
+    #[serde(default = "default_max_image_bytes")]
+    pub max_image_bytes: u64,
+
+    /// Channel messages handed to the agent at once across all channels;
+    /// the rest wait their turn (default: 4, 0 = no limit)
+    #[serde(default = "default_channel_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+impl Default for ChannelsConfig {
+    fn default() -> Self {
+        Self {
+            telegram: TelegramConfig::default(),
+            discord: DiscordConfig::default(),
+            whatsapp: WhatsAppConfig::default(),
+            slack: SlackConfig::default(),
+            trello: TrelloConfig::default(),
+            signal: SignalConfig::default(),
+            google_chat: GoogleChatConfig::default(),
+            imessage: IMessageConfig::default(),
+            matrix: MatrixConfig::default(),
+            max_images_per_message: default_max_images_per_message(),
+            max_image_bytes: default_max_image_bytes(),
+            max_concurrent: default_channel_max_concurrent(),
+        }
+    }
+}
+
+fn default_channel_max_concurrent() -> usize {
+    4
+}
+
+fn default_max_images_per_message() -> usize {
+    4
+}
+
+fn default_max_image_bytes() -> u64 {
+    10 * 1024 * 1024
 }
 
 /// When the bot should respond to messages in group channels.
@@ -173,6 +606,12 @@ pub struct TelegramConfig {
     /// Idle session timeout in hours for non-owner sessions.
     #[serde(default)]
     pub session_idle_hours: Option<f64>,
+    /// Line shown above every agent reply (markdown). Unset = none.
+    #[serde(default)]
+    pub reply_header: Option<String>,
+    /// Appended to the system prompt for Telegram requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
 }
 
 /// Discord channel configuration
@@ -194,6 +633,19 @@ pub struct DiscordConfig {
     /// Idle session timeout in hours for non-owner sessions.
     #[serde(default)]
     pub session_idle_hours: Option<f64>,
+    /// Line shown above every agent reply (markdown). Unset = none.
+    #[serde(default)]
+    pub reply_header: Option<String>,
+    /// Appended to the system prompt for Discord requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
+    /// Send a reply that is mostly one large code block as a file attachment
+    /// instead of inline chunks (default: false)
+    #[serde(default)]
+    pub large_code_as_file: bool,
+    /// Characters from which a code block counts as large (default: 1500)
+    #[serde(default)]
+    pub large_code_threshold: Option<usize>,
 }
 
 /// Slack channel configuration
@@ -219,6 +671,9 @@ pub struct SlackConfig {
     /// Idle session timeout in hours for non-owner sessions.
     #[serde(default)]
     pub session_idle_hours: Option<f64>,
+    /// Appended to the system prompt for Slack requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
 }
 
 /// WhatsApp channel configuration
@@ -233,6 +688,24 @@ pub struct WhatsAppConfig {
     /// Idle session timeout in hours for non-owner sessions.
     #[serde(default)]
     pub session_idle_hours: Option<f64>,
+    /// Line shown above every agent message. Unset = "🦀 *OpenCrabs*",
+    /// empty string = no header.
+    #[serde(default)]
+    pub reply_header: Option<String>,
+    /// Mark accepted messages as read (blue ticks). Off by default: only
+    /// delivery receipts are sent, so the sender can't tell the bot read it.
+    #[serde(default)]
+    pub send_receipts: bool,
+    /// Reconnect attempts in a row after the connection drops before giving
+    /// up. Unset = keep trying, 0 = don't reconnect.
+    #[serde(default)]
+    pub reconnect_max_attempts: Option<u32>,
+    /// Longest wait between reconnect attempts, in seconds (default: 300)
+    #[serde(default)]
+    pub reconnect_max_delay_secs: Option<u64>,
+    /// Appended to the system prompt for WhatsApp requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
 }
 
 /// Trello channel configuration
@@ -259,6 +732,45 @@ pub struct TrelloConfig {
     /// Idle session timeout in hours for non-owner sessions.
     #[serde(default)]
     pub session_idle_hours: Option<f64>,
+    /// Appended to the system prompt for Trello requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
+}
+
+/// Matrix channel configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MatrixConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Homeserver base URL (e.g. "https://matrix.org")
+    #[serde(default)]
+    pub homeserver: Option<String>,
+    /// The bot account's user ID (e.g. "@crabs:matrix.org")
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Access token for the bot account
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Device ID the access token belongs to. Unset = looked up via whoami.
+    /// Must stay the same across restarts for encrypted rooms to keep working.
+    #[serde(default)]
+    pub device_id: Option<String>,
+    /// Allowlisted Matrix user IDs ("@alice:example.org"). Empty = nobody but
+    /// `[owner] matrix_id`, since anyone can invite the bot to a room.
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Restrict bot to specific room IDs ("!abc:example.org"). Empty = all rooms.
+    #[serde(default)]
+    pub allowed_rooms: Vec<String>,
+    /// When the bot should respond in group rooms: "all", "dm_only", or "mention" (default)
+    #[serde(default)]
+    pub respond_to: RespondTo,
+    /// Idle session timeout in hours for non-owner sessions.
+    #[serde(default)]
+    pub session_idle_hours: Option<f64>,
+    /// Appended to the system prompt for Matrix requests (see [`ChannelsConfig`])
+    #[serde(default)]
+    pub system_append: Option<String>,
 }
 
 /// Signal channel configuration (placeholder — not yet implemented)
@@ -321,13 +833,41 @@ pub struct VoiceConfig {
     #[serde(default = "default_tts_model")]
     pub tts_model: String,
 
+    /// Speech-to-text backend: "groq" (default) or "whisper_cpp" (local)
+    #[serde(rename = "stt_provider", default = "default_stt_backend")]
+    pub stt_backend: String,
+
+    /// Text-to-speech backend: "openai" (default)
+    #[serde(rename = "tts_provider", default = "default_tts_backend")]
+    pub tts_backend: String,
+
+    /// whisper.cpp CLI used by the whisper_cpp backend (default: "whisper-cli")
+    #[serde(default = "default_whisper_cpp_binary")]
+    pub whisper_cpp_binary: String,
+
+    /// ggml model file for the whisper_cpp backend (e.g. ~/models/ggml-base.bin)
+    #[serde(default)]
+    pub whisper_cpp_model: Option<String>,
+
+    /// Transcription language (ISO 639-1, e.g. "pt"); unset = auto-detect
+    #[serde(default)]
+    pub stt_language: Option<String>,
+
+    /// Vocabulary hint passed to Whisper (names, jargon you use)
+    #[serde(default)]
+    pub stt_prompt: Option<String>,
+
+    /// Per-user language overrides, keyed by channel user ID or phone number
+    #[serde(default)]
+    pub stt_user_languages: BTreeMap<String, String>,
+
     /// STT provider config (runtime - from providers.stt.*)
-    /// Not serialized to config file
+    /// Not serialized to config file — the `stt_provider` TOML key is `stt_backend`
     #[serde(skip, default)]
     pub stt_provider: Option<ProviderConfig>,
 
     /// TTS provider config (runtime - from providers.tts.*)
-    /// Not serialized to config file
+    /// Not serialized to config file — the `tts_provider` TOML key is `tts_backend`
     #[serde(skip, default)]
     pub tts_provider: Option<ProviderConfig>,
 }
@@ -338,6 +878,15 @@ fn default_tts_voice() -> String {
 fn default_tts_model() -> String {
     "gpt-4o-mini-tts".to_string()
 }
+fn default_stt_backend() -> String {
+    "groq".to_string()
+}
+fn default_tts_backend() -> String {
+    "openai".to_string()
+}
+fn default_whisper_cpp_binary() -> String {
+    "whisper-cli".to_string()
+}
 
 impl Default for VoiceConfig {
     fn default() -> Self {
@@ -346,6 +895,13 @@ impl Default for VoiceConfig {
             tts_enabled: false,
             tts_voice: default_tts_voice(),
             tts_model: default_tts_model(),
+            stt_backend: default_stt_backend(),
+            tts_backend: default_tts_backend(),
+            whisper_cpp_binary: default_whisper_cpp_binary(),
+            whisper_cpp_model: None,
+            stt_language: None,
+            stt_prompt: None,
+            stt_user_languages: BTreeMap::new(),
             stt_provider: None,
             tts_provider: None,
         }
@@ -366,6 +922,9 @@ pub struct ImageConfig {
 pub struct ImageGenerationConfig {
     #[serde(default)]
     pub enabled: bool,
+    /// Image API: "gemini" (default) or "openai"
+    #[serde(default = "default_image_provider")]
+    pub provider: String,
     #[serde(default = "default_image_model")]
     pub model: String,
     /// Loaded from keys.toml at runtime, never serialized to config.toml
@@ -377,12 +936,17 @@ impl Default for ImageGenerationConfig {
     fn default() -> Self {
         Self {
             enabled: false,
+            provider: default_image_provider(),
             model: default_image_model(),
             api_key: None,
         }
     }
 }
 
+fn default_image_provider() -> String {
+    "gemini".to_string()
+}
+
 /// Image vision configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageVisionConfig {
@@ -412,6 +976,15 @@ fn default_image_model() -> String {
 /// Agent behaviour configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
+    /// Name the agent goes by in the system prompt, the TUI and channel
+    /// replies (default: "OpenCrabs")
+    #[serde(default = "default_agent_name")]
+    pub name: String,
+
+    /// Emoji shown next to the agent name (default: "🦀"; empty for none)
+    #[serde(default = "default_agent_emoji")]
+    pub emoji: String,
+
     /// Approval policy: "ask", "auto-session", "auto-always"
     #[serde(default = "default_approval_policy")]
     pub approval_policy: String,
@@ -427,6 +1000,35 @@ pub struct AgentConfig {
     /// Max output tokens for API calls (default: 65536)
     #[serde(default = "default_max_tokens")]
     pub max_tokens: u32,
+
+    /// Tokens one session may use before its turns stop until `/unlock`
+    /// (default: no limit)
+    #[serde(default)]
+    pub max_session_tokens: Option<u64>,
+
+    /// Cost in USD one session may reach before its turns stop until
+    /// `/unlock` (default: no limit)
+    #[serde(default)]
+    pub max_session_cost: Option<f64>,
+}
+
+fn default_agent_name() -> String {
+    "OpenCrabs".to_string()
+}
+
+fn default_agent_emoji() -> String {
+    "🦀".to_string()
+}
+
+impl AgentConfig {
+    /// The agent name prefixed by its emoji, e.g. `🦀 OpenCrabs`
+    pub fn label(&self) -> String {
+        if self.emoji.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} {}", self.emoji, self.name)
+        }
+    }
 }
 
 fn default_approval_policy() -> String {
@@ -448,10 +1050,14 @@ fn default_max_tokens() -> u32 {
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
+            name: default_agent_name(),
+            emoji: default_agent_emoji(),
             approval_policy: default_approval_policy(),
             max_concurrent: default_max_concurrent(),
             context_limit: default_context_limit(),
             max_tokens: default_max_tokens(),
+            max_session_tokens: None,
+            max_session_cost: None,
         }
     }
 }
@@ -468,6 +1074,30 @@ pub struct DebugConfig {
     pub profiling: bool,
 }
 
+/// `[dev]` section — options for developing and debugging tools.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DevConfig {
+    /// Allow `/tool <name> <json>` to run a tool directly, skipping the model
+    /// and approvals. Tools run with real side effects (default: false)
+    #[serde(default)]
+    pub allow_direct_tools: bool,
+}
+
+/// `[brain]` section — which workspace brain files are injected into the
+/// system prompt, and how many tokens they may take up together.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BrainConfig {
+    /// Files to inject, by name without `.md` (e.g. `["SOUL", "IDENTITY"]`).
+    /// Unset injects the defaults; files left out stay loadable on demand
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+
+    /// Token cap for the injected files combined. Over the cap, files are
+    /// dropped from the end of the assembly order until it fits (default: none)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+}
+
 /// LLM Provider configurations
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProviderConfigs {
@@ -519,9 +1149,15 @@ pub struct ProviderConfigs {
     #[serde(default)]
     pub image: Option<ImageProviders>,
 
-    /// Fallback provider configuration (under [providers.fallback] in config)
-    #[serde(default)]
+    /// Fallback provider configuration (under [providers.fallback] in config,
+    /// or the shorthand `fallback = ["openrouter", "openai"]`)
+    #[serde(default, deserialize_with = "deserialize_fallback")]
     pub fallback: Option<FallbackProviderConfig>,
+
+    /// Mark the system brain and tool definitions as cacheable where the
+    /// provider supports explicit prompt caching (Anthropic)
+    #[serde(default)]
+    pub prompt_caching: bool,
 }
 
 impl ProviderConfigs {
@@ -538,6 +1174,38 @@ impl ProviderConfigs {
     pub fn custom_by_name(&self, name: &str) -> Option<&ProviderConfig> {
         self.custom.as_ref()?.get(name)
     }
+
+    /// Model-selector entries for every configured custom provider, as
+    /// `custom:<name>/<model>`. Uses `models`, or `default_model` when the list
+    /// is empty, so two providers serving the same model stay distinct.
+    /// `enabled` is ignored: picking a provider disables all the others, and
+    /// they must stay selectable.
+    pub fn custom_model_choices(&self) -> Vec<String> {
+        let Some(customs) = &self.custom else {
+            return Vec::new();
+        };
+        customs
+            .iter()
+            .filter(|(_, cfg)| cfg.base_url.is_some())
+            .flat_map(|(name, cfg)| {
+                let models = if cfg.models.is_empty() {
+                    cfg.default_model.iter().cloned().collect()
+                } else {
+                    cfg.models.clone()
+                };
+                models
+                    .into_iter()
+                    .map(move |model| format!("custom:{}/{}", name, model))
+            })
+            .collect()
+    }
+
+    /// Split a `custom:<name>/<model>` entry into provider name and model.
+    /// The model keeps any further slashes (`custom:vllm/meta-llama/Llama-3`).
+    pub fn parse_custom_model_choice(choice: &str) -> Option<(&str, &str)> {
+        let (name, model) = choice.strip_prefix("custom:")?.split_once('/')?;
+        (!name.is_empty() && !model.is_empty()).then_some((name, model))
+    }
 }
 
 /// Custom deserializer that handles both old flat format `[providers.custom]`
@@ -622,6 +1290,39 @@ pub struct FallbackProviderConfig {
     /// Each name must match a configured provider (e.g. "anthropic", "openrouter").
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub providers: Vec<String>,
+
+    /// Model to request from each fallback provider, keyed by provider name
+    /// (`[providers.fallback.models] openrouter = "anthropic/claude-sonnet-4"`).
+    /// Unmapped fallbacks use their own default model.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, String>,
+}
+
+/// Accept `[providers.fallback]` as a table or as a bare list of provider
+/// names. The list form enables the chain with default model mapping.
+fn deserialize_fallback<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<FallbackProviderConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FallbackSpec {
+        List(Vec<String>),
+        Table(FallbackProviderConfig),
+    }
+
+    Ok(
+        Option::<FallbackSpec>::deserialize(deserializer)?.map(|spec| match spec {
+            FallbackSpec::List(providers) => FallbackProviderConfig {
+                enabled: !providers.is_empty(),
+                providers,
+                ..Default::default()
+            },
+            FallbackSpec::Table(cfg) => cfg,
+        }),
+    )
 }
 
 /// STT (Speech-to-Text) provider configurations
@@ -666,6 +1367,10 @@ pub struct ImageProviders {
     /// Google Gemini image configuration
     #[serde(default)]
     pub gemini: Option<ProviderConfig>,
+
+    /// OpenAI image generation key (falls back to `[providers.openai]`)
+    #[serde(default)]
+    pub openai: Option<ProviderConfig>,
 }
 
 /// Individual provider configuration
@@ -722,7 +1427,7 @@ fn default_db_path() -> PathBuf {
 }
 
 /// Expand leading `~` or `~/` in a path to the actual home directory.
-fn expand_tilde(p: &Path) -> PathBuf {
+pub(crate) fn expand_tilde(p: &Path) -> PathBuf {
     if let Ok(rest) = p.strip_prefix("~") {
         dirs::home_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -732,12 +1437,64 @@ fn expand_tilde(p: &Path) -> PathBuf {
     }
 }
 
-/// Canonical base directory: `~/.opencrabs/`
+/// Base directory set by `--workspace` for this process.
+static HOME_OVERRIDE: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Override the base directory for this process (`--workspace <dir>`).
+///
+/// Must be called at startup, before anything resolves `opencrabs_home()`.
+/// Only the first call takes effect.
+pub fn set_opencrabs_home(path: &Path) {
+    let path = expand_tilde(path);
+    let path = std::path::absolute(&path).unwrap_or(path);
+    let _ = HOME_OVERRIDE.set(path);
+}
+
+/// Config file set by `--config` for this process.
+static CONFIG_PATH_OVERRIDE: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Use `path` as the config file for this process (`--config <file>`).
+///
+/// Every later `Config::load()`, `write_key`/`write_array` and the config
+/// watcher then use this file instead of `~/.opencrabs/config.toml`.
+/// Must be called at startup; only the first call takes effect.
+pub fn set_config_path(path: &Path) {
+    let path = expand_tilde(path);
+    let path = std::path::absolute(&path).unwrap_or(path);
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
+/// Config file set by `--config` for this process, if any.
+pub fn config_path_override() -> Option<PathBuf> {
+    CONFIG_PATH_OVERRIDE.get().cloned()
+}
+
+/// Workspace override in effect, if any: `--workspace` first, then `OPENCRABS_HOME`.
+pub fn opencrabs_home_override() -> Option<PathBuf> {
+    if let Some(p) = HOME_OVERRIDE.get() {
+        return Some(p.clone());
+    }
+    std::env::var("OPENCRABS_HOME")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let p = expand_tilde(Path::new(&v));
+            std::path::absolute(&p).unwrap_or(p)
+        })
+}
+
+/// Canonical base directory: `~/.opencrabs/`, or the workspace override.
 ///
 /// All OpenCrabs data lives here: config, database, history, brain workspace.
+/// `--workspace <dir>` / `OPENCRABS_HOME` point it elsewhere so each project
+/// can keep its own config, sessions DB and memory.
 pub fn opencrabs_home() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let p = home.join(".opencrabs");
+    let p = opencrabs_home_override().unwrap_or_else(|| {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".opencrabs")
+    });
     if !p.exists() {
         let _ = std::fs::create_dir_all(&p);
     }
@@ -764,7 +1521,7 @@ pub fn daily_backup(path: &Path, max_days: usize) {
         .to_string_lossy()
         .to_string();
 
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let today = super::local_today();
     let today_backup = parent.join(format!("{stem}.{today}.bak"));
 
     // Skip if today's backup already exists (preserve the day's first snapshot)
@@ -1049,6 +1806,13 @@ fn merge_provider_keys(mut base: ProviderConfigs, keys: ProviderConfigs) -> Prov
             let entry = base_img.gemini.get_or_insert_with(ProviderConfig::default);
             entry.api_key = Some(key);
         }
+        if let Some(openai) = img.openai
+            && let Some(key) = openai.api_key
+            && !key.is_empty()
+        {
+            let entry = base_img.openai.get_or_insert_with(ProviderConfig::default);
+            entry.api_key = Some(key);
+        }
     }
     base
 }
@@ -1082,6 +1846,13 @@ fn merge_channel_keys(mut base: ChannelsConfig, keys: ChannelsConfig) -> Channel
         base.slack.app_token = Some(app_token.clone());
     }
 
+    // Matrix
+    if let Some(ref token) = keys.matrix.access_token
+        && !token.is_empty()
+    {
+        base.matrix.access_token = Some(token.clone());
+    }
+
     // WhatsApp uses QR-code pairing stored in session.db — no token to merge.
 
     // Trello (app_token = API Key, token = API Token)
@@ -1141,6 +1912,21 @@ impl Default for Config {
             agent: AgentConfig::default(),
             a2a: A2aConfig::default(),
             image: ImageConfig::default(),
+            owner: OwnerConfig::default(),
+            tools: ToolsConfig::default(),
+            heartbeat: HeartbeatConfig::default(),
+            boot: BootConfig::default(),
+            keybindings: BTreeMap::new(),
+            tui: TuiConfig::default(),
+            context: ContextConfig::default(),
+            general: GeneralConfig::default(),
+            audit: AuditConfig::default(),
+            sampling: SamplingConfig::default(),
+            models: BTreeMap::new(),
+            sessions: SessionsConfig::default(),
+            chat: ChatConfig::default(),
+            dev: DevConfig::default(),
+            brain: BrainConfig::default(),
         }
     }
 }
@@ -1154,6 +1940,9 @@ impl Config {
     /// 3. Local config: ./opencrabs.toml
     /// 4. Environment variables
     pub fn load() -> Result<Self> {
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return Self::load_from_path(path);
+        }
         tracing::debug!("Loading configuration...");
 
         // Start with defaults
@@ -1215,6 +2004,22 @@ impl Config {
                     config.image.generation.api_key = Some(key.clone());
                     config.image.vision.api_key = Some(key);
                 }
+                // OpenAI image generation: [providers.image.openai], else the chat key
+                if config
+                    .image
+                    .generation
+                    .provider
+                    .eq_ignore_ascii_case("openai")
+                {
+                    config.image.generation.api_key = config
+                        .providers
+                        .image
+                        .as_ref()
+                        .and_then(|img| img.openai.as_ref())
+                        .or(config.providers.openai.as_ref())
+                        .and_then(|p| p.api_key.clone())
+                        .filter(|k| !k.is_empty());
+                }
             }
         }
 
@@ -1224,6 +2029,8 @@ impl Config {
         // Expand tilde in database path (TOML doesn't expand ~)
         config.database.path = expand_tilde(&config.database.path);
 
+        super::set_timezone(config.general.timezone.as_deref());
+
         tracing::debug!("Configuration loaded successfully");
         Ok(config)
     }
@@ -1254,6 +2061,8 @@ impl Config {
         // Expand tilde in database path (TOML doesn't expand ~)
         config.database.path = expand_tilde(&config.database.path);
 
+        super::set_timezone(config.general.timezone.as_deref());
+
         tracing::debug!("Configuration loaded successfully from custom path");
         Ok(config)
     }
@@ -1304,8 +2113,11 @@ impl Config {
         }
     }
 
-    /// Get the system config path: ~/.opencrabs/config.toml
+    /// Get the system config path: ~/.opencrabs/config.toml, or the `--config` file
     pub fn system_config_path() -> Option<PathBuf> {
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return Some(path.clone());
+        }
         Some(opencrabs_home().join("config.toml"))
     }
 
@@ -1314,6 +2126,18 @@ impl Config {
         PathBuf::from("./opencrabs.toml")
     }
 
+    /// Config files `load` (or `load_from_path` for `custom`) reads, if present.
+    pub fn source_paths(custom: Option<&Path>) -> Vec<PathBuf> {
+        match custom {
+            Some(path) => vec![path.to_path_buf()],
+            None => Self::system_config_path()
+                .into_iter()
+                .chain([Self::local_config_path()])
+                .filter(|p| p.exists())
+                .collect(),
+        }
+    }
+
     /// Load and merge configuration from a TOML file
     fn merge_from_file(base: Self, path: &Path) -> Result<Self> {
         let contents = fs::read_to_string(path)
@@ -1340,6 +2164,21 @@ impl Config {
             agent: overlay.agent,
             a2a: overlay.a2a,
             image: overlay.image,
+            owner: overlay.owner,
+            tools: overlay.tools,
+            heartbeat: overlay.heartbeat,
+            boot: overlay.boot,
+            keybindings: overlay.keybindings,
+            tui: overlay.tui,
+            context: overlay.context,
+            general: overlay.general,
+            audit: overlay.audit,
+            sampling: overlay.sampling,
+            models: overlay.models,
+            sessions: overlay.sessions,
+            chat: overlay.chat,
+            dev: overlay.dev,
+            brain: overlay.brain,
         }
     }
 
@@ -1546,6 +2385,8 @@ impl Config {
         has_anthropic || has_openai || has_gemini
     }
 
+    /// Fail with a readable report if [`Config::check`] finds any errors;
+    /// warnings are logged.
     pub fn validate(&self) -> Result<()> {
         tracing::debug!("Validating configuration...");
 
@@ -1559,19 +2400,12 @@ impl Config {
             );
         }
 
-        // Validate log level
-        let valid_levels = ["trace", "debug", "info", "warn", "error"];
-        if !valid_levels.contains(&self.logging.level.as_str()) {
-            anyhow::bail!(
-                "Invalid log level: {}. Must be one of: {:?}",
-                self.logging.level,
-                valid_levels
-            );
+        let report = self.check();
+        for warning in report.warnings() {
+            tracing::warn!("Config: {}", warning);
         }
-
-        // Validate Crabrace URL if enabled
-        if self.crabrace.enabled && self.crabrace.base_url.is_empty() {
-            anyhow::bail!("Crabrace is enabled but base_url is empty");
+        if report.has_errors() {
+            anyhow::bail!("{}", report.render());
         }
 
         tracing::debug!("Configuration validation passed");
@@ -1796,6 +2630,318 @@ max_concurrent = 8
         assert_eq!(config.agent.max_concurrent, 8);
     }
 
+    #[test]
+    fn test_tools_config_from_toml() {
+        let toml_content = r#"
+[tools]
+disabled = ["discord_send", "self_update"]
+require_approval = ["http_request"]
+approval_timeout_secs = 60
+max_result_chars = 2000
+inject_tools_md = true
+        "#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.tools.disabled, vec!["discord_send", "self_update"]);
+        assert_eq!(config.tools.require_approval, vec!["http_request"]);
+        assert_eq!(config.tools.approval_timeout_secs, 60);
+        assert_eq!(config.tools.max_result_chars, 2000);
+        assert!(config.tools.inject_tools_md);
+        assert!(Config::default().tools.disabled.is_empty());
+        assert!(!Config::default().tools.inject_tools_md);
+        assert_eq!(Config::default().tools.approval_timeout_secs, 300);
+        assert_eq!(Config::default().tools.max_result_chars, 8000);
+    }
+
+    #[test]
+    fn test_heartbeat_config_from_toml() {
+        let toml_content = r#"
+[heartbeat]
+enabled = true
+interval_minutes = 15
+        "#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(config.heartbeat.enabled);
+        assert_eq!(config.heartbeat.interval_minutes, 15);
+
+        let defaults: Config = toml::from_str("[heartbeat]\nenabled = true").unwrap();
+        assert_eq!(defaults.heartbeat.interval_minutes, 30);
+        assert!(!Config::default().heartbeat.enabled);
+    }
+
+    #[test]
+    fn test_boot_config_from_toml() {
+        let config: Config = toml::from_str("[boot]\nrun_on_start = true").unwrap();
+        assert!(config.boot.run_on_start);
+        assert!(!Config::default().boot.run_on_start);
+    }
+
+    #[test]
+    fn test_parse_custom_model_choice() {
+        assert_eq!(
+            ProviderConfigs::parse_custom_model_choice("custom:vllm/meta-llama/Llama-3"),
+            Some(("vllm", "meta-llama/Llama-3"))
+        );
+        assert_eq!(
+            ProviderConfigs::parse_custom_model_choice("custom:ollama/llama3"),
+            Some(("ollama", "llama3"))
+        );
+        assert_eq!(
+            ProviderConfigs::parse_custom_model_choice("custom:ollama"),
+            None
+        );
+        assert_eq!(ProviderConfigs::parse_custom_model_choice("llama3"), None);
+    }
+
+    #[test]
+    fn test_sampling_from_toml() {
+        use crate::brain::provider::ReasoningEffort;
+
+        let config: Config = toml::from_str(
+            "[sampling]\ntemperature = 0.7\ntop_p = 0.9\n\n[models.\"gpt-4o\"]\ntemperature = 0.2\nmax_tokens = 2048",
+        )
+        .unwrap();
+        let sampling_for = |config: &Config, model| {
+            SamplingConfig::for_model(config.sampling, &config.models, model)
+        };
+        let gpt = sampling_for(&config, "gpt-4o");
+        assert_eq!(gpt.temperature, Some(0.2));
+        assert_eq!(gpt.top_p, Some(0.9));
+        assert_eq!(gpt.max_tokens, Some(2048));
+        assert_eq!(sampling_for(&config, "other").temperature, Some(0.7));
+        assert!(config.validate().is_ok());
+
+        let think: Config = toml::from_str(
+            "[sampling]\nreasoning_effort = \"low\"\n\n[models.o3]\nreasoning_effort = 12000",
+        )
+        .unwrap();
+        assert_eq!(
+            sampling_for(&think, "o3").reasoning_effort,
+            Some(ReasoningEffort::Tokens(12000))
+        );
+        assert_eq!(
+            sampling_for(&think, "other").reasoning_effort,
+            Some(ReasoningEffort::Low)
+        );
+        assert!(toml::from_str::<Config>("[sampling]\nreasoning_effort = \"max\"").is_err());
+
+        let bad: Config = toml::from_str("[models.m]\ntop_p = 1.5").unwrap();
+        assert!(bad.validate().is_err());
+        assert_eq!(
+            sampling_for(&Config::default(), "any"),
+            SamplingConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_keybindings_from_toml() {
+        let config: Config = toml::from_str(
+            "[keybindings]\nsessions = \"ctrl+o\"\nscroll_up = [\"pageup\", \"ctrl+b\"]",
+        )
+        .unwrap();
+        assert_eq!(
+            config.keybindings.get("sessions"),
+            Some(&KeySpec::One("ctrl+o".into()))
+        );
+        assert_eq!(
+            config.keybindings["scroll_up"].specs(),
+            vec!["pageup", "ctrl+b"]
+        );
+        assert!(Config::default().keybindings.is_empty());
+    }
+
+    #[test]
+    fn test_tui_mouse_defaults_on() {
+        assert!(Config::default().tui.mouse);
+        let config: Config = toml::from_str("[tui]\nmouse = false").unwrap();
+        assert!(!config.tui.mouse);
+        let config: Config = toml::from_str("[tui]").unwrap();
+        assert!(config.tui.mouse);
+        assert_eq!(config.tui.max_content_width, None);
+        let config: Config = toml::from_str("[tui]\nmax_content_width = 100").unwrap();
+        assert_eq!(config.tui.max_content_width, Some(100));
+        assert!(config.tui.save_draft);
+        let config: Config = toml::from_str("[tui]\nsave_draft = false").unwrap();
+        assert!(!config.tui.save_draft);
+        assert_eq!(config.tui.greeting, None);
+        let config: Config = toml::from_str("[tui]\ngreeting = \"\"").unwrap();
+        assert_eq!(config.tui.greeting.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_context_auto_continue_from_toml() {
+        let config = Config::default();
+        assert!(!config.context.auto_continue);
+        assert_eq!(config.context.max_auto_continues, 3);
+        let config: Config = toml::from_str("[context]\nauto_continue = true").unwrap();
+        assert!(config.context.auto_continue);
+        assert_eq!(config.context.max_auto_continues, 3);
+    }
+
+    #[test]
+    fn test_general_timezone_from_toml() {
+        assert_eq!(Config::default().general.timezone, None);
+        let config: Config = toml::from_str("[general]\ntimezone = \"Europe/Lisbon\"").unwrap();
+        assert_eq!(config.general.timezone.as_deref(), Some("Europe/Lisbon"));
+    }
+
+    #[test]
+    fn test_audit_config_from_toml() {
+        assert!(!Config::default().audit.enabled);
+        let config: Config = toml::from_str("[audit]\nenabled = true").unwrap();
+        assert!(config.audit.enabled);
+    }
+
+    #[test]
+    fn test_sessions_config_from_toml() {
+        let sessions = Config::default().sessions;
+        assert!(sessions.archive_idle);
+        assert_eq!(sessions.channel_idle_timeout(None), None);
+
+        let config: Config =
+            toml::from_str("[sessions]\nchannel_idle_minutes = 30\narchive_idle = false").unwrap();
+        assert!(!config.sessions.archive_idle);
+        assert_eq!(
+            config.sessions.channel_idle_timeout(None),
+            Some(Duration::from_secs(1800))
+        );
+        assert_eq!(
+            config.sessions.channel_idle_timeout(Some(2.0)),
+            Some(Duration::from_secs(7200))
+        );
+    }
+
+    #[test]
+    fn test_chat_config_from_toml() {
+        assert!(Config::default().chat.prompt_prefix.is_none());
+        let config: Config =
+            toml::from_str("[chat]\nprompt_prefix = \"Respond concisely.\"").unwrap();
+        assert_eq!(
+            config.chat.prompt_prefix.as_deref(),
+            Some("Respond concisely.")
+        );
+        assert!(config.chat.prompt_suffix.is_none());
+    }
+
+    #[test]
+    fn test_voice_backends_from_toml() {
+        let config = Config::default();
+        assert_eq!(config.voice.stt_backend, "groq");
+        assert_eq!(config.voice.tts_backend, "openai");
+        let config: Config = toml::from_str(
+            "[voice]\nstt_provider = \"whisper_cpp\"\nwhisper_cpp_model = \"/m/ggml-base.bin\"",
+        )
+        .unwrap();
+        assert_eq!(config.voice.stt_backend, "whisper_cpp");
+        assert_eq!(config.voice.whisper_cpp_binary, "whisper-cli");
+        assert_eq!(
+            config.voice.whisper_cpp_model.as_deref(),
+            Some("/m/ggml-base.bin")
+        );
+    }
+
+    #[test]
+    fn test_voice_stt_language_from_toml() {
+        let config: Config = toml::from_str(
+            "[voice]\nstt_language = \"pt\"\n\n[voice.stt_user_languages]\n\"123456789\" = \"de\"",
+        )
+        .unwrap();
+        assert_eq!(config.voice.stt_language.as_deref(), Some("pt"));
+        assert_eq!(
+            config
+                .voice
+                .stt_user_languages
+                .get("123456789")
+                .map(String::as_str),
+            Some("de")
+        );
+    }
+
+    #[test]
+    fn test_channel_image_limits_from_toml() {
+        let config = Config::default();
+        assert_eq!(config.channels.max_images_per_message, 4);
+        assert_eq!(config.channels.max_image_bytes, 10 * 1024 * 1024);
+        let config: Config = toml::from_str(
+            "[channels]
+max_images_per_message = 2
+
+[channels.telegram]",
+        )
+        .unwrap();
+        assert_eq!(config.channels.max_images_per_message, 2);
+        assert_eq!(config.channels.max_image_bytes, 10 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_whatsapp_send_receipts_from_toml() {
+        assert!(!Config::default().channels.whatsapp.send_receipts);
+        let config: Config = toml::from_str("[channels.whatsapp]\nsend_receipts = true").unwrap();
+        assert!(config.channels.whatsapp.send_receipts);
+    }
+
+    #[test]
+    fn test_channel_max_concurrent_from_toml() {
+        assert_eq!(Config::default().channels.max_concurrent, 4);
+        let config: Config = toml::from_str("[channels]\nmax_concurrent = 1").unwrap();
+        assert_eq!(config.channels.max_concurrent, 1);
+    }
+
+    #[test]
+    fn test_dev_config_from_toml() {
+        assert!(!Config::default().dev.allow_direct_tools);
+        let config: Config = toml::from_str("[dev]\nallow_direct_tools = true").unwrap();
+        assert!(config.dev.allow_direct_tools);
+    }
+
+    #[test]
+    fn test_brain_config_from_toml() {
+        let defaults = Config::default().brain;
+        assert!(defaults.include.is_none());
+        assert!(defaults.max_tokens.is_none());
+
+        let config: Config =
+            toml::from_str("[brain]\ninclude = [\"SOUL\", \"USER\"]\nmax_tokens = 2000").unwrap();
+        assert_eq!(
+            config.brain.include,
+            Some(vec!["SOUL".to_string(), "USER".to_string()])
+        );
+        assert_eq!(config.brain.max_tokens, Some(2000));
+    }
+
+    #[test]
+    fn test_matrix_config_from_toml() {
+        let config: Config = toml::from_str(
+            "[channels.matrix]
+enabled = true
+homeserver = \"https://matrix.example.org\"
+user_id = \"@crabs:example.org\"
+allowed_users = [\"@alice:example.org\"]
+respond_to = \"all\"",
+        )
+        .unwrap();
+        let mx = &config.channels.matrix;
+        assert!(mx.enabled);
+        assert_eq!(mx.homeserver.as_deref(), Some("https://matrix.example.org"));
+        assert_eq!(mx.allowed_users, vec!["@alice:example.org"]);
+        assert_eq!(mx.respond_to, RespondTo::All);
+        assert!(mx.access_token.is_none() && mx.device_id.is_none());
+        assert!(config.is_matrix_owner("@alice:example.org"));
+        assert!(!config.is_matrix_owner("@bob:example.org"));
+    }
+
+    #[test]
+    fn test_image_generation_provider() {
+        assert_eq!(Config::default().image.generation.provider, "gemini");
+        let config: Config = toml::from_str(
+            "[image.generation]\nenabled = true\nprovider = \"openai\"\nmodel = \"gpt-image-1\"",
+        )
+        .unwrap();
+        assert_eq!(config.image.generation.provider, "openai");
+        assert_eq!(config.image.generation.model, "gpt-image-1");
+    }
+
     #[test]
     fn test_agent_config_defaults_when_absent() {
         // Config without [agent] section should use defaults
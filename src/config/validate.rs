@@ -0,0 +1,317 @@
+//! Config Validation
+//!
+//! Checks a loaded [`Config`] for values that parse but can't work — out of
+//! range numbers, contradictory settings, enabled features missing their
+//! credentials — plus keys in `config.toml` that nothing reads. Each finding
+//! names the section it came from so it can be fixed by hand.
+
+use super::Config;
+use std::fmt;
+use std::path::Path;
+
+/// How serious a config finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Startup continues; the setting is ignored or the feature stays off.
+    Warning,
+    /// Startup stops until the setting is fixed.
+    Error,
+}
+
+/// One finding, tied to the TOML section it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Table the finding is about, e.g. `channels.telegram`
+    pub section: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let icon = match self.severity {
+            Severity::Warning => "⚠️ ",
+            Severity::Error => "✗",
+        };
+        write!(f, "{icon} [{}] {}", self.section, self.message)
+    }
+}
+
+/// Everything [`Config::check`] found, in the order it was found.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    fn warn(&mut self, section: &str, message: impl Into<String>) {
+        self.push(Severity::Warning, section, message);
+    }
+
+    fn error(&mut self, section: &str, message: impl Into<String>) {
+        self.push(Severity::Error, section, message);
+    }
+
+    fn push(&mut self, severity: Severity, section: &str, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            section: section.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Add a warning for every key in `path` that no setting reads.
+    pub fn add_unknown_keys(&mut self, path: &Path) {
+        let file = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |f| f.to_string_lossy().into_owned(),
+        );
+        for key in unknown_keys(path) {
+            let (section, name) = key.rsplit_once('.').unwrap_or(("(top level)", &key));
+            self.warn(
+                section,
+                format!("unknown key `{name}` in {file} is ignored — check the spelling"),
+            );
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    /// Readable multi-line summary, errors first.
+    pub fn render(&self) -> String {
+        let errors = self.errors().count();
+        let warnings = self.diagnostics.len() - errors;
+        let mut out = format!("config.toml: {} error(s), {} warning(s)", errors, warnings);
+        for d in self.errors().chain(self.warnings()) {
+            out.push_str("\n  ");
+            out.push_str(&d.to_string());
+        }
+        out
+    }
+}
+
+/// Keys in a config file that don't map to any setting, as dotted paths.
+/// Empty when the file can't be read or parsed — loading reports that.
+pub fn unknown_keys(path: &Path) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut unknown = Vec::new();
+    let _: Result<Config, _> =
+        serde_ignored::deserialize(toml::Deserializer::new(&contents), |key| {
+            unknown.push(key.to_string())
+        });
+    unknown
+}
+
+impl Config {
+    /// Check settings that parse but can't work. Doesn't look at the files
+    /// themselves — see [`unknown_keys`] for that.
+    pub fn check(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        let valid_levels = ["trace", "debug", "info", "warn", "error"];
+        if !valid_levels.contains(&self.logging.level.as_str()) {
+            report.error(
+                "logging",
+                format!(
+                    "level = \"{}\" isn't a log level; use one of {}",
+                    self.logging.level,
+                    valid_levels.join(", ")
+                ),
+            );
+        }
+
+        if self.crabrace.enabled && self.crabrace.base_url.is_empty() {
+            report.error("crabrace", "enabled but base_url is empty");
+        }
+
+        // SamplingConfig::validate prefixes its own section name; leave it off
+        if let Err(e) = self.sampling.validate("") {
+            report.error("sampling", e.to_string().trim_start());
+        }
+        for (model, sampling) in &self.models {
+            if let Err(e) = sampling.validate("") {
+                report.error(&format!("models.\"{model}\""), e.to_string().trim_start());
+            }
+        }
+
+        if self.agent.context_limit == 0 {
+            report.error("agent", "context_limit must be greater than 0");
+        } else if self.agent.max_tokens >= self.agent.context_limit {
+            report.warn(
+                "agent",
+                format!(
+                    "max_tokens ({}) is not below context_limit ({}); long replies leave no room for history",
+                    self.agent.max_tokens, self.agent.context_limit
+                ),
+            );
+        }
+
+        if self.a2a.enabled {
+            if self.a2a.port == 0 {
+                report.error("a2a", "port must be between 1 and 65535");
+            }
+            let loopback = matches!(self.a2a.bind.as_str(), "127.0.0.1" | "::1" | "localhost");
+            if !loopback && self.a2a.api_key.as_deref().is_none_or(str::is_empty) {
+                report.warn(
+                    "a2a",
+                    format!(
+                        "bind = \"{}\" exposes the gateway without an api_key",
+                        self.a2a.bind
+                    ),
+                );
+            }
+        }
+
+        if self.heartbeat.enabled && self.heartbeat.interval_minutes == 0 {
+            report.error("heartbeat", "interval_minutes must be greater than 0");
+        }
+
+        for tool in &self.tools.disabled {
+            if self.tools.require_approval.contains(tool) {
+                report.warn(
+                    "tools",
+                    format!(
+                        "\"{tool}\" is in both disabled and require_approval; it stays disabled"
+                    ),
+                );
+            }
+        }
+
+        self.check_channel_credentials(&mut report);
+
+        report
+    }
+
+    /// Enabled channels that are missing what they need to connect. These
+    /// are warnings: the channel is skipped and everything else still starts.
+    fn check_channel_credentials(&self, report: &mut ValidationReport) {
+        let missing = |value: &Option<String>| value.as_deref().is_none_or(str::is_empty);
+        let ch = &self.channels;
+
+        if ch.telegram.enabled && missing(&ch.telegram.token) {
+            report.warn(
+                "channels.telegram",
+                "enabled but no token is set; add it to keys.toml",
+            );
+        }
+        if ch.discord.enabled && missing(&ch.discord.token) {
+            report.warn(
+                "channels.discord",
+                "enabled but no token is set; add it to keys.toml",
+            );
+        }
+        if ch.slack.enabled {
+            if missing(&ch.slack.token) {
+                report.warn(
+                    "channels.slack",
+                    "enabled but no bot token (xoxb-…) is set; add it to keys.toml",
+                );
+            }
+            if missing(&ch.slack.app_token) {
+                report.warn(
+                    "channels.slack",
+                    "enabled but no app_token (xapp-…) is set; Socket Mode needs it",
+                );
+            }
+        }
+        if ch.trello.enabled && (missing(&ch.trello.token) || missing(&ch.trello.app_token)) {
+            report.warn(
+                "channels.trello",
+                "enabled but the API key (app_token) or token is missing",
+            );
+        }
+        if ch.matrix.enabled {
+            for (key, value) in [
+                ("homeserver", &ch.matrix.homeserver),
+                ("user_id", &ch.matrix.user_id),
+                ("access_token", &ch.matrix.access_token),
+            ] {
+                if missing(value) {
+                    report.warn("channels.matrix", format!("enabled but {key} is not set"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_default_config_is_clean() {
+        assert!(Config::default().check().diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_enabled_channel_without_token_warns() {
+        let config: Config = toml::from_str("[channels.telegram]\nenabled = true").unwrap();
+        let report = config.check();
+        assert!(!report.has_errors());
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.section, "channels.telegram");
+        assert!(warning.message.contains("token"));
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_errors() {
+        let config: Config = toml::from_str(
+            "[agent]\ncontext_limit = 0\n[heartbeat]\nenabled = true\ninterval_minutes = 0",
+        )
+        .unwrap();
+        let report = config.check();
+        let sections: Vec<&str> = report.errors().map(|d| d.section.as_str()).collect();
+        assert_eq!(sections, ["agent", "heartbeat"]);
+        assert!(
+            report
+                .render()
+                .starts_with("config.toml: 2 error(s), 0 warning(s)")
+        );
+    }
+
+    #[test]
+    fn test_contradictory_tool_policy_warns() {
+        let config: Config =
+            toml::from_str("[tools]\ndisabled = [\"bash\"]\nrequire_approval = [\"bash\"]")
+                .unwrap();
+        let report = config.check();
+        assert!(report.warnings().any(|d| d.message.contains("\"bash\"")));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported_with_their_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "[agent]\ncontext_limt = 1000\n\n[channels.telegram]\nenabled = false\n\n[nonsense]\nx = 1"
+        )
+        .unwrap();
+        let unknown = unknown_keys(file.path());
+        assert_eq!(unknown, ["agent.context_limt", "nonsense"]);
+
+        let mut report = ValidationReport::default();
+        report.add_unknown_keys(file.path());
+        let first = &report.diagnostics[0];
+        assert_eq!(first.section, "agent");
+        assert!(first.message.contains("`context_limt`"));
+        assert_eq!(report.diagnostics[1].section, "(top level)");
+    }
+}
@@ -0,0 +1,198 @@
+//! Heartbeat Scheduler
+//!
+//! Background task that runs the checklist in `HEARTBEAT.md` as an autonomous
+//! agent turn in the owner's active session every `[heartbeat]
+//! interval_minutes`. Config is re-read each cycle, so enabling or changing
+//! the interval takes effect without a restart.
+//!
+//! A heartbeat never overlaps a user turn: it is skipped while the session is
+//! busy, and cancelled if the user starts a turn mid-heartbeat. Replies that
+//! are just `HEARTBEAT_OK` stay in the session; anything else is also pushed
+//! to the owner's Telegram/Discord/WhatsApp chat.
+
+use super::owner::OwnerChannels;
+use crate::brain::agent::AgentError;
+use crate::channels::ChannelFactory;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Reply token meaning "nothing needs attention".
+pub const HEARTBEAT_OK: &str = "HEARTBEAT_OK";
+
+/// Replies this short around `HEARTBEAT_OK` count as an acknowledgement.
+const ACK_MAX_CHARS: usize = 300;
+
+/// How often to re-check config while the heartbeat is disabled.
+const DISABLED_POLL: Duration = Duration::from_secs(60);
+
+/// Background scheduler that runs HEARTBEAT.md on an interval.
+pub struct HeartbeatScheduler {
+    factory: Arc<ChannelFactory>,
+    heartbeat_path: PathBuf,
+    owner: OwnerChannels,
+    shutdown: CancellationToken,
+    /// The session that was active when the scheduler was spawned.
+    initial_session_id: Option<Uuid>,
+}
+
+impl HeartbeatScheduler {
+    pub fn new(factory: Arc<ChannelFactory>, brain_path: PathBuf) -> Self {
+        Self {
+            factory,
+            heartbeat_path: brain_path.join("HEARTBEAT.md"),
+            owner: OwnerChannels::default(),
+            shutdown: CancellationToken::new(),
+            initial_session_id: None,
+        }
+    }
+
+    /// Push non-OK heartbeat replies to the owner's Telegram chat.
+    #[cfg(feature = "telegram")]
+    pub fn with_telegram(
+        mut self,
+        state: std::sync::Arc<crate::channels::telegram::TelegramState>,
+    ) -> Self {
+        self.owner.telegram = Some(state);
+        self
+    }
+
+    /// Push non-OK heartbeat replies to the owner's Discord channel.
+    #[cfg(feature = "discord")]
+    pub fn with_discord(
+        mut self,
+        state: std::sync::Arc<crate::channels::discord::DiscordState>,
+    ) -> Self {
+        self.owner.discord = Some(state);
+        self
+    }
+
+    /// Push non-OK heartbeat replies to the owner's WhatsApp chat.
+    #[cfg(feature = "whatsapp")]
+    pub fn with_whatsapp(
+        mut self,
+        state: std::sync::Arc<crate::channels::whatsapp::WhatsAppState>,
+    ) -> Self {
+        self.owner.whatsapp = Some(state);
+        self
+    }
+
+    /// Token that stops the scheduler (and any heartbeat in flight) when cancelled.
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// Spawn the scheduler as a background tokio task.
+    pub fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.initial_session_id = *self.factory.shared_session_id().lock().await;
+            tracing::info!("Heartbeat scheduler started");
+            loop {
+                let hb = self.factory.config_rx().borrow().heartbeat.clone();
+                let wait = if hb.enabled {
+                    Duration::from_secs(hb.interval_minutes.max(1) * 60)
+                } else {
+                    DISABLED_POLL
+                };
+
+                tokio::select! {
+                    _ = self.shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(wait) => {}
+                }
+
+                if hb.enabled
+                    && let Err(e) = self.tick().await
+                {
+                    tracing::error!("Heartbeat tick error: {e}");
+                }
+            }
+            tracing::info!("Heartbeat scheduler stopped");
+        })
+    }
+
+    /// One heartbeat: run HEARTBEAT.md in the owner's session if it's idle.
+    async fn tick(&self) -> anyhow::Result<()> {
+        let checklist = match tokio::fs::read_to_string(&self.heartbeat_path).await {
+            Ok(content) if !is_effectively_empty(&content) => content,
+            _ => {
+                tracing::debug!("Heartbeat skipped — HEARTBEAT.md missing or empty");
+                return Ok(());
+            }
+        };
+
+        let Some(session_id) = self.resolve_session_id().await else {
+            tracing::debug!("Heartbeat skipped — no owner session");
+            return Ok(());
+        };
+
+        let agent = self.factory.create_agent_service();
+        let result = agent
+            .send_background_message(
+                session_id,
+                heartbeat_prompt(&checklist),
+                self.shutdown.child_token(),
+            )
+            .await;
+
+        match result {
+            Ok(response) if is_heartbeat_ack(&response.content) => {
+                tracing::info!("Heartbeat — nothing needs attention");
+            }
+            Ok(response) => {
+                tracing::info!(
+                    "Heartbeat completed — {} tokens, ${:.6}",
+                    response.usage.total(),
+                    response.cost
+                );
+                let text = format!("💓 Heartbeat\n\n{}", response.content);
+                if !self.owner.send(None, &text).await {
+                    tracing::debug!("Heartbeat — no connected owner channel, TUI only");
+                }
+            }
+            Err(AgentError::SessionBusy(_)) => {
+                tracing::debug!("Heartbeat skipped — session {session_id} is busy");
+            }
+            Err(e) => return Err(e.into()),
+        }
+        Ok(())
+    }
+
+    /// The owner's current session, else the session at startup. Never
+    /// another session: the most recent one may belong to a channel user.
+    async fn resolve_session_id(&self) -> Option<Uuid> {
+        let current = *self.factory.shared_session_id().lock().await;
+        current.or(self.initial_session_id)
+    }
+}
+
+/// The user message sent for a heartbeat turn.
+fn heartbeat_prompt(checklist: &str) -> String {
+    format!(
+        "[Heartbeat] Follow this HEARTBEAT.md checklist strictly. Do not infer or repeat \
+         old tasks from prior chats. If nothing needs attention, reply {HEARTBEAT_OK}.\n\n{}",
+        checklist.trim()
+    )
+}
+
+/// True when HEARTBEAT.md has only blank lines and markdown headings.
+pub fn is_effectively_empty(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .all(|line| line.is_empty() || line.starts_with('#'))
+}
+
+/// True when the reply is `HEARTBEAT_OK`, optionally with short padding.
+pub fn is_heartbeat_ack(reply: &str) -> bool {
+    let reply = reply.trim();
+    let rest = if let Some(rest) = reply.strip_prefix(HEARTBEAT_OK) {
+        rest
+    } else if let Some(rest) = reply.strip_suffix(HEARTBEAT_OK) {
+        rest
+    } else {
+        return false;
+    };
+    rest.trim().chars().count() <= ACK_MAX_CHARS
+}
@@ -4,7 +4,16 @@
 //! executes due jobs in the user's active session. Never spawns new sessions —
 //! follows the user, falls back to initial session. Results are optionally
 //! delivered to a configured channel (Telegram, Discord, Slack).
+//!
+//! The reminder scheduler fires one-shot reminders set by the agent and
+//! delivers them to the owner, and runs tasks the agent scheduled for itself. The heartbeat scheduler runs HEARTBEAT.md as
+//! a periodic autonomous turn in the owner's session.
 
+pub mod heartbeat;
+mod owner;
+mod reminders;
 mod scheduler;
 
+pub use heartbeat::HeartbeatScheduler;
+pub use reminders::ReminderScheduler;
 pub use scheduler::CronScheduler;
@@ -0,0 +1,98 @@
+//! Owner Channels
+//!
+//! Proactive delivery to the owner's Telegram chat, Discord channel or
+//! WhatsApp chat, via the same owner-channel state the `*_send` tools use.
+//! Shared by the reminder and heartbeat schedulers.
+
+/// Connected owner channels a scheduler can push messages to.
+#[derive(Default, Clone)]
+pub(crate) struct OwnerChannels {
+    #[cfg(feature = "telegram")]
+    pub(crate) telegram: Option<std::sync::Arc<crate::channels::telegram::TelegramState>>,
+    #[cfg(feature = "discord")]
+    pub(crate) discord: Option<std::sync::Arc<crate::channels::discord::DiscordState>>,
+    #[cfg(feature = "whatsapp")]
+    pub(crate) whatsapp: Option<std::sync::Arc<crate::channels::whatsapp::WhatsAppState>>,
+}
+
+impl OwnerChannels {
+    /// Send to the preferred channel, falling back to the others.
+    /// Returns true if any channel delivered the message.
+    pub(crate) async fn send(&self, preferred: Option<&str>, text: &str) -> bool {
+        let order: &[&str] = match preferred {
+            Some("discord") => &["discord", "telegram", "whatsapp"],
+            Some("whatsapp") => &["whatsapp", "telegram", "discord"],
+            _ => &["telegram", "discord", "whatsapp"],
+        };
+        for channel in order {
+            if self.send_to(channel, text).await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Send to one owner channel. Returns true if delivered.
+    async fn send_to(&self, channel: &str, text: &str) -> bool {
+        match channel {
+            #[cfg(feature = "telegram")]
+            "telegram" => {
+                use teloxide::prelude::Requester;
+                use teloxide::types::ChatId;
+
+                let Some(ref state) = self.telegram else {
+                    return false;
+                };
+                let (Some(bot), Some(chat_id)) = (state.bot().await, state.owner_chat_id().await)
+                else {
+                    return false;
+                };
+                match bot.send_message(ChatId(chat_id), text).await {
+                    Ok(_) => true,
+                    Err(e) => {
+                        tracing::warn!("Owner delivery to Telegram failed: {e}");
+                        false
+                    }
+                }
+            }
+            #[cfg(feature = "discord")]
+            "discord" => {
+                let Some(ref state) = self.discord else {
+                    return false;
+                };
+                let (Some(http), Some(channel_id)) =
+                    (state.http().await, state.owner_channel_id().await)
+                else {
+                    return false;
+                };
+                match serenity::model::id::ChannelId::new(channel_id)
+                    .say(&http, text)
+                    .await
+                {
+                    Ok(_) => true,
+                    Err(e) => {
+                        tracing::warn!("Owner delivery to Discord failed: {e}");
+                        false
+                    }
+                }
+            }
+            #[cfg(feature = "whatsapp")]
+            "whatsapp" => {
+                let Some(ref state) = self.whatsapp else {
+                    return false;
+                };
+                if !state.is_connected().await || state.owner_jid().await.is_none() {
+                    return false;
+                }
+                match state.send_to_owner(text).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!("Owner delivery to WhatsApp failed: {e}");
+                        false
+                    }
+                }
+            }
+            _ => false,
+        }
+    }
+}
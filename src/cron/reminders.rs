@@ -0,0 +1,235 @@
+//! Reminder Scheduler
+//!
+//! Background task that checks the `reminders` table every 30 seconds and
+//! delivers due reminders to the owner: in the TUI, and on the reminder's
+//! channel (or the first connected one of Telegram/Discord/WhatsApp).
+//!
+//! Scheduled tasks (`schedule_task`) are reminders with `run_agent` set:
+//! their instruction runs as an autonomous turn in the session that scheduled
+//! it, under the usual approval rules, and the reply is delivered the same
+//! way. A task whose session is busy waits for the next tick.
+
+use super::owner::OwnerChannels;
+use crate::channels::ChannelFactory;
+use crate::db::ReminderRepository;
+use crate::db::models::Reminder;
+use crate::tui::events::TuiEvent;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Background scheduler that fires due reminders.
+pub struct ReminderScheduler {
+    repo: ReminderRepository,
+    /// TUI event sender — every fired reminder is also shown as a system message
+    tui_sender: Option<UnboundedSender<TuiEvent>>,
+    owner: OwnerChannels,
+    /// Builds the agent that runs scheduled tasks; without it tasks stay pending
+    factory: Option<Arc<ChannelFactory>>,
+}
+
+impl ReminderScheduler {
+    pub fn new(repo: ReminderRepository) -> Self {
+        Self {
+            repo,
+            tui_sender: None,
+            owner: OwnerChannels::default(),
+            factory: None,
+        }
+    }
+
+    /// Run scheduled tasks with agents from `factory`.
+    pub fn with_agent(mut self, factory: Arc<ChannelFactory>) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Show fired reminders in the TUI.
+    pub fn with_tui(mut self, sender: UnboundedSender<TuiEvent>) -> Self {
+        self.tui_sender = Some(sender);
+        self
+    }
+
+    /// Deliver reminders to the owner's Telegram chat.
+    #[cfg(feature = "telegram")]
+    pub fn with_telegram(
+        mut self,
+        state: std::sync::Arc<crate::channels::telegram::TelegramState>,
+    ) -> Self {
+        self.owner.telegram = Some(state);
+        self
+    }
+
+    /// Deliver reminders to the owner's Discord channel.
+    #[cfg(feature = "discord")]
+    pub fn with_discord(
+        mut self,
+        state: std::sync::Arc<crate::channels::discord::DiscordState>,
+    ) -> Self {
+        self.owner.discord = Some(state);
+        self
+    }
+
+    /// Deliver reminders to the owner's WhatsApp chat.
+    #[cfg(feature = "whatsapp")]
+    pub fn with_whatsapp(
+        mut self,
+        state: std::sync::Arc<crate::channels::whatsapp::WhatsAppState>,
+    ) -> Self {
+        self.owner.whatsapp = Some(state);
+        self
+    }
+
+    /// Spawn the scheduler as a background tokio task.
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Reminder scheduler started — polling every 30s");
+            loop {
+                if let Err(e) = self.tick().await {
+                    tracing::error!("Reminder scheduler tick error: {e}");
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            }
+        })
+    }
+
+    /// One scheduler tick: deliver every due reminder, start every due task
+    /// whose session is idle, and mark them fired. Returns how many fired.
+    pub async fn tick(&self) -> anyhow::Result<usize> {
+        let due = self.repo.list_due(Utc::now()).await?;
+        let mut fired = 0;
+        for reminder in &due {
+            let task = if reminder.run_agent {
+                match self.task_target(reminder).await {
+                    Some(target) => target,
+                    None => continue,
+                }
+            } else {
+                None
+            };
+
+            // Mark first so a slow or failing channel can't re-fire it every tick
+            self.repo.mark_fired(&reminder.id.to_string()).await?;
+            fired += 1;
+            match task {
+                Some((factory, session_id)) => {
+                    tracing::info!("Scheduled task {} is due — running", reminder.id);
+                    let delivery = self.clone_delivery();
+                    let reminder = reminder.clone();
+                    tokio::spawn(async move {
+                        let text = run_task(&factory, session_id, &reminder).await;
+                        delivery.deliver(&reminder, &text).await;
+                    });
+                }
+                None => {
+                    tracing::info!("Reminder {} is due — delivering", reminder.id);
+                    self.clone_delivery()
+                        .deliver(reminder, &format_reminder(reminder))
+                        .await;
+                }
+            }
+        }
+        Ok(fired)
+    }
+
+    /// The agent factory and the session that scheduled a due task, which is
+    /// where it runs. `None` leaves it pending until the next tick;
+    /// `Some(None)` delivers it as a plain reminder since it has no session.
+    #[allow(clippy::type_complexity)]
+    async fn task_target(
+        &self,
+        reminder: &Reminder,
+    ) -> Option<Option<(Arc<ChannelFactory>, Uuid)>> {
+        let Some(ref factory) = self.factory else {
+            tracing::debug!(
+                "Scheduled task {} waiting — no agent configured",
+                reminder.id
+            );
+            return None;
+        };
+        let Some(session_id) = reminder.session_id else {
+            tracing::warn!(
+                "Scheduled task {} has no session — delivering it as a reminder",
+                reminder.id
+            );
+            return Some(None);
+        };
+        if crate::brain::agent::service::is_session_busy(session_id) {
+            tracing::debug!("Scheduled task {} waiting — session is busy", reminder.id);
+            return None;
+        }
+        Some(Some((factory.clone(), session_id)))
+    }
+
+    fn clone_delivery(&self) -> Delivery {
+        Delivery {
+            tui_sender: self.tui_sender.clone(),
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+/// Where fired reminders and task results go.
+struct Delivery {
+    tui_sender: Option<UnboundedSender<TuiEvent>>,
+    owner: OwnerChannels,
+}
+
+impl Delivery {
+    async fn deliver(&self, reminder: &Reminder, text: &str) {
+        if let Some(ref sender) = self.tui_sender {
+            let _ = sender.send(TuiEvent::SystemMessage(text.to_string()));
+        }
+
+        if self.owner.send(reminder.channel.as_deref(), text).await {
+            return;
+        }
+        tracing::warn!(
+            "Reminder {} — no connected owner channel, shown in TUI only",
+            reminder.id
+        );
+    }
+}
+
+/// Run a scheduled task's instruction as an autonomous turn and return the
+/// text to deliver.
+async fn run_task(factory: &ChannelFactory, session_id: Uuid, reminder: &Reminder) -> String {
+    let agent = factory.create_agent_service();
+    let result = agent
+        .send_background_message(
+            session_id,
+            task_prompt(&reminder.message),
+            CancellationToken::new(),
+        )
+        .await;
+    match result {
+        Ok(response) => format!(
+            "⏱ Scheduled task: {}\n\n{}",
+            reminder.message, response.content
+        ),
+        Err(e) => {
+            tracing::warn!("Scheduled task {} failed: {}", reminder.id, e);
+            format!(
+                "⏱ Scheduled task failed: {}\n\n{}",
+                reminder.message,
+                e.user_message()
+            )
+        }
+    }
+}
+
+/// The user message sent for a scheduled task turn.
+fn task_prompt(instruction: &str) -> String {
+    format!(
+        "[Scheduled task] Earlier you scheduled this for now. Carry it out and report the \
+         result to the user.\n\n{}",
+        instruction.trim()
+    )
+}
+
+/// Message text delivered when a reminder fires.
+fn format_reminder(reminder: &Reminder) -> String {
+    format!("⏰ Reminder: {}", reminder.message)
+}
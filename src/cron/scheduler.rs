@@ -234,7 +234,7 @@ async fn execute_job(
             None, // no cancel token
             Some(Arc::new(|_| {
                 // Auto-approve all tools for cron jobs
-                Box::pin(async { Ok((true, false)) })
+                Box::pin(async { Ok(crate::brain::agent::ApprovalDecision::Approve) })
             })),
             None, // no progress callback
         )
@@ -245,7 +245,7 @@ async fn execute_job(
             tracing::info!(
                 "Cron job '{}' completed — {} tokens, ${:.6}",
                 job.name,
-                response.usage.input_tokens + response.usage.output_tokens,
+                response.usage.total(),
                 response.cost
             );
 
@@ -106,6 +106,35 @@ impl Database {
         Ok(())
     }
 
+    /// Newest migration this build knows about.
+    pub fn latest_schema_version() -> i64 {
+        sqlx::migrate!("./src/migrations")
+            .iter()
+            .map(|m| m.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Newest migration applied to this database, or `None` if it has never
+    /// been migrated.
+    pub async fn schema_version(&self) -> Option<i64> {
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(&self.pool)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Write a consistent copy of the database to `dest`, WAL included.
+    pub async fn snapshot_to(&self, dest: &Path) -> Result<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(dest.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to snapshot database to {}", dest.display()))?;
+        Ok(())
+    }
+
     /// Close the database connection
     pub async fn close(self) -> Result<()> {
         self.pool.close().await;
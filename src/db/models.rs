@@ -20,6 +20,8 @@ pub struct Session {
     pub token_count: i32,
     pub total_cost: f64,
     pub working_directory: Option<String>,
+    /// Unique human-readable name for sessions addressed by name (e.g. `run --session-name`)
+    pub name: Option<String>,
 }
 
 /// Message model
@@ -278,6 +280,83 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for CronJob {
     }
 }
 
+/// Reminder model — a one-shot message delivered to the owner when due, or
+/// (with `run_agent`) an instruction the agent carries out when due
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: Uuid,
+    pub message: String,
+    pub remind_at: DateTime<Utc>,
+    pub channel: Option<String>,
+    pub session_id: Option<Uuid>,
+    pub fired_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// Run `message` as an agent turn and deliver the reply
+    pub run_agent: bool,
+}
+
+impl Reminder {
+    pub fn new(
+        message: String,
+        remind_at: DateTime<Utc>,
+        channel: Option<String>,
+        session_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            message,
+            remind_at,
+            channel,
+            session_id,
+            fired_at: None,
+            created_at: Utc::now(),
+            run_agent: false,
+        }
+    }
+
+    /// A scheduled task: `instruction` runs as an agent turn at `run_at`.
+    pub fn new_task(
+        instruction: String,
+        run_at: DateTime<Utc>,
+        channel: Option<String>,
+        session_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            run_agent: true,
+            ..Self::new(instruction, run_at, channel, session_id)
+        }
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for Reminder {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> std::result::Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let parse_ts = |s: String| -> std::result::Result<DateTime<Utc>, sqlx::Error> {
+            DateTime::parse_from_rfc3339(&s)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        Ok(Reminder {
+            id: Uuid::parse_str(row.try_get("id")?)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            message: row.try_get("message")?,
+            remind_at: parse_ts(row.try_get("remind_at")?)?,
+            channel: row.try_get("channel")?,
+            session_id: row
+                .try_get::<Option<String>, _>("session_id")?
+                .and_then(|s| Uuid::parse_str(&s).ok()),
+            fired_at: row
+                .try_get::<Option<String>, _>("fired_at")?
+                .map(parse_ts)
+                .transpose()?,
+            created_at: parse_ts(row.try_get("created_at")?)?,
+            run_agent: row.try_get("run_agent")?,
+        })
+    }
+}
+
 impl Session {
     /// Create a new session
     pub fn new(
@@ -297,6 +376,7 @@ impl Session {
             token_count: 0,
             total_cost: 0.0,
             working_directory: None,
+            name: None,
         }
     }
 
@@ -358,6 +438,7 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for Session {
             token_count: row.try_get("token_count")?,
             total_cost: row.try_get("total_cost")?,
             working_directory: row.try_get("working_directory")?,
+            name: row.try_get("name")?,
         })
     }
 }
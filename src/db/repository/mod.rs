@@ -7,7 +7,9 @@ pub mod cron_job;
 pub mod file;
 pub mod message;
 pub mod plan;
+pub mod reminder;
 pub mod session;
+pub mod setting;
 pub mod usage_ledger;
 
 pub use channel_message::ChannelMessageRepository;
@@ -15,7 +17,9 @@ pub use cron_job::CronJobRepository;
 pub use file::FileRepository;
 pub use message::MessageRepository;
 pub use plan::PlanRepository;
+pub use reminder::ReminderRepository;
 pub use session::{SessionListOptions, SessionRepository};
+pub use setting::SettingRepository;
 pub use usage_ledger::UsageLedgerRepository;
 
 use anyhow::Result;
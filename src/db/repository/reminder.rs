@@ -0,0 +1,86 @@
+use crate::db::models::Reminder;
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::SqlitePool;
+
+/// Fixed-width UTC timestamp ("2026-01-02T09:00:00Z") so SQL string
+/// comparison on `remind_at` matches chronological order.
+fn sortable_ts(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Clone)]
+pub struct ReminderRepository {
+    pool: SqlitePool,
+}
+
+impl ReminderRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert(&self, reminder: &Reminder) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO reminders (id, message, remind_at, channel, session_id, fired_at, created_at, run_agent)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(reminder.id.to_string())
+        .bind(&reminder.message)
+        .bind(sortable_ts(reminder.remind_at))
+        .bind(&reminder.channel)
+        .bind(reminder.session_id.map(|id| id.to_string()))
+        .bind(reminder.fired_at.map(|d| d.to_rfc3339()))
+        .bind(reminder.created_at.to_rfc3339())
+        .bind(reminder.run_agent)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// All reminders that have not fired yet, soonest first.
+    pub async fn list_pending(&self) -> Result<Vec<Reminder>> {
+        let reminders = sqlx::query_as::<_, Reminder>(
+            "SELECT * FROM reminders WHERE fired_at IS NULL ORDER BY remind_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(reminders)
+    }
+
+    /// Pending reminders whose time is at or before `now`.
+    pub async fn list_due(&self, now: DateTime<Utc>) -> Result<Vec<Reminder>> {
+        let reminders = sqlx::query_as::<_, Reminder>(
+            "SELECT * FROM reminders WHERE fired_at IS NULL AND remind_at <= ? ORDER BY remind_at",
+        )
+        .bind(sortable_ts(now))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(reminders)
+    }
+
+    pub async fn find_by_id(&self, id: &str) -> Result<Option<Reminder>> {
+        let reminder = sqlx::query_as::<_, Reminder>("SELECT * FROM reminders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(reminder)
+    }
+
+    pub async fn mark_fired(&self, id: &str) -> Result<()> {
+        sqlx::query("UPDATE reminders SET fired_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a pending reminder. Returns false if it doesn't exist or already fired.
+    pub async fn cancel(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM reminders WHERE id = ? AND fired_at IS NULL")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
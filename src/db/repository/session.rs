@@ -42,13 +42,24 @@ impl SessionRepository {
         Ok(session)
     }
 
+    /// Find session by its unique name
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<Session>> {
+        let session = sqlx::query_as::<_, Session>("SELECT * FROM sessions WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to find session by name")?;
+
+        Ok(session)
+    }
+
     /// Create a new session
     pub async fn create(&self, session: &Session) -> Result<()> {
         sqlx::query(
             r#"
             INSERT INTO sessions (id, title, model, provider_name, created_at, updated_at,
-                                 archived_at, token_count, total_cost, working_directory)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                                 archived_at, token_count, total_cost, working_directory, name)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(session.id.to_string())
@@ -61,6 +72,7 @@ impl SessionRepository {
         .bind(session.token_count)
         .bind(session.total_cost)
         .bind(&session.working_directory)
+        .bind(&session.name)
         .execute(&self.pool)
         .await
         .context("Failed to create session")?;
@@ -75,7 +87,8 @@ impl SessionRepository {
             r#"
             UPDATE sessions
             SET title = ?, model = ?, provider_name = ?, updated_at = ?,
-                archived_at = ?, token_count = ?, total_cost = ?, working_directory = ?
+                archived_at = ?, token_count = ?, total_cost = ?, working_directory = ?,
+                name = ?
             WHERE id = ?
             "#,
         )
@@ -87,6 +100,7 @@ impl SessionRepository {
         .bind(session.token_count)
         .bind(session.total_cost)
         .bind(&session.working_directory)
+        .bind(&session.name)
         .bind(session.id.to_string())
         .execute(&self.pool)
         .await
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+/// Raw access to the `settings` table. Values are JSON text; typed access
+/// goes through `SettingsService`.
+#[derive(Clone)]
+pub struct SettingRepository {
+    pool: SqlitePool,
+}
+
+impl SettingRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<String>> {
+        let value = sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(value)
+    }
+
+    /// Insert or overwrite `key`.
+    pub async fn set(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        )
+        .bind(key)
+        .bind(value)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns false if the key wasn't set.
+    pub async fn delete(&self, key: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM settings WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// All `(key, value)` pairs whose key starts with `prefix`, sorted by key.
+    pub async fn list_prefix(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        // instr() instead of LIKE so `_` and `%` in keys match literally
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT key, value FROM settings WHERE instr(key, ?) = 1 ORDER BY key",
+        )
+        .bind(prefix)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,209 @@
+//! Audit Trail
+//!
+//! A durable record of what the agent did, separate from tracing logs: every
+//! tool invocation and every agent response is appended as one JSON line to
+//! `~/.opencrabs/audit/YYYY-MM-DD.jsonl`. Enabled with `[audit] enabled`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Longest tool result summary kept per entry
+const MAX_SUMMARY_CHARS: usize = 500;
+
+/// Something the agent did.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    ToolCall {
+        session_id: Uuid,
+        tool: String,
+        input: Value,
+        success: bool,
+        summary: String,
+        duration_ms: u64,
+    },
+    Response {
+        session_id: Uuid,
+        provider: String,
+        model: String,
+        input_tokens: u32,
+        output_tokens: u32,
+        cost: f64,
+    },
+}
+
+/// One line of the audit file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+/// Directory holding the daily audit files
+pub fn audit_dir() -> PathBuf {
+    crate::config::opencrabs_home().join("audit")
+}
+
+/// Append `event` to today's audit file. Failures are logged, never fatal.
+/// Inside a runtime the write runs on the blocking pool, so callers in async
+/// code never stall on file I/O.
+pub fn record(event: AuditEvent) {
+    let entry = AuditEntry {
+        timestamp: Utc::now(),
+        event,
+    };
+    let write = move || {
+        if let Err(e) = append(&audit_dir(), &crate::config::local_today(), &entry) {
+            tracing::warn!("Failed to write audit entry: {}", e);
+        }
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn_blocking(write);
+        }
+        Err(_) => write(),
+    }
+}
+
+/// Cap a tool result for the audit file.
+pub fn summarize(output: &str) -> String {
+    output.chars().take(MAX_SUMMARY_CHARS).collect()
+}
+
+fn append(dir: &Path, day: &str, entry: &AuditEntry) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{day}.jsonl")))?
+        .write_all(line.as_bytes())
+}
+
+/// The last `n` entries across the daily files, oldest first.
+/// Lines that don't parse are skipped.
+pub fn tail(dir: &Path, n: usize) -> Vec<AuditEntry> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = read_dir
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    // YYYY-MM-DD names sort chronologically; walk back from the newest day
+    files.sort();
+
+    let mut entries: Vec<AuditEntry> = Vec::new();
+    for file in files.iter().rev() {
+        let content = std::fs::read_to_string(file).unwrap_or_default();
+        let mut day: Vec<AuditEntry> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        day.append(&mut entries);
+        entries = day;
+        if entries.len() >= n {
+            break;
+        }
+    }
+    let skip = entries.len().saturating_sub(n);
+    entries.split_off(skip)
+}
+
+impl AuditEntry {
+    /// One-line human-readable form for `/audit tail`.
+    pub fn display_line(&self) -> String {
+        let time = crate::config::format_local(self.timestamp, "%m-%d %H:%M:%S");
+        match &self.event {
+            AuditEvent::ToolCall {
+                tool,
+                success,
+                duration_ms,
+                summary,
+                ..
+            } => {
+                let status = if *success { "✓" } else { "✗" };
+                let first_line = summary.lines().next().unwrap_or("");
+                let preview: String = first_line.chars().take(60).collect();
+                format!("{time}  {status} {tool} ({duration_ms}ms) {preview}")
+            }
+            AuditEvent::Response {
+                provider,
+                model,
+                input_tokens,
+                output_tokens,
+                cost,
+                ..
+            } => format!(
+                "{time}  ↩ {provider}/{model} in={input_tokens} out={output_tokens} ${cost:.4}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_entry(tool: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            event: AuditEvent::ToolCall {
+                session_id: Uuid::nil(),
+                tool: tool.to_string(),
+                input: serde_json::json!({"path": "src/main.rs"}),
+                success: true,
+                summary: "ok".to_string(),
+                duration_ms: 12,
+            },
+        }
+    }
+
+    #[test]
+    fn test_append_and_tail_across_days() {
+        let dir = tempfile::tempdir().unwrap();
+        append(dir.path(), "2025-01-01", &tool_entry("read")).unwrap();
+        append(dir.path(), "2025-01-02", &tool_entry("edit")).unwrap();
+        append(dir.path(), "2025-01-02", &tool_entry("bash")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let names = |entries: Vec<AuditEntry>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|e| match e.event {
+                    AuditEvent::ToolCall { tool, .. } => tool,
+                    AuditEvent::Response { model, .. } => model,
+                })
+                .collect()
+        };
+        assert_eq!(names(tail(dir.path(), 2)), vec!["edit", "bash"]);
+        assert_eq!(names(tail(dir.path(), 10)), vec!["read", "edit", "bash"]);
+        assert!(tail(&dir.path().join("missing"), 5).is_empty());
+    }
+
+    #[test]
+    fn test_entry_is_flat_json() {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            event: AuditEvent::Response {
+                session_id: Uuid::nil(),
+                provider: "anthropic".to_string(),
+                model: "claude-sonnet-4-5".to_string(),
+                input_tokens: 1200,
+                output_tokens: 300,
+                cost: 0.0081,
+            },
+        };
+        let json: Value = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["kind"], "response");
+        assert_eq!(json["input_tokens"], 1200);
+        assert!(json["timestamp"].is_string());
+    }
+}
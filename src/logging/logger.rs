@@ -3,9 +3,15 @@
 //! Provides configurable logging with conditional file output for debug mode.
 
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use tracing::Level;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+/// Live filter handle for `/loglevel`, plus whether debug mode is on
+static FILTER_HANDLE: OnceLock<(reload::Handle<EnvFilter, Registry>, bool)> = OnceLock::new();
 
 /// Logging configuration
 #[derive(Debug, Clone)]
@@ -31,10 +37,9 @@ pub struct LogConfig {
 
 impl Default for LogConfig {
     fn default() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
         Self {
             debug_mode: false,
-            log_dir: home.join(".opencrabs").join("logs"),
+            log_dir: crate::config::opencrabs_home().join("logs"),
             log_level: Level::INFO,
             console_output: false,
             log_prefix: "opencrabs".to_string(),
@@ -83,6 +88,67 @@ impl LogConfig {
     }
 }
 
+/// Parse a level name (`warn`, `info`, `debug`, `trace`, `error`).
+pub fn parse_level(name: &str) -> Option<Level> {
+    name.trim().parse().ok()
+}
+
+/// `-v` count → level: none = warn, `-v` info, `-vv` debug, `-vvv` trace.
+pub fn level_for_verbosity(verbose: u8) -> Level {
+    match verbose {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Build the filter for `level`. `RUST_LOG`, when set, wins outright.
+fn build_filter(level: Level, debug_mode: bool) -> Result<EnvFilter, Box<dyn std::error::Error>> {
+    if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+        return Ok(EnvFilter::from_default_env());
+    }
+    if !debug_mode {
+        // Minimal logging: dependencies stay at WARN, `level` for opencrabs itself
+        return Ok(EnvFilter::new("")
+            .add_directive(Level::WARN.into())
+            .add_directive(format!("opencrabs={}", level).parse()?));
+    }
+    Ok(EnvFilter::new("")
+        .add_directive(level.into())
+        .add_directive("sqlx=warn".parse()?)
+        .add_directive("hyper=warn".parse()?)
+        .add_directive("reqwest=warn".parse()?)
+        .add_directive("tower=warn".parse()?)
+        // whatsapp-rust logs TODO stubs for unimplemented upstream handlers — suppress
+        .add_directive("whatsapp_rust::client=error".parse()?)
+        .add_directive("whatsapp_rust=warn".parse()?))
+}
+
+/// Wrap `filter` so `set_log_level` can swap it at runtime.
+fn reloadable(filter: EnvFilter, debug_mode: bool) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(filter);
+    let _ = FILTER_HANDLE.set((handle, debug_mode));
+    layer
+}
+
+/// Change the log level of the running process (`/loglevel`).
+/// Ignored while `RUST_LOG` is set, since that overrides everything.
+pub fn set_log_level(name: &str) -> Result<Level, String> {
+    let level = parse_level(name)
+        .ok_or_else(|| format!("unknown log level '{name}' (error, warn, info, debug, trace)"))?;
+    if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+        return Err("RUST_LOG is set and overrides the log level".to_string());
+    }
+    let (handle, debug_mode) = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "logging is not initialized".to_string())?;
+    let filter = build_filter(level, *debug_mode).map_err(|e| e.to_string())?;
+    handle.reload(filter).map_err(|e| e.to_string())?;
+    tracing::info!("📊 Log level changed to {}", level);
+    Ok(level)
+}
+
 /// Result of logger initialization
 pub struct LoggerGuard {
     /// Keep the worker guard alive to ensure logs are flushed
@@ -145,15 +211,7 @@ fn init_debug_logging(config: LogConfig) -> Result<LoggerGuard, Box<dyn std::err
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
     // Build environment filter
-    let env_filter = EnvFilter::from_default_env()
-        .add_directive(config.log_level.into())
-        .add_directive("sqlx=warn".parse()?)
-        .add_directive("hyper=warn".parse()?)
-        .add_directive("reqwest=warn".parse()?)
-        .add_directive("tower=warn".parse()?)
-        // whatsapp-rust logs TODO stubs for unimplemented upstream handlers — suppress
-        .add_directive("whatsapp_rust::client=error".parse()?)
-        .add_directive("whatsapp_rust=warn".parse()?);
+    let env_filter = reloadable(build_filter(config.log_level, true)?, true);
 
     // Initialize subscriber with file logging
     tracing_subscriber::registry()
@@ -181,9 +239,7 @@ fn init_debug_logging(config: LogConfig) -> Result<LoggerGuard, Box<dyn std::err
 /// Initialize minimal logging (no file output)
 fn init_minimal_logging(config: LogConfig) -> Result<LoggerGuard, Box<dyn std::error::Error>> {
     // Build environment filter - minimal logging
-    let env_filter = EnvFilter::from_default_env()
-        .add_directive(Level::WARN.into()) // Only warnings and errors
-        .add_directive("opencrabs=info".parse()?); // INFO for opencrabs itself
+    let env_filter = reloadable(build_filter(config.log_level, false)?, false);
 
     if config.console_output {
         // Console output for non-TUI modes
@@ -216,8 +272,7 @@ pub fn setup_from_cli(debug: bool) -> Result<LoggerGuard, Box<dyn std::error::Er
 
 /// Get the path to the current log file (if debug mode is enabled)
 pub fn get_log_path() -> Option<PathBuf> {
-    let home = dirs::home_dir()?;
-    let log_dir = home.join(".opencrabs").join("logs");
+    let log_dir = crate::config::opencrabs_home().join("logs");
 
     if log_dir.exists() {
         // Return the most recent log file
@@ -240,8 +295,7 @@ pub fn get_log_path() -> Option<PathBuf> {
 
 /// Clean up old log files based on max age
 pub fn cleanup_old_logs(max_age_days: u64) -> Result<usize, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let log_dir = home.join(".opencrabs").join("logs");
+    let log_dir = crate::config::opencrabs_home().join("logs");
 
     if !log_dir.exists() {
         return Ok(0);
@@ -301,6 +355,16 @@ mod tests {
         assert_eq!(config.log_prefix, "test");
     }
 
+    #[test]
+    fn test_verbosity_and_level_names() {
+        assert_eq!(level_for_verbosity(0), Level::WARN);
+        assert_eq!(level_for_verbosity(1), Level::INFO);
+        assert_eq!(level_for_verbosity(2), Level::DEBUG);
+        assert_eq!(level_for_verbosity(7), Level::TRACE);
+        assert_eq!(parse_level("Debug"), Some(Level::DEBUG));
+        assert_eq!(parse_level("loud"), None);
+    }
+
     #[test]
     fn test_log_dir_in_home_opencrabs_folder() {
         let config = LogConfig::default();
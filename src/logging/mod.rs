@@ -1,5 +1,6 @@
 //! Logging and Debug System
 
+pub mod audit;
 mod logger;
 
 pub use logger::*;
@@ -11,8 +11,26 @@ async fn main() -> Result<()> {
     // Parse CLI arguments first to check for debug flag
     let cli_args = cli::Cli::parse();
 
-    // Initialize logging based on --debug flag
+    // Apply --workspace before anything resolves the OpenCrabs home directory
+    if let Some(ref workspace) = cli_args.workspace {
+        opencrabs::config::set_opencrabs_home(workspace);
+    }
+
+    // Apply --config so every later config read and write uses that file
+    if let Some(ref path) = cli_args.config {
+        opencrabs::config::set_config_path(std::path::Path::new(path));
+    }
+
+    // Initialize logging based on --debug, -v and [logging] level.
+    // A command-line flag always wins over the config file.
     let mut log_config = logging::LogConfig::new().with_debug_mode(cli_args.debug);
+    if cli_args.verbose > 0 {
+        log_config = log_config.with_log_level(logging::level_for_verbosity(cli_args.verbose));
+    } else if !cli_args.debug
+        && let Some(level) = configured_log_level()
+    {
+        log_config = log_config.with_log_level(level);
+    }
 
     // Custom log directory from env
     if let Ok(log_dir) = std::env::var("DEBUG_LOGS_LOCATION") {
@@ -32,6 +50,9 @@ async fn main() -> Result<()> {
 
     // Run CLI application
     let result = cli::run().await;
+    if let Err(ref e) = result {
+        eprintln!("Error: {:#}", e);
+    }
 
     // Use libc::_exit instead of std::process::exit — skips C atexit handlers
     // which avoids llama.cpp Metal device destructor crash on macOS ARM.
@@ -39,3 +60,9 @@ async fn main() -> Result<()> {
     let code = if result.is_ok() { 0 } else { 1 };
     unsafe { libc::_exit(code) }
 }
+
+/// `[logging] level` from the config file, if it loads and names a level.
+fn configured_log_level() -> Option<tracing::Level> {
+    let config = opencrabs::config::Config::load().ok()?;
+    logging::parse_level(&config.logging.level)
+}
@@ -1,5 +1,7 @@
 //! Embedding — singleton engine, generate and store vector embeddings.
 
+use super::error::{MemoryError, Result};
+use super::store::lock_store;
 use once_cell::sync::OnceCell;
 use qmd::{EmbeddingEngine, Store, pull_model};
 use std::sync::Mutex;
@@ -21,16 +23,16 @@ fn silence_llama_logs() {
 /// Downloads the embeddinggemma-300M model (~300MB) on first call.
 /// Returns Err if the download fails (e.g. no internet) or if the CPU lacks
 /// AVX (required by llama.cpp GGUF inference) — callers fall back to FTS-only.
-pub fn get_engine() -> Result<&'static Mutex<EmbeddingEngine>, String> {
+pub fn get_engine() -> Result<&'static Mutex<EmbeddingEngine>> {
     ENGINE.get_or_try_init(|| {
         check_cpu_features()?;
         silence_llama_logs();
 
         let pull = pull_model(qmd::llm::DEFAULT_EMBED_MODEL_URI, false)
-            .map_err(|e| format!("Failed to pull embedding model: {e}"))?;
+            .map_err(|e| MemoryError::ModelPull(e.to_string()))?;
 
-        let engine = EmbeddingEngine::new(&pull.path)
-            .map_err(|e| format!("Failed to init embedding engine: {e}"))?;
+        let engine =
+            EmbeddingEngine::new(&pull.path).map_err(|e| MemoryError::EngineInit(e.to_string()))?;
 
         tracing::info!(
             "Embedding engine ready: {} ({:.1} MB)",
@@ -43,15 +45,15 @@ pub fn get_engine() -> Result<&'static Mutex<EmbeddingEngine>, String> {
 
 /// Verify the CPU supports the instruction sets required by llama.cpp.
 /// Returns Err on x86 without AVX; passes through on ARM/other architectures.
-fn check_cpu_features() -> Result<(), String> {
+fn check_cpu_features() -> Result<()> {
     #[cfg(target_arch = "x86_64")]
     {
         if !std::arch::is_x86_feature_detected!("avx") {
-            return Err(
+            return Err(MemoryError::UnsupportedCpu(
                 "CPU lacks AVX — llama.cpp GGUF inference requires AVX (Sandy Bridge 2011+). \
                  Memory search will use FTS-only."
                     .to_string(),
-            );
+            ));
         }
     }
     Ok(())
@@ -87,8 +89,8 @@ pub fn embed_content(store: &Mutex<Store>, body: &str) {
 
     // Store lock → insert → release
     let now = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string();
-    if let Ok(s) = store.lock()
-        && let Err(e) = s.insert_embedding(&hash, 0, 0, &emb.embedding, &emb.model, &now)
+    if let Err(e) =
+        lock_store(store).insert_embedding(&hash, 0, 0, &emb.embedding, &emb.model, &now)
     {
         tracing::debug!("Failed to store embedding: {e}");
     }
@@ -108,10 +110,9 @@ pub(super) fn backfill_embeddings(store: &Mutex<Store>) {
     };
 
     // Store lock: get hashes needing embeddings → release
-    let needing = match store.lock() {
-        Ok(s) => s.get_hashes_needing_embedding().unwrap_or_default(),
-        Err(_) => return,
-    };
+    let needing = lock_store(store)
+        .get_hashes_needing_embedding()
+        .unwrap_or_default();
 
     if needing.is_empty() {
         return;
@@ -142,8 +143,8 @@ pub(super) fn backfill_embeddings(store: &Mutex<Store>) {
 
         // Store lock: insert embedding → release
         if let Some(emb) = emb
-            && let Ok(s) = store.lock()
-            && s.insert_embedding(hash, 0, 0, &emb.embedding, &emb.model, &now)
+            && lock_store(store)
+                .insert_embedding(hash, 0, 0, &emb.embedding, &emb.model, &now)
                 .is_ok()
         {
             stored += 1;
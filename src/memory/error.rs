@@ -0,0 +1,125 @@
+//! Memory error types
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Memory error types
+///
+/// Errors raised by the `qmd` crate are carried as their message, since
+/// callers only need to tell which operation failed.
+#[derive(Debug, Error)]
+pub enum MemoryError {
+    /// Could not create `~/.opencrabs/memory/`
+    #[error("Failed to create memory dir: {0}")]
+    CreateDir(#[source] std::io::Error),
+
+    /// Could not list `~/.opencrabs/memory/`
+    #[error("Failed to read memory dir: {0}")]
+    ReadDir(#[source] std::io::Error),
+
+    /// File to index does not exist
+    #[error("Failed to read {}: {source}", .path.display())]
+    NotFound {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Any other I/O error reading a file to index
+    #[error("Failed to read {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Opening the memory database failed
+    #[error("Failed to open memory store: {0}")]
+    StoreOpen(String),
+
+    /// Moving a corrupted memory database aside failed
+    #[error("Failed to move corrupted memory store aside: {0}")]
+    Recover(#[source] std::io::Error),
+
+    /// Creating the vector table failed
+    #[error("Failed to create vector table: {0}")]
+    VectorTable(String),
+
+    /// Inserting content or a document row failed
+    #[error("Failed to insert {what}: {message}")]
+    Insert { what: &'static str, message: String },
+
+    /// FTS5 query failed (e.g. syntax error)
+    #[error("FTS search failed: {0}")]
+    Fts(String),
+
+    /// Downloading the embedding model failed
+    #[error("Failed to pull embedding model: {0}")]
+    ModelPull(String),
+
+    /// Loading the embedding model failed
+    #[error("Failed to init embedding engine: {0}")]
+    EngineInit(String),
+
+    /// CPU lacks instructions llama.cpp needs
+    #[error("{0}")]
+    UnsupportedCpu(String),
+
+    /// A blocking task panicked or was cancelled
+    #[error("spawn_blocking failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+impl MemoryError {
+    /// Build the right read error for `path`, splitting out missing files.
+    pub(crate) fn read(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        let path = path.into();
+        if source.kind() == std::io::ErrorKind::NotFound {
+            Self::NotFound { path, source }
+        } else {
+            Self::Io { path, source }
+        }
+    }
+}
+
+/// Result type for memory operations
+pub type Result<T> = std::result::Result<T, MemoryError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_error_splits_not_found() {
+        let missing = MemoryError::read(
+            "/tmp/nope.md",
+            std::io::Error::from(std::io::ErrorKind::NotFound),
+        );
+        assert!(matches!(missing, MemoryError::NotFound { .. }));
+        assert!(
+            missing
+                .to_string()
+                .starts_with("Failed to read /tmp/nope.md: ")
+        );
+
+        let denied = MemoryError::read(
+            "/tmp/locked.md",
+            std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        );
+        assert!(matches!(denied, MemoryError::Io { .. }));
+    }
+
+    #[test]
+    fn test_messages_match_previous_strings() {
+        assert_eq!(
+            MemoryError::Fts("syntax error".into()).to_string(),
+            "FTS search failed: syntax error"
+        );
+        assert_eq!(
+            MemoryError::Insert {
+                what: "document",
+                message: "constraint failed".into()
+            }
+            .to_string(),
+            "Failed to insert document: constraint failed"
+        );
+    }
+}
@@ -5,6 +5,8 @@ use std::path::Path;
 use std::sync::Mutex;
 
 use super::embedding::{backfill_embeddings, embed_content};
+use super::error::{MemoryError, Result};
+use super::store::lock_store;
 use super::{COLLECTION_BRAIN, COLLECTION_MEMORY};
 
 /// Brain files loaded from the workspace root (`~/.opencrabs/`).
@@ -25,18 +27,30 @@ const BRAIN_FILES: &[&str] = &[
 ///
 /// Skips re-indexing if the file's SHA-256 hash hasn't changed.
 /// Generates an embedding when the engine is already initialized.
-pub async fn index_file(store: &'static Mutex<Store>, path: &Path) -> Result<(), String> {
+pub async fn index_file(store: &'static Mutex<Store>, path: &Path) -> Result<()> {
+    index_into(store, COLLECTION_MEMORY, path).await
+}
+
+/// Index a single workspace brain file (`USER.md`, `AGENTS.md`, …) into the
+/// `"brain"` collection, with the same hash skip and embedding as [`index_file`].
+pub async fn index_brain_file(store: &'static Mutex<Store>, path: &Path) -> Result<()> {
+    index_into(store, COLLECTION_BRAIN, path).await
+}
+
+async fn index_into(
+    store: &'static Mutex<Store>,
+    collection: &'static str,
+    path: &Path,
+) -> Result<()> {
     let body = tokio::fs::read_to_string(path)
         .await
-        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        .map_err(|e| MemoryError::read(path, e))?;
 
     let path = path.to_path_buf();
     tokio::task::spawn_blocking(move || {
         let indexed = {
-            let s = store
-                .lock()
-                .map_err(|e| format!("Store lock poisoned: {e}"))?;
-            index_file_sync(&s, COLLECTION_MEMORY, &path, &body)?
+            let s = lock_store(store);
+            index_file_sync(&s, collection, &path, &body)?
         };
 
         if indexed {
@@ -45,18 +59,12 @@ pub async fn index_file(store: &'static Mutex<Store>, path: &Path) -> Result<(),
 
         Ok(())
     })
-    .await
-    .map_err(|e| format!("spawn_blocking failed: {e}"))?
+    .await?
 }
 
 /// Synchronous inner implementation for indexing a single file into a given collection.
 /// Returns `true` if new content was indexed, `false` if hash-skipped.
-fn index_file_sync(
-    store: &Store,
-    collection: &str,
-    path: &Path,
-    body: &str,
-) -> Result<bool, String> {
+fn index_file_sync(store: &Store, collection: &str, path: &Path, body: &str) -> Result<bool> {
     let hash = Store::hash_content(body);
     let rel_path = path
         .file_name()
@@ -81,10 +89,16 @@ fn index_file_sync(
 
     store
         .insert_content(&hash, body, &now)
-        .map_err(|e| format!("Failed to insert content: {e}"))?;
+        .map_err(|e| MemoryError::Insert {
+            what: "content",
+            message: e.to_string(),
+        })?;
     store
         .insert_document(collection, &rel_path, &title, &hash, &now, &now)
-        .map_err(|e| format!("Failed to insert document: {e}"))?;
+        .map_err(|e| MemoryError::Insert {
+            what: "document",
+            message: e.to_string(),
+        })?;
 
     tracing::debug!("Indexed {collection} file: {}", path.display());
     Ok(true)
@@ -95,7 +109,7 @@ fn index_file_sync(
 /// Also deactivates entries for files that no longer exist on disk.
 /// After indexing, backfills embeddings for any documents missing them.
 /// Returns the number of files indexed.
-pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize, String> {
+pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize> {
     let home = crate::config::opencrabs_home();
     let dir = home.join("memory");
     let mut indexed = 0usize;
@@ -104,8 +118,7 @@ pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize, String> {
 
     // --- Index daily memory logs ---
     if dir.exists() {
-        let entries =
-            std::fs::read_dir(&dir).map_err(|e| format!("Failed to read memory dir: {e}"))?;
+        let entries = std::fs::read_dir(&dir).map_err(MemoryError::ReadDir)?;
 
         for entry in entries.flatten() {
             let path = entry.path();
@@ -135,17 +148,14 @@ pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize, String> {
             };
             brain_on_disk.push(name.to_string());
 
-            let result: Result<bool, String> = tokio::task::spawn_blocking({
+            let result: Result<bool> = tokio::task::spawn_blocking({
                 let path = path.clone();
                 move || {
-                    let store = store
-                        .lock()
-                        .map_err(|e| format!("Store lock poisoned: {e}"))?;
+                    let store = lock_store(store);
                     index_file_sync(&store, COLLECTION_BRAIN, &path, &body)
                 }
             })
-            .await
-            .map_err(|e| format!("spawn_blocking failed: {e}"))?;
+            .await?;
 
             match result {
                 Ok(_) => indexed += 1,
@@ -155,11 +165,9 @@ pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize, String> {
     }
 
     // --- Prune deleted files from both collections ---
-    let prune_result: Result<(), String> = tokio::task::spawn_blocking({
+    let prune_result: Result<()> = tokio::task::spawn_blocking({
         move || {
-            let store = store
-                .lock()
-                .map_err(|e| format!("Store lock poisoned: {e}"))?;
+            let store = lock_store(store);
 
             if let Ok(db_paths) = store.get_active_document_paths(COLLECTION_MEMORY) {
                 for db_path in &db_paths {
@@ -182,17 +190,14 @@ pub async fn reindex(store: &'static Mutex<Store>) -> Result<usize, String> {
             Ok(())
         }
     })
-    .await
-    .map_err(|e| format!("spawn_blocking failed: {e}"))?;
+    .await?;
 
     if let Err(e) = prune_result {
         tracing::warn!("Memory prune failed: {e}");
     }
 
     // --- Backfill embeddings for documents missing them ---
-    tokio::task::spawn_blocking(move || backfill_embeddings(store))
-        .await
-        .map_err(|e| format!("spawn_blocking failed: {e}"))?;
+    tokio::task::spawn_blocking(move || backfill_embeddings(store)).await?;
 
     tracing::info!("Memory reindex complete: {} files", indexed);
     Ok(indexed)
@@ -5,14 +5,16 @@
 //! is available, FTS-only fallback otherwise.
 
 mod embedding;
+mod error;
 mod index;
 mod search;
 mod store;
 
 pub use embedding::{embed_content, engine_if_ready, get_engine};
-pub use index::{index_file, reindex};
+pub use error::MemoryError;
+pub use index::{index_brain_file, index_file, reindex};
 pub use search::search;
-pub use store::get_store;
+pub use store::{get_store, lock_store};
 
 /// A single search result from the memory index.
 #[derive(Debug, Clone)]
@@ -5,6 +5,8 @@ use std::path::Path;
 use std::sync::Mutex;
 
 use super::embedding::engine_if_ready;
+use super::error::{MemoryError, Result};
+use super::store::lock_store;
 use super::{COLLECTION_BRAIN, MemoryResult};
 
 /// Hybrid search across memory logs: FTS5 (BM25) + vector (cosine) via RRF.
@@ -15,7 +17,7 @@ pub async fn search(
     store: &'static Mutex<Store>,
     query: &str,
     n: usize,
-) -> Result<Vec<MemoryResult>, String> {
+) -> Result<Vec<MemoryResult>> {
     let fts_query = sanitize_fts_query(query);
     if fts_query.is_empty() {
         return Ok(vec![]);
@@ -32,14 +34,12 @@ pub async fn search(
         });
 
         // Store lock → search
-        let store = store
-            .lock()
-            .map_err(|e| format!("Store lock poisoned: {e}"))?;
+        let store = lock_store(store);
         let home = crate::config::opencrabs_home();
 
         let fts_results = store
             .search_fts(&fts_query, n, None)
-            .map_err(|e| format!("FTS search failed: {e}"))?;
+            .map_err(|e| MemoryError::Fts(e.to_string()))?;
 
         // Hybrid path: combine FTS + vector results via Reciprocal Rank Fusion
         if let Some(ref query_emb) = query_embedding {
@@ -81,8 +81,7 @@ pub async fn search(
             })
             .collect())
     })
-    .await
-    .map_err(|e| format!("spawn_blocking failed: {e}"))?
+    .await?
 }
 
 /// Convert SearchResults to RRF tuple format: (file_path, display_path, title, body).
@@ -121,17 +120,57 @@ fn resolve_path(home: &Path, collection: &str, doc_path: &str) -> String {
     p.to_string_lossy().to_string()
 }
 
+/// FTS5 operators passed through from the user's query
+const FTS_OPERATORS: &[&str] = &["AND", "OR", "NOT"];
+
 /// Sanitize a search query for FTS5: wrap each word in double quotes
 /// to avoid syntax errors from special characters, then join with spaces (implicit AND).
+/// `"quoted phrases"` stay phrases and uppercase `AND`/`OR`/`NOT` stay operators;
+/// an operator without a term on both sides is dropped (the later of two wins).
 fn sanitize_fts_query(query: &str) -> String {
-    query
-        .split_whitespace()
-        .map(|w| {
-            let clean: String = w.chars().filter(|c| *c != '"').collect();
-            format!("\"{clean}\"")
-        })
-        .collect::<Vec<_>>()
-        .join(" ")
+    let mut terms: Vec<String> = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+            let words: Vec<&str> = phrase.split_whitespace().collect();
+            if !words.is_empty() {
+                terms.push(format!("\"{}\"", words.join(" ")));
+            }
+        } else {
+            let word: String = chars.by_ref().take_while(|c| !c.is_whitespace()).collect();
+            if FTS_OPERATORS.contains(&word.as_str()) {
+                terms.push(word);
+                continue;
+            }
+            let clean: String = word.chars().filter(|c| *c != '"').collect();
+            if !clean.is_empty() {
+                terms.push(format!("\"{clean}\""));
+            }
+        }
+    }
+
+    let is_operator = |t: &String| FTS_OPERATORS.contains(&t.as_str());
+    let mut query: Vec<String> = Vec::new();
+    for term in terms {
+        if is_operator(&term) {
+            match query.last() {
+                None => continue,
+                Some(last) if is_operator(last) => {
+                    query.pop();
+                }
+                Some(_) => {}
+            }
+        }
+        query.push(term);
+    }
+    if query.last().is_some_and(is_operator) {
+        query.pop();
+    }
+    query.join(" ")
 }
 
 /// Extract a snippet from body text around the first query term match.
@@ -141,6 +180,10 @@ fn extract_snippet(body: &str, query: &str, max_len: usize) -> String {
 
     let mut best_pos = 0;
     for word in query_lower.split_whitespace() {
+        // Unquoted words are operators from sanitize_fts_query
+        if !word.starts_with('"') && !word.ends_with('"') {
+            continue;
+        }
         let clean: String = word.chars().filter(|c| *c != '"').collect();
         if !clean.is_empty()
             && let Some(pos) = body_lower.find(&clean)
@@ -179,6 +222,24 @@ mod tests {
         assert_eq!(sanitize_fts_query("auth\"bug"), "\"authbug\"");
     }
 
+    #[test]
+    fn test_sanitize_fts_query_phrases_and_operators() {
+        assert_eq!(
+            sanitize_fts_query("\"login flow\" OR oauth"),
+            "\"login flow\" OR \"oauth\""
+        );
+        assert_eq!(
+            sanitize_fts_query("auth AND NOT legacy"),
+            "\"auth\" NOT \"legacy\""
+        );
+        assert_eq!(sanitize_fts_query("NOT auth OR"), "\"auth\"");
+        assert_eq!(sanitize_fts_query("and or"), "\"and\" \"or\"");
+        assert_eq!(
+            sanitize_fts_query("\"unclosed phrase"),
+            "\"unclosed phrase\""
+        );
+    }
+
     #[test]
     fn test_extract_snippet() {
         let body = "# Today\nFixed the authentication bug in login flow. Also refactored database.";
@@ -1,35 +1,188 @@
 //! Store — singleton qmd Store for the memory database.
 
+use super::error::{MemoryError, Result};
 use once_cell::sync::OnceCell;
 use qmd::Store;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+use std::time::{Duration, Instant};
 
 static STORE: OnceCell<Mutex<Store>> = OnceCell::new();
 
+/// Waits for the store lock longer than this are logged as warnings
+const SLOW_LOCK_WAIT: Duration = Duration::from_millis(250);
+
+/// Lock acquisitions that had to wait for another holder
+static CONTENDED_LOCKS: AtomicU64 = AtomicU64::new(0);
+
+/// Total time spent waiting in those acquisitions, in microseconds
+static LOCK_WAIT_MICROS: AtomicU64 = AtomicU64::new(0);
+
+/// First 16 bytes of every SQLite database file
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
 /// Get (or create) the shared memory qmd Store.
 ///
 /// The database lives at `~/.opencrabs/memory/memory.db`.
 /// First call initializes the schema via `Store::open` and creates the vector table.
-pub fn get_store() -> Result<&'static Mutex<Store>, String> {
-    STORE.get_or_try_init(|| {
+/// A corrupted database is moved aside and recreated empty, with a reindex
+/// scheduled to rebuild it from the markdown files.
+pub fn get_store() -> Result<&'static Mutex<Store>> {
+    let mut recovered = false;
+    let store = STORE.get_or_try_init(|| {
         let db_path = memory_dir().join("memory.db");
 
         if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create memory dir: {e}"))?;
+            std::fs::create_dir_all(parent).map_err(MemoryError::CreateDir)?;
         }
 
-        let store =
-            Store::open(&db_path).map_err(|e| format!("Failed to open memory store: {e}"))?;
-
-        store
-            .ensure_vector_table(768)
-            .map_err(|e| format!("Failed to create vector table: {e}"))?;
+        let (store, was_recovered) = open_or_recover(&db_path)?;
+        recovered = was_recovered;
 
         tracing::info!("Memory qmd store ready at {}", db_path.display());
         Ok(Mutex::new(store))
-    })
+    })?;
+
+    if recovered {
+        schedule_reindex(store);
+    }
+    Ok(store)
+}
+
+/// Lock the shared store, waiting for any other holder.
+///
+/// A task that panicked while holding the lock leaves it poisoned. The guard
+/// is recovered and the poison cleared rather than failing every later search
+/// and index. A panic partway through indexing a file can leave that file
+/// deactivated or without its document row; it isn't rolled back, but with
+/// no active entry left for that file, the next index of it writes it again.
+///
+/// Contended acquisitions are counted and slow ones logged.
+pub fn lock_store(store: &Mutex<Store>) -> MutexGuard<'_, Store> {
+    match store.try_lock() {
+        Ok(guard) => return guard,
+        Err(TryLockError::Poisoned(poisoned)) => return recover_poisoned(store, poisoned),
+        Err(TryLockError::WouldBlock) => {}
+    }
+
+    let started = Instant::now();
+    let guard = store
+        .lock()
+        .unwrap_or_else(|poisoned| recover_poisoned(store, poisoned));
+    let waited = started.elapsed();
+
+    let contended = CONTENDED_LOCKS.fetch_add(1, Ordering::Relaxed) + 1;
+    let total_micros = LOCK_WAIT_MICROS.fetch_add(waited.as_micros() as u64, Ordering::Relaxed)
+        + waited.as_micros() as u64;
+    if waited >= SLOW_LOCK_WAIT {
+        tracing::warn!(
+            "Waited {}ms for the memory store lock ({} contended acquisitions, {}ms total)",
+            waited.as_millis(),
+            contended,
+            total_micros / 1000
+        );
+    } else {
+        tracing::debug!("Memory store lock contended for {}µs", waited.as_micros());
+    }
+    guard
+}
+
+fn recover_poisoned<'a>(
+    store: &'a Mutex<Store>,
+    poisoned: PoisonError<MutexGuard<'a, Store>>,
+) -> MutexGuard<'a, Store> {
+    tracing::warn!("Memory store lock was poisoned by a panicking task; recovering it");
+    store.clear_poison();
+    poisoned.into_inner()
+}
+
+/// Open the store at `db_path`. If it turns out to be corrupted, rename it to
+/// `memory.db.corrupt-<timestamp>` and open a fresh one in its place.
+/// Returns whether that happened.
+fn open_or_recover(db_path: &Path) -> Result<(Store, bool)> {
+    let err = match open_checked(db_path) {
+        Ok(store) => return Ok((store, false)),
+        Err(e) => e,
+    };
+    if !looks_corrupt(db_path, &err) {
+        return Err(err);
+    }
+
+    let backup = corrupt_backup_path(db_path);
+    tracing::error!(
+        "Memory store {} is corrupted ({}); moving it to {} and starting a new one",
+        db_path.display(),
+        err,
+        backup.display()
+    );
+    std::fs::rename(db_path, &backup).map_err(MemoryError::Recover)?;
+    // WAL/SHM sidecars belong to the old file and would poison the new one
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(sidecar);
+    }
+
+    Ok((open_checked(db_path)?, true))
+}
+
+/// Open the store, create the vector table and run a probe query — SQLite
+/// reads pages lazily, so damage often only shows up on the first read.
+fn open_checked(db_path: &Path) -> Result<Store> {
+    let store = Store::open(db_path).map_err(|e| MemoryError::StoreOpen(e.to_string()))?;
+    store
+        .ensure_vector_table(768)
+        .map_err(|e| MemoryError::VectorTable(e.to_string()))?;
+    store
+        .search_fts("\"integrity\"", 1, None)
+        .map_err(|e| MemoryError::StoreOpen(e.to_string()))?;
+    Ok(store)
+}
+
+/// Whether `err` from opening `db_path` means the file itself is damaged, as
+/// opposed to e.g. a permission or disk-full error that recreating won't fix.
+fn looks_corrupt(db_path: &Path, err: &MemoryError) -> bool {
+    let mut header = Vec::with_capacity(SQLITE_HEADER.len());
+    if let Ok(file) = std::fs::File::open(db_path) {
+        let _ = file
+            .take(SQLITE_HEADER.len() as u64)
+            .read_to_end(&mut header);
+    }
+    // An empty file is a valid, new database
+    if !header.is_empty() && header != SQLITE_HEADER {
+        return true;
+    }
+    let message = err.to_string().to_lowercase();
+    ["malformed", "not a database", "corrupt"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+/// `memory.db` → `memory.db.corrupt-20250101T120000`
+fn corrupt_backup_path(db_path: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%dT%H%M%S");
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(format!(".corrupt-{stamp}"));
+    PathBuf::from(name)
+}
+
+/// Rebuild a freshly recreated store from the markdown files in the background.
+fn schedule_reindex(store: &'static Mutex<Store>) {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                match super::index::reindex(store).await {
+                    Ok(n) => tracing::info!("Recreated memory store reindexed: {n} files"),
+                    Err(e) => tracing::error!("Reindex of recreated memory store failed: {e}"),
+                }
+            });
+        }
+        Err(_) => tracing::error!(
+            "Memory store was recreated outside a runtime; search stays empty until the next reindex"
+        ),
+    }
 }
 
 /// Path to the memory directory: `~/.opencrabs/memory/`
@@ -41,12 +194,122 @@ fn memory_dir() -> PathBuf {
 mod tests {
     use super::*;
 
+    /// Store in a temp dir that outlives the test, as `index_file` and
+    /// `search` need a `'static` store
+    fn leaked_store(dir: &Path) -> &'static Mutex<Store> {
+        let store = Store::open(&dir.join("memory.db")).unwrap();
+        store.ensure_vector_table(768).unwrap();
+        Box::leak(Box::new(Mutex::new(store)))
+    }
+
+    #[test]
+    fn test_poisoned_lock_is_recovered() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = leaked_store(dir.path());
+
+        let _ = std::thread::spawn(move || {
+            let _guard = store.lock().unwrap();
+            panic!("holder panicked");
+        })
+        .join();
+        assert!(store.is_poisoned());
+
+        let guard = lock_store(store);
+        assert!(guard.search_fts("\"anything\"", 1, None).is_ok());
+        drop(guard);
+        assert!(!store.is_poisoned());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_index_and_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = leaked_store(dir.path());
+
+        let files: Vec<PathBuf> = (0..16)
+            .map(|i| {
+                let path = dir.path().join(format!("2024-01-{:02}.md", i + 1));
+                std::fs::write(&path, format!("# Day {i}\nStress marker entry number {i}"))
+                    .unwrap();
+                path
+            })
+            .collect();
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for round in 0..4 {
+            for path in files.clone() {
+                tasks.spawn(async move {
+                    crate::memory::index_file(store, &path)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| format!("index round {round}: {e}"))
+                });
+            }
+            for _ in 0..8 {
+                tasks.spawn(async move {
+                    crate::memory::search(store, "stress marker", 20)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| format!("search round {round}: {e}"))
+                });
+            }
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        let results = crate::memory::search(store, "stress marker", 50)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), files.len());
+        assert!(!store.is_poisoned());
+    }
+
     #[test]
     fn test_memory_dir() {
         let dir = memory_dir();
         assert!(dir.to_string_lossy().contains("memory"));
     }
 
+    #[test]
+    fn test_corrupted_store_is_recreated() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("memory.db");
+        std::fs::write(&db_path, vec![0xAB; 8192]).unwrap();
+
+        let (store, recovered) = open_or_recover(&db_path).unwrap();
+        assert!(recovered);
+        let backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with("memory.db.corrupt-")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+
+        // The new store works
+        let body = "# Recovered\nSearch works again after recovery";
+        let hash = Store::hash_content(body);
+        let now = "2024-01-01T00:00:00";
+        store.insert_content(&hash, body, now).unwrap();
+        store
+            .insert_document("memory", "r.md", "Recovered", &hash, now, now)
+            .unwrap();
+        assert!(
+            !store
+                .search_fts("\"recovery\"", 5, None)
+                .unwrap()
+                .is_empty()
+        );
+        drop(store);
+
+        // A healthy store opens without recovery
+        let (_store, recovered) = open_or_recover(&db_path).unwrap();
+        assert!(!recovered);
+    }
+
     #[test]
     fn test_index_and_search_integration() {
         let dir = tempfile::tempdir().unwrap();
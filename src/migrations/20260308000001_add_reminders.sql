@@ -0,0 +1,12 @@
+-- Reminders: one-shot messages delivered to the owner at a given time
+CREATE TABLE IF NOT EXISTS reminders (
+    id          TEXT PRIMARY KEY NOT NULL,
+    message     TEXT NOT NULL,           -- text delivered when the reminder fires
+    remind_at   TEXT NOT NULL,           -- ISO 8601 UTC timestamp when the reminder is due
+    channel     TEXT,                    -- preferred channel ("telegram", "discord"); NULL = first connected
+    session_id  TEXT,                    -- session the reminder was created from
+    fired_at    TEXT,                    -- ISO 8601 timestamp of delivery (NULL = pending)
+    created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminders_pending ON reminders (fired_at, remind_at);
@@ -0,0 +1,6 @@
+-- Settings: small persistent preferences (per-user toggles, pinned sessions, ...)
+CREATE TABLE IF NOT EXISTS settings (
+    key         TEXT PRIMARY KEY NOT NULL,  -- namespaced key, e.g. "voice.prefer:telegram:123"
+    value       TEXT NOT NULL,              -- JSON-encoded value
+    updated_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
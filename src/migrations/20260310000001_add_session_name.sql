@@ -0,0 +1,4 @@
+-- Optional human-readable session name, used by `opencrabs run --session-name`
+-- to land repeated scripted runs in the same session
+ALTER TABLE sessions ADD COLUMN name TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sessions_name ON sessions(name);
@@ -0,0 +1,3 @@
+-- Scheduled tasks: a reminder with run_agent = 1 runs its message as an
+-- autonomous agent turn in the owner's session instead of only notifying
+ALTER TABLE reminders ADD COLUMN run_agent INTEGER NOT NULL DEFAULT 0;
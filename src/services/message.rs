@@ -126,6 +126,19 @@ impl MessageService {
         Ok(())
     }
 
+    /// Re-insert previously deleted messages unchanged (ids, order, usage)
+    pub async fn restore_messages(&self, messages: &[Message]) -> Result<()> {
+        let repo = MessageRepository::new(self.context.pool());
+        for message in messages {
+            repo.create(message)
+                .await
+                .context("Failed to restore message")?;
+        }
+
+        tracing::info!("Restored {} messages", messages.len());
+        Ok(())
+    }
+
     /// Delete all messages for a session
     pub async fn delete_messages_for_session(&self, session_id: Uuid) -> Result<()> {
         let repo = MessageRepository::new(self.context.pool());
@@ -260,6 +273,44 @@ mod tests {
         assert_eq!(messages[1].sequence, 2);
     }
 
+    #[tokio::test]
+    async fn test_restore_messages_after_delete() {
+        let (message_service, session_service) = create_test_service().await;
+        let session = session_service
+            .create_session(Some("Test".to_string()))
+            .await
+            .unwrap();
+
+        for content in ["First", "Second"] {
+            message_service
+                .create_message(session.id, "user".to_string(), content.to_string())
+                .await
+                .unwrap();
+        }
+        let before = message_service
+            .list_messages_for_session(session.id)
+            .await
+            .unwrap();
+
+        message_service
+            .delete_messages_for_session(session.id)
+            .await
+            .unwrap();
+        message_service.restore_messages(&before).await.unwrap();
+
+        let after = message_service
+            .list_messages_for_session(session.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            after.iter().map(|m| (m.id, m.sequence)).collect::<Vec<_>>(),
+            before
+                .iter()
+                .map(|m| (m.id, m.sequence))
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[tokio::test]
     async fn test_update_message_usage() {
         let (message_service, session_service) = create_test_service().await;
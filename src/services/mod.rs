@@ -8,9 +8,11 @@ pub mod file;
 pub mod message;
 pub mod plan;
 pub mod session;
+pub mod settings;
 
 pub use context::{ServiceContext, ServiceManager};
 pub use file::FileService;
 pub use message::MessageService;
 pub use plan::PlanService;
 pub use session::SessionService;
+pub use settings::SettingsService;
@@ -53,6 +53,7 @@ impl SessionService {
             token_count: 0,
             total_cost: 0.0,
             working_directory: None,
+            name: None,
         };
 
         repo.create(&session)
@@ -63,6 +64,29 @@ impl SessionService {
         Ok(session)
     }
 
+    /// Get the session with the given name, creating it on first use.
+    ///
+    /// Names are unique, so repeated scripted runs with the same name always
+    /// land in the same session.
+    pub async fn get_or_create_by_name(&self, name: &str) -> Result<Session> {
+        let repo = SessionRepository::new(self.context.pool());
+        if let Some(session) = repo.find_by_name(name).await? {
+            return Ok(session);
+        }
+
+        let mut session = Session::new(Some(name.to_string()), None, None);
+        session.name = Some(name.to_string());
+        match repo.create(&session).await {
+            Ok(()) => {
+                tracing::info!("Created named session '{}': {}", name, session.id);
+                Ok(session)
+            }
+            // A concurrent run may have won the insert — the unique index
+            // rejected ours, so hand back theirs
+            Err(e) => repo.find_by_name(name).await?.ok_or(e),
+        }
+    }
+
     /// Get a session by ID
     pub async fn get_session(&self, id: Uuid) -> Result<Option<Session>> {
         let repo = SessionRepository::new(self.context.pool());
@@ -425,4 +449,19 @@ mod tests {
         assert_eq!(active_count, 2);
         assert_eq!(archived_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_get_or_create_by_name_is_idempotent() {
+        let service = create_test_service().await;
+
+        let first = service.get_or_create_by_name("nightly").await.unwrap();
+        let second = service.get_or_create_by_name("nightly").await.unwrap();
+        let other = service.get_or_create_by_name("weekly").await.unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.name.as_deref(), Some("nightly"));
+        assert_eq!(second.title.as_deref(), Some("nightly"));
+        assert_ne!(first.id, other.id);
+        assert_eq!(service.count_sessions().await.unwrap(), 2);
+    }
 }
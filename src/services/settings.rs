@@ -0,0 +1,139 @@
+//! Settings Service
+//!
+//! Typed key/value store for small persistent preferences — per-user
+//! toggles, pinned sessions and the like — kept in the `settings` table
+//! as JSON so each feature doesn't need its own file or migration.
+
+use crate::db::repository::SettingRepository;
+use crate::services::ServiceContext;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Service for persistent settings
+#[derive(Clone)]
+pub struct SettingsService {
+    repo: SettingRepository,
+}
+
+impl SettingsService {
+    /// Create a new settings service
+    pub fn new(context: ServiceContext) -> Self {
+        Self {
+            repo: SettingRepository::new(context.pool()),
+        }
+    }
+
+    /// Read `key`, or `None` if it was never set
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let Some(raw) = self.repo.get(key).await? else {
+            return Ok(None);
+        };
+        let value = serde_json::from_str(&raw)
+            .with_context(|| format!("Setting '{}' has an unexpected shape", key))?;
+        Ok(Some(value))
+    }
+
+    /// Read `key`, falling back to `T::default()` when unset
+    pub async fn get_or_default<T: DeserializeOwned + Default>(&self, key: &str) -> Result<T> {
+        Ok(self.get(key).await?.unwrap_or_default())
+    }
+
+    /// Store `value` under `key`, replacing any previous value
+    pub async fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<()> {
+        let raw = serde_json::to_string(value)?;
+        self.repo.set(key, &raw).await
+    }
+
+    /// Remove `key`. Returns false if it wasn't set.
+    pub async fn remove(&self, key: &str) -> Result<bool> {
+        self.repo.delete(key).await
+    }
+
+    /// All settings under `prefix`, e.g. every `"pinned:"` entry.
+    /// Entries that don't decode as `T` are skipped.
+    pub async fn list<T: DeserializeOwned>(&self, prefix: &str) -> Result<Vec<(String, T)>> {
+        let rows = self.repo.list_prefix(prefix).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(key, raw)| match serde_json::from_str(&raw) {
+                Ok(value) => Some((key, value)),
+                Err(e) => {
+                    tracing::warn!("Skipping setting '{}': {}", key, e);
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    async fn create_test_service() -> SettingsService {
+        use crate::db::Database;
+
+        let db = Database::connect_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let pool = db.pool().clone();
+
+        SettingsService::new(ServiceContext::new(pool))
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct VoicePrefs {
+        prefer_voice: bool,
+        speed: f32,
+        languages: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn test_struct_round_trip_and_overwrite() {
+        let service = create_test_service().await;
+        let prefs = VoicePrefs {
+            prefer_voice: true,
+            speed: 1.25,
+            languages: vec!["pt".to_string(), "en".to_string()],
+        };
+
+        assert_eq!(service.get::<VoicePrefs>("voice:42").await.unwrap(), None);
+        service.set("voice:42", &prefs).await.unwrap();
+        assert_eq!(service.get("voice:42").await.unwrap(), Some(prefs));
+
+        service
+            .set("voice:42", &VoicePrefs::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .get_or_default::<VoicePrefs>("voice:42")
+                .await
+                .unwrap(),
+            VoicePrefs::default()
+        );
+
+        assert!(service.remove("voice:42").await.unwrap());
+        assert!(!service.remove("voice:42").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_prefix_and_bad_shape() {
+        let service = create_test_service().await;
+        service.set("pinned:b", &true).await.unwrap();
+        service.set("pinned:a", &true).await.unwrap();
+        service.set("pinned_other", &true).await.unwrap();
+        service.set("pinned:c", "not a bool").await.unwrap();
+
+        let pinned: Vec<(String, bool)> = service.list("pinned:").await.unwrap();
+        assert_eq!(
+            pinned,
+            vec![
+                ("pinned:a".to_string(), true),
+                ("pinned:b".to_string(), true)
+            ]
+        );
+        assert!(service.get::<bool>("pinned:c").await.is_err());
+    }
+}
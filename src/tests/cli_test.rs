@@ -44,8 +44,9 @@ fn test_cli_parse_run_command() {
             prompt,
             auto_approve,
             format,
+            ..
         }) => {
-            assert_eq!(prompt, "Hello, how are you?");
+            assert_eq!(prompt.as_deref(), Some("Hello, how are you?"));
             assert!(!auto_approve);
             assert!(matches!(format, OutputFormat::Text));
         }
@@ -61,8 +62,9 @@ fn test_cli_parse_run_with_json_format() {
             prompt,
             auto_approve,
             format,
+            ..
         }) => {
-            assert_eq!(prompt, "Test prompt");
+            assert_eq!(prompt.as_deref(), Some("Test prompt"));
             assert!(!auto_approve);
             assert!(matches!(format, OutputFormat::Json));
         }
@@ -79,8 +81,9 @@ fn test_cli_parse_run_with_markdown_format() {
             prompt,
             auto_approve,
             format,
+            ..
         }) => {
-            assert_eq!(prompt, "Test prompt");
+            assert_eq!(prompt.as_deref(), Some("Test prompt"));
             assert!(!auto_approve);
             assert!(matches!(format, OutputFormat::Markdown));
         }
@@ -95,15 +98,79 @@ fn test_cli_parse_run_with_auto_approve() {
         Some(Commands::Run {
             prompt,
             auto_approve,
-            format: _,
+            ..
         }) => {
-            assert_eq!(prompt, "Test prompt");
+            assert_eq!(prompt.as_deref(), Some("Test prompt"));
             assert!(auto_approve);
         }
         _ => panic!("Expected Run command with auto-approve"),
     }
 }
 
+#[test]
+fn test_cli_parse_run_without_prompt_reads_stdin() {
+    let cli = Cli::try_parse_from(["opencrabs", "run"]).unwrap();
+    match cli.command {
+        Some(Commands::Run {
+            prompt, prompt_opt, ..
+        }) => {
+            assert!(prompt.is_none());
+            assert!(prompt_opt.is_none());
+        }
+        _ => panic!("Expected Run command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_run_with_prompt_flag() {
+    let cli = Cli::try_parse_from(["opencrabs", "run", "--prompt", "Test prompt"]).unwrap();
+    match cli.command {
+        Some(Commands::Run {
+            prompt, prompt_opt, ..
+        }) => {
+            assert!(prompt.is_none());
+            assert_eq!(prompt_opt.as_deref(), Some("Test prompt"));
+        }
+        _ => panic!("Expected Run command with --prompt"),
+    }
+}
+
+#[test]
+fn test_cli_parse_run_prompt_flag_conflicts_with_positional() {
+    let result = Cli::try_parse_from(["opencrabs", "run", "--prompt", "a", "b"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_parse_run_scripting_flags() {
+    let cli = Cli::try_parse_from([
+        "opencrabs",
+        "run",
+        "--json",
+        "--no-tools",
+        "--quiet",
+        "--session",
+        "abc",
+        "Test prompt",
+    ])
+    .unwrap();
+    match cli.command {
+        Some(Commands::Run {
+            json,
+            no_tools,
+            quiet,
+            session,
+            ..
+        }) => {
+            assert!(json);
+            assert!(no_tools);
+            assert!(quiet);
+            assert_eq!(session.as_deref(), Some("abc"));
+        }
+        _ => panic!("Expected Run command with scripting flags"),
+    }
+}
+
 #[test]
 fn test_cli_parse_run_with_yolo_alias() {
     let cli = Cli::try_parse_from(["opencrabs", "run", "--yolo", "Test prompt"]).unwrap();
@@ -111,9 +178,9 @@ fn test_cli_parse_run_with_yolo_alias() {
         Some(Commands::Run {
             prompt,
             auto_approve,
-            format: _,
+            ..
         }) => {
-            assert_eq!(prompt, "Test prompt");
+            assert_eq!(prompt.as_deref(), Some("Test prompt"));
             assert!(auto_approve);
         }
         _ => panic!("Expected Run command with yolo alias"),
@@ -146,7 +213,8 @@ fn test_cli_parse_init_with_force() {
 fn test_cli_parse_config_command() {
     let cli = Cli::try_parse_from(["opencrabs", "config"]).unwrap();
     match cli.command {
-        Some(Commands::Config { show_secrets }) => {
+        Some(Commands::Config { show, show_secrets }) => {
+            assert!(!show);
             assert!(!show_secrets);
         }
         _ => panic!("Expected Config command"),
@@ -157,13 +225,46 @@ fn test_cli_parse_config_command() {
 fn test_cli_parse_config_with_show_secrets() {
     let cli = Cli::try_parse_from(["opencrabs", "config", "--show-secrets"]).unwrap();
     match cli.command {
-        Some(Commands::Config { show_secrets }) => {
+        Some(Commands::Config { show_secrets, .. }) => {
             assert!(show_secrets);
         }
         _ => panic!("Expected Config command with show-secrets"),
     }
 }
 
+#[test]
+fn test_cli_parse_config_show() {
+    let cli = Cli::try_parse_from(["opencrabs", "config", "--show"]).unwrap();
+    match cli.command {
+        Some(Commands::Config { show, show_secrets }) => {
+            assert!(show);
+            assert!(!show_secrets);
+        }
+        _ => panic!("Expected Config command with show"),
+    }
+}
+
+#[test]
+fn test_cli_parse_export_and_import() {
+    let cli = Cli::try_parse_from(["opencrabs", "export", "backup.tar.gz"]).unwrap();
+    match cli.command {
+        Some(Commands::Export {
+            file,
+            include_secrets,
+        }) => {
+            assert_eq!(file, std::path::PathBuf::from("backup.tar.gz"));
+            assert!(!include_secrets);
+        }
+        _ => panic!("Expected Export command"),
+    }
+
+    let cli = Cli::try_parse_from(["opencrabs", "import", "backup.tar.gz", "--force"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Import { force: true, .. })
+    ));
+}
+
 #[test]
 fn test_cli_parse_db_init() {
     let cli = Cli::try_parse_from(["opencrabs", "db", "init"]).unwrap();
@@ -233,8 +334,9 @@ fn test_cli_parse_combined_flags() {
             prompt,
             auto_approve,
             format,
+            ..
         }) => {
-            assert_eq!(prompt, "Test prompt");
+            assert_eq!(prompt.as_deref(), Some("Test prompt"));
             assert!(auto_approve);
             assert!(matches!(format, OutputFormat::Json));
         }
@@ -306,3 +408,31 @@ fn test_cli_daemon_takes_no_args() {
     let result = Cli::try_parse_from(["opencrabs", "daemon", "--session", "foo"]);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_cli_parse_global_workspace_flag() {
+    let cli =
+        Cli::try_parse_from(["opencrabs", "--workspace", "/tmp/project-a", "daemon"]).unwrap();
+    assert_eq!(
+        cli.workspace,
+        Some(std::path::PathBuf::from("/tmp/project-a"))
+    );
+    assert!(matches!(cli.command, Some(Commands::Daemon)));
+}
+
+#[test]
+fn test_cli_parse_workspace_after_subcommand() {
+    let cli = Cli::try_parse_from(["opencrabs", "chat", "-w", "./proj"]).unwrap();
+    assert_eq!(cli.workspace, Some(std::path::PathBuf::from("./proj")));
+}
+
+#[test]
+fn test_cli_parse_verbosity_count() {
+    assert_eq!(Cli::try_parse_from(["opencrabs"]).unwrap().verbose, 0);
+    assert_eq!(
+        Cli::try_parse_from(["opencrabs", "-vv"]).unwrap().verbose,
+        2
+    );
+    let cli = Cli::try_parse_from(["opencrabs", "daemon", "-v", "--verbose", "-v"]).unwrap();
+    assert_eq!(cli.verbose, 3);
+}
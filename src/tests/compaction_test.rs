@@ -5,6 +5,7 @@
 //! - Snapshot injection into compaction summary
 //! - compact_with_summary preserves snapshot + kept messages
 //! - Post-compaction instruction correctness (no name="all")
+//! - Idle compaction policy for channel sessions
 
 // --- format_recent_messages tests ---
 
@@ -462,3 +463,63 @@ mod post_compaction_instruction {
         assert!(!mid_loop_instruction.contains("name=\"all\""));
     }
 }
+
+// --- Idle compaction tests ---
+
+mod idle_compaction {
+    use crate::brain::agent::service::AgentService;
+    use crate::config::SessionsConfig;
+    use chrono::{Duration as ChronoDuration, Utc};
+    use std::time::Duration;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn compacts_long_history_after_threshold() {
+        let now = Utc::now();
+        let last = Some(now - ChronoDuration::hours(2));
+        assert!(AgentService::is_idle_for_compaction(
+            last,
+            now,
+            Some(HOUR),
+            20
+        ));
+    }
+
+    #[test]
+    fn skips_recent_or_short_or_disabled() {
+        let now = Utc::now();
+        let recent = Some(now - ChronoDuration::minutes(5));
+        let stale = Some(now - ChronoDuration::hours(2));
+        assert!(!AgentService::is_idle_for_compaction(
+            recent,
+            now,
+            Some(HOUR),
+            20
+        ));
+        // Compaction keeps the last 8 messages verbatim anyway
+        assert!(!AgentService::is_idle_for_compaction(
+            stale,
+            now,
+            Some(HOUR),
+            8
+        ));
+        assert!(!AgentService::is_idle_for_compaction(stale, now, None, 20));
+        assert!(!AgentService::is_idle_for_compaction(
+            None,
+            now,
+            Some(HOUR),
+            20
+        ));
+    }
+
+    #[test]
+    fn off_by_default() {
+        assert_eq!(SessionsConfig::default().idle_compact_after(), None);
+        let cfg = SessionsConfig {
+            compact_after_idle_minutes: Some(90),
+            ..Default::default()
+        };
+        assert_eq!(cfg.idle_compact_after(), Some(Duration::from_secs(90 * 60)));
+    }
+}
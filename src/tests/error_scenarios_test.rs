@@ -330,6 +330,7 @@ impl Provider for WorkingMockProvider {
             usage: TokenUsage {
                 input_tokens: 10,
                 output_tokens: 20,
+                ..Default::default()
             },
         })
     }
@@ -21,6 +21,7 @@ mod fallback_chain {
             enabled: true,
             provider: Some("openrouter".into()),
             providers: vec![],
+            models: Default::default(),
         };
         assert_eq!(fallback_chain(&cfg), vec!["openrouter"]);
     }
@@ -31,6 +32,7 @@ mod fallback_chain {
             enabled: true,
             provider: None,
             providers: vec!["anthropic".into(), "openai".into()],
+            models: Default::default(),
         };
         assert_eq!(fallback_chain(&cfg), vec!["anthropic", "openai"]);
     }
@@ -41,6 +43,7 @@ mod fallback_chain {
             enabled: true,
             provider: Some("gemini".into()),
             providers: vec!["anthropic".into(), "openai".into()],
+            models: Default::default(),
         };
         assert_eq!(fallback_chain(&cfg), vec!["anthropic", "openai", "gemini"]);
     }
@@ -51,6 +54,7 @@ mod fallback_chain {
             enabled: true,
             provider: Some("anthropic".into()),
             providers: vec!["anthropic".into(), "openai".into()],
+            models: Default::default(),
         };
         // "anthropic" already in array — should NOT be appended again
         assert_eq!(fallback_chain(&cfg), vec!["anthropic", "openai"]);
@@ -62,6 +66,7 @@ mod fallback_chain {
             enabled: true,
             provider: None,
             providers: vec!["minimax".into()],
+            models: Default::default(),
         };
         assert_eq!(fallback_chain(&cfg), vec!["minimax"]);
     }
@@ -78,6 +83,35 @@ providers = ["openrouter", "anthropic"]
         assert!(cfg.provider.is_none());
     }
 
+    #[test]
+    fn deserialization_from_providers_shorthand() {
+        let toml_str = r#"
+[providers]
+fallback = ["openrouter", "openai"]
+"#;
+        let config: crate::config::Config = toml::from_str(toml_str).unwrap();
+        let cfg = config.providers.fallback.unwrap();
+        assert!(cfg.enabled);
+        assert_eq!(fallback_chain(&cfg), vec!["openrouter", "openai"]);
+        assert!(cfg.models.is_empty());
+    }
+
+    #[test]
+    fn deserialization_with_model_mapping() {
+        let toml_str = r#"
+enabled = true
+providers = ["openrouter"]
+
+[models]
+openrouter = "anthropic/claude-sonnet-4"
+"#;
+        let cfg: FallbackProviderConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            cfg.models.get("openrouter").map(String::as_str),
+            Some("anthropic/claude-sonnet-4")
+        );
+    }
+
     #[test]
     fn deserialization_from_toml_legacy() {
         let toml_str = r#"
@@ -108,16 +142,19 @@ providers = ["anthropic", "openai"]
 mod fallback_runtime {
     use crate::brain::provider::{
         FallbackProvider, LLMRequest, LLMResponse, Provider, ProviderError, ProviderStream,
+        track_served_by,
     };
     use async_trait::async_trait;
-    use std::sync::Arc;
+    use std::collections::HashMap;
     use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
 
-    /// A mock provider that fails N times, then succeeds.
+    /// A mock provider that fails N times (rate limited), then succeeds.
     struct MockProvider {
         name: String,
         fail_count: AtomicUsize,
         max_failures: usize,
+        last_model: Mutex<Option<String>>,
     }
 
     impl MockProvider {
@@ -126,6 +163,7 @@ mod fallback_runtime {
                 name: name.to_string(),
                 fail_count: AtomicUsize::new(0),
                 max_failures: usize::MAX,
+                last_model: Mutex::new(None),
             }
         }
 
@@ -134,19 +172,61 @@ mod fallback_runtime {
                 name: name.to_string(),
                 fail_count: AtomicUsize::new(0),
                 max_failures: 0,
+                last_model: Mutex::new(None),
             }
         }
     }
 
+    /// Fails with a non-retryable error (bad request)
+    struct RejectingProvider;
+
     #[async_trait]
-    impl Provider for MockProvider {
+    impl Provider for RejectingProvider {
         async fn complete(
             &self,
             _request: LLMRequest,
         ) -> crate::brain::provider::error::Result<LLMResponse> {
+            Err(ProviderError::InvalidRequest("rejected by primary".into()))
+        }
+
+        async fn stream(
+            &self,
+            _request: LLMRequest,
+        ) -> crate::brain::provider::error::Result<ProviderStream> {
+            Err(ProviderError::InvalidRequest("rejected by primary".into()))
+        }
+
+        fn name(&self) -> &str {
+            "rejecting"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+
+        fn supported_models(&self) -> Vec<String> {
+            vec!["mock-model".into()]
+        }
+
+        fn context_window(&self, _model: &str) -> Option<u32> {
+            Some(4096)
+        }
+
+        fn calculate_cost(&self, _model: &str, _input: u32, _output: u32) -> f64 {
+            0.0
+        }
+    }
+
+    #[async_trait]
+    impl Provider for MockProvider {
+        async fn complete(
+            &self,
+            request: LLMRequest,
+        ) -> crate::brain::provider::error::Result<LLMResponse> {
+            *self.last_model.lock().unwrap() = Some(request.model);
             let count = self.fail_count.fetch_add(1, Ordering::SeqCst);
             if count < self.max_failures {
-                Err(ProviderError::Internal(format!(
+                Err(ProviderError::RateLimitExceeded(format!(
                     "{} mock failure #{}",
                     self.name,
                     count + 1
@@ -160,6 +240,7 @@ mod fallback_runtime {
                     usage: crate::brain::provider::TokenUsage {
                         input_tokens: 0,
                         output_tokens: 0,
+                        ..Default::default()
                     },
                 })
             }
@@ -167,11 +248,12 @@ mod fallback_runtime {
 
         async fn stream(
             &self,
-            _request: LLMRequest,
+            request: LLMRequest,
         ) -> crate::brain::provider::error::Result<ProviderStream> {
+            *self.last_model.lock().unwrap() = Some(request.model);
             let count = self.fail_count.fetch_add(1, Ordering::SeqCst);
             if count < self.max_failures {
-                Err(ProviderError::Internal(format!(
+                Err(ProviderError::RateLimitExceeded(format!(
                     "{} stream mock failure #{}",
                     self.name,
                     count + 1
@@ -209,6 +291,8 @@ mod fallback_runtime {
             system: None,
             max_tokens: None,
             temperature: None,
+            top_p: None,
+            reasoning_effort: None,
             tools: None,
             stream: false,
             metadata: None,
@@ -291,6 +375,54 @@ mod fallback_runtime {
         }
     }
 
+    #[tokio::test]
+    async fn non_retryable_error_skips_fallbacks() {
+        let fb1 = Arc::new(MockProvider::always_succeed("fallback1"));
+        let provider = FallbackProvider::new(Arc::new(RejectingProvider), vec![fb1.clone()]);
+
+        let err = provider.complete(mock_request()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidRequest(_)));
+        assert_eq!(fb1.fail_count.load(Ordering::SeqCst), 0);
+        assert!(provider.stream(mock_request()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn fallback_gets_mapped_or_default_model() {
+        let primary = Arc::new(MockProvider::always_fail("primary"));
+        let fb1 = Arc::new(MockProvider::always_fail("fallback1"));
+        let fb2 = Arc::new(MockProvider::always_succeed("fallback2"));
+        let provider =
+            FallbackProvider::new(primary.clone(), vec![fb1.clone(), fb2.clone()]).with_models(
+                HashMap::from([("fallback1".to_string(), "mapped-model".to_string())]),
+            );
+
+        let mut request = mock_request();
+        request.model = "primary-only-model".into();
+        provider.complete(request).await.unwrap();
+
+        let model = |p: &MockProvider| p.last_model.lock().unwrap().clone();
+        assert_eq!(model(&primary).as_deref(), Some("primary-only-model"));
+        assert_eq!(model(&fb1).as_deref(), Some("mapped-model"));
+        // Unmapped: the fallback's own default
+        assert_eq!(model(&fb2).as_deref(), Some("mock-model"));
+    }
+
+    #[tokio::test]
+    async fn tracks_which_provider_served() {
+        let primary = Arc::new(MockProvider::always_fail("primary"));
+        let fb1 = Arc::new(MockProvider::always_succeed("fallback1"));
+        let provider = FallbackProvider::new(primary, vec![fb1]);
+
+        let (resp, served_by) = track_served_by(provider.complete(mock_request())).await;
+        assert!(resp.is_ok());
+        assert_eq!(served_by.as_deref(), Some("fallback1"));
+
+        // Outside a fallback chain nothing is recorded
+        let plain = MockProvider::always_succeed("plain");
+        let (_, served_by) = track_served_by(plain.complete(mock_request())).await;
+        assert_eq!(served_by, None);
+    }
+
     #[tokio::test]
     async fn delegates_name_to_primary() {
         let primary = Arc::new(MockProvider::always_succeed("my-primary"));
@@ -325,6 +457,29 @@ mod vision_model {
         assert!(provider.supports_vision());
     }
 
+    #[test]
+    fn chat_model_capability_decides_vision() {
+        let vision = OpenAIProvider::new("test-key".into()).with_default_model("gpt-4o".into());
+        assert!(vision.supports_vision());
+
+        // A vision_model backs analyze_image; it doesn't make a text model see
+        let text_only = OpenAIProvider::new("test-key".into())
+            .with_default_model("MiniMax-M2.5".into())
+            .with_vision_model("MiniMax-Text-01".into());
+        assert!(!text_only.supports_vision());
+    }
+
+    #[test]
+    fn vision_is_judged_by_model_name() {
+        use crate::brain::provider::custom_openai_compatible::model_accepts_images;
+        assert!(model_accepts_images("openai/gpt-4o-mini"));
+        assert!(model_accepts_images("qwen2.5-vl-72b-instruct"));
+        assert!(model_accepts_images("llama-3.2-90b-vision-preview"));
+        assert!(!model_accepts_images("o3-mini"));
+        assert!(!model_accepts_images("gpt-3.5-turbo"));
+        assert!(!model_accepts_images("deepseek-chat"));
+    }
+
     #[test]
     fn vision_model_accessor() {
         let provider =
@@ -369,7 +524,7 @@ default_model = "gpt-4"
                     enabled: true,
                     api_key: Some("test-key".into()),
                     base_url: None,
-                    default_model: Some("gpt-4".into()),
+                    default_model: Some("gpt-4o".into()),
                     models: vec![],
                     vision_model: Some("gpt-5-nano".into()),
                 }),
@@ -448,6 +603,7 @@ mod factory_fallback {
                     enabled: false,
                     provider: Some("anthropic".into()),
                     providers: vec![],
+                    models: Default::default(),
                 }),
                 ..Default::default()
             },
@@ -479,6 +635,7 @@ mod factory_fallback {
                     enabled: true,
                     provider: None,
                     providers: vec!["anthropic".into(), "openai".into()],
+                    models: Default::default(),
                 }),
                 ..Default::default()
             },
@@ -0,0 +1,27 @@
+//! Heartbeat Tests
+//!
+//! Tests for the HEARTBEAT.md emptiness check and `HEARTBEAT_OK` detection.
+
+use crate::cron::heartbeat::{is_effectively_empty, is_heartbeat_ack};
+
+#[test]
+fn test_headings_and_blank_lines_are_empty() {
+    assert!(is_effectively_empty(""));
+    assert!(is_effectively_empty("# HEARTBEAT.md\n\n## Checks\n   \n"));
+    assert!(!is_effectively_empty("# Checks\n- Check unread email\n"));
+}
+
+#[test]
+fn test_heartbeat_ack_detection() {
+    assert!(is_heartbeat_ack("HEARTBEAT_OK"));
+    assert!(is_heartbeat_ack("  HEARTBEAT_OK\n"));
+    assert!(is_heartbeat_ack("All quiet. HEARTBEAT_OK"));
+    assert!(is_heartbeat_ack("HEARTBEAT_OK — nothing new."));
+    assert!(!is_heartbeat_ack(
+        "You have 3 urgent emails from your landlord."
+    ));
+    assert!(!is_heartbeat_ack(&format!(
+        "HEARTBEAT_OK {}",
+        "x".repeat(400)
+    )));
+}
@@ -4,8 +4,10 @@ pub mod compaction_test;
 pub mod cron_test;
 //pub mod error_scenarios_test;
 pub mod fallback_vision_test;
+pub mod heartbeat_test;
 //pub mod integration_test;
 pub mod onboarding_keys_test;
 //pub mod plan_mode_integration_test;
+pub mod reminder_test;
 pub mod session_working_dir_test;
 //pub mod streaming_test;
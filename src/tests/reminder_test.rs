@@ -0,0 +1,571 @@
+//! Reminder Tests
+//!
+//! Tests for natural-language time parsing, the reminders repository,
+//! the set/list/cancel reminder and schedule_task tools, and the reminder
+//! scheduler tick.
+
+// --- Time Parsing Tests ---
+
+mod parse_when {
+    use crate::brain::tools::reminder::parse_when;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    /// Wednesday 2026-03-11 14:30 UTC
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 11, 14, 30, 0).unwrap()
+    }
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_iso_formats() {
+        assert_eq!(
+            parse_when("2026-03-12T09:00:00Z", now()).unwrap(),
+            at(2026, 3, 12, 9, 0)
+        );
+        assert_eq!(
+            parse_when("2026-03-12 18:15", now()).unwrap(),
+            at(2026, 3, 12, 18, 15)
+        );
+        assert_eq!(
+            parse_when("2026-03-20", now()).unwrap(),
+            at(2026, 3, 20, 9, 0)
+        );
+    }
+
+    #[test]
+    fn test_relative() {
+        assert_eq!(
+            parse_when("in 20 minutes", now()).unwrap(),
+            at(2026, 3, 11, 14, 50)
+        );
+        assert_eq!(parse_when("in 2h", now()).unwrap(), at(2026, 3, 11, 16, 30));
+        assert_eq!(
+            parse_when("in an hour", now()).unwrap(),
+            at(2026, 3, 11, 15, 30)
+        );
+        assert_eq!(
+            parse_when("3 days from now", now()).unwrap(),
+            at(2026, 3, 14, 14, 30)
+        );
+    }
+
+    #[test]
+    fn test_day_words() {
+        assert_eq!(
+            parse_when("tomorrow at 9am", now()).unwrap(),
+            at(2026, 3, 12, 9, 0)
+        );
+        assert_eq!(
+            parse_when("Tomorrow", now()).unwrap(),
+            at(2026, 3, 12, 9, 0)
+        );
+        assert_eq!(
+            parse_when("today 17:30", now()).unwrap(),
+            at(2026, 3, 11, 17, 30)
+        );
+        assert_eq!(
+            parse_when("tonight", now()).unwrap(),
+            at(2026, 3, 11, 20, 0)
+        );
+        assert_eq!(
+            parse_when("friday at noon", now()).unwrap(),
+            at(2026, 3, 13, 12, 0)
+        );
+        // Same weekday as today means next week
+        assert_eq!(
+            parse_when("next wednesday at 8:15pm", now()).unwrap(),
+            at(2026, 3, 18, 20, 15)
+        );
+    }
+
+    #[test]
+    fn test_bare_time_rolls_to_tomorrow_when_past() {
+        assert_eq!(
+            parse_when("at 18:00", now()).unwrap(),
+            at(2026, 3, 11, 18, 0)
+        );
+        assert_eq!(parse_when("9am", now()).unwrap(), at(2026, 3, 12, 9, 0));
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(parse_when("", now()).is_err());
+        assert!(parse_when("whenever you like", now()).is_err());
+        assert!(parse_when("in a fortnight", now()).is_err());
+        // Out-of-range amounts are errors, not panics
+        assert!(parse_when("in 9223372036854775807 weeks", now()).is_err());
+        assert!(parse_when("in 99999999999 days", now()).is_err());
+        assert!(parse_when("tomorrow at 25pm", now()).is_err());
+    }
+}
+
+// --- Repository Tests ---
+
+mod repository {
+    use crate::db::Database;
+    use crate::db::ReminderRepository;
+    use crate::db::models::Reminder;
+    use chrono::{Duration, Utc};
+
+    async fn setup() -> (Database, ReminderRepository) {
+        let db = Database::connect_in_memory()
+            .await
+            .expect("Failed to create database");
+        db.run_migrations().await.expect("Failed to run migrations");
+        let repo = ReminderRepository::new(db.pool().clone());
+        (db, repo)
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_list_pending_sorted() {
+        let (_db, repo) = setup().await;
+        let later = Reminder::new("later".into(), Utc::now() + Duration::hours(2), None, None);
+        let sooner = Reminder::new(
+            "sooner".into(),
+            Utc::now() + Duration::hours(1),
+            Some("telegram".into()),
+            None,
+        );
+        repo.insert(&later).await.unwrap();
+        repo.insert(&sooner).await.unwrap();
+
+        let pending = repo.list_pending().await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].message, "sooner");
+        assert_eq!(pending[0].channel.as_deref(), Some("telegram"));
+        assert_eq!(pending[1].message, "later");
+    }
+
+    #[tokio::test]
+    async fn test_list_due_and_mark_fired() {
+        let (_db, repo) = setup().await;
+        let due = Reminder::new("due".into(), Utc::now() - Duration::minutes(1), None, None);
+        let future = Reminder::new("future".into(), Utc::now() + Duration::hours(1), None, None);
+        repo.insert(&due).await.unwrap();
+        repo.insert(&future).await.unwrap();
+
+        let found = repo.list_due(Utc::now()).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, due.id);
+
+        repo.mark_fired(&due.id.to_string()).await.unwrap();
+        assert!(repo.list_due(Utc::now()).await.unwrap().is_empty());
+        let stored = repo.find_by_id(&due.id.to_string()).await.unwrap().unwrap();
+        assert!(stored.fired_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_only_pending() {
+        let (_db, repo) = setup().await;
+        let r = Reminder::new("x".into(), Utc::now() + Duration::hours(1), None, None);
+        repo.insert(&r).await.unwrap();
+
+        assert!(repo.cancel(&r.id.to_string()).await.unwrap());
+        assert!(!repo.cancel(&r.id.to_string()).await.unwrap());
+        assert!(repo.list_pending().await.unwrap().is_empty());
+    }
+}
+
+// --- Tool Tests ---
+
+mod tool {
+    use crate::brain::tools::reminder::{
+        CancelReminderTool, ListRemindersTool, ScheduleTaskTool, SetReminderTool,
+    };
+    use crate::brain::tools::{Tool, ToolExecutionContext};
+    use crate::db::Database;
+    use crate::db::ReminderRepository;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Shared owner session slot, as `ui.rs` hands to the reminder tools
+    fn owner(session_id: Uuid) -> Arc<Mutex<Option<Uuid>>> {
+        Arc::new(Mutex::new(Some(session_id)))
+    }
+
+    async fn setup() -> (Database, ReminderRepository) {
+        let db = Database::connect_in_memory()
+            .await
+            .expect("Failed to create database");
+        db.run_migrations().await.expect("Failed to run migrations");
+        let repo = ReminderRepository::new(db.pool().clone());
+        (db, repo)
+    }
+
+    #[tokio::test]
+    async fn test_set_list_cancel_roundtrip() {
+        let (_db, repo) = setup().await;
+        let session_id = Uuid::new_v4();
+        let set = SetReminderTool::new(repo.clone(), owner(session_id));
+        let list = ListRemindersTool::new(repo.clone(), owner(session_id));
+        let cancel = CancelReminderTool::new(repo.clone(), owner(session_id));
+        let ctx = ToolExecutionContext::new(session_id);
+
+        let result = set
+            .execute(
+                serde_json::json!({"when": "in 30 minutes", "message": "stretch"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let pending = repo.list_pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].session_id, Some(session_id));
+
+        let listed = list.execute(serde_json::json!({}), &ctx).await.unwrap();
+        assert!(listed.output.contains("stretch"));
+        assert!(listed.output.contains(&pending[0].id.to_string()));
+
+        let cancelled = cancel
+            .execute(
+                serde_json::json!({"reminder_id": pending[0].id.to_string()}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(cancelled.success);
+
+        let listed = list.execute(serde_json::json!({}), &ctx).await.unwrap();
+        assert_eq!(listed.output, "No pending reminders.");
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_past_and_bad_input() {
+        let (_db, repo) = setup().await;
+        let session_id = Uuid::new_v4();
+        let set = SetReminderTool::new(repo.clone(), owner(session_id));
+        let ctx = ToolExecutionContext::new(session_id);
+
+        let past = set
+            .execute(
+                serde_json::json!({"when": "2000-01-01T00:00:00Z", "message": "late"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!past.success);
+
+        let garbage = set
+            .execute(serde_json::json!({"when": "someday", "message": "x"}), &ctx)
+            .await
+            .unwrap();
+        assert!(!garbage.success);
+
+        let bad_channel = set
+            .execute(
+                serde_json::json!({"when": "in 1h", "message": "x", "channel": "fax"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!bad_channel.success);
+
+        assert!(repo.list_pending().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reminder_tools_do_not_require_approval() {
+        let (_db, repo) = setup().await;
+        let owner = owner(Uuid::new_v4());
+        assert!(!SetReminderTool::new(repo.clone(), owner.clone()).requires_approval());
+        assert!(!ListRemindersTool::new(repo.clone(), owner.clone()).requires_approval());
+        assert!(!CancelReminderTool::new(repo, owner).requires_approval());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_task_stores_agent_task() {
+        let (_db, repo) = setup().await;
+        let session_id = Uuid::new_v4();
+        let schedule = ScheduleTaskTool::new(repo.clone(), owner(session_id));
+        assert!(schedule.requires_approval());
+        let ctx = ToolExecutionContext::new(session_id);
+
+        let result = schedule
+            .execute(
+                serde_json::json!({"when": "in 2 hours", "instruction": "check the build"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let pending = repo.list_pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].run_agent);
+        assert_eq!(pending[0].message, "check the build");
+
+        let listed = ListRemindersTool::new(repo, owner(session_id))
+            .execute(serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+        assert!(listed.output.contains("[task]"));
+        assert!(listed.output.contains("Instruction: check the build"));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_task_is_owner_only() {
+        let (_db, repo) = setup().await;
+        let schedule = ScheduleTaskTool::new(repo.clone(), owner(Uuid::new_v4()));
+
+        // A channel user's session is not the owner's
+        let result = schedule
+            .execute(
+                serde_json::json!({"when": "in 2 hours", "instruction": "rm -rf ~"}),
+                &ToolExecutionContext::new(Uuid::new_v4()),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(repo.list_pending().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reminder_tools_are_owner_only() {
+        let (_db, repo) = setup().await;
+        let owner_id = Uuid::new_v4();
+        let owner_ctx = ToolExecutionContext::new(owner_id);
+        let stranger = ToolExecutionContext::new(Uuid::new_v4());
+        let set = SetReminderTool::new(repo.clone(), owner(owner_id));
+        let list = ListRemindersTool::new(repo.clone(), owner(owner_id));
+        let cancel = CancelReminderTool::new(repo.clone(), owner(owner_id));
+
+        // A channel user can't push a message into the owner's chat
+        let result = set
+            .execute(
+                serde_json::json!({"when": "in 1h", "message": "hi from a stranger"}),
+                &stranger,
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(repo.list_pending().await.unwrap().is_empty());
+
+        set.execute(
+            serde_json::json!({"when": "in 1h", "message": "owner's secret"}),
+            &owner_ctx,
+        )
+        .await
+        .unwrap();
+        let id = repo.list_pending().await.unwrap()[0].id.to_string();
+
+        // ...nor see or cancel the owner's reminders
+        let listed = list
+            .execute(serde_json::json!({}), &stranger)
+            .await
+            .unwrap();
+        assert!(!listed.success);
+        assert!(!listed.output.contains("owner's secret"));
+        let cancelled = cancel
+            .execute(serde_json::json!({"reminder_id": id}), &stranger)
+            .await
+            .unwrap();
+        assert!(!cancelled.success);
+        assert_eq!(repo.list_pending().await.unwrap().len(), 1);
+    }
+}
+
+// --- Scheduler Tests ---
+
+mod scheduler {
+    use crate::brain::provider::{
+        ContentBlock, LLMRequest, LLMResponse, Provider, ProviderStream, StopReason, TokenUsage,
+    };
+    use crate::channels::ChannelFactory;
+    use crate::config::Config;
+    use crate::cron::ReminderScheduler;
+    use crate::db::Database;
+    use crate::db::ReminderRepository;
+    use crate::db::models::Reminder;
+    use crate::services::{MessageService, ServiceContext, SessionService};
+    use crate::tui::events::TuiEvent;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_tick_fires_due_reminders_once() {
+        let db = Database::connect_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let repo = ReminderRepository::new(db.pool().clone());
+        repo.insert(&Reminder::new(
+            "drink water".into(),
+            Utc::now() - Duration::seconds(5),
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+        repo.insert(&Reminder::new(
+            "not yet".into(),
+            Utc::now() + Duration::hours(1),
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let scheduler = ReminderScheduler::new(repo.clone()).with_tui(tx);
+
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+        match rx.try_recv() {
+            Ok(TuiEvent::SystemMessage(text)) => assert!(text.contains("drink water")),
+            _ => panic!("expected a SystemMessage for the fired reminder"),
+        }
+
+        // Already fired — the next tick delivers nothing
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(repo.list_pending().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_due_task_waits_without_an_agent() {
+        let db = Database::connect_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let repo = ReminderRepository::new(db.pool().clone());
+        repo.insert(&Reminder::new_task(
+            "check the build".into(),
+            Utc::now() - Duration::seconds(5),
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let scheduler = ReminderScheduler::new(repo.clone()).with_tui(tx);
+
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        assert!(rx.try_recv().is_err());
+        assert!(repo.list_pending().await.unwrap()[0].run_agent);
+    }
+
+    /// Answers every request with a fixed text
+    struct TaskProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for TaskProvider {
+        async fn complete(
+            &self,
+            _request: LLMRequest,
+        ) -> crate::brain::provider::error::Result<LLMResponse> {
+            Ok(LLMResponse {
+                id: "task-response".into(),
+                model: "mock-model".into(),
+                content: vec![ContentBlock::Text {
+                    text: "The build is green.".into(),
+                }],
+                stop_reason: Some(StopReason::EndTurn),
+                usage: TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    ..Default::default()
+                },
+            })
+        }
+
+        async fn stream(
+            &self,
+            _request: LLMRequest,
+        ) -> crate::brain::provider::error::Result<ProviderStream> {
+            Err(crate::brain::provider::error::ProviderError::StreamingNotSupported)
+        }
+
+        fn supports_streaming(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+
+        fn supported_models(&self) -> Vec<String> {
+            vec!["mock-model".into()]
+        }
+
+        fn context_window(&self, _model: &str) -> Option<u32> {
+            Some(8192)
+        }
+
+        fn calculate_cost(&self, _model: &str, _input: u32, _output: u32) -> f64 {
+            0.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_due_task_runs_in_its_own_session() {
+        let db = Database::connect_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let context = ServiceContext::new(db.pool().clone());
+        let sessions = SessionService::new(context.clone());
+        let task_session = sessions.create_session(Some("Task".into())).await.unwrap();
+        let owner_session = sessions.create_session(Some("Owner".into())).await.unwrap();
+
+        let repo = ReminderRepository::new(db.pool().clone());
+        repo.insert(&Reminder::new_task(
+            "check the build".into(),
+            Utc::now() - Duration::seconds(5),
+            None,
+            Some(task_session.id),
+        ))
+        .await
+        .unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let (_config_tx, config_rx) = tokio::sync::watch::channel(Config::default());
+        let factory = Arc::new(ChannelFactory::new(
+            Arc::new(TaskProvider),
+            context.clone(),
+            "You are a test agent.".into(),
+            dir.path().to_path_buf(),
+            dir.path().to_path_buf(),
+            Arc::new(tokio::sync::Mutex::new(Some(owner_session.id))),
+            config_rx,
+        ));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let scheduler = ReminderScheduler::new(repo.clone())
+            .with_agent(factory)
+            .with_tui(tx);
+
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+        let delivered = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .expect("task result was not delivered")
+            .unwrap();
+        match delivered {
+            TuiEvent::SystemMessage(text) => {
+                assert!(text.contains("check the build"), "{text}");
+                assert!(text.contains("The build is green."), "{text}");
+            }
+            _ => panic!("expected a SystemMessage with the task result"),
+        }
+        assert!(repo.list_pending().await.unwrap().is_empty());
+
+        // The turn ran in the session that scheduled it, not the owner's current one
+        let messages = MessageService::new(context.clone());
+        assert!(
+            !messages
+                .list_messages_for_session(task_session.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            messages
+                .list_messages_for_session(owner_session.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
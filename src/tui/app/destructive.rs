@@ -0,0 +1,250 @@
+//! Confirmation and undo for destructive commands.
+//!
+//! Clearing the view (`/clear`), clearing a session (Ctrl+K) and deleting a
+//! session ([D] in the session list) first ask for `y`. Once done, `/undo`
+//! (or [U] in the session list) puts things back for [`UNDO_WINDOW`]. A
+//! deleted session is only archived during the window and removed for good
+//! when it closes.
+
+use super::events::AppMode;
+use super::*;
+use crate::db::models::Message;
+use anyhow::Result;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long `/undo` can restore the last destructive action
+pub(crate) const UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+/// A destructive action waiting for `y`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DestructiveAction {
+    /// `/clear` — empty the chat view
+    ClearView,
+    /// Ctrl+K — delete every message of a session
+    ClearSession { session_id: Uuid },
+    /// [D] in the session list — delete a session
+    DeleteSession { session_id: Uuid, title: String },
+}
+
+impl DestructiveAction {
+    /// The y/n question shown for this action
+    pub(crate) fn prompt(&self) -> String {
+        match self {
+            Self::ClearView => "Clear the chat view? The session history is kept.".to_string(),
+            Self::ClearSession { .. } => {
+                "Delete every message in this session? The agent loses this context.".to_string()
+            }
+            Self::DeleteSession { title, .. } => format!("Delete session \"{}\"?", title),
+        }
+    }
+}
+
+/// What `/undo` puts back
+#[derive(Debug)]
+pub(crate) enum UndoAction {
+    ClearView {
+        messages: Vec<DisplayMessage>,
+        hidden_older_messages: usize,
+        scroll_offset: usize,
+    },
+    ClearSession {
+        session_id: Uuid,
+        messages: Vec<Message>,
+    },
+    /// Archived, not yet deleted
+    DeleteSession {
+        session_id: Uuid,
+        title: String,
+        was_current: bool,
+    },
+}
+
+impl UndoAction {
+    /// What was done, e.g. `Deleted 12 messages`
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::ClearView { .. } => "Cleared the chat view".to_string(),
+            Self::ClearSession { messages, .. } => format!("Deleted {} messages", messages.len()),
+            Self::DeleteSession { title, .. } => format!("Deleted session \"{}\"", title),
+        }
+    }
+}
+
+impl App {
+    /// Ask for confirmation before running `action`
+    pub(crate) fn request_destructive(&mut self, action: DestructiveAction) {
+        let prompt = format!("{} [y/N]", action.prompt());
+        if self.mode == AppMode::Chat {
+            self.push_system_message(prompt);
+        }
+        self.pending_destructive = Some(action);
+    }
+
+    /// Key press while a destructive action is pending: `y` runs it,
+    /// anything else cancels.
+    pub(crate) async fn answer_destructive(&mut self, confirmed: bool) -> Result<()> {
+        let Some(action) = self.pending_destructive.take() else {
+            return Ok(());
+        };
+        if !confirmed {
+            if self.mode == AppMode::Chat {
+                self.push_system_message("Cancelled.".to_string());
+            }
+            return Ok(());
+        }
+        // Only one action can be undone; a new one makes the last permanent
+        self.finalize_undo().await;
+
+        let undo = match action {
+            DestructiveAction::ClearView => {
+                let undo = UndoAction::ClearView {
+                    messages: std::mem::take(&mut self.messages),
+                    hidden_older_messages: self.hidden_older_messages,
+                    scroll_offset: self.scroll_offset,
+                };
+                self.clear_view();
+                undo
+            }
+            DestructiveAction::ClearSession { session_id } => {
+                let messages = self
+                    .message_service
+                    .list_messages_for_session(session_id)
+                    .await?;
+                self.clear_session().await?;
+                UndoAction::ClearSession {
+                    session_id,
+                    messages,
+                }
+            }
+            DestructiveAction::DeleteSession { session_id, title } => {
+                let was_current = self
+                    .current_session
+                    .as_ref()
+                    .is_some_and(|s| s.id == session_id);
+                self.session_service.archive_session(session_id).await?;
+                if was_current {
+                    self.current_session = None;
+                    self.messages.clear();
+                    *self.shared_session_id.lock().await = None;
+                }
+                self.load_sessions().await?;
+                if self.selected_session_index >= self.sessions.len() {
+                    self.selected_session_index = self.sessions.len().saturating_sub(1);
+                }
+                UndoAction::DeleteSession {
+                    session_id,
+                    title,
+                    was_current,
+                }
+            }
+        };
+
+        let notice = format!(
+            "{}. /undo within {}s restores it.",
+            undo.describe(),
+            UNDO_WINDOW.as_secs()
+        );
+        if self.mode == AppMode::Chat {
+            self.push_system_message(notice);
+        }
+        self.undo = Some((undo, Instant::now()));
+        Ok(())
+    }
+
+    /// `/undo` — restore the last destructive action while its window is open
+    pub(crate) async fn undo_destructive(&mut self) -> Result<()> {
+        let Some((undo, at)) = self.undo.take() else {
+            if self.mode == AppMode::Chat {
+                self.push_system_message("Nothing to undo.".to_string());
+            }
+            return Ok(());
+        };
+        if at.elapsed() >= UNDO_WINDOW {
+            self.undo = Some((undo, at));
+            self.finalize_undo().await;
+            if self.mode == AppMode::Chat {
+                self.push_system_message(format!(
+                    "Too late — undo is only possible for {}s.",
+                    UNDO_WINDOW.as_secs()
+                ));
+            }
+            return Ok(());
+        }
+
+        match undo {
+            UndoAction::ClearView {
+                messages,
+                hidden_older_messages,
+                scroll_offset,
+            } => {
+                self.messages = messages;
+                self.hidden_older_messages = hidden_older_messages;
+                self.scroll_offset = scroll_offset;
+            }
+            UndoAction::ClearSession {
+                session_id,
+                messages,
+            } => {
+                // Restored messages keep their sequence numbers, which would
+                // interleave with anything written since
+                if !self
+                    .message_service
+                    .list_messages_for_session(session_id)
+                    .await?
+                    .is_empty()
+                {
+                    self.push_system_message(
+                        "Can't undo: the session has new messages since it was cleared."
+                            .to_string(),
+                    );
+                    return Ok(());
+                }
+                self.message_service.restore_messages(&messages).await?;
+                if self
+                    .current_session
+                    .as_ref()
+                    .is_some_and(|s| s.id == session_id)
+                {
+                    self.load_session(session_id).await?;
+                }
+            }
+            UndoAction::DeleteSession {
+                session_id,
+                was_current,
+                ..
+            } => {
+                self.session_service.unarchive_session(session_id).await?;
+                self.load_sessions().await?;
+                if was_current {
+                    self.load_session(session_id).await?;
+                }
+            }
+        }
+        if self.mode == AppMode::Chat {
+            self.push_system_message("Restored.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Make the last destructive action permanent once its window has passed
+    pub(crate) async fn tick_undo(&mut self) {
+        if self
+            .undo
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= UNDO_WINDOW)
+        {
+            self.finalize_undo().await;
+        }
+    }
+
+    /// Make the last destructive action permanent now (window closed, a new
+    /// action replaced it, or the app is quitting)
+    pub(crate) async fn finalize_undo(&mut self) {
+        if let Some((UndoAction::DeleteSession { session_id, .. }, _)) = self.undo.take()
+            && let Err(e) = self.session_service.delete_session(session_id).await
+        {
+            tracing::warn!("Failed to delete session {}: {}", session_id, e);
+        }
+    }
+}
@@ -1,7 +1,8 @@
-//! Dialogs — model selector, onboarding wizard, file/directory pickers.
+//! Dialogs — model selector, onboarding wizard, file/directory pickers,
+//! memory search view, command palette.
 
 use super::events::{AppMode, TuiEvent};
-use super::onboarding::WizardAction;
+use super::onboarding::{OnboardingWizard, WizardAction};
 use super::*;
 use crate::brain::provider::{ContentBlock, LLMRequest};
 use anyhow::Result;
@@ -510,11 +511,12 @@ impl App {
                 } else {
                     // Fetch live models from the provider (for non-Custom)
                     if !is_custom {
-                        self.model_selector_models = super::onboarding::fetch_provider_models(
+                        let models = super::onboarding::fetch_provider_models(
                             provider_idx,
                             api_key.as_deref(),
                         )
                         .await;
+                        self.model_selector_models = self.with_custom_model_choices(models);
                     }
                     self.model_selector_selected = 0;
 
@@ -536,6 +538,11 @@ impl App {
                     self.save_provider_selection(self.model_selector_provider_selected, false)
                         .await?;
                 }
+            } else if !is_custom && let Some(choice) = self.highlighted_custom_model_choice() {
+                // A custom:<name>/<model> entry — switch to that provider for this session
+                if let Err(e) = self.select_custom_model(&choice).await {
+                    self.push_system_message(format!("Error: {}", e));
+                }
             } else {
                 // Non-custom: on model field — save and close
                 self.save_provider_selection(self.model_selector_provider_selected, false)
@@ -546,6 +553,62 @@ impl App {
         Ok(())
     }
 
+    /// Add a `custom:<name>/<model>` entry for every configured custom provider,
+    /// so they can be picked next to the selected provider's own models.
+    /// An empty fetch falls back to the provider's static list first.
+    pub(crate) fn with_custom_model_choices(&self, mut models: Vec<String>) -> Vec<String> {
+        if models.is_empty() {
+            models = OnboardingWizard::load_default_models(self.model_selector_provider_selected);
+        }
+        if let Ok(config) = crate::config::Config::load() {
+            for choice in config.providers.custom_model_choices() {
+                if !models.contains(&choice) {
+                    models.push(choice);
+                }
+            }
+        }
+        models
+    }
+
+    /// The highlighted model-selector entry, if it is a `custom:<name>/<model>` one
+    fn highlighted_custom_model_choice(&self) -> Option<String> {
+        let filter = self.model_selector_filter.to_lowercase();
+        self.model_selector_models
+            .iter()
+            .filter(|m| m.to_lowercase().contains(&filter))
+            .nth(self.model_selector_selected)
+            .filter(|m| crate::config::ProviderConfigs::parse_custom_model_choice(m).is_some())
+            .cloned()
+    }
+
+    /// Switch the current session to a `custom:<name>/<model>` entry. Unlike
+    /// `save_provider_selection`, this leaves the `enabled` flags in config alone,
+    /// so every custom provider stays usable from other sessions.
+    async fn select_custom_model(&mut self, choice: &str) -> Result<()> {
+        let config = crate::config::Config::load()?;
+        let (provider, model) =
+            crate::brain::provider::factory::create_provider_for_custom_model(&config, choice)?;
+        let provider_name = provider.name().to_string();
+        self.agent_service.swap_provider(provider.clone());
+        self.provider_cache.insert(provider_name.clone(), provider);
+        self.default_model_name = model.clone();
+
+        if let Some(ref mut session) = self.current_session {
+            session.provider_name = Some(provider_name.clone());
+            session.model = Some(model.clone());
+            let session_copy = session.clone();
+            if let Err(e) = self.session_service.update_session(&session_copy).await {
+                tracing::warn!("Failed to persist provider to session: {}", e);
+            }
+        }
+
+        let change_msg = format!("[Model changed to {} (provider: {})]", model, provider_name);
+        self.push_system_message(change_msg.clone());
+        self.pending_context.push(change_msg);
+        self.mode = AppMode::Chat;
+        Ok(())
+    }
+
     /// Save provider selection to config and reload agent service
     /// If `close_dialog` is false, stays in model selector (for step 1 and 2)
     async fn save_provider_selection(
@@ -1031,12 +1094,12 @@ impl App {
 
                     let sender = self.event_sender();
                     tokio::spawn(async move {
-                        let models = super::onboarding::fetch_provider_models(
+                        let result = super::onboarding::try_fetch_provider_models(
                             provider_idx,
                             api_key.as_deref(),
                         )
                         .await;
-                        let _ = sender.send(TuiEvent::OnboardingModelsFetched(models));
+                        let _ = sender.send(TuiEvent::OnboardingModelsFetched(result));
                     });
                 }
                 WizardAction::WhatsAppConnect => {
@@ -1348,22 +1411,55 @@ impl App {
         self.file_picker_files = files;
         self.file_picker_selected = 0;
         self.file_picker_scroll_offset = 0;
+        self.file_picker_fuzzy = false;
         self.switch_mode(AppMode::FilePicker).await?;
 
         Ok(())
     }
 
+    /// Switch the file picker to fuzzy find over everything below the
+    /// current directory
+    async fn start_file_finder(&mut self) {
+        let root = self.file_picker_current_dir.clone();
+        self.file_picker_index =
+            tokio::task::spawn_blocking(move || crate::tui::file_finder::walk_files(&root))
+                .await
+                .unwrap_or_default();
+        self.file_picker_fuzzy = true;
+        self.file_picker_query.clear();
+        self.refresh_file_finder();
+    }
+
+    /// Re-rank the fuzzy-find results for the current query
+    fn refresh_file_finder(&mut self) {
+        let root = &self.file_picker_current_dir;
+        self.file_picker_files =
+            crate::tui::file_finder::rank(&self.file_picker_query, &self.file_picker_index)
+                .into_iter()
+                .map(|rel| root.join(rel))
+                .collect();
+        self.file_picker_selected = 0;
+        self.file_picker_scroll_offset = 0;
+    }
+
     /// Handle keys in file picker mode
     pub(crate) async fn handle_file_picker_key(
         &mut self,
         event: crossterm::event::KeyEvent,
     ) -> Result<()> {
         use super::events::keys;
-        use crossterm::event::KeyCode;
+        use crossterm::event::{KeyCode, KeyModifiers};
 
         if keys::is_cancel(&event) {
             // Cancel file picker and return to chat
             self.switch_mode(AppMode::Chat).await?;
+        } else if keys::is_fuzzy_toggle(&event) {
+            if self.file_picker_fuzzy {
+                // Back to folder navigation
+                self.open_file_picker().await?;
+            } else {
+                self.start_file_finder().await;
+            }
         } else if keys::is_up(&event) {
             // Move selection up
             self.file_picker_selected = self.file_picker_selected.saturating_sub(1);
@@ -1383,6 +1479,15 @@ impl App {
                     self.file_picker_scroll_offset = self.file_picker_selected - visible_items + 1;
                 }
             }
+        } else if self.file_picker_fuzzy && event.code == KeyCode::Backspace {
+            self.file_picker_query.pop();
+            self.refresh_file_finder();
+        } else if self.file_picker_fuzzy
+            && let KeyCode::Char(c) = event.code
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            self.file_picker_query.push(c);
+            self.refresh_file_finder();
         } else if keys::is_enter(&event) || event.code == KeyCode::Char(' ') || keys::is_tab(&event)
         {
             // Select file or navigate into directory
@@ -1529,6 +1634,138 @@ impl App {
 
         Ok(())
     }
+
+    /// Keys for the /memory results view: ↑↓ select, PgUp/PgDn page,
+    /// Enter queues the selected file as context for the next message.
+    pub(crate) async fn handle_memory_search_key(
+        &mut self,
+        event: crossterm::event::KeyEvent,
+    ) -> Result<()> {
+        use super::events::keys;
+
+        if keys::is_cancel(&event) {
+            self.memory_view = None;
+            return self.switch_mode(AppMode::Chat).await;
+        }
+        let Some(view) = self.memory_view.as_mut() else {
+            return self.switch_mode(AppMode::Chat).await;
+        };
+        let last = view.results.len().saturating_sub(1);
+
+        if keys::is_up(&event) {
+            view.selected = view.selected.saturating_sub(1);
+        } else if keys::is_down(&event) {
+            view.selected = (view.selected + 1).min(last);
+        } else if keys::is_page_up(&event) {
+            view.selected = (view.page().saturating_sub(1)) * MEMORY_PAGE_SIZE;
+        } else if keys::is_page_down(&event) {
+            view.selected = ((view.page() + 1) * MEMORY_PAGE_SIZE).min(last);
+        } else if keys::is_enter(&event)
+            && let Some(result) = view.results.get(view.selected).cloned()
+        {
+            match tokio::fs::read_to_string(&result.path).await {
+                Ok(content) => {
+                    self.pending_context.push(format!(
+                        "[Memory file {} loaded by the user]\n{}",
+                        result.path, content
+                    ));
+                    self.push_system_message(format!(
+                        "Added {} to the context of your next message.",
+                        result.path
+                    ));
+                }
+                Err(e) => {
+                    self.push_system_message(format!("Can't read {}: {e}", result.path));
+                }
+            }
+            self.memory_view = None;
+            self.switch_mode(AppMode::Chat).await?;
+        }
+        Ok(())
+    }
+
+    /// Open the Ctrl+P command palette with every action and slash command
+    pub(crate) async fn open_command_palette(&mut self) -> Result<()> {
+        self.command_palette = Some(CommandPalette::new(
+            &self.user_commands,
+            super::events::keys::binding_label,
+        ));
+        self.switch_mode(AppMode::CommandPalette).await
+    }
+
+    /// Keys for the command palette: type to filter, ↑↓ select, Enter runs
+    /// the highlighted entry.
+    pub(crate) async fn handle_command_palette_key(
+        &mut self,
+        event: crossterm::event::KeyEvent,
+    ) -> Result<()> {
+        use super::events::keys;
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if keys::is_cancel(&event) {
+            self.command_palette = None;
+            return self.switch_mode(AppMode::Chat).await;
+        }
+        let Some(palette) = self.command_palette.as_mut() else {
+            return self.switch_mode(AppMode::Chat).await;
+        };
+
+        if keys::is_up(&event) {
+            palette.selected = palette.selected.saturating_sub(1);
+        } else if keys::is_down(&event) {
+            palette.selected = (palette.selected + 1).min(palette.filtered.len().saturating_sub(1));
+        } else if event.code == KeyCode::Backspace {
+            palette.query.pop();
+            palette.refresh();
+        } else if keys::is_enter(&event) {
+            let command = palette.selected_entry().map(|e| e.command.clone());
+            self.command_palette = None;
+            self.switch_mode(AppMode::Chat).await?;
+            if let Some(command) = command {
+                self.run_palette_command(command).await?;
+            }
+        } else if let KeyCode::Char(c) = event.code
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            palette.query.push(c);
+            palette.refresh();
+        }
+        Ok(())
+    }
+
+    /// Run a command picked from the palette the same way its key or slash
+    /// command would
+    async fn run_palette_command(&mut self, command: PaletteCommand) -> Result<()> {
+        use crate::tui::keymap::Action;
+
+        match command {
+            PaletteCommand::Action(Action::Sessions) => {
+                self.switch_mode(AppMode::Sessions).await?;
+            }
+            PaletteCommand::Action(Action::NewSession) => self.create_new_session().await?,
+            PaletteCommand::Action(Action::ClearSession) => {
+                if let Some(session_id) = self.current_session.as_ref().map(|s| s.id) {
+                    self.request_destructive(DestructiveAction::ClearSession { session_id });
+                }
+            }
+            PaletteCommand::Action(Action::Help) => self.mode = AppMode::Help,
+            PaletteCommand::Action(Action::ModelSelector) => self.open_model_selector().await,
+            PaletteCommand::Action(Action::ExplainError) => self.explain_tool_error().await?,
+            // Not listed in the palette
+            PaletteCommand::Action(_) => {}
+            PaletteCommand::Slash(name) => {
+                if super::input::SLASH_COMMANDS_WITH_ARGS.contains(&name.as_str()) {
+                    // Needs an argument — leave it in the input to finish
+                    self.input_buffer = format!("{} ", name);
+                    self.cursor_position = self.input_buffer.len();
+                    self.update_slash_suggestions();
+                } else {
+                    self.handle_slash_command(&name).await;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Download WhisperCrabs binary if not cached, return the path to the binary.
@@ -1748,10 +1985,17 @@ async fn test_whatsapp_connection(
         .map_err(|e| format!("Invalid phone number format: {}", e))?;
 
     let wa_msg = waproto::whatsapp::Message {
-        conversation: Some(format!(
-            "{}\n\nOpenCrabs connected! I'm living in your WhatsApp now. 🦀",
-            crate::channels::whatsapp::handler::MSG_HEADER
-        )),
+        conversation: Some({
+            use crate::channels::whatsapp::handler::{reply_header, tag_outgoing};
+            let cfg = crate::config::Config::load().unwrap_or_default();
+            tag_outgoing(
+                &reply_header(&cfg),
+                &format!(
+                    "{} connected! I'm living in your WhatsApp now. {}",
+                    cfg.agent.name, cfg.agent.emoji
+                ),
+            )
+        }),
         ..Default::default()
     };
 
@@ -2,11 +2,57 @@
 
 use super::events::{AppMode, ToolApprovalResponse, TuiEvent};
 use super::*;
+use crate::brain::agent::ApprovalDecision;
+use crate::channels::image_limits::ImageLimits;
 use anyhow::Result;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// Prepended by plan-first (Ctrl+T) — matches the prompt analyzer's plan keywords, so the
+/// agent builds a plan with the `plan` tool before touching anything.
+const PLAN_FIRST_PREFIX: &str = "Make a plan for this: ";
+
+/// Commands that need an argument — picking one from the autocomplete
+/// fills it into the input instead of running it bare.
+pub(super) const SLASH_COMMANDS_WITH_ARGS: &[&str] = &["/save", "/load", "/tool"];
+
+/// How long the input must sit unchanged before its draft is written
+const DRAFT_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Directory holding one draft file per session
+fn drafts_dir() -> std::path::PathBuf {
+    crate::config::opencrabs_home().join("drafts")
+}
+
+/// Write `text` as the draft for `session_id`; an empty draft removes the file.
+fn write_draft_file(dir: &std::path::Path, session_id: Uuid, text: &str) -> std::io::Result<()> {
+    let path = dir.join(format!("{session_id}.txt"));
+    if text.trim().is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, text)
+}
+
+/// The saved draft for `session_id`, if any.
+fn read_draft_file(dir: &std::path::Path, session_id: Uuid) -> Option<String> {
+    std::fs::read_to_string(dir.join(format!("{session_id}.txt")))
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
+
 impl App {
+    /// Whether the input looks like a multi-step task and the
+    /// "plan it first" hint should show under the input box.
+    pub(crate) fn shows_plan_hint(&self) -> bool {
+        self.mode == AppMode::Chat
+            && !self.is_processing
+            && self.prompt_analyzer.suggests_plan(&self.input_buffer)
+    }
+
     /// Returns (line_start_byte, column_chars) for the cursor's current line.
     /// `line_start_byte` is the byte offset where the current line begins.
     /// `column_chars` is how many bytes into the line the cursor is.
@@ -174,6 +220,50 @@ impl App {
         }
     }
 
+    /// Write the current session's draft now (removing it when the input is
+    /// empty). No-op with `[tui] save_draft = false`.
+    pub(crate) fn flush_draft(&mut self) {
+        if !self.save_draft || self.input_buffer == self.draft_saved {
+            return;
+        }
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        if let Err(e) = write_draft_file(&drafts_dir(), session_id, &self.input_buffer) {
+            tracing::warn!("Failed to save input draft: {}", e);
+        }
+        self.draft_saved = self.input_buffer.clone();
+    }
+
+    /// Called every tick: flush the draft once the input has been still for
+    /// `DRAFT_DEBOUNCE`.
+    pub(crate) fn tick_draft(&mut self) {
+        if !self.save_draft {
+            return;
+        }
+        if self.input_buffer != self.draft_seen.0 {
+            self.draft_seen = (self.input_buffer.clone(), std::time::Instant::now());
+        } else if self.draft_seen.1.elapsed() >= DRAFT_DEBOUNCE {
+            self.flush_draft();
+        }
+    }
+
+    /// Load the current session's draft into the input box, replacing what
+    /// was there (the previous session's draft was flushed before switching).
+    pub(crate) fn restore_draft(&mut self) {
+        if !self.save_draft {
+            return;
+        }
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        let draft = read_draft_file(&drafts_dir(), session_id).unwrap_or_default();
+        self.input_buffer = draft.clone();
+        self.cursor_position = self.input_buffer.len();
+        self.draft_seen = (draft.clone(), std::time::Instant::now());
+        self.draft_saved = draft;
+    }
+
     pub fn has_pending_approval(&self) -> bool {
         self.messages.iter().rev().any(|msg| {
             msg.approval
@@ -190,6 +280,14 @@ impl App {
         })
     }
 
+    fn pending_variant_menu(&mut self) -> Option<&mut VariantMenu> {
+        self.messages
+            .iter_mut()
+            .rev()
+            .find_map(|m| m.variant_menu.as_mut())
+            .filter(|m| m.state == VariantMenuState::Pending)
+    }
+
     /// Handle keys in chat mode
     pub(crate) async fn handle_chat_key(
         &mut self,
@@ -299,8 +397,40 @@ impl App {
             return Ok(());
         }
 
+        // Intercept keys when a /variants picker is pending
+        if let Some(menu) = self.pending_variant_menu() {
+            if keys::is_up(&event) {
+                menu.selected_option = menu.selected_option.saturating_sub(1);
+            } else if keys::is_down(&event) {
+                menu.selected_option =
+                    (menu.selected_option + 1).min(menu.variants.len().saturating_sub(1));
+            } else if keys::is_enter(&event) || keys::is_submit(&event) {
+                let choice = menu.selected_option;
+                menu.state = VariantMenuState::Kept(choice);
+                let session_id = menu.session_id;
+                let anchor_id = menu.anchor_id;
+                let content = menu.variants[choice].clone();
+                let agent = self.agent_service.clone();
+                let sender = self.event_sender();
+                tokio::spawn(async move {
+                    let event = match agent.keep_variant(session_id, anchor_id, content).await {
+                        // Reload the chat so the kept reply replaces the old one
+                        Ok(_) => TuiEvent::SessionUpdated(session_id),
+                        Err(e) => TuiEvent::Error {
+                            session_id,
+                            message: format!("Could not keep variant: {e}"),
+                        },
+                    };
+                    let _ = sender.send(event);
+                });
+            } else if keys::is_cancel(&event) {
+                menu.state = VariantMenuState::Discarded;
+            }
+            return Ok(());
+        }
+
         // Intercept keys when an inline approval is pending
-        // Options: Yes(0), Always(1), No(2)
+        // Options: Yes(0), Always(1), No(2), No and stop the turn(3)
         if self.has_pending_approval() {
             if keys::is_left(&event) || keys::is_up(&event) {
                 // Navigate options left
@@ -323,11 +453,12 @@ impl App {
                     .find_map(|m| m.approval.as_mut())
                     .filter(|a| a.state == ApprovalState::Pending)
                 {
-                    approval.selected_option = (approval.selected_option + 1).min(2);
+                    approval.selected_option = (approval.selected_option + 1).min(3);
                 }
                 return Ok(());
             } else if keys::is_enter(&event) || keys::is_submit(&event) {
-                // Confirm: Yes(0)=approve once, Always(1)=approve always, No(2)=deny
+                // Confirm: Yes(0)=approve once, Always(1)=approve always, No(2)=deny,
+                // Stop(3)=deny and end the turn
                 let approval_data: Option<(
                     Uuid,
                     usize,
@@ -341,12 +472,21 @@ impl App {
                     .map(|a| (a.request_id, a.selected_option, a.response_tx.clone()));
 
                 if let Some((request_id, selected, response_tx)) = approval_data {
-                    if selected == 2 {
-                        // "No" — deny
+                    if selected >= 2 {
+                        // "No" — deny; "No, stop" also ends the turn
+                        let stop = selected == 3;
                         let response = ToolApprovalResponse {
                             request_id,
-                            approved: false,
-                            reason: Some("User denied permission".to_string()),
+                            decision: if stop {
+                                ApprovalDecision::DenyAndStop
+                            } else {
+                                ApprovalDecision::Deny
+                            },
+                            reason: Some(if stop {
+                                "User denied permission and stopped the turn".to_string()
+                            } else {
+                                "User denied permission".to_string()
+                            }),
                         };
                         if let Err(e) = response_tx.send(response.clone()) {
                             tracing::error!(
@@ -374,7 +514,7 @@ impl App {
                         }
                         let response = ToolApprovalResponse {
                             request_id,
-                            approved: true,
+                            decision: ApprovalDecision::Approve,
                             reason: None,
                         };
                         if let Err(e) = response_tx.send(response.clone()) {
@@ -408,7 +548,7 @@ impl App {
                 if let Some((request_id, response_tx)) = approval_data {
                     let response = ToolApprovalResponse {
                         request_id,
-                        approved: false,
+                        decision: ApprovalDecision::Deny,
                         reason: Some("User denied permission".to_string()),
                     };
                     if let Err(e) = response_tx.send(response.clone()) {
@@ -477,6 +617,12 @@ impl App {
                 // Select the highlighted command and execute it
                 if let Some(&cmd_idx) = self.slash_filtered.get(self.slash_selected_index) {
                     let cmd_name = self.slash_command_name(cmd_idx).unwrap_or("").to_string();
+                    if SLASH_COMMANDS_WITH_ARGS.contains(&cmd_name.as_str()) {
+                        self.input_buffer = format!("{} ", cmd_name);
+                        self.cursor_position = self.input_buffer.len();
+                        self.update_slash_suggestions();
+                        return Ok(());
+                    }
                     self.input_buffer.clear();
                     self.cursor_position = 0;
                     self.slash_suggestions_active = false;
@@ -520,7 +666,52 @@ impl App {
             self.escape_pending_at = None;
         }
 
-        if keys::is_newline(&event) {
+        // `/login` waiting for the authorization code — never echoed to the
+        // LLM or input history
+        if self.pending_login.is_some() {
+            if keys::is_submit(&event) {
+                let code = std::mem::take(&mut self.input_buffer);
+                self.cursor_position = 0;
+                if !code.trim().is_empty()
+                    && let Some(login) = self.pending_login.take()
+                {
+                    self.complete_login(login, code);
+                }
+                return Ok(());
+            } else if keys::is_cancel(&event) {
+                self.pending_login = None;
+                self.push_system_message("Login cancelled.".to_string());
+                return Ok(());
+            }
+        }
+
+        // `/load` collecting placeholder values — Enter takes the input as the
+        // next value (empty allowed), Esc abandons the template
+        if self.template_fill.is_some() {
+            if keys::is_submit(&event) {
+                let value = std::mem::take(&mut self.input_buffer);
+                self.cursor_position = 0;
+                if let Some(fill) = self.template_fill.as_mut()
+                    && let Some(filled) = fill.push(value.trim().to_string())
+                {
+                    self.template_fill = None;
+                    self.cursor_position = filled.len();
+                    self.input_buffer = filled;
+                }
+                return Ok(());
+            } else if keys::is_cancel(&event) {
+                if let Some(fill) = self.template_fill.take() {
+                    self.push_system_message(format!("Cancelled template '{}'.", fill.name));
+                }
+                return Ok(());
+            }
+        }
+
+        if keys::is_plan_first(&event) && self.shows_plan_hint() {
+            // Ctrl+T on a multi-step prompt = ask for a plan before executing
+            self.input_buffer.insert_str(0, PLAN_FIRST_PREFIX);
+            self.cursor_position += PLAN_FIRST_PREFIX.len();
+        } else if keys::is_newline(&event) {
             // Alt+Enter or Shift+Enter = insert newline for multi-line input
             self.input_buffer.insert(self.cursor_position, '\n');
             self.cursor_position += 1;
@@ -549,6 +740,21 @@ impl App {
                     content.clone()
                 };
 
+            // Same image caps as the channels ([channels] max_images_per_message / max_image_bytes)
+            if !all_attachments.is_empty() {
+                let mut images = crate::config::Config::load()
+                    .map(|c| ImageLimits::from_config(&c.channels))
+                    .unwrap_or_default()
+                    .budget("TUI");
+                all_attachments.retain(|att| {
+                    let size = std::fs::metadata(&att.path).ok().map(|m| m.len());
+                    images.admit(&att.name, size)
+                });
+                if let Some(note) = images.note() {
+                    self.push_system_message(note);
+                }
+            }
+
             // Enter = send message
             // Save to input history (dedup consecutive) and persist to disk
             let trimmed = content.trim().to_string();
@@ -561,6 +767,7 @@ impl App {
 
             self.input_buffer.clear();
             self.cursor_position = 0;
+            self.flush_draft();
             self.attachments.clear();
             self.slash_suggestions_active = false;
             self.dismiss_emoji_picker();
@@ -576,6 +783,9 @@ impl App {
                 }
                 msg
             };
+            if send_content.trim().is_empty() {
+                return Ok(());
+            }
             self.send_message(send_content).await?;
         } else if keys::is_cancel(&event) {
             // When processing, double-Escape aborts the operation
@@ -605,12 +815,13 @@ impl App {
                                 cost: None,
                                 approval: None,
                                 approve_menu: None,
+                                variant_menu: None,
                                 details: None,
                                 expanded: false,
                                 tool_group: None,
                             });
                         }
-                        self.streaming_reasoning = None;
+                        self.clear_streaming_reasoning();
                         self.cancel_token = None;
                         self.escape_pending_at = None;
                         // Deny any pending approvals so agent callbacks don't hang
@@ -620,7 +831,7 @@ impl App {
                             {
                                 let _ = approval.response_tx.send(ToolApprovalResponse {
                                     request_id: approval.request_id,
-                                    approved: false,
+                                    decision: ApprovalDecision::Deny,
                                     reason: Some("Operation cancelled".to_string()),
                                 });
                                 approval.state =
@@ -643,6 +854,7 @@ impl App {
                                 cost: None,
                                 approval: None,
                                 approve_menu: None,
+                                variant_menu: None,
                                 details: None,
                                 expanded: false,
                                 tool_group: Some(group),
@@ -698,6 +910,8 @@ impl App {
                 self.error_message = Some("Press Esc again to clear input".to_string());
                 self.error_message_shown_at = Some(std::time::Instant::now());
             }
+        } else if keys::is_explain_error(&event) {
+            self.explain_tool_error().await?;
         } else if event.code == KeyCode::Char('o') && event.modifiers == KeyModifiers::CONTROL {
             if self.hidden_older_messages > 0 && self.display_token_count < 300_000 {
                 // Load more history from DB
@@ -1015,28 +1229,45 @@ impl App {
             self.create_new_session().await?;
             self.switch_mode(AppMode::Chat).await?;
         } else if event.code == KeyCode::Char('d') || event.code == KeyCode::Char('D') {
-            // Delete the selected session
+            // Delete the selected session (asks first; [U] undoes)
             if let Some(session) = self.sessions.get(self.selected_session_index) {
-                let session_id = session.id;
-                let is_current = self
-                    .current_session
-                    .as_ref()
-                    .map(|s| s.id == session_id)
-                    .unwrap_or(false);
-                self.session_service.delete_session(session_id).await?;
-                if is_current {
-                    self.current_session = None;
-                    self.messages.clear();
-                    *self.shared_session_id.lock().await = None;
-                }
-                self.load_sessions().await?;
-                // Adjust index if it's now out of bounds
-                if self.selected_session_index >= self.sessions.len() {
-                    self.selected_session_index = self.sessions.len().saturating_sub(1);
-                }
+                let action = DestructiveAction::DeleteSession {
+                    session_id: session.id,
+                    title: session
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| "Untitled".to_string()),
+                };
+                self.request_destructive(action);
             }
+        } else if event.code == KeyCode::Char('u') || event.code == KeyCode::Char('U') {
+            self.undo_destructive().await?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draft_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(read_draft_file(dir.path(), a), None);
+
+        write_draft_file(dir.path(), a, "First paragraph\n\nSecond").unwrap();
+        assert_eq!(
+            read_draft_file(dir.path(), a).as_deref(),
+            Some("First paragraph\n\nSecond")
+        );
+        assert_eq!(read_draft_file(dir.path(), b), None);
+
+        // An empty input clears the draft; clearing twice is fine
+        write_draft_file(dir.path(), a, "  ").unwrap();
+        write_draft_file(dir.path(), a, "").unwrap();
+        assert_eq!(read_draft_file(dir.path(), a), None);
+    }
+}
@@ -5,6 +5,9 @@ use super::events::{AppMode, ToolApprovalResponse, TuiEvent};
 use super::onboarding::OnboardingWizard;
 use super::*;
 use crate::brain::SelfUpdater;
+use crate::brain::agent::ApprovalDecision;
+use crate::brain::provider::anthropic_oauth::PkceLogin;
+use crate::tui::prompts::{self, TemplateFill};
 use anyhow::Result;
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
@@ -34,7 +37,9 @@ impl App {
             .create_session_with_provider(Some("New Chat".to_string()), provider_name, model)
             .await?;
 
+        self.flush_draft();
         self.current_session = Some(session.clone());
+        self.restore_draft();
         self.set_plan_file_for_session(session.id);
         self.is_processing = false; // New session is never processing
         self.messages.clear();
@@ -43,7 +48,7 @@ impl App {
         self.mode = AppMode::Chat;
         // Clear streaming state from any previous session
         self.streaming_response = None;
-        self.streaming_reasoning = None;
+        self.clear_streaming_reasoning();
         self.active_tool_group = None;
         self.streaming_output_tokens = 0;
         self.intermediate_text_received = false;
@@ -92,7 +97,7 @@ impl App {
         // Clear streaming state from previous session so it doesn't
         // bleed into the newly loaded session's chat view.
         self.streaming_response = None;
-        self.streaming_reasoning = None;
+        self.clear_streaming_reasoning();
         self.active_tool_group = None;
         self.streaming_output_tokens = 0;
         self.intermediate_text_received = false;
@@ -111,7 +116,16 @@ impl App {
             }
         }
 
+        // Swap input drafts only on a real switch — reloading the current
+        // session (e.g. a channel message landed) must not touch the input
+        let switching = self.current_session.as_ref().map(|s| s.id) != Some(session.id);
+        if switching {
+            self.flush_draft();
+        }
         self.current_session = Some(session.clone());
+        if switching {
+            self.restore_draft();
+        }
         self.set_plan_file_for_session(session.id);
         // Sync is_processing flag with per-session state
         self.is_processing = self.processing_sessions.contains(&session.id);
@@ -159,7 +173,10 @@ impl App {
         self.sessions_with_unread.remove(&session_id);
 
         // Auto-restore provider if session has a different one than current
-        if let Some(ref saved_provider) = session.provider_name {
+        // (safe mode sticks to the default provider)
+        if !self.safe_mode
+            && let Some(ref saved_provider) = session.provider_name
+        {
             let current_provider = self.agent_service.provider_name();
             if *saved_provider != current_provider {
                 // Try cache first
@@ -247,6 +264,7 @@ impl App {
             cost: None,
             approval: None,
             approve_menu: None,
+            variant_menu: None,
             details: None,
             expanded: false,
             tool_group: None,
@@ -343,7 +361,7 @@ impl App {
             self.messages.clear();
             self.scroll_offset = 0;
             self.streaming_response = None;
-            self.streaming_reasoning = None;
+            self.clear_streaming_reasoning();
             self.error_message = None;
             self.error_message_shown_at = None;
         }
@@ -351,6 +369,18 @@ impl App {
         Ok(())
     }
 
+    /// `/clear` — empty the chat view. The session and its stored messages
+    /// are untouched, so the agent keeps full context. Runs once confirmed
+    /// (see `request_destructive`).
+    pub(crate) fn clear_view(&mut self) {
+        self.messages.clear();
+        self.scroll_offset = 0;
+        // Older messages stay hidden too; Ctrl+O would bring them back
+        self.hidden_older_messages = 0;
+        self.error_message = None;
+        self.error_message_shown_at = None;
+    }
+
     /// Handle slash commands locally (returns true if handled)
     pub(crate) async fn handle_slash_command(&mut self, input: &str) -> bool {
         let cmd = input.split_whitespace().next().unwrap_or("");
@@ -422,6 +452,7 @@ impl App {
                         selected_option: 0,
                         state: ApproveMenuState::Pending,
                     }),
+                    variant_menu: None,
                     details: None,
                     expanded: false,
                     tool_group: None,
@@ -443,6 +474,20 @@ impl App {
                 ));
                 true
             }
+            "/summary" => {
+                self.summarize_session(input);
+                true
+            }
+            "/clear" => {
+                self.request_destructive(DestructiveAction::ClearView);
+                true
+            }
+            "/undo" => {
+                if let Err(e) = self.undo_destructive().await {
+                    self.push_system_message(format!("Undo failed: {}", e));
+                }
+                true
+            }
             "/rebuild" => {
                 self.push_system_message(
                     "🔨 Building from source... (streaming output below)".to_string(),
@@ -474,15 +519,56 @@ impl App {
                                 })
                                 .await
                             {
-                                Ok(_) => {
-                                    let _ = sender
-                                        .send(TuiEvent::RestartReady("✅ Build complete".into()));
-                                }
+                                Ok(_) => {}
                                 Err(e) => {
                                     let _ = sender.send(TuiEvent::Error {
                                         session_id: sid,
                                         message: format!("Build failed: {}", e),
                                     });
+                                    return;
+                                }
+                            }
+
+                            // Gate the restart on the test suite, reporting the
+                            // tally each time a test binary finishes
+                            let _ = sender
+                                .send(TuiEvent::SystemMessage("🧪 Running tests...".to_string()));
+                            let tx = sender.clone();
+                            let progress_tx = sender.clone();
+                            let tests = updater
+                                .test_streaming(
+                                    move |line| {
+                                        if line.trim_end().ends_with(" ... FAILED") {
+                                            let _ = tx.send(TuiEvent::SystemMessage(line));
+                                        }
+                                    },
+                                    move |progress| {
+                                        if progress.done == progress.total {
+                                            let _ = progress_tx.send(TuiEvent::SystemMessage(
+                                                progress.status(false),
+                                            ));
+                                        }
+                                    },
+                                )
+                                .await;
+                            match tests {
+                                Ok(progress) => {
+                                    let diff = updater.diff("HEAD").ok();
+                                    let _ = sender.send(TuiEvent::RestartReady {
+                                        status: format!(
+                                            "✅ Build complete, {}",
+                                            progress.status(true)
+                                        ),
+                                        diff,
+                                    });
+                                }
+                                Err(output) => {
+                                    let lines: Vec<&str> = output.lines().collect();
+                                    let tail = lines[lines.len().saturating_sub(30)..].join("\n");
+                                    let _ = sender.send(TuiEvent::Error {
+                                        session_id: sid,
+                                        message: format!("Tests failed, not restarting:\n{}", tail),
+                                    });
                                 }
                             }
                         }
@@ -556,6 +642,115 @@ impl App {
                 let _ = self.open_directory_picker().await;
                 true
             }
+            "/config" => {
+                let shown =
+                    crate::config::Config::load().and_then(|config| config.to_redacted_toml());
+                self.push_system_message(match shown {
+                    Ok(toml) => {
+                        format!("Effective config (secrets redacted):\n```toml\n{}```", toml)
+                    }
+                    Err(e) => format!("Couldn't load config: {:#}", e),
+                });
+                true
+            }
+            "/tools" => {
+                let table = Self::format_tools_table(&self.agent_service.tool_registry().list());
+                self.push_system_message(table);
+                true
+            }
+            "/tool" => {
+                self.run_tool_directly(input);
+                true
+            }
+            "/metrics" => {
+                use crate::brain::agent::{reset_tool_metrics, tool_metrics};
+                if input.strip_prefix("/metrics").unwrap_or("").trim() == "reset" {
+                    reset_tool_metrics();
+                    self.push_system_message("Tool metrics cleared.".to_string());
+                } else {
+                    self.push_system_message(Self::format_metrics_table(&tool_metrics()));
+                }
+                true
+            }
+            "/save" => {
+                self.save_prompt_template(input);
+                true
+            }
+            "/load" => {
+                self.load_prompt_template(input);
+                true
+            }
+            "/login" => {
+                self.start_login(input);
+                true
+            }
+            "/audit" => {
+                self.show_audit_tail(input);
+                true
+            }
+            "/reload" => {
+                self.reload_config();
+                true
+            }
+            "/variants" => {
+                self.start_variants(input);
+                true
+            }
+            "/temp" => {
+                self.set_temperature(input);
+                true
+            }
+            "/think" => {
+                self.set_reasoning_effort(input);
+                true
+            }
+            "/pause" | "/resume" => {
+                self.set_channel_paused(input, cmd == "/pause");
+                true
+            }
+            "/unlock" => {
+                self.unlock_session(input).await;
+                true
+            }
+            "/memory" => {
+                self.open_memory_search(input);
+                true
+            }
+            "/loglevel" => {
+                let level = input.strip_prefix("/loglevel").unwrap_or("").trim();
+                if level.is_empty() {
+                    self.push_system_message(
+                        "Usage: /loglevel <error|warn|info|debug|trace>".to_string(),
+                    );
+                } else {
+                    match crate::logging::set_log_level(level) {
+                        Ok(level) => {
+                            self.push_system_message(format!("Log level set to {}", level))
+                        }
+                        Err(e) => self.push_system_message(format!("Can't change log level: {e}")),
+                    }
+                }
+                true
+            }
+            "/prompts" => {
+                let names = prompts::list(&prompts::prompts_dir());
+                if names.is_empty() {
+                    self.push_system_message(
+                        "No prompt templates yet. Save one with /save <name> [text].".to_string(),
+                    );
+                } else {
+                    self.push_system_message(format!(
+                        "Prompt templates ({}):\n{}\n\nInsert one with /load <name>.",
+                        names.len(),
+                        names
+                            .iter()
+                            .map(|n| format!("  {n}"))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    ));
+                }
+                true
+            }
             _ if input.starts_with('/') => {
                 // Check user-defined commands
                 if let Some(user_cmd) = self.user_commands.iter().find(|c| c.name == cmd) {
@@ -583,6 +778,737 @@ impl App {
         }
     }
 
+    /// `/memory <query>` — search long-term memory and open the results view.
+    /// Results arrive as `TuiEvent::MemoryResults`.
+    fn open_memory_search(&mut self, input: &str) {
+        let query = input
+            .strip_prefix("/memory")
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        if query.is_empty() {
+            self.push_system_message(
+                "Usage: /memory <query> — \"exact phrase\", AND, OR and NOT are supported"
+                    .to_string(),
+            );
+            return;
+        }
+
+        self.memory_view = Some(MemoryView {
+            query: query.clone(),
+            loading: true,
+            ..Default::default()
+        });
+        self.mode = AppMode::MemorySearch;
+
+        let sender = self.event_sender();
+        tokio::spawn(async move {
+            let results = match crate::memory::get_store() {
+                Ok(store) => crate::memory::search(store, &query, MEMORY_MAX_RESULTS)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = sender.send(TuiEvent::MemoryResults { query, results });
+        });
+    }
+
+    /// `/temp [value|reset]` — show or override the current session's
+    /// sampling temperature. The override outlives provider switches.
+    fn set_temperature(&mut self, input: &str) {
+        use crate::brain::agent::{session_temperature, set_session_temperature};
+
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        let arg = input.strip_prefix("/temp").unwrap_or("").trim();
+        if arg.is_empty() {
+            let configured = self
+                .agent_service
+                .sampling_for(&self.default_model_name)
+                .temperature;
+            let current = match (session_temperature(session_id), configured) {
+                (Some(t), _) => format!("{t} (session override)"),
+                (None, Some(t)) => format!("{t} (from config)"),
+                (None, None) => "provider default".to_string(),
+            };
+            self.push_system_message(format!(
+                "Temperature: {current}. Usage: /temp <0.0-2.0|reset>"
+            ));
+            return;
+        }
+        if arg == "reset" {
+            set_session_temperature(session_id, None);
+            self.push_system_message("Temperature override cleared.".to_string());
+            return;
+        }
+        match arg.parse::<f32>() {
+            Ok(t) if (0.0..=2.0).contains(&t) => {
+                set_session_temperature(session_id, Some(t));
+                self.push_system_message(format!("Temperature set to {t} for this session."));
+            }
+            _ => self.push_system_message(
+                "Temperature must be a number between 0.0 and 2.0 (or 'reset').".to_string(),
+            ),
+        }
+    }
+
+    /// `/pause <channel>` / `/resume <channel>` — stop or restart a channel's
+    /// replies without touching its connection or config
+    fn set_channel_paused(&mut self, input: &str, paused: bool) {
+        use crate::channels::{MessageSource, paused_channels, set_paused};
+
+        let arg = input.split_whitespace().nth(1).unwrap_or("").to_lowercase();
+        let names: Vec<&str> = MessageSource::PAUSABLE.iter().map(|s| s.name()).collect();
+        let Some(source) = MessageSource::PAUSABLE
+            .into_iter()
+            .find(|source| source.name() == arg)
+        else {
+            let paused_now: Vec<&str> = paused_channels().iter().map(|s| s.name()).collect();
+            self.push_system_message(format!(
+                "Usage: /{} <{}>. Paused: {}",
+                if paused { "pause" } else { "resume" },
+                names.join("|"),
+                if paused_now.is_empty() {
+                    "none".to_string()
+                } else {
+                    paused_now.join(", ")
+                }
+            ));
+            return;
+        };
+        let message = match (set_paused(source, paused), paused) {
+            (true, true) => format!(
+                "{} paused — messages are ignored; the owner is told once.",
+                source.name()
+            ),
+            (true, false) => format!("{} resumed.", source.name()),
+            (false, true) => format!("{} is already paused.", source.name()),
+            (false, false) => format!("{} isn't paused.", source.name()),
+        };
+        self.push_system_message(message);
+    }
+
+    /// `/unlock [session id|name]`: let a session continue past its
+    /// token/cost limit — the current one by default, or a channel user's
+    /// session named in the limit message.
+    async fn unlock_session(&mut self, input: &str) {
+        let arg = input.strip_prefix("/unlock").unwrap_or("").trim();
+        let session_id = if arg.is_empty() {
+            let Some(id) = self.current_session.as_ref().map(|s| s.id) else {
+                self.push_system_message("No active session to unlock.".to_string());
+                return;
+            };
+            id
+        } else {
+            if let Err(e) = self.load_sessions().await {
+                tracing::warn!("Failed to load sessions for /unlock: {}", e);
+            }
+            let lower = arg.to_lowercase();
+            let matches: Vec<Uuid> = self
+                .sessions
+                .iter()
+                .filter(|s| {
+                    s.id.to_string().starts_with(&lower)
+                        || s.title
+                            .as_deref()
+                            .is_some_and(|t| t.to_lowercase() == lower)
+                })
+                .map(|s| s.id)
+                .collect();
+            match (matches.as_slice(), arg.parse::<Uuid>()) {
+                ([id], _) => *id,
+                (_, Ok(id)) => id,
+                ([], _) => {
+                    self.push_system_message(format!(
+                        "No session matches '{arg}'. Usage: /unlock [session id|name]"
+                    ));
+                    return;
+                }
+                _ => {
+                    self.push_system_message(format!(
+                        "'{arg}' matches {} sessions — use more of the session id.",
+                        matches.len()
+                    ));
+                    return;
+                }
+            }
+        };
+        if self.agent_service.session_limits().is_unlimited() {
+            self.push_system_message(
+                "No session limits are set ([agent] max_session_tokens / max_session_cost)."
+                    .to_string(),
+            );
+            return;
+        }
+        let message = match self.agent_service.unlock_session(session_id).await {
+            Ok(()) => {
+                "Session unlocked — it gets a fresh allowance from its current usage.".to_string()
+            }
+            Err(e) => format!("Can't unlock session: {}", e),
+        };
+        self.push_system_message(message);
+    }
+
+    /// `/think [level|tokens|reset]` — show or override the current
+    /// session's reasoning effort. The override outlives provider switches.
+    fn set_reasoning_effort(&mut self, input: &str) {
+        use crate::brain::agent::{session_reasoning_effort, set_session_reasoning_effort};
+        use crate::brain::provider::ReasoningEffort;
+
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        let arg = input.strip_prefix("/think").unwrap_or("").trim();
+        let provider = self.agent_service.provider();
+        let ignored_note = if provider.supports_reasoning_effort(&self.default_model_name) {
+            String::new()
+        } else {
+            format!(
+                "\nNote: {} ({}) ignores this setting — it only reaches OpenAI reasoning models and Gemini thinking models.",
+                provider.name(),
+                self.default_model_name
+            )
+        };
+        if arg.is_empty() {
+            let configured = self
+                .agent_service
+                .sampling_for(&self.default_model_name)
+                .reasoning_effort;
+            let current = match (session_reasoning_effort(session_id), configured) {
+                (Some(e), _) => format!("{e} (session override)"),
+                (None, Some(e)) => format!("{e} (from config)"),
+                (None, None) => "provider default".to_string(),
+            };
+            self.push_system_message(format!(
+                "Reasoning effort: {current}. Usage: /think <low|medium|high|tokens|reset>{ignored_note}"
+            ));
+            return;
+        }
+        if arg == "reset" {
+            set_session_reasoning_effort(session_id, None);
+            self.push_system_message("Reasoning effort override cleared.".to_string());
+            return;
+        }
+        match arg.parse::<ReasoningEffort>() {
+            Ok(effort) => {
+                set_session_reasoning_effort(session_id, Some(effort));
+                self.push_system_message(format!(
+                    "Reasoning effort set to {effort} for this session.{ignored_note}"
+                ));
+            }
+            Err(e) => self.push_system_message(format!("Reasoning effort: {e} (or 'reset').")),
+        }
+    }
+
+    /// `/variants [n]` — regenerate the last reply `n` times (default 3) at a
+    /// looser temperature; the results arrive as `TuiEvent::VariantsReady`.
+    fn start_variants(&mut self, input: &str) {
+        use crate::brain::agent::{MAX_VARIANTS, VARIANT_TEMPERATURE};
+
+        let arg = input.strip_prefix("/variants").unwrap_or("").trim();
+        let n = match arg.parse::<usize>() {
+            _ if arg.is_empty() => 3,
+            Ok(n) if (2..=MAX_VARIANTS).contains(&n) => n,
+            _ => {
+                self.push_system_message(format!(
+                    "Usage: /variants [n] — n between 2 and {MAX_VARIANTS} (default 3)"
+                ));
+                return;
+            }
+        };
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        if self.processing_sessions.contains(&session_id) {
+            self.push_system_message(
+                "Wait for the current reply to finish before generating variants.".to_string(),
+            );
+            return;
+        }
+
+        self.push_system_message(format!("Generating {n} variants of the last reply…"));
+        let agent = self.agent_service.clone();
+        let sender = self.event_sender();
+        let model = Some(self.default_model_name.clone());
+        tokio::spawn(async move {
+            let event = match agent
+                .generate_variants(session_id, n, VARIANT_TEMPERATURE, model)
+                .await
+            {
+                Ok((anchor_id, variants)) => TuiEvent::VariantsReady {
+                    session_id,
+                    anchor_id,
+                    variants,
+                },
+                Err(e) => TuiEvent::Error {
+                    session_id,
+                    message: format!("Variants failed: {e}"),
+                },
+            };
+            let _ = sender.send(event);
+        });
+    }
+
+    /// `/save <name> [text]` — save the text after the name (may span lines),
+    /// or the last prompt sent if there is none.
+    fn save_prompt_template(&mut self, input: &str) {
+        let rest = input.strip_prefix("/save").unwrap_or("").trim_start();
+        let (name, body) = rest
+            .split_once(char::is_whitespace)
+            .map(|(n, b)| (n, b.trim()))
+            .unwrap_or((rest.trim(), ""));
+
+        if !prompts::is_valid_name(name) {
+            self.push_system_message(
+                "Usage: /save <name> [text] — name may use letters, digits, '-' and '_'. \
+                 Without text, saves your last sent prompt."
+                    .to_string(),
+            );
+            return;
+        }
+        let body = if body.is_empty() {
+            match self.input_history.last() {
+                Some(last) => last.clone(),
+                None => {
+                    self.push_system_message(
+                        "Nothing to save — add the prompt text after the name.".to_string(),
+                    );
+                    return;
+                }
+            }
+        } else {
+            body.to_string()
+        };
+
+        match prompts::save(&prompts::prompts_dir(), name, &body) {
+            Ok(path) => {
+                let count = prompts::placeholders(&body).len();
+                let hint = if count > 0 {
+                    format!(" ({count} placeholder(s))")
+                } else {
+                    String::new()
+                };
+                self.push_system_message(format!(
+                    "Saved prompt template '{}'{} → {}",
+                    name,
+                    hint,
+                    path.display()
+                ));
+            }
+            Err(e) => {
+                self.push_system_message(format!("Failed to save template '{}': {}", name, e))
+            }
+        }
+    }
+
+    /// `/summary [save]` — ask the model for a recap of the current session and
+    /// show it as a system message. The recap is never added to the session;
+    /// `save` also appends it to today's memory log.
+    fn summarize_session(&mut self, input: &str) {
+        let remember = match input.split_whitespace().nth(1) {
+            None => false,
+            Some("save") => true,
+            Some(_) => {
+                self.push_system_message("Usage: /summary [save]".to_string());
+                return;
+            }
+        };
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            self.push_system_message("No active session to summarize.".to_string());
+            return;
+        };
+
+        self.push_system_message("Summarizing session...".to_string());
+        let agent_service = self.agent_service.clone();
+        let sender = self.event_sender();
+        tokio::spawn(async move {
+            match agent_service.summarize_session(session_id, remember).await {
+                Ok(summary) => {
+                    let saved = if remember {
+                        "\n\n(saved to today's memory log)"
+                    } else {
+                        ""
+                    };
+                    let _ = sender.send(TuiEvent::SystemMessage(format!(
+                        "📋 Session summary\n\n{}{}",
+                        summary.trim(),
+                        saved
+                    )));
+                }
+                Err(e) => {
+                    let _ = sender.send(TuiEvent::Error {
+                        session_id,
+                        message: format!("Summary failed: {}", e),
+                    });
+                }
+            }
+        });
+    }
+
+    /// `/audit tail [n]` — the last `n` audit trail entries (default 20).
+    fn show_audit_tail(&mut self, input: &str) {
+        use crate::logging::audit;
+
+        let mut args = input.split_whitespace().skip(1);
+        let (Some("tail"), count) = (args.next(), args.next()) else {
+            self.push_system_message("Usage: /audit tail [n]".to_string());
+            return;
+        };
+        let Some(count) = count.map_or(Some(20), |n| n.parse::<usize>().ok()) else {
+            self.push_system_message("Usage: /audit tail [n]".to_string());
+            return;
+        };
+
+        let entries = audit::tail(&audit::audit_dir(), count);
+        if entries.is_empty() {
+            self.push_system_message(
+                "No audit entries yet. Enable the trail with [audit] enabled = true.".to_string(),
+            );
+            return;
+        }
+        self.push_system_message(format!(
+            "Audit trail (last {}):\n{}",
+            entries.len(),
+            entries
+                .iter()
+                .map(|e| format!("  {}", e.display_line()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ));
+    }
+
+    /// `/reload` — re-read config now. The watcher answers with a
+    /// `ConfigReloaded` report listing applied and deferred changes.
+    fn reload_config(&mut self) {
+        // Surface parse errors here; the watcher only logs them
+        if let Err(e) = crate::config::Config::load() {
+            self.push_system_message(format!("Reload failed, keeping current config: {e}"));
+            return;
+        }
+        if crate::utils::config_watcher::trigger_reload() {
+            self.reload_requested = true;
+        } else {
+            self.push_system_message("Config watcher is not running.".to_string());
+        }
+    }
+
+    /// `/load <name>` — insert a template into the input, asking for any
+    /// `{{placeholder}}` values first.
+    fn load_prompt_template(&mut self, input: &str) {
+        let name = input.strip_prefix("/load").unwrap_or("").trim();
+        if name.is_empty() {
+            self.push_system_message(
+                "Usage: /load <name> — see /prompts for the list.".to_string(),
+            );
+            return;
+        }
+        if !prompts::is_valid_name(name) {
+            self.push_system_message(format!("Invalid template name: {}", name));
+            return;
+        }
+        let template = match prompts::load(&prompts::prompts_dir(), name) {
+            Ok(t) => t,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.push_system_message(format!(
+                    "No prompt template named '{}'. See /prompts.",
+                    name
+                ));
+                return;
+            }
+            Err(e) => {
+                self.push_system_message(format!("Failed to load template '{}': {}", name, e));
+                return;
+            }
+        };
+
+        let placeholders = prompts::placeholders(&template);
+        if placeholders.is_empty() {
+            let _ = self.event_sender().send(TuiEvent::InsertInput(template));
+            return;
+        }
+        self.push_system_message(format!(
+            "Template '{}' has {} placeholder(s) — type each value and press Enter (Esc cancels).",
+            name,
+            placeholders.len()
+        ));
+        self.template_fill = Some(TemplateFill {
+            name: name.to_string(),
+            template,
+            placeholders,
+            values: Vec::new(),
+        });
+    }
+
+    /// `/login [anthropic]` — open the Claude authorize page and wait for the
+    /// user to paste back the code shown after approving.
+    fn start_login(&mut self, input: &str) {
+        let provider = input.strip_prefix("/login").unwrap_or("").trim();
+        if !provider.is_empty() && !provider.eq_ignore_ascii_case("anthropic") {
+            self.push_system_message(format!(
+                "Unsupported login provider: {}. Usage: /login anthropic",
+                provider
+            ));
+            return;
+        }
+
+        let login = PkceLogin::start();
+        open_in_browser(&login.url);
+        self.push_system_message(format!(
+            "Sign in with your Claude Pro/Max account (opening your browser):\n\n  {}\n\n\
+             After approving, paste the code shown on the page and press Enter (Esc cancels).",
+            login.url
+        ));
+        self.pending_login = Some(login);
+    }
+
+    /// Finish `/login` with the pasted `code#state` in the background.
+    pub(crate) fn complete_login(&mut self, login: PkceLogin, code: String) {
+        self.push_system_message("Exchanging authorization code…".to_string());
+        let sender = self.event_sender();
+        tokio::spawn(async move {
+            let result = match login.exchange(&reqwest::Client::new(), &code).await {
+                Ok(tokens) => tokens.save().map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = sender.send(TuiEvent::LoginCompleted {
+                provider: "Anthropic".to_string(),
+                error: result.err(),
+            });
+        });
+    }
+
+    /// `/tool <name> [json]` — execute a tool straight through the registry,
+    /// bypassing the model and approvals, and show the raw `ToolResult`.
+    /// Only with `[dev] allow_direct_tools`, since the tool really runs.
+    fn run_tool_directly(&mut self, input: &str) {
+        const USAGE: &str =
+            "Usage: /tool <name> <json input>, e.g. /tool read_file {\"path\": \"Cargo.toml\"}";
+
+        let config = crate::config::Config::load().unwrap_or_default();
+        if !config.dev.allow_direct_tools {
+            self.push_system_message(
+                "Direct tool calls are off. Set [dev] allow_direct_tools = true to enable \
+                 /tool — tools run for real, with no approval prompt."
+                    .to_string(),
+            );
+            return;
+        }
+
+        let rest = input.strip_prefix("/tool").unwrap_or("").trim();
+        let (name, json) = rest
+            .split_once(char::is_whitespace)
+            .map(|(n, j)| (n, j.trim()))
+            .unwrap_or((rest, ""));
+        if name.is_empty() {
+            self.push_system_message(USAGE.to_string());
+            return;
+        }
+        let tool_input: Value = if json.is_empty() {
+            serde_json::json!({})
+        } else {
+            match serde_json::from_str(json) {
+                Ok(v) => v,
+                Err(e) => {
+                    self.push_system_message(format!("Invalid JSON input: {e}\n{USAGE}"));
+                    return;
+                }
+            }
+        };
+        let registry = self.agent_service.tool_registry().clone();
+        if !registry.has_tool(name) {
+            self.push_system_message(format!(
+                "Unknown tool '{name}'. /tools lists the registered ones."
+            ));
+            return;
+        }
+
+        let session_id = self
+            .current_session
+            .as_ref()
+            .map(|s| s.id)
+            .unwrap_or(Uuid::nil());
+        let mut context = crate::brain::tools::ToolExecutionContext::new(session_id)
+            .with_auto_approve(true)
+            .with_working_directory(self.agent_service.get_working_directory());
+        context.service_context = Some(self.agent_service.context().clone());
+
+        self.push_system_message(format!("🔧 Running {name} directly..."));
+        let name = name.to_string();
+        let sender = self.event_sender();
+        tokio::spawn(async move {
+            let result = registry.execute(&name, tool_input, &context).await;
+            let _ = sender.send(TuiEvent::SystemMessage(Self::format_direct_tool_result(
+                &name, result,
+            )));
+        });
+    }
+
+    /// Explain-error quick action: send the last failed call in the selected
+    /// tool group (or the most recent one) back to the agent as a new turn.
+    pub(crate) async fn explain_tool_error(&mut self) -> Result<()> {
+        if self.is_processing {
+            self.push_system_message(
+                "Wait for the current reply to finish before asking about an error.".to_string(),
+            );
+            return Ok(());
+        }
+        let group = self
+            .selected_message_idx
+            .and_then(|idx| self.messages.get(idx))
+            .and_then(|m| m.tool_group.as_ref())
+            .or_else(|| {
+                self.messages
+                    .iter()
+                    .rev()
+                    .find_map(|m| m.tool_group.as_ref())
+            });
+        let Some(call) = group.and_then(|g| g.calls.iter().rev().find(|c| !c.success)) else {
+            self.push_system_message(
+                "No failed tool call to explain — click a tool group with a failure first."
+                    .to_string(),
+            );
+            return Ok(());
+        };
+        let prompt = Self::explain_error_prompt(call);
+        self.selected_message_idx = None;
+        self.send_message(prompt).await
+    }
+
+    /// The turn sent by the explain-error action: tool, input and error text
+    pub(crate) fn explain_error_prompt(call: &ToolCallEntry) -> String {
+        let tool = if call.tool_name.is_empty() {
+            &call.description
+        } else {
+            &call.tool_name
+        };
+        let mut prompt = format!(
+            "The `{tool}` tool call failed. Explain what this error means and suggest a fix.\n"
+        );
+        if !call.tool_input.is_null() {
+            prompt.push_str(&format!(
+                "\nInput:\n```json\n{}\n```\n",
+                serde_json::to_string_pretty(&call.tool_input).unwrap_or_default()
+            ));
+        }
+        prompt.push_str(&format!(
+            "\nError:\n```\n{}\n```",
+            call.details.as_deref().unwrap_or("(no error output)")
+        ));
+        prompt
+    }
+
+    /// The raw outcome of a `/tool` call
+    pub(crate) fn format_direct_tool_result(
+        name: &str,
+        result: crate::brain::tools::Result<crate::brain::tools::ToolResult>,
+    ) -> String {
+        match result {
+            Ok(r) => {
+                let mut out = format!(
+                    "🔧 {name} → {}",
+                    if r.success { "success" } else { "failed" }
+                );
+                if !r.output.is_empty() {
+                    out.push_str(&format!("\n{}", r.output));
+                }
+                if let Some(error) = r.error {
+                    out.push_str(&format!("\nerror: {error}"));
+                }
+                if !r.metadata.is_empty() {
+                    out.push_str(&format!("\nmetadata: {:?}", r.metadata));
+                }
+                out
+            }
+            Err(e) => format!("🔧 {name} → error: {e}"),
+        }
+    }
+
+    /// Render per-tool metrics as a fixed-width table for `/metrics`
+    pub fn format_metrics_table(metrics: &[(String, crate::brain::agent::ToolMetrics)]) -> String {
+        if metrics.is_empty() {
+            return "No tool calls recorded yet.".to_string();
+        }
+        let fmt_duration = |d: std::time::Duration| {
+            if d.as_millis() < 1000 {
+                format!("{}ms", d.as_millis())
+            } else {
+                format!("{:.1}s", d.as_secs_f64())
+            }
+        };
+        let name_w = metrics
+            .iter()
+            .map(|(n, _)| n.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        let calls: u64 = metrics.iter().map(|(_, m)| m.calls).sum();
+        let failures: u64 = metrics.iter().map(|(_, m)| m.failures).sum();
+
+        let mut out = format!(
+            "{} tool calls, {} failed (/metrics reset to clear)\n\n{:<name_w$}  {:>6}  {:>5}  {:>8}  {:>8}",
+            calls, failures, "TOOL", "CALLS", "OK%", "AVG", "MAX",
+        );
+        for (name, m) in metrics {
+            out.push_str(&format!(
+                "\n{:<name_w$}  {:>6}  {:>4.0}%  {:>8}  {:>8}",
+                name,
+                m.calls,
+                m.success_rate() * 100.0,
+                fmt_duration(m.avg_duration()),
+                fmt_duration(m.max_duration),
+            ));
+        }
+        out
+    }
+
+    /// Render the tool registry as a fixed-width table for `/tools`
+    pub fn format_tools_table(tools: &[crate::brain::tools::ToolInfo]) -> String {
+        const DESC_MAX: usize = 60;
+        let approval_count = tools.iter().filter(|t| t.requires_approval).count();
+        let caps: Vec<String> = tools
+            .iter()
+            .map(|t| {
+                if t.capabilities.is_empty() {
+                    "-".to_string()
+                } else {
+                    t.capabilities
+                        .iter()
+                        .map(|c| format!("{:?}", c))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }
+            })
+            .collect();
+        let name_w = tools.iter().map(|t| t.name.len()).max().unwrap_or(0).max(4);
+        let caps_w = caps.iter().map(|c| c.len()).max().unwrap_or(0).max(12);
+
+        let mut out = format!(
+            "{} tools registered ({} require approval)\n\n{:<name_w$}  {:<8}  {:<caps_w$}  DESCRIPTION",
+            tools.len(),
+            approval_count,
+            "NAME",
+            "APPROVAL",
+            "CAPABILITIES",
+        );
+        for (tool, caps) in tools.iter().zip(&caps) {
+            let first_line = tool.description.lines().next().unwrap_or("");
+            let desc = if first_line.len() > DESC_MAX {
+                format!("{}…", crate::utils::truncate_str(first_line, DESC_MAX))
+            } else {
+                first_line.to_string()
+            };
+            out.push_str(&format!(
+                "\n{:<name_w$}  {:<8}  {:<caps_w$}  {}",
+                tool.name,
+                if tool.requires_approval { "yes" } else { "no" },
+                caps,
+                desc,
+            ));
+        }
+        out
+    }
+
     /// Format a human-readable description of a tool call from its name and input
     pub fn format_tool_description(tool_name: &str, tool_input: &Value) -> String {
         match tool_name {
@@ -619,6 +1545,13 @@ impl App {
                     .unwrap_or("?");
                 format!("Edit {}", path)
             }
+            "apply_patch" => {
+                let path = tool_input
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?");
+                format!("Patch {}", path)
+            }
             "ls" => {
                 let path = tool_input
                     .get("path")
@@ -626,6 +1559,13 @@ impl App {
                     .unwrap_or(".");
                 format!("ls {}", path)
             }
+            "list_files" => {
+                let path = tool_input
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(".");
+                format!("Tree {}", path)
+            }
             "glob" => {
                 let pattern = tool_input
                     .get("pattern")
@@ -859,6 +1799,7 @@ impl App {
                         cost: if first_text { cost } else { None },
                         approval: None,
                         approve_menu: None,
+                        variant_menu: None,
                         details: reasoning,
                         expanded: false,
                         tool_group: None,
@@ -876,6 +1817,7 @@ impl App {
                         cost: if first_text { cost } else { None },
                         approval: None,
                         approve_menu: None,
+                        variant_menu: None,
                         details: Some(r),
                         expanded: false,
                         tool_group: None,
@@ -906,10 +1848,12 @@ impl App {
                                 .map(|s| s.to_string())
                                 .filter(|s| !s.is_empty());
                             ToolCallEntry {
+                                tool_name: String::new(),
                                 description: desc,
                                 success,
                                 details: output,
                                 tool_input: serde_json::Value::Null,
+                                live_output: Vec::new(),
                             }
                         })
                         .collect()
@@ -918,10 +1862,12 @@ impl App {
                     tools_str
                         .split(" | ")
                         .map(|desc| ToolCallEntry {
+                            tool_name: String::new(),
                             description: desc.to_string(),
                             success: true,
                             details: None,
                             tool_input: serde_json::Value::Null,
+                            live_output: Vec::new(),
                         })
                         .collect()
                 };
@@ -941,6 +1887,7 @@ impl App {
                         cost: None,
                         approval: None,
                         approve_menu: None,
+                        variant_menu: None,
                         details: None,
                         expanded: false,
                         tool_group: Some(ToolCallGroup {
@@ -970,6 +1917,7 @@ impl App {
                     cost: if first_text { cost } else { None },
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: reasoning,
                     expanded: false,
                     tool_group: None,
@@ -984,6 +1932,7 @@ impl App {
                     cost: if first_text { cost } else { None },
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: Some(r),
                     expanded: false,
                     tool_group: None,
@@ -1002,6 +1951,7 @@ impl App {
                 cost,
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: None,
                 expanded: false,
                 tool_group: None,
@@ -1174,6 +2124,7 @@ impl App {
             cost: None,
             approval: None,
             approve_menu: None,
+            variant_menu: None,
             details: None,
             expanded: false,
             tool_group: None,
@@ -1236,7 +2187,7 @@ impl App {
             {
                 let _ = approval.response_tx.send(ToolApprovalResponse {
                     request_id: approval.request_id,
-                    approved: false,
+                    decision: ApprovalDecision::Deny,
                     reason: Some("Superseded".to_string()),
                 });
                 approval.state = ApprovalState::Denied("Superseded".to_string());
@@ -1300,6 +2251,7 @@ impl App {
                     cost: None,
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: None,
                     expanded: false,
                     tool_group: None,
@@ -1350,7 +2302,7 @@ impl App {
                         tracing::error!("[agent_task] ERROR: {}", e);
                         if let Err(e2) = event_sender.send(TuiEvent::Error {
                             session_id,
-                            message: e.to_string(),
+                            message: e.user_message(),
                         }) {
                             tracing::error!("[agent_task] FAILED to send Error event: {}", e2);
                         }
@@ -1376,6 +2328,13 @@ impl App {
 
     /// Append a streaming chunk
     pub(crate) fn append_streaming_chunk(&mut self, chunk: String) {
+        // First answer token after reasoning: freeze the thinking time so the
+        // reasoning block collapses to a summary while the answer streams
+        if self.reasoning_elapsed.is_none()
+            && let Some(started) = self.reasoning_started_at
+        {
+            self.reasoning_elapsed = Some(started.elapsed());
+        }
         if let Some(ref mut response) = self.streaming_response {
             response.push_str(&chunk);
         } else {
@@ -1387,6 +2346,13 @@ impl App {
         }
     }
 
+    /// Drop in-progress reasoning along with its phase timing
+    pub(crate) fn clear_streaming_reasoning(&mut self) {
+        self.streaming_reasoning = None;
+        self.reasoning_started_at = None;
+        self.reasoning_elapsed = None;
+    }
+
     /// Complete the streaming response
     pub(crate) async fn complete_response(
         &mut self,
@@ -1409,6 +2375,7 @@ impl App {
         self.streaming_response = None;
         self.streaming_output_tokens = 0;
         let reasoning_details = self.streaming_reasoning.take();
+        self.clear_streaming_reasoning();
         self.cancel_token = None;
         self.escape_pending_at = None; // Reset so abort hint doesn't leak to input clear
 
@@ -1423,7 +2390,7 @@ impl App {
                 );
                 let _ = approval.response_tx.send(ToolApprovalResponse {
                     request_id: approval.request_id,
-                    approved: false,
+                    decision: ApprovalDecision::Deny,
                     reason: Some("Agent completed without resolution".to_string()),
                 });
                 approval.state =
@@ -1442,6 +2409,7 @@ impl App {
                 cost: None,
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: Some("queued".to_string()),
                 expanded: false,
                 tool_group: None,
@@ -1463,6 +2431,7 @@ impl App {
                 cost: None,
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: None,
                 expanded: false,
                 tool_group: Some(group),
@@ -1503,6 +2472,7 @@ impl App {
                 cost: Some(response.cost),
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: reasoning_details,
                 expanded: false,
                 tool_group: None,
@@ -1527,3 +2497,22 @@ impl App {
         Ok(())
     }
 }
+
+/// Best-effort: open `url` in the default browser. The URL is also shown in
+/// chat, so failures (headless, SSH) are only logged.
+fn open_in_browser(url: &str) {
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open").arg(url).spawn();
+    #[cfg(target_os = "linux")]
+    let result = std::process::Command::new("xdg-open").arg(url).spawn();
+    #[cfg(target_os = "windows")]
+    let result = std::process::Command::new("cmd")
+        .args(["/C", "start", "", url])
+        .spawn();
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    let result: std::io::Result<std::process::Child> = Err(std::io::ErrorKind::Unsupported.into());
+
+    if let Err(e) = result {
+        tracing::debug!("Could not open browser: {}", e);
+    }
+}
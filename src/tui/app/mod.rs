@@ -1,10 +1,13 @@
 //! App Module — TUI application state and logic.
 
+mod destructive;
 mod dialogs;
 mod input;
 mod messaging;
+mod mouse;
 mod state;
 
+pub(crate) use destructive::{DestructiveAction, UndoAction};
 pub use state::*;
 
 // Re-export sibling modules so sub-modules can use `super::events`, etc.
@@ -0,0 +1,125 @@
+//! Mouse handling — wheel scrolling and click-to-select, per mode.
+//!
+//! Clicks are hit-tested against `click_regions`, which the renderer
+//! rebuilds every frame, so they always match what's on screen.
+
+use super::events::AppMode;
+use super::*;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Chat/help lines scrolled per wheel notch
+const SCROLL_LINES: usize = 3;
+
+impl App {
+    /// Wheel scroll; positive `delta` is up. Scrolls the chat or help view,
+    /// and moves the selection in the sessions list and model selector.
+    pub(crate) async fn handle_mouse_scroll(&mut self, delta: i32) -> Result<()> {
+        if delta == 0 {
+            return Ok(());
+        }
+        let steps = delta.unsigned_abs() as usize;
+        let up = delta > 0;
+
+        match self.mode {
+            AppMode::Chat => {
+                if up {
+                    // Scrolling up — disable auto-scroll
+                    self.scroll_offset = self.scroll_offset.saturating_add(steps * SCROLL_LINES);
+                    self.auto_scroll = false;
+                } else {
+                    self.scroll_offset = self.scroll_offset.saturating_sub(steps * SCROLL_LINES);
+                    // Re-enable auto-scroll when back at bottom
+                    if self.scroll_offset == 0 {
+                        self.auto_scroll = true;
+                    }
+                }
+            }
+            AppMode::Help => {
+                self.help_scroll_offset = if up {
+                    self.help_scroll_offset.saturating_sub(steps * SCROLL_LINES)
+                } else {
+                    self.help_scroll_offset.saturating_add(steps * SCROLL_LINES)
+                };
+            }
+            AppMode::Sessions if !self.session_renaming => {
+                self.selected_session_index = if up {
+                    self.selected_session_index.saturating_sub(steps)
+                } else {
+                    (self.selected_session_index + steps).min(self.sessions.len().saturating_sub(1))
+                };
+            }
+            AppMode::ModelSelector => {
+                // Reuse the arrow-key handling so bounds and provider
+                // side effects stay in one place
+                let code = if up { KeyCode::Up } else { KeyCode::Down };
+                for _ in 0..steps {
+                    self.handle_model_selector_key(KeyEvent::new(code, KeyModifiers::NONE))
+                        .await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Left-click. Selects a message in chat, focuses the input, or selects a
+    /// row in the sessions list / model selector — clicking an already
+    /// selected row activates it like Enter.
+    pub(crate) async fn handle_mouse_click(&mut self, col: u16, row: u16) -> Result<()> {
+        let target = self
+            .click_regions
+            .iter()
+            .find(|(rect, _)| {
+                col >= rect.x
+                    && col < rect.x.saturating_add(rect.width)
+                    && row >= rect.y
+                    && row < rect.y.saturating_add(rect.height)
+            })
+            .map(|(_, target)| *target);
+
+        match (self.mode, target) {
+            (AppMode::Chat, Some(ClickTarget::Input)) => {
+                // Focus the input: drop any message selection, cursor to end
+                self.selected_message_idx = None;
+                self.cursor_position = self.input_buffer.len();
+            }
+            (AppMode::Chat, _) => self.handle_click_select(row),
+            (AppMode::Sessions, Some(ClickTarget::Session(idx))) if !self.session_renaming => {
+                if idx == self.selected_session_index {
+                    self.handle_sessions_key(enter_key()).await?;
+                } else if idx < self.sessions.len() {
+                    self.selected_session_index = idx;
+                }
+            }
+            (AppMode::ModelSelector, Some(ClickTarget::Provider(idx))) => {
+                let already = self.model_selector_focused_field == 0
+                    && idx == self.model_selector_provider_selected;
+                self.model_selector_focused_field = 0;
+                self.model_selector_showing_providers = true;
+                if already {
+                    self.handle_model_selector_key(enter_key()).await?;
+                } else if idx != self.model_selector_provider_selected {
+                    self.model_selector_provider_selected = idx;
+                    self.detect_model_selector_key_for_provider();
+                }
+            }
+            (AppMode::ModelSelector, Some(ClickTarget::Model(idx))) => {
+                let already =
+                    self.model_selector_focused_field == 2 && idx == self.model_selector_selected;
+                self.model_selector_focused_field = 2;
+                self.model_selector_showing_providers = false;
+                self.model_selector_selected = idx;
+                if already {
+                    self.handle_model_selector_key(enter_key()).await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn enter_key() -> KeyEvent {
+    KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)
+}
@@ -8,12 +8,18 @@ use super::events::{
 };
 use super::onboarding::OnboardingWizard;
 use super::prompt_analyzer::PromptAnalyzer;
-use crate::brain::agent::AgentService;
+use super::{DestructiveAction, UndoAction};
+use crate::brain::agent::{AgentService, ApprovalDecision};
 use crate::brain::provider::Provider;
+use crate::brain::provider::anthropic_oauth::PkceLogin;
 use crate::brain::{BrainLoader, CommandLoader, SelfUpdater, UserCommand};
 use crate::db::models::{Message, Session};
 use crate::services::{MessageService, ServiceContext, SessionService};
+use crate::tui::fuzzy::fuzzy_score;
+use crate::tui::keymap::Action;
+use crate::tui::prompts::{self, TemplateFill};
 use anyhow::Result;
+use ratatui::layout::Rect;
 use ratatui::text::Line;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -23,6 +29,19 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// What a mouse click at a recorded region selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    /// The chat input box
+    Input,
+    /// Row in the sessions list
+    Session(usize),
+    /// Provider row in the model selector
+    Provider(usize),
+    /// Model row in the model selector (index into the filtered list)
+    Model(usize),
+}
+
 /// Slash command definition
 #[derive(Debug, Clone)]
 pub struct SlashCommand {
@@ -30,11 +49,12 @@ pub struct SlashCommand {
     pub description: &'static str,
 }
 
-/// Available slash commands for autocomplete
+/// Built-in slash commands — the single source for autocomplete and the help
+/// screen. Add new commands here with a short description.
 pub const SLASH_COMMANDS: &[SlashCommand] = &[
     SlashCommand {
         name: "/help",
-        description: "Show available commands",
+        description: "Show this screen",
     },
     SlashCommand {
         name: "/models",
@@ -42,11 +62,11 @@ pub const SLASH_COMMANDS: &[SlashCommand] = &[
     },
     SlashCommand {
         name: "/usage",
-        description: "Session usage stats",
+        description: "Token & cost stats",
     },
     SlashCommand {
         name: "/onboard",
-        description: "Run setup wizard",
+        description: "Setup wizard (start)",
     },
     SlashCommand {
         name: "/onboard:provider",
@@ -78,7 +98,7 @@ pub const SLASH_COMMANDS: &[SlashCommand] = &[
     },
     SlashCommand {
         name: "/sessions",
-        description: "List all sessions",
+        description: "Session manager",
     },
     SlashCommand {
         name: "/approve",
@@ -88,9 +108,21 @@ pub const SLASH_COMMANDS: &[SlashCommand] = &[
         name: "/compact",
         description: "Compact context now",
     },
+    SlashCommand {
+        name: "/clear",
+        description: "Clear the chat view (history is kept)",
+    },
+    SlashCommand {
+        name: "/undo",
+        description: "Restore what /clear, Ctrl+K or a session delete removed (30s)",
+    },
+    SlashCommand {
+        name: "/summary",
+        description: "Recap this session (not saved as a turn): /summary [save]",
+    },
     SlashCommand {
         name: "/rebuild",
-        description: "Build & restart from source",
+        description: "Build, test & restart from source",
     },
     SlashCommand {
         name: "/evolve",
@@ -104,6 +136,78 @@ pub const SLASH_COMMANDS: &[SlashCommand] = &[
         name: "/cd",
         description: "Change working directory",
     },
+    SlashCommand {
+        name: "/tools",
+        description: "List registered tools",
+    },
+    SlashCommand {
+        name: "/metrics",
+        description: "Per-tool calls, durations and success rate: /metrics [reset]",
+    },
+    SlashCommand {
+        name: "/tool",
+        description: "Run a tool directly: /tool <name> <json> ([dev] allow_direct_tools)",
+    },
+    SlashCommand {
+        name: "/save",
+        description: "Save a prompt template: /save <name> [text]",
+    },
+    SlashCommand {
+        name: "/load",
+        description: "Insert a prompt template: /load <name>",
+    },
+    SlashCommand {
+        name: "/prompts",
+        description: "List saved prompt templates",
+    },
+    SlashCommand {
+        name: "/login",
+        description: "Sign in with a Claude Pro/Max subscription: /login anthropic",
+    },
+    SlashCommand {
+        name: "/audit",
+        description: "Show recent audit trail entries: /audit tail [n]",
+    },
+    SlashCommand {
+        name: "/loglevel",
+        description: "Change log verbosity live: /loglevel <warn|info|debug|trace>",
+    },
+    SlashCommand {
+        name: "/config",
+        description: "Show the effective config (secrets redacted)",
+    },
+    SlashCommand {
+        name: "/reload",
+        description: "Re-read config.toml and apply what can change without a restart",
+    },
+    SlashCommand {
+        name: "/variants",
+        description: "Regenerate the last reply N times and keep one: /variants [n]",
+    },
+    SlashCommand {
+        name: "/memory",
+        description: "Browse long-term memory: /memory <query>",
+    },
+    SlashCommand {
+        name: "/temp",
+        description: "Set this session's temperature: /temp <0.0-2.0|reset>",
+    },
+    SlashCommand {
+        name: "/pause",
+        description: "Stop a channel replying until /resume: /pause <telegram|discord|whatsapp>",
+    },
+    SlashCommand {
+        name: "/resume",
+        description: "Let a paused channel reply again: /resume <telegram|discord|whatsapp>",
+    },
+    SlashCommand {
+        name: "/unlock",
+        description: "Let a session continue past its token/cost limit: /unlock [session id|name]",
+    },
+    SlashCommand {
+        name: "/think",
+        description: "Set this session's reasoning effort: /think <low|medium|high|tokens|reset>",
+    },
 ];
 
 /// Approval option selected by the user
@@ -154,6 +258,152 @@ pub struct ApproveMenu {
     pub state: ApproveMenuState,
 }
 
+/// State for the /variants picker
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantMenuState {
+    Pending,
+    Kept(usize),
+    Discarded,
+}
+
+/// Data for the /variants inline picker
+#[derive(Debug, Clone)]
+pub struct VariantMenu {
+    pub session_id: Uuid,
+    /// The user message the variants answer — the kept one replaces what follows it
+    pub anchor_id: Uuid,
+    pub variants: Vec<String>,
+    pub selected_option: usize,
+    pub state: VariantMenuState,
+}
+
+/// Live output lines kept per running tool call
+pub const MAX_LIVE_OUTPUT_LINES: usize = 8;
+
+/// Results shown per page in the /memory view
+pub const MEMORY_PAGE_SIZE: usize = 8;
+
+/// Most results fetched for one /memory query
+pub const MEMORY_MAX_RESULTS: usize = 50;
+
+/// State of the /memory search view
+#[derive(Debug, Clone, Default)]
+pub struct MemoryView {
+    pub query: String,
+    pub results: Vec<crate::memory::MemoryResult>,
+    pub selected: usize,
+    /// Search still running
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl MemoryView {
+    /// Zero-based page holding the selected result
+    pub fn page(&self) -> usize {
+        self.selected / MEMORY_PAGE_SIZE
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.results.len().div_ceil(MEMORY_PAGE_SIZE).max(1)
+    }
+}
+
+/// What a command palette entry runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+    /// A remappable keymap action
+    Action(Action),
+    /// A built-in or user-defined slash command, e.g. `/compact`
+    Slash(String),
+}
+
+/// One row of the command palette
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub description: String,
+    /// Keys bound to the entry, e.g. `Ctrl+N`
+    pub keys: Option<String>,
+    pub command: PaletteCommand,
+}
+
+/// State of the Ctrl+P command palette
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub entries: Vec<PaletteEntry>,
+    /// Indices into `entries` matching `query`, best first
+    pub filtered: Vec<usize>,
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// Palette over every keymap action with a description, then built-in
+    /// and user slash commands. `keys` looks up an action's current binding.
+    pub fn new(user_commands: &[UserCommand], keys: impl Fn(Action) -> Option<String>) -> Self {
+        let mut entries: Vec<PaletteEntry> = Action::ALL
+            .iter()
+            .filter_map(|&(action, _)| {
+                action.description().map(|description| PaletteEntry {
+                    label: description.to_string(),
+                    description: String::new(),
+                    keys: keys(action),
+                    command: PaletteCommand::Action(action),
+                })
+            })
+            .collect();
+        entries.extend(SLASH_COMMANDS.iter().map(|cmd| PaletteEntry {
+            label: cmd.name.to_string(),
+            description: cmd.description.to_string(),
+            keys: None,
+            command: PaletteCommand::Slash(cmd.name.to_string()),
+        }));
+        entries.extend(
+            user_commands
+                .iter()
+                .filter(|u| !SLASH_COMMANDS.iter().any(|b| b.name == u.name))
+                .map(|u| PaletteEntry {
+                    label: u.name.clone(),
+                    description: u.description.clone(),
+                    keys: None,
+                    command: PaletteCommand::Slash(u.name.clone()),
+                }),
+        );
+
+        let mut palette = Self {
+            entries,
+            ..Self::default()
+        };
+        palette.refresh();
+        palette
+    }
+
+    /// Re-rank entries for the current query. Label matches outrank
+    /// description-only matches; an empty query keeps the listed order.
+    pub fn refresh(&mut self) {
+        let mut scored: Vec<(u32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                fuzzy_score(&self.query, &entry.label)
+                    .or_else(|| fuzzy_score(&self.query, &entry.description).map(|s| s / 4))
+                    .map(|score| (score, i))
+            })
+            .collect();
+        // Stable sort keeps the listed order within equal scores
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        self.filtered = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.filtered
+            .get(self.selected)
+            .and_then(|&i| self.entries.get(i))
+    }
+}
+
 /// An image file attached to the input (detected from pasted paths)
 #[derive(Debug, Clone)]
 pub struct ImageAttachment {
@@ -177,11 +427,15 @@ pub(crate) const TEXT_EXTENSIONS: &[&str] = &[
 /// A single tool call entry within a grouped display
 #[derive(Debug, Clone)]
 pub struct ToolCallEntry {
+    /// Tool name — empty for calls restored from history
+    pub tool_name: String,
     pub description: String,
     pub success: bool,
     pub details: Option<String>,
     /// Full raw tool input — shown untruncated in expanded view
     pub tool_input: serde_json::Value,
+    /// Latest output lines streamed while the tool is still running
+    pub live_output: Vec<String>,
 }
 
 /// A group of tool calls displayed as a collapsible bullet
@@ -202,6 +456,7 @@ pub struct DisplayMessage {
     pub cost: Option<f64>,
     pub approval: Option<ApprovalData>,
     pub approve_menu: Option<ApproveMenu>,
+    pub variant_menu: Option<VariantMenu>,
     /// Collapsible details (tool output, etc.) — shown when expanded
     pub details: Option<String>,
     /// Whether details are currently expanded
@@ -221,6 +476,7 @@ impl From<Message> for DisplayMessage {
             cost: msg.cost,
             approval: None,
             approve_menu: None,
+            variant_menu: None,
             details: None,
             expanded: false,
             tool_group: None,
@@ -257,6 +513,11 @@ pub struct App {
     pub streaming_response: Option<String>,
     /// Reasoning/thinking content from providers like MiniMax (display-only, cleared on complete)
     pub streaming_reasoning: Option<String>,
+    /// When the first reasoning chunk of the current turn arrived
+    pub reasoning_started_at: Option<std::time::Instant>,
+    /// Frozen thinking time once the answer starts streaming — the reasoning
+    /// block collapses to "Thought for Ns" from then on
+    pub reasoning_elapsed: Option<std::time::Duration>,
     pub error_message: Option<String>,
     /// When error_message was set — used to auto-dismiss after 2.5s
     pub error_message_shown_at: Option<std::time::Instant>,
@@ -282,6 +543,12 @@ pub struct App {
     /// Ctrl+C confirmation state (first clears input, second quits)
     pub(crate) ctrl_c_pending_at: Option<std::time::Instant>,
 
+    /// Destructive command waiting for `y` (`/clear`, Ctrl+K, session delete)
+    pub(crate) pending_destructive: Option<DestructiveAction>,
+
+    /// Last destructive command and when it ran, restorable by `/undo`
+    pub(crate) undo: Option<(UndoAction, std::time::Instant)>,
+
     /// Help/Settings scroll offset
     pub help_scroll_offset: usize,
 
@@ -292,17 +559,31 @@ pub struct App {
     pub approval_auto_session: bool,
     pub approval_auto_always: bool,
 
+    /// `/reload` is waiting for its report
+    pub reload_requested: bool,
+
     /// File picker state
     pub file_picker_files: Vec<std::path::PathBuf>,
     pub file_picker_selected: usize,
     pub file_picker_scroll_offset: usize,
     pub file_picker_current_dir: std::path::PathBuf,
+    /// Fuzzy-find mode: typed query ranks a recursive walk of the current dir
+    pub file_picker_fuzzy: bool,
+    pub file_picker_query: String,
+    /// Files found by the fuzzy-find walk, relative to `file_picker_current_dir`
+    pub file_picker_index: Vec<std::path::PathBuf>,
 
     /// Slash autocomplete state
     pub slash_suggestions_active: bool,
-    /// Indices into SLASH_COMMANDS
+    /// Indices into SLASH_COMMANDS, then user commands, then `template_suggestions`
     pub slash_filtered: Vec<usize>,
     pub slash_selected_index: usize,
+    /// `/load <name>` completions for the current input
+    pub(crate) template_suggestions: Vec<String>,
+    /// `/load` waiting on `{{placeholder}}` values
+    pub(crate) template_fill: Option<TemplateFill>,
+    /// `/login` waiting for the pasted authorization code
+    pub(crate) pending_login: Option<PkceLogin>,
 
     /// Emoji picker state
     pub emoji_picker_active: bool,
@@ -388,13 +669,25 @@ pub struct App {
 
     /// Self-update state
     pub rebuild_status: Option<String>,
+    /// Source changes under review in the restart dialog
+    pub rebuild_diff: Option<crate::brain::SourceDiff>,
+    /// Restart dialog shows the full patch instead of the file list
+    pub rebuild_diff_expanded: bool,
+    pub rebuild_diff_scroll: u16,
+
+    /// /memory search view
+    pub memory_view: Option<MemoryView>,
+
+    /// Ctrl+P command palette
+    pub command_palette: Option<CommandPalette>,
 
     /// Session to resume after restart (set via --session CLI arg)
     pub resume_session_id: Option<Uuid>,
 
     /// Cache of rendered lines per message to avoid re-parsing markdown every frame.
     /// Key: (message_id, content_width). Invalidated on terminal resize.
-    pub render_cache: HashMap<(Uuid, u16), Vec<Line<'static>>>,
+    /// Lines are flagged `true` when they belong to a code block.
+    pub render_cache: HashMap<(Uuid, u16), Vec<(Line<'static>, bool)>>,
 
     /// Mapping from rendered line index → message index (for click-to-copy).
     /// Updated each frame by render_chat.
@@ -403,6 +696,26 @@ pub struct App {
     pub chat_render_scroll: usize,
     /// The top-left Y coordinate of the chat area in the terminal
     pub chat_area_y: u16,
+    /// Clickable regions from the last render (for mouse click-to-select)
+    pub(crate) click_regions: Vec<(Rect, ClickTarget)>,
+    /// Mouse capture is on (`[tui] mouse`)
+    pub mouse_enabled: bool,
+    /// Prose wrap width cap in the chat view (`[tui] max_content_width`)
+    pub max_content_width: Option<usize>,
+    /// Auto-save the input buffer per session (`[tui] save_draft`)
+    pub save_draft: bool,
+    /// Empty-chat greeting (`[tui] greeting`); `None` = default, "" = hidden
+    pub greeting: Option<String>,
+    /// `[agent]` settings; `name` and `emoji` label the agent in the UI
+    pub agent: crate::config::AgentConfig,
+    /// Input as of the last tick, and when it last changed — the draft is
+    /// written once it has been still for `DRAFT_DEBOUNCE`
+    pub(crate) draft_seen: (String, std::time::Instant),
+    /// Input last written to the draft file
+    pub(crate) draft_saved: String,
+    /// Launched with `--safe-mode`: no brain files, tools, channels or BOOT.md,
+    /// and sessions don't switch away from the default provider
+    pub safe_mode: bool,
 
     /// History paging — how many DB messages are hidden above the current view
     pub hidden_older_messages: usize,
@@ -464,6 +777,8 @@ impl App {
             processing_started_at: None,
             streaming_response: None,
             streaming_reasoning: None,
+            reasoning_started_at: None,
+            reasoning_elapsed: None,
             error_message: None,
             error_message_shown_at: None,
             notification: None,
@@ -473,16 +788,25 @@ impl App {
             animation_frame: 0,
             splash_shown_at: Some(std::time::Instant::now()),
             escape_pending_at: None,
+            pending_destructive: None,
+            undo: None,
             ctrl_c_pending_at: None,
             help_scroll_offset: 0,
             approval_auto_session,
             approval_auto_always,
+            reload_requested: false,
             file_picker_files: Vec::new(),
             file_picker_selected: 0,
             file_picker_scroll_offset: 0,
             file_picker_current_dir: std::env::current_dir().unwrap_or_default(),
+            file_picker_fuzzy: false,
+            file_picker_query: String::new(),
+            file_picker_index: Vec::new(),
             slash_suggestions_active: false,
             slash_filtered: Vec::new(),
+            template_suggestions: Vec::new(),
+            template_fill: None,
+            pending_login: None,
             slash_selected_index: 0,
             emoji_picker_active: false,
             emoji_filtered: Vec::new(),
@@ -526,11 +850,25 @@ impl App {
             session_context_cache: HashMap::new(),
             active_tool_group: None,
             rebuild_status: None,
+            rebuild_diff: None,
+            rebuild_diff_expanded: false,
+            rebuild_diff_scroll: 0,
+            memory_view: None,
+            command_palette: None,
             resume_session_id: None,
             render_cache: HashMap::new(),
             chat_line_to_msg: Vec::new(),
             chat_render_scroll: 0,
             chat_area_y: 0,
+            click_regions: Vec::new(),
+            mouse_enabled: true,
+            max_content_width: None,
+            save_draft: true,
+            greeting: None,
+            agent: crate::config::AgentConfig::default(),
+            draft_seen: (String::new(), std::time::Instant::now()),
+            draft_saved: String::new(),
+            safe_mode: false,
             hidden_older_messages: 0,
             oldest_displayed_sequence: 0,
             display_token_count: 0,
@@ -607,45 +945,30 @@ impl App {
             self.mode = AppMode::Chat;
             self.splash_shown_at = None;
             // Send a hidden wake-up message to the agent (not shown in UI)
-            self.processing_sessions.insert(session_id);
-            self.is_processing = true;
-            self.processing_started_at = Some(std::time::Instant::now());
-            let agent_service = self.agent_service.clone();
-            let event_sender = self.event_sender();
-            let token = CancellationToken::new();
-            self.cancel_token = Some(token.clone());
-            tokio::spawn(async move {
+            if !self.safe_mode {
                 let wake_up = "[SYSTEM: You just rebuilt yourself from source and restarted \
                     via exec(). Greet the user, confirm the restart succeeded, and continue \
                     where you left off.]";
-                match agent_service
-                    .send_message_with_tools_and_mode(
-                        session_id,
-                        wake_up.to_string(),
-                        None,
-                        Some(token),
-                    )
-                    .await
-                {
-                    Ok(response) => {
-                        let _ = event_sender.send(TuiEvent::ResponseComplete {
-                            session_id,
-                            response,
-                        });
-                    }
-                    Err(e) => {
-                        let _ = event_sender.send(TuiEvent::Error {
-                            session_id,
-                            message: e.to_string(),
-                        });
-                    }
-                }
-            });
-        } else if let Some(session) = self.session_service.get_most_recent_session().await? {
-            self.load_session(session.id).await?;
+                self.spawn_hidden_turn(session_id, wake_up.to_string());
+            }
         } else {
-            // Create a new session if none exists
-            self.create_new_session().await?;
+            if let Some(session) = self.session_service.get_most_recent_session().await? {
+                self.load_session(session.id).await?;
+            } else {
+                // Create a new session if none exists
+                self.create_new_session().await?;
+            }
+            if !self.safe_mode {
+                self.run_boot_routine().await;
+            }
+        }
+
+        if self.safe_mode {
+            self.push_system_message(
+                "🛟 Safe mode: no brain files, tools, channels or schedulers are loaded. \
+                 Fix what broke in ~/.opencrabs, then restart without --safe-mode."
+                    .to_string(),
+            );
         }
 
         // Load sessions list
@@ -674,6 +997,60 @@ impl App {
         Ok(())
     }
 
+    /// Run BOOT.md as the launch's first agent turn when `[boot] run_on_start` is set.
+    /// Skipped after a /rebuild restart, which sends its own wake-up turn instead.
+    async fn run_boot_routine(&mut self) {
+        let run_on_start = crate::config::Config::load()
+            .map(|c| c.boot.run_on_start)
+            .unwrap_or(false);
+        if !run_on_start {
+            return;
+        }
+        let Some(session_id) = self.current_session.as_ref().map(|s| s.id) else {
+            return;
+        };
+        let content = tokio::fs::read_to_string(self.brain_path.join("BOOT.md"))
+            .await
+            .unwrap_or_default();
+        let Some(prompt) = boot_prompt(&content) else {
+            tracing::debug!("Boot routine skipped — BOOT.md missing or empty");
+            return;
+        };
+        tracing::info!("Running BOOT.md startup routine in session {session_id}");
+        self.spawn_hidden_turn(session_id, prompt);
+    }
+
+    /// Send a message the user didn't type (not shown in UI); the agent's reply
+    /// lands in the chat through the normal `ResponseComplete` flow.
+    fn spawn_hidden_turn(&mut self, session_id: Uuid, message: String) {
+        self.processing_sessions.insert(session_id);
+        self.is_processing = true;
+        self.processing_started_at = Some(std::time::Instant::now());
+        let agent_service = self.agent_service.clone();
+        let event_sender = self.event_sender();
+        let token = CancellationToken::new();
+        self.cancel_token = Some(token.clone());
+        tokio::spawn(async move {
+            match agent_service
+                .send_message_with_tools_and_mode(session_id, message, None, Some(token))
+                .await
+            {
+                Ok(response) => {
+                    let _ = event_sender.send(TuiEvent::ResponseComplete {
+                        session_id,
+                        response,
+                    });
+                }
+                Err(e) => {
+                    let _ = event_sender.send(TuiEvent::Error {
+                        session_id,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        });
+    }
+
     /// Get event handler
     pub fn event_handler(&self) -> &EventHandler {
         &self.event_handler
@@ -778,6 +1155,7 @@ impl App {
                     tool_description: tool_info.tool_description,
                     tool_input: tool_info.tool_input,
                     capabilities: tool_info.capabilities,
+                    forced: tool_info.forced,
                     response_tx,
                     requested_at: std::time::Instant::now(),
                 };
@@ -795,9 +1173,8 @@ impl App {
                     crate::brain::agent::AgentError::Internal("Approval channel closed".to_string())
                 })?;
 
-                // TUI handles "always" internally via approval_auto_session;
-                // return false for always_approve so tool_loop doesn't duplicate it
-                Ok((response.approved, false))
+                // TUI handles "always" internally via approval_auto_session
+                Ok(response.decision)
             })
         });
 
@@ -813,6 +1190,7 @@ impl App {
             .expect("working_directory lock poisoned")
             .clone();
         let brain_path = self.agent_service.brain_path().clone();
+        let live_config = self.agent_service.live_config();
 
         // Create new agent service with new provider — preserve ALL callbacks
         let mut new_agent_service = AgentService::new(provider, context)
@@ -831,6 +1209,10 @@ impl App {
             new_agent_service = new_agent_service.with_brain_path(bp);
         }
 
+        if let Some(rx) = live_config {
+            new_agent_service = new_agent_service.with_live_config(rx);
+        }
+
         // Add system brain if it exists
         if let Some(brain) = system_brain {
             new_agent_service = new_agent_service.with_system_brain(brain);
@@ -867,24 +1249,10 @@ impl App {
                 self.handle_key_event(key_event).await?;
             }
             TuiEvent::MouseScroll(direction) => {
-                if self.mode == AppMode::Chat {
-                    if direction > 0 {
-                        // Scrolling up — disable auto-scroll
-                        self.scroll_offset = self.scroll_offset.saturating_add(3);
-                        self.auto_scroll = false;
-                    } else {
-                        self.scroll_offset = self.scroll_offset.saturating_sub(3);
-                        // Re-enable auto-scroll when back at bottom
-                        if self.scroll_offset == 0 {
-                            self.auto_scroll = true;
-                        }
-                    }
-                }
+                self.handle_mouse_scroll(direction as i32).await?;
             }
-            TuiEvent::MouseClick(_col, row) => {
-                if self.mode == AppMode::Chat {
-                    self.handle_click_select(row);
-                }
+            TuiEvent::MouseClick(col, row) => {
+                self.handle_mouse_click(col, row).await?;
             }
             TuiEvent::MouseRightClick(_col, row) => {
                 if self.mode == AppMode::Chat {
@@ -919,12 +1287,12 @@ impl App {
                             wizard.models_fetching = true;
                             let sender = self.event_sender();
                             tokio::spawn(async move {
-                                let models = super::onboarding::fetch_provider_models(
+                                let result = super::onboarding::try_fetch_provider_models(
                                     provider_idx,
                                     Some(&api_key),
                                 )
                                 .await;
-                                let _ = sender.send(TuiEvent::OnboardingModelsFetched(models));
+                                let _ = sender.send(TuiEvent::OnboardingModelsFetched(result));
                             });
                         }
                     }
@@ -986,6 +1354,9 @@ impl App {
                     } else {
                         self.streaming_reasoning = Some(text);
                     }
+                    if self.reasoning_started_at.is_none() {
+                        self.reasoning_started_at = Some(std::time::Instant::now());
+                    }
                     if self.auto_scroll {
                         self.scroll_offset = 0;
                     }
@@ -1042,6 +1413,19 @@ impl App {
                     wizard.tick_health_check();
                 }
 
+                // Approvals the agent stopped waiting for ([tools] approval_timeout_secs)
+                for msg in &mut self.messages {
+                    if let Some(ref mut approval) = msg.approval
+                        && approval.state == ApprovalState::Pending
+                        && approval.response_tx.is_closed()
+                    {
+                        approval.state = ApprovalState::Denied("timed out".to_string());
+                    }
+                }
+
+                self.tick_draft();
+                self.tick_undo().await;
+
                 // Auto-dismiss error/warning messages after 2.5 seconds
                 if let Some(shown_at) = self.error_message_shown_at
                     && shown_at.elapsed() >= std::time::Duration::from_millis(2500)
@@ -1055,15 +1439,7 @@ impl App {
                     && let Some(shown_at) = self.splash_shown_at
                     && shown_at.elapsed() >= std::time::Duration::from_secs(3)
                 {
-                    self.splash_shown_at = None;
-                    let is_first = super::onboarding::is_first_time();
-                    if self.force_onboard || is_first {
-                        self.force_onboard = false;
-                        self.onboarding = Some(OnboardingWizard::new());
-                        self.switch_mode(AppMode::Onboarding).await?;
-                    } else {
-                        self.switch_mode(AppMode::Chat).await?;
-                    }
+                    self.leave_splash().await?;
                 }
             }
             TuiEvent::ToolApprovalRequested(request) => {
@@ -1089,10 +1465,12 @@ impl App {
                 // Show tool call in progress
                 let desc = Self::format_tool_description(&tool_name, &tool_input);
                 let entry = ToolCallEntry {
+                    tool_name: tool_name.clone(),
                     description: desc,
                     success: true,
                     details: None,
                     tool_input: tool_input.clone(),
+                    live_output: Vec::new(),
                 };
                 if let Some(ref mut group) = self.active_tool_group {
                     group.calls.push(entry);
@@ -1125,7 +1503,7 @@ impl App {
 
                 // Clear streaming response - text is now going to be a permanent message
                 self.streaming_response = None;
-                self.streaming_reasoning = None;
+                self.clear_streaming_reasoning();
                 self.intermediate_text_received = true;
 
                 // Check if there was a queued message that was just processed
@@ -1140,6 +1518,7 @@ impl App {
                         cost: None,
                         approval: None,
                         approve_menu: None,
+                        variant_menu: None,
                         details: Some("queued".to_string()), // Mark as queued
                         expanded: false,
                         tool_group: None,
@@ -1166,6 +1545,7 @@ impl App {
                         cost: None,
                         approval: None,
                         approve_menu: None,
+                        variant_menu: None,
                         details: None,
                         expanded: false,
                         tool_group: Some(group),
@@ -1182,6 +1562,7 @@ impl App {
                     cost: None,
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: reasoning_details,
                     expanded: false,
                     tool_group: None,
@@ -1218,6 +1599,7 @@ impl App {
                     {
                         existing.success = success;
                         existing.details = details.clone();
+                        existing.live_output.clear();
                         true
                     } else {
                         false
@@ -1229,10 +1611,12 @@ impl App {
                 // Fallback: push as new entry if no matching Started entry found
                 if !updated {
                     let entry = ToolCallEntry {
+                        tool_name: tool_name.clone(),
                         description: desc,
                         success,
                         details,
                         tool_input: tool_input.clone(),
+                        live_output: Vec::new(),
                     };
                     if let Some(ref mut group) = self.active_tool_group {
                         group.calls.push(entry);
@@ -1251,6 +1635,22 @@ impl App {
                     self.scroll_offset = 0;
                 }
             }
+            TuiEvent::ToolOutput {
+                session_id, line, ..
+            } if self.is_current_session(session_id) => {
+                if let Some(entry) = self
+                    .active_tool_group
+                    .as_mut()
+                    .and_then(|g| g.calls.iter_mut().rev().find(|c| c.details.is_none()))
+                {
+                    entry.live_output.push(line);
+                    let excess = entry
+                        .live_output
+                        .len()
+                        .saturating_sub(MAX_LIVE_OUTPUT_LINES);
+                    entry.live_output.drain(..excess);
+                }
+            }
             TuiEvent::CompactionSummary {
                 session_id,
                 summary,
@@ -1263,7 +1663,7 @@ impl App {
                 self.display_token_count = 0;
                 // Reset streaming state so post-compaction tool calls render cleanly
                 self.streaming_response = None;
-                self.streaming_reasoning = None;
+                self.clear_streaming_reasoning();
                 self.active_tool_group = None;
                 // Allow post-compaction TokenCountUpdated to set a lower value
                 self.last_input_tokens = None;
@@ -1278,6 +1678,7 @@ impl App {
                     cost: None,
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: None,
                     expanded: false,
                     tool_group: None,
@@ -1293,17 +1694,25 @@ impl App {
                     cost: None,
                     approval: None,
                     approve_menu: None,
+                    variant_menu: None,
                     details: None,
                     expanded: false,
                     tool_group: None,
                 });
                 // auto_scroll stays true — new messages continue below
             }
-            TuiEvent::RestartReady(_status) => {
-                self.rebuild_status = None;
-                // Auto exec() restart — no prompt, no permission needed
-                if let Some(session) = &self.current_session {
+            TuiEvent::RestartReady { status, diff } => {
+                // Self-modified source: let the user review the diff before running it
+                if let Some(diff) = diff.filter(|d| !d.is_empty()) {
+                    self.rebuild_status = Some(status);
+                    self.rebuild_diff = Some(diff);
+                    self.rebuild_diff_expanded = false;
+                    self.rebuild_diff_scroll = 0;
+                    self.switch_mode(AppMode::RestartPending).await?;
+                } else if let Some(session) = &self.current_session {
+                    // No source changes: auto exec() restart, no prompt
                     let session_id = session.id;
+                    self.rebuild_status = None;
                     match SelfUpdater::auto_detect() {
                         Ok(updater) => {
                             if let Err(e) = updater.restart(session_id) {
@@ -1319,13 +1728,18 @@ impl App {
                     }
                 }
             }
-            TuiEvent::ConfigReloaded => {
+            TuiEvent::ConfigReloaded(report) => {
                 // Refresh commands autocomplete
                 self.reload_user_commands();
                 // Refresh approval policy
                 (self.approval_auto_session, self.approval_auto_always) =
                     Self::read_approval_policy_from_config();
                 tracing::info!("Config reloaded — refreshed commands, approval policy");
+                // Always answer /reload; otherwise only speak up when something changed
+                let requested = std::mem::take(&mut self.reload_requested);
+                if requested || report != crate::config::ReloadReport::default() {
+                    self.push_system_message(report.summary());
+                }
             }
             TuiEvent::TokenCountUpdated { session_id, count }
                 if self.is_current_session(session_id) =>
@@ -1351,14 +1765,85 @@ impl App {
             {
                 self.streaming_output_tokens = tokens;
             }
+            TuiEvent::AutoContinued {
+                session_id,
+                attempt,
+            } if self.is_current_session(session_id) => {
+                self.notification = Some(format!(
+                    "↻ Output limit reached — continuing reply ({attempt})"
+                ));
+                self.notification_shown_at = Some(std::time::Instant::now());
+            }
+            TuiEvent::StreamResumed {
+                session_id,
+                attempt,
+            } if self.is_current_session(session_id) => {
+                self.notification =
+                    Some(format!("↻ Connection dropped — resuming reply ({attempt})"));
+                self.notification_shown_at = Some(std::time::Instant::now());
+            }
+            TuiEvent::HistoryTrimmed {
+                session_id,
+                dropped,
+            } if self.is_current_session(session_id) => {
+                self.notification = Some(format!(
+                    "✂ {dropped} older message(s) left out to fit the context window"
+                ));
+                self.notification_shown_at = Some(std::time::Instant::now());
+            }
             // Silently ignore events for background sessions (already handled above for ResponseComplete/Error)
             TuiEvent::ToolCallStarted { .. }
             | TuiEvent::ToolCallCompleted { .. }
+            | TuiEvent::ToolOutput { .. }
             | TuiEvent::IntermediateText { .. }
             | TuiEvent::CompactionSummary { .. }
             | TuiEvent::TokenCountUpdated { .. }
-            | TuiEvent::StreamingOutputTokens { .. } => {}
+            | TuiEvent::StreamingOutputTokens { .. }
+            | TuiEvent::AutoContinued { .. }
+            | TuiEvent::StreamResumed { .. }
+            | TuiEvent::HistoryTrimmed { .. } => {}
+
+            TuiEvent::MemoryResults { query, results } => {
+                if let Some(view) = self.memory_view.as_mut()
+                    && view.query == query
+                {
+                    view.loading = false;
+                    match results {
+                        Ok(results) => view.results = results,
+                        Err(e) => view.error = Some(e),
+                    }
+                }
+            }
 
+            TuiEvent::VariantsReady {
+                session_id,
+                anchor_id,
+                variants,
+            } => {
+                if self.is_current_session(session_id) {
+                    self.messages.push(DisplayMessage {
+                        id: Uuid::new_v4(),
+                        role: "system".to_string(),
+                        content: String::new(),
+                        timestamp: chrono::Utc::now(),
+                        token_count: None,
+                        cost: None,
+                        approval: None,
+                        approve_menu: None,
+                        variant_menu: Some(VariantMenu {
+                            session_id,
+                            anchor_id,
+                            variants,
+                            selected_option: 0,
+                            state: VariantMenuState::Pending,
+                        }),
+                        details: None,
+                        expanded: false,
+                        tool_group: None,
+                    });
+                    self.scroll_offset = 0;
+                }
+            }
             TuiEvent::SessionUpdated(session_id) => {
                 // A remote channel completed an agent response. Only react when the TUI
                 // itself is NOT processing this session (to avoid conflicting with the
@@ -1372,16 +1857,13 @@ impl App {
                 }
             }
 
-            TuiEvent::OnboardingModelsFetched(models) => {
+            TuiEvent::OnboardingModelsFetched(result) => {
                 if let Some(ref mut wizard) = self.onboarding {
-                    wizard.models_fetching = false;
-                    if !models.is_empty() {
-                        wizard.fetched_models = models;
-                        wizard.selected_model = 0;
-                    }
+                    wizard.apply_fetched_models(result);
                 }
             }
             TuiEvent::ModelSelectorModelsFetched(models) => {
+                let models = self.with_custom_model_choices(models);
                 if self.mode == AppMode::ModelSelector && !models.is_empty() {
                     // Pre-select session model or config model
                     let current_model = self
@@ -1389,7 +1871,16 @@ impl App {
                         .as_ref()
                         .and_then(|s| s.model.as_deref())
                         .unwrap_or(&self.default_model_name);
-                    let selected = models.iter().position(|m| m == current_model).unwrap_or(0);
+                    // A session on a custom provider matches its custom:<name>/<model> entry
+                    let current_choice = self
+                        .current_session
+                        .as_ref()
+                        .and_then(|s| s.provider_name.as_deref())
+                        .map(|p| format!("custom:{}/{}", p, current_model));
+                    let selected = models
+                        .iter()
+                        .position(|m| m == current_model || Some(m) == current_choice.as_ref())
+                        .unwrap_or(0);
                     self.model_selector_models = models;
                     self.model_selector_selected = selected;
                     self.model_selector_filter.clear();
@@ -1425,11 +1916,31 @@ impl App {
             }
             TuiEvent::SudoPasswordRequested(request) => {
                 self.sudo_pending = Some(request);
-                self.sudo_input.clear();
+                zeroize::Zeroize::zeroize(&mut self.sudo_input);
             }
             TuiEvent::SystemMessage(msg) => {
                 self.push_system_message(msg);
             }
+            TuiEvent::InsertInput(text) => {
+                self.input_buffer.insert_str(self.cursor_position, &text);
+                self.cursor_position += text.len();
+                self.update_slash_suggestions();
+            }
+            TuiEvent::LoginCompleted { provider, error } => match error {
+                Some(e) => self.push_system_message(format!("Login to {} failed: {}", provider, e)),
+                None => {
+                    // Pick up the new token without a restart
+                    if let Err(e) = self.rebuild_agent_service().await {
+                        tracing::warn!("Failed to rebuild agent service after login: {}", e);
+                    }
+                    self.push_system_message(format!(
+                        "Logged in to {} — requests now use your subscription and the token \
+                         refreshes automatically. Switch to it with /models if another provider \
+                         is active.",
+                        provider
+                    ));
+                }
+            },
             TuiEvent::FocusGained | TuiEvent::FocusLost => {
                 // Handled by the event loop for tick coalescing
             }
@@ -1444,6 +1955,49 @@ impl App {
         Ok(())
     }
 
+    /// Leave the splash screen: onboarding on a true first run (or
+    /// `--onboard`), otherwise chat — with a repair prompt when config.toml
+    /// fails to load, or the model selector when no provider is enabled.
+    async fn leave_splash(&mut self) -> Result<()> {
+        use super::onboarding::SetupState;
+
+        self.splash_shown_at = None;
+        let state = super::onboarding::setup_state();
+        tracing::debug!(
+            "[Splash] force_onboard={}, setup_state={:?}",
+            self.force_onboard,
+            state
+        );
+        if std::mem::take(&mut self.force_onboard) || state == SetupState::FirstRun {
+            tracing::info!("[Splash] Starting onboarding wizard");
+            self.onboarding = Some(OnboardingWizard::new());
+            return self.switch_mode(AppMode::Onboarding).await;
+        }
+
+        tracing::debug!("[Splash] Skipping onboarding, going to Chat");
+        self.switch_mode(AppMode::Chat).await?;
+        match state {
+            SetupState::ConfigInvalid(error) => {
+                self.push_system_message(format!(
+                    "config.toml could not be loaded: {}\n\
+                     Your settings were left untouched. Fix the file and run /reload, \
+                     start with --safe-mode for help, or run /onboard to start over.",
+                    error
+                ));
+            }
+            SetupState::NoProviderEnabled => {
+                self.push_system_message(
+                    "No provider is enabled in config.toml. Pick one to enable it — \
+                     your other settings stay as they are."
+                        .to_string(),
+                );
+                self.open_model_selector().await;
+            }
+            SetupState::FirstRun | SetupState::Ready => {}
+        }
+        Ok(())
+    }
+
     /// Handle keyboard input
     async fn handle_key_event(&mut self, event: crossterm::event::KeyEvent) -> Result<()> {
         use super::events::keys;
@@ -1468,7 +2022,7 @@ impl App {
                             .response_tx
                             .send(SudoPasswordResponse { password: None });
                     }
-                    self.sudo_input.clear();
+                    zeroize::Zeroize::zeroize(&mut self.sudo_input);
                 }
                 KeyCode::Backspace => {
                     self.sudo_input.pop();
@@ -1481,6 +2035,13 @@ impl App {
             return Ok(());
         }
 
+        // A pending destructive command takes the next key: `y` confirms,
+        // anything else cancels
+        if self.pending_destructive.is_some() {
+            let confirmed = matches!(event.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+            return self.answer_destructive(confirmed).await;
+        }
+
         // Ctrl+C: first press clears input, second press (within 3s) quits
         if keys::is_quit(&event) {
             if let Some(pending_at) = self.ctrl_c_pending_at
@@ -1621,7 +2182,31 @@ impl App {
         }
 
         if keys::is_clear_session(&event) {
-            self.clear_session().await?;
+            if let Some(session_id) = self.current_session.as_ref().map(|s| s.id) {
+                self.request_destructive(DestructiveAction::ClearSession { session_id });
+            }
+            return Ok(());
+        }
+
+        if keys::is_help(&event) {
+            self.mode = AppMode::Help;
+            return Ok(());
+        }
+
+        if keys::is_model_selector(&event) {
+            self.open_model_selector().await;
+            return Ok(());
+        }
+
+        if keys::is_command_palette(&event)
+            && !matches!(self.mode, AppMode::Splash | AppMode::Onboarding)
+        {
+            if self.mode == AppMode::CommandPalette {
+                self.command_palette = None;
+                self.switch_mode(AppMode::Chat).await?;
+            } else {
+                self.open_command_palette().await?;
+            }
             return Ok(());
         }
 
@@ -1633,23 +2218,7 @@ impl App {
                 if let Some(shown_at) = self.splash_shown_at
                     && shown_at.elapsed() >= std::time::Duration::from_secs(3)
                 {
-                    self.splash_shown_at = None;
-                    // Check if onboarding should be shown
-                    let is_first = super::onboarding::is_first_time();
-                    tracing::debug!(
-                        "[Splash] force_onboard={}, is_first_time={}",
-                        self.force_onboard,
-                        is_first
-                    );
-                    if self.force_onboard || is_first {
-                        self.force_onboard = false;
-                        tracing::info!("[Splash] Starting onboarding wizard");
-                        self.onboarding = Some(OnboardingWizard::new());
-                        self.switch_mode(AppMode::Onboarding).await?;
-                    } else {
-                        tracing::debug!("[Splash] Skipping onboarding, going to Chat");
-                        self.switch_mode(AppMode::Chat).await?;
-                    }
+                    self.leave_splash().await?;
                 }
                 // If not enough time has elapsed, ignore the key press
             }
@@ -1657,6 +2226,8 @@ impl App {
             AppMode::Sessions => self.handle_sessions_key(event).await?,
             AppMode::FilePicker => self.handle_file_picker_key(event).await?,
             AppMode::DirectoryPicker => self.handle_directory_picker_key(event).await?,
+            AppMode::MemorySearch => self.handle_memory_search_key(event).await?,
+            AppMode::CommandPalette => self.handle_command_palette_key(event).await?,
             AppMode::ModelSelector => self.handle_model_selector_key(event).await?,
             AppMode::UsageDialog => {
                 if keys::is_cancel(&event) || keys::is_enter(&event) {
@@ -1666,7 +2237,22 @@ impl App {
             AppMode::RestartPending => {
                 if keys::is_cancel(&event) {
                     self.rebuild_status = None;
+                    self.rebuild_diff = None;
+                    self.push_system_message(
+                        "Restart cancelled — the new binary is built but not running.".to_string(),
+                    );
                     self.switch_mode(AppMode::Chat).await?;
+                } else if event.code == KeyCode::Char('d') && self.rebuild_diff.is_some() {
+                    self.rebuild_diff_expanded = !self.rebuild_diff_expanded;
+                    self.rebuild_diff_scroll = 0;
+                } else if keys::is_up(&event) {
+                    self.rebuild_diff_scroll = self.rebuild_diff_scroll.saturating_sub(1);
+                } else if keys::is_down(&event) {
+                    self.rebuild_diff_scroll = self.rebuild_diff_scroll.saturating_add(1);
+                } else if keys::is_page_up(&event) {
+                    self.rebuild_diff_scroll = self.rebuild_diff_scroll.saturating_sub(20);
+                } else if keys::is_page_down(&event) {
+                    self.rebuild_diff_scroll = self.rebuild_diff_scroll.saturating_add(20);
                 } else if keys::is_enter(&event) {
                     // Perform the restart
                     if let Some(session) = &self.current_session {
@@ -1708,7 +2294,7 @@ impl App {
         self.is_processing = false;
         self.processing_started_at = None;
         self.streaming_response = None;
-        self.streaming_reasoning = None;
+        self.clear_streaming_reasoning();
         self.cancel_token = None;
         self.escape_pending_at = None;
         // Preserve context token count from real-time updates if we never got a complete response
@@ -1722,7 +2308,7 @@ impl App {
             {
                 let _ = approval.response_tx.send(ToolApprovalResponse {
                     request_id: approval.request_id,
-                    approved: false,
+                    decision: ApprovalDecision::Deny,
                     reason: Some("Error occurred".to_string()),
                 });
                 approval.state = ApprovalState::Denied("Error occurred".to_string());
@@ -1740,6 +2326,7 @@ impl App {
                 cost: None,
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: None,
                 expanded: false,
                 tool_group: Some(group),
@@ -1796,10 +2383,10 @@ impl App {
         );
 
         // Auto-approve silently if policy allows
-        if self.approval_auto_always || self.approval_auto_session {
+        if !request.forced && (self.approval_auto_always || self.approval_auto_session) {
             let response = ToolApprovalResponse {
                 request_id: request.request_id,
-                approved: true,
+                decision: ApprovalDecision::Approve,
                 reason: None,
             };
             let _ = request.response_tx.send(response.clone());
@@ -1811,7 +2398,7 @@ impl App {
 
         // Background session approval — auto-approve (user can't interact with it)
         // They'll see the results when they switch to that session
-        if !is_current {
+        if !is_current && !request.forced {
             tracing::info!(
                 "[APPROVAL] Auto-approving background session {} tool '{}'",
                 request.session_id,
@@ -1819,7 +2406,7 @@ impl App {
             );
             let response = ToolApprovalResponse {
                 request_id: request.request_id,
-                approved: true,
+                decision: ApprovalDecision::Approve,
                 reason: Some("Auto-approved (background session)".to_string()),
             };
             let _ = request.response_tx.send(response.clone());
@@ -1836,7 +2423,7 @@ impl App {
             {
                 let _ = approval.response_tx.send(ToolApprovalResponse {
                     request_id: approval.request_id,
-                    approved: false,
+                    decision: ApprovalDecision::Deny,
                     reason: Some("Superseded by new request".to_string()),
                 });
                 approval.state = ApprovalState::Denied("Superseded by new request".to_string());
@@ -1857,6 +2444,7 @@ impl App {
                 cost: None,
                 approval: None,
                 approve_menu: None,
+                variant_menu: None,
                 details: None,
                 expanded: false,
                 tool_group: None,
@@ -1884,6 +2472,7 @@ impl App {
                 show_details: false,
             }),
             approve_menu: None,
+            variant_menu: None,
             details: None,
             expanded: false,
             tool_group: None,
@@ -1918,48 +2507,55 @@ impl App {
     /// Update slash command autocomplete suggestions (built-in + user-defined)
     pub(crate) fn update_slash_suggestions(&mut self) {
         let input = self.input_buffer.trim_start();
-        if input.starts_with('/') && !input.contains(' ') && !input.is_empty() {
-            let prefix = input.to_lowercase();
+        self.template_suggestions.clear();
+        if let Some(partial) = input.strip_prefix("/load ")
+            && !partial.contains(char::is_whitespace)
+        {
+            // `/load <name>` — complete saved template names
+            let mut scored: Vec<(u32, String)> = prompts::list(&prompts::prompts_dir())
+                .into_iter()
+                .filter_map(|name| fuzzy_score(partial, &name).map(|score| (score, name)))
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            self.template_suggestions = scored
+                .into_iter()
+                .map(|(_, name)| format!("/load {name}"))
+                .collect();
 
-            // Built-in commands: indices 0..SLASH_COMMANDS.len()
-            self.slash_filtered = SLASH_COMMANDS
+            let base = SLASH_COMMANDS.len() + self.user_commands.len();
+            self.slash_filtered = (base..base + self.template_suggestions.len()).collect();
+            self.slash_suggestions_active = !self.slash_filtered.is_empty();
+            if self.slash_selected_index >= self.slash_filtered.len() {
+                self.slash_selected_index = 0;
+            }
+        } else if input.starts_with('/') && !input.contains(' ') && !input.is_empty() {
+            // Fuzzy subsequence match — `/ses` finds `/sessions`, prefix matches rank first
+            let mut scored: Vec<(u32, usize)> = SLASH_COMMANDS
                 .iter()
                 .enumerate()
-                .filter(|(_, cmd)| cmd.name.starts_with(&prefix))
-                .map(|(i, _)| i)
+                .filter_map(|(i, cmd)| fuzzy_score(input, cmd.name).map(|score| (score, i)))
                 .collect();
 
             // User-defined commands: indices starting at SLASH_COMMANDS.len()
             // Skip user commands that shadow a built-in name
             let base = SLASH_COMMANDS.len();
             for (i, ucmd) in self.user_commands.iter().enumerate() {
-                if ucmd.name.to_lowercase().starts_with(&prefix)
-                    && !SLASH_COMMANDS.iter().any(|b| b.name == ucmd.name)
+                if !SLASH_COMMANDS.iter().any(|b| b.name == ucmd.name)
+                    && let Some(score) = fuzzy_score(input, &ucmd.name)
                 {
-                    self.slash_filtered.push(base + i);
+                    scored.push((score, base + i));
                 }
             }
 
-            // Sort suggestions alphabetically by command name
-            self.slash_filtered.sort_by(|&a, &b| {
-                let name_a = if a < SLASH_COMMANDS.len() {
-                    SLASH_COMMANDS[a].name
-                } else {
-                    self.user_commands
-                        .get(a - SLASH_COMMANDS.len())
-                        .map(|c| c.name.as_str())
-                        .unwrap_or("")
-                };
-                let name_b = if b < SLASH_COMMANDS.len() {
-                    SLASH_COMMANDS[b].name
-                } else {
-                    self.user_commands
-                        .get(b - SLASH_COMMANDS.len())
-                        .map(|c| c.name.as_str())
+            // Best match first, ties alphabetically by command name
+            scored.sort_by(|&(score_a, a), &(score_b, b)| {
+                score_b.cmp(&score_a).then_with(|| {
+                    self.slash_command_name(a)
                         .unwrap_or("")
-                };
-                name_a.cmp(name_b)
+                        .cmp(self.slash_command_name(b).unwrap_or(""))
+                })
             });
+            self.slash_filtered = scored.into_iter().map(|(_, i)| i).collect();
 
             self.slash_suggestions_active = !self.slash_filtered.is_empty();
             // Clamp selected index
@@ -1974,25 +2570,35 @@ impl App {
     }
 
     /// Get the name of a slash command by its combined index
-    /// (built-in indices 0..N, user command indices N..)
+    /// (built-in indices 0..N, user command indices N..N+U, then `/load` completions)
     pub fn slash_command_name(&self, index: usize) -> Option<&str> {
-        if index < SLASH_COMMANDS.len() {
+        let user_base = SLASH_COMMANDS.len();
+        let template_base = user_base + self.user_commands.len();
+        if index < user_base {
             Some(SLASH_COMMANDS[index].name)
-        } else {
+        } else if index < template_base {
             self.user_commands
-                .get(index - SLASH_COMMANDS.len())
+                .get(index - user_base)
                 .map(|c| c.name.as_str())
+        } else {
+            self.template_suggestions
+                .get(index - template_base)
+                .map(String::as_str)
         }
     }
 
     /// Get the description of a slash command by its combined index
     pub fn slash_command_description(&self, index: usize) -> Option<&str> {
-        if index < SLASH_COMMANDS.len() {
+        let user_base = SLASH_COMMANDS.len();
+        let template_base = user_base + self.user_commands.len();
+        if index < user_base {
             Some(SLASH_COMMANDS[index].description)
-        } else {
+        } else if index < template_base {
             self.user_commands
-                .get(index - SLASH_COMMANDS.len())
+                .get(index - user_base)
                 .map(|c| c.description.as_str())
+        } else {
+            (index - template_base < self.template_suggestions.len()).then_some("Prompt template")
         }
     }
 
@@ -2053,10 +2659,84 @@ impl App {
     }
 }
 
+/// Wrap BOOT.md in the startup-turn instruction. `None` when the file is blank.
+fn boot_prompt(content: &str) -> Option<String> {
+    let content = content.trim();
+    if content.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "[SYSTEM: OpenCrabs just started. Run your startup routine from BOOT.md below. \
+         Your reply is the first message the user sees this launch.]\n\n{content}"
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_command_palette_lists_actions_and_commands() {
+        let user = vec![UserCommand {
+            name: "/deploy".to_string(),
+            description: "Ship it".to_string(),
+            action: "prompt".to_string(),
+            prompt: "Deploy".to_string(),
+        }];
+        let palette = CommandPalette::new(&user, |a| a.default_label().map(str::to_string));
+
+        // Actions without a description (submit, scroll, …) are left out
+        assert!(palette.entries.iter().all(|e| !matches!(
+            e.command,
+            PaletteCommand::Action(Action::Submit | Action::CommandPalette)
+        )));
+        let new_session = palette
+            .entries
+            .iter()
+            .find(|e| e.command == PaletteCommand::Action(Action::NewSession))
+            .unwrap();
+        assert_eq!(new_session.keys.as_deref(), Some("Ctrl+N"));
+        assert!(palette.entries.iter().any(|e| e.label == "/compact"));
+        assert!(palette.entries.iter().any(|e| e.label == "/deploy"));
+        assert_eq!(palette.filtered.len(), palette.entries.len());
+    }
+
+    #[test]
+    fn test_command_palette_filters_fuzzily() {
+        let mut palette = CommandPalette::new(&[], |_| None);
+        palette.query = "/cpt".to_string();
+        palette.refresh();
+        assert_eq!(palette.selected_entry().unwrap().label, "/compact");
+
+        // Description matches still show up, below label matches
+        palette.query = "session".to_string();
+        palette.refresh();
+        assert_eq!(
+            palette.selected_entry().unwrap().command,
+            PaletteCommand::Slash("/sessions".to_string())
+        );
+        assert!(
+            palette
+                .filtered
+                .iter()
+                .any(|&i| palette.entries[i].command == PaletteCommand::Action(Action::NewSession))
+        );
+
+        palette.query = "zzzz".to_string();
+        palette.refresh();
+        assert!(palette.selected_entry().is_none());
+    }
+
+    #[test]
+    fn test_boot_prompt() {
+        assert!(boot_prompt("").is_none());
+        assert!(boot_prompt("  \n\n ").is_none());
+
+        let prompt = boot_prompt("\nGreet the user and list open TODOs.\n").unwrap();
+        assert!(prompt.starts_with("[SYSTEM: OpenCrabs just started."));
+        assert!(prompt.ends_with("\n\nGreet the user and list open TODOs."));
+    }
+
     #[test]
     fn test_display_message_from_db_message() {
         let msg = Message {
@@ -2074,4 +2754,85 @@ mod tests {
         assert_eq!(display_msg.role, "user");
         assert_eq!(display_msg.content, "Hello");
     }
+
+    #[test]
+    fn test_format_tools_table() {
+        use crate::brain::tools::{ToolCapability, ToolInfo};
+
+        let tools = vec![
+            ToolInfo {
+                name: "bash".to_string(),
+                description: "Run a shell command".to_string(),
+                capabilities: vec![ToolCapability::ExecuteShell],
+                requires_approval: true,
+                input_schema: serde_json::json!({}),
+            },
+            ToolInfo {
+                name: "read_file".to_string(),
+                description: "Read a file".to_string(),
+                capabilities: vec![ToolCapability::ReadFiles],
+                requires_approval: false,
+                input_schema: serde_json::json!({}),
+            },
+        ];
+
+        let table = App::format_tools_table(&tools);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "2 tools registered (1 require approval)");
+        assert!(lines[2].starts_with("NAME"));
+        assert!(lines[3].starts_with("bash "));
+        assert!(lines[3].contains("yes"));
+        assert!(lines[3].contains("ExecuteShell"));
+        assert!(lines[4].starts_with("read_file"));
+        assert!(lines[4].contains("no"));
+    }
+
+    #[test]
+    fn test_format_direct_tool_result() {
+        use crate::brain::tools::{ToolError, ToolResult};
+
+        let ok = App::format_direct_tool_result("echo", Ok(ToolResult::success("hi".to_string())));
+        assert_eq!(ok, "🔧 echo → success\nhi");
+
+        let failed =
+            App::format_direct_tool_result("bash", Ok(ToolResult::error("boom".to_string())));
+        assert!(failed.starts_with("🔧 bash → failed"));
+        assert!(failed.contains("error: boom"));
+
+        let err = App::format_direct_tool_result(
+            "nope",
+            Err(ToolError::InvalidInput("missing path".to_string())),
+        );
+        assert!(err.starts_with("🔧 nope → error:"));
+        assert!(err.contains("missing path"));
+    }
+
+    #[test]
+    fn test_explain_error_prompt() {
+        let call = ToolCallEntry {
+            tool_name: "bash".to_string(),
+            description: "bash: cargo bild".to_string(),
+            success: false,
+            details: Some("error: no such command: `bild`".to_string()),
+            tool_input: serde_json::json!({"command": "cargo bild"}),
+            live_output: Vec::new(),
+        };
+        let prompt = App::explain_error_prompt(&call);
+        assert!(prompt.starts_with("The `bash` tool call failed."));
+        assert!(prompt.contains("suggest a fix"));
+        assert!(prompt.contains("\"command\": \"cargo bild\""));
+        assert!(prompt.contains("no such command: `bild`"));
+
+        // Restored from history: no name or input, fall back to the description
+        let restored = ToolCallEntry {
+            tool_name: String::new(),
+            tool_input: serde_json::Value::Null,
+            details: None,
+            ..call
+        };
+        let prompt = App::explain_error_prompt(&restored);
+        assert!(prompt.starts_with("The `bash: cargo bild` tool call failed."));
+        assert!(!prompt.contains("Input:"));
+        assert!(prompt.contains("(no error output)"));
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Handles user input and application events for the terminal interface.
 
-use crate::brain::agent::AgentResponse;
+use crate::brain::agent::{AgentResponse, ApprovalDecision};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde_json::Value;
 use tokio::sync::mpsc;
@@ -90,6 +90,13 @@ pub enum TuiEvent {
         summary: String,
     },
 
+    /// A line of live output from a running tool
+    ToolOutput {
+        session_id: Uuid,
+        tool_name: String,
+        line: String,
+    },
+
     /// Intermediate text the agent sent between tool call batches
     IntermediateText {
         session_id: Uuid,
@@ -100,11 +107,29 @@ pub enum TuiEvent {
     /// Context was auto-compacted — show the summary to the user
     CompactionSummary { session_id: Uuid, summary: String },
 
-    /// Build completed — offer restart to the user
-    RestartReady(String), // global, not per-session
+    /// Build completed — offer restart to the user (global, not per-session).
+    /// A non-empty `diff` is shown for review before restarting.
+    RestartReady {
+        status: String,
+        diff: Option<crate::brain::SourceDiff>,
+    },
+
+    /// Configuration was reloaded (e.g. after config_tool write, /reload or SIGHUP)
+    ConfigReloaded(crate::config::ReloadReport),
 
-    /// Configuration was reloaded (e.g. after config_tool write)
-    ConfigReloaded,
+    /// `/memory` search finished
+    MemoryResults {
+        query: String,
+        results: Result<Vec<crate::memory::MemoryResult>, String>,
+    },
+
+    /// `/variants` finished — offer the alternative replies in a picker
+    VariantsReady {
+        session_id: Uuid,
+        /// The user message the variants answer
+        anchor_id: Uuid,
+        variants: Vec<String>,
+    },
 
     /// Real-time token count update from the agent loop
     TokenCountUpdated { session_id: Uuid, count: usize },
@@ -112,8 +137,17 @@ pub enum TuiEvent {
     /// Streaming output token count (per-response, counted via tiktoken)
     StreamingOutputTokens { session_id: Uuid, tokens: u32 },
 
+    /// Reply hit the output token limit and is being continued
+    AutoContinued { session_id: Uuid, attempt: u32 },
+
+    /// The stream dropped mid-reply and the partial reply is being resumed
+    StreamResumed { session_id: Uuid, attempt: u32 },
+
+    /// Older messages were left out of the request to fit the context window
+    HistoryTrimmed { session_id: Uuid, dropped: usize },
+
     /// Onboarding wizard received fetched model list from provider API
-    OnboardingModelsFetched(Vec<String>),
+    OnboardingModelsFetched(Result<Vec<String>, crate::tui::onboarding::FetchError>),
 
     /// Model selector (/models) received fetched model list after paste
     ModelSelectorModelsFetched(Vec<String>),
@@ -128,6 +162,15 @@ pub enum TuiEvent {
     /// A system message to display in chat
     SystemMessage(String),
 
+    /// Insert text into the input box at the cursor (e.g. a loaded prompt template)
+    InsertInput(String),
+
+    /// `/login` finished exchanging the authorization code (`error` on failure)
+    LoginCompleted {
+        provider: String,
+        error: Option<String>,
+    },
+
     /// Channel test message result during onboarding
     ChannelTestResult {
         channel: String,
@@ -135,18 +178,19 @@ pub enum TuiEvent {
         error: Option<String>,
     },
 
-    /// Sudo password requested by bash tool
+    /// Sudo password requested by the bash or shell tool
     SudoPasswordRequested(SudoPasswordRequest),
 
     /// Reasoning/thinking content chunk from providers like MiniMax (display-only)
     ReasoningChunk { session_id: Uuid, text: String },
 
-    /// A remote channel (Telegram, WhatsApp, Discord, Slack) completed an agent
-    /// response — the TUI should refresh if it's the current session.
+    /// A remote channel (Telegram, WhatsApp, Discord, Slack) wrote to a session —
+    /// the user's message, a tool iteration or the final response. The TUI
+    /// should refresh if it's the current session.
     SessionUpdated(Uuid),
 }
 
-/// Sudo password request from the bash tool
+/// Sudo password request from the bash or shell tool
 #[derive(Debug)]
 pub struct SudoPasswordRequest {
     /// Unique ID for this request
@@ -169,12 +213,21 @@ impl Clone for SudoPasswordRequest {
 }
 
 /// Sudo password response from the TUI
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SudoPasswordResponse {
     /// The password (None if cancelled by user)
     pub password: Option<String>,
 }
 
+// Manual Debug — the password must never reach logs
+impl std::fmt::Debug for SudoPasswordResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SudoPasswordResponse")
+            .field("password", &self.password.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
+}
+
 /// Tool approval request details
 #[derive(Debug, Clone)]
 pub struct ToolApprovalRequest {
@@ -196,6 +249,9 @@ pub struct ToolApprovalRequest {
     /// Tool capabilities
     pub capabilities: Vec<String>,
 
+    /// Listed in `[tools] require_approval` — never auto-approved
+    pub forced: bool,
+
     /// Channel to send response back
     pub response_tx: mpsc::UnboundedSender<ToolApprovalResponse>,
 
@@ -216,8 +272,9 @@ pub struct ToolApprovalResponse {
     /// Request ID this is responding to
     pub request_id: Uuid,
 
-    /// Whether the user approved
-    pub approved: bool,
+    /// The user's answer. "Always" is tracked by the TUI itself, so this is
+    /// never `ApproveAll`
+    pub decision: ApprovalDecision,
 
     /// Optional reason for denial
     pub reason: Option<String>,
@@ -246,6 +303,10 @@ pub enum AppMode {
     RestartPending,
     /// Directory picker dialog (triggered by /cd)
     DirectoryPicker,
+    /// Memory search results (triggered by /memory)
+    MemorySearch,
+    /// Command palette (triggered by Ctrl+P)
+    CommandPalette,
     /// Onboarding wizard
     Onboarding,
 }
@@ -374,32 +435,84 @@ pub fn key_matches(event: &KeyEvent, code: KeyCode, modifiers: KeyModifiers) ->
 /// Common key bindings
 pub mod keys {
     use super::*;
+    use crate::tui::keymap::{Action, KeyMap};
+    use once_cell::sync::Lazy;
+    use std::sync::RwLock;
+
+    /// Active key map — built-in bindings until `set_keymap` is called
+    static KEYMAP: Lazy<RwLock<KeyMap>> = Lazy::new(|| RwLock::new(KeyMap::default()));
+
+    /// Install the key map built from `[keybindings]`
+    pub fn set_keymap(map: KeyMap) {
+        *KEYMAP.write().unwrap_or_else(|e| e.into_inner()) = map;
+    }
+
+    fn bound(action: Action, event: &KeyEvent) -> bool {
+        KEYMAP
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .matches(action, event)
+    }
+
+    /// Keys currently bound to `action`, for display
+    pub fn binding_label(action: Action) -> Option<String> {
+        KEYMAP
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .label(action)
+    }
 
     /// Ctrl+C - Quit
     pub fn is_quit(event: &KeyEvent) -> bool {
         key_matches(event, KeyCode::Char('c'), KeyModifiers::CONTROL)
     }
 
-    /// Ctrl+N - New session
+    /// Ctrl+N - New session (remappable: `new_session`)
     pub fn is_new_session(event: &KeyEvent) -> bool {
-        key_matches(event, KeyCode::Char('n'), KeyModifiers::CONTROL)
+        bound(Action::NewSession, event)
     }
 
-    /// Ctrl+L - List sessions
+    /// Ctrl+L - List sessions (remappable: `sessions`)
     pub fn is_list_sessions(event: &KeyEvent) -> bool {
-        key_matches(event, KeyCode::Char('l'), KeyModifiers::CONTROL)
+        bound(Action::Sessions, event)
     }
 
-    /// Ctrl+K - Clear current session
+    /// Ctrl+K - Clear current session (remappable: `clear_session`)
     pub fn is_clear_session(event: &KeyEvent) -> bool {
-        key_matches(event, KeyCode::Char('k'), KeyModifiers::CONTROL)
+        bound(Action::ClearSession, event)
+    }
+
+    /// Open help — unbound unless set via `[keybindings] help`
+    pub fn is_help(event: &KeyEvent) -> bool {
+        bound(Action::Help, event)
+    }
+
+    /// Open the model selector — unbound unless set via `[keybindings] model_selector`
+    pub fn is_model_selector(event: &KeyEvent) -> bool {
+        bound(Action::ModelSelector, event)
+    }
+
+    /// Ctrl+E - Ask the agent to explain the last failed tool call
+    /// (remappable: `explain_error`)
+    pub fn is_explain_error(event: &KeyEvent) -> bool {
+        bound(Action::ExplainError, event)
+    }
+
+    /// Ctrl+P - Open the command palette (remappable: `command_palette`)
+    pub fn is_command_palette(event: &KeyEvent) -> bool {
+        bound(Action::CommandPalette, event)
+    }
+
+    /// Ctrl+T - Plan the typed task first, only while the plan hint is showing
+    /// (remappable: `plan_first`)
+    pub fn is_plan_first(event: &KeyEvent) -> bool {
+        bound(Action::PlanFirst, event)
     }
 
     /// Enter - Submit (plain Enter sends the message)
-    /// Also accepts Ctrl+Enter for backwards compatibility
+    /// Also accepts Ctrl+Enter for backwards compatibility (remappable: `submit`)
     pub fn is_submit(event: &KeyEvent) -> bool {
-        event.code == KeyCode::Enter
-            && (event.modifiers.is_empty() || event.modifiers.contains(KeyModifiers::CONTROL))
+        bound(Action::Submit, event)
     }
 
     /// Insert newline — Alt+Enter, Shift+Enter, or Ctrl+J
@@ -412,9 +525,9 @@ pub mod keys {
             || (event.code == KeyCode::Char('j') && event.modifiers.contains(KeyModifiers::CONTROL))
     }
 
-    /// Escape - Cancel/Back
+    /// Escape - Cancel/Back (remappable: `cancel`)
     pub fn is_cancel(event: &KeyEvent) -> bool {
-        event.code == KeyCode::Esc
+        bound(Action::Cancel, event)
     }
 
     /// Enter - Select/Confirm
@@ -422,6 +535,11 @@ pub mod keys {
         event.code == KeyCode::Enter && event.modifiers.is_empty()
     }
 
+    /// Ctrl+F - Toggle the file picker between folders and fuzzy find
+    pub fn is_fuzzy_toggle(event: &KeyEvent) -> bool {
+        key_matches(event, KeyCode::Char('f'), KeyModifiers::CONTROL)
+    }
+
     /// Up arrow
     pub fn is_up(event: &KeyEvent) -> bool {
         event.code == KeyCode::Up && event.modifiers.is_empty()
@@ -442,14 +560,14 @@ pub mod keys {
         event.code == KeyCode::Right && event.modifiers.is_empty()
     }
 
-    /// Page up
+    /// Page up (remappable: `scroll_up`)
     pub fn is_page_up(event: &KeyEvent) -> bool {
-        event.code == KeyCode::PageUp
+        bound(Action::ScrollUp, event)
     }
 
-    /// Page down
+    /// Page down (remappable: `scroll_down`)
     pub fn is_page_down(event: &KeyEvent) -> bool {
-        event.code == KeyCode::PageDown
+        bound(Action::ScrollDown, event)
     }
 
     /// Tab - Select/Navigate
@@ -515,6 +633,20 @@ mod tests {
         assert!(!keys::is_quit(&event));
     }
 
+    #[test]
+    fn test_plan_first_key() {
+        let event = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
+        assert!(keys::is_plan_first(&event));
+
+        let event = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::empty());
+        assert!(!keys::is_plan_first(&event));
+
+        // Ctrl+P is the command palette, not plan-first
+        let event = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert!(!keys::is_plan_first(&event));
+        assert!(keys::is_command_palette(&event));
+    }
+
     #[test]
     fn test_submit_key() {
         // Plain Enter sends
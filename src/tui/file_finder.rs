@@ -0,0 +1,105 @@
+//! Fuzzy File Finder
+//!
+//! Recursive walk + ranking behind the file picker's fuzzy-find mode
+//! (Ctrl+F). The walk honours `.gitignore`/`.ignore`, skips hidden files
+//! and stops at `MAX_DEPTH`; ranking reuses [`fuzzy_score`].
+
+use crate::tui::fuzzy::fuzzy_score;
+use std::path::{Path, PathBuf};
+
+/// How deep the walk goes below the picker's directory
+pub const MAX_DEPTH: usize = 12;
+
+/// Stop collecting after this many files
+pub const MAX_FILES: usize = 50_000;
+
+/// Most results kept per query
+pub const MAX_RESULTS: usize = 200;
+
+/// Files under `root`, relative to it, in walk order.
+pub fn walk_files(root: &Path) -> Vec<PathBuf> {
+    ignore::WalkBuilder::new(root)
+        .max_depth(Some(MAX_DEPTH))
+        // Respect .gitignore even outside a git checkout
+        .require_git(false)
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .take(MAX_FILES)
+        .collect()
+}
+
+/// Rank `files` against `query`, best first. Matches on the file name
+/// outrank matches that only work against the full relative path, so
+/// `mod` finds `mod.rs` files before `src/models/user.rs`.
+pub fn rank<'a>(query: &str, files: &'a [PathBuf]) -> Vec<&'a PathBuf> {
+    let query = query.trim();
+    if query.is_empty() {
+        return files.iter().take(MAX_RESULTS).collect();
+    }
+
+    let mut scored: Vec<(u32, &PathBuf)> = files
+        .iter()
+        .filter_map(|path| {
+            let full = path.to_string_lossy();
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            let by_name = fuzzy_score(query, &name).map(|s| s + 10_000);
+            let by_path = fuzzy_score(query, &full);
+            by_name.max(by_path).map(|score| (score, path))
+        })
+        .collect();
+    // Stable sort keeps walk order within equal scores
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/tui")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.join("src/tui/app.rs"), "").unwrap();
+        std::fs::write(root.join("target/debug/app.rs"), "").unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+
+        let mut files = walk_files(root);
+        files.sort();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("README.md"), PathBuf::from("src/tui/app.rs")]
+        );
+    }
+
+    #[test]
+    fn test_rank_prefers_file_name_matches() {
+        let files: Vec<PathBuf> = ["src/models/user.rs", "src/tui/mod.rs", "docs/notes.md"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let ranked = rank("mod", &files);
+        assert_eq!(ranked[0], &PathBuf::from("src/tui/mod.rs"));
+        assert_eq!(ranked.len(), 2);
+
+        // Path queries still work
+        assert_eq!(
+            rank("tui/mo", &files),
+            vec![&PathBuf::from("src/tui/mod.rs")]
+        );
+        assert_eq!(rank("", &files).len(), 3);
+    }
+}
@@ -0,0 +1,97 @@
+//! Fuzzy Matching
+//!
+//! Case-insensitive subsequence matching used by the slash-command
+//! autocomplete, so `/ses` finds `/sessions` and `/cpt` finds `/compact`.
+
+/// Score tiers — any prefix match outranks any substring match, which
+/// outranks any scattered subsequence match.
+const PREFIX_SCORE: u32 = 3000;
+const SUBSTRING_SCORE: u32 = 2000;
+const SUBSEQUENCE_SCORE: u32 = 1000;
+
+/// Score `candidate` against `query`, or `None` if `query` isn't a
+/// subsequence of it. Higher is better. A leading `/` on either side is
+/// ignored, and an empty query matches everything with the same score.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query = query.trim_start_matches('/').to_lowercase();
+    let candidate = candidate.trim_start_matches('/').to_lowercase();
+
+    if query.is_empty() {
+        return Some(PREFIX_SCORE);
+    }
+    // Within a tier, closer length means a tighter match
+    let slack = candidate
+        .chars()
+        .count()
+        .saturating_sub(query.chars().count()) as u32;
+
+    if candidate.starts_with(&query) {
+        return Some(PREFIX_SCORE - slack.min(999));
+    }
+    if let Some(pos) = candidate.find(&query) {
+        return Some(SUBSTRING_SCORE - (pos as u32 + slack).min(999));
+    }
+
+    // Scattered subsequence: penalize where the match starts and every gap
+    let mut penalty = 0u32;
+    let mut last_match: Option<usize> = None;
+    let mut chars = candidate.chars().enumerate();
+    for q in query.chars() {
+        let (idx, _) = chars.by_ref().find(|&(_, c)| c == q)?;
+        penalty += match last_match {
+            Some(prev) => (idx - prev - 1) as u32,
+            None => idx as u32,
+        };
+        last_match = Some(idx);
+    }
+    Some(SUBSEQUENCE_SCORE - (penalty + slack).min(999))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_matches() {
+        assert!(fuzzy_score("/ses", "/sessions").is_some());
+        assert!(fuzzy_score("/exp", "/export").is_some());
+        assert!(fuzzy_score("/cpt", "/compact").is_some());
+        assert!(fuzzy_score("/ONB", "/onboard").is_some());
+        assert!(fuzzy_score("/xyz", "/sessions").is_none());
+        // Order matters — it's a subsequence, not a bag of letters
+        assert!(fuzzy_score("/tpc", "/compact").is_none());
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything_equally() {
+        assert_eq!(
+            fuzzy_score("/", "/help"),
+            fuzzy_score("/", "/onboard:brain")
+        );
+    }
+
+    #[test]
+    fn test_prefix_ranks_above_substring_and_scattered() {
+        let prefix = fuzzy_score("/co", "/compact").unwrap();
+        let substring = fuzzy_score("/co", "/recompile").unwrap();
+        let scattered = fuzzy_score("/co", "/cd-home-dir").unwrap();
+        assert!(prefix > substring);
+        assert!(substring > scattered);
+
+        // A long prefix match still beats a short non-prefix one
+        let long_prefix = fuzzy_score("/s", "/sessions-and-more-words").unwrap();
+        let short_substring = fuzzy_score("/s", "/us").unwrap();
+        assert!(long_prefix > short_substring);
+    }
+
+    #[test]
+    fn test_tighter_scattered_match_ranks_higher() {
+        let tight = fuzzy_score("/cmp", "/compact").unwrap();
+        let loose = fuzzy_score("/cmp", "/clear-my-prompt").unwrap();
+        assert!(tight > loose);
+
+        let close_gaps = fuzzy_score("/apv", "/approve").unwrap();
+        let far_gaps = fuzzy_score("/apv", "/a-long-path-to-v").unwrap();
+        assert!(close_gaps > far_gaps);
+    }
+}
@@ -132,6 +132,224 @@ pub fn highlight_code(code: &str, language: &str) -> Vec<Line<'static>> {
     lines
 }
 
+/// A hint that a line of code belongs to a language
+enum Signal {
+    /// The trimmed line starts with this
+    Prefix(&'static str),
+    /// The line contains this anywhere
+    Contains(&'static str),
+    /// The trimmed line is exactly this
+    Line(&'static str),
+}
+
+/// Per-language hints for [`detect_language`], keyed by syntect token
+const LANGUAGE_SIGNALS: &[(&str, &[Signal])] = {
+    use Signal::*;
+    &[
+        (
+            "rust",
+            &[
+                Prefix("fn "),
+                Prefix("pub fn "),
+                Prefix("let "),
+                Prefix("impl "),
+                Prefix("use "),
+                Prefix("#["),
+                Contains("println!"),
+                Contains("&mut "),
+                Contains(") -> "),
+                Contains("::"),
+            ],
+        ),
+        (
+            "python",
+            &[
+                Prefix("def "),
+                Prefix("import "),
+                Prefix("elif "),
+                Prefix("if __name__"),
+                Contains(" import "),
+                Contains("self."),
+                Contains("print("),
+            ],
+        ),
+        (
+            "js",
+            &[
+                Prefix("function "),
+                Prefix("const "),
+                Prefix("export "),
+                Prefix("var "),
+                Contains("=> {"),
+                Contains("console.log"),
+                Contains("require("),
+            ],
+        ),
+        (
+            "go",
+            &[
+                Prefix("package "),
+                Prefix("func "),
+                Line("import ("),
+                Contains(":= "),
+                Contains("fmt."),
+            ],
+        ),
+        (
+            "bash",
+            &[
+                Prefix("echo "),
+                Prefix("export "),
+                Prefix("cd "),
+                Prefix("sudo "),
+                Prefix("cargo "),
+                Prefix("npm "),
+                Prefix("git "),
+                Line("fi"),
+                Line("done"),
+                Contains("$("),
+            ],
+        ),
+        (
+            "c",
+            &[
+                Prefix("#include"),
+                Prefix("int main("),
+                Contains("printf("),
+                Contains("malloc("),
+            ],
+        ),
+        (
+            "cpp",
+            &[
+                Prefix("#include"),
+                Prefix("template<"),
+                Contains("std::"),
+                Contains("cout"),
+                Contains("nullptr"),
+            ],
+        ),
+        (
+            "java",
+            &[
+                Prefix("public class "),
+                Prefix("private "),
+                Prefix("import java"),
+                Contains("System.out."),
+                Contains("public static void"),
+            ],
+        ),
+        (
+            "sql",
+            &[
+                Prefix("SELECT "),
+                Prefix("INSERT INTO "),
+                Prefix("CREATE TABLE "),
+                Prefix("UPDATE "),
+                Contains(" FROM "),
+                Contains(" WHERE "),
+            ],
+        ),
+        (
+            "html",
+            &[
+                Prefix("<!DOCTYPE html"),
+                Prefix("<html"),
+                Prefix("<head"),
+                Prefix("<body"),
+                Contains("</div>"),
+                Contains("</p>"),
+            ],
+        ),
+    ]
+};
+
+/// Lines of a code block looked at by [`detect_language`]
+const DETECT_MAX_LINES: usize = 40;
+
+/// The interpreter a shebang line (without `#!`) runs: the program's file
+/// name, looking through `env` and its flags, with any version suffix dropped
+/// (`/usr/bin/env python3.12` → `python`).
+fn shebang_interpreter(shebang: &str) -> Option<&str> {
+    let mut words = shebang.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words
+            .find(|w| !w.starts_with('-') && !w.contains('='))?
+            .rsplit('/')
+            .next()?;
+    }
+    Some(program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.'))
+}
+
+/// Guess the language of a code block that came without one, as a syntect
+/// token for [`highlight_code`].
+///
+/// A shebang or valid JSON decides outright. Otherwise each language scores
+/// one point per distinct hint found (`fn ` and `let ` for Rust, `def ` and
+/// `import ` for Python, ...). The best score must be at least 2 and beat
+/// every other language, so ambiguous or short snippets stay plain text.
+pub fn detect_language(code: &str) -> Option<&'static str> {
+    let trimmed = code.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Some(interpreter) = trimmed
+        .lines()
+        .next()
+        .and_then(|l| l.strip_prefix("#!"))
+        .and_then(shebang_interpreter)
+    {
+        let lang = match interpreter {
+            "python" => Some("python"),
+            "node" | "nodejs" => Some("js"),
+            "ruby" => Some("ruby"),
+            "perl" => Some("perl"),
+            "sh" | "bash" | "zsh" | "dash" | "ksh" => Some("bash"),
+            _ => None,
+        };
+        if lang.is_some() {
+            return lang;
+        }
+    }
+
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return Some("json");
+    }
+
+    let lines: Vec<&str> = trimmed.lines().take(DETECT_MAX_LINES).collect();
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut runner_up = 0;
+    for (lang, signals) in LANGUAGE_SIGNALS {
+        let score = signals
+            .iter()
+            .filter(|signal| {
+                lines.iter().any(|line| {
+                    let line_trimmed = line.trim_start();
+                    match signal {
+                        Signal::Prefix(p) => line_trimmed.starts_with(p),
+                        Signal::Contains(s) => line.contains(s),
+                        Signal::Line(l) => line.trim() == *l,
+                    }
+                })
+            })
+            .count();
+        match best {
+            Some((_, top)) if score <= top => runner_up = runner_up.max(score),
+            _ => {
+                runner_up = best.map_or(0, |(_, top)| top);
+                best = Some((*lang, score));
+            }
+        }
+    }
+
+    best.filter(|&(_, score)| score >= 2 && score > runner_up)
+        .map(|(lang, _)| lang)
+}
+
 /// Get a list of all supported languages
 pub fn supported_languages() -> Vec<String> {
     SYNTAX_SET
@@ -197,6 +415,71 @@ mod tests {
         assert!(!is_language_supported("not_a_real_language"));
     }
 
+    #[test]
+    fn test_detect_language_from_keywords() {
+        let rust = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}";
+        assert_eq!(detect_language(rust), Some("rust"));
+
+        let python = "import os\n\ndef main():\n    print(os.getcwd())";
+        assert_eq!(detect_language(python), Some("python"));
+
+        let go = "package main\n\nimport \"fmt\"\n\nfunc main() {\n    fmt.Println(\"hi\")\n}";
+        assert_eq!(detect_language(go), Some("go"));
+
+        let js = "const add = (a, b) => {\n  return a + b;\n};\nconsole.log(add(1, 2));";
+        assert_eq!(detect_language(js), Some("js"));
+
+        let sql = "SELECT id, name FROM users WHERE active = 1;";
+        assert_eq!(detect_language(sql), Some("sql"));
+    }
+
+    #[test]
+    fn test_detect_language_from_shebang_and_json() {
+        assert_eq!(
+            detect_language("#!/usr/bin/env bash\nset -e\nmake"),
+            Some("bash")
+        );
+        assert_eq!(
+            detect_language("#!/usr/bin/env python3\nx = 1"),
+            Some("python")
+        );
+        assert_eq!(detect_language("#!/bin/sh\necho hi"), Some("bash"));
+        assert_eq!(detect_language("{\"a\": [1, 2]}"), Some("json"));
+    }
+
+    #[test]
+    fn test_shebang_interpreter_matches_exactly() {
+        assert_eq!(shebang_interpreter("/usr/bin/fish"), Some("fish"));
+        assert_eq!(shebang_interpreter(" /bin/zsh -e"), Some("zsh"));
+        assert_eq!(
+            shebang_interpreter("/usr/bin/env -S python3.12 -u"),
+            Some("python")
+        );
+        assert_eq!(shebang_interpreter("/usr/bin/env"), None);
+        // "fish" contains "sh" but is not a POSIX shell
+        assert_eq!(detect_language("#!/usr/bin/fish\necho hi"), None);
+    }
+
+    #[test]
+    fn test_detect_language_stays_plain_when_unsure() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("hello world\nthis is just text"), None);
+        // A single hint is not enough
+        assert_eq!(detect_language("let x = 1"), None);
+        // Not valid JSON
+        assert_eq!(detect_language("{ not json"), None);
+    }
+
+    #[test]
+    fn test_detected_languages_are_supported() {
+        for (lang, _) in LANGUAGE_SIGNALS {
+            assert!(is_language_supported(lang), "no syntax for {}", lang);
+        }
+        for lang in ["json", "ruby", "perl"] {
+            assert!(is_language_supported(lang), "no syntax for {}", lang);
+        }
+    }
+
     #[test]
     fn test_empty_code() {
         let code = "";
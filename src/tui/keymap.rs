@@ -0,0 +1,375 @@
+//! Configurable Key Bindings
+//!
+//! Maps remappable TUI actions to key combos. Defaults match the built-in
+//! bindings; `[keybindings]` in config.toml overrides them per action.
+//!
+//! ```toml
+//! [keybindings]
+//! sessions = "ctrl+o"
+//! help = "f1"
+//! scroll_up = ["pageup", "ctrl+b"]
+//! ```
+
+use crate::config::KeySpec;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A remappable TUI action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Submit,
+    Cancel,
+    Sessions,
+    NewSession,
+    ClearSession,
+    Help,
+    ModelSelector,
+    ScrollUp,
+    ScrollDown,
+    ExplainError,
+    CommandPalette,
+    PlanFirst,
+}
+
+impl Action {
+    /// Every action with its config name
+    pub const ALL: &'static [(Action, &'static str)] = &[
+        (Action::Submit, "submit"),
+        (Action::Cancel, "cancel"),
+        (Action::Sessions, "sessions"),
+        (Action::NewSession, "new_session"),
+        (Action::ClearSession, "clear_session"),
+        (Action::Help, "help"),
+        (Action::ModelSelector, "model_selector"),
+        (Action::ScrollUp, "scroll_up"),
+        (Action::ScrollDown, "scroll_down"),
+        (Action::ExplainError, "explain_error"),
+        (Action::CommandPalette, "command_palette"),
+        (Action::PlanFirst, "plan_first"),
+    ];
+
+    /// Look up an action by its config name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(a, _)| *a)
+    }
+
+    /// Built-in binding. `help` and `model_selector` are unbound by default
+    /// (use `/help` and `/models`).
+    fn default_matches(self, event: &KeyEvent) -> bool {
+        let ctrl =
+            |c: char| event.code == KeyCode::Char(c) && event.modifiers == KeyModifiers::CONTROL;
+        match self {
+            // Plain Enter, plus Ctrl+Enter for backwards compatibility
+            Action::Submit => {
+                event.code == KeyCode::Enter
+                    && (event.modifiers.is_empty()
+                        || event.modifiers.contains(KeyModifiers::CONTROL))
+            }
+            Action::Cancel => event.code == KeyCode::Esc,
+            Action::Sessions => ctrl('l'),
+            Action::NewSession => ctrl('n'),
+            Action::ClearSession => ctrl('k'),
+            Action::Help | Action::ModelSelector => false,
+            Action::ScrollUp => event.code == KeyCode::PageUp,
+            Action::ScrollDown => event.code == KeyCode::PageDown,
+            Action::ExplainError => ctrl('e'),
+            Action::CommandPalette => ctrl('p'),
+            Action::PlanFirst => ctrl('t'),
+        }
+    }
+
+    /// Built-in binding as shown to the user, if any
+    pub fn default_label(self) -> Option<&'static str> {
+        match self {
+            Action::Submit => Some("Enter"),
+            Action::Cancel => Some("Esc"),
+            Action::Sessions => Some("Ctrl+L"),
+            Action::NewSession => Some("Ctrl+N"),
+            Action::ClearSession => Some("Ctrl+K"),
+            Action::Help | Action::ModelSelector => None,
+            Action::ScrollUp => Some("PageUp"),
+            Action::ScrollDown => Some("PageDown"),
+            Action::ExplainError => Some("Ctrl+E"),
+            Action::CommandPalette => Some("Ctrl+P"),
+            Action::PlanFirst => Some("Ctrl+T"),
+        }
+    }
+
+    /// What the action does, for the command palette. `None` for keys that
+    /// only make sense pressed in place (submit, scroll, …).
+    pub fn description(self) -> Option<&'static str> {
+        match self {
+            Action::Sessions => Some("Open the session manager"),
+            Action::NewSession => Some("Start a new session"),
+            Action::ClearSession => Some("Clear the current session"),
+            Action::Help => Some("Show keybindings and commands"),
+            Action::ModelSelector => Some("Switch provider or model"),
+            Action::ExplainError => Some("Explain the last failed tool call"),
+            Action::Submit
+            | Action::Cancel
+            | Action::ScrollUp
+            | Action::ScrollDown
+            | Action::CommandPalette
+            | Action::PlanFirst => None,
+        }
+    }
+}
+
+/// One key combo, e.g. `ctrl+l`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parse a key spec like `ctrl+l`, `alt+enter`, `f1` or `pagedown`.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::empty();
+        let parts: Vec<&str> = spec.split('+').map(str::trim).collect();
+        let (key, mods) = parts.split_last()?;
+        for m in mods {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" | "cmd" => KeyModifiers::SUPER,
+                _ => return None,
+            };
+        }
+
+        let lower = key.to_ascii_lowercase();
+        let code = match lower.as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+            _ => {
+                let mut chars = lower.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        };
+        Some(Self { code, modifiers })
+    }
+
+    /// Whether a key event is this combo. Letters match regardless of case,
+    /// since terminals differ in how they report Ctrl+letter.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let code_matches = match (self.code, event.code) {
+            (KeyCode::Char(a), KeyCode::Char(b)) => a.eq_ignore_ascii_case(&b),
+            (a, b) => a == b,
+        };
+        code_matches && event.modifiers == self.modifiers
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Readable form, e.g. `Ctrl+Shift+Enter` or `F1`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+            (KeyModifiers::SUPER, "Super"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Action → key combos lookup. Actions without an override use their
+/// built-in binding.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    overrides: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl KeyMap {
+    /// Defaults overlaid with `[keybindings]`. Unknown actions and
+    /// unparsable key specs are logged and ignored.
+    pub fn from_config(overrides: &BTreeMap<String, KeySpec>) -> Self {
+        let mut map = Self::default();
+        for (name, spec) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                tracing::warn!("[keybindings] unknown action '{}' — ignored", name);
+                continue;
+            };
+            let keys: Vec<KeyBinding> = spec
+                .specs()
+                .into_iter()
+                .filter_map(|s| {
+                    let parsed = KeyBinding::parse(s);
+                    if parsed.is_none() {
+                        tracing::warn!(
+                            "[keybindings] invalid key '{}' for '{}' — ignored",
+                            s,
+                            name
+                        );
+                    }
+                    parsed
+                })
+                .collect();
+            if keys.is_empty() {
+                tracing::warn!(
+                    "[keybindings] no valid keys for '{}' — keeping the default",
+                    name
+                );
+                continue;
+            }
+            map.overrides.insert(action, keys);
+        }
+        map
+    }
+
+    /// Whether the event triggers `action`
+    pub fn matches(&self, action: Action, event: &KeyEvent) -> bool {
+        match self.overrides.get(&action) {
+            Some(keys) => keys.iter().any(|k| k.matches(event)),
+            None => action.default_matches(event),
+        }
+    }
+
+    /// Keys bound to `action` as shown to the user, e.g. `Ctrl+O / F2`
+    pub fn label(&self, action: Action) -> Option<String> {
+        match self.overrides.get(&action) {
+            Some(keys) => Some(
+                keys.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+            ),
+            None => action.default_label().map(str::to_string),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_key_specs() {
+        assert_eq!(
+            KeyBinding::parse("ctrl+l"),
+            Some(KeyBinding {
+                code: KeyCode::Char('l'),
+                modifiers: KeyModifiers::CONTROL
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("Ctrl + Shift + Enter").map(|k| k.modifiers),
+            Some(KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        );
+        assert_eq!(KeyBinding::parse("f1").map(|k| k.code), Some(KeyCode::F(1)));
+        assert_eq!(
+            KeyBinding::parse("PageDown").map(|k| k.code),
+            Some(KeyCode::PageDown)
+        );
+        assert!(KeyBinding::parse("hyper+x").is_none());
+        assert!(KeyBinding::parse("ctrl+").is_none());
+        assert!(KeyBinding::parse("banana").is_none());
+    }
+
+    #[test]
+    fn test_defaults_match_builtin_bindings() {
+        let map = KeyMap::default();
+        assert!(map.matches(Action::Submit, &key(KeyCode::Enter, KeyModifiers::empty())));
+        assert!(map.matches(Action::Submit, &key(KeyCode::Enter, KeyModifiers::CONTROL)));
+        assert!(!map.matches(Action::Submit, &key(KeyCode::Enter, KeyModifiers::SHIFT)));
+        assert!(!map.matches(Action::Submit, &key(KeyCode::Enter, KeyModifiers::ALT)));
+        assert!(map.matches(Action::Cancel, &key(KeyCode::Esc, KeyModifiers::empty())));
+        assert!(map.matches(
+            Action::Sessions,
+            &key(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        ));
+        assert!(map.matches(
+            Action::ScrollDown,
+            &key(KeyCode::PageDown, KeyModifiers::SHIFT)
+        ));
+        assert!(!map.matches(Action::Help, &key(KeyCode::F(1), KeyModifiers::empty())));
+        assert!(map.matches(
+            Action::ExplainError,
+            &key(KeyCode::Char('e'), KeyModifiers::CONTROL)
+        ));
+        assert!(map.matches(
+            Action::CommandPalette,
+            &key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        ));
+        assert!(map.matches(
+            Action::PlanFirst,
+            &key(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        ));
+    }
+
+    #[test]
+    fn test_config_overrides_and_ignores_unknown() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("sessions".to_string(), KeySpec::One("ctrl+o".into()));
+        overrides.insert(
+            "help".to_string(),
+            KeySpec::Many(vec!["f1".into(), "not-a-key".into()]),
+        );
+        overrides.insert("teleport".to_string(), KeySpec::One("ctrl+t".into()));
+        overrides.insert("cancel".to_string(), KeySpec::One("nonsense".into()));
+
+        let map = KeyMap::from_config(&overrides);
+        let ctrl_o = key(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        let ctrl_l = key(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert!(map.matches(Action::Sessions, &ctrl_o));
+        assert!(!map.matches(Action::Sessions, &ctrl_l));
+        assert!(map.matches(Action::Help, &key(KeyCode::F(1), KeyModifiers::empty())));
+        // Invalid-only override keeps the default
+        assert!(map.matches(Action::Cancel, &key(KeyCode::Esc, KeyModifiers::empty())));
+    }
+
+    #[test]
+    fn test_labels_follow_overrides() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert(
+            "sessions".to_string(),
+            KeySpec::Many(vec!["ctrl+o".into(), "f2".into()]),
+        );
+        let map = KeyMap::from_config(&overrides);
+        assert_eq!(map.label(Action::Sessions).as_deref(), Some("Ctrl+O / F2"));
+        assert_eq!(map.label(Action::NewSession).as_deref(), Some("Ctrl+N"));
+        assert_eq!(map.label(Action::Help), None);
+        assert_eq!(
+            KeyBinding::parse("alt+shift+pagedown").unwrap().to_string(),
+            "Alt+Shift+PageDown"
+        );
+    }
+}
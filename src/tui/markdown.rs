@@ -8,12 +8,24 @@ use ratatui::{
     text::{Line, Span},
 };
 
-use super::highlight::highlight_code;
+use super::highlight::{detect_language, highlight_code};
 
 /// Parse markdown and convert to styled lines for Ratatui
 pub fn parse_markdown(markdown: &str) -> Vec<Line<'static>> {
+    parse_markdown_with_code(markdown)
+        .into_iter()
+        .map(|(line, _)| line)
+        .collect()
+}
+
+/// Like [`parse_markdown`], flagging each line that belongs to a code block
+/// (including its language header and footer) so callers can wrap prose and
+/// code to different widths.
+pub fn parse_markdown_with_code(markdown: &str) -> Vec<(Line<'static>, bool)> {
     let parser = Parser::new(markdown);
     let mut lines = Vec::new();
+    let mut code_ranges: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut code_start = 0;
     let mut current_line = Vec::new();
     let mut in_code_block = false;
     let mut code_language = String::new();
@@ -34,11 +46,13 @@ pub fn parse_markdown(markdown: &str) -> Vec<Line<'static>> {
                         CodeBlockKind::Indented => String::new(),
                     };
 
+                    if !current_line.is_empty() {
+                        lines.push(Line::from(std::mem::take(&mut current_line)));
+                    }
+                    code_start = lines.len();
+
                     // Add code block header if language is specified
                     if !code_language.is_empty() {
-                        if !current_line.is_empty() {
-                            lines.push(Line::from(std::mem::take(&mut current_line)));
-                        }
                         lines.push(Line::from(vec![
                             Span::styled("╭─ ", Style::default().fg(Color::DarkGray)),
                             Span::styled(
@@ -101,12 +115,16 @@ pub fn parse_markdown(markdown: &str) -> Vec<Line<'static>> {
                         lines.push(Line::from(std::mem::take(&mut current_line)));
                     }
 
-                    // Use syntax highlighting if we have code content
+                    // Use syntax highlighting if we have code content; unlabeled
+                    // blocks get a guessed language, or plain text when unsure
                     if !code_content.is_empty() {
                         let highlighted_lines = if !code_language.is_empty() {
                             highlight_code(&code_content, &code_language)
                         } else {
-                            highlight_code(&code_content, "text")
+                            highlight_code(
+                                &code_content,
+                                detect_language(&code_content).unwrap_or("text"),
+                            )
                         };
                         lines.extend(highlighted_lines);
                     }
@@ -118,6 +136,7 @@ pub fn parse_markdown(markdown: &str) -> Vec<Line<'static>> {
                             Style::default().fg(Color::DarkGray),
                         )));
                     }
+                    code_ranges.push(code_start..lines.len());
 
                     lines.push(Line::from("")); // Add spacing after code block
                     in_code_block = false;
@@ -197,6 +216,10 @@ pub fn parse_markdown(markdown: &str) -> Vec<Line<'static>> {
     }
 
     lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| (line, code_ranges.iter().any(|r| r.contains(&i))))
+        .collect()
 }
 
 #[cfg(test)]
@@ -224,6 +247,16 @@ mod tests {
         assert!(lines.len() > 2); // Header, code, footer
     }
 
+    #[test]
+    fn test_code_lines_are_flagged() {
+        let md = "Intro text\n\n```rust\nfn main() {}\n```\n\nOutro";
+        let lines = parse_markdown_with_code(md);
+        let flags: Vec<bool> = lines.iter().map(|(_, code)| *code).collect();
+        assert!(!flags[0]);
+        assert!(flags.iter().filter(|c| **c).count() >= 3); // header, code, footer
+        assert!(!*flags.last().unwrap());
+    }
+
     #[test]
     fn test_parse_inline_code() {
         let md = "Use `cargo build` to compile";
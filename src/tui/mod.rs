@@ -5,10 +5,14 @@
 pub mod app;
 pub mod error;
 pub mod events;
+pub mod file_finder;
+pub mod fuzzy;
+pub mod keymap;
 pub mod onboarding;
 pub mod onboarding_render;
 pub mod plan;
 pub mod prompt_analyzer;
+pub mod prompts;
 pub mod render;
 pub mod runner;
 
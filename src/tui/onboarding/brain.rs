@@ -1,4 +1,3 @@
-use chrono::Local;
 use crossterm::event::{KeyCode, KeyEvent};
 
 use super::types::*;
@@ -95,7 +94,7 @@ impl OnboardingWizard {
     /// Build the prompt sent to the AI to generate personalized brain files.
     /// Uses existing workspace files if available, falls back to static templates.
     pub fn build_brain_prompt(&self) -> String {
-        let today = Local::now().format("%Y-%m-%d").to_string();
+        let today = crate::config::local_today();
         let workspace = std::path::Path::new(&self.workspace_path);
 
         // Read current brain files from workspace, fall back to static templates
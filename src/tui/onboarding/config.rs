@@ -670,6 +670,10 @@ fn install_systemd_service() -> Result<(), String> {
         .map_err(|e| format!("Failed to create systemd dir: {}", e))?;
 
     let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
+    // Keep the daemon on the same workspace the wizard was launched with
+    let workspace_arg = crate::config::opencrabs_home_override()
+        .map(|p| format!(" --workspace \"{}\"", p.display()))
+        .unwrap_or_default();
 
     let service_content = format!(
         r#"[Unit]
@@ -678,14 +682,15 @@ After=network.target
 
 [Service]
 Type=simple
-ExecStart={} daemon
+ExecStart={}{} daemon
 Restart=on-failure
 RestartSec=5
 
 [Install]
 WantedBy=default.target
 "#,
-        exe_path.display()
+        exe_path.display(),
+        workspace_arg
     );
 
     let service_path = service_dir.join("opencrabs.service");
@@ -717,6 +722,15 @@ fn install_launchagent() -> Result<(), String> {
         .map_err(|e| format!("Failed to create LaunchAgents dir: {}", e))?;
 
     let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
+    // Keep the daemon on the same workspace the wizard was launched with
+    let workspace_args = crate::config::opencrabs_home_override()
+        .map(|p| {
+            format!(
+                "\n        <string>--workspace</string>\n        <string>{}</string>",
+                p.display()
+            )
+        })
+        .unwrap_or_default();
 
     let plist_content = format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -727,7 +741,7 @@ fn install_launchagent() -> Result<(), String> {
     <string>com.opencrabs.agent</string>
     <key>ProgramArguments</key>
     <array>
-        <string>{}</string>
+        <string>{}</string>{}
         <string>daemon</string>
     </array>
     <key>RunAtLoad</key>
@@ -737,7 +751,8 @@ fn install_launchagent() -> Result<(), String> {
 </dict>
 </plist>
 "#,
-        exe_path.display()
+        exe_path.display(),
+        workspace_args
     );
 
     let plist_path = agents_dir.join("com.opencrabs.agent.plist");
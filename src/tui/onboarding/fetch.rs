@@ -1,4 +1,5 @@
 use crossterm::event::{KeyCode, KeyEvent};
+use thiserror::Error;
 
 use super::types::*;
 use super::wizard::OnboardingWizard;
@@ -128,30 +129,38 @@ impl OnboardingWizard {
     }
 }
 
-/// First-time detection: no config file AND no API keys in environment.
-/// Once config.toml is written (by onboarding or manually), this returns false forever.
-/// If any API key env var is set, the user has already configured auth — skip onboarding.
-/// To re-run the wizard, use `opencrabs onboard`, `--onboard` flag, or `/onboard`.
-pub fn is_first_time() -> bool {
-    tracing::debug!("[is_first_time] checking if first time setup needed...");
+/// Where launch should go after the splash screen, judged from config.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupState {
+    /// No config.toml — a true first run; show the onboarding wizard
+    FirstRun,
+    /// config.toml exists but fails to load; prompt for a repair instead of
+    /// onboarding over the user's settings
+    ConfigInvalid(String),
+    /// config.toml loads but no provider is enabled; offer to enable one
+    NoProviderEnabled,
+    /// Ready to chat
+    Ready,
+}
 
-    // Check if config exists
+/// Classify the config at `~/.opencrabs/config.toml`.
+/// To re-run the wizard regardless, use `opencrabs onboard`, `--onboard` or `/onboard`.
+pub fn setup_state() -> SetupState {
     let config_path = crate::config::opencrabs_home().join("config.toml");
     if !config_path.exists() {
-        tracing::debug!("[is_first_time] no config found, need onboarding");
-        return true;
+        tracing::debug!("[setup_state] no config found, need onboarding");
+        return SetupState::FirstRun;
     }
+    let state = classify_config(crate::config::Config::load());
+    tracing::debug!("[setup_state] {:?}", state);
+    state
+}
 
-    // Config exists - check if any provider is actually enabled
-    let config = match crate::config::Config::load() {
+/// Classify an existing config file from the result of loading it.
+pub(crate) fn classify_config(loaded: anyhow::Result<crate::config::Config>) -> SetupState {
+    let config = match loaded {
         Ok(c) => c,
-        Err(e) => {
-            tracing::debug!(
-                "[is_first_time] failed to load config: {}, need onboarding",
-                e
-            );
-            return true;
-        }
+        Err(e) => return SetupState::ConfigInvalid(format!("{:#}", e)),
     };
 
     let has_enabled_provider = config
@@ -169,27 +178,62 @@ pub fn is_first_time() -> bool {
         || config.providers.minimax.as_ref().is_some_and(|p| p.enabled)
         || config.providers.active_custom().is_some();
 
-    tracing::debug!(
-        "[is_first_time] has_enabled_provider={}, result={}",
-        has_enabled_provider,
-        !has_enabled_provider
-    );
-    !has_enabled_provider
+    if has_enabled_provider {
+        SetupState::Ready
+    } else {
+        SetupState::NoProviderEnabled
+    }
+}
+
+/// First-time detection: true only when there is no config.toml at all.
+/// An unparseable config or one with every provider disabled is not a first
+/// run — see [`setup_state`].
+pub fn is_first_time() -> bool {
+    setup_state() == SetupState::FirstRun
 }
 
-/// Fetch models from provider API. No API key needed for most providers.
-/// If api_key is provided, includes it (some endpoints filter by access level).
+/// Why a provider's model list couldn't be fetched
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FetchError {
+    /// The provider refused the API key (HTTP 401/403)
+    #[error("key rejected (HTTP {0})")]
+    Auth(u16),
+    /// The request never got a usable answer: connection, timeout or server error
+    #[error("couldn't reach provider: {0}")]
+    Network(String),
+    /// The catalog came back with no models in it
+    #[error("provider returned no models")]
+    Empty,
+    /// The response wasn't the expected `{"data": [{"id": ...}]}` shape
+    #[error("unexpected models response: {0}")]
+    Parse(String),
+}
+
+impl FetchError {
+    /// Classify a non-success HTTP status
+    fn from_status(status: reqwest::StatusCode) -> Self {
+        match status.as_u16() {
+            code @ (401 | 403) => Self::Auth(code),
+            _ => Self::Network(format!("HTTP {}", status)),
+        }
+    }
+}
+
+/// Fetch models from provider API, ignoring why a fetch failed.
 /// Returns empty vec on failure (callers fall back to static list).
 pub async fn fetch_provider_models(provider_index: usize, api_key: Option<&str>) -> Vec<String> {
-    #[derive(serde::Deserialize)]
-    struct ModelEntry {
-        id: String,
-    }
-    #[derive(serde::Deserialize)]
-    struct ModelsResponse {
-        data: Vec<ModelEntry>,
-    }
+    try_fetch_provider_models(provider_index, api_key)
+        .await
+        .unwrap_or_default()
+}
 
+/// Fetch models from provider API. No API key needed for most providers.
+/// If api_key is provided, includes it (some endpoints filter by access level).
+/// Providers without a models endpoint return `Ok` with an empty vec.
+pub async fn try_fetch_provider_models(
+    provider_index: usize,
+    api_key: Option<&str>,
+) -> Result<Vec<String>, FetchError> {
     // Handle Minimax specially - no /models API, must use config
     if provider_index == 4 {
         // Minimax — NO /models API endpoint, must use config.models
@@ -197,15 +241,15 @@ pub async fn fetch_provider_models(provider_index: usize, api_key: Option<&str>)
             && let Some(p) = &config.providers.minimax
         {
             if !p.models.is_empty() {
-                return p.models.clone();
+                return Ok(p.models.clone());
             }
             // Fall back to default_model if no models list
             if let Some(model) = &p.default_model {
-                return vec![model.clone()];
+                return Ok(vec![model.clone()]);
             }
         }
         // Return hardcoded defaults if no config
-        return vec!["MiniMax-M2.5".to_string(), "MiniMax-M2.1".to_string()];
+        return Ok(vec!["MiniMax-M2.5".to_string(), "MiniMax-M2.1".to_string()]);
     }
 
     let client = reqwest::Client::new();
@@ -250,18 +294,37 @@ pub async fn fetch_provider_models(provider_index: usize, api_key: Option<&str>)
             }
             req.send().await
         }
-        _ => return Vec::new(),
+        _ => return Ok(Vec::new()),
     };
 
-    match result {
-        Ok(resp) if resp.status().is_success() => match resp.json::<ModelsResponse>().await {
-            Ok(body) => {
-                let mut models: Vec<String> = body.data.into_iter().map(|m| m.id).collect();
-                models.sort();
-                models
-            }
-            Err(_) => Vec::new(),
-        },
-        _ => Vec::new(),
+    let resp = result.map_err(|e| FetchError::Network(e.to_string()))?;
+    if !resp.status().is_success() {
+        return Err(FetchError::from_status(resp.status()));
+    }
+    let body = resp
+        .text()
+        .await
+        .map_err(|e| FetchError::Network(e.to_string()))?;
+    parse_models_response(&body)
+}
+
+/// Model ids from a `/models` response body, sorted
+pub(super) fn parse_models_response(body: &str) -> Result<Vec<String>, FetchError> {
+    #[derive(serde::Deserialize)]
+    struct ModelEntry {
+        id: String,
+    }
+    #[derive(serde::Deserialize)]
+    struct ModelsResponse {
+        data: Vec<ModelEntry>,
+    }
+
+    let body: ModelsResponse =
+        serde_json::from_str(body).map_err(|e| FetchError::Parse(e.to_string()))?;
+    let mut models: Vec<String> = body.data.into_iter().map(|m| m.id).collect();
+    if models.is_empty() {
+        return Err(FetchError::Empty);
     }
+    models.sort();
+    Ok(models)
 }
@@ -273,6 +273,7 @@ impl OnboardingWizard {
                     self.model_filter.clear();
                     self.api_key_input.clear();
                     self.fetched_models.clear();
+                    self.models_fetch_error = None;
                     self.config_models.clear();
                     self.reload_config_models();
                     self.detect_existing_key();
@@ -283,6 +284,7 @@ impl OnboardingWizard {
                     self.model_filter.clear();
                     self.api_key_input.clear();
                     self.fetched_models.clear();
+                    self.models_fetch_error = None;
                     self.config_models.clear();
                     self.reload_config_models();
                     self.detect_existing_key();
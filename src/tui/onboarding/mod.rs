@@ -27,4 +27,7 @@ pub use types::{
 
 pub use wizard::OnboardingWizard;
 
-pub use fetch::{fetch_provider_models, is_first_time};
+pub use fetch::{
+    FetchError, SetupState, fetch_provider_models, is_first_time, setup_state,
+    try_fetch_provider_models,
+};
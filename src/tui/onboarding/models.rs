@@ -1,33 +1,46 @@
+use super::fetch::FetchError;
+use super::types::PROVIDERS;
 use super::wizard::OnboardingWizard;
 
 impl OnboardingWizard {
+    /// Take the result of a live model fetch. On failure the previous list
+    /// stays and the error is kept to explain why no live models appeared.
+    pub fn apply_fetched_models(&mut self, result: Result<Vec<String>, FetchError>) {
+        self.models_fetching = false;
+        match result {
+            Ok(models) if !models.is_empty() => {
+                self.fetched_models = models;
+                self.selected_model = 0;
+                self.models_fetch_error = None;
+            }
+            Ok(_) => self.models_fetch_error = None,
+            Err(e) => {
+                tracing::info!("Onboarding: model fetch failed: {}", e);
+                self.models_fetch_error = Some(e);
+            }
+        }
+    }
+
     /// Reload config_models for the currently selected provider.
-    /// Tries config.toml first, falls back to config.toml.example defaults.
+    /// Tries config.toml first (Minimax and Custom only), falls back to the
+    /// static defaults.
     pub(super) fn reload_config_models(&mut self) {
         self.config_models.clear();
         // Try live config first
         if let Ok(config) = crate::config::Config::load() {
-            match self.selected_provider {
-                4 => {
-                    if let Some(p) = &config.providers.minimax
-                        && !p.models.is_empty()
-                    {
-                        self.config_models = p.models.clone();
-                        return;
-                    }
-                }
-                5 => {
-                    if let Some((_name, p)) = config.providers.active_custom()
-                        && !p.models.is_empty()
-                    {
-                        self.config_models = p.models.clone();
-                        return;
-                    }
-                }
-                _ => return,
+            let live = match self.selected_provider {
+                4 => config.providers.minimax.as_ref(),
+                5 => config.providers.active_custom().map(|(_name, p)| p),
+                _ => None,
+            };
+            if let Some(p) = live
+                && !p.models.is_empty()
+            {
+                self.config_models = p.models.clone();
+                return;
             }
         }
-        // Fall back to embedded config.toml.example
+        // Fall back to the static lists
         self.config_models = Self::load_default_models(self.selected_provider);
     }
 
@@ -77,8 +90,16 @@ impl OnboardingWizard {
         matches!(self.selected_provider, 0 | 1 | 3) // Anthropic, OpenAI, OpenRouter
     }
 
-    /// Load default models from embedded config.toml.example for MiniMax and Custom
-    pub(super) fn load_default_models(provider_index: usize) -> Vec<String> {
+    /// Static model list for a provider, used whenever no live list can be
+    /// fetched (offline, API down): `PROVIDERS[].models`, or for Minimax and
+    /// Custom the lists in the embedded config.toml.example
+    pub fn load_default_models(provider_index: usize) -> Vec<String> {
+        if let Some(provider) = PROVIDERS.get(provider_index)
+            && !provider.models.is_empty()
+        {
+            return provider.models.iter().map(|m| m.to_string()).collect();
+        }
+
         // Parse the embedded config.toml.example to extract default models for a specific provider
         let config_content = include_str!("../../../config.toml.example");
         let mut models = Vec::new();
@@ -409,11 +409,20 @@ fn test_openrouter_provider_index() {
     assert_eq!(PROVIDERS.last().unwrap().name, "Custom OpenAI-Compatible");
 }
 
+#[test]
+fn test_every_provider_has_static_models() {
+    for (index, provider) in PROVIDERS.iter().enumerate() {
+        let models = OnboardingWizard::load_default_models(index);
+        assert!(!models.is_empty(), "{} has no static models", provider.name);
+        assert!(models.iter().all(|m| !m.trim().is_empty()));
+    }
+}
+
 #[test]
 fn test_model_count_uses_fetched_when_available() {
     let mut wizard = OnboardingWizard::new();
-    // Static fallback is empty - models fetched from API
-    assert_eq!(wizard.model_count(), 0);
+    // Static fallback until a live list arrives
+    assert_eq!(wizard.model_count(), PROVIDERS[0].models.len());
 
     // After fetching
     wizard.fetched_models = vec![
@@ -458,6 +467,45 @@ fn test_fetch_models_unsupported_provider_returns_empty() {
     let rt = tokio::runtime::Runtime::new().unwrap();
     let result = rt.block_on(fetch_provider_models(99, None));
     assert!(result.is_empty());
+    assert_eq!(
+        rt.block_on(try_fetch_provider_models(99, None)),
+        Ok(Vec::new())
+    );
+}
+
+#[test]
+fn test_parse_models_response_distinguishes_empty_and_garbage() {
+    use super::fetch::parse_models_response;
+
+    assert_eq!(
+        parse_models_response(r#"{"data": [{"id": "b"}, {"id": "a"}]}"#),
+        Ok(vec!["a".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+        parse_models_response(r#"{"data": []}"#),
+        Err(FetchError::Empty)
+    );
+    assert!(matches!(
+        parse_models_response("<html>Bad Gateway</html>"),
+        Err(FetchError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_failed_fetch_keeps_static_models_and_reason() {
+    let mut wizard = OnboardingWizard::new();
+    wizard.selected_provider = 0;
+    wizard.models_fetching = true;
+    let static_count = wizard.model_count();
+
+    wizard.apply_fetched_models(Err(FetchError::Auth(401)));
+    assert!(!wizard.models_fetching);
+    assert_eq!(wizard.models_fetch_error, Some(FetchError::Auth(401)));
+    assert_eq!(wizard.model_count(), static_count);
+
+    wizard.apply_fetched_models(Ok(vec!["live-model".to_string()]));
+    assert_eq!(wizard.models_fetch_error, None);
+    assert_eq!(wizard.all_model_names(), ["live-model"]);
 }
 
 // --- Live API integration tests (skipped if env var not set) ---
@@ -551,3 +599,28 @@ fn test_fetch_models_bad_key_returns_empty() {
         models.len()
     );
 }
+
+#[test]
+fn test_classify_config_keeps_returning_users_out_of_onboarding() {
+    use super::fetch::classify_config;
+    use crate::config::{Config, ProviderConfig};
+
+    // Parsed, but every provider switched off
+    assert_eq!(
+        classify_config(Ok(Config::default())),
+        SetupState::NoProviderEnabled
+    );
+
+    // Unparseable config is a repair, not a first run
+    assert!(matches!(
+        classify_config(Err(anyhow::anyhow!("expected `=`, found newline"))),
+        SetupState::ConfigInvalid(msg) if msg.contains("expected `=`")
+    ));
+
+    let mut config = Config::default();
+    config.providers.anthropic = Some(ProviderConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    assert_eq!(classify_config(Ok(config)), SetupState::Ready);
+}
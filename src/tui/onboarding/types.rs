@@ -6,28 +6,41 @@ pub(super) const EXISTING_KEY_SENTINEL: &str = "__EXISTING_KEY__";
 pub const PROVIDERS: &[ProviderInfo] = &[
     ProviderInfo {
         name: "Anthropic Claude",
-        models: &[], // Fetched from API
+        // Offline fallback for the live list fetched from the API
+        models: &[
+            "claude-sonnet-4-6",
+            "claude-opus-4-6",
+            "claude-sonnet-4-5-20250929",
+            "claude-haiku-4-5-20251001",
+        ],
         key_label: "Setup Token",
         help_lines: &[
             "Claude Max / Code: run 'claude setup-token'",
             "Or paste API key from console.anthropic.com",
+            "Or sign in from chat later: /login anthropic",
         ],
     },
     ProviderInfo {
         name: "OpenAI",
-        models: &[],
+        models: &["gpt-5-nano", "gpt-5-mini", "gpt-5", "o3", "o3-mini"],
         key_label: "API Key",
         help_lines: &["Get key from platform.openai.com"],
     },
     ProviderInfo {
         name: "Google Gemini",
-        models: &[],
+        models: &["gemini-2.5-flash", "gemini-2.5-pro", "gemini-2.0-flash"],
         key_label: "API Key",
         help_lines: &["Get key from aistudio.google.com"],
     },
     ProviderInfo {
         name: "OpenRouter",
-        models: &[],
+        models: &[
+            "qwen/qwen3-coder-next",
+            "anthropic/claude-sonnet-4.5",
+            "openai/gpt-5",
+            "google/gemini-2.5-flash",
+            "moonshotai/kimi-k2.5",
+        ],
         key_label: "API Key",
         help_lines: &["Get key from openrouter.ai/keys"],
     },
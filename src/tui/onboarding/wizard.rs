@@ -1,5 +1,3 @@
-use std::path::PathBuf;
-
 use super::types::*;
 
 /// Main onboarding wizard state
@@ -19,6 +17,8 @@ pub struct OnboardingWizard {
     /// Models fetched live from provider API (overrides static list when non-empty)
     pub fetched_models: Vec<String>,
     pub models_fetching: bool,
+    /// Why the last live fetch came back without models (shown next to the list)
+    pub models_fetch_error: Option<super::FetchError>,
     /// Models from config.toml (used when API fetch not available)
     pub config_models: Vec<String>,
 
@@ -131,9 +131,7 @@ impl OnboardingWizard {
     /// Create a new wizard with default state
     /// Loads existing config if available to pre-fill settings
     pub fn new() -> Self {
-        let default_workspace = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("~"))
-            .join(".opencrabs");
+        let default_workspace = crate::config::opencrabs_home();
 
         // config_models loaded on demand per provider via reload_config_models()
         let config_models = Vec::new();
@@ -224,6 +222,7 @@ impl OnboardingWizard {
             custom_model,
             fetched_models: Vec::new(),
             models_fetching: false,
+            models_fetch_error: None,
             config_models,
 
             workspace_path: default_workspace.to_string_lossy().to_string(),
@@ -3,9 +3,9 @@
 //! Render functions for each step of the onboarding wizard.
 
 use super::onboarding::{
-    AuthField, BrainField, CHANNEL_NAMES, ChannelTestStatus, DiscordField, HealthStatus,
-    ImageField, OnboardingStep, OnboardingWizard, PROVIDERS, SlackField, TelegramField,
-    TrelloField, VoiceField, WizardMode,
+    AuthField, BrainField, CHANNEL_NAMES, ChannelTestStatus, DiscordField, FetchError,
+    HealthStatus, ImageField, OnboardingStep, OnboardingWizard, PROVIDERS, SlackField,
+    TelegramField, TrelloField, VoiceField, WizardMode,
 };
 use ratatui::{
     Frame,
@@ -712,10 +712,16 @@ fn render_provider_auth(lines: &mut Vec<Line<'static>>, wizard: &OnboardingWizar
             if model_focused {
                 focused_line = lines.len().saturating_sub(1);
             }
-            let label = if wizard.models_fetching {
-                "  Model: (fetching...)".to_string()
-            } else {
-                "  Model:".to_string()
+            let label = match (wizard.models_fetching, &wizard.models_fetch_error) {
+                (true, _) => "  Model: (fetching...)".to_string(),
+                (false, Some(FetchError::Auth(_))) => {
+                    "  Model: (key rejected — showing default models)".to_string()
+                }
+                (false, Some(FetchError::Network(_))) => {
+                    "  Model: (couldn't reach provider — showing default models)".to_string()
+                }
+                (false, Some(e)) => format!("  Model: ({} — showing default models)", e),
+                (false, None) => "  Model:".to_string(),
             };
             lines.push(Line::from(Span::styled(
                 label,
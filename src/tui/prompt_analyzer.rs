@@ -88,9 +88,27 @@ const WEB_SEARCH_KEYWORDS: &[&str] = &[
     "web search",
 ];
 
+/// Words that chain steps ("..., then ...", "after that ...")
+const STEP_CONNECTORS: &[&str] = &[
+    "then",
+    "after that",
+    "afterwards",
+    "once that's done",
+    "once done",
+    "finally",
+    "and finally",
+];
+
+/// Minimum words before a prompt is considered for a plan suggestion
+const PLAN_SUGGEST_MIN_WORDS: usize = 5;
+
 /// Prompt analyzer that detects keywords and suggests tool usage
 pub struct PromptAnalyzer {
     plan_regex: Regex,
+    step_connector_regex: Regex,
+    first_then_regex: Regex,
+    list_item_regex: Regex,
+    compound_task_regex: Regex,
     read_file_regex: Regex,
     search_regex: Regex,
     write_file_regex: Regex,
@@ -104,6 +122,17 @@ impl PromptAnalyzer {
     pub fn new() -> Self {
         Self {
             plan_regex: Self::build_keyword_regex(PLAN_KEYWORDS),
+            step_connector_regex: Self::build_keyword_regex(STEP_CONNECTORS),
+            first_then_regex: Regex::new(
+                r"(?is)\b(first|firstly|start by)\b.+\b(then|next|after that|afterwards|finally)\b",
+            )
+            .expect("Failed to compile first/then regex"),
+            list_item_regex: Regex::new(r"(?m)^\s*(\d+[.)]|[-*•])\s+\S")
+                .expect("Failed to compile list item regex"),
+            compound_task_regex: Regex::new(
+                r"(?i)\b(build|implement|create|set up|write|refactor|migrate|add)\b[^.?!\n]{0,60}\band\s+(then\s+)?(deploy|ship|release|publish|test|migrate|document)\b",
+            )
+            .expect("Failed to compile compound task regex"),
             read_file_regex: Self::build_keyword_regex(READ_FILE_KEYWORDS),
             search_regex: Self::build_keyword_regex(SEARCH_KEYWORDS),
             write_file_regex: Self::build_keyword_regex(WRITE_FILE_KEYWORDS),
@@ -123,6 +152,28 @@ impl PromptAnalyzer {
         Regex::new(&format!(r"(?i)\b({})\b", pattern)).expect("Failed to compile keyword regex")
     }
 
+    /// Whether a prompt looks like a multi-step task worth planning first.
+    ///
+    /// Deliberately conservative — only fires on a numbered/bulleted list of
+    /// 3+ items, "first … then …" sequencing, two or more step connectors,
+    /// or a compound task like "build and deploy". Questions, short prompts
+    /// and prompts that already ask for a plan never match.
+    pub fn suggests_plan(&self, prompt: &str) -> bool {
+        let trimmed = prompt.trim();
+        if trimmed.starts_with('/')
+            || trimmed.ends_with('?')
+            || trimmed.split_whitespace().count() < PLAN_SUGGEST_MIN_WORDS
+            || self.plan_regex.is_match(trimmed)
+        {
+            return false;
+        }
+
+        self.list_item_regex.find_iter(trimmed).count() >= 3
+            || self.first_then_regex.is_match(trimmed)
+            || self.step_connector_regex.find_iter(trimmed).count() >= 2
+            || self.compound_task_regex.is_match(trimmed)
+    }
+
     /// Analyze a prompt and transform it if needed
     pub fn analyze_and_transform(&self, prompt: &str) -> String {
         let mut transformations = Vec::new();
@@ -276,6 +327,46 @@ mod tests {
         assert!(result.contains("`web_search` tool"));
     }
 
+    #[test]
+    fn test_suggests_plan_for_multi_step_prompts() {
+        let analyzer = PromptAnalyzer::new();
+
+        for prompt in [
+            "First add a users table migration, then wire the signup endpoint to it",
+            "Build the release binary and deploy it to the staging server",
+            "1. Add a config flag\n2. Read it in the scheduler\n3. Document it in the README",
+            "- rename the module\n- update all imports\n- fix the failing tests",
+            "Refactor the parser into its own crate, then update the CLI, then publish a new version",
+        ] {
+            assert!(
+                analyzer.suggests_plan(prompt),
+                "expected a plan hint: {prompt}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_suggests_plan_stays_quiet_on_simple_prompts() {
+        let analyzer = PromptAnalyzer::new();
+
+        for prompt in [
+            "fix the typo in main.rs",
+            "explain how to use rust",
+            "read the file src/main.rs and explain it",
+            "What should I do first, then?",
+            "How do I build and deploy a rust binary to fly.io?",
+            "make a plan to build and deploy the app",
+            "Add a test for the parser and then run it",
+            "/compact then summarize",
+            "1. just one item\n2. and another",
+        ] {
+            assert!(
+                !analyzer.suggests_plan(prompt),
+                "unexpected plan hint: {prompt}"
+            );
+        }
+    }
+
     #[test]
     fn test_bash_detection() {
         let analyzer = PromptAnalyzer::new();
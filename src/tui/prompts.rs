@@ -0,0 +1,166 @@
+//! Prompt Templates
+//!
+//! Reusable prompts saved with `/save <name>` as `~/.opencrabs/prompts/<name>.md`
+//! and inserted with `/load <name>`. `{{placeholder}}` tokens are filled in
+//! one by one before the template lands in the input box.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_][A-Za-z0-9_ \-]*?)\s*\}\}").unwrap());
+
+/// Directory holding the templates
+pub fn prompts_dir() -> PathBuf {
+    crate::config::opencrabs_home().join("prompts")
+}
+
+/// Template names are plain file stems: letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Write `body` as template `name`, replacing any existing one.
+pub fn save(dir: &Path, name: &str, body: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{name}.md"));
+    std::fs::write(&path, body)?;
+    Ok(path)
+}
+
+/// Read template `name`.
+pub fn load(dir: &Path, name: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(dir.join(format!("{name}.md")))
+}
+
+/// Saved template names, sorted. Empty if the directory doesn't exist.
+pub fn list(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|p| p.file_stem()?.to_str().map(str::to_string))
+        .filter(|name| is_valid_name(name))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Distinct `{{placeholder}}` names in order of first appearance.
+pub fn placeholders(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for cap in PLACEHOLDER.captures_iter(template) {
+        let name = cap[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Replace every `{{placeholder}}` with its value. `values` pairs with
+/// `placeholders(template)`; unmatched tokens are left as-is.
+pub fn fill(template: &str, names: &[String], values: &[String]) -> String {
+    PLACEHOLDER
+        .replace_all(template, |cap: &regex::Captures| {
+            names
+                .iter()
+                .position(|n| n == &cap[1])
+                .and_then(|i| values.get(i))
+                .cloned()
+                .unwrap_or_else(|| cap[0].to_string())
+        })
+        .into_owned()
+}
+
+/// A `/load` in progress: collecting placeholder values before insertion.
+#[derive(Debug, Clone)]
+pub struct TemplateFill {
+    pub name: String,
+    pub template: String,
+    pub placeholders: Vec<String>,
+    pub values: Vec<String>,
+}
+
+impl TemplateFill {
+    /// The placeholder currently being asked for
+    pub fn current(&self) -> Option<&str> {
+        self.placeholders.get(self.values.len()).map(String::as_str)
+    }
+
+    /// Record a value; returns the filled template once all are in.
+    pub fn push(&mut self, value: String) -> Option<String> {
+        self.values.push(value);
+        if self.values.len() < self.placeholders.len() {
+            return None;
+        }
+        Some(fill(&self.template, &self.placeholders, &self.values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_and_fill() {
+        let tpl = "Review {{ file }} for {{focus}} issues. Then re-check {{file}}.";
+        let names = placeholders(tpl);
+        assert_eq!(names, vec!["file", "focus"]);
+
+        let filled = fill(tpl, &names, &["main.rs".into(), "security".into()]);
+        assert_eq!(
+            filled,
+            "Review main.rs for security issues. Then re-check main.rs."
+        );
+        assert!(placeholders("no tokens, just {braces}").is_empty());
+    }
+
+    #[test]
+    fn test_template_fill_steps() {
+        let template = "Explain {{topic}} to a {{audience}}".to_string();
+        let mut fill = TemplateFill {
+            name: "explain".into(),
+            placeholders: placeholders(&template),
+            template,
+            values: Vec::new(),
+        };
+        assert_eq!(fill.current(), Some("topic"));
+        assert_eq!(fill.push("lifetimes".into()), None);
+        assert_eq!(fill.current(), Some("audience"));
+        assert_eq!(
+            fill.push("beginner".into()).as_deref(),
+            Some("Explain lifetimes to a beginner")
+        );
+    }
+
+    #[test]
+    fn test_save_load_list_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompts = dir.path().join("prompts");
+        assert!(list(&prompts).is_empty());
+
+        save(&prompts, "review", "Review this code").unwrap();
+        save(&prompts, "a-first", "First").unwrap();
+        std::fs::write(prompts.join("notes.txt"), "ignored").unwrap();
+
+        assert_eq!(list(&prompts), vec!["a-first", "review"]);
+        assert_eq!(load(&prompts, "review").unwrap(), "Review this code");
+        assert!(load(&prompts, "missing").is_err());
+    }
+
+    #[test]
+    fn test_valid_names() {
+        assert!(is_valid_name("security_review-2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../etc/passwd"));
+        assert!(!is_valid_name("two words"));
+    }
+}
@@ -2,10 +2,12 @@
 //!
 //! Main chat view and thinking indicator.
 
-use super::super::app::App;
-use super::super::markdown::parse_markdown;
-use super::tools::{render_approve_menu, render_inline_approval, render_tool_group};
-use super::utils::wrap_line_with_padding;
+use super::super::app::{App, SLASH_COMMANDS};
+use super::super::markdown::{parse_markdown, parse_markdown_with_code};
+use super::tools::{
+    render_approve_menu, render_inline_approval, render_tool_group, render_variant_menu,
+};
+use super::utils::{format_thought_for, wrap_line_with_padding};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -15,13 +17,86 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
+/// Empty-chat greeting when `[tui] greeting` is unset
+const DEFAULT_GREETING: &str = "Hey! What are we working on today?";
+
+/// Commands suggested in the empty chat, described from `SLASH_COMMANDS`
+const EMPTY_STATE_COMMANDS: &[&str] = &["/help", "/models", "/sessions", "/onboard"];
+
+/// Example prompts suggested in the empty chat
+const EMPTY_STATE_PROMPTS: &[&str] = &[
+    "Summarise what changed in this repo since yesterday",
+    "Remind me to stretch every hour",
+];
+
+/// Lines shown while a session has no messages: `greeting` after the agent
+/// `emoji`, the active provider and model, example prompts and a few slash
+/// commands.
+fn empty_state_lines(
+    emoji: &str,
+    greeting: &str,
+    provider: &str,
+    model: &str,
+) -> Vec<Line<'static>> {
+    let accent = Style::default().fg(Color::Rgb(215, 100, 20));
+    let dim = Style::default().fg(Color::DarkGray);
+    let title = if emoji.is_empty() {
+        format!("  {}", greeting)
+    } else {
+        format!("  {} {}", emoji, greeting)
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(title, accent.add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Model  ", dim),
+            Span::styled(format!("{} · {}", provider, model), Style::default()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("  Try asking", dim)),
+    ];
+    for prompt in EMPTY_STATE_PROMPTS {
+        lines.push(Line::from(Span::styled(
+            format!("    “{}”", prompt),
+            Style::default().add_modifier(Modifier::ITALIC),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("  Commands", dim)));
+    for cmd in SLASH_COMMANDS
+        .iter()
+        .filter(|c| EMPTY_STATE_COMMANDS.contains(&c.name))
+    {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<11}", cmd.name), accent),
+            Span::styled(cmd.description, dim),
+        ]));
+    }
+    lines
+}
+
 /// Render the chat messages
 pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
     let mut lines: Vec<Line> = Vec::new();
+
+    // Empty-state greeting until the first message arrives ([tui] greeting)
+    let greeting = app.greeting.as_deref().unwrap_or(DEFAULT_GREETING);
+    if app.messages.is_empty() && !app.is_processing && !greeting.is_empty() {
+        lines.extend(empty_state_lines(
+            &app.agent.emoji,
+            greeting,
+            &app.provider_name(),
+            &app.default_model_name,
+        ));
+    }
     // Track which message index each rendered line belongs to (for click-to-copy)
     let mut line_to_msg: Vec<Option<usize>> = Vec::new();
 
     let content_width = area.width.saturating_sub(4) as usize; // borders + padding
+    // Prose wraps narrower on wide terminals when [tui] max_content_width is set
+    let text_width = app
+        .max_content_width
+        .map_or(content_width, |max| max.min(content_width));
 
     // Iterate by index to allow mutable access to render_cache while reading messages
     for msg_idx in 0..app.messages.len() {
@@ -43,6 +118,14 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
             continue;
         }
 
+        // Render /variants picker
+        if let Some(ref menu) = app.messages[msg_idx].variant_menu {
+            render_variant_menu(&mut lines, menu, content_width);
+            lines.push(Line::from(""));
+            line_to_msg.resize(lines.len(), None);
+            continue;
+        }
+
         // Render history paging marker
         if app.messages[msg_idx].role == "history_marker" {
             lines.push(Line::from(Span::styled(
@@ -138,11 +221,11 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
         let msg_id = app.messages[msg_idx].id;
         let cache_key = (msg_id, content_width as u16);
         if !app.render_cache.contains_key(&cache_key) {
-            let parsed = parse_markdown(&app.messages[msg_idx].content);
+            let parsed = parse_markdown_with_code(&app.messages[msg_idx].content);
             app.render_cache.insert(cache_key, parsed);
         }
         let content_lines = app.render_cache[&cache_key].clone();
-        for (i, line) in content_lines.into_iter().enumerate() {
+        for (i, (line, is_code)) in content_lines.into_iter().enumerate() {
             let mut padded_spans = if i == 0 {
                 if is_user {
                     // User: arrow prefix
@@ -164,7 +247,8 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
             };
             padded_spans.extend(line.spans);
             let padded_line = Line::from(padded_spans);
-            for wrapped in wrap_line_with_padding(padded_line, content_width, "  ") {
+            let wrap_width = if is_code { content_width } else { text_width };
+            for wrapped in wrap_line_with_padding(padded_line, wrap_width, "  ") {
                 if let Some(bg) = msg_bg {
                     // Apply bg to all spans and pad to full line width
                     let mut spans: Vec<Span> = wrapped
@@ -212,7 +296,7 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
                         padded_spans.push(Span::styled(span.content.to_string(), reasoning_style));
                     }
                     let padded_line = Line::from(padded_spans);
-                    for wrapped in wrap_line_with_padding(padded_line, content_width, "  ") {
+                    for wrapped in wrap_line_with_padding(padded_line, text_width, "  ") {
                         lines.push(wrapped);
                     }
                 }
@@ -250,7 +334,7 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "🦀 OpenCrabs ",
+                format!("{} ", app.agent.label()),
                 Style::default()
                     .fg(Color::Gray)
                     .add_modifier(Modifier::BOLD),
@@ -276,40 +360,29 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
         }
         lines.push(Line::from(spans));
 
-        // Render reasoning/thinking content above the response text (dimmed style)
-        if let Some(ref reasoning) = app.streaming_reasoning {
-            lines.push(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    "Thinking...",
-                    Style::default()
-                        .fg(Color::DarkGray)
-                        .add_modifier(Modifier::ITALIC | Modifier::BOLD),
-                ),
-            ]));
-            let reasoning_lines = parse_markdown(reasoning);
-            let reasoning_style = Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::ITALIC);
-            for line in reasoning_lines {
-                let mut padded_spans = vec![Span::styled("  ", Style::default())];
-                for span in line.spans {
-                    padded_spans.push(Span::styled(span.content.to_string(), reasoning_style));
-                }
-                let padded_line = Line::from(padded_spans);
-                for wrapped in wrap_line_with_padding(padded_line, content_width, "  ") {
-                    lines.push(wrapped);
-                }
-            }
+        // The answer has started: reasoning collapses to a one-line summary
+        // above it (full text stays available on the finished message)
+        if app.streaming_reasoning.is_some() {
+            let thought = app
+                .reasoning_elapsed
+                .or_else(|| app.reasoning_started_at.map(|t| t.elapsed()))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![Span::styled(
+                format!("  ▸ {}", format_thought_for(thought)),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            )]));
             lines.push(Line::from("")); // separator between reasoning and response
         }
 
-        let streaming_lines = parse_markdown(response);
-        for line in streaming_lines {
+        let streaming_lines = parse_markdown_with_code(response);
+        for (line, is_code) in streaming_lines {
             let mut padded_spans = vec![Span::raw("  ")];
             padded_spans.extend(line.spans);
             let padded_line = Line::from(padded_spans);
-            for wrapped in wrap_line_with_padding(padded_line, content_width, "  ") {
+            let wrap_width = if is_code { content_width } else { text_width };
+            for wrapped in wrap_line_with_padding(padded_line, wrap_width, "  ") {
                 lines.push(wrapped);
             }
         }
@@ -339,7 +412,7 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "🦀 OpenCrabs ",
+                format!("{} ", app.agent.label()),
                 Style::default()
                     .fg(Color::Gray)
                     .add_modifier(Modifier::BOLD),
@@ -383,7 +456,7 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
                 padded_spans.push(Span::styled(span.content.to_string(), reasoning_style));
             }
             let padded_line = Line::from(padded_spans);
-            for wrapped in wrap_line_with_padding(padded_line, content_width, "  ") {
+            for wrapped in wrap_line_with_padding(padded_line, text_width, "  ") {
                 lines.push(wrapped);
             }
         }
@@ -508,3 +581,35 @@ pub(super) fn render_chat(f: &mut Frame, app: &mut App, area: Rect) {
 
     f.render_widget(chat, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_empty_state_lists_model_and_commands() {
+        let out = text(&empty_state_lines(
+            "🦀",
+            "Hello there",
+            "anthropic",
+            "claude-x",
+        ));
+        assert!(out.starts_with("  🦀 Hello there"));
+        assert!(out.contains("anthropic · claude-x"));
+        assert!(out.contains("/models"));
+        assert!(out.contains("Switch model"));
+    }
+}
@@ -1,8 +1,9 @@
 //! Dialog rendering
 //!
-//! File picker, directory picker, model selector, usage dialog, and restart dialog.
+//! File picker, directory picker, model selector, usage dialog, restart dialog,
+//! memory search view, and command palette.
 
-use super::super::app::App;
+use super::super::app::{App, ClickTarget, MEMORY_PAGE_SIZE};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -18,7 +19,11 @@ pub(super) fn render_file_picker(f: &mut Frame, app: &App, area: Rect) {
     // Header
     lines.push(Line::from(vec![
         Span::styled(
-            "📁 File Picker",
+            if app.file_picker_fuzzy {
+                "🔎 Fuzzy Find"
+            } else {
+                "📁 File Picker"
+            },
             Style::default()
                 .fg(Color::Rgb(120, 120, 120))
                 .add_modifier(Modifier::BOLD),
@@ -29,10 +34,28 @@ pub(super) fn render_file_picker(f: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(Color::Rgb(215, 100, 20)),
         ),
     ]));
+    if app.file_picker_fuzzy {
+        lines.push(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Rgb(215, 100, 20))),
+            Span::styled(
+                format!("{}▏", app.file_picker_query),
+                Style::default().fg(Color::Reset),
+            ),
+            Span::styled(
+                format!(
+                    "  {} of {} files",
+                    app.file_picker_files.len(),
+                    app.file_picker_index.len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
     lines.push(Line::from(""));
 
     // Calculate visible range
-    let visible_items = (area.height as usize).saturating_sub(6); // Leave space for header and help
+    let header_rows = if app.file_picker_fuzzy { 7 } else { 6 };
+    let visible_items = (area.height as usize).saturating_sub(header_rows); // Leave space for header and help
     let start = app.file_picker_scroll_offset;
     let end = (start + visible_items).min(app.file_picker_files.len());
 
@@ -55,7 +78,18 @@ pub(super) fn render_file_picker(f: &mut Frame, app: &App, area: Rect) {
             "📄"
         };
 
-        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+        // Fuzzy results show where the file lives, not just its name
+        let filename = if app.file_picker_fuzzy {
+            path.strip_prefix(&app.file_picker_current_dir)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string()
+        } else {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("?")
+                .to_string()
+        };
 
         let style = if is_selected {
             Style::default()
@@ -107,6 +141,20 @@ pub(super) fn render_file_picker(f: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(" Select  ", Style::default().fg(Color::Reset)),
+        Span::styled(
+            "[Ctrl+F]",
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            if app.file_picker_fuzzy {
+                " Browse  "
+            } else {
+                " Fuzzy find  "
+            },
+            Style::default().fg(Color::Reset),
+        ),
         Span::styled(
             "[Esc]",
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -261,7 +309,7 @@ pub(super) fn render_directory_picker(f: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Render the model selector dialog - matches onboarding ProviderAuth style
-pub(super) fn render_model_selector(f: &mut Frame, app: &App, area: Rect) {
+pub(super) fn render_model_selector(f: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::onboarding::PROVIDERS;
 
     const BRAND_BLUE: Color = Color::Rgb(120, 120, 120);
@@ -314,10 +362,13 @@ pub(super) fn render_model_selector(f: &mut Frame, app: &App, area: Rect) {
     let dialog_area = h_chunks[1];
 
     let mut lines: Vec<Line> = Vec::new();
+    // (line index, target) for click-to-select
+    let mut clickable: Vec<(usize, ClickTarget)> = Vec::new();
     lines.push(Line::from(""));
 
     // Provider list (like onboarding)
     for (i, provider) in PROVIDERS.iter().enumerate() {
+        clickable.push((lines.len(), ClickTarget::Provider(i)));
         let selected = i == provider_idx;
         let focused = focused_field == 0;
 
@@ -546,6 +597,7 @@ pub(super) fn render_model_selector(f: &mut Frame, app: &App, area: Rect) {
 
             let suffix = if active { " (active)" } else { "" };
 
+            clickable.push((lines.len(), ClickTarget::Model(i)));
             lines.push(Line::from(vec![
                 Span::styled(prefix, style),
                 Span::styled(*model, style),
@@ -644,6 +696,16 @@ pub(super) fn render_model_selector(f: &mut Frame, app: &App, area: Rect) {
             )),
     );
     f.render_widget(dialog, dialog_area);
+
+    // No wrapping here, so each line is one row inside the border
+    let inner = Block::default().borders(Borders::ALL).inner(dialog_area);
+    for (line_idx, target) in clickable {
+        let row = inner.y + line_idx as u16;
+        if row < inner.bottom() {
+            app.click_regions
+                .push((Rect::new(inner.x, row, inner.width, 1), target));
+        }
+    }
 }
 
 /// Estimate cost for a model/token pair using the same pricing table as the provider.
@@ -877,35 +939,80 @@ pub(super) fn render_usage_dialog(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(dialog, dialog_area);
 }
 
-/// Render restart confirmation dialog
+/// Render restart confirmation dialog. When the rebuild changed the source
+/// tree, it lists the changed files (or the full diff, toggled with `d`) so
+/// the user can review self-modified code before restarting into it.
 pub(super) fn render_restart_dialog(f: &mut Frame, app: &App, area: Rect) {
     let status = app.rebuild_status.as_deref().unwrap_or("Build successful");
+    let diff = app.rebuild_diff.as_ref();
+    let expanded = app.rebuild_diff_expanded && diff.is_some();
+
+    // Body: changed files, or the full patch when expanded
+    let body: Vec<Line> = match diff {
+        Some(diff) if expanded => diff
+            .patch
+            .lines()
+            .map(|line| {
+                let color = if line.starts_with("+++") || line.starts_with("---") {
+                    Color::White
+                } else if line.starts_with('+') {
+                    Color::Green
+                } else if line.starts_with('-') {
+                    Color::Red
+                } else if line.starts_with("@@") {
+                    Color::Cyan
+                } else {
+                    Color::Gray
+                };
+                Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+            })
+            .collect(),
+        Some(diff) => diff
+            .files
+            .iter()
+            .map(|file| {
+                Line::from(vec![
+                    Span::raw(format!("  {}  ", file.path)),
+                    Span::styled(
+                        format!("+{}", file.insertions),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("−{}", file.deletions),
+                        Style::default().fg(Color::Red),
+                    ),
+                ])
+            })
+            .collect(),
+        None => Vec::new(),
+    };
 
-    let dialog_height = 8u16;
-    let dialog_width = 50u16.min(area.width.saturating_sub(4));
-
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(40),
-            Constraint::Length(dialog_height),
-            Constraint::Percentage(40),
-        ])
-        .split(area);
-
-    let horizontal = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Min((area.width.saturating_sub(dialog_width)) / 2),
-            Constraint::Length(dialog_width),
-            Constraint::Min(0),
-        ])
-        .split(vertical[1]);
-
-    let dialog_area = horizontal[1];
+    let (dialog_width, dialog_height) = if expanded {
+        (area.width * 9 / 10, area.height * 9 / 10)
+    } else if diff.is_some() {
+        (72, 9 + body.len().min(12) as u16)
+    } else {
+        (50, 8)
+    };
+    let dialog_width = dialog_width.min(area.width.saturating_sub(4));
+    let dialog_height = dialog_height.min(area.height.saturating_sub(2));
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+        y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
     f.render_widget(Clear, dialog_area);
 
-    let lines = vec![
+    let block = Block::default()
+        .title(" Rebuild Complete ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let mut header = vec![
         Line::from(""),
         Line::from(Span::styled(
             format!("  {}", status),
@@ -913,30 +1020,269 @@ pub(super) fn render_restart_dialog(f: &mut Frame, app: &App, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         )),
+    ];
+    if let Some(diff) = diff {
+        header.push(Line::from(Span::styled(
+            format!("  {}", diff.summary()),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    header.push(Line::from(""));
+
+    let key = |label: &'static str, color: Color| {
+        Span::styled(
+            label,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut keys = vec![key("  [Enter] ", Color::Cyan), Span::raw("Restart  ")];
+    if diff.is_some() {
+        keys.push(key("[D] ", Color::Cyan));
+        keys.push(Span::raw(if expanded { "Files  " } else { "Full diff  " }));
+        keys.push(key("[↑↓] ", Color::Cyan));
+        keys.push(Span::raw("Scroll  "));
+    }
+    keys.push(key("[Esc] ", Color::Red));
+    keys.push(Span::raw("Cancel"));
+    let footer = vec![
+        Line::from(if diff.is_some() {
+            "  Restart with the modified source?"
+        } else {
+            "  Restart with new binary?"
+        }),
         Line::from(""),
-        Line::from("  Restart with new binary?"),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "  [Enter] ",
+        Line::from(keys),
+    ];
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(header.len() as u16),
+            Constraint::Min(0),
+            Constraint::Length(footer.len() as u16),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(header), chunks[0]);
+    let max_scroll = (body.len() as u16).saturating_sub(chunks[1].height);
+    f.render_widget(
+        Paragraph::new(body).scroll((app.rebuild_diff_scroll.min(max_scroll), 0)),
+        chunks[1],
+    );
+    f.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
+/// Render the /memory search view: ranked results with their file and a
+/// snippet, one page at a time.
+pub(super) fn render_memory_search(f: &mut Frame, app: &App, area: Rect) {
+    let Some(view) = app.memory_view.as_ref() else {
+        return;
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    if view.loading {
+        lines.push(Line::from(Span::styled("  Searching…", dim)));
+    } else if let Some(error) = &view.error {
+        lines.push(Line::from(Span::styled(
+            format!("  Search failed: {error}"),
+            Style::default().fg(Color::Red),
+        )));
+    } else if view.results.is_empty() {
+        lines.push(Line::from(Span::styled("  No matches.", dim)));
+    } else {
+        let home = crate::config::opencrabs_home();
+        let start = view.page() * MEMORY_PAGE_SIZE;
+        for (i, result) in view
+            .results
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(MEMORY_PAGE_SIZE)
+        {
+            let selected = i == view.selected;
+            let path = std::path::Path::new(&result.path)
+                .strip_prefix(&home)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| result.path.clone());
+            let (marker, path_style) = if selected {
+                (
+                    "▶ ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default().fg(Color::Reset))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {marker}{:>2}. ", i + 1), path_style),
+                Span::styled(path, path_style),
+                Span::styled(format!("  {:.3}", result.rank), dim),
+            ]));
+            let snippet: String = result
+                .snippet
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .chars()
+                .take(160)
+                .collect();
+            lines.push(Line::from(Span::styled(format!("       {snippet}"), dim)));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let key = |label: &'static str| {
+        Span::styled(
+            label,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut footer = vec![
+        key("  [↑↓] "),
+        Span::raw("Select  "),
+        key("[Enter] "),
+        Span::raw("Add to context  "),
+    ];
+    if view.page_count() > 1 {
+        footer.push(key("[PgUp/PgDn] "));
+        footer.push(Span::raw(format!(
+            "Page {}/{}  ",
+            view.page() + 1,
+            view.page_count()
+        )));
+    }
+    footer.push(Span::styled("[Esc] ", Style::default().fg(Color::Red)));
+    footer.push(Span::raw("Close"));
+    lines.push(Line::from(footer));
+
+    let dialog_width = 90u16.min(area.width.saturating_sub(4));
+    let dialog_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+        y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+    f.render_widget(Clear, dialog_area);
+    let title = format!(" Memory: {} ({} results) ", view.query, view.results.len());
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(120, 120, 120)))
+            .title(Span::styled(
+                title,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(Color::Rgb(120, 120, 120))
                     .add_modifier(Modifier::BOLD),
+            )),
+    );
+    f.render_widget(dialog, dialog_area);
+}
+
+/// Render the Ctrl+P command palette over the chat: query line, then the
+/// matching actions and slash commands with their keys.
+pub(super) fn render_command_palette(f: &mut Frame, app: &App, area: Rect) {
+    let Some(palette) = app.command_palette.as_ref() else {
+        return;
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+    let dialog_width = 72u16.min(area.width.saturating_sub(4));
+    let dialog_height = 20u16.min(area.height.saturating_sub(2));
+    // Borders, query line, blank lines around the list, footer
+    let visible = (dialog_height as usize).saturating_sub(6).max(1);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(Color::Rgb(215, 100, 20))),
+            Span::styled(
+                format!("{}▏", palette.query),
+                Style::default().fg(Color::Reset),
             ),
-            Span::raw("Restart  "),
             Span::styled(
-                "[Esc] ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                format!("  {} of {}", palette.filtered.len(), palette.entries.len()),
+                dim,
             ),
-            Span::raw("Cancel"),
         ]),
+        Line::from(""),
     ];
 
+    if palette.filtered.is_empty() {
+        lines.push(Line::from(Span::styled("  No matching commands.", dim)));
+    }
+    // Keep the selection on screen
+    let start = palette.selected.saturating_sub(visible - 1);
+    let inner_width = dialog_width.saturating_sub(2) as usize;
+    for (row, &idx) in palette
+        .filtered
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+    {
+        let entry = &palette.entries[idx];
+        let selected = row == palette.selected;
+        let label_style = if selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Reset)
+        };
+        let keys = entry.keys.as_deref().unwrap_or("");
+        let mut text = format!(" {}{:<20}", if selected { "▶ " } else { "  " }, entry.label);
+        let description_width =
+            inner_width.saturating_sub(text.chars().count() + keys.chars().count() + 2);
+        let description: String = entry.description.chars().take(description_width).collect();
+        let padding = inner_width.saturating_sub(
+            text.chars().count() + description.chars().count() + keys.chars().count() + 1,
+        );
+        lines.push(Line::from(vec![
+            Span::styled(text, label_style),
+            Span::styled(description, dim),
+            Span::raw(" ".repeat(padding)),
+            Span::styled(keys.to_string(), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let key = |label: &'static str| {
+        Span::styled(
+            label,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    lines.push(Line::from(vec![
+        key("  [↑↓] "),
+        Span::raw("Select  "),
+        key("[Enter] "),
+        Span::raw("Run  "),
+        Span::styled("[Esc] ", Style::default().fg(Color::Red)),
+        Span::raw("Close"),
+    ]));
+
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+        y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+    f.render_widget(Clear, dialog_area);
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Rebuild Complete ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(Style::default().fg(Color::Rgb(120, 120, 120)))
+            .title(Span::styled(
+                " Command Palette ",
+                Style::default()
+                    .fg(Color::Rgb(120, 120, 120))
+                    .add_modifier(Modifier::BOLD),
+            )),
     );
     f.render_widget(dialog, dialog_area);
 }
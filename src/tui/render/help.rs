@@ -50,6 +50,7 @@ pub(super) fn render_help(f: &mut Frame, app: &App, area: Rect) {
         kv("Ctrl+N", "New session", cyan),
         kv("Ctrl+L", "List sessions", cyan),
         kv("Ctrl+K", "Clear session", cyan),
+        kv("Ctrl+P", "Command palette", cyan),
         Line::from(""),
         section_header("CHAT"),
         kv("Enter", "Send message", cyan),
@@ -57,6 +58,8 @@ pub(super) fn render_help(f: &mut Frame, app: &App, area: Rect) {
         kv("Escape (x2)", "Clear input / abort", cyan),
         kv("Page Up/Down", "Scroll history", cyan),
         kv("@", "File picker", cyan),
+        kv("Ctrl+T", "Plan first (when the hint shows)", cyan),
+        kv("Ctrl+E", "Explain a failed tool call", cyan),
         Line::from(""),
         section_header("INPUT EDITING"),
         kv("↑ / ↓", "Line nav / start-end / history", cyan),
@@ -67,25 +70,12 @@ pub(super) fn render_help(f: &mut Frame, app: &App, area: Rect) {
         kv("Ctrl+U", "Delete to line start (vim)", cyan),
         Line::from(""),
         section_header("SLASH COMMANDS"),
-        kv("/help", "Show this screen", cyan),
-        kv("/models", "Switch model", cyan),
-        kv("/usage", "Token & cost stats", cyan),
-        kv("/onboard", "Setup wizard (start)", cyan),
-        kv("/onboard:provider", "Jump to AI provider setup", cyan),
-        kv("/onboard:workspace", "Jump to workspace settings", cyan),
-        kv("/onboard:channels", "Jump to channel config", cyan),
-        kv("/onboard:voice", "Jump to voice STT/TTS setup", cyan),
-        kv("/onboard:image", "Jump to image handling setup", cyan),
-        kv("/onboard:brain", "Jump to brain/persona setup", cyan),
-        kv("/doctor", "Run connection health check", cyan),
-        kv("/sessions", "Session manager", cyan),
-        kv("/approve", "Tool approval policy", cyan),
-        kv("/compact", "Compact context now", cyan),
-        kv("/rebuild", "Build & restart from source", cyan),
-        kv("/evolve", "Download latest release & restart", cyan),
-        kv("/cd", "Change working directory", cyan),
-        kv("/whisper", "Speak anywhere, paste to clipboard", cyan),
     ];
+    left.extend(
+        crate::tui::app::SLASH_COMMANDS
+            .iter()
+            .map(|cmd| kv(cmd.name, cmd.description, cyan)),
+    );
 
     // Append user-defined commands from commands.toml
     let brain_path = crate::brain::BrainLoader::resolve_path();
@@ -178,10 +178,47 @@ pub(super) fn render_input(f: &mut Frame, app: &App, area: Rect) {
         .title_bottom(context_title)
         .border_style(border_style);
 
+    if app.shows_plan_hint() {
+        let key = crate::tui::events::keys::binding_label(crate::tui::keymap::Action::PlanFirst)
+            .unwrap_or_default();
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" This looks like a multi-step task — {key} to plan it first "),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )));
+    }
+
     if !app.attachments.is_empty() {
         block = block.title(attach_title);
     }
 
+    if let Some(ref fill) = app.template_fill
+        && let Some(current) = fill.current()
+    {
+        block = block.title(Line::from(Span::styled(
+            format!(
+                " {{{{{}}}}} for '{}' ({}/{}) — Enter to confirm, Esc to cancel ",
+                current,
+                fill.name,
+                fill.values.len() + 1,
+                fill.placeholders.len()
+            ),
+            Style::default()
+                .fg(Color::Rgb(215, 100, 20))
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    if app.pending_login.is_some() {
+        block = block.title(Line::from(Span::styled(
+            " Paste the authorization code — Enter to confirm, Esc to cancel ",
+            Style::default()
+                .fg(Color::Rgb(215, 100, 20))
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
     let input = Paragraph::new(input_lines)
         .style(Style::default().fg(Color::Reset))
         .block(block);
@@ -201,13 +238,21 @@ pub(super) fn render_slash_autocomplete(f: &mut Frame, app: &App, input_area: Re
     let pad_x: u16 = 1;
     let pad_y: u16 = 1;
     let height = count + 2 + pad_y * 2; // +2 for borders, +2 for top/bottom padding
+    // Name column fits the longest match so descriptions stay aligned
+    let name_width = app
+        .slash_filtered
+        .iter()
+        .map(|&idx| app.slash_command_name(idx).unwrap_or("").chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(10);
     let max_content_width = app
         .slash_filtered
         .iter()
         .map(|&idx| {
             let desc = app.slash_command_description(idx).unwrap_or("");
-            // pad + " " + 10-char name + " " + desc + " " + pad
-            pad_x + 1 + 10 + 1 + desc.len() as u16 + 1 + pad_x
+            // pad + " " + name column + " " + desc + " " + pad
+            pad_x + 1 + name_width as u16 + 1 + desc.len() as u16 + 1 + pad_x
         })
         .max()
         .unwrap_or(40);
@@ -246,7 +291,7 @@ pub(super) fn render_slash_autocomplete(f: &mut Frame, app: &App, input_area: Re
             };
 
             Line::from(vec![
-                Span::styled(format!("  {:<10}", name), style),
+                Span::styled(format!("  {:<name_width$}", name), style),
                 Span::styled(format!(" {} ", desc), desc_style),
             ])
         })
@@ -385,7 +430,7 @@ pub(super) fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         ("🔒 approve", Color::DarkGray)
     };
 
-    let spans = vec![
+    let mut spans = vec![
         Span::styled(
             session_text,
             Style::default().fg(orange).add_modifier(Modifier::BOLD),
@@ -398,6 +443,36 @@ pub(super) fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         Span::styled(policy_text, Style::default().fg(policy_color)),
     ];
 
+    // --- Channels stopped with /pause ---
+    let paused = crate::channels::paused_channels();
+    if !paused.is_empty() {
+        let names: Vec<&str> = paused.iter().map(|s| s.name()).collect();
+        spans.push(Span::styled(sep_text, Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("⏸ {}", names.join(", ")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    // --- Live estimate of what the input would cost to send ---
+    if !app.input_buffer.trim().is_empty() {
+        let input_tokens =
+            crate::brain::tokenizer::count_tokens_for_model(&model_str, &app.input_buffer);
+        // Red when sending it would overflow the context window
+        let projected = app.last_input_tokens.unwrap_or(0) as usize + input_tokens;
+        let estimate_color =
+            if app.context_max_tokens > 0 && projected > app.context_max_tokens as usize {
+                Color::Red
+            } else {
+                Color::DarkGray
+            };
+        spans.push(Span::styled(sep_text, Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("~{} input", format_token_count_raw(input_tokens as i32)),
+            Style::default().fg(estimate_color),
+        ));
+    }
+
     let line = Line::from(spans);
     let para = Paragraph::new(line).alignment(Alignment::Left);
     f.render_widget(para, area);
@@ -29,8 +29,8 @@ use unicode_width::UnicodeWidthStr;
 
 use chat::render_chat;
 use dialogs::{
-    render_directory_picker, render_file_picker, render_model_selector, render_restart_dialog,
-    render_usage_dialog,
+    render_command_palette, render_directory_picker, render_file_picker, render_memory_search,
+    render_model_selector, render_restart_dialog, render_usage_dialog,
 };
 use help::{render_help, render_settings};
 use input::{render_emoji_picker, render_input, render_slash_autocomplete, render_status_bar};
@@ -39,10 +39,12 @@ use sessions::render_sessions;
 
 /// Render the entire UI
 pub fn render(f: &mut Frame, app: &mut App) {
+    app.click_regions.clear();
+
     if app.mode == AppMode::Splash {
         let config = crate::config::Config::load().unwrap_or_default();
         let (provider, model) = crate::config::resolve_provider_from_config(&config);
-        splash::render_splash(f, f.area(), provider, model);
+        splash::render_splash(f, f.area(), &config.agent.label(), provider, model);
         return;
     }
 
@@ -131,6 +133,8 @@ pub fn render(f: &mut Frame, app: &mut App) {
                 render_thinking_indicator(f, app, chunks[2]);
             }
             render_input(f, app, chunks[3]);
+            app.click_regions
+                .push((chunks[3], crate::tui::app::ClickTarget::Input));
             render_status_bar(f, app, chunks[4]);
             if app.slash_suggestions_active {
                 render_slash_autocomplete(f, app, chunks[3]);
@@ -140,17 +144,17 @@ pub fn render(f: &mut Frame, app: &mut App) {
         }
         AppMode::Sessions => {
             let (title_area, content_area) = split_title_area(full_content_area);
-            render_app_title(f, title_area);
+            render_app_title(f, app, title_area);
             render_sessions(f, app, content_area);
         }
         AppMode::Help => {
             let (title_area, content_area) = split_title_area(full_content_area);
-            render_app_title(f, title_area);
+            render_app_title(f, app, title_area);
             render_help(f, app, content_area);
         }
         AppMode::Settings => {
             let (title_area, content_area) = split_title_area(full_content_area);
-            render_app_title(f, title_area);
+            render_app_title(f, app, title_area);
             render_settings(f, app, content_area);
         }
         AppMode::FilePicker => {
@@ -195,10 +199,34 @@ pub fn render(f: &mut Frame, app: &mut App) {
             render_status_bar(f, app, chunks[4]);
             render_restart_dialog(f, app, f.area());
         }
+        AppMode::MemorySearch => {
+            render_chat(f, app, chunks[0]);
+            if plan_height > 0 {
+                render_plan_checklist(f, app, chunks[1]);
+            }
+            if thinking_height > 0 {
+                render_thinking_indicator(f, app, chunks[2]);
+            }
+            render_input(f, app, chunks[3]);
+            render_status_bar(f, app, chunks[4]);
+            render_memory_search(f, app, f.area());
+        }
+        AppMode::CommandPalette => {
+            render_chat(f, app, chunks[0]);
+            if plan_height > 0 {
+                render_plan_checklist(f, app, chunks[1]);
+            }
+            if thinking_height > 0 {
+                render_thinking_indicator(f, app, chunks[2]);
+            }
+            render_input(f, app, chunks[3]);
+            render_status_bar(f, app, chunks[4]);
+            render_command_palette(f, app, f.area());
+        }
     }
 }
 
-/// Render the sticky "OpenCrabs is thinking..." spinner row.
+/// Render the sticky "<agent name> is thinking..." spinner row.
 /// This sits between the chat area and the input box so it is always visible
 /// and never scrolls away with chat history.
 fn render_thinking_indicator(f: &mut Frame, app: &App, area: Rect) {
@@ -218,7 +246,7 @@ fn render_thinking_indicator(f: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            "OpenCrabs is thinking...",
+            format!("{} is thinking...", app.agent.name),
             Style::default().fg(Color::Rgb(215, 100, 20)),
         ),
     ];
@@ -257,9 +285,9 @@ fn split_title_area(area: Rect) -> (Rect, Rect) {
 }
 
 /// Render the app name header used on Sessions, Help, and Settings screens.
-fn render_app_title(f: &mut Frame, area: Rect) {
+fn render_app_title(f: &mut Frame, app: &App, area: Rect) {
     let para = Paragraph::new(vec![Line::from(Span::styled(
-        " 🦀 OpenCrabs AI Orchestration Agent",
+        format!(" {} AI Orchestration Agent", app.agent.label()),
         Style::default()
             .fg(Color::Rgb(120, 120, 120))
             .add_modifier(Modifier::BOLD),
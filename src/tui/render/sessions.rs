@@ -2,7 +2,7 @@
 //!
 //! Session manager view with navigation, renaming, and status indicators.
 
-use super::super::app::App;
+use super::super::app::{App, ClickTarget};
 use super::utils::{format_token_count_raw, format_token_count_with_label};
 use ratatui::{
     Frame,
@@ -13,7 +13,7 @@ use ratatui::{
 };
 
 /// Render the sessions list
-pub(super) fn render_sessions(f: &mut Frame, app: &App, area: Rect) {
+pub(super) fn render_sessions(f: &mut Frame, app: &mut App, area: Rect) {
     let mut lines: Vec<Line> = Vec::new();
 
     lines.push(Line::from(vec![
@@ -56,6 +56,17 @@ pub(super) fn render_sessions(f: &mut Frame, app: &App, area: Rect) {
         ),
         Span::styled("Back", Style::default().fg(Color::Reset)),
     ]));
+    if let Some(ref action) = app.pending_destructive {
+        lines.push(Line::from(Span::styled(
+            format!("  {} [y/N]", action.prompt()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    } else if let Some((ref undo, _)) = app.undo {
+        lines.push(Line::from(Span::styled(
+            format!("  {} — [U] Undo", undo.describe()),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
     lines.push(Line::from(""));
 
     for (idx, session) in app.sessions.iter().enumerate() {
@@ -71,7 +82,7 @@ pub(super) fn render_sessions(f: &mut Frame, app: &App, area: Rect) {
         let prefix = if is_selected { "  > " } else { "    " };
 
         let name = session.title.as_deref().unwrap_or("Untitled");
-        let created = session.created_at.format("%Y-%m-%d %H:%M");
+        let created = crate::config::format_local(session.created_at, "%Y-%m-%d %H:%M");
 
         // Format session total usage (cumulative billing tokens)
         let history_label = format_token_count_with_label(session.token_count, "total");
@@ -209,9 +220,30 @@ pub(super) fn render_sessions(f: &mut Frame, app: &App, area: Rect) {
         }
     }
 
+    // Clickable rows: lines after the 2-line header are one session each,
+    // possibly wrapped over several rows
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let mut regions = Vec::new();
+    let mut row = inner.y;
+    for (line_idx, line) in lines.iter().enumerate() {
+        if row >= inner.bottom() {
+            break;
+        }
+        let height = (line.width().div_ceil(inner.width.max(1) as usize).max(1) as u16)
+            .min(inner.bottom() - row);
+        if let Some(session_idx) = line_idx.checked_sub(2) {
+            regions.push((
+                Rect::new(inner.x, row, inner.width, height),
+                ClickTarget::Session(session_idx),
+            ));
+        }
+        row += height;
+    }
+
     let sessions = Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL).title(" Sessions "))
         .wrap(Wrap { trim: false });
 
     f.render_widget(sessions, area);
+    app.click_regions.extend(regions);
 }
@@ -1,6 +1,7 @@
 //! Tool call rendering
 //!
-//! Tool group display, inline approval dialogs, and approval policy menu.
+//! Tool group display, inline approval dialogs, approval policy menu, and the
+//! /variants picker.
 
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -130,6 +131,18 @@ pub(super) fn render_tool_group<'a>(
 
             // If the call is still in-flight, show a running indicator
             if in_flight {
+                for live_line in &call.live_output {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("    {}  ", continuation),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled(
+                            live_line.clone(),
+                            Style::default().fg(Color::Rgb(90, 90, 90)),
+                        ),
+                    ]));
+                }
                 let spinner_frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
                 let frame = spinner_frames[animation_frame % spinner_frames.len()];
                 lines.push(Line::from(vec![
@@ -195,6 +208,18 @@ pub(super) fn render_tool_group<'a>(
                 Span::styled("    └─ ".to_string(), Style::default().fg(Color::DarkGray)),
                 Span::styled(last.description.clone(), style),
             ]));
+            // Latest live output line while the tool is still running
+            if last.details.is_none()
+                && let Some(live_line) = last.live_output.last()
+            {
+                lines.push(Line::from(vec![
+                    Span::styled("       ".to_string(), Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        live_line.clone(),
+                        Style::default().fg(Color::Rgb(90, 90, 90)),
+                    ),
+                ]));
+            }
         }
     }
 }
@@ -253,13 +278,18 @@ pub(super) fn render_inline_approval<'a>(
                             _ => vec![value.to_string()],
                         };
                         let total = value_lines.len();
+                        // A patch previews as a diff so the exact change is visible
+                        let is_diff = key == "patch";
                         for vline in value_lines.iter().take(60) {
+                            let color = match vline.chars().next() {
+                                Some('+') if is_diff => Color::Green,
+                                Some('-') if is_diff => Color::Red,
+                                Some('@') if is_diff => Color::Cyan,
+                                _ => Color::Rgb(200, 200, 200),
+                            };
                             lines.push(Line::from(vec![
                                 Span::styled("      ", Style::default()),
-                                Span::styled(
-                                    vline.clone(),
-                                    Style::default().fg(Color::Rgb(200, 200, 200)),
-                                ),
+                                Span::styled(vline.clone(), Style::default().fg(color)),
                             ]));
                         }
                         if total > 60 {
@@ -295,7 +325,7 @@ pub(super) fn render_inline_approval<'a>(
             }
 
             // "Do you approve?" + vertical option list with ❯ selector
-            // Order: Yes(0), Always(1), No(2)
+            // Order: Yes(0), Always(1), No(2), No, stop(3)
             lines.push(Line::from(vec![Span::styled(
                 "  Do you approve?",
                 Style::default().fg(Color::DarkGray),
@@ -304,6 +334,7 @@ pub(super) fn render_inline_approval<'a>(
                 ("Yes", Color::Cyan),
                 ("Always", Color::Rgb(215, 100, 20)),
                 ("No", Color::Red),
+                ("No, stop this turn", Color::Red),
             ];
             for (i, (label, color)) in options.iter().enumerate() {
                 if i == approval.selected_option {
@@ -434,3 +465,94 @@ pub(super) fn render_approve_menu<'a>(
         }
     }
 }
+
+/// Lines of the highlighted variant shown in the /variants picker
+const VARIANT_PREVIEW_LINES: usize = 12;
+
+/// Render the /variants picker: one row per variant, with the highlighted
+/// one previewed underneath
+pub(super) fn render_variant_menu<'a>(
+    lines: &mut Vec<Line<'a>>,
+    menu: &super::super::app::VariantMenu,
+    content_width: usize,
+) {
+    use super::super::app::VariantMenuState;
+
+    let first_line = |text: &str| -> String {
+        let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        let max = content_width.saturating_sub(20).max(20);
+        if line.chars().count() > max {
+            format!("{}…", line.chars().take(max).collect::<String>())
+        } else {
+            line.to_string()
+        }
+    };
+
+    match &menu.state {
+        VariantMenuState::Pending => {
+            let gold = Color::Rgb(215, 100, 20);
+            lines.push(Line::from(vec![Span::styled(
+                format!("  RESPONSE VARIANTS ({})", menu.variants.len()),
+                Style::default().fg(gold).add_modifier(Modifier::BOLD),
+            )]));
+            lines.push(Line::from(""));
+
+            for (i, variant) in menu.variants.iter().enumerate() {
+                let is_selected = i == menu.selected_option;
+                let prefix = if is_selected { "\u{25b6} " } else { "  " };
+                let style = if is_selected {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Reset)
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(format!("{}{}. ", prefix, i + 1), style),
+                    Span::styled(first_line(variant), style),
+                ]));
+
+                if is_selected {
+                    let total = variant.lines().count();
+                    for line in variant.lines().take(VARIANT_PREVIEW_LINES) {
+                        lines.push(Line::from(vec![
+                            Span::raw("      "),
+                            Span::styled(line.to_string(), Style::default().fg(Color::Gray)),
+                        ]));
+                    }
+                    if total > VARIANT_PREVIEW_LINES {
+                        lines.push(Line::from(Span::styled(
+                            format!("      … {} more lines", total - VARIANT_PREVIEW_LINES),
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::ITALIC),
+                        )));
+                    }
+                }
+            }
+
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  [\u{2191}\u{2193}] Navigate  [Enter] Keep  [Esc] Discard all",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        VariantMenuState::Kept(choice) => {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  Kept variant {} of {}", choice + 1, menu.variants.len()),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )]));
+        }
+        VariantMenuState::Discarded => {
+            lines.push(Line::from(vec![Span::styled(
+                "  Variants discarded",
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            )]));
+        }
+    }
+}
@@ -112,6 +112,16 @@ pub(super) fn format_token_count_with_label(tokens: i32, label: &str) -> String
     }
 }
 
+/// Collapsed reasoning summary, e.g. "Thought for 12s" or "Thought for 1m 05s"
+pub(super) fn format_thought_for(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs().max(1);
+    if secs >= 60 {
+        format!("Thought for {}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("Thought for {}s", secs)
+    }
+}
+
 /// Format token count as raw number without label (e.g. "150K", "1.2M")
 pub(super) fn format_token_count_raw(tokens: i32) -> String {
     let tokens = tokens.max(0) as f64;
@@ -130,6 +140,25 @@ pub(super) fn format_token_count_raw(tokens: i32) -> String {
 mod tests {
     use super::*;
 
+    // ── format_thought_for ──────────────────────────────────────────
+
+    #[test]
+    fn test_format_thought_for() {
+        use std::time::Duration;
+        assert_eq!(
+            format_thought_for(Duration::from_millis(300)),
+            "Thought for 1s"
+        );
+        assert_eq!(
+            format_thought_for(Duration::from_secs(12)),
+            "Thought for 12s"
+        );
+        assert_eq!(
+            format_thought_for(Duration::from_secs(65)),
+            "Thought for 1m 05s"
+        );
+    }
+
     // ── char_boundary_at_width ──────────────────────────────────────
 
     #[test]
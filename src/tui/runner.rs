@@ -8,8 +8,9 @@ use super::render;
 use anyhow::Result;
 use crossterm::{
     event::{
-        DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
-        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -38,6 +39,12 @@ pub async fn run(mut app: App) -> Result<()> {
         io::stdout(),
         PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
     );
+    // Mouse capture disables the terminal's native selection, so it's opt-out
+    // via `[tui] mouse = false`.
+    let mouse_enabled = app.mouse_enabled;
+    if mouse_enabled {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
@@ -54,9 +61,16 @@ pub async fn run(mut app: App) -> Result<()> {
 
     // Run main loop
     let result = run_loop(&mut terminal, &mut app).await;
+    // Keep any half-typed input for next launch
+    app.flush_draft();
+    // A session deleted moments ago is only archived until its undo window closes
+    app.finalize_undo().await;
 
     // Restore terminal
     let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
+    if mouse_enabled {
+        let _ = execute!(terminal.backend_mut(), DisableMouseCapture);
+    }
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -120,12 +134,8 @@ where
                 }
             }
             // Apply coalesced scroll as a single operation
-            if pending_scroll > 0 {
-                app.scroll_offset = app.scroll_offset.saturating_add(pending_scroll as usize);
-            } else if pending_scroll < 0 {
-                app.scroll_offset = app
-                    .scroll_offset
-                    .saturating_sub(pending_scroll.unsigned_abs() as usize);
+            if let Err(e) = app.handle_mouse_scroll(pending_scroll).await {
+                app.error_message = Some(e.to_string());
             }
         }
     }
@@ -11,7 +11,14 @@ use ratatui::{
 };
 
 /// Render the splash screen
-pub fn render_splash(f: &mut Frame, area: Rect, provider_name: &str, model_name: &str) {
+/// `agent_label` is the agent emoji and name (`[agent] emoji` / `name`).
+pub fn render_splash(
+    f: &mut Frame,
+    area: Rect,
+    agent_label: &str,
+    provider_name: &str,
+    model_name: &str,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -31,10 +38,16 @@ pub fn render_splash(f: &mut Frame, area: Rect, provider_name: &str, model_name:
         ])
         .split(chunks[1]);
 
-    render_splash_content(f, center_chunks[1], provider_name, model_name);
+    render_splash_content(f, center_chunks[1], agent_label, provider_name, model_name);
 }
 
-fn render_splash_content(f: &mut Frame, area: Rect, provider_name: &str, model_name: &str) {
+fn render_splash_content(
+    f: &mut Frame,
+    area: Rect,
+    agent_label: &str,
+    provider_name: &str,
+    model_name: &str,
+) {
     let version = env!("CARGO_PKG_VERSION");
 
     let logo_style = Style::default()
@@ -78,7 +91,7 @@ fn render_splash_content(f: &mut Frame, area: Rect, provider_name: &str, model_n
         Line::from(vec![
             Span::styled("╭─── ", Style::default().fg(Color::Rgb(90, 110, 150))),
             Span::styled(
-                "🦀 OpenCrabs",
+                agent_label.to_string(),
                 Style::default()
                     .fg(Color::Rgb(215, 100, 20))
                     .add_modifier(Modifier::BOLD),
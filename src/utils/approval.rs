@@ -3,17 +3,20 @@
 //! Centralises the config-level approval policy check and the
 //! "always approve" persistence so every channel behaves identically.
 
+use crate::brain::agent::ApprovalDecision;
+use crate::config::Config;
+
 /// Check config-level approval policy.
-/// Returns `Some((true, true))` when the policy auto-approves, `None` otherwise.
-pub fn check_approval_policy() -> Option<(bool, bool)> {
-    match crate::config::Config::load() {
+/// Returns `Some(ApproveAll)` when the policy auto-approves, `None` otherwise.
+pub fn check_approval_policy() -> Option<ApprovalDecision> {
+    match Config::load() {
         Ok(cfg) => match cfg.agent.approval_policy.as_str() {
             "auto-always" | "auto-session" => {
                 tracing::debug!(
                     "Approval policy is '{}' — auto-approving",
                     cfg.agent.approval_policy
                 );
-                Some((true, true))
+                Some(ApprovalDecision::ApproveAll)
             }
             _ => None,
         },
@@ -24,9 +27,19 @@ pub fn check_approval_policy() -> Option<(bool, bool)> {
     }
 }
 
+/// Whether an unattended turn (heartbeat, scheduled task) may run a tool that
+/// needs approval. Nobody is there to answer, so only the permanent
+/// `auto-always` policy approves; `auto-session` was granted for an
+/// interactive session and does not carry over.
+pub fn unattended_approval_allowed() -> bool {
+    Config::load()
+        .map(|cfg| cfg.agent.approval_policy == "auto-always")
+        .unwrap_or(false)
+}
+
 /// Persist "auto-session" approval policy to config.toml (single source of truth).
 pub fn persist_auto_session_policy() {
-    match crate::config::Config::write_key("agent", "approval_policy", "auto-session") {
+    match Config::write_key("agent", "approval_policy", "auto-session") {
         Ok(_) => tracing::info!("Persisted approval_policy = auto-session to config.toml"),
         Err(e) => tracing::error!("Failed to persist approval_policy to config.toml: {}", e),
     }
@@ -34,8 +47,29 @@ pub fn persist_auto_session_policy() {
 
 /// Persist "auto-always" (YOLO) approval policy to config.toml — permanent, survives restarts.
 pub fn persist_auto_always_policy() {
-    match crate::config::Config::write_key("agent", "approval_policy", "auto-always") {
+    match Config::write_key("agent", "approval_policy", "auto-always") {
         Ok(_) => tracing::info!("Persisted approval_policy = auto-always to config.toml"),
         Err(e) => tracing::error!("Failed to persist approval_policy to config.toml: {}", e),
     }
 }
+
+/// How long a channel approval prompt waits for an answer
+/// (`[tools] approval_timeout_secs`, `None` = forever).
+pub fn approval_timeout(config: &Config) -> Option<std::time::Duration> {
+    let secs = config.tools.approval_timeout_secs;
+    (secs > 0).then(|| std::time::Duration::from_secs(secs))
+}
+
+/// Wait for an approval answer, up to the configured [`approval_timeout`]
+/// read from the channel's live config.
+/// `None` = timed out; `Some(Err)` = the sender was dropped.
+pub async fn await_approval<T>(
+    rx: tokio::sync::oneshot::Receiver<T>,
+    config_rx: &tokio::sync::watch::Receiver<Config>,
+) -> Option<Result<T, tokio::sync::oneshot::error::RecvError>> {
+    let timeout = approval_timeout(&config_rx.borrow());
+    match timeout {
+        Some(limit) => tokio::time::timeout(limit, rx).await.ok(),
+        None => Some(rx.await),
+    }
+}
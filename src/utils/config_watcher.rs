@@ -1,19 +1,36 @@
 //! Config hot-reload watcher.
 //!
-//! Watches `~/.opencrabs/config.toml` and `~/.opencrabs/keys.toml` for changes.
+//! Watches `~/.opencrabs/config.toml` (or the `--config` file) and
+//! `~/.opencrabs/keys.toml` for changes.
 //! On any modification, re-loads the full `Config` and fires all registered callbacks.
 //!
+//! `trigger_reload()` forces the same reload without a file change — used by
+//! `/reload` and SIGHUP.
+//!
 //! Designed to be extended: register any channel state update or command reload
 //! by pushing a `ReloadCallback` via `spawn()`.
 
 use crate::config::{Config, opencrabs_home};
 use notify::{RecursiveMode, Watcher};
-use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
 /// Callback fired on every successful config reload.
 pub type ReloadCallback = Arc<dyn Fn(Config) + Send + Sync>;
 
+/// Wakes the running watcher for a manual reload
+static MANUAL_TRIGGER: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+
+/// Reload config now and fire all callbacks, as if a file had changed.
+/// Returns false when no watcher is running.
+pub fn trigger_reload() -> bool {
+    MANUAL_TRIGGER
+        .get()
+        .and_then(|tx| tx.lock().ok().map(|tx| tx.send(()).is_ok()))
+        .unwrap_or(false)
+}
+
 /// Spawn a background task that watches config files and fires callbacks on change.
 /// Debounces rapid file-save events (300 ms window) before reloading.
 ///
@@ -32,23 +49,25 @@ pub fn spawn(callbacks: Vec<ReloadCallback>) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         let base = opencrabs_home();
-        let config_path = base.join("config.toml");
+        let config_path = Config::system_config_path().unwrap_or_else(|| base.join("config.toml"));
         let keys_path = base.join("keys.toml");
         let commands_path = base.join("commands.toml");
 
         let (tx, rx) = std::sync::mpsc::channel();
+        let _ = MANUAL_TRIGGER.set(Mutex::new(tx.clone()));
 
-        let mut watcher = match notify::recommended_watcher(move |res| {
-            if let Ok(event) = res {
-                let _ = tx.send(event);
-            }
-        }) {
-            Ok(w) => w,
-            Err(e) => {
-                tracing::error!("ConfigWatcher: failed to create watcher: {}", e);
-                return;
-            }
-        };
+        let mut watcher =
+            match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if res.is_ok() {
+                    let _ = tx.send(());
+                }
+            }) {
+                Ok(w) => w,
+                Err(e) => {
+                    tracing::error!("ConfigWatcher: failed to create watcher: {}", e);
+                    return;
+                }
+            };
 
         for path in [&config_path, &keys_path, &commands_path] {
             if path.exists()
@@ -9,7 +9,8 @@ pub mod sanitize;
 mod string;
 
 pub use approval::{
-    check_approval_policy, persist_auto_always_policy, persist_auto_session_policy,
+    approval_timeout, await_approval, check_approval_policy, persist_auto_always_policy,
+    persist_auto_session_policy, unattended_approval_allowed,
 };
 pub use file_extract::{FileContent, classify_file};
 pub use image::extract_img_markers;